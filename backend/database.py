@@ -1,11 +1,19 @@
 from __future__ import annotations
 
 import os
+import sqlite3
 from pathlib import Path
 from typing import Iterator, Optional
 
+from sqlalchemy import event
 from sqlmodel import Session, SQLModel, create_engine
 
+from utils.paths import has_long_path_prefix, long_path_obj, strip_long_path_prefix
+
+# Platz für Unterpfade im Datenverzeichnis
+# (z.B. "backups/daily/billino_2025-01-01_12-00-00.db")
+_DATA_DIR_HEADROOM = 64
+
 # Default: Datenbank im data/ Ordner (für standalone FE/BE)
 _DEFAULT_DB_DIR = Path(__file__).resolve().parent / "data"
 _DEFAULT_DB_FILE = _DEFAULT_DB_DIR / "billino.db"
@@ -17,10 +25,13 @@ def get_data_dir() -> Path:
 
     - Electron Desktop-App: Nutzt DATA_DIR Umgebungsvariable (AppData/Roaming)
     - Standalone FE/BE: Nutzt backend/data/ (default)
+
+    Lange Windows-Pfade bekommen das Präfix \\?\ (siehe utils.paths), damit
+    auch tief verschachtelte Ordner und UNC-Freigaben funktionieren.
     """
     data_dir = os.getenv("DATA_DIR")
     if data_dir:
-        path = Path(data_dir)
+        path = long_path_obj(data_dir, _DATA_DIR_HEADROOM)
         path.mkdir(parents=True, exist_ok=True)
         return path
 
@@ -35,7 +46,8 @@ def get_db_file() -> Path:
 
 
 def _build_sqlite_url(db_path: Path) -> str:
-    return f"sqlite:///{db_path}"
+    # "?" aus dem Präfix \\?\ würde als Query-String gelesen
+    return f"sqlite:///{strip_long_path_prefix(db_path)}"
 
 
 def get_db_url() -> str:
@@ -49,6 +61,16 @@ def get_db_url() -> str:
 _engine = None  # lazy Singleton
 
 
+def get_sqlite_cache_kib() -> Optional[int]:
+    """SQLITE_CACHE_MB in KiB (für PRAGMA cache_size), None = SQLite-Standard."""
+    value = os.getenv("SQLITE_CACHE_MB")
+    return int(value) * 1024 if value else None
+
+# Schema-Version (PRAGMA user_version); erhöhen, wenn sich Tabellen ändern.
+# Datenbanken von vor der Versionierung haben Version 0.
+SCHEMA_VERSION = 2
+
+
 def get_engine(url: Optional[str] = None):
     """Erzeuge/merke Engine (keine Nebenwirkungen außerhalb dieses Moduls)."""
     global _engine
@@ -56,10 +78,33 @@ def get_engine(url: Optional[str] = None):
         url = get_db_url()
     if _engine is None:
         connect_args = {"check_same_thread": False} if url.startswith("sqlite") else {}
-        _engine = create_engine(url, connect_args=connect_args)
+        db_file = get_db_file()
+        if has_long_path_prefix(db_file) and url == _build_sqlite_url(db_file):
+            # Langer Windows-Pfad: Verbindung mit Präfix selbst öffnen
+            _engine = create_engine(
+                url,
+                creator=lambda: sqlite3.connect(str(db_file), check_same_thread=False),
+            )
+        else:
+            _engine = create_engine(url, connect_args=connect_args)
+
+        cache_kib = get_sqlite_cache_kib()
+        if cache_kib and url.startswith("sqlite"):
+
+            @event.listens_for(_engine, "connect")
+            def _set_cache_size(dbapi_connection, _record):
+                # Negativer Wert = Größe in KiB statt Anzahl Seiten
+                dbapi_connection.execute(f"PRAGMA cache_size = -{cache_kib}")
+
     return _engine
 
 
+def dispose_engine() -> None:
+    """Verwirf gepoolte Verbindungen (z.B. nachdem die DB-Datei ersetzt wurde)."""
+    if _engine is not None:
+        _engine.dispose()
+
+
 def init_db(engine=None) -> None:
     """Erzeuge Tabellen gemäß SQLModel-Metadaten."""
     # Stelle sicher, dass data/ Verzeichnis existiert
@@ -72,6 +117,12 @@ def init_db(engine=None) -> None:
 
     SQLModel.metadata.create_all(engine)
 
+    if engine.url.get_backend_name() == "sqlite":
+        with engine.begin() as conn:
+            current = conn.exec_driver_sql("PRAGMA user_version").scalar() or 0
+            if current < SCHEMA_VERSION:
+                conn.exec_driver_sql(f"PRAGMA user_version = {SCHEMA_VERSION}")
+
 
 def get_session() -> Iterator[Session]:
     """FastAPI-Dependency: liefert eine Session pro Request."""
@@ -12,17 +12,22 @@ This module initializes and configures the FastAPI application with:
 
 import os
 import signal
+import time
 from contextlib import asynccontextmanager
 from pathlib import Path
 
 from dotenv import load_dotenv
-from fastapi import FastAPI
+from fastapi import FastAPI, Request
 from fastapi.middleware.cors import CORSMiddleware
 
 from database import init_db
 from routers import (
+    attachments,
     backups,
     customers,
+    db_maintenance,
+    exports,
+    fiscal_years,
     health,
     invoices,
     pdfs,
@@ -31,8 +36,10 @@ from routers import (
 )
 from services.backup_scheduler import BackupScheduler
 from utils import logger
-from utils.config import BackendConfig, validate_startup_conditions
+from utils.config import APP_VERSION, BackendConfig, validate_startup_conditions
 from utils.errors import StartupError
+from utils.launch_args import LaunchArgsError, parse_launch_args
+from utils.parent_process import bind_to_parent
 
 
 def setup_signal_handlers() -> None:
@@ -54,6 +61,35 @@ def setup_signal_handlers() -> None:
     logger.info("✅ Signal handlers registered (SIGTERM, SIGINT)")
 
 
+def _scheduler_lock_file() -> Path:
+    from database import get_data_dir
+
+    # Worker processes share the supervisor's process id
+    return get_data_dir() / f"scheduler-{os.getppid()}.lock"
+
+
+def _owns_scheduler(config: BackendConfig) -> bool:
+    """
+    Decide whether this process runs the backup scheduler.
+
+    With several uvicorn workers only the first worker that creates the lock
+    file starts it – otherwise every worker would create the nightly backup.
+    Lock files of earlier runs are removed.
+    """
+    if config.workers == 1:
+        return True
+
+    lock_file = _scheduler_lock_file()
+    for stale in lock_file.parent.glob("scheduler-*.lock"):
+        if stale != lock_file:
+            stale.unlink(missing_ok=True)
+    try:
+        os.close(os.open(lock_file, os.O_CREAT | os.O_EXCL | os.O_WRONLY))
+        return True
+    except FileExistsError:
+        return False
+
+
 @asynccontextmanager
 async def lifespan(app: FastAPI):
     """
@@ -78,6 +114,9 @@ async def lifespan(app: FastAPI):
     logger.info("🚀 Backend startup sequence initiated...")
     logger.info("=" * 60)
 
+    # End together with the desktop app (also in worker processes)
+    bind_to_parent()
+
     try:
         # Load and validate configuration
         logger.info("⚙️ Loading configuration...")
@@ -110,9 +149,16 @@ async def lifespan(app: FastAPI):
         logger.info("📊 Initializing database...")
         init_db()
         logger.info("✅ Database initialized")
+        pending_migrations = health.refresh_pending_migrations()
+        if pending_migrations:
+            logger.warning(
+                f"⚠️ {pending_migrations} pending schema changes (scripts/migrate_*.py)"
+            )
 
-        # Backup Scheduler initialization
-        if config.backup_enabled:
+        # Backup Scheduler initialization (in one worker process only)
+        if config.backup_enabled and not _owns_scheduler(config):
+            logger.info("⏸️ Backup scheduler runs in another worker process")
+        elif config.backup_enabled:
             try:
                 logger.info(
                     f"⏰ Configuring backup scheduler: "
@@ -169,6 +215,7 @@ async def lifespan(app: FastAPI):
         if BackupScheduler._scheduler is not None:
             logger.info("⏳ Stopping backup scheduler...")
             BackupScheduler.stop()
+            _scheduler_lock_file().unlink(missing_ok=True)
             logger.info("✅ Backup scheduler stopped")
 
         logger.info("=" * 60)
@@ -184,7 +231,7 @@ load_dotenv(dotenv_path=Path(__file__).parent / ".env", override=False)
 
 app = FastAPI(
     title="Billino Backend API",
-    version="2.0.0",
+    version=APP_VERSION,
     description="""
 # Billino Invoice Management API
 
@@ -279,8 +326,28 @@ app.add_middleware(
     allow_credentials=True,
     allow_methods=["*"],
     allow_headers=["*"],
+    expose_headers=["Server-Timing"],
 )
 
+# Requests slower than this are logged (with the shell's trace id)
+SLOW_REQUEST_MS = 1000
+
+
+@app.middleware("http")
+async def server_timing(request: Request, call_next):
+    """Bearbeitungszeit als Server-Timing-Header für das Latenz-Tracing der Shell."""
+    start = time.perf_counter()
+    response = await call_next(request)
+    duration_ms = (time.perf_counter() - start) * 1000
+    response.headers["Server-Timing"] = f"app;dur={duration_ms:.1f}"
+    if duration_ms > SLOW_REQUEST_MS:
+        trace_id = request.headers.get("X-Trace-Id", "-")
+        logger.warning(
+            f"🐢 {request.method} {request.url.path} took {duration_ms:.0f}ms "
+            f"[trace {trace_id}]"
+        )
+    return response
+
 # Router registrieren
 app.include_router(health.router)
 app.include_router(customers.router)
@@ -289,6 +356,10 @@ app.include_router(invoices.router)
 app.include_router(summary_invoices.router)
 app.include_router(pdfs.router)
 app.include_router(backups.router)
+app.include_router(exports.router)
+app.include_router(fiscal_years.router)
+app.include_router(db_maintenance.router)
+app.include_router(attachments.router)
 
 
 if __name__ == "__main__":
@@ -300,18 +371,42 @@ if __name__ == "__main__":
     - Starts uvicorn server on the configured host:port
     - Host and port are configurable via environment variables
     """
+    import multiprocessing
     import socket
+    import sys
+    import uuid
 
     import uvicorn
 
+    # Worker processes re-run the bundled executable (PyInstaller)
+    multiprocessing.freeze_support()
+
     # Setup signal handlers before starting the server
     setup_signal_handlers()
 
+    # No orphaned backend if the app is killed (task manager, crash)
+    bind_to_parent()
+
+    # Reported by /health; worker processes inherit both
+    os.environ.setdefault("BILLINO_INSTANCE_ID", uuid.uuid4().hex)
+    os.environ["BILLINO_SUPERVISOR_PID"] = str(os.getpid())
+
     # Get server configuration from environment
     host = os.getenv("BACKEND_HOST", "127.0.0.1")
     port = int(os.getenv("BACKEND_PORT", "8000"))
     env = os.getenv("ENV", "development")
-    reload = env == "development"
+
+    # uvicorn flags passed through by the desktop app (config.toml backend_args)
+    try:
+        uvicorn_options = parse_launch_args(sys.argv[1:])
+    except LaunchArgsError as e:
+        logger.error(f"❌ Invalid backend arguments: {e}")
+        sys.exit(2)
+    if uvicorn_options:
+        logger.info(f"⚙️ Backend arguments: {uvicorn_options}")
+    reload = uvicorn_options.pop("reload", env == "development")
+    workers = int(os.getenv("BACKEND_WORKERS", "1"))
+    log_config = uvicorn_options.pop("log_config", None)
 
     logger.info(f"🌐 Starting Billino backend on {host}:{port} (environment: {env})")
 
@@ -329,7 +424,19 @@ if __name__ == "__main__":
                 host=host,
                 port=port,
                 reload=True,
-                log_config=None,  # Use our custom logger
+                log_config=log_config,  # None: use our custom logger
+                **uvicorn_options,
+            )
+        elif workers > 1:
+            # Several processes need the import string as well
+            logger.info(f"👷 Starting {workers} worker processes")
+            uvicorn.run(
+                "main:app",
+                host=host,
+                port=port,
+                workers=workers,
+                log_config=log_config,  # None: use our custom logger
+                **uvicorn_options,
             )
         else:
             # Use app instance for production
@@ -338,7 +445,8 @@ if __name__ == "__main__":
                 host=host,
                 port=port,
                 reload=False,
-                log_config=None,  # Use our custom logger
+                log_config=log_config,  # None: use our custom logger
+                **uvicorn_options,
             )
     except KeyboardInterrupt:
         logger.info("🛑 Server interrupted by user")
@@ -1,8 +1,19 @@
+from .csv_dialect import CsvDialect  # noqa: F401
 from .customer import Customer  # noqa: F401
 from .invoice import Invoice  # noqa: F401
+from .invoice_attachment import (  # noqa: F401
+    InvoiceAttachment,
+    InvoiceAttachmentCreate,
+    InvoiceAttachmentRead,
+)
 from .invoice_create import InvoiceCreate, InvoiceCreateWithNumber  # noqa: F401
 from .invoice_item import InvoiceItem  # noqa: F401
+from .invoice_number_format import (  # noqa: F401
+    InvoiceNumberFormatResult,
+    InvoiceNumberFormatTest,
+)
 from .invoice_read import InvoiceItemRead, InvoiceRead  # noqa: F401
+from .pdf_signing import PdfSigningRequest, Pkcs11Token  # noqa: F401
 from .profile import Profile  # noqa: F401
 from .stored_pdf import StoredPDF, StoredPDFCreate, StoredPDFRead  # noqa: F401
 from .summary_invoice import SummaryInvoice  # noqa: F401
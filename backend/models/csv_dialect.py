@@ -0,0 +1,14 @@
+from typing import Literal
+
+from sqlmodel import Field, SQLModel
+
+
+class CsvDialect(SQLModel):
+    """Format einer CSV-Datei; Standard ist das, was deutsches Excel erwartet."""
+
+    separator: Literal[";", ",", "\t"] = Field(default=";", description="Trennzeichen")
+    decimal_mark: Literal[",", "."] = Field(default=",", description="Dezimalzeichen")
+    encoding: Literal["utf-8", "utf-8-sig", "cp1252"] = Field(
+        default="utf-8-sig",
+        description="utf-8-sig: UTF-8 mit BOM, cp1252: Windows-1252",
+    )
@@ -0,0 +1,43 @@
+from datetime import datetime
+from typing import Optional
+
+from sqlmodel import Field, SQLModel, UniqueConstraint
+
+
+class InvoiceAttachmentBase(SQLModel):
+    """Datei zu einer Rechnung (Lieferschein, Vertrag, …)."""
+
+    filename: str = Field(description="Ursprünglicher Dateiname")
+    sha256: str = Field(
+        min_length=64,
+        max_length=64,
+        description="Inhalts-Hash; die Datei liegt unter DATA_DIR/attachments/",
+    )
+    size_bytes: int = Field(ge=0)
+    content_type: Optional[str] = None
+
+
+class InvoiceAttachment(InvoiceAttachmentBase, table=True):
+    """
+    Anhang einer Rechnung.
+
+    Dateien werden nach Inhalt abgelegt (attachments/<ab>/<sha256><endung>);
+    dieselbe Datei an mehreren Rechnungen liegt nur einmal auf der Platte.
+    """
+
+    __tablename__ = "invoice_attachment"
+    __table_args__ = (UniqueConstraint("invoice_id", "sha256"),)
+
+    id: Optional[int] = Field(default=None, primary_key=True)
+    invoice_id: int = Field(foreign_key="invoice.id", index=True)
+    created_at: datetime = Field(default_factory=lambda: datetime.now())
+
+
+class InvoiceAttachmentCreate(InvoiceAttachmentBase):
+    """Anhang registrieren, nachdem die Datei abgelegt wurde."""
+
+
+class InvoiceAttachmentRead(InvoiceAttachmentBase):
+    id: int
+    invoice_id: int
+    created_at: datetime
@@ -0,0 +1,26 @@
+from datetime import date
+from typing import List, Optional
+
+from sqlmodel import Field, SQLModel
+
+
+class InvoiceNumberFormatTest(SQLModel):
+    """Request to render and validate an invoice-number pattern."""
+
+    pattern: str = Field(description="z. B. '{YYYY}-{N:4}' oder '{YY} | {N:3}'")
+    sample_date: date
+    counter: int = Field(ge=1)
+
+
+class InvoiceNumberFormatResult(SQLModel):
+    """Rendered sample number plus validation findings."""
+
+    pattern: str
+    rendered: Optional[str] = None
+    valid: bool
+    errors: List[str] = []
+    warnings: List[str] = []
+    highest_counter: Optional[int] = Field(
+        default=None,
+        description="Höchster vorhandener Zähler im selben Zeitraum",
+    )
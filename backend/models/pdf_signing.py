@@ -0,0 +1,27 @@
+from typing import Optional
+
+from sqlmodel import Field, SQLModel
+
+
+class Pkcs11Token(SQLModel):
+    """Signaturschlüssel auf einem USB-Token/einer Smartcard (PKCS#11)."""
+
+    module_path: str = Field(description="PKCS#11-Bibliothek des Token-Herstellers")
+    token_label: str
+    key_label: str
+    cert_label: Optional[str] = Field(
+        default=None, description="Standard: wie key_label"
+    )
+    pin: str
+
+
+class PdfSigningRequest(SQLModel):
+    """Zertifikat für die PAdES-Signatur: PKCS#12-Datei oder PKCS#11-Token."""
+
+    pkcs12: Optional[str] = Field(
+        default=None, description="Base64-kodierte PKCS#12-Datei (.p12/.pfx)"
+    )
+    passphrase: Optional[str] = None
+    pkcs11: Optional[Pkcs11Token] = None
+    reason: Optional[str] = Field(default=None, description="z. B. 'Rechnung'")
+    location: Optional[str] = None
@@ -0,0 +1,100 @@
+"""
+API-Routen für Rechnungsanhänge.
+
+Endpoints:
+- GET /invoices/{invoice_id}/attachments - Anhänge einer Rechnung
+- POST /invoices/{invoice_id}/attachments - Abgelegte Datei als Anhang registrieren
+- DELETE /invoices/{invoice_id}/attachments/{attachment_id} - Anhang entfernen
+"""
+
+from fastapi import APIRouter, Depends, HTTPException, Response
+from sqlmodel import Session
+
+from database import get_session
+from models import InvoiceAttachment, InvoiceAttachmentCreate, InvoiceAttachmentRead
+from services.attachment_service import (
+    delete_attachment,
+    list_attachments,
+    register_attachment,
+)
+from utils.logger import logger
+
+router = APIRouter(prefix="/invoices", tags=["attachments"])
+
+
+@router.get("/{invoice_id}/attachments", response_model=list[InvoiceAttachmentRead])
+def get_attachments(invoice_id: int, session: Session = Depends(get_session)):
+    """
+    Liste die Anhänge einer Rechnung auf (älteste zuerst).
+    """
+    logger.debug(f"📎 GET /invoices/{invoice_id}/attachments")
+    return list_attachments(session, invoice_id)
+
+
+@router.post(
+    "/{invoice_id}/attachments",
+    response_model=InvoiceAttachmentRead,
+    status_code=201,
+)
+def create_attachment(
+    invoice_id: int,
+    data: InvoiceAttachmentCreate,
+    response: Response,
+    session: Session = Depends(get_session),
+):
+    """
+    Registriere eine Datei als Anhang einer Rechnung.
+
+    Die Datei muss vorher unter DATA_DIR/attachments/<ab>/<sha256><endung>
+    abgelegt worden sein (macht die Desktop-App); Hash und Größe werden
+    geprüft. Ist dieselbe Datei schon angehängt, kommt der vorhandene
+    Anhang mit Status 200 zurück.
+
+    **Request Body:**
+    - filename (string): Ursprünglicher Dateiname
+    - sha256 (string): SHA-256 des Inhalts (hex)
+    - size_bytes (number): Dateigröße
+    - content_type (string, optional): MIME-Typ
+
+    **Fehler:**
+    - 404: Rechnung nicht gefunden
+    - 409: Datei nicht abgelegt oder Inhalt passt nicht zu Hash/Größe
+    """
+    logger.debug(f"📎 POST /invoices/{invoice_id}/attachments - {data.filename}")
+    try:
+        attachment, created = register_attachment(session, invoice_id, data)
+    except LookupError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except FileNotFoundError:
+        raise HTTPException(status_code=409, detail="Anhang-Datei nicht abgelegt")
+    except ValueError as e:
+        raise HTTPException(status_code=409, detail=str(e))
+
+    if created:
+        logger.info(f"✅ Anhang {data.filename} an Rechnung {invoice_id} angehängt")
+    else:
+        response.status_code = 200
+    return attachment
+
+
+@router.delete("/{invoice_id}/attachments/{attachment_id}", status_code=204)
+def remove_attachment(
+    invoice_id: int, attachment_id: int, session: Session = Depends(get_session)
+):
+    """
+    Entferne einen Anhang. Die Datei wird gelöscht, wenn keine andere
+    Rechnung sie verwendet.
+
+    **Fehler:**
+    - 404: Anhang nicht gefunden
+    """
+    attachment = session.get(InvoiceAttachment, attachment_id)
+    if attachment is None or attachment.invoice_id != invoice_id:
+        raise HTTPException(status_code=404, detail="Anhang nicht gefunden")
+
+    file_deleted = delete_attachment(session, attachment)
+    logger.info(
+        f"🗑️ Anhang {attachment.filename} von Rechnung {invoice_id} entfernt"
+        + (" (Datei gelöscht)" if file_deleted else "")
+    )
+    return Response(status_code=204)
@@ -3,26 +3,39 @@ API-Routen für Backup-Management.
 
 Endpoints:
 - POST /backups/trigger - Manuelles Backup erzeugen
+- GET /backups/trigger/{job_id} - Status eines Hintergrund-Backups
 - GET /backups/status - Status der letzten Backups
 - GET /backups/list - Verfügbare Backups auflisten
 - GET /backups/jobs - Scheduler-Jobs auflisten
+- POST /backups/inspect - Inhalt eines Backups anzeigen (ohne Restore)
+- POST /backups/restore - Backup ganz oder teilweise wiederherstellen
 """
 
+import sqlite3
 from datetime import datetime
+from pathlib import Path
+from typing import Literal, Optional
 
-from fastapi import APIRouter, HTTPException
+from fastapi import APIRouter, Body, HTTPException, Response
 
+from routers.health import refresh_pending_migrations
 from services.backup_scheduler import BackupScheduler
+from services.backup_service import inspect_backup, restore_backup
 from utils.logger import logger
 
 router = APIRouter(prefix="/backups", tags=["backups"])
 
 
 @router.post("/trigger", status_code=200)
-def trigger_backup():
+def trigger_backup(response: Response, wait: bool = True):
     """
     Triggere ein manuelles Datenbank-Backup.
 
+    **Query:**
+    - `wait` (bool, default true): Auf das Backup warten. Mit `false` startet
+      das Backup im Hintergrund, die Antwort ist 202 mit dem Job (siehe
+      `GET /backups/trigger/{job_id}`).
+
     **Response:**
     - success (boolean): Backup erfolgreich erstellt
     - backup_path (string): Pfad zur Backup-Datei (falls erfolgreich)
@@ -40,6 +53,12 @@ def trigger_backup():
     """
     logger.debug("POST /backups/trigger - Manuelles Backup angefordert")
 
+    if not wait:
+        job = BackupScheduler.start_backup_job()
+        logger.info(f"Manuelles Backup im Hintergrund gestartet: {job['job_id']}")
+        response.status_code = 202
+        return job
+
     result = BackupScheduler.trigger_backup_now()
 
     if result.get("success"):
@@ -55,6 +74,27 @@ def trigger_backup():
     return result
 
 
+@router.get("/trigger/{job_id}", status_code=200)
+def get_backup_job(job_id: str):
+    """
+    Status eines mit `wait=false` gestarteten Backups.
+
+    **Response:**
+    - job_id (string)
+    - state (string): "running", "done" oder "failed"
+    - started / finished (string|null): ISO-Zeitstempel
+    - backup_path (string|null): Pfad zur Backup-Datei (bei "done")
+    - error (string|null): Fehlermeldung (bei "failed")
+
+    **Fehler:**
+    - 404: Job unbekannt (z. B. nach einem Neustart des Backends)
+    """
+    job = BackupScheduler.get_backup_job(job_id)
+    if job is None:
+        raise HTTPException(status_code=404, detail="Backup-Job nicht gefunden")
+    return job
+
+
 @router.get("/status", status_code=200)
 def get_backup_status():
     """
@@ -169,3 +209,90 @@ def list_scheduler_jobs():
     logger.info(f"Scheduler-Jobs aufgelistet: {len(jobs)}")
 
     return {"jobs": jobs, "job_count": len(jobs)}
+
+
+@router.post("/inspect", status_code=200)
+def inspect_backup_file(path: str = Body(..., embed=True)):
+    """
+    Zeige den Inhalt eines DB-Backups an, ohne es wiederherzustellen.
+
+    Das Backup wird nur lesend geöffnet. So kann vor einem Restore geprüft
+    werden, ob die richtige Datei gewählt wurde.
+
+    **Request Body:**
+    - `path` (string, required): Absoluter Pfad zur Backup-Datei
+
+    **Response:**
+    - schema_version (number): Schema-Version des Backups (0 = vor Versionierung)
+    - current_schema_version (number): Schema-Version dieser App
+    - compatible (boolean): Backup kann mit dieser Version geöffnet werden
+    - invoice_count (number): Anzahl Rechnungen
+    - first_invoice_date / last_invoice_date (string|null): Datumsbereich
+    - pdfs (array): Enthaltene PDFs (filename, type, created_at)
+
+    **Fehler:**
+    - 400: Pfad nicht absolut oder keine Billino-Datenbank
+    - 404: Datei nicht gefunden
+    """
+    logger.debug(f"POST /backups/inspect - {path}")
+
+    backup_path = Path(path)
+    if not backup_path.is_absolute():
+        raise HTTPException(status_code=400, detail="Pfad muss absolut sein")
+
+    try:
+        return inspect_backup(backup_path)
+    except FileNotFoundError:
+        raise HTTPException(status_code=404, detail="Backup-Datei nicht gefunden")
+    except ValueError as e:
+        logger.warning(f"⚠️ Backup nicht lesbar: {path}: {e}")
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@router.post("/restore", status_code=200)
+def restore_backup_file(
+    path: str = Body(...),
+    scope: Literal["full", "database", "pdfs"] = Body("full"),
+    months: Optional[list[str]] = Body(None),
+):
+    """
+    Stelle ein DB-Backup ganz oder teilweise wieder her.
+
+    Vor der Wiederherstellung wird automatisch ein Backup des aktuellen
+    Stands erstellt.
+
+    **Request Body:**
+    - `path` (string, required): Absoluter Pfad zur Backup-Datei
+    - `scope` (string): "full" (alles), "database" (ohne PDFs) oder "pdfs"
+    - `months` (array): Nur PDFs dieser Monate, z.B. ["2025-03"] (nur "pdfs")
+
+    **Response:**
+    - scope (string): Ausgeführter Scope
+    - safety_backup_path (string|null): Backup des Stands vor dem Restore
+    - restored_pdfs (number): Wiederhergestellte PDFs
+    - skipped_pdfs (number): Übersprungene PDFs (Rechnung existiert nicht)
+
+    **Fehler:**
+    - 400: Ungültige Parameter oder Backup nicht kompatibel
+    - 404: Datei nicht gefunden
+    - 500: Wiederherstellung fehlgeschlagen
+    """
+    logger.debug(f"POST /backups/restore - {path} ({scope})")
+
+    backup_path = Path(path)
+    if not backup_path.is_absolute():
+        raise HTTPException(status_code=400, detail="Pfad muss absolut sein")
+
+    try:
+        result = restore_backup(backup_path, scope, months)
+    except FileNotFoundError:
+        raise HTTPException(status_code=404, detail="Backup-Datei nicht gefunden")
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except (OSError, sqlite3.Error) as e:
+        logger.error(f"❌ Wiederherstellung fehlgeschlagen: {e}")
+        raise HTTPException(status_code=500, detail="Wiederherstellung fehlgeschlagen")
+
+    # Die wiederhergestellte Datenbank kann ein älteres Schema haben (/health)
+    refresh_pending_migrations()
+    return result
@@ -9,6 +9,10 @@ from models.table_models import (
     SortDirection,
     SortField,
 )
+from services.customer_duplicates_service import (
+    DEFAULT_THRESHOLD,
+    find_duplicate_customers,
+)
 from services.filter_service import FilterService, create_paginated_response, paginate
 from utils import logger
 from utils.router_utils import parse_filter_params, parse_sort_params
@@ -204,6 +208,73 @@ def list_customers(
         raise HTTPException(status_code=400, detail=str(e))
 
 
+@router.get("/duplicates", status_code=200)
+def get_duplicate_customers(
+    threshold: float = Query(
+        DEFAULT_THRESHOLD,
+        ge=0.5,
+        le=1.0,
+        description="Minimum similarity (0.5-1.0) for two customers to be duplicates",
+    ),
+):
+    """
+    Find customers that were probably created more than once.
+
+    Names are compared after normalization (case, umlauts, legal forms such as
+    GmbH, punctuation, word order) with fuzzy matching; addresses count in when
+    both customers have one. Nothing is changed – the response only contains
+    a merge suggestion per cluster.
+
+    **Query Parameters:**
+    - `threshold` (float, default=0.85): Minimum similarity
+
+    **Returns:**
+    - `customer_count` (integer): Number of customers scanned
+    - `clusters` (array): Groups of duplicate candidates
+      - `customers`: id, name, address, city, note, invoice_count
+      - `score` (float): Highest similarity within the group
+      - `reasons`: e.g. `same_name`, `similar_name`, `same_address`, `same_city`
+      - `suggestion`: `primary_id` (most invoices), `merge_ids`, merged
+        `fields` and `conflicts` (fields with differing values)
+
+    **Example Response:**
+    ```json
+    {
+        "customer_count": 42,
+        "threshold": 0.85,
+        "clusters": [
+            {
+                "customers": [
+                    {"id": 3, "name": "Müller GmbH", "address": "Hauptstraße 5",
+                     "city": "Berlin", "note": null, "invoice_count": 12},
+                    {"id": 17, "name": "Mueller", "address": "Hauptstr. 5",
+                     "city": "Berlin", "note": "Blau", "invoice_count": 1}
+                ],
+                "score": 1.0,
+                "reasons": ["same_address", "same_city", "same_name"],
+                "suggestion": {
+                    "primary_id": 3,
+                    "merge_ids": [17],
+                    "fields": {"name": "Müller GmbH", "address": "Hauptstraße 5",
+                               "city": "Berlin", "note": "Blau"},
+                    "conflicts": ["name", "address"]
+                }
+            }
+        ]
+    }
+    ```
+
+    **Errors:**
+    - 404: No database present
+    """
+    logger.debug(f"👥 GET /customers/duplicates - threshold={threshold}")
+    try:
+        return find_duplicate_customers(threshold=threshold)
+    except FileNotFoundError:
+        logger.warning("⚠️ Duplicate search requested, but no database exists")
+        raise HTTPException(status_code=404, detail="No database present")
+
+
 @router.get("/search", response_model=list[Customer])
 def search_customers(
     q: str = Query(..., min_length=2, description="Search query (min. 2 characters)"),
@@ -0,0 +1,268 @@
+"""
+API-Routen für Datenbank-Wartung.
+
+Endpoints:
+- GET /database/stats - Statistiken zur Datenbankdatei
+- POST /database/checkpoint - WAL-Checkpoint ausführen
+- PUT /database/journal-mode - Journal-Modus umstellen (delete/wal/...)
+- POST /database/reindex - Indizes neu aufbauen
+- POST /database/analyze - Statistiken des Query-Planers aktualisieren
+- GET /database/missing-pdfs - Rechnungen ohne gespeichertes PDF
+- POST /database/recalculate-totals - Summen der Sammelrechnungen neu berechnen
+- POST /database/cleanup - Alte Exporte und verwaiste PDFs löschen (mit Probelauf)
+"""
+
+import sqlite3
+from typing import Optional
+
+from fastapi import APIRouter, Body, Depends, HTTPException
+from sqlmodel import Session
+
+from database import get_session
+from services import recalculate_summary_totals
+from services.db_maintenance_service import (
+    analyze_database,
+    checkpoint_wal,
+    delete_stored_pdfs,
+    find_invoices_without_pdf,
+    find_orphaned_pdfs,
+    get_db_stats,
+    rebuild_indexes,
+    set_journal_mode,
+)
+from services.export_service import delete_exports, find_expired_exports
+from utils.logger import logger
+
+router = APIRouter(prefix="/database", tags=["database"])
+
+
+@router.get("/stats", status_code=200)
+def get_database_stats():
+    """
+    Statistiken zur SQLite-Datenbank (Einstellungen → Datenbank).
+
+    **Response:**
+    - file_size_bytes (number): Größe der Datenbankdatei
+    - wal_size_bytes (number): Größe der -wal Datei (0 = keine)
+    - page_size / page_count (number): SQLite-Seitengröße und -anzahl
+    - freelist_pages / freelist_bytes (number): Freie Seiten (per VACUUM
+      zurückzugewinnen)
+    - journal_mode (string): z.B. "delete" oder "wal"
+    - index_count (number): Anzahl Indizes
+    - pdf_content_bytes (number): Größe der gespeicherten PDFs (Base64)
+    - tables (array): name, row_count je Tabelle
+
+    **Fehler:**
+    - 404: Keine Datenbank vorhanden
+    """
+    try:
+        return get_db_stats()
+    except FileNotFoundError:
+        logger.warning("⚠️ Datenbank-Statistik angefordert, aber keine DB vorhanden")
+        raise HTTPException(status_code=404, detail="Keine Datenbank vorhanden")
+
+
+@router.post("/checkpoint", status_code=200)
+def run_wal_checkpoint(mode: str = Body("TRUNCATE", embed=True)):
+    """
+    Übernimm den Inhalt der -wal Datei in die Datenbank.
+
+    **Request Body:**
+    - `mode` (string): PASSIVE, FULL, RESTART oder TRUNCATE (Standard)
+
+    **Response:**
+    - busy (boolean): Checkpoint wurde durch aktive Verbindungen blockiert
+    - wal_frames / checkpointed_frames (number): Frames im WAL / übernommen
+    - wal_size_bytes (number): Größe der -wal Datei danach
+
+    **Fehler:**
+    - 400: Unbekannter Modus
+    - 404: Keine Datenbank vorhanden
+    """
+    logger.debug(f"POST /database/checkpoint - {mode}")
+    try:
+        return checkpoint_wal(mode=mode)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except FileNotFoundError:
+        raise HTTPException(status_code=404, detail="Keine Datenbank vorhanden")
+
+
+@router.put("/journal-mode", status_code=200)
+def update_journal_mode(mode: str = Body(..., embed=True)):
+    """
+    Stelle den Journal-Modus der Datenbank um.
+
+    Beim Verlassen des WAL-Modus wird vorher ein Checkpoint ausgeführt.
+
+    **Request Body:**
+    - `mode` (string, required): delete, truncate, persist oder wal
+
+    **Response:**
+    - previous_mode (string): Modus vor der Umstellung
+    - journal_mode (string): Aktiver Modus
+
+    **Fehler:**
+    - 400: Unbekannter Modus
+    - 404: Keine Datenbank vorhanden
+    - 409: Datenbank gesperrt (z.B. laufender Export)
+    """
+    logger.debug(f"PUT /database/journal-mode - {mode}")
+    try:
+        return set_journal_mode(mode)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except FileNotFoundError:
+        raise HTTPException(status_code=404, detail="Keine Datenbank vorhanden")
+    except sqlite3.OperationalError as e:
+        logger.warning(f"⚠️ Journal-Modus nicht umgestellt: {e}")
+        raise HTTPException(status_code=409, detail=str(e))
+
+
+@router.post("/reindex", status_code=200)
+def run_reindex():
+    """
+    Baue alle Indizes der Datenbank neu auf (nächtliche Wartung).
+
+    **Response:**
+    - index_count (number): Anzahl Indizes
+
+    **Fehler:**
+    - 404: Keine Datenbank vorhanden
+    - 409: Datenbank gesperrt
+    """
+    logger.debug("POST /database/reindex")
+    try:
+        return rebuild_indexes()
+    except FileNotFoundError:
+        raise HTTPException(status_code=404, detail="Keine Datenbank vorhanden")
+    except sqlite3.OperationalError as e:
+        logger.warning(f"⚠️ REINDEX nicht möglich: {e}")
+        raise HTTPException(status_code=409, detail=str(e))
+
+
+@router.post("/analyze", status_code=200)
+def run_analyze():
+    """
+    Aktualisiere die Statistiken des Query-Planers (ANALYZE).
+
+    **Response:**
+    - analyzed_tables (number): Tabellen mit Statistik
+
+    **Fehler:**
+    - 404: Keine Datenbank vorhanden
+    - 409: Datenbank gesperrt
+    """
+    logger.debug("POST /database/analyze")
+    try:
+        return analyze_database()
+    except FileNotFoundError:
+        raise HTTPException(status_code=404, detail="Keine Datenbank vorhanden")
+    except sqlite3.OperationalError as e:
+        logger.warning(f"⚠️ ANALYZE nicht möglich: {e}")
+        raise HTTPException(status_code=409, detail=str(e))
+
+
+@router.get("/missing-pdfs", status_code=200)
+def get_missing_pdfs():
+    """
+    Rechnungen, zu denen kein PDF gespeichert ist.
+
+    **Response:**
+    - invoice_ids (array): IDs der Rechnungen, aufsteigend
+
+    **Fehler:**
+    - 404: Keine Datenbank vorhanden
+    """
+    try:
+        return {"invoice_ids": find_invoices_without_pdf()}
+    except FileNotFoundError:
+        raise HTTPException(status_code=404, detail="Keine Datenbank vorhanden")
+
+
+@router.post("/recalculate-totals", status_code=200)
+def run_recalculate_totals(
+    confirm: bool = Body(False, embed=True),
+    session: Session = Depends(get_session),
+):
+    """
+    Berechne die gespeicherten Summen aller Sammelrechnungen aus den
+    verknüpften Rechnungen neu und berichte abweichende.
+
+    Standardmäßig ein Probelauf; korrigiert wird nur mit `confirm`.
+    Sammelrechnungen mit gespeichertem PDF wurden mit ihren Summen
+    ausgestellt und werden nie geändert.
+
+    **Request Body:**
+    - `confirm` (boolean): Abweichende Summen korrigieren (Standard false)
+
+    **Response:**
+    - dry_run (boolean)
+    - checked (number): Geprüfte Sammelrechnungen
+    - discrepancies (array): id, stored_gross, calculated_gross, has_pdf
+    - corrected (array): IDs der korrigierten Sammelrechnungen
+    """
+    logger.debug(f"POST /database/recalculate-totals - confirm={confirm}")
+    return recalculate_summary_totals(session, confirm=confirm)
+
+
+@router.post("/cleanup", status_code=200)
+def run_cleanup(
+    export_max_age_days: int = Body(7, embed=True, ge=0),
+    orphaned_pdfs: bool = Body(False, embed=True),
+    dry_run: bool = Body(True, embed=True),
+    export_filenames: Optional[list[str]] = Body(None, embed=True),
+    pdf_ids: Optional[list[int]] = Body(None, embed=True),
+):
+    """
+    Lösche Export-Dateien, die älter als `export_max_age_days` sind, und
+    gespeicherte PDFs gelöschter Rechnungen (Aufbewahrungsregeln der
+    nächtlichen Wartung).
+
+    Standardmäßig ein Probelauf: es wird nur berichtet, was gelöscht würde.
+    Mit `export_filenames` / `pdf_ids` (aus dem Bericht des Probelaufs)
+    werden nur diese Einträge gelöscht – und nur, wenn sie weiterhin
+    abgelaufen bzw. verwaist sind. Was seit dem Probelauf dazukam, bleibt.
+
+    **Request Body:**
+    - `export_max_age_days` (number): Höchstalter der Exporte in Tagen (Standard 7)
+    - `orphaned_pdfs` (boolean): Verwaiste PDFs einbeziehen (Standard false)
+    - `dry_run` (boolean): Nichts löschen, nur berichten (Standard true)
+    - `export_filenames` (array, optional): Nur diese Exporte berücksichtigen
+    - `pdf_ids` (array, optional): Nur diese gespeicherten PDFs berücksichtigen
+
+    **Response:**
+    - dry_run (boolean)
+    - exports (array): job_id, filename, size_bytes, modified_at
+    - orphaned_pdfs (array): id, type, invoice_id, summary_invoice_id, size_bytes
+    - freed_bytes (number): Freigegebener (bzw. freizugebender) Speicher
+
+    **Fehler:**
+    - 404: Keine Datenbank vorhanden
+    - 409: Datenbank gesperrt
+    """
+    logger.debug(f"POST /database/cleanup - dry_run={dry_run}")
+    exports = find_expired_exports(export_max_age_days)
+    if export_filenames is not None:
+        exports = [entry for entry in exports if entry["filename"] in export_filenames]
+    try:
+        pdfs = find_orphaned_pdfs() if orphaned_pdfs else []
+        if pdf_ids is not None:
+            pdfs = [entry for entry in pdfs if entry["id"] in pdf_ids]
+        if not dry_run:
+            delete_exports([entry["filename"] for entry in exports])
+            delete_stored_pdfs([entry["id"] for entry in pdfs])
+    except FileNotFoundError:
+        raise HTTPException(status_code=404, detail="Keine Datenbank vorhanden")
+    except sqlite3.OperationalError as e:
+        logger.warning(f"⚠️ Bereinigung nicht möglich: {e}")
+        raise HTTPException(status_code=409, detail=str(e))
+
+    if not dry_run and (exports or pdfs):
+        logger.info(f"🗑️ Bereinigt: {len(exports)} Exporte, {len(pdfs)} verwaiste PDFs")
+    return {
+        "dry_run": dry_run,
+        "exports": exports,
+        "orphaned_pdfs": pdfs,
+        "freed_bytes": sum(entry["size_bytes"] for entry in exports + pdfs),
+    }
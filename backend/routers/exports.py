@@ -0,0 +1,96 @@
+"""
+API-Routen für Export-Dateien.
+
+Endpoints:
+- GET /exports/ - Vorhandene Exporte auflisten
+- GET /exports/{job_id}/download - Export-Datei streamen
+- POST /exports/anonymized-db - Anonymisierte DB-Kopie für Fehlerberichte
+"""
+
+import sqlite3
+
+from fastapi import APIRouter, HTTPException
+from fastapi.responses import FileResponse
+
+from services.anonymize_service import export_anonymized_db
+from services.export_service import find_export_file, is_valid_job_id, list_exports
+from utils.logger import logger
+
+router = APIRouter(prefix="/exports", tags=["exports"])
+
+
+@router.get("/", status_code=200)
+def get_exports():
+    """
+    Liste alle vorhandenen Export-Dateien auf.
+
+    **Response:**
+    Array von Export-Objekten:
+    - job_id (string): ID für den Download
+    - filename (string): Dateiname
+    - size_bytes (number): Dateigröße in Bytes
+    - created_at (number): Unix-Timestamp der Erstellung
+    """
+    return list_exports()
+
+
+@router.get("/{job_id}/download")
+def download_export(job_id: str):
+    """
+    Streame eine Export-Datei.
+
+    Die Datei wird in Blöcken gesendet (kein vollständiges Laden in den
+    Speicher); `Content-Length` erlaubt dem Client eine Fortschrittsanzeige.
+
+    **Path Parameters:**
+    - `job_id` (string, required): ID des Exports
+
+    **Fehler:**
+    - 400: Ungültige Job-ID
+    - 404: Export nicht gefunden
+    """
+    if not is_valid_job_id(job_id):
+        raise HTTPException(status_code=400, detail="Ungültige Export-ID")
+
+    export_file = find_export_file(job_id)
+    if export_file is None:
+        logger.warning(f"⚠️ Export nicht gefunden: {job_id}")
+        raise HTTPException(status_code=404, detail="Export nicht gefunden")
+
+    logger.debug(f"📤 GET /exports/{job_id}/download - {export_file.name}")
+    return FileResponse(
+        export_file,
+        filename=export_file.name,
+        media_type="application/octet-stream",
+    )
+
+
+@router.post("/anonymized-db", status_code=201)
+def create_anonymized_db_export():
+    """
+    Erzeuge eine anonymisierte Kopie der Datenbank für Fehlerberichte.
+
+    Namen, Adressen, Bankdaten und Texte werden formerhaltend verwürfelt,
+    Beträge skaliert und gespeicherte PDFs entfernt.
+
+    **Response:**
+    - job_id (string): ID für GET /exports/{job_id}/download
+    - filename (string): Dateiname
+    - size_bytes (number): Dateigröße in Bytes
+    - anonymized_values (number): Verwürfelte Textwerte
+    - scaled_amounts (number): Skalierte Beträge
+    - removed_pdfs (number): Entfernte PDFs
+
+    **Fehler:**
+    - 404: Keine Datenbank vorhanden
+    - 500: Export fehlgeschlagen
+    """
+    logger.debug("POST /exports/anonymized-db")
+
+    try:
+        return export_anonymized_db()
+    except FileNotFoundError:
+        raise HTTPException(status_code=404, detail="Keine Datenbank vorhanden")
+    except (OSError, sqlite3.Error) as e:
+        logger.error(f"❌ Anonymisierter Export fehlgeschlagen: {e}")
+        raise HTTPException(status_code=500, detail="Export fehlgeschlagen")
@@ -0,0 +1,132 @@
+"""
+API-Routen für den Jahresabschluss.
+
+Endpoints:
+- GET /fiscal-years/{year}/vat-summary - USt-Übersicht je Steuersatz
+- POST /fiscal-years/{year}/archive - Jahresarchiv (ZIP) erzeugen
+- GET /fiscal-years/{year}/rollover - Nummernkreis-Wechsel ins Folgejahr prüfen
+"""
+
+from typing import Optional
+
+from fastapi import APIRouter, Body, Depends, HTTPException, Path, Query
+from sqlmodel import Session
+
+from database import get_session
+from models import CsvDialect
+from services.fiscal_year_service import (
+    check_counter_rollover,
+    compute_vat_summary,
+    create_year_archive,
+)
+from utils.logger import logger
+
+router = APIRouter(prefix="/fiscal-years", tags=["fiscal-years"])
+
+# Rechnungsnummern tragen das Jahr zweistellig ("25 | 001")
+YearPath = Path(..., ge=2000, le=2098, description="Geschäftsjahr, z.B. 2025")
+
+
+@router.get("/{year}/vat-summary", status_code=200)
+def get_vat_summary(
+    year: int = YearPath,
+    small_business_only: bool = Query(
+        False, description="Nur Profile ohne Steuerausweis (§19 UStG)"
+    ),
+    session: Session = Depends(get_session),
+):
+    """
+    Umsatzsteuer-Übersicht eines Geschäftsjahres.
+
+    Summiert Netto, Steuer und Brutto aller Rechnungen mit Rechnungsdatum im
+    Jahr, gruppiert nach Steuersatz. Rechnungen ohne Steuerausweis (§19 UStG)
+    werden unter Satz 0.0 geführt.
+
+    **Query:**
+    - `small_business_only` (bool, default false): nur Rechnungen von
+      Kleinunternehmer-Profilen (include_tax = false)
+
+    **Beispiel-Response:**
+    ```json
+    {
+        "year": 2025,
+        "invoice_count": 2,
+        "total_net": 200.0,
+        "total_tax": 38.0,
+        "total_gross": 238.0,
+        "rates": [
+            {
+                "tax_rate": 0.19,
+                "invoice_count": 2,
+                "net": 200.0,
+                "tax": 38.0,
+                "gross": 238.0
+            }
+        ]
+    }
+    ```
+    """
+    logger.debug(f"📊 GET /fiscal-years/{year}/vat-summary")
+    return compute_vat_summary(session, year, small_business_only)
+
+
+@router.post("/{year}/archive", status_code=201)
+def create_archive(
+    year: int = YearPath,
+    dialect: Optional[CsvDialect] = Body(None),
+    session: Session = Depends(get_session),
+):
+    """
+    Erzeuge das Jahresarchiv (ZIP) für ein Geschäftsjahr.
+
+    Das Archiv enthält die Rechnungsliste als CSV, die USt-Übersicht, alle
+    gespeicherten PDFs und die Rechnungsanhänge des Jahres. Download über
+    GET /exports/{job_id}/download.
+
+    **Request Body (optional):** CSV-Dialekt der Rechnungsliste
+    - separator (string): ";" (Standard), "," oder Tabulator
+    - decimal_mark (string): "," (Standard) oder "."
+    - encoding (string): "utf-8-sig" (Standard, mit BOM), "utf-8" oder "cp1252"
+
+    **Response:**
+    - job_id (string): ID für den Download
+    - filename (string): Dateiname des Archivs
+    - invoice_count (number): Anzahl Rechnungen im Archiv
+    - pdf_count (number): Anzahl enthaltener PDFs
+    - attachment_count (number): Anzahl enthaltener Rechnungsanhänge
+    - size_bytes (number): Größe des Archivs
+
+    **Fehler:**
+    - 500: Archiv konnte nicht erstellt werden
+    """
+    logger.debug(f"🗄️ POST /fiscal-years/{year}/archive")
+    try:
+        result = create_year_archive(session, year, dialect)
+    except OSError as e:
+        logger.error(f"❌ Jahresarchiv {year} fehlgeschlagen: {e}")
+        raise HTTPException(
+            status_code=500, detail="Jahresarchiv konnte nicht erstellt werden"
+        )
+
+    logger.info(
+        f"✅ Jahresarchiv {year} erstellt: {result['filename']} "
+        f"({result['invoice_count']} Rechnungen, {result['pdf_count']} PDFs)"
+    )
+    return result
+
+
+@router.get("/{year}/rollover", status_code=200)
+def get_counter_rollover(year: int = YearPath, session: Session = Depends(get_session)):
+    """
+    Prüfe den Nummernkreis-Wechsel vom Geschäftsjahr ins Folgejahr.
+
+    **Response:**
+    - year (number): Abgeschlossenes Jahr
+    - last_number (string|null): Letzte vergebene Nummer des Jahres
+    - last_counter (number|null): Zähler der letzten Nummer
+    - next_number (string): Nächste Nummer im Folgejahr
+    - next_year_started (boolean): Im Folgejahr wurden bereits Nummern vergeben
+    - warnings (array): Hinweise, z.B. Rechnungen mit Nummer eines anderen Jahres
+    """
+    logger.debug(f"🔢 GET /fiscal-years/{year}/rollover")
+    return check_counter_rollover(session, year)
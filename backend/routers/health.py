@@ -1,19 +1,39 @@
 # Health router with enhanced status monitoring
+import os
+import secrets
+import signal
 import time
 from datetime import datetime
+from typing import Optional
 
-from fastapi import APIRouter, BackgroundTasks
+from fastapi import APIRouter, BackgroundTasks, Header, HTTPException
 from pydantic import BaseModel
-from sqlmodel import Session, select
+from sqlalchemy import inspect
+from sqlmodel import Session, SQLModel, select
 
-from database import get_engine, get_session
+from database import get_data_dir, get_engine, get_session
+from services.background_pdf_generator import BackgroundPDFGenerator
+from services.backup_scheduler import BackupScheduler
+from services.db_maintenance_service import is_write_in_progress
 from utils import logger
+from utils.config import APP_VERSION
+from utils.paths import strip_long_path_prefix
 
 router = APIRouter()
 
 # Startup tracking
 _start_time = time.time()
 _is_ready = False
+# Schema inspection is too slow for every /health request: cached at startup
+# and after a restore (refresh_pending_migrations)
+_pending_migrations: Optional[int] = None
+
+# Time for the shutdown response to reach the shell before the server stops
+SHUTDOWN_DELAY_S = 0.2
+
+# Per-launch secret of the desktop shell (BILLINO_SHUTDOWN_TOKEN). A custom
+# header also forces a CORS preflight, so web pages cannot send it.
+SHUTDOWN_TOKEN_HEADER = "X-Billino-Shutdown-Token"
 
 
 class HealthResponse(BaseModel):
@@ -27,6 +47,66 @@ class HealthResponse(BaseModel):
     db_response_time_ms: int
     version: str
     environment: str
+    # Optional details for the desktop shell (older backends omit them)
+    pending_migrations: Optional[int] = None  # schema changes not yet applied
+    last_backup: Optional[str] = None  # ISO timestamp of the newest DB backup
+    queue_depth: int = 0  # PDF generations currently running in background
+    # Identify an instance left over from an earlier session
+    instance_id: Optional[str] = None  # set per start, shared by all workers
+    pid: Optional[int] = None  # process the shell started (uvicorn supervisor)
+    data_dir: Optional[str] = None
+
+
+def _count_pending_migrations() -> Optional[int]:
+    """
+    Count schema differences between the SQLModel models and the database.
+
+    `SQLModel.metadata.create_all()` only creates missing tables, never
+    missing columns – so every missing table/column means a migration script
+    (see `scripts/migrate_*.py`) has not been applied yet.
+
+    Returns:
+        Number of missing tables + columns, or None if inspection failed
+    """
+    try:
+        inspector = inspect(get_engine())
+        existing_tables = set(inspector.get_table_names())
+        pending = 0
+        for table in SQLModel.metadata.sorted_tables:
+            if table.name not in existing_tables:
+                pending += 1
+                continue
+            existing_columns = {c["name"] for c in inspector.get_columns(table.name)}
+            pending += sum(1 for c in table.columns if c.name not in existing_columns)
+        return pending
+    except Exception as e:
+        logger.warning(f"⚠️ Could not inspect database schema: {e}")
+        return None
+
+
+def refresh_pending_migrations() -> Optional[int]:
+    """Inspect the database schema again and cache the result for /health."""
+    global _pending_migrations
+    _pending_migrations = _count_pending_migrations()
+    return _pending_migrations
+
+
+def _get_last_backup_iso() -> Optional[str]:
+    """Return the ISO timestamp of the newest database backup, if any."""
+    backup_status = BackupScheduler.get_status().get("backup_status")
+    if not backup_status or not backup_status.get("last_db_backup"):
+        return None
+    return datetime.fromtimestamp(backup_status["last_db_backup"]).isoformat()
+
+
+def _instance_info() -> dict:
+    """Instance id, process and data directory (set by main.py)."""
+    supervisor_pid = os.getenv("BILLINO_SUPERVISOR_PID", "")
+    return {
+        "instance_id": os.getenv("BILLINO_INSTANCE_ID"),
+        "pid": int(supervisor_pid) if supervisor_pid.isdigit() else os.getpid(),
+        "data_dir": strip_long_path_prefix(get_data_dir()),
+    }
 
 
 def set_app_ready(ready: bool) -> None:
@@ -48,6 +128,12 @@ def health() -> HealthResponse:
     - `ready`: Whether the backend is accepting production traffic
     - `db_status`: Database connectivity status
     - `db_response_time_ms`: Round-trip time to database (timeout: 1000ms)
+    - `pending_migrations`: Missing tables/columns as of startup or the last
+      restore (null if unknown)
+    - `last_backup`: Timestamp of the newest database backup (null if none)
+    - `queue_depth`: Number of PDF generations running in background
+    - `instance_id`, `pid`, `data_dir`: Identify the instance, so the shell
+      can recognize a backend left over from an earlier session
 
     **Status Meanings:**
     - `ok`: Fully operational, ready for traffic
@@ -64,8 +150,14 @@ def health() -> HealthResponse:
         "timestamp": "2025-01-01T12:00:00.000000",
         "db_status": "ok",
         "db_response_time_ms": 2,
-        "version": "0.1.0",
-        "environment": "production"
+        "version": "2.0.0",
+        "environment": "production",
+        "pending_migrations": 0,
+        "last_backup": "2025-01-01T02:00:00.000000",
+        "queue_depth": 0,
+        "instance_id": "3f2a9c0e5b7d4e1f8a6b2c4d9e0f1a2b",
+        "pid": 12345,
+        "data_dir": "C:\\Users\\Jörg\\AppData\\Roaming\\Billino"
     }
     ```
 
@@ -74,8 +166,6 @@ def health() -> HealthResponse:
     2. Monitor health during application runtime
     3. Trigger alerts if degraded
     """
-    import os
-
     logger.debug("🏥 Health check requested")
 
     start = time.time()
@@ -112,6 +202,116 @@ def health() -> HealthResponse:
         timestamp=datetime.now().isoformat(),
         db_status=db_status,
         db_response_time_ms=db_response_time,
-        version="0.1.0",
+        version=APP_VERSION,
         environment=os.getenv("ENV", "development"),
+        pending_migrations=_pending_migrations,
+        last_backup=_get_last_backup_iso(),
+        queue_depth=BackgroundPDFGenerator.active_count(),
+        **_instance_info(),
+    )
+
+
+class PendingWorkResponse(BaseModel):
+    """Work that would be lost or cut off if the backend stopped now."""
+
+    idle: bool  # nothing below pending – safe to stop
+    write_in_progress: Optional[bool]  # open write transaction (None: unknown)
+    pdf_jobs: int  # PDF generations running in background
+    backup_running: bool
+
+
+@router.get(
+    "/shutdown/pending-work", tags=["health"], response_model=PendingWorkResponse
+)
+def pending_work() -> PendingWorkResponse:
+    """
+    Report unfinished writes before the backend is stopped.
+
+    The desktop shell asks this before `POST /shutdown` and waits briefly
+    while something is pending, so an invoice saved right before closing
+    the app is committed instead of rolled back by the kill.
+
+    **Returns:**
+    - `idle`: True if nothing is known to be pending
+    - `write_in_progress`: Another connection holds the database write lock
+      (null if the check failed)
+    - `pdf_jobs`: PDF generations running in background
+    - `backup_running`: A manual backup (e.g. the shutdown backup) runs
+    """
+    try:
+        write_in_progress: Optional[bool] = is_write_in_progress()
+    except FileNotFoundError:
+        write_in_progress = False
+    except Exception as e:
+        logger.warning(f"⚠️ Could not check for open write transactions: {e}")
+        write_in_progress = None
+
+    pdf_jobs = BackgroundPDFGenerator.active_count()
+    backup_running = BackupScheduler.is_backup_running()
+    return PendingWorkResponse(
+        # An unknown lock state does not hold up every shutdown
+        idle=not write_in_progress and pdf_jobs == 0 and not backup_running,
+        write_in_progress=write_in_progress,
+        pdf_jobs=pdf_jobs,
+        backup_running=backup_running,
     )
+
+
+class ShutdownResponse(BaseModel):
+    """Answer to a shutdown request."""
+
+    accepted: bool
+
+
+def _request_server_exit() -> None:
+    """Stop uvicorn the same way as Ctrl+C: lifespan shutdown, then exit."""
+    time.sleep(SHUTDOWN_DELAY_S)
+    signal.raise_signal(signal.SIGINT)
+
+
+@router.post(
+    "/shutdown",
+    tags=["health"],
+    response_model=ShutdownResponse,
+    status_code=202,
+)
+def shutdown(
+    background_tasks: BackgroundTasks,
+    token: Optional[str] = Header(None, alias=SHUTDOWN_TOKEN_HEADER),
+) -> ShutdownResponse:
+    """
+    Shut the backend down gracefully.
+
+    The desktop shell calls this first when it stops the backend, because
+    on Windows the windowless process cannot be asked to exit otherwise.
+    The app is marked not ready at once; the server runs its shutdown
+    sequence (scheduler stop, connections closed) right after answering.
+
+    Only the shell that spawned the backend may call it: the request must
+    carry the secret from BILLINO_SHUTDOWN_TOKEN in the
+    `X-Billino-Shutdown-Token` header. Without that variable (backend not
+    started by the shell) the endpoint rejects every request.
+
+    **Returns:**
+    - 202 with `{"accepted": true}`
+
+    **Errors:**
+    - 403: Token missing or wrong
+    - 409: Several worker processes run; only the supervisor can stop them
+      (the shell falls back to terminating the process)
+    """
+    expected = os.getenv("BILLINO_SHUTDOWN_TOKEN", "")
+    if not expected or not token or not secrets.compare_digest(token, expected):
+        logger.warning("🔒 Shutdown request without valid token rejected")
+        raise HTTPException(status_code=403, detail="Invalid shutdown token")
+
+    if int(os.getenv("BACKEND_WORKERS", "1")) > 1:
+        raise HTTPException(
+            status_code=409,
+            detail="Shutdown via API is not supported with several workers",
+        )
+
+    logger.warning("🛑 Shutdown requested via API")
+    set_app_ready(False)
+    background_tasks.add_task(_request_server_exit)
+    return ShutdownResponse(accepted=True)
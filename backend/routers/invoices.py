@@ -8,6 +8,8 @@ from models import (
     InvoiceCreate,
     InvoiceItem,
     InvoiceItemRead,
+    InvoiceNumberFormatResult,
+    InvoiceNumberFormatTest,
     InvoiceRead,
     Profile,
     SummaryInvoiceCreate,
@@ -22,6 +24,7 @@ from models.table_models import (
     SortField,
 )
 from services import (
+    check_number_format,
     create_summary_invoice,
     generate_next_invoice_number,
     get_preview_invoice_number,
@@ -62,6 +65,41 @@ def get_invoice_number_preview(session: Session = Depends(get_session)):
     return {"preview_number": next_number}
 
 
+@router.post("/number-format/test", response_model=InvoiceNumberFormatResult)
+def test_invoice_number_format(
+    request: InvoiceNumberFormatTest, session: Session = Depends(get_session)
+):
+    """
+    Render and validate an invoice-number pattern before it is adopted.
+
+    Placeholders: `{YYYY}`, `{YY}`, `{MM}`, `{DD}`, `{N}` / `{N:4}` (counter,
+    zero-padded). The rendered sample is checked against existing invoice
+    numbers of the same period: it must not exist yet and must continue the
+    sequence without gaps.
+
+    **Example Request:**
+    ```json
+    {"pattern": "RE-{YYYY}-{N:4}", "sample_date": "2026-01-02", "counter": 1}
+    ```
+
+    **Example Response (200):**
+    ```json
+    {
+        "pattern": "RE-{YYYY}-{N:4}",
+        "rendered": "RE-2026-0001",
+        "valid": true,
+        "errors": [],
+        "warnings": [],
+        "highest_counter": null
+    }
+    ```
+    """
+    logger.debug(f"🔢 POST /invoices/number-format/test - {request.pattern!r}")
+    return check_number_format(
+        session, request.pattern, request.sample_date, request.counter
+    )
+
+
 @router.post("/", response_model=InvoiceRead, status_code=201)
 def create_invoice(invoice: InvoiceCreate, session: Session = Depends(get_session)):
     """
@@ -1,17 +1,25 @@
 import base64
 import os
+from datetime import date
 from typing import List, Optional
 
-from fastapi import APIRouter, Depends, HTTPException, status
+from fastapi import APIRouter, Depends, HTTPException, Query, status
 from sqlmodel import Session, select
 
 from database import get_session
+from models.customer import Customer
 from models.invoice import Invoice
+from models.pdf_signing import PdfSigningRequest
 from models.stored_pdf import StoredPDF, StoredPDFCreate, StoredPDFRead
 from models.summary_invoice import SummaryInvoice
 from services.pdf_a6_generator import PDFA6Generator
 from services.pdf_data_service import PDFDataService
 from services.pdf_generator import PDFGenerator
+from services.pdf_signing_service import (
+    SigningError,
+    SigningUnavailableError,
+    sign_pdf,
+)
 from utils import logger
 
 LOG_DEV = os.getenv("ENV", "dev").lower() != "prod"
@@ -104,6 +112,68 @@ def create_invoice_pdf(invoice_id: int, session: Session = Depends(get_session))
         raise HTTPException(status_code=status.HTTP_404_NOT_FOUND, detail=str(e))
 
 
+@router.post("/invoices/{invoice_id}/sign", response_model=StoredPDFRead)
+def sign_invoice_pdf(
+    invoice_id: int,
+    request: PdfSigningRequest,
+    session: Session = Depends(get_session),
+):
+    """
+    Sign the stored PDF of an invoice (PAdES).
+
+    The signature is appended as an incremental update and replaces the
+    stored content. The certificate is passed with the request and never
+    stored by the backend.
+
+    **Path Parameters:**
+    - `invoice_id` (integer, required): ID of the invoice whose PDF to sign
+
+    **Request Body:**
+    - `pkcs12` (string): base64-encoded PKCS#12 file, with `passphrase`
+    - `pkcs11` (object): USB token instead: `module_path`, `token_label`,
+      `key_label`, `cert_label` (optional), `pin`
+    - `reason`, `location` (string, optional): shown in the signature
+
+    **Returns:**
+    - StoredPDFRead object with the signed PDF
+
+    **Errors:**
+    - 404: No PDF stored for this invoice
+    - 422: Invalid certificate/PIN, or the PDF is already signed
+    - 503: Signing support (pyHanko) is not installed
+    """
+    logger.debug(f"📥 POST /pdfs/invoices/{invoice_id}/sign - Signing invoice PDF")
+
+    stored_pdf = session.exec(
+        select(StoredPDF).where(StoredPDF.invoice_id == invoice_id)
+    ).first()
+    if not stored_pdf:
+        raise HTTPException(
+            status_code=status.HTTP_404_NOT_FOUND, detail="PDF for invoice not found"
+        )
+
+    try:
+        signed = sign_pdf(base64.b64decode(stored_pdf.content), request)
+    except SigningUnavailableError as e:
+        logger.error(f"❌ PDF signing unavailable: {e}")
+        raise HTTPException(
+            status_code=status.HTTP_503_SERVICE_UNAVAILABLE, detail=str(e)
+        )
+    except SigningError as e:
+        logger.warning(f"⚠️ Signing PDF of invoice {invoice_id} failed: {e}")
+        raise HTTPException(
+            status_code=status.HTTP_422_UNPROCESSABLE_ENTITY, detail=str(e)
+        )
+
+    stored_pdf.content = base64.b64encode(signed).decode("utf-8")
+    session.add(stored_pdf)
+    session.commit()
+    session.refresh(stored_pdf)
+
+    logger.info(f"✅ PDF of invoice {invoice_id} signed (PDF ID: {stored_pdf.id})")
+    return stored_pdf
+
+
 @router.post(
     "/summary-invoices/{summary_invoice_id}",
     response_model=StoredPDFRead,
@@ -295,6 +365,85 @@ def create_a6_invoices_pdf(
         raise HTTPException(status_code=status.HTTP_404_NOT_FOUND, detail=str(e))
 
 
+@router.post(
+    "/customers/{customer_id}/statement",
+    response_model=StoredPDFRead,
+    status_code=status.HTTP_201_CREATED,
+)
+def create_customer_statement_pdf(
+    customer_id: int,
+    date_from: date = Query(..., description="First day of the period (inclusive)"),
+    date_to: date = Query(..., description="Last day of the period (inclusive)"),
+    profile_id: Optional[int] = Query(
+        None, description="Issuing profile (default: of the latest invoice)"
+    ),
+    session: Session = Depends(get_session),
+):
+    """
+    Generate and store a customer statement (Kontoauszug).
+
+    Lists all invoices of the customer dated within the period, with net,
+    tax and gross amounts and the totals of the period. Only invoices of
+    the issuing profile are included.
+
+    **Path Parameters:**
+    - `customer_id` (integer, required): ID of the customer
+
+    **Query Parameters:**
+    - `date_from`, `date_to` (date, required): Period, e.g. `2025-01-01`
+    - `profile_id` (integer, optional): Issuing profile; defaults to the
+      profile of the customer's most recent invoice
+
+    **Returns:**
+    - StoredPDFRead object with type "customer_statement"
+
+    **Errors:**
+    - 400: `date_from` is after `date_to`
+    - 404: Customer or profile not found, or the customer has no invoices
+    """
+    logger.debug(
+        f"📥 POST /pdfs/customers/{customer_id}/statement - {date_from} to {date_to}"
+    )
+
+    if date_from > date_to:
+        raise HTTPException(
+            status_code=status.HTTP_400_BAD_REQUEST,
+            detail="date_from must not be after date_to",
+        )
+
+    if not session.get(Customer, customer_id):
+        raise HTTPException(
+            status_code=status.HTTP_404_NOT_FOUND, detail="Customer not found"
+        )
+
+    pdf_data_service = PDFDataService(session)
+    pdf_generator = PDFGenerator()
+
+    try:
+        statement_data = pdf_data_service.get_customer_statement_pdf_data(
+            customer_id, date_from, date_to, profile_id
+        )
+        pdf_bytes = pdf_generator.generate_customer_statement_pdf(statement_data)
+    except ValueError as e:
+        logger.warning(f"⚠️ Statement for customer {customer_id} failed: {e}")
+        raise HTTPException(status_code=status.HTTP_404_NOT_FOUND, detail=str(e))
+
+    # Like a6_invoices: a collection, so invoice_id and summary_invoice_id stay None
+    stored_pdf = StoredPDF(
+        type="customer_statement",
+        content=base64.b64encode(pdf_bytes).decode("utf-8"),
+    )
+    session.add(stored_pdf)
+    session.commit()
+    session.refresh(stored_pdf)
+
+    logger.info(
+        f"✅ Statement for customer {customer_id} created "
+        f"({len(statement_data.entries)} invoice(s), PDF ID: {stored_pdf.id})"
+    )
+    return stored_pdf
+
+
 @router.get("/", response_model=List[StoredPDFRead])
 def get_all_pdfs(session: Session = Depends(get_session)):
     """
@@ -26,7 +26,7 @@ def load_env_file(env_path: Path) -> Dict[str, str]:
     env_vars = {}
     if not env_path.exists():
         return env_vars
-    with open(env_path) as f:
+    with open(env_path, encoding="utf-8") as f:
         for line in f:
             line = line.strip()
             if not line or line.startswith("#"):
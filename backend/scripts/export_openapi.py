@@ -0,0 +1,32 @@
+"""
+Export the backend's OpenAPI spec as JSON
+
+The Electron shell generates its typed backend client from this file
+(electron: `npm run generate:api`). No server is started and no database
+is touched.
+
+Usage:
+    python scripts/export_openapi.py [output.json]
+"""
+
+import json
+import os
+import sys
+from pathlib import Path
+
+sys.path.append(os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from main import app  # noqa: E402
+
+
+def export_openapi(output: Path) -> None:
+    """Write the OpenAPI spec of the FastAPI app to `output`."""
+    output.parent.mkdir(parents=True, exist_ok=True)
+    spec = app.openapi()
+    output.write_text(json.dumps(spec, indent=2, ensure_ascii=False), encoding="utf-8")
+    print(f"OpenAPI spec written to {output} ({len(spec.get('paths', {}))} paths)")
+
+
+if __name__ == "__main__":
+    target = Path(sys.argv[1]) if len(sys.argv) > 1 else Path("openapi.json")
+    export_openapi(target.resolve())
@@ -1,6 +1,12 @@
 from .invoice_number_generator import (
+    check_number_format,
     generate_next_invoice_number,
     get_preview_invoice_number,
+    render_invoice_number,
     validate_invoice_number_format,
 )
-from .summary_invoice_generator import create_summary_invoice
+from .summary_invoice_generator import (
+    calculate_summary_totals,
+    create_summary_invoice,
+    recalculate_summary_totals,
+)
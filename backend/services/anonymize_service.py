@@ -0,0 +1,178 @@
+"""
+Anonymisierter Datenbank-Export für Fehlerberichte.
+
+Erzeugt eine Kopie der Datenbank, die Nutzer gefahrlos an einen
+Fehlerbericht anhängen können und die datenabhängige Fehler trotzdem
+reproduziert:
+- Namen, Adressen, Notizen, Positionstexte, Bankdaten und Steuernummern
+  werden zeichenweise verwürfelt – Länge, Groß-/Kleinschreibung, Umlaute,
+  Ziffern, Leer- und Satzzeichen bleiben erhalten
+- Beträge werden mit einem gemeinsamen Faktor skaliert (Verhältnisse,
+  Vorzeichen und zwei Nachkommastellen bleiben erhalten)
+- gespeicherte PDFs werden entfernt (sie enthalten die Originaldaten)
+
+Innerhalb eines Exports ist die Verwürfelung deterministisch: gleiche Werte
+ergeben gleiche Ersatzwerte. Der Schlüssel wird pro Export zufällig gewählt
+und nicht gespeichert, sodass sich die Originalwerte nicht zurückrechnen
+lassen.
+"""
+
+import hashlib
+import hmac
+import random
+import secrets
+import sqlite3
+from contextlib import closing
+from pathlib import Path
+from typing import Optional
+
+from services.export_service import create_export_path
+from utils.logger import logger
+
+# Tabelle → Textspalten mit personenbezogenen Daten
+TEXT_COLUMNS = {
+    "customer": ["name", "address", "city", "note"],
+    "profile": ["name", "address", "city", "bank_data", "tax_number"],
+    "invoice_item": ["description"],
+}
+
+# Tabelle → Betragsspalten
+AMOUNT_COLUMNS = {
+    "invoice": ["total_amount"],
+    "invoice_item": ["price"],
+    "summary_invoice": ["total_net", "total_tax", "total_gross"],
+}
+
+_LOWER = "abcdefghijklmnopqrstuvwxyz"
+_UPPER = _LOWER.upper()
+_DIGITS = "0123456789"
+_UMLAUTS_LOWER = "äöüß"
+_UMLAUTS_UPPER = "ÄÖÜ"
+
+
+def scramble_text(value: Optional[str], key: bytes) -> Optional[str]:
+    """
+    Verwürfle einen Text formerhaltend und deterministisch (pro Schlüssel).
+
+    "Müller GmbH, DE89 3704" → z.B. "Kühzqa VzdL, QW27 9150"
+    """
+    if value is None:
+        return None
+    seed = hmac.new(key, value.encode("utf-8"), hashlib.sha256).digest()
+    rng = random.Random(seed)
+    result = []
+    for char in value:
+        for alphabet in (_LOWER, _UPPER, _DIGITS, _UMLAUTS_LOWER, _UMLAUTS_UPPER):
+            if char in alphabet:
+                result.append(rng.choice(alphabet))
+                break
+        else:
+            result.append(char)
+    return "".join(result)
+
+
+def amount_factor(key: bytes) -> float:
+    """Gemeinsamer Skalierungsfaktor aller Beträge eines Exports (0.5–2.0)."""
+    return round(random.Random(key).uniform(0.5, 2.0), 4)
+
+
+def _table_columns(conn: sqlite3.Connection, table: str) -> set[str]:
+    return {row[1] for row in conn.execute(f"PRAGMA table_info({table})")}
+
+
+def anonymize_database(db_path: Path, key: Optional[bytes] = None) -> dict:
+    """
+    Anonymisiere eine Datenbank-Kopie in place.
+
+    Args:
+        db_path: Pfad zur Kopie (niemals die aktive Datenbank!)
+        key: Schlüssel für die Verwürfelung (standard: zufällig)
+
+    Returns:
+        dict mit anonymized_values, scaled_amounts, removed_pdfs
+    """
+    key = key or secrets.token_bytes(32)
+    factor = amount_factor(key)
+    stats = {"anonymized_values": 0, "scaled_amounts": 0, "removed_pdfs": 0}
+
+    with closing(sqlite3.connect(str(db_path))) as conn:
+        conn.create_function(
+            "scramble", 1, lambda value: scramble_text(value, key), deterministic=True
+        )
+        conn.create_function(
+            "scale_amount",
+            1,
+            lambda value: None if value is None else round(value * factor, 2),
+            deterministic=True,
+        )
+        tables = {
+            row[0]
+            for row in conn.execute("SELECT name FROM sqlite_master WHERE type='table'")
+        }
+
+        with conn:
+            for table, columns in TEXT_COLUMNS.items():
+                if table not in tables:
+                    continue
+                existing = [c for c in columns if c in _table_columns(conn, table)]
+                if not existing:
+                    continue
+                assignments = ", ".join(f"{c} = scramble({c})" for c in existing)
+                cursor = conn.execute(f"UPDATE {table} SET {assignments}")
+                stats["anonymized_values"] += cursor.rowcount * len(existing)
+
+            for table, columns in AMOUNT_COLUMNS.items():
+                if table not in tables:
+                    continue
+                existing = [c for c in columns if c in _table_columns(conn, table)]
+                if not existing:
+                    continue
+                assignments = ", ".join(f"{c} = scale_amount({c})" for c in existing)
+                cursor = conn.execute(f"UPDATE {table} SET {assignments}")
+                stats["scaled_amounts"] += cursor.rowcount * len(existing)
+
+            if "stored_pdfs" in tables:
+                stats["removed_pdfs"] = conn.execute("DELETE FROM stored_pdfs").rowcount
+
+        # Freigegebene Seiten enthalten noch die Originaldaten → Datei neu aufbauen
+        conn.execute("VACUUM")
+
+    return stats
+
+
+def export_anonymized_db(source_db: Optional[Path] = None) -> dict:
+    """
+    Erzeuge einen anonymisierten DB-Export unter DATA_DIR/exports/.
+
+    Args:
+        source_db: Quelldatenbank (standard: get_db_file())
+
+    Returns:
+        dict mit job_id, filename, size_bytes und den Statistiken aus
+        anonymize_database()
+    """
+    from database import get_db_file
+
+    source = source_db or get_db_file()
+    if not source.is_file():
+        raise FileNotFoundError(str(source))
+
+    job_id, export_path = create_export_path(".db", prefix="anonymized")
+    try:
+        # Backup-API: konsistente Kopie auch bei aktiver Datenbank
+        with closing(sqlite3.connect(str(source))) as source_conn:
+            with closing(sqlite3.connect(str(export_path))) as export_conn:
+                source_conn.backup(export_conn)
+        stats = anonymize_database(export_path)
+    except Exception:
+        export_path.unlink(missing_ok=True)
+        raise
+
+    size_bytes = export_path.stat().st_size
+    logger.info(f"🕶️ Anonymisierter DB-Export erstellt: {export_path.name}")
+    return {
+        "job_id": job_id,
+        "filename": export_path.name,
+        "size_bytes": size_bytes,
+        **stats,
+    }
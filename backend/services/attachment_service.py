@@ -0,0 +1,122 @@
+"""
+Rechnungsanhänge (Lieferscheine, Verträge, …).
+
+Die Desktop-App kopiert eine Datei nach DATA_DIR/attachments/ und
+registriert sie anschließend hier. Abgelegt wird nach Inhalt:
+
+    attachments/<erste 2 Zeichen des Hash>/<sha256><endung>
+
+So liegt dieselbe Datei nur einmal auf der Platte, auch wenn sie an
+mehreren Rechnungen hängt. Die Datei wird gelöscht, sobald kein Anhang
+mehr auf sie verweist.
+"""
+
+import hashlib
+from pathlib import Path
+
+from sqlmodel import Session, select
+
+from database import get_data_dir
+from models import Invoice, InvoiceAttachment, InvoiceAttachmentCreate
+from utils.logger import logger
+
+
+def get_attachment_dir() -> Path:
+    """Gibt das Anhang-Verzeichnis zurück (respektiert DATA_DIR)."""
+    return get_data_dir() / "attachments"
+
+
+def attachment_file(sha256: str, filename: str) -> Path:
+    """Ablageort einer Datei mit diesem Inhalt."""
+    return get_attachment_dir() / sha256[:2] / (sha256 + Path(filename).suffix.lower())
+
+
+def _file_hash(path: Path) -> str:
+    digest = hashlib.sha256()
+    with open(path, "rb") as f:
+        for chunk in iter(lambda: f.read(1024 * 1024), b""):
+            digest.update(chunk)
+    return digest.hexdigest()
+
+
+def list_attachments(session: Session, invoice_id: int) -> list[InvoiceAttachment]:
+    """Anhänge einer Rechnung (älteste zuerst)."""
+    stmt = (
+        select(InvoiceAttachment)
+        .where(InvoiceAttachment.invoice_id == invoice_id)
+        .order_by(InvoiceAttachment.created_at)
+    )
+    return list(session.exec(stmt).all())
+
+
+def register_attachment(
+    session: Session, invoice_id: int, data: InvoiceAttachmentCreate
+) -> tuple[InvoiceAttachment, bool]:
+    """
+    Registriere eine bereits abgelegte Datei als Anhang.
+
+    Returns:
+        (Anhang, neu angelegt?) – dieselbe Datei zweimal an derselben
+        Rechnung ergibt den vorhandenen Anhang
+
+    Raises:
+        LookupError: Rechnung existiert nicht
+        FileNotFoundError: Datei liegt nicht unter attachments/
+        ValueError: Inhalt passt nicht zu Hash oder Größe
+    """
+    if session.get(Invoice, invoice_id) is None:
+        raise LookupError(f"Rechnung {invoice_id} nicht gefunden")
+
+    sha256 = data.sha256.lower()
+    path = attachment_file(sha256, data.filename)
+    if not path.is_file():
+        raise FileNotFoundError(str(path))
+    if path.stat().st_size != data.size_bytes or _file_hash(path) != sha256:
+        raise ValueError("Datei passt nicht zu Hash/Größe")
+
+    existing = session.exec(
+        select(InvoiceAttachment).where(
+            InvoiceAttachment.invoice_id == invoice_id,
+            InvoiceAttachment.sha256 == sha256,
+        )
+    ).first()
+    if existing:
+        return existing, False
+
+    attachment = InvoiceAttachment(
+        invoice_id=invoice_id,
+        filename=data.filename,
+        sha256=sha256,
+        size_bytes=data.size_bytes,
+        content_type=data.content_type,
+    )
+    session.add(attachment)
+    session.commit()
+    session.refresh(attachment)
+    return attachment, True
+
+
+def delete_attachment(session: Session, attachment: InvoiceAttachment) -> bool:
+    """
+    Entferne einen Anhang; die Datei nur, wenn kein anderer darauf verweist.
+
+    Returns:
+        True, wenn die Datei gelöscht wurde
+    """
+    path = attachment_file(attachment.sha256, attachment.filename)
+    session.delete(attachment)
+    session.commit()
+
+    still_used = session.exec(
+        select(InvoiceAttachment).where(InvoiceAttachment.sha256 == attachment.sha256)
+    ).first()
+    if still_used:
+        return False
+    try:
+        path.unlink()
+    except FileNotFoundError:
+        return False
+    except OSError as e:
+        logger.warning(f"⚠️ Anhang-Datei nicht gelöscht: {path}: {e}")
+        return False
+    return True
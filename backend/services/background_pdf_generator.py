@@ -107,6 +107,16 @@ class BackgroundPDFGenerator:
 
         return pdf_thread
 
+    @classmethod
+    def active_count(cls) -> int:
+        """
+        Return the number of PDF generations currently running.
+
+        Reported as `queue_depth` by the health endpoint.
+        """
+        with cls._lock:
+            return sum(1 for t in cls._active_threads if t.is_alive())
+
     @classmethod
     def wait_for_active_threads(cls, timeout: float = None) -> bool:
         """
@@ -4,8 +4,10 @@ Scheduler für periodische Backup-Jobs.
 Nutzt APScheduler für tägliche Backups und Cleanup-Tasks.
 """
 
+import threading
 from datetime import datetime
 from typing import Optional
+from uuid import uuid4
 
 from apscheduler.schedulers.background import BackgroundScheduler
 from apscheduler.triggers.cron import CronTrigger
@@ -25,6 +27,8 @@ class BackupScheduler:
 
     _scheduler: Optional[BackgroundScheduler] = None
     _handler: Optional[BackupHandler] = None
+    _backup_job: Optional[dict] = None
+    _backup_job_lock = threading.Lock()
 
     @classmethod
     def initialize(
@@ -111,6 +115,7 @@ class BackupScheduler:
         try:
             backup_path = cls._handler.backup_database()
             if backup_path:
+                cls._handler.backup_attachments()
                 return {
                     "success": True,
                     "backup_path": str(backup_path),
@@ -126,6 +131,63 @@ class BackupScheduler:
                 "error": "Backup fehlgeschlagen (interner Fehler)",
             }
 
+    @classmethod
+    def start_backup_job(cls) -> dict:
+        """
+        Starte ein manuelles Backup im Hintergrund.
+
+        Die Shell fragt den Fortschritt über get_backup_job() ab, statt auf
+        die Antwort zu warten (große Datenbanken beim Beenden). Läuft bereits
+        ein Backup, wird dessen Job zurückgegeben.
+
+        Returns:
+            Job-Dict (job_id, state, started, finished, backup_path, error)
+        """
+        with cls._backup_job_lock:
+            job = cls._backup_job
+            if job is not None and job["state"] == "running":
+                return dict(job)
+
+            job = {
+                "job_id": uuid4().hex,
+                "state": "running",
+                "started": datetime.now().isoformat(),
+                "finished": None,
+                "backup_path": None,
+                "error": None,
+            }
+            cls._backup_job = job
+            snapshot = dict(job)
+
+        threading.Thread(
+            target=cls._run_backup_job, args=(job,), name="manual-backup", daemon=True
+        ).start()
+        return snapshot
+
+    @classmethod
+    def get_backup_job(cls, job_id: str) -> Optional[dict]:
+        """Status des manuellen Backup-Jobs oder None, wenn unbekannt."""
+        with cls._backup_job_lock:
+            job = cls._backup_job
+            if job is None or job["job_id"] != job_id:
+                return None
+            return dict(job)
+
+    @classmethod
+    def is_backup_running(cls) -> bool:
+        """Läuft gerade ein manuelles Backup (z. B. das Backup beim Beenden)?"""
+        with cls._backup_job_lock:
+            return cls._backup_job is not None and cls._backup_job["state"] == "running"
+
+    @classmethod
+    def _run_backup_job(cls, job: dict) -> None:
+        result = cls.trigger_backup_now()
+        with cls._backup_job_lock:
+            job["state"] = "done" if result.get("success") else "failed"
+            job["backup_path"] = result.get("backup_path")
+            job["error"] = result.get("error")
+            job["finished"] = datetime.now().isoformat()
+
     @classmethod
     def get_status(cls) -> dict:
         """
@@ -194,6 +256,7 @@ class BackupScheduler:
             if backup_path:
                 # Optional: PDF-Backup auch machen
                 BackupScheduler._handler.backup_pdfs()
+                BackupScheduler._handler.backup_attachments()
                 logger.info(f"✅ Geplantes Backup erfolgreich: {backup_path}")
             else:
                 logger.error("❌ Backup fehlgeschlagen")
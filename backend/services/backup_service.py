@@ -9,12 +9,14 @@ Unterstützt zwei Modi:
 import os
 import shutil
 import sqlite3
+from contextlib import closing
 from datetime import datetime, timedelta
 from pathlib import Path
 from typing import Optional
 
 from database import get_data_dir
 from utils.logger import logger
+from utils.paths import long_path_obj
 
 # Backend-Root Verzeichnis (wo main.py liegt)
 BACKEND_ROOT = Path(__file__).parent.parent
@@ -34,6 +36,7 @@ def get_backup_paths():
         "pdf_archive": data_dir / "pdfs" / "archive",
         "pdf_invoices": data_dir / "pdfs" / "invoices",
         "pdf_summary": data_dir / "pdfs" / "summary_invoices",
+        "attachments": data_dir / "attachments",
     }
 
 
@@ -64,12 +67,14 @@ class BackupHandler:
 
         paths = get_backup_paths()
 
-        self.BACKUP_ROOT = backup_root or paths["backup_root"]
+        # Explizit übergebene Pfade können lang oder UNC-Freigaben sein
+        self.BACKUP_ROOT = long_path_obj(backup_root or paths["backup_root"], 48)
         self.BACKUP_DAILY = self.BACKUP_ROOT / "daily"
         self.PDF_ARCHIVE = paths["pdf_archive"]
-        self.DB_PATH = db_path or get_db_file()
+        self.DB_PATH = long_path_obj(db_path or get_db_file())
         self.PDF_INVOICES_PATH = paths["pdf_invoices"]
         self.PDF_SUMMARY_PATH = paths["pdf_summary"]
+        self.ATTACHMENTS_PATH = paths["attachments"]
 
         self.desktop_enabled = self._detect_desktop_enabled(desktop_enabled)
         self.retention_days = retention_days
@@ -149,9 +154,12 @@ class BackupHandler:
             # damit Verbindungen auch bei Fehlern sicher geschlossen werden
             with sqlite3.connect(str(self.DB_PATH)) as source_conn:
                 with sqlite3.connect(str(backup_path)) as dest_conn:
-                    # Führe Backup durch (sicher auch bei aktiver Datenbank)
+                    # Führe Backup durch (sicher auch bei aktiver Datenbank);
+                    # enthält auch Änderungen, die nur in der -wal Datei stehen
                     with dest_conn:
                         source_conn.backup(dest_conn)
+                    # Backup als eigenständige Datei (ohne -wal/-shm) ablegen
+                    dest_conn.execute("PRAGMA journal_mode = DELETE")
             logger.info(f"✅ Datenbank-Backup erstellt: {backup_path}")
 
             # Cleanup alte Backups
@@ -210,6 +218,36 @@ class BackupHandler:
 
         return stats
 
+    def backup_attachments(self) -> int:
+        """
+        Spiegle Rechnungsanhänge nach backups/attachments/.
+
+        Anhänge sind nach Inhalt benannt und ändern sich nie; kopiert
+        werden nur Dateien, die im Backup noch fehlen.
+
+        Returns:
+            Anzahl neu gesicherter Dateien
+        """
+        if not self.ATTACHMENTS_PATH.exists():
+            return 0
+
+        target_root = self.BACKUP_ROOT / "attachments"
+        copied = 0
+        try:
+            for source in self.ATTACHMENTS_PATH.glob("*/*"):
+                target = target_root / source.parent.name / source.name
+                if target.exists():
+                    continue
+                target.parent.mkdir(parents=True, exist_ok=True)
+                shutil.copy2(source, target)
+                copied += 1
+        except (IOError, OSError) as e:
+            logger.error(f"❌ Fehler beim Backup von Anhängen: {e}")
+
+        if copied:
+            logger.info(f"✅ Anhang-Backup: {copied} neue Dateien")
+        return copied
+
     def _cleanup_old_backups(self) -> None:
         """
         Lösche Backup-Dateien älter als `retention_days`.
@@ -326,3 +364,242 @@ class BackupHandler:
 
         logger.info(f"✅ Backup verifiziert: {backup_path}")
         return True
+
+
+def inspect_backup(backup_path: Path) -> dict:
+    """
+    Lies den Inhalt eines DB-Backups, ohne es wiederherzustellen.
+
+    Das Backup wird nur lesend geöffnet (keine Änderungen). Lange
+    Windows-Pfade und UNC-Freigaben werden unterstützt.
+
+    Args:
+        backup_path: Absoluter Pfad zur Backup-Datei (.db)
+
+    Returns:
+        Dict mit Schema-Version, Rechnungsanzahl, Datumsbereich und den
+        Dateinamen der enthaltenen PDFs
+
+    Raises:
+        FileNotFoundError: Datei existiert nicht
+        ValueError: Keine (gültige) Billino-Datenbank
+    """
+    from database import SCHEMA_VERSION
+    from services.fiscal_year_service import pdf_filename
+
+    path = long_path_obj(backup_path)
+    if not path.is_file():
+        raise FileNotFoundError(str(backup_path))
+
+    try:
+        with closing(sqlite3.connect(str(path))) as conn:
+            # Keine Schreibzugriffe (auch für UNC-Pfade, die als URI mit
+            # mode=ro nicht darstellbar sind)
+            conn.execute("PRAGMA query_only = ON")
+            tables = {
+                row[0]
+                for row in conn.execute(
+                    "SELECT name FROM sqlite_master WHERE type = 'table'"
+                )
+            }
+            if "invoice" not in tables:
+                raise ValueError("Keine Billino-Datenbank (Tabelle 'invoice' fehlt)")
+
+            schema_version = conn.execute("PRAGMA user_version").fetchone()[0]
+            invoice_count, first_date, last_date = conn.execute(
+                "SELECT COUNT(*), MIN(substr(date, 1, 10)), MAX(substr(date, 1, 10)) "
+                "FROM invoice"
+            ).fetchone()
+
+            pdfs = []
+            if "stored_pdfs" in tables:
+                # stored_pdfs referenziert summary_invoice → beide Tabellen existieren
+                rows = conn.execute(
+                    "SELECT p.type, p.created_at, i.number, s.range_text "
+                    "FROM stored_pdfs p "
+                    "LEFT JOIN invoice i ON i.id = p.invoice_id "
+                    "LEFT JOIN summary_invoice s ON s.id = p.summary_invoice_id "
+                    "ORDER BY p.created_at"
+                ).fetchall()
+                pdfs = [
+                    {
+                        "filename": pdf_filename(number or range_text or pdf_type),
+                        "type": pdf_type,
+                        "created_at": created_at,
+                    }
+                    for pdf_type, created_at, number, range_text in rows
+                ]
+    except sqlite3.DatabaseError as e:
+        raise ValueError(f"Keine gültige SQLite-Datenbank: {e}") from e
+
+    stat = path.stat()
+    return {
+        "path": str(backup_path),
+        "filename": backup_path.name,
+        "size_bytes": stat.st_size,
+        "created_iso": datetime.fromtimestamp(stat.st_mtime).isoformat(),
+        "schema_version": schema_version,
+        "current_schema_version": SCHEMA_VERSION,
+        "compatible": schema_version <= SCHEMA_VERSION,
+        "invoice_count": invoice_count,
+        "first_invoice_date": first_date,
+        "last_invoice_date": last_date,
+        "pdfs": pdfs,
+    }
+
+
+RESTORE_SCOPES = ("full", "database", "pdfs")
+
+_PDF_COLUMNS = "type, content, created_at, invoice_id, summary_invoice_id"
+
+
+def _copy_database(source: Path, target: Path) -> None:
+    """Kopiere eine SQLite-Datenbank per Backup-API (auch bei offener Ziel-DB)."""
+    with closing(sqlite3.connect(str(source))) as source_conn:
+        with closing(sqlite3.connect(str(target))) as target_conn:
+            source_conn.backup(target_conn)
+
+
+def _restore_pdfs(
+    backup_path: Path, db_path: Path, months: Optional[list[str]]
+) -> tuple[int, int]:
+    """
+    Übernimm gespeicherte PDFs aus dem Backup in die aktuelle Datenbank.
+
+    PDFs zu Rechnungen, die es in der aktuellen DB nicht (mehr) gibt, werden
+    übersprungen. Vorhandene PDFs derselben Rechnung werden ersetzt.
+
+    Returns:
+        (wiederhergestellt, übersprungen)
+    """
+    month_filter = ""
+    params: list[str] = []
+    if months:
+        placeholders = ",".join("?" * len(months))
+        month_filter = f"AND substr(p.created_at, 1, 7) IN ({placeholders})"
+        params = list(months)
+
+    with closing(sqlite3.connect(str(db_path))) as conn:
+        conn.execute("ATTACH DATABASE ? AS restore_src", (str(backup_path),))
+        try:
+            with conn:
+                total = conn.execute(
+                    "SELECT COUNT(*) FROM restore_src.stored_pdfs p "
+                    f"WHERE 1 {month_filter}",
+                    params,
+                ).fetchone()[0]
+                # INSERT OR REPLACE ersetzt PDFs mit gleicher invoice_id bzw.
+                # summary_invoice_id (Unique-Constraints)
+                restored = conn.execute(
+                    f"INSERT OR REPLACE INTO main.stored_pdfs ({_PDF_COLUMNS}) "
+                    f"SELECT {_PDF_COLUMNS} FROM restore_src.stored_pdfs p "
+                    "WHERE (p.invoice_id IS NULL "
+                    "OR p.invoice_id IN (SELECT id FROM main.invoice)) "
+                    "AND (p.summary_invoice_id IS NULL "
+                    "OR p.summary_invoice_id IN (SELECT id FROM main.summary_invoice)) "
+                    "AND NOT EXISTS (SELECT 1 FROM main.stored_pdfs c "
+                    "WHERE c.invoice_id IS NULL AND c.summary_invoice_id IS NULL "
+                    "AND p.invoice_id IS NULL AND p.summary_invoice_id IS NULL "
+                    "AND c.type = p.type AND c.created_at = p.created_at) "
+                    f"{month_filter}",
+                    params,
+                ).rowcount
+        finally:
+            conn.execute("DETACH DATABASE restore_src")
+
+    return restored, total - restored
+
+
+def restore_backup(
+    backup_path: Path,
+    scope: str = "full",
+    months: Optional[list[str]] = None,
+    db_path: Optional[Path] = None,
+) -> dict:
+    """
+    Stelle ein DB-Backup ganz oder teilweise wieder her.
+
+    Grundlage ist der Inhalt des Backups laut `inspect_backup` (Manifest).
+    Vorher wird ein Sicherheits-Backup der aktuellen Datenbank erstellt.
+
+    Scopes:
+    - full: Datenbank inkl. PDFs komplett ersetzen
+    - database: Daten ersetzen, aktuelle PDFs behalten (soweit die Rechnung
+      im Backup existiert)
+    - pdfs: nur PDFs übernehmen, optional gefiltert nach Monaten ("YYYY-MM",
+      Erstellungsdatum des PDFs)
+
+    Args:
+        backup_path: Absoluter Pfad zur Backup-Datei (.db)
+        scope: "full", "database" oder "pdfs"
+        months: Nur PDFs dieser Monate (nur für scope="pdfs")
+        db_path: Ziel-Datenbank (standard: get_db_file())
+
+    Returns:
+        Dict mit scope, safety_backup_path, restored_pdfs, skipped_pdfs
+
+    Raises:
+        FileNotFoundError: Backup existiert nicht
+        ValueError: Ungültiger Scope, ungültige Monate oder Backup nicht kompatibel
+    """
+    from database import dispose_engine, get_db_file
+
+    if scope not in RESTORE_SCOPES:
+        raise ValueError(f"Unbekannter Scope: {scope}")
+    if months and scope != "pdfs":
+        raise ValueError("Monate können nur beim Scope 'pdfs' gewählt werden")
+    for month in months or []:
+        try:
+            datetime.strptime(month, "%Y-%m")
+        except ValueError:
+            raise ValueError(f"Ungültiger Monat: {month} (erwartet YYYY-MM)") from None
+
+    manifest = inspect_backup(backup_path)
+    source = long_path_obj(backup_path)
+    if not manifest["compatible"]:
+        raise ValueError(
+            f"Backup hat Schema-Version {manifest['schema_version']}, "
+            f"diese Version unterstützt bis {manifest['current_schema_version']}"
+        )
+
+    target = db_path or get_db_file()
+    handler = BackupHandler(db_path=target)
+    safety_backup = handler.backup_database()
+    if safety_backup is None and target.exists():
+        raise OSError("Sicherheits-Backup vor der Wiederherstellung fehlgeschlagen")
+
+    restored_pdfs = skipped_pdfs = 0
+    if scope == "full":
+        _copy_database(source, target)
+        restored_pdfs = len(manifest["pdfs"])
+    elif scope == "database":
+        # Backup in Zwischenkopie laden, aktuelle PDFs dort einspielen und
+        # das Ergebnis in einem Schritt übernehmen
+        staging = target.with_name(f"{target.stem}.restore.db")
+        try:
+            _copy_database(source, staging)
+            with closing(sqlite3.connect(str(staging))) as conn:
+                with conn:
+                    conn.execute("DELETE FROM stored_pdfs")
+            if safety_backup is not None:
+                _, skipped_pdfs = _restore_pdfs(safety_backup, staging, None)
+            _copy_database(staging, target)
+        finally:
+            staging.unlink(missing_ok=True)
+    else:
+        restored_pdfs, skipped_pdfs = _restore_pdfs(source, target, months)
+
+    # Gepoolte Verbindungen verwerfen, damit keine alten Daten gelesen werden
+    dispose_engine()
+
+    logger.info(
+        f"♻️ Backup wiederhergestellt ({scope}): {backup_path.name} "
+        f"({restored_pdfs} PDFs, {skipped_pdfs} übersprungen)"
+    )
+    return {
+        "scope": scope,
+        "backup_path": str(backup_path),
+        "safety_backup_path": str(safety_backup) if safety_backup else None,
+        "restored_pdfs": restored_pdfs,
+        "skipped_pdfs": skipped_pdfs,
+    }
@@ -0,0 +1,47 @@
+"""
+CSV-Dateien für Exporte im gewünschten Dialekt schreiben.
+
+Deutsches Excel öffnet CSVs nur mit Semikolon als Trennzeichen und Komma
+als Dezimalzeichen korrekt; ältere Versionen erkennen Umlaute nur mit BOM
+oder in Windows-1252. Alle Exporte schreiben CSVs über write_csv(), damit
+ein Dialekt (CsvDialect) überall gleich wirkt.
+"""
+
+import csv
+import io
+from typing import Iterable, Optional, Sequence
+
+from models import CsvDialect
+
+
+def format_decimal(
+    value: float, dialect: Optional[CsvDialect] = None, places: int = 2
+) -> str:
+    """Zahl mit fester Nachkommastellenzahl und dem Dezimalzeichen des Dialekts."""
+    dialect = dialect or CsvDialect()
+    return f"{value:.{places}f}".replace(".", dialect.decimal_mark)
+
+
+def write_csv(
+    header: Sequence[str],
+    rows: Iterable[Sequence[object]],
+    dialect: Optional[CsvDialect] = None,
+) -> bytes:
+    """
+    CSV mit Kopfzeile als Bytes in der Kodierung des Dialekts.
+
+    Zahlen (float) werden mit format_decimal() formatiert; Zeichen, die es
+    in Windows-1252 nicht gibt, werden durch "?" ersetzt.
+    """
+    dialect = dialect or CsvDialect()
+    buffer = io.StringIO()
+    writer = csv.writer(buffer, delimiter=dialect.separator, lineterminator="\r\n")
+    writer.writerow(header)
+    for row in rows:
+        writer.writerow(
+            [
+                format_decimal(cell, dialect) if isinstance(cell, float) else cell
+                for cell in row
+            ]
+        )
+    return buffer.getvalue().encode(dialect.encoding, errors="replace")
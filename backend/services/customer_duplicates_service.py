@@ -0,0 +1,272 @@
+"""
+Dubletten-Erkennung für Kunden.
+
+find_duplicate_customers() liest die Kundentabelle direkt per SQLite und
+gruppiert Kunden, die vermutlich dieselbe Person/Firma sind – typischerweise
+entstanden durch Tippfehler, andere Schreibweisen ("Müller GmbH" vs.
+"Mueller"), vertauschte Vor-/Nachnamen oder "Str." statt "Straße".
+
+Vergleich:
+- Namen werden normalisiert (Kleinschreibung, Umlaute, Rechtsformen und
+  Satzzeichen entfernt) und unscharf verglichen (auch mit sortierten Wörtern)
+- Adressen fließen mit 30 % ein, sofern bei beiden Kunden vorhanden
+- Verglichen werden nur Kunden mit gemeinsamem Namensanfang oder gleicher
+  Adresse, damit auch große Kundenstämme schnell bleiben
+
+Zu jeder Gruppe gibt es einen Zusammenführungsvorschlag für das Frontend:
+Hauptkunde ist der mit den meisten Rechnungen, leere Felder werden aus den
+übrigen Kunden ergänzt, abweichende Werte als Konflikt gemeldet. Die Daten
+werden hier nicht verändert.
+"""
+
+import re
+import sqlite3
+import unicodedata
+from contextlib import closing
+from difflib import SequenceMatcher
+from pathlib import Path
+from typing import Optional
+
+from utils.logger import logger
+
+DEFAULT_THRESHOLD = 0.85
+NAME_WEIGHT = 0.7
+MERGE_FIELDS = ("name", "address", "city", "note")
+
+# Rechtsformen und Füllwörter, die für den Vergleich keine Rolle spielen
+_IGNORED_TOKENS = {
+    "gmbh",
+    "mbh",
+    "ug",
+    "haftungsbeschraenkt",
+    "ag",
+    "kg",
+    "ohg",
+    "gbr",
+    "ek",
+    "ev",
+    "co",
+    "inh",
+    "und",
+    "herr",
+    "frau",
+    "dr",
+}
+_UMLAUTS = str.maketrans({"ä": "ae", "ö": "oe", "ü": "ue", "ß": "ss"})
+
+
+def _fold(value: Optional[str]) -> str:
+    """Kleinschreibung, Umlaute ausgeschrieben, Akzente entfernt."""
+    text = (value or "").casefold().translate(_UMLAUTS).replace("&", " und ")
+    text = unicodedata.normalize("NFKD", text)
+    return "".join(ch for ch in text if not unicodedata.combining(ch))
+
+
+def normalize_name(value: Optional[str]) -> str:
+    """
+    Vergleichsform eines Kundennamens.
+
+    "Müller & Söhne GmbH" → "mueller soehne"
+    """
+    # "e.K." / "e. V." zuerst zusammenziehen, sonst bleiben "e", "k" übrig
+    text = re.sub(r"\b(e)\.\s*([kv])\.", r"\1\2", _fold(value))
+    tokens = re.sub(r"[^a-z0-9]+", " ", text).split()
+    kept = [token for token in tokens if token not in _IGNORED_TOKENS]
+    # Nur aus Rechtsform bestehende Namen nicht komplett leeren
+    return " ".join(kept or tokens)
+
+
+def normalize_address(value: Optional[str]) -> str:
+    """
+    Vergleichsform einer Adresse.
+
+    "Hauptstraße 5a" und "Hauptstr. 5 a" → "hauptstr5a"
+    """
+    text = re.sub(r"(strasse|str\.?)(?=\s|\d|$)", "str", _fold(value))
+    return re.sub(r"[^a-z0-9]+", "", text)
+
+
+def _similarity(a: str, b: str) -> float:
+    if not a or not b:
+        return 0.0
+    if a == b:
+        return 1.0
+    direct = SequenceMatcher(None, a, b).ratio()
+    # "Hans Müller" vs. "Müller Hans"
+    swapped = SequenceMatcher(
+        None, " ".join(sorted(a.split())), " ".join(sorted(b.split()))
+    ).ratio()
+    return max(direct, swapped)
+
+
+def _blocking_keys(name: str, address: str) -> set[str]:
+    keys = {f"n:{token[:3]}" for token in name.split() if len(token) >= 3}
+    if not keys and name:
+        keys.add(f"n:{name[:3]}")
+    if address:
+        keys.add(f"a:{address}")
+    return keys
+
+
+def _score_pair(left: dict, right: dict) -> tuple[float, list[str]]:
+    name_score = _similarity(left["_name"], right["_name"])
+    reasons = []
+    if name_score == 1.0:
+        reasons.append("same_name")
+    elif name_score > 0:
+        reasons.append("similar_name")
+
+    if left["city"] and _fold(left["city"]).strip() == _fold(right["city"]).strip():
+        reasons.append("same_city")
+
+    if not left["_address"] or not right["_address"]:
+        return name_score, reasons
+
+    address_score = SequenceMatcher(None, left["_address"], right["_address"]).ratio()
+    if address_score == 1.0:
+        reasons.append("same_address")
+    score = NAME_WEIGHT * name_score + (1 - NAME_WEIGHT) * address_score
+    return score, reasons
+
+
+def _load_customers(conn: sqlite3.Connection) -> list[dict]:
+    tables = {
+        row[0]
+        for row in conn.execute("SELECT name FROM sqlite_master WHERE type = 'table'")
+    }
+    if "customer" not in tables:
+        return []
+
+    counts: dict[int, int] = {}
+    if "invoice" in tables:
+        for customer_id, count in conn.execute(
+            "SELECT customer_id, COUNT(*) FROM invoice GROUP BY customer_id"
+        ):
+            counts[customer_id] = count
+    if "summary_invoice" in tables:
+        for customer_id, count in conn.execute(
+            "SELECT recipient_customer_id, COUNT(*) FROM summary_invoice "
+            "WHERE recipient_customer_id IS NOT NULL GROUP BY recipient_customer_id"
+        ):
+            counts[customer_id] = counts.get(customer_id, 0) + count
+
+    customers = []
+    for row in conn.execute(
+        "SELECT id, name, address, city, note FROM customer ORDER BY id"
+    ):
+        customer = dict(zip(("id",) + MERGE_FIELDS, row))
+        customer["invoice_count"] = counts.get(customer["id"], 0)
+        customer["_name"] = normalize_name(customer["name"])
+        customer["_address"] = normalize_address(customer["address"])
+        customers.append(customer)
+    return customers
+
+
+def _merge_suggestion(members: list[dict]) -> dict:
+    # Hauptkunde: meiste Rechnungen, bei Gleichstand der älteste (kleinste ID)
+    ordered = sorted(members, key=lambda c: (-c["invoice_count"], c["id"]))
+    primary = ordered[0]
+    fields = {}
+    conflicts = []
+    for field in MERGE_FIELDS:
+        values = [c[field] for c in ordered if c[field] and c[field].strip()]
+        fields[field] = values[0] if values else primary[field]
+        if len({_fold(value).strip() for value in values}) > 1:
+            conflicts.append(field)
+    return {
+        "primary_id": primary["id"],
+        "merge_ids": [c["id"] for c in ordered[1:]],
+        "fields": fields,
+        "conflicts": conflicts,
+    }
+
+
+def find_duplicate_customers(
+    db_path: Optional[Path] = None, threshold: float = DEFAULT_THRESHOLD
+) -> dict:
+    """
+    Finde vermutlich doppelt angelegte Kunden.
+
+    Args:
+        db_path: Datenbank (standard: get_db_file())
+        threshold: Mindestähnlichkeit (0–1) für ein Dubletten-Paar
+
+    Returns:
+        dict mit customer_count, threshold und clusters. Jede Gruppe enthält
+        customers (id, name, address, city, note, invoice_count), score
+        (höchste Paar-Ähnlichkeit), reasons und suggestion (primary_id,
+        merge_ids, fields, conflicts)
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+        ValueError: threshold außerhalb von 0–1
+    """
+    from database import get_db_file
+
+    if not 0 < threshold <= 1:
+        raise ValueError(f"Ungültiger Schwellwert: {threshold}")
+    path = db_path or get_db_file()
+    if not path.is_file():
+        raise FileNotFoundError(str(path))
+
+    with closing(sqlite3.connect(str(path))) as conn:
+        customers = _load_customers(conn)
+
+    blocks: dict[str, list[int]] = {}
+    for index, customer in enumerate(customers):
+        for key in _blocking_keys(customer["_name"], customer["_address"]):
+            blocks.setdefault(key, []).append(index)
+
+    # Union-Find über alle Paare oberhalb des Schwellwerts
+    parent = list(range(len(customers)))
+
+    def find(i: int) -> int:
+        while parent[i] != i:
+            parent[i] = parent[parent[i]]
+            i = parent[i]
+        return i
+
+    compared: set[tuple[int, int]] = set()
+    pair_results: list[tuple[int, int, float, list[str]]] = []
+    for members in blocks.values():
+        for pos, i in enumerate(members):
+            for j in members[pos + 1 :]:
+                if (i, j) in compared:
+                    continue
+                compared.add((i, j))
+                score, reasons = _score_pair(customers[i], customers[j])
+                if score >= threshold:
+                    pair_results.append((i, j, score, reasons))
+                    parent[find(j)] = find(i)
+
+    grouped: dict[int, dict] = {}
+    for i, j, score, reasons in pair_results:
+        group = grouped.setdefault(find(i), {"score": 0.0, "reasons": set()})
+        group["score"] = max(group["score"], score)
+        group["reasons"].update(reasons)
+
+    clusters = []
+    for root, group in grouped.items():
+        members = [c for k, c in enumerate(customers) if find(k) == root]
+        clusters.append(
+            {
+                "customers": [
+                    {key: value for key, value in c.items() if not key.startswith("_")}
+                    for c in members
+                ],
+                "score": round(group["score"], 3),
+                "reasons": sorted(group["reasons"]),
+                "suggestion": _merge_suggestion(members),
+            }
+        )
+    clusters.sort(key=lambda c: (-c["score"], c["customers"][0]["id"]))
+
+    logger.info(
+        f"👥 Dubletten-Suche: {len(clusters)} Gruppe(n) bei {len(customers)} Kunden "
+        f"({len(compared)} Vergleiche)"
+    )
+    return {
+        "customer_count": len(customers),
+        "threshold": threshold,
+        "clusters": clusters,
+    }
@@ -0,0 +1,358 @@
+"""
+Datenbank-Wartung: Statistiken, WAL-Checkpoint, Journal-Modus und Pflege.
+
+- get_db_stats(): Zahlen für das Einstellungs-Panel "Datenbank", damit
+  Nutzer nachvollziehen können, woher die Dateigröße kommt (z.B.
+  gespeicherte PDFs oder freie Seiten, die erst ein VACUUM zurückgibt)
+- checkpoint_wal(): Inhalt der -wal Datei in die Datenbank übernehmen
+- set_journal_mode(): zwischen Rollback-Journal und WAL umschalten
+- rebuild_indexes() / analyze_database(): Indizes neu aufbauen und
+  Statistiken für den Query-Planer aktualisieren (nächtliche Wartung)
+- find_invoices_without_pdf(): Rechnungen, deren PDF fehlt
+- find_orphaned_pdfs() / delete_stored_pdfs(): gespeicherte PDFs, deren
+  Rechnung oder Sammelrechnung gelöscht wurde
+- is_write_in_progress(): läuft gerade eine Schreib-Transaktion? (Prüfung
+  vor dem Beenden)
+
+Im WAL-Modus stehen die letzten Änderungen bis zum Checkpoint nur in
+billino.db-wal. Wer nur billino.db kopiert, verliert sie – Backups laufen
+deshalb über die SQLite-Backup-API und werden als eigenständige Datei
+(Journal-Modus DELETE) geschrieben.
+"""
+
+import sqlite3
+from contextlib import closing
+from pathlib import Path
+from typing import Optional
+
+from utils.logger import logger
+
+CHECKPOINT_MODES = ("PASSIVE", "FULL", "RESTART", "TRUNCATE")
+JOURNAL_MODES = ("delete", "truncate", "persist", "wal")
+
+
+def _file_size(path: Path) -> int:
+    return path.stat().st_size if path.is_file() else 0
+
+
+def _resolve_db(db_path: Optional[Path]) -> Path:
+    from database import get_db_file
+
+    path = db_path or get_db_file()
+    if not path.is_file():
+        raise FileNotFoundError(str(path))
+    return path
+
+
+def get_db_stats(db_path: Optional[Path] = None) -> dict:
+    """
+    Sammle Statistiken zur Datenbankdatei.
+
+    Args:
+        db_path: Datenbank (standard: get_db_file())
+
+    Returns:
+        dict mit path, file_size_bytes, wal_size_bytes, page_size,
+        page_count, freelist_pages, freelist_bytes, journal_mode,
+        index_count, pdf_content_bytes und tables (name, row_count)
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+    """
+    path = _resolve_db(db_path)
+    with closing(sqlite3.connect(str(path))) as conn:
+        page_size = conn.execute("PRAGMA page_size").fetchone()[0]
+        page_count = conn.execute("PRAGMA page_count").fetchone()[0]
+        freelist_pages = conn.execute("PRAGMA freelist_count").fetchone()[0]
+        journal_mode = conn.execute("PRAGMA journal_mode").fetchone()[0]
+        index_count = conn.execute(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index'"
+        ).fetchone()[0]
+        table_names = [
+            row[0]
+            for row in conn.execute(
+                "SELECT name FROM sqlite_master "
+                "WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name"
+            )
+        ]
+        tables = []
+        for name in table_names:
+            row_count = conn.execute(f'SELECT COUNT(*) FROM "{name}"').fetchone()[0]
+            tables.append({"name": name, "row_count": row_count})
+        pdf_content_bytes = 0
+        if "stored_pdfs" in table_names:
+            pdf_content_bytes = conn.execute(
+                "SELECT COALESCE(SUM(LENGTH(content)), 0) FROM stored_pdfs"
+            ).fetchone()[0]
+
+    return {
+        "path": str(path),
+        "file_size_bytes": _file_size(path),
+        "wal_size_bytes": _file_size(path.with_name(f"{path.name}-wal")),
+        "page_size": page_size,
+        "page_count": page_count,
+        "freelist_pages": freelist_pages,
+        "freelist_bytes": freelist_pages * page_size,
+        "journal_mode": journal_mode,
+        "index_count": index_count,
+        "pdf_content_bytes": pdf_content_bytes,
+        "tables": tables,
+    }
+
+
+def checkpoint_wal(db_path: Optional[Path] = None, mode: str = "TRUNCATE") -> dict:
+    """
+    Übernimm den Inhalt der -wal Datei in die Datenbank.
+
+    Args:
+        db_path: Datenbank (standard: get_db_file())
+        mode: PASSIVE, FULL, RESTART oder TRUNCATE (leert die -wal Datei)
+
+    Returns:
+        dict mit mode, busy (Checkpoint durch Leser/Schreiber blockiert),
+        wal_frames, checkpointed_frames, wal_size_bytes (danach)
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+        ValueError: Unbekannter Modus
+    """
+    mode = mode.upper()
+    if mode not in CHECKPOINT_MODES:
+        raise ValueError(f"Unbekannter Checkpoint-Modus: {mode}")
+
+    path = _resolve_db(db_path)
+    with closing(sqlite3.connect(str(path))) as conn:
+        busy, wal_frames, checkpointed = conn.execute(
+            f"PRAGMA wal_checkpoint({mode})"
+        ).fetchone()
+
+    # Nicht im WAL-Modus liefert SQLite -1 für die Frame-Zahlen
+    result = {
+        "mode": mode,
+        "busy": bool(busy),
+        "wal_frames": max(wal_frames, 0),
+        "checkpointed_frames": max(checkpointed, 0),
+        "wal_size_bytes": _file_size(path.with_name(f"{path.name}-wal")),
+    }
+    logger.info(
+        f"🧹 WAL-Checkpoint ({mode}): {result['checkpointed_frames']}/"
+        f"{result['wal_frames']} Frames{' (blockiert)' if busy else ''}"
+    )
+    return result
+
+
+def is_write_in_progress(db_path: Optional[Path] = None) -> bool:
+    """
+    Prüfe, ob eine andere Verbindung gerade eine Schreib-Transaktion offen hat.
+
+    SQLite erlaubt nur einen Schreiber: BEGIN IMMEDIATE schlägt ohne
+    Wartezeit fehl, solange eine andere Verbindung schreibt. Die eigene
+    Transaktion wird sofort zurückgerollt und ändert nichts.
+
+    Args:
+        db_path: Datenbank (standard: get_db_file())
+
+    Returns:
+        True, wenn die Schreibsperre belegt ist
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+    """
+    path = _resolve_db(db_path)
+    with closing(sqlite3.connect(str(path), timeout=0, isolation_level=None)) as conn:
+        try:
+            conn.execute("BEGIN IMMEDIATE")
+        except sqlite3.OperationalError as e:
+            if "locked" in str(e) or "busy" in str(e):
+                return True
+            raise
+        conn.execute("ROLLBACK")
+    return False
+
+
+def set_journal_mode(mode: str, db_path: Optional[Path] = None) -> dict:
+    """
+    Stelle den Journal-Modus der Datenbank um.
+
+    Vor dem Wechsel werden gepoolte Verbindungen geschlossen und die -wal
+    Datei per Checkpoint übernommen, damit keine Änderungen verloren gehen.
+    Der WAL-Modus bleibt in der Datei gespeichert, die übrigen Modi gelten
+    als Standard (DELETE), sobald WAL verlassen wurde.
+
+    Args:
+        mode: delete, truncate, persist oder wal
+        db_path: Datenbank (standard: get_db_file())
+
+    Returns:
+        dict mit previous_mode und journal_mode
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+        ValueError: Unbekannter Modus
+        sqlite3.OperationalError: Datenbank gesperrt (andere Verbindung aktiv)
+    """
+    from database import dispose_engine
+
+    mode = mode.lower()
+    if mode not in JOURNAL_MODES:
+        raise ValueError(f"Unbekannter Journal-Modus: {mode}")
+
+    path = _resolve_db(db_path)
+    dispose_engine()
+    with closing(sqlite3.connect(str(path))) as conn:
+        previous = conn.execute("PRAGMA journal_mode").fetchone()[0]
+        if previous == "wal":
+            conn.execute("PRAGMA wal_checkpoint(TRUNCATE)")
+        current = conn.execute(f"PRAGMA journal_mode = {mode}").fetchone()[0]
+
+    if current != mode:
+        raise sqlite3.OperationalError(
+            f"Journal-Modus konnte nicht auf {mode} gesetzt werden (aktuell: {current})"
+        )
+    logger.info(f"🗄️ Journal-Modus: {previous} → {current}")
+    return {"previous_mode": previous, "journal_mode": current}
+
+
+def rebuild_indexes(db_path: Optional[Path] = None) -> dict:
+    """
+    Baue alle Indizes neu auf (REINDEX).
+
+    Args:
+        db_path: Datenbank (standard: get_db_file())
+
+    Returns:
+        dict mit index_count
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+        sqlite3.OperationalError: Datenbank gesperrt
+    """
+    path = _resolve_db(db_path)
+    with closing(sqlite3.connect(str(path))) as conn:
+        conn.execute("REINDEX")
+        conn.commit()
+        index_count = conn.execute(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index'"
+        ).fetchone()[0]
+
+    logger.info(f"🧹 Indizes neu aufgebaut: {index_count}")
+    return {"index_count": index_count}
+
+
+def analyze_database(db_path: Optional[Path] = None) -> dict:
+    """
+    Aktualisiere die Statistiken des Query-Planers (ANALYZE).
+
+    Args:
+        db_path: Datenbank (standard: get_db_file())
+
+    Returns:
+        dict mit analyzed_tables (Tabellen mit Statistik danach)
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+        sqlite3.OperationalError: Datenbank gesperrt
+    """
+    path = _resolve_db(db_path)
+    with closing(sqlite3.connect(str(path))) as conn:
+        conn.execute("ANALYZE")
+        conn.commit()
+        analyzed = conn.execute(
+            "SELECT COUNT(DISTINCT tbl) FROM sqlite_stat1"
+        ).fetchone()[0]
+
+    logger.info(f"🧹 ANALYZE ausgeführt: {analyzed} Tabellen")
+    return {"analyzed_tables": analyzed}
+
+
+def find_invoices_without_pdf(db_path: Optional[Path] = None) -> list[int]:
+    """
+    Finde Rechnungen ohne gespeichertes PDF.
+
+    Args:
+        db_path: Datenbank (standard: get_db_file())
+
+    Returns:
+        IDs der Rechnungen, aufsteigend
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+    """
+    path = _resolve_db(db_path)
+    with closing(sqlite3.connect(str(path))) as conn:
+        rows = conn.execute(
+            "SELECT i.id FROM invoice i "
+            "LEFT JOIN stored_pdfs p ON p.invoice_id = i.id "
+            "WHERE p.id IS NULL ORDER BY i.id"
+        ).fetchall()
+    return [row[0] for row in rows]
+
+
+def find_orphaned_pdfs(db_path: Optional[Path] = None) -> list[dict]:
+    """
+    Finde gespeicherte PDFs, deren Rechnung oder Sammelrechnung nicht mehr
+    existiert (Fremdschlüssel werden von SQLite nicht erzwungen, beim
+    Löschen einer Rechnung bleibt ihr PDF sonst liegen).
+
+    PDFs ohne Verknüpfung (z.B. A6-Sammeldrucke) gelten nicht als verwaist.
+
+    Args:
+        db_path: Datenbank (standard: get_db_file())
+
+    Returns:
+        Liste mit id, type, invoice_id, summary_invoice_id und size_bytes,
+        aufsteigend nach id
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+    """
+    path = _resolve_db(db_path)
+    with closing(sqlite3.connect(str(path))) as conn:
+        rows = conn.execute(
+            "SELECT p.id, p.type, p.invoice_id, p.summary_invoice_id, "
+            "LENGTH(p.content) FROM stored_pdfs p "
+            "LEFT JOIN invoice i ON i.id = p.invoice_id "
+            "LEFT JOIN summary_invoice s ON s.id = p.summary_invoice_id "
+            "WHERE (p.invoice_id IS NOT NULL AND i.id IS NULL) "
+            "OR (p.summary_invoice_id IS NOT NULL AND s.id IS NULL) "
+            "ORDER BY p.id"
+        ).fetchall()
+    return [
+        {
+            "id": row[0],
+            "type": row[1],
+            "invoice_id": row[2],
+            "summary_invoice_id": row[3],
+            "size_bytes": row[4] or 0,
+        }
+        for row in rows
+    ]
+
+
+def delete_stored_pdfs(pdf_ids: list[int], db_path: Optional[Path] = None) -> int:
+    """
+    Lösche gespeicherte PDFs.
+
+    Args:
+        pdf_ids: IDs in stored_pdfs
+        db_path: Datenbank (standard: get_db_file())
+
+    Returns:
+        Anzahl gelöschter PDFs
+
+    Raises:
+        FileNotFoundError: Datenbank existiert nicht
+        sqlite3.OperationalError: Datenbank gesperrt
+    """
+    if not pdf_ids:
+        return 0
+    path = _resolve_db(db_path)
+    placeholders = ", ".join("?" for _ in pdf_ids)
+    with closing(sqlite3.connect(str(path))) as conn:
+        deleted = conn.execute(
+            f"DELETE FROM stored_pdfs WHERE id IN ({placeholders})", pdf_ids
+        ).rowcount
+        conn.commit()
+
+    logger.info(f"🗑️ {deleted} verwaiste PDFs gelöscht")
+    return deleted
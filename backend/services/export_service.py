@@ -0,0 +1,122 @@
+"""
+Export-Dateien für den Download durch die Desktop-App.
+
+Große, vom Backend erzeugte Dateien (Jahresarchive, anonymisierte
+DB-Exporte, ...) werden unter DATA_DIR/exports/ abgelegt. Jede Datei
+bekommt eine Job-ID (Dateiname ohne Endung), über die sie per
+GET /exports/{job_id}/download gestreamt werden kann.
+
+Die Dateien sind nur für den Download gedacht; die nächtliche Wartung der
+Desktop-App löscht sie nach einigen Tagen (find_expired_exports()).
+"""
+
+import re
+import time
+import uuid
+from pathlib import Path
+from typing import Optional
+
+from database import get_data_dir
+
+_JOB_ID_PATTERN = re.compile(r"^[A-Za-z0-9_-]{1,64}$")
+
+
+def get_export_dir() -> Path:
+    """Gibt das Export-Verzeichnis zurück (respektiert DATA_DIR)."""
+    path = get_data_dir() / "exports"
+    path.mkdir(parents=True, exist_ok=True)
+    return path
+
+
+def is_valid_job_id(job_id: str) -> bool:
+    """Job-IDs sind reine Dateinamen – keine Pfadbestandteile erlaubt."""
+    return bool(_JOB_ID_PATTERN.match(job_id))
+
+
+def create_export_path(suffix: str, prefix: str = "export") -> tuple[str, Path]:
+    """
+    Reserviere einen Dateipfad für einen neuen Export.
+
+    Args:
+        suffix: Dateiendung inkl. Punkt (z.B. ".zip")
+        prefix: Sprechender Präfix der Job-ID
+
+    Returns:
+        (job_id, path)
+    """
+    job_id = f"{prefix}_{uuid.uuid4().hex[:12]}"
+    return job_id, get_export_dir() / f"{job_id}{suffix}"
+
+
+def find_export_file(job_id: str) -> Optional[Path]:
+    """Finde die Export-Datei zu einer Job-ID (None, falls unbekannt)."""
+    if not is_valid_job_id(job_id):
+        return None
+    for candidate in get_export_dir().iterdir():
+        if candidate.is_file() and candidate.stem == job_id:
+            return candidate
+    return None
+
+
+def list_exports() -> list[dict]:
+    """Liste alle vorhandenen Export-Dateien (neueste zuerst)."""
+    files = [p for p in get_export_dir().iterdir() if p.is_file()]
+    files.sort(key=lambda p: p.stat().st_mtime, reverse=True)
+    return [
+        {
+            "job_id": p.stem,
+            "filename": p.name,
+            "size_bytes": p.stat().st_size,
+            "created_at": p.stat().st_mtime,
+        }
+        for p in files
+    ]
+
+
+def find_expired_exports(max_age_days: int, now: Optional[float] = None) -> list[dict]:
+    """
+    Finde Export-Dateien, die älter als `max_age_days` Tage sind.
+
+    Args:
+        max_age_days: Höchstalter in Tagen (0 = alle)
+        now: Zeitpunkt als Unix-Zeit (standard: jetzt)
+
+    Returns:
+        Liste mit job_id, filename, size_bytes und modified_at, älteste
+        zuerst
+    """
+    cutoff = (now if now is not None else time.time()) - max_age_days * 86400
+    expired = [
+        p
+        for p in get_export_dir().iterdir()
+        if p.is_file() and p.stat().st_mtime <= cutoff
+    ]
+    expired.sort(key=lambda p: p.stat().st_mtime)
+    return [
+        {
+            "job_id": p.stem,
+            "filename": p.name,
+            "size_bytes": p.stat().st_size,
+            "modified_at": p.stat().st_mtime,
+        }
+        for p in expired
+    ]
+
+
+def delete_exports(filenames: list[str]) -> int:
+    """
+    Lösche Export-Dateien (Dateinamen im Export-Verzeichnis).
+
+    Returns:
+        Anzahl gelöschter Dateien
+    """
+    deleted = 0
+    export_dir = get_export_dir()
+    for filename in filenames:
+        path = export_dir / filename
+        # Nur Dateien direkt im Export-Verzeichnis
+        if path.parent != export_dir or not path.is_file():
+            continue
+        path.unlink()
+        deleted += 1
+    return deleted
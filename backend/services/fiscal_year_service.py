@@ -0,0 +1,285 @@
+"""
+Jahresabschluss: Auswertungen und Archiv für ein Geschäftsjahr.
+
+Die Desktop-App führt den Jahresabschluss als geführte Aktion aus
+(Backup → Jahresarchiv → USt-Übersicht → Nummernkreis-Prüfung). Dieses
+Modul liefert die Backend-Schritte dafür:
+- compute_vat_summary(): Netto/Steuer/Brutto je Steuersatz
+- create_year_archive(): ZIP mit Rechnungsliste (CSV), USt-Übersicht und PDFs
+- check_counter_rollover(): erste Rechnungsnummer des Folgejahres
+"""
+
+import base64
+import json
+import re
+import zipfile
+from datetime import date
+from pathlib import Path
+from typing import Optional
+
+from sqlmodel import Session, select
+
+from models import (
+    CsvDialect,
+    Customer,
+    Invoice,
+    InvoiceAttachment,
+    Profile,
+    StoredPDF,
+    SummaryInvoice,
+)
+from services.attachment_service import attachment_file
+from services.csv_export import write_csv
+from services.export_service import create_export_path
+from services.invoice_number_generator import (
+    DEFAULT_NUMBER_FORMAT,
+    _period_regex,
+    render_invoice_number,
+)
+
+
+def _invoices_of_year(session: Session, year: int) -> list[Invoice]:
+    """Alle Rechnungen mit Rechnungsdatum im Jahr (nach Nummer sortiert)."""
+    stmt = select(Invoice).where(Invoice.date.startswith(f"{year}-"))
+    return sorted(session.exec(stmt).all(), key=lambda inv: inv.number)
+
+
+def invoice_amounts(invoice: Invoice, profile: Profile) -> tuple[float, float, float]:
+    """
+    Netto, Steuer und Brutto einer Rechnung (Profil-Einstellungen als Fallback).
+
+    Returns:
+        (net, tax, gross) – ungerundet, damit Summen exakt bleiben
+    """
+    rate = invoice.tax_rate or profile.default_tax_rate
+    include_tax = (
+        invoice.include_tax if invoice.include_tax is not None else profile.include_tax
+    )
+
+    # Kleinunternehmer / keine Steuer (§19 UStG)
+    if not include_tax or not rate:
+        return invoice.total_amount, 0.0, invoice.total_amount
+
+    if invoice.is_gross_amount:
+        gross = invoice.total_amount
+        net = gross / (1 + rate)
+        return net, gross - net, gross
+
+    net = invoice.total_amount
+    return net, net * rate, net + net * rate
+
+
+def compute_vat_summary(
+    session: Session, year: int, small_business_only: bool = False
+) -> dict:
+    """
+    Umsatzsteuer-Übersicht eines Jahres, gruppiert nach Steuersatz.
+
+    Rechnungen ohne ausgewiesene Steuer (§19 UStG) erscheinen mit Satz 0.0.
+
+    Args:
+        small_business_only: Nur Rechnungen von Profilen ohne Steuerausweis
+            (Kleinunternehmer), z.B. für die Umsatzgrenzen nach §19 UStG
+
+    Returns:
+        dict mit year, invoice_count, total_net, total_tax, total_gross, rates
+    """
+    profiles = {p.id: p for p in session.exec(select(Profile)).all()}
+    rates: dict[float, dict] = {}
+
+    invoices = _invoices_of_year(session, year)
+    if small_business_only:
+        invoices = [inv for inv in invoices if not profiles[inv.profile_id].include_tax]
+    for invoice in invoices:
+        net, tax, gross = invoice_amounts(invoice, profiles[invoice.profile_id])
+        rate = invoice.tax_rate or profiles[invoice.profile_id].default_tax_rate
+        key = rate if tax else 0.0
+        entry = rates.setdefault(
+            key,
+            {"tax_rate": key, "invoice_count": 0, "net": 0.0, "tax": 0.0, "gross": 0.0},
+        )
+        entry["invoice_count"] += 1
+        entry["net"] += net
+        entry["tax"] += tax
+        entry["gross"] += gross
+
+    rows = sorted(rates.values(), key=lambda r: r["tax_rate"], reverse=True)
+    for row in rows:
+        for field in ("net", "tax", "gross"):
+            row[field] = round(row[field], 2)
+
+    return {
+        "year": year,
+        "invoice_count": len(invoices),
+        "total_net": round(sum(r["net"] for r in rows), 2),
+        "total_tax": round(sum(r["tax"] for r in rows), 2),
+        "total_gross": round(sum(r["gross"] for r in rows), 2),
+        "rates": rows,
+    }
+
+
+def pdf_filename(label: str) -> str:
+    """Dateiname aus Rechnungsnummer ("25 | 001" → "25_001.pdf")."""
+    return re.sub(r"[^A-Za-z0-9-]+", "_", label).strip("_") + ".pdf"
+
+
+def create_year_archive(
+    session: Session, year: int, dialect: Optional[CsvDialect] = None
+) -> dict:
+    """
+    Erzeuge das Jahresarchiv als ZIP unter DATA_DIR/exports/.
+
+    Inhalt:
+    - rechnungen_<Jahr>.csv: alle Rechnungen mit Beträgen (im CSV-Dialekt
+      `dialect`, Standard: Semikolon, Dezimalkomma, UTF-8 mit BOM)
+    - ust_<Jahr>.json: USt-Übersicht (compute_vat_summary)
+    - pdfs/: gespeicherte PDFs der Rechnungen und Sammelrechnungen des Jahres
+    - anhaenge/<Rechnungsnummer>/: Anhänge der Rechnungen
+
+    Returns:
+        dict mit job_id, filename, invoice_count, pdf_count, attachment_count,
+        size_bytes
+    """
+    profiles = {p.id: p for p in session.exec(select(Profile)).all()}
+    customers = {c.id: c for c in session.exec(select(Customer)).all()}
+    invoices = _invoices_of_year(session, year)
+
+    rows = []
+    for invoice in invoices:
+        profile = profiles[invoice.profile_id]
+        customer = customers.get(invoice.customer_id)
+        net, tax, gross = invoice_amounts(invoice, profile)
+        rows.append(
+            [
+                invoice.number,
+                invoice.date[:10],
+                customer.name if customer else "",
+                profile.name,
+                (invoice.tax_rate or profile.default_tax_rate) if tax else 0.0,
+                net,
+                tax,
+                gross,
+            ]
+        )
+    invoice_csv = write_csv(
+        [
+            "Nummer",
+            "Datum",
+            "Kunde",
+            "Profil",
+            "Steuersatz",
+            "Netto",
+            "Steuer",
+            "Brutto",
+        ],
+        rows,
+        dialect,
+    )
+
+    job_id, archive_path = create_export_path(".zip", prefix=f"fiscal_{year}")
+    pdf_count = 0
+    attachment_count = 0
+
+    with zipfile.ZipFile(archive_path, "w", compression=zipfile.ZIP_DEFLATED) as zf:
+        zf.writestr(f"rechnungen_{year}.csv", invoice_csv)
+        zf.writestr(
+            f"ust_{year}.json",
+            json.dumps(compute_vat_summary(session, year), indent=2),
+        )
+
+        numbers = {inv.id: inv.number for inv in invoices}
+        stmt = select(StoredPDF).where(StoredPDF.invoice_id.in_(numbers.keys()))
+        for pdf in session.exec(stmt).all():
+            name = pdf_filename(numbers[pdf.invoice_id])
+            zf.writestr(f"pdfs/rechnungen/{name}", base64.b64decode(pdf.content))
+            pdf_count += 1
+
+        summaries = session.exec(
+            select(SummaryInvoice).where(SummaryInvoice.date.startswith(f"{year}-"))
+        ).all()
+        ranges = {s.id: s.range_text for s in summaries}
+        stmt = select(StoredPDF).where(StoredPDF.summary_invoice_id.in_(ranges.keys()))
+        for pdf in session.exec(stmt).all():
+            name = pdf_filename(ranges[pdf.summary_invoice_id])
+            zf.writestr(f"pdfs/sammelrechnungen/{name}", base64.b64decode(pdf.content))
+            pdf_count += 1
+
+        stmt = select(InvoiceAttachment).where(
+            InvoiceAttachment.invoice_id.in_(numbers.keys())
+        )
+        names = set()
+        for attachment in session.exec(stmt).all():
+            path = attachment_file(attachment.sha256, attachment.filename)
+            if not path.is_file():
+                continue
+            folder = pdf_filename(numbers[attachment.invoice_id])[: -len(".pdf")]
+            name = f"anhaenge/{folder}/{Path(attachment.filename).name}"
+            if name in names:
+                # Gleicher Name, anderer Inhalt
+                name = f"anhaenge/{folder}/{attachment.sha256[:8]}_{Path(name).name}"
+            names.add(name)
+            zf.write(path, name)
+            attachment_count += 1
+
+    return {
+        "job_id": job_id,
+        "filename": archive_path.name,
+        "invoice_count": len(invoices),
+        "pdf_count": pdf_count,
+        "attachment_count": attachment_count,
+        "size_bytes": archive_path.stat().st_size,
+    }
+
+
+def check_counter_rollover(session: Session, year: int) -> dict:
+    """
+    Prüfe den Nummernkreis-Wechsel vom abgeschlossenen Jahr ins Folgejahr.
+
+    Der Zähler beginnt mit dem Jahreswechsel neu ("26 | 001"). Bereits
+    vergebene Nummern des Folgejahres werden berücksichtigt.
+
+    Returns:
+        dict mit year, last_number, last_counter, next_number,
+        next_year_started und warnings
+    """
+    numbers = session.exec(select(Invoice.number)).all()
+    warnings = []
+
+    def highest(period: date) -> tuple[int, str] | None:
+        """Höchster Zähler (und zugehörige Nummer) im Zeitraum."""
+        regex = _period_regex(DEFAULT_NUMBER_FORMAT, period)
+        matches = [
+            (int(m.group(1)), m.group(0)) for m in map(regex.match, numbers) if m
+        ]
+        return max(matches, default=None)
+
+    closed = date(year, 12, 31)
+    following = date(year + 1, 1, 1)
+    last = highest(closed)
+    started = highest(following)
+
+    if last is None:
+        warnings.append(f"Für {year} wurden keine Rechnungsnummern vergeben.")
+
+    closed_period = _period_regex(DEFAULT_NUMBER_FORMAT, closed)
+    misdated = [
+        inv.number
+        for inv in _invoices_of_year(session, year)
+        if not closed_period.match(inv.number)
+    ]
+    if misdated:
+        warnings.append(
+            f"{len(misdated)} Rechnung(en) von {year} tragen eine Nummer eines "
+            f"anderen Jahres: {', '.join(misdated[:5])}"
+        )
+
+    return {
+        "year": year,
+        "last_number": last[1] if last else None,
+        "last_counter": last[0] if last else None,
+        "next_number": render_invoice_number(
+            DEFAULT_NUMBER_FORMAT, following, started[0] + 1 if started else 1
+        ),
+        "next_year_started": started is not None,
+        "warnings": warnings,
+    }
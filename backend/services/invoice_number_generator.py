@@ -1,10 +1,18 @@
-from datetime import datetime
+import re
+from datetime import date, datetime
 from typing import Optional
 
 from sqlmodel import Session, func, select
 
 from models import Invoice
 
+# Current numbering scheme expressed as a pattern ("25 | 001")
+DEFAULT_NUMBER_FORMAT = "{YY} | {N:3}"
+MAX_NUMBER_LENGTH = 40
+
+_PLACEHOLDER = re.compile(r"\{([A-Z]+)(?::(\d+))?\}")
+_DATE_PLACEHOLDERS = {"YYYY": "%Y", "YY": "%y", "MM": "%m", "DD": "%d"}
+
 
 def generate_next_invoice_number(session: Session) -> str:
     """
@@ -84,3 +92,127 @@ def validate_invoice_number_format(number: str) -> bool:
     import re
 
     return bool(re.match(r"^\d{2} \| \d{3,}$", number))
+
+
+def _pattern_errors(pattern: str) -> list[str]:
+    """Syntax errors of an invoice-number pattern (empty list if valid)."""
+    errors = []
+    counters = 0
+    for match in _PLACEHOLDER.finditer(pattern):
+        name, width = match.group(1), match.group(2)
+        if name == "N":
+            counters += 1
+            if width is not None and not 1 <= int(width) <= 10:
+                errors.append("Zählerbreite muss zwischen 1 und 10 liegen ({N:3}).")
+        elif name not in _DATE_PLACEHOLDERS or width is not None:
+            errors.append(f"Unbekannter Platzhalter: {match.group(0)}")
+
+    if counters != 1:
+        errors.append("Das Muster muss genau einen Zähler {N} enthalten.")
+
+    literal = _PLACEHOLDER.sub("", pattern)
+    if "{" in literal or "}" in literal:
+        errors.append("Ungültige geschweifte Klammer im Muster.")
+    if any(not ch.isprintable() for ch in literal):
+        errors.append("Das Muster enthält Steuerzeichen.")
+    return errors
+
+
+def render_invoice_number(pattern: str, sample_date: date, counter: int) -> str:
+    """
+    Render an invoice number from a pattern.
+
+    Placeholders: {YYYY}, {YY}, {MM}, {DD} (from the date) and {N} / {N:w}
+    (counter, zero-padded to width w).
+
+    Raises:
+        ValueError: If the pattern is invalid
+    """
+    errors = _pattern_errors(pattern)
+    if errors:
+        raise ValueError(" ".join(errors))
+
+    def replace(match: re.Match) -> str:
+        name, width = match.group(1), match.group(2)
+        if name == "N":
+            return f"{counter:0{int(width or 1)}d}"
+        return sample_date.strftime(_DATE_PLACEHOLDERS[name])
+
+    return _PLACEHOLDER.sub(replace, pattern)
+
+
+def _period_regex(pattern: str, sample_date: date) -> re.Pattern:
+    """Regex matching all numbers of the sample date's period, capturing the counter."""
+    parts = []
+    last = 0
+    for match in _PLACEHOLDER.finditer(pattern):
+        parts.append(re.escape(pattern[last : match.start()]))
+        if match.group(1) == "N":
+            parts.append(r"(\d+)")
+        else:
+            date_part = sample_date.strftime(_DATE_PLACEHOLDERS[match.group(1)])
+            parts.append(re.escape(date_part))
+        last = match.end()
+    parts.append(re.escape(pattern[last:]))
+    return re.compile("^" + "".join(parts) + "$")
+
+
+def check_number_format(
+    session: Session, pattern: str, sample_date: date, counter: int
+) -> dict:
+    """
+    Render a sample number and validate the pattern against existing invoices.
+
+    Checks syntax, length, duplicates and continuity: within the sample
+    date's period (e.g. the year for "{YY}") the counter must continue
+    directly after the highest existing one – no gaps, no duplicates
+    (§ 14 UStG).
+
+    Returns:
+        dict with pattern, rendered, valid, errors, warnings, highest_counter
+    """
+    result = {
+        "pattern": pattern,
+        "rendered": None,
+        "valid": False,
+        "errors": _pattern_errors(pattern),
+        "warnings": [],
+        "highest_counter": None,
+    }
+    if result["errors"]:
+        return result
+
+    rendered = render_invoice_number(pattern, sample_date, counter)
+    result["rendered"] = rendered
+    errors, warnings = result["errors"], result["warnings"]
+
+    if len(rendered) > MAX_NUMBER_LENGTH:
+        errors.append(f"Die Nummer ist länger als {MAX_NUMBER_LENGTH} Zeichen.")
+
+    period = _period_regex(pattern, sample_date)
+    existing = session.exec(select(Invoice.number)).all()
+    counters = [int(m.group(1)) for m in map(period.match, existing) if m]
+    highest = max(counters, default=None)
+    result["highest_counter"] = highest
+
+    if rendered in existing:
+        errors.append(f"Die Nummer {rendered} ist bereits vergeben.")
+    elif highest is not None and counter <= highest:
+        errors.append(
+            f"Zähler {counter} liegt nicht über dem höchsten vorhandenen ({highest})."
+        )
+    elif highest is not None and counter > highest + 1:
+        errors.append(
+            f"Lücke in der Nummernfolge: Zähler {highest + 1} bis {counter - 1} fehlen."
+        )
+    elif highest is None and counter != 1:
+        warnings.append(
+            "Im Zeitraum gibt es noch keine Rechnung – der Zähler beginnt nicht bei 1."
+        )
+
+    if not any(p in pattern for p in ("{YYYY}", "{YY}")):
+        warnings.append("Ohne Jahr im Muster wird der Zähler nie zurückgesetzt.")
+
+    result["valid"] = not errors
+    return result
+
@@ -14,7 +14,11 @@ from models import (
     SummaryInvoiceRead,
 )
 
-from .pdf_data_structures import PDFInvoiceData, PDFSummaryInvoiceData
+from .pdf_data_structures import (
+    PDFCustomerStatementData,
+    PDFInvoiceData,
+    PDFSummaryInvoiceData,
+)
 
 
 class PDFDataService:
@@ -373,6 +377,107 @@ class PDFDataService:
             sender_tax_number=profile.tax_number,
         )
 
+    def get_customer_statement_pdf_data(
+        self,
+        customer_id: int,
+        period_start: date,
+        period_end: date,
+        profile_id: Optional[int] = None,
+    ) -> PDFCustomerStatementData:
+        """
+        Collect all invoices of a customer within a date range for a statement.
+
+        A statement is issued by one profile and only lists that profile's
+        invoices. Without `profile_id` the profile of the customer's most
+        recent invoice is used.
+
+        Args:
+            customer_id: ID of the customer
+            period_start: First day of the period (inclusive)
+            period_end: Last day of the period (inclusive)
+            profile_id: Optional issuing profile
+
+        Returns:
+            PDFCustomerStatementData object with all necessary information
+
+        Raises:
+            ValueError: If customer or profile is not found, or the customer
+                        has no invoices to take the profile from
+        """
+        customer = self.session.get(Customer, customer_id)
+        if not customer:
+            raise ValueError("Customer not found")
+
+        invoices = self.session.exec(
+            select(Invoice).where(Invoice.customer_id == customer_id)
+        ).all()
+
+        if profile_id is None:
+            if not invoices:
+                raise ValueError("Customer has no invoices")
+            latest = max(invoices, key=lambda inv: self._parse_date(inv.date))
+            profile_id = latest.profile_id
+
+        profile = self.session.get(Profile, profile_id)
+        if not profile:
+            raise ValueError("Profile not found")
+
+        entries = []
+        for invoice in invoices:
+            if invoice.profile_id != profile_id:
+                continue
+            invoice_date = self._parse_date(invoice.date)
+            if not period_start <= invoice_date <= period_end:
+                continue
+
+            total_net, total_tax, total_gross = self._calculate_amounts(
+                invoice.total_amount,
+                invoice.tax_rate or profile.default_tax_rate,
+                invoice.is_gross_amount,
+                (
+                    invoice.include_tax
+                    if invoice.include_tax is not None
+                    else profile.include_tax
+                ),
+            )
+            entries.append(
+                {
+                    "number": invoice.number,
+                    "date": invoice_date,
+                    "total_net": total_net,
+                    "total_tax": total_tax,
+                    "total_gross": total_gross,
+                }
+            )
+
+        entries.sort(key=lambda entry: (entry["date"], entry["number"]))
+
+        return PDFCustomerStatementData(
+            period_start=period_start,
+            period_end=period_end,
+            date=date.today(),
+            sender_name=profile.name,
+            sender_address=self._format_address(profile.address, profile.city),
+            customer_name=customer.name,
+            customer_address=self._format_address(customer.address, customer.city),
+            total_net=round(sum(entry["total_net"] for entry in entries), 2),
+            total_tax=round(sum(entry["total_tax"] for entry in entries), 2),
+            total_gross=round(sum(entry["total_gross"] for entry in entries), 2),
+            entries=entries,
+            sender_bank_data=profile.bank_data,
+            sender_tax_number=profile.tax_number,
+        )
+
+    def _parse_date(self, value) -> date:
+        """
+        Convert a stored invoice date ("YYYY-MM-DD" or ISO with time) to a date.
+        """
+        if not isinstance(value, str):
+            return value
+        if "T" in value:
+            return datetime.fromisoformat(value.replace("Z", "+00:00")).date()
+        return datetime.strptime(value, "%Y-%m-%d").date()
+
     def _format_address(
         self, address: Optional[str], city: Optional[str], name: Optional[str] = None
     ) -> str:
@@ -72,3 +72,38 @@ class PDFSummaryInvoiceData:
     # Optional fields (must come last)
     sender_bank_data: Optional[str] = None
     sender_tax_number: Optional[str] = None
+
+
+@dataclass
+class PDFCustomerStatementData:
+    """
+    Data structure for customer statement (Kontoauszug) PDF generation.
+    Lists all invoices of one customer within a date range.
+    """
+
+    # Statement period
+    period_start: date
+    period_end: date
+    date: date
+
+    # Sender (Profile) information
+    sender_name: str
+    sender_address: str
+
+    # Customer information
+    customer_name: str
+    customer_address: str
+
+    # Aggregated financial data
+    total_net: float
+    total_tax: float
+    total_gross: float
+
+    # Invoices in the period
+    entries: List[
+        dict
+    ]  # List of {"number": str, "date": date, "total_net": float, "total_tax": float, "total_gross": float}
+
+    # Optional fields (must come last)
+    sender_bank_data: Optional[str] = None
+    sender_tax_number: Optional[str] = None
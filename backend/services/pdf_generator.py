@@ -15,8 +15,12 @@ from reportlab.platypus import (
     TableStyle,
 )
 
-from .pdf_data_structures import PDFInvoiceData, PDFSummaryInvoiceData
-from .pdf_helpers import create_address_table
+from .pdf_data_structures import (
+    PDFCustomerStatementData,
+    PDFInvoiceData,
+    PDFSummaryInvoiceData,
+)
+from .pdf_helpers import create_address_table, create_logo_image
 
 
 class PDFGenerator:
@@ -144,6 +148,12 @@ class PDFGenerator:
         # Build PDF content
         story = []
 
+        # Company logo (optional, top right)
+        logo = create_logo_image(max_width=60 * mm, max_height=25 * mm)
+        if logo:
+            story.append(logo)
+            story.append(Spacer(1, 4 * mm))
+
         # Document header with elegant styling
         story.append(
             Paragraph(f"Rechnung {data.invoice_number}", self.styles["DocumentTitle"])
@@ -339,6 +349,12 @@ class PDFGenerator:
         # Build PDF content
         story = []
 
+        # Company logo (optional, top right)
+        logo = create_logo_image(max_width=60 * mm, max_height=25 * mm)
+        if logo:
+            story.append(logo)
+            story.append(Spacer(1, 4 * mm))
+
         # Document header with elegant styling
         story.append(
             Paragraph(f"Sammelrechnung {data.range_text}", self.styles["DocumentTitle"])
@@ -530,3 +546,206 @@ class PDFGenerator:
         buffer.close()
 
         return pdf_content
+
+    def generate_customer_statement_pdf(self, data: PDFCustomerStatementData) -> bytes:
+        """
+        Generate a customer statement (Kontoauszug) listing all invoices of a period.
+
+        Args:
+            data: PDFCustomerStatementData object with all necessary information
+
+        Returns:
+            PDF content as bytes
+        """
+        buffer = io.BytesIO()
+        doc = SimpleDocTemplate(
+            buffer,
+            pagesize=A4,
+            rightMargin=25 * mm,
+            leftMargin=25 * mm,
+            topMargin=20 * mm,
+            bottomMargin=25 * mm,
+        )
+
+        story = []
+
+        # Company logo (optional, top right)
+        logo = create_logo_image(max_width=60 * mm, max_height=25 * mm)
+        if logo:
+            story.append(logo)
+            story.append(Spacer(1, 4 * mm))
+
+        story.append(Paragraph("Kontoauszug", self.styles["DocumentTitle"]))
+        story.append(
+            HRFlowable(
+                width="100%",
+                thickness=0.5,
+                lineCap="round",
+                color=self.colors["accent"],
+                spaceAfter=8 * mm,
+            )
+        )
+
+        address_table = create_address_table(
+            sender_name=data.sender_name,
+            sender_address=data.sender_address,
+            customer_name=data.customer_name,
+            customer_address=data.customer_address,
+            style=self.styles["Address"],
+            col_widths=[9 * cm, 9 * cm],
+            row_heights=[35 * mm],
+            bottom_padding=8 * mm,
+        )
+        story.append(address_table)
+
+        # Statement metadata
+        story.append(Paragraph("<b>Details</b>", self.styles["SectionHeader"]))
+
+        meta_data = [
+            ["Datum:", data.date.strftime("%d.%m.%Y")],
+            [
+                "Zeitraum:",
+                f"{data.period_start.strftime('%d.%m.%Y')} – "
+                f"{data.period_end.strftime('%d.%m.%Y')}",
+            ],
+        ]
+        if data.sender_tax_number:
+            meta_data.append(["Steuernummer:", data.sender_tax_number])
+
+        meta_table = Table(meta_data, colWidths=[4 * cm, 8 * cm])
+        meta_table.setStyle(
+            TableStyle(
+                [
+                    ("FONTNAME", (0, 0), (0, -1), "Helvetica-Bold"),
+                    ("FONTSIZE", (0, 0), (-1, -1), 10),
+                    ("TEXTCOLOR", (0, 0), (0, -1), self.colors["secondary"]),
+                    ("TEXTCOLOR", (1, 0), (1, -1), self.colors["text"]),
+                    ("LEFTPADDING", (0, 0), (-1, -1), 0),
+                    ("RIGHTPADDING", (0, 0), (-1, -1), 0),
+                    ("TOPPADDING", (0, 0), (-1, -1), 2 * mm),
+                    ("BOTTOMPADDING", (0, 0), (-1, -1), 2 * mm),
+                ]
+            )
+        )
+        story.append(meta_table)
+        story.append(Spacer(1, 8 * mm))
+
+        story.append(Paragraph("<b>Rechnungen</b>", self.styles["SectionHeader"]))
+
+        if data.entries:
+            entry_rows = [["Datum", "Rechnungsnummer", "Netto", "USt.", "Brutto"]]
+            for entry in data.entries:
+                entry_rows.append(
+                    [
+                        entry["date"].strftime("%d.%m.%Y"),
+                        entry["number"],
+                        f"{entry['total_net']:.2f} €",
+                        f"{entry['total_tax']:.2f} €",
+                        f"{entry['total_gross']:.2f} €",
+                    ]
+                )
+
+            # repeatRows keeps the header on every page for long statements
+            entry_table = Table(
+                entry_rows,
+                colWidths=[2.5 * cm, 4.5 * cm, 3 * cm, 3 * cm, 3 * cm],
+                repeatRows=1,
+            )
+            entry_table.setStyle(
+                TableStyle(
+                    [
+                        # Header styling
+                        ("BACKGROUND", (0, 0), (-1, 0), self.colors["primary"]),
+                        ("TEXTCOLOR", (0, 0), (-1, 0), colors.white),
+                        ("FONTNAME", (0, 0), (-1, 0), "Helvetica-Bold"),
+                        ("FONTSIZE", (0, 0), (-1, 0), 10),
+                        ("ALIGN", (0, 0), (1, -1), "LEFT"),
+                        ("ALIGN", (2, 0), (-1, -1), "RIGHT"),
+                        # Data rows styling
+                        ("FONTNAME", (0, 1), (-1, -1), "Helvetica"),
+                        ("FONTSIZE", (0, 1), (-1, -1), 9),
+                        ("TEXTCOLOR", (0, 1), (-1, -1), self.colors["text"]),
+                        # Grid and borders
+                        ("GRID", (0, 0), (-1, -1), 0.5, self.colors["accent"]),
+                        ("LINEBELOW", (0, 0), (-1, 0), 1.5, self.colors["primary"]),
+                        # Padding
+                        ("LEFTPADDING", (0, 0), (-1, -1), 3 * mm),
+                        ("RIGHTPADDING", (0, 0), (-1, -1), 3 * mm),
+                        ("TOPPADDING", (0, 0), (-1, -1), 2 * mm),
+                        ("BOTTOMPADDING", (0, 0), (-1, -1), 2 * mm),
+                        ("VALIGN", (0, 0), (-1, -1), "MIDDLE"),
+                        (
+                            "ROWBACKGROUNDS",
+                            (0, 1),
+                            (-1, -1),
+                            [colors.white, colors.Color(0.95, 0.95, 0.95)],
+                        ),
+                    ]
+                )
+            )
+            story.append(entry_table)
+        else:
+            story.append(
+                Paragraph(
+                    "Im angegebenen Zeitraum wurden keine Rechnungen gestellt.",
+                    self.styles["InfoText"],
+                )
+            )
+        story.append(Spacer(1, 8 * mm))
+
+        # Totals of the period
+        story.append(Paragraph("<b>Summe</b>", self.styles["SectionHeader"]))
+
+        if data.total_tax > 0:
+            totals_data = [
+                ["Nettobetrag:", f"{data.total_net:.2f} €"],
+                ["Umsatzsteuer:", f"{data.total_tax:.2f} €"],
+                ["", ""],  # Spacer row
+                ["Gesamtbetrag:", f"{data.total_gross:.2f} €"],
+            ]
+        else:
+            # §19 UStG case - no tax
+            totals_data = [
+                ["Umsatzsteuer:", "keine (§19 UStG)"],
+                ["Gesamtbetrag:", f"{data.total_gross:.2f} €"],
+            ]
+
+        totals_table = Table(totals_data, colWidths=[10 * cm, 6 * cm])
+        totals_table.setStyle(
+            TableStyle(
+                [
+                    # Regular rows
+                    ("FONTNAME", (0, 0), (-1, -2), "Helvetica"),
+                    ("FONTSIZE", (0, 0), (-1, -2), 10),
+                    ("TEXTCOLOR", (0, 0), (0, -2), self.colors["secondary"]),
+                    ("TEXTCOLOR", (1, 0), (1, -2), self.colors["text"]),
+                    ("ALIGN", (1, 0), (1, -1), "RIGHT"),
+                    # Total row emphasis
+                    ("FONTNAME", (0, -1), (-1, -1), "Helvetica-Bold"),
+                    ("FONTSIZE", (0, -1), (-1, -1), 12),
+                    ("TEXTCOLOR", (0, -1), (-1, -1), self.colors["primary"]),
+                    ("LINEABOVE", (0, -1), (-1, -1), 1.5, self.colors["primary"]),
+                    ("TOPPADDING", (0, -1), (-1, -1), 4 * mm),
+                    # General padding
+                    ("LEFTPADDING", (0, 0), (-1, -1), 0),
+                    ("RIGHTPADDING", (0, 0), (-1, -1), 0),
+                    ("TOPPADDING", (0, 0), (-1, -2), 2 * mm),
+                    ("BOTTOMPADDING", (0, 0), (-1, -2), 2 * mm),
+                ]
+            )
+        )
+        story.append(totals_table)
+
+        if data.sender_bank_data:
+            story.append(Spacer(1, 10 * mm))
+            story.append(
+                Paragraph("<b>Zahlungsinformationen</b>", self.styles["SectionHeader"])
+            )
+            story.append(Paragraph(data.sender_bank_data, self.styles["InfoText"]))
+
+        doc.build(story)
+
+        pdf_content = buffer.getvalue()
+        buffer.close()
+
+        return pdf_content
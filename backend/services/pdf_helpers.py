@@ -1,8 +1,51 @@
 """Helper functions for PDF generation to reduce code duplication."""
 
+from pathlib import Path
+from typing import Optional
+
 from reportlab.lib.styles import ParagraphStyle
 from reportlab.lib.units import mm
-from reportlab.platypus import Paragraph, Table, TableStyle
+from reportlab.lib.utils import ImageReader
+from reportlab.platypus import Image, Paragraph, Table, TableStyle
+
+from database import get_data_dir
+from utils import logger
+
+
+def get_logo_path() -> Path:
+    """
+    Pfad des Firmenlogos (respektiert DATA_DIR).
+
+    Die Desktop-App legt das Logo dort bereits optimiert als PNG ab.
+    """
+    return get_data_dir() / "branding" / "logo.png"
+
+
+def create_logo_image(max_width: float, max_height: float) -> Optional[Image]:
+    """
+    Create the company logo flowable, scaled to fit the given box.
+
+    Args:
+        max_width: Maximum width in points
+        max_height: Maximum height in points
+
+    Returns:
+        Image flowable (right-aligned), or None if no usable logo exists
+    """
+    logo_path = get_logo_path()
+    if not logo_path.is_file():
+        return None
+
+    try:
+        width, height = ImageReader(str(logo_path)).getSize()
+    except Exception as e:
+        logger.warning(f"⚠️ Firmenlogo kann nicht gelesen werden ({logo_path}): {e}")
+        return None
+
+    scale = min(max_width / width, max_height / height, 1.0)
+    logo = Image(str(logo_path), width=width * scale, height=height * scale)
+    logo.hAlign = "RIGHT"
+    return logo
 
 
 def create_address_table(
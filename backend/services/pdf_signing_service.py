@@ -0,0 +1,117 @@
+"""
+Digitale Signatur (PAdES) für gespeicherte PDFs.
+
+Die Signatur wird als inkrementelles Update an das PDF angehängt; der
+ursprüngliche Inhalt bleibt Byte für Byte erhalten. Der Schlüssel kommt
+entweder aus einer PKCS#12-Datei (von der Desktop-App aus dem
+Schlüsselbund übergeben) oder von einem USB-Token über PKCS#11.
+
+pyHanko wird erst beim Signieren importiert, damit das Backend auch ohne
+die optionale Abhängigkeit startet.
+"""
+
+import base64
+import binascii
+import io
+from contextlib import contextmanager
+from typing import Iterator
+
+from models import PdfSigningRequest
+
+SIGNATURE_FIELD = "Billino-Signatur"
+
+
+class SigningUnavailableError(RuntimeError):
+    """pyHanko (bzw. python-pkcs11 für Tokens) ist nicht installiert."""
+
+
+class SigningError(ValueError):
+    """Zertifikat, Passwort/PIN oder PDF ungeeignet."""
+
+
+def is_signed(pdf_bytes: bytes) -> bool:
+    """Ob das PDF bereits eine Signatur enthält."""
+    try:
+        from pyhanko.pdf_utils.reader import PdfFileReader
+    except ImportError as e:
+        raise SigningUnavailableError("pyHanko ist nicht installiert") from e
+
+    return len(PdfFileReader(io.BytesIO(pdf_bytes)).embedded_signatures) > 0
+
+
+@contextmanager
+def _open_signer(request: PdfSigningRequest) -> Iterator[object]:
+    """Signer aus PKCS#12-Daten oder einer PKCS#11-Sitzung (wird danach geschlossen)."""
+    from pyhanko.sign import signers
+
+    if request.pkcs11 is not None:
+        try:
+            from pyhanko.sign import pkcs11
+        except ImportError as e:
+            raise SigningUnavailableError("python-pkcs11 ist nicht installiert") from e
+
+        token = request.pkcs11
+        try:
+            session = pkcs11.open_pkcs11_session(
+                token.module_path, token_label=token.token_label, user_pin=token.pin
+            )
+        except Exception as e:
+            raise SigningError(f"Token nicht verfügbar: {e}") from e
+        try:
+            yield pkcs11.PKCS11Signer(
+                session,
+                key_label=token.key_label,
+                cert_label=token.cert_label or token.key_label,
+            )
+        finally:
+            session.close()
+        return
+
+    if not request.pkcs12:
+        raise SigningError("Kein Zertifikat angegeben (pkcs12 oder pkcs11)")
+    try:
+        pkcs12_bytes = base64.b64decode(request.pkcs12, validate=True)
+    except binascii.Error as e:
+        raise SigningError("PKCS#12-Daten sind kein gültiges Base64") from e
+    passphrase = request.passphrase.encode() if request.passphrase else None
+    try:
+        signer = signers.SimpleSigner.load_pkcs12_data(
+            pkcs12_bytes, other_certs=None, passphrase=passphrase
+        )
+    except ValueError:
+        signer = None
+    if signer is None:
+        raise SigningError("PKCS#12-Datei oder Passwort ungültig")
+    yield signer
+
+
+def sign_pdf(pdf_bytes: bytes, request: PdfSigningRequest) -> bytes:
+    """
+    Signiere ein PDF nach PAdES (ETSI.CAdES.detached).
+
+    Raises:
+        SigningUnavailableError: pyHanko fehlt
+        SigningError: Zertifikat/PIN ungültig oder PDF bereits signiert
+    """
+    try:
+        from pyhanko.pdf_utils.incremental_writer import IncrementalPdfFileWriter
+        from pyhanko.sign import fields, signers
+    except ImportError as e:
+        raise SigningUnavailableError("pyHanko ist nicht installiert") from e
+
+    if is_signed(pdf_bytes):
+        raise SigningError("Das PDF ist bereits signiert")
+
+    metadata = signers.PdfSignatureMetadata(
+        field_name=SIGNATURE_FIELD,
+        subfilter=fields.SigSeedSubFilter.PADES,
+        reason=request.reason,
+        location=request.location,
+    )
+    with _open_signer(request) as signer:
+        writer = IncrementalPdfFileWriter(io.BytesIO(pdf_bytes))
+        try:
+            output = signers.sign_pdf(writer, metadata, signer=signer)
+        except Exception as e:
+            raise SigningError(f"Signieren fehlgeschlagen: {e}") from e
+    return output.getvalue()
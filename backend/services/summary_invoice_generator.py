@@ -1,5 +1,5 @@
 from datetime import datetime, timezone
-from typing import List
+from typing import List, Tuple
 
 from sqlalchemy import select
 from sqlalchemy.orm import Session
@@ -7,6 +7,7 @@ from sqlalchemy.orm import Session
 from models import (
     Invoice,
     Profile,
+    StoredPDF,
     SummaryInvoice,
     SummaryInvoiceCreate,
     SummaryInvoiceLink,
@@ -14,6 +15,46 @@ from models import (
 )
 
 
+def calculate_summary_totals(invoices: List[Invoice]) -> Tuple[float, float, float]:
+    """
+    Sum net, tax and gross of the given invoices, rounded to cents.
+
+    Depends on whether tax is included, the tax rate and is_gross_amount
+    of each invoice.
+    """
+    total_net = 0.0
+    total_tax = 0.0
+    total_gross = 0.0
+
+    for invoice in invoices:
+        rate = invoice.tax_rate or 0.0
+
+        # Fall 1️⃣: Kleinunternehmer / keine Steuer
+        if not invoice.include_tax or rate == 0:
+            net = invoice.total_amount
+            tax = 0.0
+            gross = invoice.total_amount
+
+        # Fall 2️⃣: Bruttobetrag angegeben (is_gross_amount=True)
+        elif invoice.is_gross_amount:
+            gross = invoice.total_amount
+            net = gross / (1 + rate)
+            tax = gross - net
+
+        # Fall 3️⃣: Nettobetrag angegeben (is_gross_amount=False)
+        else:
+            net = invoice.total_amount
+            tax = net * rate
+            gross = net + tax
+
+        total_net += net
+        total_tax += tax
+        total_gross += gross
+
+    # Runde alles sauber (z. B. auf 2 Nachkommastellen)
+    return round(total_net, 2), round(total_tax, 2), round(total_gross, 2)
+
+
 def create_summary_invoice(
     session: Session, summary: SummaryInvoiceCreate
 ) -> SummaryInvoiceRead:
@@ -66,40 +107,7 @@ def create_summary_invoice(
         )
         raise ValueError("No valid invoices found for the given IDs")
 
-    # Calculate amounts: total_net, total_tax, total_gross, depending on if tax is included, tax rate and is_gross_amount
-    total_net = 0.0
-    total_tax = 0.0
-    total_gross = 0.0
-
-    for invoice in invoices:
-        rate = invoice.tax_rate or 0.0
-
-        # Fall 1️⃣: Kleinunternehmer / keine Steuer
-        if not invoice.include_tax or rate == 0:
-            net = invoice.total_amount
-            tax = 0.0
-            gross = invoice.total_amount
-
-        # Fall 2️⃣: Bruttobetrag angegeben (is_gross_amount=True)
-        elif invoice.is_gross_amount:
-            gross = invoice.total_amount
-            net = gross / (1 + rate)
-            tax = gross - net
-
-        # Fall 3️⃣: Nettobetrag angegeben (is_gross_amount=False)
-        else:
-            net = invoice.total_amount
-            tax = net * rate
-            gross = net + tax
-
-        total_net += net
-        total_tax += tax
-        total_gross += gross
-
-    # Runde alles sauber (z. B. auf 2 Nachkommastellen)
-    total_net = round(total_net, 2)
-    total_tax = round(total_tax, 2)
-    total_gross = round(total_gross, 2)
+    total_net, total_tax, total_gross = calculate_summary_totals(invoices)
 
     # Range Text ist kleinste und größte Rechnungsnummer (25 | 0025 - 25 | 0040)
     # Jahr aus Number extrahieren
@@ -156,3 +164,80 @@ def create_summary_invoice(
     )
 
     return summary_invoice_response
+
+
+def recalculate_summary_totals(session: Session, confirm: bool = False) -> dict:
+    """
+    Recompute the stored totals of every summary invoice from its linked
+    invoices and report the ones that differ (support/maintenance action,
+    e.g. after an invoice amount was fixed directly in the database).
+
+    Only reports by default; with `confirm` the differing totals are
+    corrected. A summary invoice with a stored PDF has been issued with its
+    totals and is never changed, only reported.
+
+    Returns:
+        dict with dry_run, checked (summary invoices), discrepancies (id,
+        stored and calculated gross, has_pdf) and corrected (their IDs)
+    """
+    from utils import logger
+
+    summaries = session.execute(select(SummaryInvoice)).scalars().all()
+    with_pdf = set(
+        session.execute(
+            select(StoredPDF.summary_invoice_id).where(
+                StoredPDF.summary_invoice_id.isnot(None)
+            )
+        ).scalars()
+    )
+    discrepancies = []
+    corrected = []
+    for summary in summaries:
+        links = session.execute(
+            select(SummaryInvoiceLink).where(
+                SummaryInvoiceLink.summary_invoice_id == summary.id
+            )
+        ).scalars()
+        invoices = [
+            invoice
+            for invoice in (session.get(Invoice, link.invoice_id) for link in links)
+            if invoice is not None
+        ]
+        if not invoices:
+            continue
+
+        totals = calculate_summary_totals(invoices)
+        if totals == (summary.total_net, summary.total_tax, summary.total_gross):
+            continue
+        has_pdf = summary.id in with_pdf
+        discrepancies.append(
+            {
+                "id": summary.id,
+                "stored_gross": summary.total_gross,
+                "calculated_gross": totals[2],
+                "has_pdf": has_pdf,
+            }
+        )
+        if not confirm or has_pdf:
+            logger.warning(
+                f"🧮 Summenrechnung {summary.id}: Summen weichen ab "
+                f"({summary.total_gross} ≠ {totals[2]})"
+                + (", PDF vorhanden – nicht korrigiert" if has_pdf else "")
+            )
+            continue
+        logger.warning(
+            f"🧮 Summenrechnung {summary.id}: Summen korrigiert "
+            f"({summary.total_gross} → {totals[2]})"
+        )
+        summary.total_net, summary.total_tax, summary.total_gross = totals
+        session.add(summary)
+        corrected.append(summary.id)
+
+    if corrected:
+        session.commit()
+    return {
+        "dry_run": not confirm,
+        "checked": len(summaries),
+        "discrepancies": discrepancies,
+        "corrected": corrected,
+    }
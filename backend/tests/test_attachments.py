@@ -0,0 +1,124 @@
+import hashlib
+import zipfile
+
+import pytest
+from sqlmodel import Session, create_engine
+
+from database import init_db
+from models import Customer, Invoice, InvoiceAttachmentCreate, Profile
+from services.attachment_service import (
+    attachment_file,
+    delete_attachment,
+    list_attachments,
+    register_attachment,
+)
+from services.backup_service import BackupHandler
+from services.fiscal_year_service import create_year_archive
+
+
+@pytest.fixture
+def session(tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    engine = create_engine(
+        "sqlite:///:memory:", connect_args={"check_same_thread": False}
+    )
+    init_db(engine)
+    with Session(engine) as s:
+        yield s
+
+
+@pytest.fixture
+def invoices(session: Session):
+    profile = Profile(name="Salon", address="X", city="Y")
+    customer = Customer(name="Kunde Müller")
+    session.add_all([profile, customer])
+    session.commit()
+    items = [
+        Invoice(
+            number=number,
+            date="2025-03-01",
+            profile_id=profile.id,
+            customer_id=customer.id,
+            total_amount=100.0,
+        )
+        for number in ("25 | 001", "25 | 002")
+    ]
+    session.add_all(items)
+    session.commit()
+    return items
+
+
+def store(content: bytes, filename: str) -> InvoiceAttachmentCreate:
+    """Lege eine Datei so ab, wie es die Desktop-App tut."""
+    sha256 = hashlib.sha256(content).hexdigest()
+    path = attachment_file(sha256, filename)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_bytes(content)
+    return InvoiceAttachmentCreate(
+        filename=filename, sha256=sha256, size_bytes=len(content)
+    )
+
+
+def test_register_attachment_deduplicates(session, invoices):
+    data = store(b"Lieferschein", "Lieferschein.PDF")
+
+    first, created = register_attachment(session, invoices[0].id, data)
+    again, created_again = register_attachment(session, invoices[0].id, data)
+
+    assert created and not created_again
+    assert again.id == first.id
+    assert attachment_file(data.sha256, data.filename).name.endswith(".pdf")
+    assert [a.id for a in list_attachments(session, invoices[0].id)] == [first.id]
+
+
+def test_register_attachment_checks_file(session, invoices):
+    data = store(b"Vertrag", "vertrag.pdf")
+
+    with pytest.raises(LookupError):
+        register_attachment(session, 999, data)
+    with pytest.raises(ValueError):
+        register_attachment(
+            session, invoices[0].id, data.model_copy(update={"size_bytes": 1})
+        )
+    with pytest.raises(FileNotFoundError):
+        register_attachment(
+            session, invoices[0].id, data.model_copy(update={"sha256": "0" * 64})
+        )
+
+
+def test_delete_attachment_keeps_shared_file(session, invoices):
+    data = store(b"Gemeinsam", "gemeinsam.pdf")
+    first, _ = register_attachment(session, invoices[0].id, data)
+    second, _ = register_attachment(session, invoices[1].id, data)
+    path = attachment_file(data.sha256, data.filename)
+
+    assert delete_attachment(session, first) is False
+    assert path.exists()
+    assert delete_attachment(session, second) is True
+    assert not path.exists()
+
+
+def test_year_archive_contains_attachments(session, invoices, tmp_path):
+    register_attachment(session, invoices[0].id, store(b"eins", "beleg.pdf"))
+    register_attachment(session, invoices[0].id, store(b"zwei", "beleg.pdf"))
+
+    result = create_year_archive(session, 2025)
+
+    assert result["attachment_count"] == 2
+    with zipfile.ZipFile(tmp_path / "exports" / result["filename"]) as zf:
+        names = [n for n in zf.namelist() if n.startswith("anhaenge/25_001/")]
+        assert "anhaenge/25_001/beleg.pdf" in names
+        assert len(names) == 2
+
+
+def test_backup_attachments_copies_missing_files(session, invoices, tmp_path):
+    data = store(b"Sicherung", "quittung.jpg")
+    handler = BackupHandler(
+        backup_root=tmp_path / "backups", db_path=tmp_path / "billino.db"
+    )
+    handler.ATTACHMENTS_PATH = tmp_path / "attachments"
+
+    assert handler.backup_attachments() == 1
+    assert handler.backup_attachments() == 0
+    relative = attachment_file(data.sha256, data.filename).relative_to(tmp_path)
+    assert (tmp_path / "backups" / relative).exists()
@@ -61,6 +61,23 @@ class TestConfigurationLoading:
                 environment="development",
             )
 
+    def test_config_tuning_from_env(self, monkeypatch):
+        """Worker count and SQLite cache come from the shell's settings."""
+        monkeypatch.setenv("BACKEND_WORKERS", "3")
+        monkeypatch.setenv("SQLITE_CACHE_MB", "64")
+
+        config = BackendConfig.from_env()
+
+        assert config.workers == 3
+        assert config.sqlite_cache_mb == 64
+
+    def test_config_validates_workers(self):
+        """Config rejects worker counts outside 1-16."""
+        with pytest.raises(ValueError, match="Workers must be"):
+            BackendConfig(workers=0)
+        with pytest.raises(ValueError, match="SQLite cache"):
+            BackendConfig(sqlite_cache_mb=4096)
+
     def test_config_validates_backup_hour(self):
         """Config validates backup hour (0-23)."""
         with pytest.raises(ValueError, match="Backup hour"):
@@ -99,6 +116,23 @@ class TestStartupValidation:
         assert result["valid"] is True
         assert len(result["errors"]) == 0
 
+    def test_validate_startup_ignores_bound_port_with_workers(self):
+        """With several workers the supervisor holds the port already."""
+        import socket
+
+        with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as sock:
+            sock.bind(("127.0.0.1", 0))
+            sock.listen()
+            port = sock.getsockname()[1]
+            if port < 1024:
+                pytest.skip("OS assigned a privileged port")
+
+            single = validate_startup_conditions(BackendConfig(port=port))
+            multi = validate_startup_conditions(BackendConfig(port=port, workers=2))
+
+        assert single["valid"] is False
+        assert multi["valid"] is True
+
     def test_validate_startup_creates_log_directory(self, tmp_path, monkeypatch):
         """Validation creates log directory if it doesn't exist."""
         config = BackendConfig()
@@ -205,6 +239,25 @@ class TestDatabaseInitialization:
         assert len(tables) > 0
 
 
+    def test_sqlite_cache_size_from_env(self, tmp_path, monkeypatch):
+        """SQLITE_CACHE_MB sets PRAGMA cache_size on every connection."""
+        import database
+
+        monkeypatch.setenv("DATA_DIR", str(tmp_path))
+        monkeypatch.setenv("SQLITE_CACHE_MB", "16")
+        monkeypatch.setattr(database, "_engine", None)
+
+        engine = database.get_engine()
+        try:
+            with engine.connect() as conn:
+                cache_size = conn.exec_driver_sql("PRAGMA cache_size").scalar()
+        finally:
+            engine.dispose()
+            monkeypatch.setattr(database, "_engine", None)
+
+        assert cache_size == -16 * 1024
+
+
 class TestSignalHandlers:
     """Test signal handling for graceful shutdown."""
 
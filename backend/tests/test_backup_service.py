@@ -7,6 +7,7 @@ Testet:
 """
 
 import gc
+import shutil
 import sqlite3
 import tempfile
 import time
@@ -16,7 +17,32 @@ from pathlib import Path
 import pytest
 
 from services.backup_scheduler import BackupScheduler
-from services.backup_service import BackupHandler
+from services.backup_service import BackupHandler, inspect_backup, restore_backup
+from utils.paths import long_path, long_path_obj
+
+
+def _create_billino_db(path: Path, invoices: list[tuple], pdfs: list[tuple]) -> None:
+    """Erstelle eine minimale Billino-DB mit Rechnungen und gespeicherten PDFs."""
+    conn = sqlite3.connect(str(path))
+    conn.executescript(
+        """
+        CREATE TABLE invoice (id INTEGER PRIMARY KEY, number TEXT, date TEXT);
+        CREATE TABLE summary_invoice (id INTEGER PRIMARY KEY, range_text TEXT);
+        CREATE TABLE stored_pdfs (
+            id INTEGER PRIMARY KEY, type TEXT, content TEXT, created_at TEXT,
+            invoice_id INTEGER UNIQUE, summary_invoice_id INTEGER UNIQUE
+        );
+        PRAGMA user_version = 1;
+        """
+    )
+    conn.executemany("INSERT INTO invoice VALUES (?, ?, ?)", invoices)
+    conn.executemany(
+        "INSERT INTO stored_pdfs (type, content, created_at, invoice_id) "
+        "VALUES ('invoice', ?, ?, ?)",
+        pdfs,
+    )
+    conn.commit()
+    conn.close()
 
 
 class TestBackupHandler:
@@ -269,6 +295,121 @@ class TestBackupHandler:
 
         assert is_valid is False
 
+    def test_backup_with_path_longer_than_260_chars(self, temp_dirs):
+        """Test: Backups funktionieren in tief verschachtelten Ordnern (>260)."""
+        deep_dir = temp_dirs["tmpdir"].joinpath(*["ordner-mit-langem-namen"] * 12)
+        assert len(str(deep_dir)) > 260
+        long_path_obj(deep_dir).mkdir(parents=True)
+        db_file = deep_dir / "billino.db"
+        shutil.copy2(long_path(temp_dirs["db_file"]), long_path(db_file))
+
+        handler = BackupHandler(backup_root=deep_dir / "backups", db_path=db_file)
+        backup_path = handler.backup_database()
+
+        assert backup_path is not None
+        conn = sqlite3.connect(long_path(backup_path))
+        assert conn.execute("SELECT data FROM test").fetchone() == ("test data",)
+        conn.close()
+        assert len(handler.list_backups()) == 1
+
+    def test_inspect_backup(self, temp_dirs):
+        """Test: Backup-Inhalt wird gelesen, ohne es wiederherzustellen."""
+        backup_file = temp_dirs["tmpdir"] / "billino_2025-12-27.db"
+        _create_billino_db(
+            backup_file,
+            invoices=[(1, "25 | 001", "2025-01-02"), (2, "25 | 002", "2025-03-04T10")],
+            pdfs=[("x", "2025-01-02", 1)],
+        )
+
+        info = inspect_backup(backup_file)
+
+        assert info["schema_version"] == 1
+        assert info["compatible"] is True
+        assert info["invoice_count"] == 2
+        assert info["first_invoice_date"] == "2025-01-02"
+        assert info["last_invoice_date"] == "2025-03-04"
+        assert [pdf["filename"] for pdf in info["pdfs"]] == ["25_001.pdf"]
+
+    def test_inspect_backup_rejects_foreign_database(self, temp_dirs):
+        """Test: Datenbanken ohne Rechnungstabelle werden abgelehnt."""
+        with pytest.raises(ValueError):
+            inspect_backup(temp_dirs["db_file"])
+
+        with pytest.raises(FileNotFoundError):
+            inspect_backup(temp_dirs["tmpdir"] / "missing.db")
+
+
+class TestRestoreBackup:
+    """Tests für die (teilweise) Wiederherstellung von Backups."""
+
+    @pytest.fixture
+    def dbs(self, monkeypatch):
+        """Aktuelle DB (3 Rechnungen) und Backup (2 Rechnungen, ältere PDFs)."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            tmpdir = Path(tmpdir)
+            monkeypatch.setenv("DATA_DIR", str(tmpdir))
+            live = tmpdir / "billino.db"
+            backup = tmpdir / "backup.db"
+            _create_billino_db(
+                live,
+                invoices=[
+                    (1, "25 | 001", "2025-01-02"),
+                    (2, "25 | 002", "2025-02-02"),
+                    (3, "25 | 003", "2025-03-01"),
+                ],
+                pdfs=[("new-1", "2025-01-02 10:00:00", 1), ("new-3", "2025-03-01", 3)],
+            )
+            _create_billino_db(
+                backup,
+                invoices=[(1, "25 | 001", "2025-01-02"), (2, "25 | 002", "2025-02-02")],
+                pdfs=[("old-1", "2025-01-02 10:00:00", 1), ("old-2", "2025-02-03", 2)],
+            )
+            yield {"live": live, "backup": backup}
+            gc.collect()
+
+    @staticmethod
+    def _state(db_path: Path) -> tuple[list[int], dict[int, str]]:
+        conn = sqlite3.connect(str(db_path))
+        invoices = [row[0] for row in conn.execute("SELECT id FROM invoice")]
+        pdfs = dict(conn.execute("SELECT invoice_id, content FROM stored_pdfs"))
+        conn.close()
+        return invoices, pdfs
+
+    def test_restore_full(self, dbs):
+        """Test: Vollständiger Restore ersetzt Daten und PDFs, sichert vorher."""
+        result = restore_backup(dbs["backup"], "full", db_path=dbs["live"])
+
+        assert self._state(dbs["live"]) == ([1, 2], {1: "old-1", 2: "old-2"})
+        assert Path(result["safety_backup_path"]).exists()
+
+    def test_restore_database_only_keeps_current_pdfs(self, dbs):
+        """Test: Nur DB – aktuelle PDFs bleiben, sofern die Rechnung existiert."""
+        result = restore_backup(dbs["backup"], "database", db_path=dbs["live"])
+
+        assert self._state(dbs["live"]) == ([1, 2], {1: "new-1"})
+        assert result["skipped_pdfs"] == 1
+
+    def test_restore_pdfs_of_month(self, dbs):
+        """Test: Nur PDFs eines Monats werden aus dem Backup übernommen."""
+        result = restore_backup(
+            dbs["backup"], "pdfs", months=["2025-02"], db_path=dbs["live"]
+        )
+
+        assert self._state(dbs["live"]) == (
+            [1, 2, 3],
+            {1: "new-1", 2: "old-2", 3: "new-3"},
+        )
+        assert result["restored_pdfs"] == 1
+
+    def test_restore_rejects_invalid_options(self, dbs):
+        """Test: Monate nur für PDFs, gültiges Format, bekannter Scope."""
+        for scope, months in [("database", ["2025-01"]), ("pdfs", ["01/2025"])]:
+            with pytest.raises(ValueError):
+                restore_backup(dbs["backup"], scope, months, db_path=dbs["live"])
+
+        with pytest.raises(ValueError):
+            restore_backup(dbs["backup"], "everything", db_path=dbs["live"])
+
 
 class TestBackupScheduler:
     """Tests für BackupScheduler Klasse."""
@@ -277,6 +418,7 @@ class TestBackupScheduler:
         """Reset Scheduler vor jedem Test."""
         BackupScheduler._scheduler = None
         BackupScheduler._handler = None
+        BackupScheduler._backup_job = None
 
     def test_scheduler_initialization(self):
         """Test: Scheduler wird korrekt initialisiert."""
@@ -348,3 +490,23 @@ class TestBackupScheduler:
             assert "success" in result
             # Kann fehlschlag haben wegen Pfad-Unterschieden, aber nicht crashen
             assert isinstance(result, dict)
+
+    def test_scheduler_backup_job(self):
+        """Test: Hintergrund-Backup liefert Job und meldet das Ergebnis."""
+        BackupScheduler._handler = type(
+            "FakeHandler", (), {"backup_database": lambda self: Path("/tmp/b.db")}
+        )()
+
+        job = BackupScheduler.start_backup_job()
+        assert job["state"] == "running"
+
+        for _ in range(50):
+            status = BackupScheduler.get_backup_job(job["job_id"])
+            if status["state"] != "running":
+                break
+            time.sleep(0.02)
+
+        assert status["state"] == "done"
+        assert status["backup_path"] == str(Path("/tmp/b.db"))
+        assert status["finished"] is not None
+        assert BackupScheduler.get_backup_job("unknown") is None
@@ -0,0 +1,108 @@
+import sqlite3
+
+from fastapi.testclient import TestClient
+
+from main import app
+from services.customer_duplicates_service import (
+    find_duplicate_customers,
+    normalize_address,
+    normalize_name,
+)
+
+client = TestClient(app)
+
+
+def _create_db(path, customers, invoices=()):
+    conn = sqlite3.connect(str(path))
+    conn.executescript(
+        """
+        CREATE TABLE customer (
+            id INTEGER PRIMARY KEY, name TEXT, address TEXT, city TEXT, note TEXT
+        );
+        CREATE TABLE invoice (id INTEGER PRIMARY KEY, customer_id INTEGER);
+        """
+    )
+    conn.executemany(
+        "INSERT INTO customer (id, name, address, city, note) VALUES (?, ?, ?, ?, ?)",
+        customers,
+    )
+    conn.executemany("INSERT INTO invoice (customer_id) VALUES (?)", invoices)
+    conn.commit()
+    conn.close()
+
+
+def test_normalize_name_and_address():
+    assert normalize_name("Müller & Söhne GmbH") == "mueller soehne"
+    assert normalize_name("Bäckerei Schulz e.K.") == "baeckerei schulz"
+    assert normalize_name("GmbH") == "gmbh"
+    assert normalize_address("Hauptstraße 5a") == normalize_address("Hauptstr. 5 a")
+
+
+def test_find_duplicates_clusters_and_suggestion(tmp_path):
+    """Schreibvarianten werden gruppiert, Hauptkunde hat die meisten Rechnungen."""
+    db_file = tmp_path / "billino.db"
+    _create_db(
+        db_file,
+        [
+            (1, "Mueller", "Hauptstr. 5", "Berlin", None),
+            (2, "Müller GmbH", "Hauptstraße 5", "Berlin", "Blau"),
+            (3, "Schmidt", "Gartenweg 1", "Hamburg", None),
+            (4, "Hans Meier", None, None, None),
+            (5, "Meier Hans", "Ringstraße 2", "Köln", None),
+        ],
+        invoices=[(2,), (2,), (1,)],
+    )
+
+    result = find_duplicate_customers(db_file)
+
+    assert result["customer_count"] == 5
+    clusters = {
+        tuple(c["id"] for c in cluster["customers"]): cluster
+        for cluster in result["clusters"]
+    }
+    assert set(clusters) == {(1, 2), (4, 5)}
+
+    mueller = clusters[(1, 2)]
+    assert "same_address" in mueller["reasons"]
+    assert mueller["suggestion"]["primary_id"] == 2
+    assert mueller["suggestion"]["merge_ids"] == [1]
+    assert mueller["suggestion"]["fields"]["note"] == "Blau"
+    assert mueller["suggestion"]["conflicts"] == ["name", "address"]
+    assert mueller["customers"][1]["invoice_count"] == 2
+
+    meier = clusters[(4, 5)]
+    assert meier["suggestion"]["fields"]["address"] == "Ringstraße 2"
+
+
+def test_same_name_different_address_is_not_a_duplicate(tmp_path):
+    db_file = tmp_path / "billino.db"
+    _create_db(
+        db_file,
+        [
+            (1, "Thomas Weber", "Lindenallee 12", "München", None),
+            (2, "Thomas Weber", "Am Markt 3", "Dresden", None),
+        ],
+    )
+
+    assert find_duplicate_customers(db_file)["clusters"] == []
+
+
+def test_duplicates_route(tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    _create_db(
+        tmp_path / "billino.db",
+        [(1, "Anna Schulz", None, None, None), (2, "Anna Schultz", None, None, None)],
+    )
+
+    response = client.get("/customers/duplicates", params={"threshold": 0.9})
+
+    assert response.status_code == 200
+    assert [c["id"] for c in response.json()["clusters"][0]["customers"]] == [1, 2]
+
+
+def test_duplicates_route_without_database(tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+
+    response = client.get("/customers/duplicates")
+
+    assert response.status_code == 404
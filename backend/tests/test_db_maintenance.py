@@ -0,0 +1,270 @@
+import os
+import sqlite3
+import time
+
+from fastapi.testclient import TestClient
+
+from main import app
+from services.backup_service import BackupHandler
+from services.db_maintenance_service import (
+    analyze_database,
+    checkpoint_wal,
+    find_invoices_without_pdf,
+    find_orphaned_pdfs,
+    get_db_stats,
+    is_write_in_progress,
+    rebuild_indexes,
+    set_journal_mode,
+)
+
+client = TestClient(app)
+
+
+def _create_db(path):
+    conn = sqlite3.connect(str(path))
+    conn.executescript(
+        """
+        CREATE TABLE invoice (id INTEGER PRIMARY KEY, number TEXT);
+        CREATE INDEX ix_invoice_number ON invoice (number);
+        CREATE TABLE stored_pdfs (id INTEGER PRIMARY KEY, content TEXT);
+        INSERT INTO invoice (number) VALUES ('25 | 001'), ('25 | 002');
+        INSERT INTO stored_pdfs (content) VALUES ('JVBERi0xLjQ=');
+        """
+    )
+    conn.close()
+
+
+def test_get_db_stats(tmp_path):
+    """Zeilenzahlen, Indizes und PDF-Größe werden ermittelt."""
+    db_file = tmp_path / "billino.db"
+    _create_db(db_file)
+
+    stats = get_db_stats(db_file)
+
+    assert stats["tables"] == [
+        {"name": "invoice", "row_count": 2},
+        {"name": "stored_pdfs", "row_count": 1},
+    ]
+    assert stats["index_count"] == 1
+    assert stats["pdf_content_bytes"] == len("JVBERi0xLjQ=")
+    assert stats["file_size_bytes"] == stats["page_size"] * stats["page_count"]
+    assert stats["wal_size_bytes"] == 0
+
+
+def test_database_stats_route(tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    _create_db(tmp_path / "billino.db")
+
+    response = client.get("/database/stats")
+
+    assert response.status_code == 200
+    assert response.json()["tables"][0]["row_count"] == 2
+
+
+def test_database_stats_without_database(tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+
+    response = client.get("/database/stats")
+
+    assert response.status_code == 404
+
+
+def test_checkpoint_and_journal_mode(tmp_path):
+    """Checkpoint leert die -wal Datei, Umstellung auf DELETE entfernt sie."""
+    db_file = tmp_path / "billino.db"
+    _create_db(db_file)
+    conn = sqlite3.connect(str(db_file))
+    conn.execute("PRAGMA journal_mode = wal")
+    conn.execute("PRAGMA wal_autocheckpoint = 0")
+    conn.execute("INSERT INTO invoice (number) VALUES ('25 | 003')")
+    conn.commit()
+    conn.close()
+
+    assert get_db_stats(db_file)["journal_mode"] == "wal"
+    result = checkpoint_wal(db_file)
+    assert result["busy"] is False
+    assert result["wal_size_bytes"] == 0
+
+    assert set_journal_mode("delete", db_file) == {
+        "previous_mode": "wal",
+        "journal_mode": "delete",
+    }
+    assert not (tmp_path / "billino.db-wal").exists()
+
+
+def test_backup_includes_wal_changes(tmp_path):
+    """Backups enthalten Änderungen aus der -wal Datei und sind eigenständig."""
+    db_file = tmp_path / "billino.db"
+    _create_db(db_file)
+    writer = sqlite3.connect(str(db_file))
+    writer.execute("PRAGMA journal_mode = wal")
+    writer.execute("PRAGMA wal_autocheckpoint = 0")
+    writer.execute("INSERT INTO invoice (number) VALUES ('25 | 003')")
+    writer.commit()
+
+    handler = BackupHandler(backup_root=tmp_path / "backups", db_path=db_file)
+    backup = handler.backup_database()
+    writer.close()
+
+    conn = sqlite3.connect(str(backup))
+    assert conn.execute("PRAGMA journal_mode").fetchone()[0] == "delete"
+    assert conn.execute("SELECT COUNT(*) FROM invoice").fetchone()[0] == 3
+    conn.close()
+
+
+def test_journal_mode_route_rejects_unknown_mode(tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    _create_db(tmp_path / "billino.db")
+
+    response = client.put("/database/journal-mode", json={"mode": "memory"})
+
+    assert response.status_code == 400
+
+
+def test_reindex_and_analyze(tmp_path):
+    """REINDEX und ANALYZE laufen durch, ANALYZE legt Statistiken an."""
+    db_file = tmp_path / "billino.db"
+    _create_db(db_file)
+
+    assert rebuild_indexes(db_file) == {"index_count": 1}
+    assert analyze_database(db_file) == {"analyzed_tables": 2}
+
+
+def test_find_invoices_without_pdf(tmp_path):
+    db_file = tmp_path / "billino.db"
+    conn = sqlite3.connect(str(db_file))
+    conn.executescript(
+        """
+        CREATE TABLE invoice (id INTEGER PRIMARY KEY, number TEXT);
+        CREATE TABLE stored_pdfs (id INTEGER PRIMARY KEY, invoice_id INTEGER);
+        INSERT INTO invoice (number) VALUES ('25 | 001'), ('25 | 002'), ('25 | 003');
+        INSERT INTO stored_pdfs (invoice_id) VALUES (2);
+        """
+    )
+    conn.close()
+
+    assert find_invoices_without_pdf(db_file) == [1, 3]
+
+
+def test_write_in_progress_detects_open_transaction(tmp_path):
+    """Eine offene Schreib-Transaktion einer anderen Verbindung wird erkannt."""
+    db_file = tmp_path / "billino.db"
+    _create_db(db_file)
+    assert is_write_in_progress(db_file) is False
+
+    writer = sqlite3.connect(str(db_file))
+    writer.execute("INSERT INTO invoice (number) VALUES ('25 | 003')")
+    assert is_write_in_progress(db_file) is True
+
+    writer.commit()
+    writer.close()
+    assert is_write_in_progress(db_file) is False
+
+
+def _create_db_with_orphans(path):
+    conn = sqlite3.connect(str(path))
+    conn.executescript(
+        """
+        CREATE TABLE invoice (id INTEGER PRIMARY KEY, number TEXT);
+        CREATE TABLE summary_invoice (id INTEGER PRIMARY KEY);
+        CREATE TABLE stored_pdfs (
+            id INTEGER PRIMARY KEY, type TEXT, content TEXT,
+            invoice_id INTEGER, summary_invoice_id INTEGER
+        );
+        INSERT INTO invoice (id, number) VALUES (1, '25 | 001');
+        INSERT INTO summary_invoice (id) VALUES (1);
+        INSERT INTO stored_pdfs (type, content, invoice_id, summary_invoice_id) VALUES
+            ('invoice', 'AAAA', 1, NULL),
+            ('invoice', 'BBBBBBBB', 2, NULL),
+            ('summary_invoice', 'CC', NULL, 1),
+            ('summary_invoice', 'DDD', NULL, 5),
+            ('a6_invoices', 'EE', NULL, NULL);
+        """
+    )
+    conn.close()
+
+
+def test_find_orphaned_pdfs(tmp_path):
+    """Nur PDFs gelöschter (Sammel-)Rechnungen gelten als verwaist."""
+    db_file = tmp_path / "billino.db"
+    _create_db_with_orphans(db_file)
+
+    orphans = find_orphaned_pdfs(db_file)
+
+    assert [entry["id"] for entry in orphans] == [2, 4]
+    assert orphans[0] == {
+        "id": 2,
+        "type": "invoice",
+        "invoice_id": 2,
+        "summary_invoice_id": None,
+        "size_bytes": 8,
+    }
+
+
+def test_cleanup_route_reports_before_deleting(tmp_path, monkeypatch):
+    """Der Probelauf löscht nichts; erst dry_run=false räumt auf."""
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    _create_db_with_orphans(tmp_path / "billino.db")
+    export_dir = tmp_path / "exports"
+    export_dir.mkdir()
+    old_export = export_dir / "export_old.zip"
+    old_export.write_bytes(b"1234")
+    os.utime(old_export, (time.time() - 10 * 86400,) * 2)
+    (export_dir / "export_new.zip").write_bytes(b"56")
+
+    default = client.post("/database/cleanup", json={"export_max_age_days": 7}).json()
+    assert default["orphaned_pdfs"] == []
+
+    report = client.post(
+        "/database/cleanup", json={"export_max_age_days": 7, "orphaned_pdfs": True}
+    ).json()
+
+    assert report["dry_run"] is True
+    assert [entry["filename"] for entry in report["exports"]] == ["export_old.zip"]
+    assert [entry["id"] for entry in report["orphaned_pdfs"]] == [2, 4]
+    assert report["freed_bytes"] == 4 + 8 + 3
+    assert old_export.exists()
+    assert len(find_orphaned_pdfs(tmp_path / "billino.db")) == 2
+
+    response = client.post(
+        "/database/cleanup",
+        json={"export_max_age_days": 7, "orphaned_pdfs": True, "dry_run": False},
+    )
+
+    assert response.status_code == 200
+    assert not old_export.exists()
+    assert (export_dir / "export_new.zip").exists()
+    assert find_orphaned_pdfs(tmp_path / "billino.db") == []
+
+
+def test_cleanup_route_deletes_only_listed_entries(tmp_path, monkeypatch):
+    """Mit Dateinamen/IDs aus dem Probelauf wird nur genau das gelöscht."""
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    _create_db_with_orphans(tmp_path / "billino.db")
+    export_dir = tmp_path / "exports"
+    export_dir.mkdir()
+    for name in ("export_a.zip", "export_b.zip"):
+        (export_dir / name).write_bytes(b"1234")
+        os.utime(export_dir / name, (time.time() - 10 * 86400,) * 2)
+    (export_dir / "export_new.zip").write_bytes(b"56")
+
+    response = client.post(
+        "/database/cleanup",
+        json={
+            "export_max_age_days": 7,
+            "orphaned_pdfs": True,
+            "dry_run": False,
+            # export_new.zip ist (noch) nicht abgelaufen, ID 1 nicht verwaist
+            "export_filenames": ["export_a.zip", "export_new.zip"],
+            "pdf_ids": [1, 4],
+        },
+    )
+
+    assert response.status_code == 200
+    report = response.json()
+    assert [entry["filename"] for entry in report["exports"]] == ["export_a.zip"]
+    assert [entry["id"] for entry in report["orphaned_pdfs"]] == [4]
+    assert not (export_dir / "export_a.zip").exists()
+    assert (export_dir / "export_b.zip").exists()
+    assert (export_dir / "export_new.zip").exists()
+    assert [entry["id"] for entry in find_orphaned_pdfs(tmp_path / "billino.db")] == [2]
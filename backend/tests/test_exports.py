@@ -0,0 +1,126 @@
+import sqlite3
+
+from fastapi.testclient import TestClient
+
+from main import app
+from services.anonymize_service import anonymize_database, scramble_text
+
+client = TestClient(app)
+
+
+def test_download_export_streams_file(tmp_path, monkeypatch):
+    """Export-Dateien werden über ihre Job-ID ausgeliefert."""
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    export_dir = tmp_path / "exports"
+    export_dir.mkdir()
+    content = b"x" * 200_000
+    (export_dir / "export_abc123.zip").write_bytes(content)
+
+    response = client.get("/exports/export_abc123/download")
+
+    assert response.status_code == 200
+    assert response.content == content
+    assert response.headers["content-length"] == str(len(content))
+
+
+def test_download_export_unknown_job(tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+
+    response = client.get("/exports/does_not_exist/download")
+
+    assert response.status_code == 404
+
+
+def test_download_export_rejects_invalid_job_id(tmp_path, monkeypatch):
+    """Job-IDs mit Pfadbestandteilen werden abgelehnt."""
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+
+    response = client.get("/exports/..%2Fbillino/download")
+
+    assert response.status_code in (400, 404)
+
+
+def test_list_exports(tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    export_dir = tmp_path / "exports"
+    export_dir.mkdir()
+    (export_dir / "export_one.zip").write_bytes(b"1234")
+
+    response = client.get("/exports/")
+
+    assert response.status_code == 200
+    assert response.json()[0]["job_id"] == "export_one"
+    assert response.json()[0]["size_bytes"] == 4
+
+
+def test_scramble_text_keeps_shape_and_is_deterministic():
+    """Verwürfelte Texte behalten Länge, Zeichenklassen und Umlaute."""
+    key = b"k" * 32
+    original = "Müller GmbH, DE89 3704"
+
+    scrambled = scramble_text(original, key)
+
+    assert scrambled != original
+    assert scrambled == scramble_text(original, key)
+    assert len(scrambled) == len(original)
+    assert scrambled[1] in "äöüß"
+    assert scrambled[6] == " " and scrambled[11] == ","
+    assert scrambled[13:15].isupper() and scrambled[15:17].isdigit()
+    assert scramble_text(None, key) is None
+
+
+def test_anonymize_database(tmp_path):
+    """Personendaten werden ersetzt, Beträge skaliert, PDFs entfernt."""
+    db_file = tmp_path / "copy.db"
+    conn = sqlite3.connect(str(db_file))
+    conn.executescript(
+        """
+        CREATE TABLE customer (id INTEGER PRIMARY KEY, name TEXT, address TEXT,
+            city TEXT, note TEXT);
+        CREATE TABLE invoice (id INTEGER PRIMARY KEY, number TEXT,
+            total_amount FLOAT);
+        CREATE TABLE stored_pdfs (id INTEGER PRIMARY KEY, content TEXT);
+        INSERT INTO customer VALUES (1, 'Erika Mustermann', 'Hauptstr. 1',
+            '12345 Berlin', NULL);
+        INSERT INTO customer VALUES (2, 'Erika Mustermann', NULL, NULL, NULL);
+        INSERT INTO invoice VALUES (1, '25 | 001', 100.0);
+        INSERT INTO invoice VALUES (2, '25 | 002', 50.0);
+        INSERT INTO stored_pdfs VALUES (1, 'JVBERi0=');
+        """
+    )
+    conn.close()
+
+    stats = anonymize_database(db_file, key=b"k" * 32)
+
+    conn = sqlite3.connect(str(db_file))
+    names = [row[0] for row in conn.execute("SELECT name FROM customer")]
+    amounts = [row[0] for row in conn.execute("SELECT total_amount FROM invoice")]
+    numbers = [row[0] for row in conn.execute("SELECT number FROM invoice")]
+    pdf_count = conn.execute("SELECT COUNT(*) FROM stored_pdfs").fetchone()[0]
+    conn.close()
+
+    assert "Erika Mustermann" not in names
+    assert names[0] == names[1]
+    assert amounts != [100.0, 50.0]
+    assert abs(amounts[0] - 2 * amounts[1]) <= 0.01
+    assert numbers == ["25 | 001", "25 | 002"]
+    assert pdf_count == 0
+    assert stats["removed_pdfs"] == 1
+
+
+def test_create_anonymized_db_export(tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    conn = sqlite3.connect(str(tmp_path / "billino.db"))
+    conn.execute("CREATE TABLE customer (id INTEGER PRIMARY KEY, name TEXT)")
+    conn.execute("INSERT INTO customer VALUES (1, 'Erika Mustermann')")
+    conn.commit()
+    conn.close()
+
+    response = client.post("/exports/anonymized-db")
+
+    assert response.status_code == 201
+    job_id = response.json()["job_id"]
+    assert job_id.startswith("anonymized_")
+    download = client.get(f"/exports/{job_id}/download")
+    assert download.status_code == 200
+    assert b"Erika Mustermann" not in download.content
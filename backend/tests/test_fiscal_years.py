@@ -0,0 +1,179 @@
+import base64
+import codecs
+import zipfile
+
+import pytest
+from fastapi.testclient import TestClient
+from sqlmodel import Session, create_engine
+
+from database import init_db
+from main import app
+from models import CsvDialect, Customer, Invoice, Profile, StoredPDF
+from services.fiscal_year_service import (
+    check_counter_rollover,
+    compute_vat_summary,
+    create_year_archive,
+)
+
+client = TestClient(app)
+
+
+@pytest.fixture
+def session():
+    engine = create_engine(
+        "sqlite:///:memory:", connect_args={"check_same_thread": False}
+    )
+    init_db(engine)
+    with Session(engine) as s:
+        yield s
+
+
+@pytest.fixture
+def invoices(session: Session):
+    """Drei Rechnungen 2025 (19 %, 7 %, netto) und eine aus 2026."""
+    profile = Profile(
+        name="Salon", address="X", city="Y", include_tax=True, default_tax_rate=0.19
+    )
+    customer = Customer(name="Kunde Müller")
+    session.add_all([profile, customer])
+    session.commit()
+
+    def invoice(number, date, total, **kwargs):
+        return Invoice(
+            number=number,
+            date=date,
+            profile_id=profile.id,
+            customer_id=customer.id,
+            total_amount=total,
+            **kwargs,
+        )
+
+    items = [
+        invoice("25 | 001", "2025-01-10", 119.0, is_gross_amount=True),
+        invoice("25 | 002", "2025-06-01", 107.0, is_gross_amount=True, tax_rate=0.07),
+        invoice("25 | 003", "2025-12-30", 100.0, is_gross_amount=False),
+        invoice("26 | 001", "2026-01-02", 50.0, include_tax=False),
+    ]
+    session.add_all(items)
+    session.commit()
+    return items
+
+
+def test_vat_summary_groups_by_rate(session, invoices):
+    summary = compute_vat_summary(session, 2025)
+
+    assert summary["invoice_count"] == 3
+    assert [r["tax_rate"] for r in summary["rates"]] == [0.19, 0.07]
+    assert summary["rates"][0] == {
+        "tax_rate": 0.19,
+        "invoice_count": 2,
+        "net": 200.0,
+        "tax": 38.0,
+        "gross": 238.0,
+    }
+    assert summary["total_tax"] == pytest.approx(45.0)
+    assert summary["total_gross"] == pytest.approx(345.0)
+
+
+def test_vat_summary_without_tax(session, invoices):
+    """§19-Rechnungen werden unter Satz 0.0 geführt."""
+    summary = compute_vat_summary(session, 2026)
+
+    assert summary["rates"] == [
+        {"tax_rate": 0.0, "invoice_count": 1, "net": 50.0, "tax": 0.0, "gross": 50.0}
+    ]
+
+
+def test_vat_summary_small_business_only(session, invoices):
+    """Nur Rechnungen von Profilen ohne Steuerausweis (§19 UStG)."""
+    small = Profile(name="Nebenjob", address="X", city="Y", include_tax=False)
+    session.add(small)
+    session.commit()
+    session.add(
+        Invoice(
+            number="25 | 004",
+            date="2025-03-01",
+            profile_id=small.id,
+            customer_id=invoices[0].customer_id,
+            total_amount=500.0,
+        )
+    )
+    session.commit()
+
+    summary = compute_vat_summary(session, 2025, small_business_only=True)
+
+    assert summary["invoice_count"] == 1
+    assert summary["total_net"] == 500.0
+    assert compute_vat_summary(session, 2025)["invoice_count"] == 4
+
+
+def test_year_archive_contains_csv_and_pdfs(session, invoices, tmp_path, monkeypatch):
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    session.add(
+        StoredPDF(
+            type="invoice",
+            content=base64.b64encode(b"%PDF-1.4 test").decode(),
+            invoice_id=invoices[0].id,
+        )
+    )
+    session.commit()
+
+    result = create_year_archive(session, 2025)
+
+    assert result["invoice_count"] == 3
+    assert result["pdf_count"] == 1
+    archive = tmp_path / "exports" / result["filename"]
+    with zipfile.ZipFile(archive) as zf:
+        csv_text = zf.read("rechnungen_2025.csv").decode("utf-8-sig")
+        assert "25 | 003" in csv_text and "26 | 001" not in csv_text
+        assert "Kunde Müller" in csv_text
+        assert zf.read("pdfs/rechnungen/25_001.pdf") == b"%PDF-1.4 test"
+        assert "ust_2025.json" in zf.namelist()
+
+
+def test_year_archive_csv_dialect(session, invoices, tmp_path, monkeypatch):
+    """Standard ist deutsches Excel; der Dialekt lässt sich pro Export ändern."""
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+
+    def read_csv(result):
+        with zipfile.ZipFile(tmp_path / "exports" / result["filename"]) as zf:
+            return zf.read("rechnungen_2025.csv")
+
+    german = read_csv(create_year_archive(session, 2025))
+    assert german.startswith(codecs.BOM_UTF8)
+    assert (
+        "25 | 001;2025-01-10;Kunde Müller;Salon;0,19;100,00;19,00;119,00"
+        in german.decode("utf-8-sig")
+    )
+
+    dialect = CsvDialect(separator=",", decimal_mark=".", encoding="cp1252")
+    english = read_csv(create_year_archive(session, 2025, dialect))
+    assert not english.startswith(codecs.BOM_UTF8)
+    assert (
+        "25 | 001,2025-01-10,Kunde Müller,Salon,0.19,100.00,19.00,119.00"
+        in english.decode("cp1252")
+    )
+
+
+def test_counter_rollover(session, invoices):
+    result = check_counter_rollover(session, 2025)
+
+    assert result["last_number"] == "25 | 003"
+    assert result["last_counter"] == 3
+    assert result["next_number"] == "26 | 002"
+    assert result["next_year_started"] is True
+    assert result["warnings"] == []
+
+
+def test_counter_rollover_empty_year(session):
+    result = check_counter_rollover(session, 2030)
+
+    assert result["last_number"] is None
+    assert result["next_number"] == "31 | 001"
+    assert result["next_year_started"] is False
+    assert len(result["warnings"]) == 1
+
+
+def test_fiscal_year_routes_reject_invalid_year():
+    assert client.get("/fiscal-years/1999/vat-summary").status_code == 422
+    assert client.get("/fiscal-years/2025/rollover").status_code == 200
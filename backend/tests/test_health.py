@@ -65,3 +65,170 @@ def test_cors_allowed_origins():
         assert (
             response.headers.get("access-control-allow-origin") == origin
         ), f"CORS header missing or wrong for origin: {origin}"
+
+
+def test_server_timing_header():
+    """Jede Antwort enthält die Bearbeitungszeit für das Tracing der Shell."""
+    response = client.get("/health", headers={"X-Trace-Id": "abc123"})
+    assert response.status_code == 200
+    name, duration = response.headers["server-timing"].split(";dur=")
+    assert name == "app"
+    assert float(duration) >= 0
+
+
+def test_health_reports_extended_fields():
+    """Test dass /health Version, Migrationen, Backup und Queue meldet."""
+    from utils.config import APP_VERSION
+
+    response = client.get("/health")
+    assert response.status_code == 200
+    data = response.json()
+
+    assert data["version"] == APP_VERSION
+    assert "pending_migrations" in data
+    assert "last_backup" in data
+    assert data["queue_depth"] >= 0
+
+
+def test_health_identifies_instance(monkeypatch, tmp_path):
+    """Test dass /health Instanz, Prozess und Datenverzeichnis meldet."""
+    monkeypatch.setenv("BILLINO_INSTANCE_ID", "abc123")
+    monkeypatch.setenv("BILLINO_SUPERVISOR_PID", "4242")
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+
+    data = client.get("/health").json()
+
+    assert data["instance_id"] == "abc123"
+    assert data["pid"] == 4242
+    assert data["data_dir"] == str(tmp_path)
+
+
+def test_health_pid_defaults_to_own_process(monkeypatch):
+    """Ohne Supervisor (z.B. TestClient) meldet /health die eigene PID."""
+    monkeypatch.delenv("BILLINO_SUPERVISOR_PID", raising=False)
+
+    assert client.get("/health").json()["pid"] == os.getpid()
+
+
+def test_pending_migrations_detects_missing_column(tmp_path, monkeypatch):
+    """Test dass fehlende Spalten als ausstehende Migration gezählt werden."""
+    import sqlite3
+
+    from sqlmodel import SQLModel, create_engine
+
+    import routers.health as health_router
+
+    db_file = tmp_path / "legacy.db"
+    engine = create_engine(f"sqlite:///{db_file}")
+    SQLModel.metadata.create_all(engine)
+    monkeypatch.setattr(health_router, "get_engine", lambda: engine)
+
+    assert health_router._count_pending_migrations() == 0
+
+    # Simuliere alte Datenbank ohne customer.note
+    with sqlite3.connect(db_file) as conn:
+        conn.execute("ALTER TABLE customer DROP COLUMN note")
+    assert health_router._count_pending_migrations() == 1
+    engine.dispose()
+
+
+def test_health_reports_cached_pending_migrations(monkeypatch):
+    """Test dass /health das Schema nicht bei jeder Anfrage prüft."""
+    import routers.health as health_router
+
+    calls = []
+
+    def count():
+        calls.append(1)
+        return 2
+
+    monkeypatch.setattr(health_router, "_count_pending_migrations", count)
+    monkeypatch.setattr(health_router, "_pending_migrations", None)
+
+    assert health_router.refresh_pending_migrations() == 2
+    assert client.get("/health").json()["pending_migrations"] == 2
+    assert client.get("/health").json()["pending_migrations"] == 2
+    assert len(calls) == 1
+
+
+def test_shutdown_stops_server_after_response(monkeypatch):
+    """Test dass /shutdown antwortet und danach SIGINT auslöst."""
+    import signal
+
+    import routers.health as health_router
+
+    raised = []
+    monkeypatch.setattr(health_router, "SHUTDOWN_DELAY_S", 0)
+    monkeypatch.setattr(health_router.signal, "raise_signal", raised.append)
+    monkeypatch.setattr(health_router, "_is_ready", True)
+    monkeypatch.delenv("BACKEND_WORKERS", raising=False)
+    monkeypatch.setenv("BILLINO_SHUTDOWN_TOKEN", "geheim")
+
+    response = client.post("/shutdown", headers={"X-Billino-Shutdown-Token": "geheim"})
+    assert response.status_code == 202
+    assert response.json() == {"accepted": True}
+    assert raised == [signal.SIGINT]
+    assert health_router._is_ready is False
+
+
+def test_shutdown_rejected_with_several_workers(monkeypatch):
+    """Test dass /shutdown mit mehreren Workern abgelehnt wird."""
+    import routers.health as health_router
+
+    raised = []
+    monkeypatch.setattr(health_router.signal, "raise_signal", raised.append)
+    monkeypatch.setenv("BACKEND_WORKERS", "2")
+    monkeypatch.setenv("BILLINO_SHUTDOWN_TOKEN", "geheim")
+
+    response = client.post("/shutdown", headers={"X-Billino-Shutdown-Token": "geheim"})
+    assert response.status_code == 409
+    assert raised == []
+
+
+def test_shutdown_requires_token(monkeypatch):
+    """Test dass /shutdown ohne oder mit falschem Token abgelehnt wird."""
+    import routers.health as health_router
+
+    raised = []
+    monkeypatch.setattr(health_router.signal, "raise_signal", raised.append)
+    monkeypatch.setattr(health_router, "_is_ready", True)
+    monkeypatch.delenv("BACKEND_WORKERS", raising=False)
+    monkeypatch.setenv("BILLINO_SHUTDOWN_TOKEN", "geheim")
+
+    assert client.post("/shutdown").status_code == 403
+    wrong = {"X-Billino-Shutdown-Token": "falsch"}
+    assert client.post("/shutdown", headers=wrong).status_code == 403
+
+    # Ohne Token in der Umgebung ist der Endpoint gesperrt
+    monkeypatch.delenv("BILLINO_SHUTDOWN_TOKEN")
+    empty = {"X-Billino-Shutdown-Token": ""}
+    assert client.post("/shutdown", headers=empty).status_code == 403
+    assert raised == []
+    assert health_router._is_ready is True
+
+
+def test_pending_work_reports_running_jobs(monkeypatch, tmp_path):
+    """Test dass /shutdown/pending-work laufende PDF-Jobs und Backups meldet."""
+    import routers.health as health_router
+
+    monkeypatch.setenv("DATA_DIR", str(tmp_path))
+    monkeypatch.setattr(
+        health_router.BackgroundPDFGenerator, "active_count", lambda: 0
+    )
+    monkeypatch.setattr(
+        health_router.BackupScheduler, "is_backup_running", lambda: False
+    )
+
+    response = client.get("/shutdown/pending-work")
+    assert response.status_code == 200
+    assert response.json() == {
+        "idle": True,
+        "write_in_progress": False,
+        "pdf_jobs": 0,
+        "backup_running": False,
+    }
+
+    monkeypatch.setattr(health_router.BackgroundPDFGenerator, "active_count", lambda: 2)
+    data = client.get("/shutdown/pending-work").json()
+    assert data["idle"] is False
+    assert data["pdf_jobs"] == 2
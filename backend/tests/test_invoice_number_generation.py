@@ -1,3 +1,5 @@
+from datetime import date
+
 import pytest
 from fastapi.testclient import TestClient
 
@@ -5,6 +7,7 @@ from main import app
 from services.invoice_number_generator import (
     generate_next_invoice_number,
     get_preview_invoice_number,
+    render_invoice_number,
     validate_invoice_number_format,
 )
 
@@ -425,3 +428,96 @@ def test_concurrent_invoice_creation_same_profile():
     # All should have valid format
     for number in numbers:
         assert validate_invoice_number_format(number) is True
+
+
+# ---------------------------------------------------------------------------
+# 🧪 Number Format Tester
+# ---------------------------------------------------------------------------
+
+
+def test_render_invoice_number():
+    """Platzhalter werden aus Datum und Zähler befüllt"""
+    sample = date(2026, 3, 7)
+
+    assert render_invoice_number("{YY} | {N:3}", sample, 5) == "26 | 005"
+    rendered = render_invoice_number("RE-{YYYY}{MM}{DD}-{N}", sample, 12)
+    assert rendered == "RE-20260307-12"
+    with pytest.raises(ValueError):
+        render_invoice_number("{YYYY}-{X}", sample, 1)
+
+
+def test_number_format_rejects_invalid_patterns():
+    for pattern in ["{YYYY}", "{N}-{N}", "{YY}-{Q}-{N}", "A{b}{N}", "{N:0}"]:
+        resp = client.post(
+            "/invoices/number-format/test",
+            json={"pattern": pattern, "sample_date": "2026-01-02", "counter": 1},
+        )
+        assert resp.status_code == 200
+        data = resp.json()
+        assert data["valid"] is False, pattern
+        assert data["rendered"] is None
+        assert data["errors"]
+
+
+def test_number_format_new_scheme_starts_at_one():
+    resp = client.post(
+        "/invoices/number-format/test",
+        json={
+            "pattern": "ZZTEST-{YYYY}-{N:4}",
+            "sample_date": "2026-01-02",
+            "counter": 1,
+        },
+    )
+    data = resp.json()
+    assert data["valid"] is True
+    assert data["rendered"] == "ZZTEST-2026-0001"
+    assert data["highest_counter"] is None
+    assert data["warnings"] == []
+
+    resp = client.post(
+        "/invoices/number-format/test",
+        json={"pattern": "ZZTEST-{N:4}", "sample_date": "2026-01-02", "counter": 3},
+    )
+    data = resp.json()
+    assert data["valid"] is True
+    assert len(data["warnings"]) == 2  # kein Start bei 1, kein Jahr
+
+
+def test_number_format_detects_duplicates_and_gaps(profile, customer):
+    """Zähler muss direkt an die höchste vorhandene Nummer anschließen"""
+    invoice_resp = client.post(
+        "/invoices/",
+        json={
+            "date": "2025-10-20",
+            "customer_id": customer["id"],
+            "profile_id": profile["id"],
+            "total_amount": 100.0,
+            "invoice_items": [
+                {"description": "Service", "quantity": 1, "price": 100.0}
+            ],
+        },
+    )
+    assert invoice_resp.status_code == 201
+    number = invoice_resp.json()["number"]
+    year, counter = number.split(" | ")
+    sample_date = f"20{year}-06-01"
+
+    def check(value):
+        return client.post(
+            "/invoices/number-format/test",
+            json={
+                "pattern": "{YY} | {N:3}",
+                "sample_date": sample_date,
+                "counter": value,
+            },
+        ).json()
+
+    duplicate = check(int(counter))
+    assert duplicate["valid"] is False
+    assert duplicate["highest_counter"] == int(counter)
+
+    gap = check(int(counter) + 2)
+    assert gap["valid"] is False
+    assert any("Lücke" in e for e in gap["errors"])
+
+    assert check(int(counter) + 1)["valid"] is True
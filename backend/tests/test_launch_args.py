@@ -0,0 +1,36 @@
+import pytest
+
+from utils.launch_args import LaunchArgsError, parse_launch_args
+
+
+def test_no_arguments_give_no_options():
+    assert parse_launch_args([]) == {}
+
+
+def test_uvicorn_flags_become_keyword_arguments():
+    options = parse_launch_args(
+        ["--log-config", "logging.json", "--timeout-keep-alive", "10", "--no-reload"]
+    )
+
+    assert options == {
+        "log_config": "logging.json",
+        "timeout_keep_alive": 10,
+        "reload": False,
+    }
+
+
+@pytest.mark.parametrize(
+    "argv",
+    [
+        ["--port", "9000"],
+        ["--host", "0.0.0.0"],
+        ["--workers", "2"],
+        ["--log-level", "debug"],
+        ["--log-conf", "logging.json"],
+        ["--timeout-keep-alive", "0"],
+        ["--limit-concurrency", "zehn"],
+    ],
+)
+def test_unknown_or_invalid_arguments_are_rejected(argv):
+    with pytest.raises(LaunchArgsError):
+        parse_launch_args(argv)
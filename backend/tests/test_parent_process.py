@@ -0,0 +1,86 @@
+import os
+import signal
+import subprocess
+import sys
+import time
+from pathlib import Path
+
+import pytest
+
+from utils import parent_process
+from utils.parent_process import PARENT_PID_ENV, bind_to_parent, parent_pid_from_env
+
+
+@pytest.fixture(autouse=True)
+def unbound(monkeypatch):
+    monkeypatch.setattr(parent_process, "_bound", False)
+    monkeypatch.delenv(PARENT_PID_ENV, raising=False)
+    monkeypatch.delenv(parent_process.BOUND_ENV, raising=False)
+
+
+def test_parent_pid_from_env(monkeypatch):
+    assert parent_pid_from_env() is None
+
+    monkeypatch.setenv(PARENT_PID_ENV, "4242")
+    assert parent_pid_from_env() == 4242
+
+    monkeypatch.setenv(PARENT_PID_ENV, "electron")
+    assert parent_pid_from_env() is None
+
+
+def test_standalone_backend_is_not_bound():
+    """Ohne BILLINO_PARENT_PID (z.B. `python main.py`) bleibt alles beim Alten."""
+    assert bind_to_parent() is False
+
+
+@pytest.mark.skipif(
+    not sys.platform.startswith("linux"), reason="PDEATHSIG is Linux-only"
+)
+def test_child_gets_sigterm_when_parent_dies(tmp_path: Path):
+    """Ein hart beendeter Elternprozess reißt das gebundene Kind mit."""
+    backend_dir = Path(__file__).resolve().parent.parent
+    pid_file = tmp_path / "child.pid"
+    child = (
+        "import os, time\n"
+        "from utils.parent_process import bind_to_parent\n"
+        "assert bind_to_parent(os.getppid())\n"
+        f"open({str(pid_file)!r}, 'w').write(str(os.getpid()))\n"
+        "time.sleep(30)\n"
+    )
+    parent = (
+        "import subprocess, sys, time\n"
+        f"subprocess.Popen([sys.executable, '-c', {child!r}], "
+        f"cwd={str(backend_dir)!r})\n"
+        "time.sleep(30)\n"
+    )
+    proc = subprocess.Popen([sys.executable, "-c", parent])
+    try:
+        for _ in range(100):
+            if pid_file.exists() and pid_file.read_text():
+                break
+            time.sleep(0.1)
+        child_pid = int(pid_file.read_text())
+    finally:
+        proc.send_signal(signal.SIGKILL)
+        proc.wait()
+
+    for _ in range(50):
+        try:
+            os.kill(child_pid, 0)
+        except ProcessLookupError:
+            return
+        time.sleep(0.1)
+    os.kill(child_pid, signal.SIGKILL)
+    pytest.fail("child survived its parent")
+
+
+@pytest.mark.skipif(not sys.platform.startswith("linux"), reason="Linux code path")
+def test_binding_happens_once_per_process(monkeypatch):
+    """Gebunden wird an den direkten Elternprozess (bei Workern der Supervisor)."""
+    monkeypatch.setattr(parent_process, "_bind_linux", lambda pid: pid == os.getppid())
+
+    assert bind_to_parent(os.getppid()) is True
+    assert os.environ[parent_process.BOUND_ENV] == "1"
+    # Weitere Aufrufe (Lifespan im selben Prozess) binden nicht erneut
+    monkeypatch.setattr(parent_process, "_bind_linux", lambda pid: False)
+    assert bind_to_parent(os.getppid()) is True
@@ -0,0 +1,109 @@
+import io
+import sqlite3
+
+from database import get_data_dir, get_db_file
+from services.backup_service import BackupHandler
+from utils.logger import use_utf8
+from utils.paths import (
+    has_long_path_prefix,
+    is_unc_path,
+    long_path,
+    strip_long_path_prefix,
+)
+
+LONG_DIR = "C:\\Users\\Jörg\\" + "\\".join(["Unterordner-mit-langem-Namen"] * 10)
+LONG_UNC = "\\\\nas\\billino\\" + "\\".join(["Unterordner-mit-langem-Namen"] * 10)
+
+
+def test_short_paths_stay_unchanged():
+    assert long_path("C:\\Users\\Jörg\\billino.db", windows=True) == (
+        "C:\\Users\\Jörg\\billino.db"
+    )
+    assert long_path(LONG_DIR, windows=False) == LONG_DIR
+
+
+def test_long_drive_path_gets_prefix():
+    result = long_path(LONG_DIR + "\\billino.db", windows=True)
+
+    assert len(result) > 260
+    assert result == "\\\\?\\" + LONG_DIR + "\\billino.db"
+    assert has_long_path_prefix(result)
+    assert strip_long_path_prefix(result) == LONG_DIR + "\\billino.db"
+
+
+def test_long_unc_path_gets_unc_prefix():
+    result = long_path(LONG_UNC, windows=True)
+
+    assert result == "\\\\?\\UNC\\" + LONG_UNC[2:]
+    assert is_unc_path(result)
+    assert strip_long_path_prefix(result) == LONG_UNC
+
+
+def test_long_path_is_normalized_before_prefixing():
+    """Mit Präfix wertet Windows weder '/' noch '..' aus."""
+    mixed = LONG_DIR.replace("\\", "/") + "/tmp/../billino.db"
+
+    assert long_path(mixed, windows=True) == "\\\\?\\" + LONG_DIR + "\\billino.db"
+
+
+def test_headroom_reserves_space_for_subpaths():
+    data_dir = "C:\\" + "d" * 200
+
+    assert long_path(data_dir, windows=True) == data_dir
+    assert long_path(data_dir, headroom=64, windows=True) == "\\\\?\\" + data_dir
+
+
+def test_relative_and_prefixed_paths_stay_unchanged():
+    relative = "data\\" + "x" * 300
+    prefixed = "\\\\?\\" + LONG_DIR
+
+    assert long_path(relative, windows=True) == relative
+    assert long_path(prefixed, windows=True) == prefixed
+
+
+def test_is_unc_path():
+    assert is_unc_path("\\\\nas\\billino")
+    assert is_unc_path("//nas/billino")
+    assert not is_unc_path("C:\\billino")
+    assert not is_unc_path("\\\\?\\C:\\billino")
+
+
+def test_non_ascii_data_dir(tmp_path, monkeypatch):
+    """Profile wie C:\\Users\\Jörg Müller dürfen DATA_DIR nicht verfälschen."""
+    data_dir = tmp_path / "Jörg Müller" / "Billino 📄"
+    monkeypatch.setenv("DATA_DIR", str(data_dir))
+
+    assert get_data_dir() == data_dir
+    assert data_dir.is_dir()
+    assert get_db_file() == data_dir / "billino.db"
+
+
+def test_backup_in_non_ascii_data_dir(tmp_path):
+    data_dir = tmp_path / "Jörg Müller"
+    data_dir.mkdir()
+    db_file = data_dir / "billino.db"
+    conn = sqlite3.connect(str(db_file))
+    conn.execute("CREATE TABLE customer (name TEXT)")
+    conn.execute("INSERT INTO customer VALUES ('Bäckerei Groß')")
+    conn.commit()
+    conn.close()
+
+    handler = BackupHandler(backup_root=data_dir / "backups", db_path=db_file)
+    backup_path = handler.backup_database()
+
+    assert backup_path is not None
+    assert backup_path.parent.parent.parent == data_dir
+    conn = sqlite3.connect(str(backup_path))
+    assert conn.execute("SELECT name FROM customer").fetchone() == ("Bäckerei Groß",)
+    conn.close()
+
+
+def test_log_stream_is_switched_to_utf8():
+    """Eine Pipe mit ANSI-Codepage würde Umlaute und Emojis verstümmeln."""
+    raw = io.BytesIO()
+    stream = io.TextIOWrapper(raw, encoding="cp1252")
+
+    use_utf8(stream).write("📂 C:\\Users\\Jörg Müller")
+    stream.flush()
+
+    assert raw.getvalue().decode("utf-8") == "📂 C:\\Users\\Jörg Müller"
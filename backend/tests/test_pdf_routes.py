@@ -439,3 +439,68 @@ class TestPDFRoutes:
         assert (
             pdf_resp2.json()["detail"] == "PDF for this summary invoice already exists"
         )
+
+    def test_create_customer_statement_pdf(self, client, session):
+        """Test generating a statement over the invoices of a period"""
+        profile_id = client.post(
+            "/profiles/",
+            json={"name": "Statement Profile", "address": "Addr", "city": "City"},
+        ).json()["id"]
+        customer_id = client.post(
+            "/customers/", json={"name": "Statement Customer"}
+        ).json()["id"]
+
+        for invoice_date in ["2025-03-10", "2025-04-02", "2025-07-15"]:
+            client.post(
+                "/invoices/",
+                json={
+                    "date": invoice_date,
+                    "customer_id": customer_id,
+                    "profile_id": profile_id,
+                    "total_amount": 40.00,
+                    "invoice_items": [
+                        {"description": "Service", "quantity": 1, "price": 40.00}
+                    ],
+                },
+            )
+
+        pdf_resp = client.post(
+            f"/pdfs/customers/{customer_id}/statement",
+            params={"date_from": "2025-03-01", "date_to": "2025-06-30"},
+        )
+
+        assert pdf_resp.status_code == 201
+        pdf_data = pdf_resp.json()
+        assert pdf_data["type"] == "customer_statement"
+        assert pdf_data["invoice_id"] is None
+        assert pdf_data["summary_invoice_id"] is None
+        assert len(pdf_data["content"]) > 0
+
+    def test_customer_statement_invalid_range(self, client, session):
+        """Test that a period ending before it starts is rejected"""
+        customer_id = client.post("/customers/", json={"name": "Range"}).json()["id"]
+
+        pdf_resp = client.post(
+            f"/pdfs/customers/{customer_id}/statement",
+            params={"date_from": "2025-12-31", "date_to": "2025-01-01"},
+        )
+        assert pdf_resp.status_code == 400
+
+    def test_customer_statement_without_invoices(self, client, session):
+        """Test that a customer without invoices has no profile to issue from"""
+        customer_id = client.post("/customers/", json={"name": "No Invoices"}).json()[
+            "id"
+        ]
+
+        pdf_resp = client.post(
+            f"/pdfs/customers/{customer_id}/statement",
+            params={"date_from": "2025-01-01", "date_to": "2025-12-31"},
+        )
+        assert pdf_resp.status_code == 404
+        assert pdf_resp.json()["detail"] == "Customer has no invoices"
+
+        missing_resp = client.post(
+            "/pdfs/customers/999999/statement",
+            params={"date_from": "2025-01-01", "date_to": "2025-12-31"},
+        )
+        assert missing_resp.status_code == 404
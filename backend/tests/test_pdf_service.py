@@ -1,3 +1,4 @@
+import base64
 from datetime import date
 from io import BytesIO
 from unittest.mock import Mock, patch
@@ -16,6 +17,12 @@ from services.pdf_data_service import (
 )
 from services.pdf_generator import PDFGenerator
 
+# 1x1 PNG für Logo-Tests
+TINY_PNG = (
+    "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6"
+    "kgAAAABJRU5ErkJggg=="
+)
+
 # Einmalige Test-DB im Speicher (mit StaticPool für persistente Verbindung)
 TEST_DB_URL = "sqlite:///:memory:"
 
@@ -519,6 +526,34 @@ class TestPDFGenerator:
         assert len(pdf_bytes) > 0
         assert pdf_bytes.startswith(b"%PDF")
 
+    def test_generate_invoice_pdf_with_logo(self, tmp_path, monkeypatch):
+        """Ein vorhandenes Firmenlogo wird in die Rechnung eingebettet."""
+        monkeypatch.setenv("DATA_DIR", str(tmp_path))
+        pdf_data = PDFInvoiceData(
+            invoice_number="25 | 001",
+            date=date(2025, 10, 31),
+            sender_name="Test Salon",
+            sender_address="Teststraße 123\n12345 Teststadt",
+            sender_bank_data=None,
+            sender_tax_number=None,
+            customer_name="Test Customer",
+            customer_address="",
+            items=[{"description": "Haarschnitt", "quantity": 1, "price": 25.00}],
+            total_net=25.00,
+            total_tax=0.0,
+            total_gross=25.00,
+            tax_rate=0.0,
+        )
+
+        without_logo = PDFGenerator().generate_invoice_pdf(pdf_data)
+        assert b"/Subtype /Image" not in without_logo
+
+        (tmp_path / "branding").mkdir()
+        (tmp_path / "branding" / "logo.png").write_bytes(base64.b64decode(TINY_PNG))
+        with_logo = PDFGenerator().generate_invoice_pdf(pdf_data)
+        assert with_logo.startswith(b"%PDF")
+        assert b"/Subtype /Image" in with_logo
+
 
 # ---------------------------------------------------------------------------
 # 🧪 Edge Cases and Error Handling
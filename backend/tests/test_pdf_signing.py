@@ -0,0 +1,76 @@
+import base64
+import datetime
+import io
+
+import pytest
+from reportlab.pdfgen import canvas
+
+from models import PdfSigningRequest
+from services.pdf_signing_service import SigningError, is_signed, sign_pdf
+
+pytest.importorskip("pyhanko")
+
+from cryptography import x509  # noqa: E402
+from cryptography.hazmat.primitives import hashes, serialization  # noqa: E402
+from cryptography.hazmat.primitives.asymmetric import rsa  # noqa: E402
+from cryptography.hazmat.primitives.serialization import pkcs12  # noqa: E402
+from cryptography.x509.oid import NameOID  # noqa: E402
+
+
+@pytest.fixture(scope="module")
+def pkcs12_base64():
+    """Selbstsigniertes Test-Zertifikat als PKCS#12 (Passwort "geheim")."""
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    name = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "Salon Test")])
+    now = datetime.datetime.now(datetime.timezone.utc)
+    cert = (
+        x509.CertificateBuilder()
+        .subject_name(name)
+        .issuer_name(name)
+        .public_key(key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(now - datetime.timedelta(days=1))
+        .not_valid_after(now + datetime.timedelta(days=30))
+        .sign(key, hashes.SHA256())
+    )
+    data = pkcs12.serialize_key_and_certificates(
+        b"billino",
+        key,
+        cert,
+        None,
+        serialization.BestAvailableEncryption(b"geheim"),
+    )
+    return base64.b64encode(data).decode()
+
+
+@pytest.fixture
+def pdf_bytes():
+    buffer = io.BytesIO()
+    pdf = canvas.Canvas(buffer)
+    pdf.drawString(100, 750, "Rechnung 25 | 001")
+    pdf.save()
+    return buffer.getvalue()
+
+
+def test_sign_pdf_appends_pades_signature(pdf_bytes, pkcs12_base64):
+    request = PdfSigningRequest(pkcs12=pkcs12_base64, passphrase="geheim")
+
+    signed = sign_pdf(pdf_bytes, request)
+
+    assert signed.startswith(pdf_bytes)  # inkrementelles Update
+    assert b"/ETSI.CAdES.detached" in signed
+    assert is_signed(signed) and not is_signed(pdf_bytes)
+
+
+def test_sign_pdf_rejects_wrong_passphrase_and_signed_pdfs(pdf_bytes, pkcs12_base64):
+    with pytest.raises(SigningError):
+        sign_pdf(pdf_bytes, PdfSigningRequest(pkcs12=pkcs12_base64, passphrase="x"))
+
+    request = PdfSigningRequest(pkcs12=pkcs12_base64, passphrase="geheim")
+    with pytest.raises(SigningError, match="bereits signiert"):
+        sign_pdf(sign_pdf(pdf_bytes, request), request)
+
+
+def test_sign_pdf_requires_certificate(pdf_bytes):
+    with pytest.raises(SigningError, match="Kein Zertifikat"):
+        sign_pdf(pdf_bytes, PdfSigningRequest())
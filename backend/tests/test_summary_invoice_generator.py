@@ -1,11 +1,21 @@
+import base64
+
 import pytest
 from sqlalchemy import event
 from sqlalchemy.exc import IntegrityError
 from sqlmodel import Session, SQLModel, create_engine, select
 
 from database import init_db
-from models import Customer, Invoice, Profile, SummaryInvoiceCreate, SummaryInvoiceLink
-from services import create_summary_invoice
+from models import (
+    Customer,
+    Invoice,
+    Profile,
+    StoredPDF,
+    SummaryInvoice,
+    SummaryInvoiceCreate,
+    SummaryInvoiceLink,
+)
+from services import create_summary_invoice, recalculate_summary_totals
 
 
 @pytest.fixture(scope="session")
@@ -685,3 +695,86 @@ def test_create_summary_invoice_rounding_precision(session: Session):
     assert summary.total_gross == pytest.approx(100.0, abs=0.01)
     assert summary.total_net == pytest.approx(84.03, abs=0.01)
     assert summary.total_tax == pytest.approx(15.97, abs=0.01)
+
+
+def _summary_with_changed_invoice(session: Session, number: str) -> SummaryInvoice:
+    """Sammelrechnung, deren Rechnung danach von 100 € auf 200 € geändert wurde."""
+    profile = Profile(
+        name="Recalc", address="X", city="Y", include_tax=True, default_tax_rate=0.19
+    )
+    customer = Customer(name="Max Mustermann")
+    session.add_all([profile, customer])
+    session.commit()
+
+    invoice = Invoice(
+        number=number,
+        date="2025-10-01",
+        profile_id=profile.id,
+        customer_id=customer.id,
+        include_tax=True,
+        is_gross_amount=False,
+        tax_rate=0.19,
+        total_amount=100.0,
+    )
+    session.add(invoice)
+    session.commit()
+    summary = create_summary_invoice(
+        session,
+        summary=SummaryInvoiceCreate(profile_id=profile.id, invoice_ids=[invoice.id]),
+    )
+
+    invoice.total_amount = 200.0
+    session.add(invoice)
+    session.commit()
+    return summary
+
+
+def test_recalculate_summary_totals_only_reports_by_default(session: Session):
+    """Ohne Bestätigung werden abweichende Summen nur gemeldet."""
+    summary = _summary_with_changed_invoice(session, "25 | 0100")
+
+    result = recalculate_summary_totals(session)
+
+    assert result["dry_run"] is True
+    assert result["corrected"] == []
+    [entry] = [d for d in result["discrepancies"] if d["id"] == summary.id]
+    assert entry["calculated_gross"] == pytest.approx(238.0)
+    assert entry["has_pdf"] is False
+    session.expire_all()
+    assert session.get(SummaryInvoice, summary.id).total_gross == pytest.approx(119.0)
+
+
+def test_recalculate_summary_totals_corrects_stale_totals(session: Session):
+    """Gespeicherte Summen werden nach geänderten Rechnungsbeträgen korrigiert."""
+    summary = _summary_with_changed_invoice(session, "25 | 0101")
+
+    result = recalculate_summary_totals(session, confirm=True)
+    assert result["dry_run"] is False
+    assert summary.id in result["corrected"]
+
+    stored = session.get(SummaryInvoice, summary.id)
+    assert stored.total_net == pytest.approx(200.0)
+    assert stored.total_gross == pytest.approx(238.0)
+    again = recalculate_summary_totals(session, confirm=True)
+    assert summary.id not in again["corrected"]
+
+
+def test_recalculate_summary_totals_keeps_summaries_with_pdf(session: Session):
+    """Eine Sammelrechnung mit gespeichertem PDF wurde ausgestellt und bleibt."""
+    summary = _summary_with_changed_invoice(session, "25 | 0102")
+    session.add(
+        StoredPDF(
+            type="summary_invoice",
+            content=base64.b64encode(b"%PDF-1.4 test").decode(),
+            summary_invoice_id=summary.id,
+        )
+    )
+    session.commit()
+
+    result = recalculate_summary_totals(session, confirm=True)
+
+    assert summary.id not in result["corrected"]
+    [entry] = [d for d in result["discrepancies"] if d["id"] == summary.id]
+    assert entry["has_pdf"] is True
+    session.expire_all()
+    assert session.get(SummaryInvoice, summary.id).total_gross == pytest.approx(119.0)
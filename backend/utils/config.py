@@ -16,6 +16,14 @@ from pydantic import BaseModel, field_validator
 
 from utils import logger
 
+# Application version reported by the API (OpenAPI + /health)
+APP_VERSION = "2.0.0"
+
+# Upper bounds for the tuning knobs (the desktop shell clamps further
+# based on the machine's CPU cores and RAM)
+MAX_WORKERS = 16
+MAX_SQLITE_CACHE_MB = 1024
+
 
 class Environment(str, Enum):
     """Application environment."""
@@ -54,6 +62,10 @@ class BackendConfig(BaseModel):
     # CORS Configuration
     allowed_origins: list[str] = ["http://localhost:3000"]
 
+    # Performance Tuning
+    workers: int = 1
+    sqlite_cache_mb: Optional[int] = None
+
     @field_validator("port")
     @classmethod
     def validate_port(cls, v: int) -> int:
@@ -95,6 +107,24 @@ class BackendConfig(BaseModel):
             raise ValueError(f"Backup retention days must be > 0, got {v}")
         return v
 
+    @field_validator("workers")
+    @classmethod
+    def validate_workers(cls, v: int) -> int:
+        """Validate uvicorn worker count."""
+        if not 1 <= v <= MAX_WORKERS:
+            raise ValueError(f"Workers must be 1-{MAX_WORKERS}, got {v}")
+        return v
+
+    @field_validator("sqlite_cache_mb")
+    @classmethod
+    def validate_sqlite_cache(cls, v: Optional[int]) -> Optional[int]:
+        """Validate SQLite page cache size."""
+        if v is not None and not 1 <= v <= MAX_SQLITE_CACHE_MB:
+            raise ValueError(
+                f"SQLite cache must be 1-{MAX_SQLITE_CACHE_MB} MB, got {v}"
+            )
+        return v
+
     @classmethod
     def from_env(cls) -> "BackendConfig":
         """
@@ -111,6 +141,8 @@ class BackendConfig(BaseModel):
         - BACKUP_SCHEDULE_MINUTE: Minute for daily backup (0-59, default: 0)
         - BACKUP_RETENTION_DAYS: Days to keep backups (default: 30)
         - ALLOWED_ORIGINS: CORS origins CSV (default: http://localhost:3000)
+        - BACKEND_WORKERS: uvicorn worker processes (1-16, default: 1)
+        - SQLITE_CACHE_MB: SQLite page cache per connection in MB (optional)
 
         Returns:
             BackendConfig: Validated configuration
@@ -132,6 +164,8 @@ class BackendConfig(BaseModel):
         retention_days = int(os.getenv("BACKUP_RETENTION_DAYS", "30"))
         origins = os.getenv("ALLOWED_ORIGINS", "http://localhost:3000")
         allowed_origins = [o.strip() for o in origins.split(",")]
+        workers = int(os.getenv("BACKEND_WORKERS", "1"))
+        cache_mb = os.getenv("SQLITE_CACHE_MB")
 
         return cls(
             host=host,
@@ -144,6 +178,8 @@ class BackendConfig(BaseModel):
             backup_schedule_minute=backup_minute,
             backup_retention_days=retention_days,
             allowed_origins=allowed_origins,
+            workers=workers,
+            sqlite_cache_mb=int(cache_mb) if cache_mb else None,
         )
 
     def server_url(self) -> str:
@@ -205,8 +241,9 @@ def validate_startup_conditions(config: BackendConfig) -> dict:
     errors = []
     warnings = []
 
-    # Check port availability
-    if not config.is_port_available():
+    # Check port availability (with several workers the uvicorn supervisor
+    # has already bound the port before the workers start up)
+    if config.workers == 1 and not config.is_port_available():
         errors.append(
             f"❌ Port {config.port} is already in use. "
             f"Check for running instances or change BACKEND_PORT."
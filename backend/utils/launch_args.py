@@ -0,0 +1,58 @@
+"""
+Startargumente für uvicorn.
+
+Die Desktop-App reicht in config.toml eingetragene Argumente
+(`backend_args`, z. B. "--log-config logging.json --timeout-keep-alive 10")
+beim Start an das Backend weiter. Erlaubt sind nur die uvicorn-Optionen
+unten. Host und Port (BACKEND_HOST/BACKEND_PORT) sowie Worker und Log-Level
+(BACKEND_WORKERS/LOG_LEVEL, Einstellung backendTuning) setzt die App selbst,
+sie lassen sich hier nicht überschreiben – sonst griffen die Prüfungen auf
+diese Variablen (z. B. 1-16 Worker) nicht.
+"""
+
+import argparse
+from typing import Any, Dict, List
+
+
+class LaunchArgsError(ValueError):
+    """Unbekannte oder ungültige Startargumente."""
+
+
+class _Parser(argparse.ArgumentParser):
+    def error(self, message: str) -> None:  # type: ignore[override]
+        raise LaunchArgsError(message)
+
+
+def _positive_int(value: str) -> int:
+    number = int(value)
+    if number <= 0:
+        raise argparse.ArgumentTypeError(f"muss > 0 sein: {value}")
+    return number
+
+
+def _build_parser() -> _Parser:
+    parser = _Parser(prog="billino-backend", add_help=False, allow_abbrev=False)
+    parser.add_argument("--log-config", dest="log_config")
+    parser.add_argument("--reload", dest="reload", action="store_true", default=None)
+    parser.add_argument("--no-reload", dest="reload", action="store_false")
+    parser.add_argument(
+        "--timeout-keep-alive", dest="timeout_keep_alive", type=_positive_int
+    )
+    parser.add_argument(
+        "--limit-concurrency", dest="limit_concurrency", type=_positive_int
+    )
+    parser.add_argument("--root-path", dest="root_path")
+    return parser
+
+
+def parse_launch_args(argv: List[str]) -> Dict[str, Any]:
+    """
+    Wandle Startargumente in Keyword-Argumente für uvicorn.run() um.
+
+    Nicht angegebene Optionen fehlen im Ergebnis.
+
+    Raises:
+        LaunchArgsError: bei unbekannten Optionen oder ungültigen Werten
+    """
+    namespace = _build_parser().parse_args(argv)
+    return {key: value for key, value in vars(namespace).items() if value is not None}
@@ -14,7 +14,9 @@ Nutzung:
 
 import logging
 import os
+import sys
 from enum import Enum
+from typing import TextIO
 
 
 class Environment(str, Enum):
@@ -30,6 +32,20 @@ def get_environment() -> Environment:
     return Environment.DEV if env_str in ["dev", "development"] else Environment.PROD
 
 
+def use_utf8(stream: TextIO) -> TextIO:
+    """
+    Stelle einen Ausgabestrom auf UTF-8 um.
+
+    Unter Windows nutzen stdout/stderr bei Umleitung in eine Pipe (Electron)
+    die ANSI-Codepage (cp1252). Pfade wie C:\\Users\\Jörg Müller kämen dann
+    verstümmelt an, Emojis führen zu UnicodeEncodeError.
+    """
+    encoding = (getattr(stream, "encoding", None) or "").lower().replace("-", "")
+    if encoding != "utf8" and hasattr(stream, "reconfigure"):
+        stream.reconfigure(encoding="utf-8", errors="backslashreplace")
+    return stream
+
+
 def setup_logger(name: str = "billino") -> logging.Logger:
     """
     Configure and return logger with environment-aware settings.
@@ -59,7 +75,8 @@ def setup_logger(name: str = "billino") -> logging.Logger:
         formatter = logging.Formatter("[%(levelname)s] [%(name)s] %(message)s")
 
         # Console handler
-        console_handler = logging.StreamHandler()
+        console_handler = logging.StreamHandler(use_utf8(sys.stderr))
+        use_utf8(sys.stdout)
         console_handler.setFormatter(formatter)
         logger.addHandler(console_handler)
 
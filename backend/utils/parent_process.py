@@ -0,0 +1,263 @@
+"""
+Bindung des Backends an die Lebensdauer der Desktop-App.
+
+Wird die App hart beendet (Task-Manager, Absturz), läuft ein verwaistes
+Backend sonst weiter und blockiert beim nächsten Start den Port. Die Shell
+übergibt ihre PID in BILLINO_PARENT_PID; bind_to_parent() sorgt dafür, dass
+das Backend samt Worker-Prozessen mit ihr endet:
+
+- Windows: Job Object mit JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE. Das Backend
+  tritt selbst bei (später gestartete Worker erben die Mitgliedschaft) und
+  legt das einzige Handle auf den Job in den Shell-Prozess. Endet die
+  Shell, schließt Windows das Handle und beendet alle Prozesse des Jobs.
+- Linux: prctl(PR_SET_PDEATHSIG, SIGTERM) – der Kernel schickt SIGTERM,
+  sobald der Elternprozess endet, das Backend fährt regulär herunter.
+- macOS: kein Gegenstück, ein Hintergrund-Thread prüft die Eltern-PID.
+
+Ohne BILLINO_PARENT_PID (Backend eigenständig gestartet) passiert nichts.
+"""
+
+import ctypes
+import os
+import signal
+import sys
+import threading
+import time
+from typing import Optional
+
+from utils.logger import logger
+
+PARENT_PID_ENV = "BILLINO_PARENT_PID"
+# Vom Supervisor gesetzt, damit Worker unter Windows keinen eigenen Job anlegen
+BOUND_ENV = "BILLINO_PARENT_BOUND"
+
+# Linux <sys/prctl.h>
+PR_SET_PDEATHSIG = 1
+PR_GET_PDEATHSIG = 2
+
+# Windows <winnt.h>
+JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE = 0x2000
+JOB_OBJECT_EXTENDED_LIMIT_INFORMATION_CLASS = 9
+PROCESS_DUP_HANDLE = 0x0040
+DUPLICATE_SAME_ACCESS = 0x0002
+
+# macOS: Abstand der Prüfungen der Eltern-PID
+PARENT_POLL_SECONDS = 2.0
+
+_bound = False
+
+
+def parent_pid_from_env() -> Optional[int]:
+    """PID der Desktop-App aus BILLINO_PARENT_PID, None wenn nicht gesetzt."""
+    value = os.getenv(PARENT_PID_ENV, "").strip()
+    if not value:
+        return None
+    try:
+        pid = int(value)
+    except ValueError:
+        logger.warning(f"⚠️ Ignoring invalid {PARENT_PID_ENV}={value!r}")
+        return None
+    return pid if pid > 0 else None
+
+
+def _bind_windows(parent_pid: int) -> bool:
+    from ctypes import wintypes
+
+    class IO_COUNTERS(ctypes.Structure):
+        _fields_ = [
+            (name, ctypes.c_ulonglong)
+            for name in (
+                "ReadOperationCount",
+                "WriteOperationCount",
+                "OtherOperationCount",
+                "ReadTransferCount",
+                "WriteTransferCount",
+                "OtherTransferCount",
+            )
+        ]
+
+    class JOBOBJECT_BASIC_LIMIT_INFORMATION(ctypes.Structure):
+        _fields_ = [
+            ("PerProcessUserTimeLimit", wintypes.LARGE_INTEGER),
+            ("PerJobUserTimeLimit", wintypes.LARGE_INTEGER),
+            ("LimitFlags", wintypes.DWORD),
+            ("MinimumWorkingSetSize", ctypes.c_size_t),
+            ("MaximumWorkingSetSize", ctypes.c_size_t),
+            ("ActiveProcessLimit", wintypes.DWORD),
+            ("Affinity", ctypes.c_size_t),
+            ("PriorityClass", wintypes.DWORD),
+            ("SchedulingClass", wintypes.DWORD),
+        ]
+
+    class JOBOBJECT_EXTENDED_LIMIT_INFORMATION(ctypes.Structure):
+        _fields_ = [
+            ("BasicLimitInformation", JOBOBJECT_BASIC_LIMIT_INFORMATION),
+            ("IoInfo", IO_COUNTERS),
+            ("ProcessMemoryLimit", ctypes.c_size_t),
+            ("JobMemoryLimit", ctypes.c_size_t),
+            ("PeakProcessMemoryUsed", ctypes.c_size_t),
+            ("PeakJobMemoryUsed", ctypes.c_size_t),
+        ]
+
+    kernel32 = ctypes.WinDLL("kernel32", use_last_error=True)
+    kernel32.CreateJobObjectW.restype = wintypes.HANDLE
+    kernel32.CreateJobObjectW.argtypes = [wintypes.LPVOID, wintypes.LPCWSTR]
+    kernel32.GetCurrentProcess.restype = wintypes.HANDLE
+    kernel32.OpenProcess.restype = wintypes.HANDLE
+    kernel32.OpenProcess.argtypes = [wintypes.DWORD, wintypes.BOOL, wintypes.DWORD]
+    kernel32.SetInformationJobObject.argtypes = [
+        wintypes.HANDLE,
+        ctypes.c_int,
+        wintypes.LPVOID,
+        wintypes.DWORD,
+    ]
+    kernel32.AssignProcessToJobObject.argtypes = [wintypes.HANDLE, wintypes.HANDLE]
+    kernel32.DuplicateHandle.argtypes = [
+        wintypes.HANDLE,
+        wintypes.HANDLE,
+        wintypes.HANDLE,
+        ctypes.POINTER(wintypes.HANDLE),
+        wintypes.DWORD,
+        wintypes.BOOL,
+        wintypes.DWORD,
+    ]
+    kernel32.CloseHandle.argtypes = [wintypes.HANDLE]
+
+    job = kernel32.CreateJobObjectW(None, None)
+    if not job:
+        logger.warning(f"⚠️ CreateJobObject failed (error {ctypes.get_last_error()})")
+        return False
+
+    parent = None
+    try:
+        info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION()
+        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE
+        if not kernel32.SetInformationJobObject(
+            job,
+            JOB_OBJECT_EXTENDED_LIMIT_INFORMATION_CLASS,
+            ctypes.byref(info),
+            ctypes.sizeof(info),
+        ):
+            logger.warning(
+                f"⚠️ SetInformationJobObject failed (error {ctypes.get_last_error()})"
+            )
+            return False
+
+        parent = kernel32.OpenProcess(PROCESS_DUP_HANDLE, False, parent_pid)
+        if not parent:
+            logger.warning(
+                f"⚠️ Cannot open app process {parent_pid} "
+                f"(error {ctypes.get_last_error()})"
+            )
+            return False
+
+        # Beitreten erst, wenn das Handle in der Shell liegen kann – sonst
+        # gehörte das Backend einem Job, der mit ihm selbst endet
+        if not kernel32.AssignProcessToJobObject(job, kernel32.GetCurrentProcess()):
+            logger.warning(
+                f"⚠️ AssignProcessToJobObject failed (error {ctypes.get_last_error()})"
+            )
+            return False
+
+        # Das Handle im Shell-Prozess hält den Job am Leben; bei einem
+        # Neustart des Backends bleibt es bis zum Ende der App offen
+        remote = wintypes.HANDLE()
+        if not kernel32.DuplicateHandle(
+            kernel32.GetCurrentProcess(),
+            job,
+            parent,
+            ctypes.byref(remote),
+            0,
+            False,
+            DUPLICATE_SAME_ACCESS,
+        ):
+            logger.warning(
+                f"⚠️ DuplicateHandle failed (error {ctypes.get_last_error()})"
+            )
+            return False
+        return True
+    finally:
+        if parent:
+            kernel32.CloseHandle(parent)
+        kernel32.CloseHandle(job)
+
+
+def _bind_linux(parent_pid: int) -> bool:
+    libc = ctypes.CDLL(None, use_errno=True)
+    if libc.prctl(PR_SET_PDEATHSIG, signal.SIGTERM, 0, 0, 0) != 0:
+        logger.warning(
+            f"⚠️ prctl(PR_SET_PDEATHSIG) failed (errno {ctypes.get_errno()})"
+        )
+        return False
+    # Endete die App schon vor dem prctl-Aufruf, kommt kein Signal mehr
+    if os.getppid() == 1 or not _pid_alive(parent_pid):
+        logger.warning("⚠️ App exited before the backend was bound to it")
+        os.kill(os.getpid(), signal.SIGTERM)
+    return True
+
+
+def _bind_polling(parent_pid: int) -> bool:
+    original_ppid = os.getppid()
+
+    def watch() -> None:
+        while True:
+            time.sleep(PARENT_POLL_SECONDS)
+            if os.getppid() != original_ppid or not _pid_alive(parent_pid):
+                logger.warning("⚠️ App process is gone – shutting down backend")
+                os.kill(os.getpid(), signal.SIGTERM)
+                return
+
+    threading.Thread(target=watch, name="parent-watch", daemon=True).start()
+    return True
+
+
+def _pid_alive(pid: int) -> bool:
+    try:
+        os.kill(pid, 0)
+    except ProcessLookupError:
+        return False
+    except PermissionError:
+        return True
+    return True
+
+
+def bind_to_parent(parent_pid: Optional[int] = None) -> bool:
+    """
+    Beende dieses Backend (und seine Worker) zusammen mit der Desktop-App.
+
+    Wird im Hauptprozess und in jedem Worker aufgerufen, wirkt aber pro
+    Prozess nur einmal. Worker binden sich unter Linux/macOS an ihren
+    direkten Elternprozess (den uvicorn-Supervisor), unter Windows erben sie
+    den Job.
+
+    Returns:
+        True, wenn der Prozess gebunden ist
+    """
+    global _bound
+    if _bound:
+        return True
+
+    app_pid = parent_pid if parent_pid is not None else parent_pid_from_env()
+    if app_pid is None:
+        return False
+
+    direct_parent = os.getppid()
+    try:
+        if sys.platform == "win32":
+            # Worker gehören über den Supervisor bereits zum Job
+            if direct_parent != app_pid and os.getenv(BOUND_ENV) == "1":
+                _bound = True
+                return True
+            _bound = _bind_windows(app_pid)
+        elif sys.platform.startswith("linux"):
+            _bound = _bind_linux(direct_parent)
+        else:
+            _bound = _bind_polling(direct_parent)
+    except Exception as e:
+        logger.warning(f"⚠️ Could not bind backend to app process {app_pid}: {e}")
+        _bound = False
+
+    if _bound:
+        os.environ[BOUND_ENV] = "1"
+        logger.info(f"🔗 Backend bound to app process {app_pid}")
+    return _bound
@@ -0,0 +1,79 @@
+"""
+Pfad-Helfer für lange Windows-Pfade und UNC-Freigaben.
+
+Windows-APIs begrenzen normale Pfade auf MAX_PATH (260 Zeichen, Ordner
+248), solange Long Paths nicht systemweit aktiviert sind. Mit dem Präfix
+`\\\\?\\` (bzw. `\\\\?\\UNC\\` für Netzlaufwerke wie `\\\\nas\\billino`)
+gilt diese Grenze nicht. Das Datenverzeichnis kann tief verschachtelt oder
+auf einem NAS liegen – Dateizugriffe auf DB und Backups laufen daher über
+long_path().
+"""
+
+import ntpath
+import os
+from pathlib import Path
+from typing import Union
+
+# CreateDirectoryW erlaubt ohne Präfix nur 248 Zeichen (Platz für 8.3-Namen)
+WINDOWS_PATH_LIMIT = 248
+
+_EXTENDED_PREFIX = "\\\\?\\"
+_UNC_PREFIX = _EXTENDED_PREFIX + "UNC\\"
+
+
+def is_unc_path(path: Union[str, Path]) -> bool:
+    """True für Netzwerkpfade (\\\\server\\freigabe\\...)."""
+    text = str(path)
+    if text.startswith(_UNC_PREFIX):
+        return True
+    return not text.startswith(_EXTENDED_PREFIX) and text.startswith(("\\\\", "//"))
+
+
+def has_long_path_prefix(path: Union[str, Path]) -> bool:
+    """True, wenn der Pfad bereits das Präfix \\\\?\\ trägt."""
+    return str(path).startswith(_EXTENDED_PREFIX)
+
+
+def strip_long_path_prefix(path: Union[str, Path]) -> str:
+    """Entferne das Präfix \\\\?\\ wieder (für Anzeige und Logs)."""
+    text = str(path)
+    if text.startswith(_UNC_PREFIX):
+        return "\\\\" + text[len(_UNC_PREFIX) :]
+    if text.startswith(_EXTENDED_PREFIX):
+        return text[len(_EXTENDED_PREFIX) :]
+    return text
+
+
+def long_path(
+    path: Union[str, Path], headroom: int = 0, windows: bool = os.name == "nt"
+) -> str:
+    """
+    Pfad, den Windows-APIs auch über MAX_PATH hinaus öffnen können.
+
+    Auf anderen Systemen und für kurze Pfade unverändert. Relative Pfade
+    werden nicht verändert (das Präfix erfordert absolute Pfade).
+
+    Args:
+        path: Datei- oder Ordnerpfad
+        headroom: Zeichen, die für Unterpfade reserviert werden (für
+            Ordner, unter denen noch Dateien angelegt werden)
+        windows: Windows-Regeln anwenden (standard: aktuelles System)
+    """
+    text = str(path)
+    if not windows or text.startswith(_EXTENDED_PREFIX):
+        return text
+    if len(text) + headroom < WINDOWS_PATH_LIMIT:
+        return text
+
+    # Mit Präfix wertet Windows weder "/" noch ".." aus → vorher normalisieren
+    normalized = ntpath.normpath(text)
+    if is_unc_path(normalized):
+        return _UNC_PREFIX + normalized.lstrip("\\")
+    if ntpath.isabs(normalized) and ntpath.splitdrive(normalized)[0]:
+        return _EXTENDED_PREFIX + normalized
+    return text
+
+
+def long_path_obj(path: Union[str, Path], headroom: int = 0) -> Path:
+    """long_path() als Path-Objekt (für pathlib-Dateioperationen)."""
+    return Path(long_path(path, headroom))
@@ -0,0 +1,11 @@
+/** @type {import('jest').Config} */
+module.exports = {
+  preset: "ts-jest",
+  testEnvironment: "node",
+  roots: ["<rootDir>/src"],
+  moduleNameMapper: {
+    "^electron$": "<rootDir>/src/test/electronStub.js",
+    "^electron-log/main$": "<rootDir>/src/test/electronLogStub.js",
+  },
+  testPathIgnorePatterns: ["/node_modules/", "/dist/"],
+};
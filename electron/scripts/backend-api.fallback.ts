@@ -0,0 +1,21 @@
+/**
+ * Billino Desktop – Backend API Types (untyped fallback)
+ *
+ * Copied to src/generated/backend-api.ts by scripts/ensure-api.mjs when the
+ * client cannot be generated (no Python or backend dependencies, e.g. a
+ * fresh checkout that only runs `npm test`). Every "METHOD /path" is
+ * accepted and all parameters and responses are `any`, so endpoint names
+ * are not checked – run `npm run generate:api` for the real types.
+ */
+
+interface UntypedEndpoint {
+  params: any;
+  query: any;
+  body: any;
+  response: any;
+}
+
+export type BackendEndpoints = Record<
+  `${"GET" | "POST" | "PUT" | "PATCH" | "DELETE"} /${string}`,
+  UntypedEndpoint
+>;
@@ -0,0 +1,30 @@
+/**
+ * Billino Desktop – Backend Client Fallback
+ *
+ * `npm test` and `npm run build` need src/generated/backend-api.ts, but
+ * generating it (`npm run generate:api`) requires Python and the backend's
+ * dependencies. If the file is missing, copy the checked-in untyped
+ * fallback (scripts/backend-api.fallback.ts) so the shell still compiles.
+ * An existing generated client is never touched.
+ *
+ * Usage: node scripts/ensure-api.mjs
+ */
+
+import fs from "fs";
+import path from "path";
+import { fileURLToPath } from "url";
+
+const root = path.dirname(path.dirname(fileURLToPath(import.meta.url)));
+const target = path.join(root, "src", "generated", "backend-api.ts");
+const fallback = path.join(root, "scripts", "backend-api.fallback.ts");
+
+if (fs.existsSync(target)) {
+  process.exit(0);
+}
+
+fs.mkdirSync(path.dirname(target), { recursive: true });
+fs.copyFileSync(fallback, target);
+console.warn(
+  `⚠️ ${path.relative(root, target)} missing – using the untyped fallback. ` +
+    "Run `npm run generate:api` to check backend calls."
+);
@@ -0,0 +1,160 @@
+/**
+ * Billino Desktop – Backend Client Generator
+ *
+ * Turns the backend's OpenAPI spec (backend/scripts/export_openapi.py) into
+ * src/generated/backend-api.ts: one entry per endpoint ("POST /backups/trigger")
+ * with its path parameters, query, body and response types, plus an
+ * interface per schema. callBackend() in src/api.ts only accepts endpoints
+ * listed there, so a renamed or removed endpoint fails `tsc`.
+ *
+ * Usage: node scripts/generate-api.mjs <openapi.json> <output.ts>
+ */
+
+import fs from "fs";
+import path from "path";
+
+const METHODS = ["get", "post", "put", "patch", "delete"];
+
+const [specPath, outputPath] = process.argv.slice(2);
+if (!specPath || !outputPath) {
+  console.error("Usage: node scripts/generate-api.mjs <openapi.json> <output.ts>");
+  process.exit(1);
+}
+
+const spec = JSON.parse(fs.readFileSync(specPath, "utf-8"));
+
+/** Schema names may contain characters TS does not allow ("Page[Invoice]"). */
+function typeName(name) {
+  return name.replace(/[^A-Za-z0-9_]/g, "_");
+}
+
+function propertyKey(name) {
+  return /^[A-Za-z_$][A-Za-z0-9_$]*$/.test(name) ? name : JSON.stringify(name);
+}
+
+function toTs(schema, indent = "") {
+  if (!schema || Object.keys(schema).length === 0) return "unknown";
+  if (schema.$ref) return typeName(schema.$ref.replace("#/components/schemas/", ""));
+  if (schema.enum) return schema.enum.map((value) => JSON.stringify(value)).join(" | ");
+  if (schema.const !== undefined) return JSON.stringify(schema.const);
+
+  const variants = schema.anyOf ?? schema.oneOf ?? schema.allOf;
+  if (variants) {
+    const joiner = schema.allOf ? " & " : " | ";
+    return [...new Set(variants.map((variant) => toTs(variant, indent)))].join(joiner);
+  }
+
+  switch (schema.type) {
+    case "string":
+      return "string";
+    case "integer":
+    case "number":
+      return "number";
+    case "boolean":
+      return "boolean";
+    case "null":
+      return "null";
+    case "array": {
+      const item = toTs(schema.items, indent);
+      return /[ |&]/.test(item) ? `Array<${item}>` : `${item}[]`;
+    }
+    case "object":
+      return objectToTs(schema, indent);
+    default:
+      return "unknown";
+  }
+}
+
+function objectToTs(schema, indent) {
+  const properties = Object.entries(schema.properties ?? {});
+  if (properties.length === 0) {
+    const values = schema.additionalProperties;
+    return values && values !== true ? `Record<string, ${toTs(values, indent)}>` : "object";
+  }
+  const required = new Set(schema.required ?? []);
+  const inner = `${indent}  `;
+  const lines = properties.map(([name, property]) => {
+    const optional = required.has(name) ? "" : "?";
+    return `${inner}${propertyKey(name)}${optional}: ${toTs(property, inner)};`;
+  });
+  return `{\n${lines.join("\n")}\n${indent}}`;
+}
+
+function parametersToTs(parameters, location) {
+  const matching = parameters.filter((parameter) => parameter.in === location);
+  if (matching.length === 0) return "Record<string, never>";
+  return objectToTs(
+    {
+      properties: Object.fromEntries(matching.map((p) => [p.name, p.schema ?? {}])),
+      required: matching.filter((p) => p.required).map((p) => p.name),
+    },
+    "    "
+  );
+}
+
+function bodyToTs(operation) {
+  const content = operation.requestBody?.content ?? {};
+  const json = content["application/json"];
+  if (json) return toTs(json.schema, "    ");
+  // File uploads etc. are not sent through callBackend()
+  return Object.keys(content).length > 0 ? "never" : "undefined";
+}
+
+function responseToTs(operation) {
+  const responses = operation.responses ?? {};
+  const success = Object.keys(responses).find((code) => code.startsWith("2"));
+  if (!success) return "unknown";
+  const content = responses[success].content;
+  if (!content) return "null";
+  // Only JSON responses are typed; files and untyped dicts stay unknown
+  const json = content["application/json"];
+  return json ? toTs(json.schema, "    ") : "unknown";
+}
+
+const schemas = Object.entries(spec.components?.schemas ?? {}).map(([name, schema]) => {
+  const ts = toTs(schema);
+  const declaration = ts.startsWith("{")
+    ? `export interface ${typeName(name)} ${ts}`
+    : `export type ${typeName(name)} = ${ts};`;
+  return schema.description
+    ? `/** ${schema.description.split("\n")[0].trim()} */\n${declaration}`
+    : declaration;
+});
+
+const endpoints = [];
+const routes = Object.entries(spec.paths ?? {}).sort(([a], [b]) => a.localeCompare(b));
+for (const [route, item] of routes) {
+  for (const method of METHODS) {
+    const operation = item[method];
+    if (!operation) continue;
+    const parameters = [...(item.parameters ?? []), ...(operation.parameters ?? [])];
+    const summary = operation.summary ? `  /** ${operation.summary} */\n` : "";
+    endpoints.push(
+      `${summary}  "${method.toUpperCase()} ${route}": {\n` +
+        `    params: ${parametersToTs(parameters, "path")};\n` +
+        `    query: ${parametersToTs(parameters, "query")};\n` +
+        `    body: ${bodyToTs(operation)};\n` +
+        `    response: ${responseToTs(operation)};\n` +
+        "  };"
+    );
+  }
+}
+
+const title = `${spec.info?.title ?? "Backend API"} ${spec.info?.version ?? ""}`.trim();
+const output = `/**
+ * Billino Desktop – Backend API Types
+ *
+ * Generated by scripts/generate-api.mjs from the OpenAPI spec of
+ * ${title}. Do not edit – run \`npm run generate:api\`.
+ */
+
+${schemas.join("\n\n")}
+
+export interface BackendEndpoints {
+${endpoints.join("\n")}
+}
+`;
+
+fs.mkdirSync(path.dirname(outputPath), { recursive: true });
+fs.writeFileSync(outputPath, output);
+console.log(`Backend client written to ${outputPath} (${endpoints.length} endpoints)`);
@@ -0,0 +1,121 @@
+/**
+ * Billino Desktop – Accessibility Preferences
+ *
+ * Reads the operating system's accessibility settings so the frontend can
+ * follow them without asking the user again:
+ * - reduced motion (Windows "Animationen anzeigen" off, macOS "Bewegung
+ *   reduzieren", GNOME animations off)
+ * - high contrast (Windows contrast themes, macOS "Kontrast erhöhen")
+ * - text scaling (Windows "Text vergrößern", GNOME text scaling factor;
+ *   macOS has no system-wide setting, so 1)
+ *
+ * Changes are reported with `accessibility:changed`. Contrast changes
+ * arrive via nativeTheme; motion and text size have no OS event and are
+ * polled.
+ */
+
+import { execFile } from "child_process";
+import { nativeTheme, systemPreferences } from "electron";
+import log from "electron-log/main";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+
+export interface AccessibilityPrefs {
+  reducedMotion: boolean;
+  highContrast: boolean;
+  /** Text size factor, 1 = 100 % (Windows allows up to 2.25). */
+  textScale: number;
+}
+
+const POLL_INTERVAL_MS = 30_000;
+const COMMAND_TIMEOUT_MS = 5_000;
+
+let current: AccessibilityPrefs = { reducedMotion: false, highContrast: false, textScale: 1 };
+
+function run(command: string, args: string[]): Promise<string | null> {
+  return new Promise((resolve) => {
+    execFile(command, args, { timeout: COMMAND_TIMEOUT_MS, windowsHide: true }, (err, stdout) =>
+      resolve(err ? null : stdout)
+    );
+  });
+}
+
+/**
+ * Text scaling factor of the OS (1 if unknown).
+ */
+async function readTextScale(): Promise<number> {
+  if (process.platform === "win32") {
+    const output = await run("reg", [
+      "query",
+      "HKCU\\Software\\Microsoft\\Accessibility",
+      "/v",
+      "TextScaleFactor",
+    ]);
+    // "    TextScaleFactor    REG_DWORD    0x7d" (percent)
+    const match = output?.match(/TextScaleFactor\s+REG_DWORD\s+0x([0-9a-f]+)/i);
+    return match ? parseInt(match[1], 16) / 100 : 1;
+  }
+  if (process.platform === "linux") {
+    const output = await run("gsettings", [
+      "get",
+      "org.gnome.desktop.interface",
+      "text-scaling-factor",
+    ]);
+    const value = Number(output?.trim());
+    return Number.isFinite(value) && value > 0 ? value : 1;
+  }
+  return 1;
+}
+
+/**
+ * Current OS accessibility settings.
+ */
+export async function readAccessibilityPrefs(): Promise<AccessibilityPrefs> {
+  const animations = systemPreferences.getAnimationSettings();
+  return {
+    reducedMotion: animations.prefersReducedMotion || !animations.shouldRenderRichAnimation,
+    highContrast: nativeTheme.shouldUseHighContrastColors,
+    textScale: Math.round((await readTextScale()) * 100) / 100,
+  };
+}
+
+/**
+ * Last known preferences (updated by the monitor).
+ */
+export function getAccessibilityPrefs(): AccessibilityPrefs {
+  return { ...current };
+}
+
+async function refresh(): Promise<void> {
+  try {
+    const next = await readAccessibilityPrefs();
+    const changed = (Object.keys(next) as Array<keyof AccessibilityPrefs>).some(
+      (key) => next[key] !== current[key]
+    );
+    if (!changed) return;
+    current = next;
+    log.info(
+      `♿ Accessibility: reduced motion ${next.reducedMotion}, high contrast ` +
+        `${next.highContrast}, text scale ${next.textScale}`
+    );
+    emitEvent("accessibility:changed", getAccessibilityPrefs());
+  } catch (err) {
+    log.warn(`⚠️ Could not read accessibility settings: ${err}`);
+  }
+}
+
+/**
+ * Read the preferences once and keep them up to date.
+ */
+export async function startAccessibilityMonitoring(): Promise<void> {
+  await refresh();
+  nativeTheme.on("updated", () => void refresh());
+  setInterval(() => void refresh(), POLL_INTERVAL_MS).unref();
+}
+
+/**
+ * Register IPC handlers for accessibility preferences.
+ */
+export function registerAccessibilityHandlers(): void {
+  handle("get-accessibility-prefs", () => getAccessibilityPrefs(), "read");
+}
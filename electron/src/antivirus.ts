@@ -0,0 +1,177 @@
+/**
+ * Billino Desktop – Antivirus Interference Detection
+ *
+ * PyInstaller executables like billino-backend.exe are a frequent false
+ * positive of antivirus/EDR products. When a product blocks or quarantines
+ * the backend, the spawn fails with a generic error (EACCES/EPERM/UNKNOWN)
+ * or the binary is simply gone. This module recognizes those patterns and
+ * turns them into a BlockedByAntivirusError with step-by-step guidance
+ * instead of a generic start error.
+ */
+
+import { app, dialog } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+
+/** Windows exit codes when the OS/AV refuses to run the image. */
+const BLOCKED_EXIT_CODES = new Map<number, string>([
+  [225, "ERROR_VIRUS_INFECTED"],
+  [226, "ERROR_VIRUS_DELETED"],
+  [0xc0000022, "STATUS_ACCESS_DENIED"],
+]);
+
+/** Spawn errors that typically mean "blocked by a filter driver". */
+const BLOCKED_SPAWN_CODES = new Set(["EACCES", "EPERM", "UNKNOWN", "EBUSY"]);
+
+/** A process dying this fast never got to run any Python code. */
+const EARLY_EXIT_MS = 5_000;
+
+/** Quarantine folders of common products (Windows). */
+const QUARANTINE_LOCATIONS: Array<{ product: string; dir: string }> = [
+  {
+    product: "Microsoft Defender",
+    dir: "%ProgramData%\\Microsoft\\Windows Defender\\Quarantine",
+  },
+  { product: "Avast", dir: "%ProgramData%\\Avast Software\\Avast\\chest" },
+  { product: "AVG", dir: "%ProgramData%\\AVG\\Antivirus\\chest" },
+  { product: "Avira", dir: "%ProgramData%\\Avira\\Antivirus\\INFECTED" },
+  { product: "Kaspersky", dir: "%ProgramData%\\Kaspersky Lab" },
+  { product: "Norton", dir: "%ProgramData%\\Norton" },
+  { product: "ESET", dir: "%LocalAppData%\\ESET\\ESET Security\\Quarantine" },
+  { product: "Bitdefender", dir: "%ProgramFiles%\\Bitdefender" },
+  { product: "G DATA", dir: "%ProgramData%\\G Data" },
+  { product: "Sophos", dir: "%ProgramData%\\Sophos" },
+];
+
+export interface AntivirusProduct {
+  product: string;
+  quarantineDir: string;
+}
+
+export class BlockedByAntivirusError extends Error {
+  readonly code = "BLOCKED_BY_ANTIVIRUS";
+
+  constructor(
+    /** What was observed, e.g. "spawn EPERM" or "binary missing". */
+    readonly reason: string,
+    readonly backendPath: string,
+    /** Products found on this machine (by their quarantine folders). */
+    readonly products: AntivirusProduct[],
+    /** Step-by-step instructions for the user (German). */
+    readonly guidance: string[]
+  ) {
+    super(`Backend blocked by antivirus software (${reason}): ${backendPath}`);
+    this.name = "BlockedByAntivirusError";
+  }
+}
+
+function expandEnv(dir: string): string {
+  return dir.replace(/%([^%]+)%/g, (match, name: string) => {
+    const key = Object.keys(process.env).find((k) => k.toLowerCase() === name.toLowerCase());
+    return key ? (process.env[key] as string) : match;
+  });
+}
+
+/**
+ * Antivirus products whose quarantine folder exists on this machine.
+ */
+export function findAntivirusProducts(): AntivirusProduct[] {
+  if (process.platform !== "win32") return [];
+  return QUARANTINE_LOCATIONS.map(({ product, dir }) => ({
+    product,
+    quarantineDir: expandEnv(dir),
+  }))
+    .filter(({ quarantineDir }) => !quarantineDir.includes("%"))
+    .filter(({ quarantineDir }) => fs.existsSync(quarantineDir));
+}
+
+function buildGuidance(backendPath: string, products: AntivirusProduct[]): string[] {
+  const installDir = path.dirname(backendPath);
+  const names = products.map((p) => p.product).join(", ") || "Ihr Virenschutzprogramm";
+  return [
+    `Öffnen Sie ${names} und prüfen Sie den Schutzverlauf bzw. die Quarantäne.`,
+    `Stellen Sie "${path.basename(backendPath)}" aus der Quarantäne wieder her ` +
+      "(Windows-Sicherheit: Viren- & Bedrohungsschutz → Schutzverlauf → Wiederherstellen).",
+    `Fügen Sie den Ordner "${installDir}" als Ausnahme hinzu ` +
+      "(Windows-Sicherheit: Einstellungen verwalten → Ausschlüsse hinzufügen).",
+    "Falls die Datei fehlt: Installieren Sie Billino nach dem Hinzufügen der " +
+      "Ausnahme erneut.",
+    "Starten Sie Billino anschließend neu.",
+  ];
+}
+
+function blocked(reason: string, backendPath: string): BlockedByAntivirusError {
+  const products = findAntivirusProducts();
+  return new BlockedByAntivirusError(
+    reason,
+    backendPath,
+    products,
+    buildGuidance(backendPath, products)
+  );
+}
+
+/**
+ * Classify a spawn error of the bundled backend.
+ *
+ * @returns A BlockedByAntivirusError if the pattern matches, otherwise null
+ */
+export function detectAntivirusSpawnBlock(
+  err: NodeJS.ErrnoException,
+  backendPath: string
+): BlockedByAntivirusError | null {
+  // Only the bundled executable is a typical AV target (not python in dev)
+  if (!app.isPackaged) return null;
+
+  if (!fs.existsSync(backendPath)) {
+    return blocked(`binary missing after spawn ${err.code ?? "error"}`, backendPath);
+  }
+  if (err.code && BLOCKED_SPAWN_CODES.has(err.code)) {
+    return blocked(`spawn ${err.code}`, backendPath);
+  }
+  return null;
+}
+
+/**
+ * Classify an unexpected exit of the bundled backend shortly after start.
+ *
+ * @param runtimeMs Time between spawn and exit
+ */
+export function detectAntivirusExitBlock(
+  code: number | null,
+  runtimeMs: number,
+  backendPath: string
+): BlockedByAntivirusError | null {
+  if (!app.isPackaged || runtimeMs > EARLY_EXIT_MS) return null;
+
+  if (!fs.existsSync(backendPath)) {
+    return blocked("binary removed right after start", backendPath);
+  }
+  // Node reports NTSTATUS codes as unsigned or negative 32-bit values
+  const exitCode = code === null ? null : code >>> 0;
+  const name = exitCode === null ? undefined : BLOCKED_EXIT_CODES.get(exitCode);
+  return name ? blocked(`exit ${name}`, backendPath) : null;
+}
+
+/**
+ * Check before spawning that the backend executable is (still) in place.
+ */
+export function checkBackendBinary(backendPath: string): BlockedByAntivirusError | null {
+  return app.isPackaged && !fs.existsSync(backendPath)
+    ? blocked("binary missing", backendPath)
+    : null;
+}
+
+/**
+ * Log the detection and show the whitelisting steps to the user.
+ */
+export function showBlockedByAntivirusDialog(err: BlockedByAntivirusError): void {
+  const products = err.products.map((p) => `${p.product} (${p.quarantineDir})`).join(", ");
+  log.error(`🛡️ ${err.message}; detected products: ${products || "none"}`);
+  dialog.showErrorBox(
+    "Billino – Vom Virenschutz blockiert",
+    "Das Billino-Backend wurde vermutlich von einem Virenschutzprogramm " +
+      "blockiert oder in Quarantäne verschoben.\n\n" +
+      err.guidance.map((step, i) => `${i + 1}. ${step}`).join("\n")
+  );
+}
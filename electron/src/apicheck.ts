@@ -0,0 +1,247 @@
+/**
+ * Billino Desktop – Backend API Check
+ *
+ * The shell calls a number of backend endpoints directly (backups, PDFs,
+ * dunning, fiscal year, …). After the backend is healthy its OpenAPI spec
+ * is compared against the endpoints and response fields listed below, so
+ * a shell/backend version mismatch shows up at startup instead of as a
+ * 404 deep inside some feature.
+ *
+ * Missing endpoints or fields are logged and reported with a
+ * `backend:api-drift` event. The check never blocks startup.
+ */
+
+import crypto from "crypto";
+import log from "electron-log/main";
+import { requestBackend } from "./api";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+
+export interface RequiredEndpoint {
+  method: "get" | "post" | "put" | "delete";
+  /** Path as in the spec; parameter names do not matter. */
+  path: string;
+  /** Response fields the shell reads; "items.number" looks inside arrays. */
+  fields?: string[];
+}
+
+export interface ApiDriftIssue {
+  method: string;
+  path: string;
+  /** null: the endpoint itself is missing. */
+  field: string | null;
+}
+
+export interface ApiCheckResult {
+  checkedAt: string;
+  /** False if the spec could not be loaded (nothing was compared). */
+  checked: boolean;
+  ok: boolean;
+  backendVersion: string | null;
+  /** SHA-256 of the spec's paths, to compare reports. */
+  specHash: string | null;
+  missing: ApiDriftIssue[];
+  error: string | null;
+}
+
+/** Endpoints and response fields the shell depends on. */
+export const REQUIRED_ENDPOINTS: RequiredEndpoint[] = [
+  { method: "get", path: "/health", fields: ["status", "ready", "version"] },
+  { method: "post", path: "/shutdown" },
+  { method: "get", path: "/shutdown/pending-work", fields: ["idle"] },
+  { method: "post", path: "/backups/trigger" },
+  { method: "get", path: "/backups/trigger/{job_id}" },
+  { method: "get", path: "/backups/list" },
+  { method: "post", path: "/backups/inspect" },
+  { method: "post", path: "/backups/restore" },
+  { method: "get", path: "/database/stats" },
+  { method: "post", path: "/database/checkpoint" },
+  { method: "put", path: "/database/journal-mode" },
+  { method: "post", path: "/database/reindex" },
+  { method: "post", path: "/database/analyze" },
+  { method: "get", path: "/database/missing-pdfs" },
+  { method: "post", path: "/database/recalculate-totals", fields: ["checked", "corrected"] },
+  { method: "post", path: "/database/cleanup", fields: ["exports", "orphaned_pdfs"] },
+  { method: "post", path: "/exports/anonymized-db" },
+  { method: "get", path: "/exports/{job_id}/download" },
+  { method: "get", path: "/customers/", fields: ["items.id", "items.name", "pageCount"] },
+  { method: "post", path: "/customers/" },
+  { method: "post", path: "/fiscal-years/{year}/archive" },
+  { method: "get", path: "/fiscal-years/{year}/vat-summary" },
+  { method: "get", path: "/fiscal-years/{year}/rollover" },
+  {
+    method: "get",
+    path: "/invoices/",
+    fields: ["items.id", "items.number", "items.date", "items.total_gross", "pageCount"],
+  },
+  { method: "post", path: "/invoices/" },
+  { method: "post", path: "/invoices/number-format/test" },
+  {
+    method: "get",
+    path: "/invoices/{invoice_id}",
+    fields: ["number", "invoice_items.price", "total_gross"],
+  },
+  { method: "get", path: "/invoices/{invoice_id}/attachments" },
+  { method: "post", path: "/invoices/{invoice_id}/attachments", fields: ["id", "sha256"] },
+  { method: "delete", path: "/invoices/{invoice_id}/attachments/{attachment_id}" },
+  { method: "get", path: "/profiles/", fields: ["items.include_tax"] },
+  { method: "post", path: "/pdfs/invoices/{invoice_id}" },
+  { method: "post", path: "/pdfs/invoices/{invoice_id}/sign" },
+  { method: "post", path: "/pdfs/customers/{customer_id}/statement", fields: ["id"] },
+  { method: "get", path: "/pdfs/by-invoice/{invoice_id}", fields: ["id", "content"] },
+  { method: "get", path: "/pdfs/by-summary/{summary_invoice_id}", fields: ["id", "content"] },
+  { method: "get", path: "/pdfs/{pdf_id}", fields: ["id", "content"] },
+  { method: "delete", path: "/pdfs/{pdf_id}" },
+];
+
+type Schema = Record<string, unknown>;
+
+interface OpenApiSpec {
+  info?: { version?: string };
+  paths?: Record<string, Record<string, Schema>>;
+  components?: { schemas?: Record<string, Schema> };
+}
+
+let lastResult: ApiCheckResult | null = null;
+
+/** "/pdfs/{pdf_id}" and "/pdfs/{id}" are the same endpoint. */
+function normalizePath(path: string): string {
+  return path.replace(/\{[^}]+\}/g, "{}");
+}
+
+function resolve(spec: OpenApiSpec, schema: Schema | undefined): Schema | undefined {
+  let current = schema;
+  // Pydantic wraps refs and optionals in allOf/anyOf
+  for (let depth = 0; current && depth < 10; depth++) {
+    const ref = current.$ref as string | undefined;
+    const variants = (current.allOf ?? current.anyOf ?? current.oneOf) as Schema[] | undefined;
+    if (ref) {
+      current = spec.components?.schemas?.[ref.replace("#/components/schemas/", "")];
+    } else if (variants) {
+      current = variants.find((variant) => variant.type !== "null");
+    } else if (current.type === "array") {
+      current = current.items as Schema | undefined;
+    } else {
+      break;
+    }
+  }
+  return current;
+}
+
+function hasField(spec: OpenApiSpec, schema: Schema | undefined, field: string): boolean {
+  let current = resolve(spec, schema);
+  for (const part of field.split(".")) {
+    const properties = current?.properties as Record<string, Schema> | undefined;
+    if (!properties || !(part in properties)) return false;
+    current = resolve(spec, properties[part]);
+  }
+  return true;
+}
+
+function responseSchema(operation: Schema): Schema | undefined {
+  const responses = (operation.responses ?? {}) as Record<string, Schema>;
+  const success = Object.keys(responses).find((code) => code.startsWith("2"));
+  if (!success) return undefined;
+  const content = responses[success].content as Record<string, Schema> | undefined;
+  return content?.["application/json"]?.schema as Schema | undefined;
+}
+
+/**
+ * Endpoints and fields from `required` that are missing in the spec.
+ *
+ * Fields are only checked where the backend declares a response model;
+ * untyped responses cannot be verified.
+ */
+export function findApiDrift(
+  spec: OpenApiSpec,
+  required: RequiredEndpoint[] = REQUIRED_ENDPOINTS
+): ApiDriftIssue[] {
+  const paths = new Map<string, Record<string, Schema>>();
+  for (const [path, item] of Object.entries(spec.paths ?? {})) {
+    paths.set(normalizePath(path), item);
+  }
+
+  const missing: ApiDriftIssue[] = [];
+  for (const endpoint of required) {
+    const method = endpoint.method.toUpperCase();
+    const operation = paths.get(normalizePath(endpoint.path))?.[endpoint.method];
+    if (!operation) {
+      missing.push({ method, path: endpoint.path, field: null });
+      continue;
+    }
+    const schema = responseSchema(operation);
+    if (!schema) continue;
+    for (const field of endpoint.fields ?? []) {
+      if (!hasField(spec, schema, field)) missing.push({ method, path: endpoint.path, field });
+    }
+  }
+  return missing;
+}
+
+function hashPaths(spec: OpenApiSpec): string {
+  const signature = Object.entries(spec.paths ?? {})
+    .flatMap(([path, item]) => Object.keys(item).map((method) => `${method} ${path}`))
+    .sort()
+    .join("\n");
+  return crypto.createHash("sha256").update(signature).digest("hex");
+}
+
+/**
+ * Load the backend's OpenAPI spec and compare it with the endpoints the
+ * shell needs. Emits `backend:api-drift` if anything is missing.
+ */
+export async function checkBackendApi(): Promise<ApiCheckResult> {
+  const checkedAt = new Date().toISOString();
+  try {
+    const spec = await requestBackend<OpenApiSpec>("/openapi.json");
+    const missing = findApiDrift(spec);
+    lastResult = {
+      checkedAt,
+      checked: true,
+      ok: missing.length === 0,
+      backendVersion: spec.info?.version ?? null,
+      specHash: hashPaths(spec),
+      missing,
+      error: null,
+    };
+  } catch (err) {
+    const message = err instanceof Error ? err.message : String(err);
+    log.warn(`⚠️ Backend API check skipped: ${message}`);
+    lastResult = {
+      checkedAt,
+      checked: false,
+      ok: true,
+      backendVersion: null,
+      specHash: null,
+      missing: [],
+      error: message,
+    };
+    return lastResult;
+  }
+
+  if (lastResult.ok) {
+    log.info(`✅ Backend API matches the shell (${REQUIRED_ENDPOINTS.length} endpoints)`);
+  } else {
+    for (const issue of lastResult.missing) {
+      const what = issue.field ? `field "${issue.field}" of ` : "";
+      log.error(`❌ Backend API drift: ${what}${issue.method} ${issue.path} missing`);
+    }
+    emitEvent("backend:api-drift", lastResult);
+  }
+  return lastResult;
+}
+
+/**
+ * Result of the last check, null before the first one.
+ */
+export function getApiCheckResult(): ApiCheckResult | null {
+  return lastResult;
+}
+
+/**
+ * Register IPC handlers for the API check.
+ */
+export function registerApiCheckHandlers(): void {
+  handle("get-api-check", () => getApiCheckResult(), "read");
+  handle("check-backend-api", () => checkBackendApi(), "read");
+}
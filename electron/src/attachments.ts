@@ -0,0 +1,245 @@
+/**
+ * Billino Desktop – Invoice Attachments
+ *
+ * Delivery notes, contracts or receipts can be attached to an invoice.
+ * The shell copies the file into the data directory, stored by content:
+ *
+ *   attachments/<first 2 hex chars>/<sha256><ext>
+ *
+ * and then registers it with the backend, which verifies hash and size.
+ * The same file attached twice is stored once. Backups mirror the folder
+ * and the fiscal-year archive includes the attachments of its invoices.
+ *
+ * Opening looks the attachment up in the backend by id. Only document and
+ * image types (CONTENT_TYPES) are opened with the default program; any
+ * other file (.exe, .bat, .lnk, ...) is only shown in the file manager, so
+ * opening an attachment never runs it.
+ */
+
+import { app, shell } from "electron";
+import crypto from "crypto";
+import fs from "fs";
+import path from "path";
+import { pipeline } from "stream/promises";
+import log from "electron-log/main";
+import { callBackend } from "./api";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+
+export interface InvoiceAttachment {
+  id: number;
+  invoiceId: number;
+  filename: string;
+  sha256: string;
+  sizeBytes: number;
+  contentType: string | null;
+  createdAt: string;
+}
+
+interface RawAttachment {
+  id: number;
+  invoice_id: number;
+  filename: string;
+  sha256: string;
+  size_bytes: number;
+  content_type: string | null;
+  created_at: string;
+}
+
+/** Same limit as other uploads. */
+const MAX_ATTACHMENT_BYTES = 100 * 1024 * 1024;
+
+const CONTENT_TYPES: Record<string, string> = {
+  ".pdf": "application/pdf",
+  ".png": "image/png",
+  ".jpg": "image/jpeg",
+  ".jpeg": "image/jpeg",
+  ".webp": "image/webp",
+  ".txt": "text/plain",
+  ".csv": "text/csv",
+  ".xml": "application/xml",
+  ".docx": "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+  ".xlsx": "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+};
+
+function getAttachmentDir(): string {
+  return path.join(app.getPath("userData"), "attachments");
+}
+
+/**
+ * Where a file with this content is stored (matches the backend).
+ */
+export function attachmentPath(sha256: string, filename: string): string {
+  const ext = path.extname(filename).toLowerCase();
+  return path.join(getAttachmentDir(), sha256.slice(0, 2), sha256 + ext);
+}
+
+function toAttachment(raw: RawAttachment): InvoiceAttachment {
+  return {
+    id: raw.id,
+    invoiceId: raw.invoice_id,
+    filename: raw.filename,
+    sha256: raw.sha256,
+    sizeBytes: raw.size_bytes,
+    contentType: raw.content_type,
+    createdAt: raw.created_at,
+  };
+}
+
+async function hashFile(filePath: string): Promise<string> {
+  const digest = crypto.createHash("sha256");
+  await pipeline(fs.createReadStream(filePath), digest);
+  return digest.digest("hex");
+}
+
+/**
+ * Copy the file into the attachment folder unless its content is there.
+ * Written to a temporary name first, so a half-copied file never carries
+ * a valid hash name.
+ */
+async function storeFile(sourcePath: string, targetPath: string): Promise<void> {
+  if (fs.existsSync(targetPath)) return;
+  fs.mkdirSync(path.dirname(targetPath), { recursive: true });
+  const partPath = `${targetPath}.part`;
+  try {
+    await fs.promises.copyFile(sourcePath, partPath);
+    fs.renameSync(partPath, targetPath);
+  } catch (err) {
+    fs.rmSync(partPath, { force: true });
+    throw err;
+  }
+}
+
+/**
+ * Attach a file to an invoice.
+ *
+ * @param filePath Absolute path of the file to attach
+ * @returns The attachment; the existing one if the file is already attached
+ */
+export async function attachInvoiceFile(
+  invoiceId: number,
+  filePath: string
+): Promise<InvoiceAttachment> {
+  if (!path.isAbsolute(filePath)) {
+    throw new AppError("invalid_input", `Attachment path must be absolute: ${filePath}`);
+  }
+  let sizeBytes: number;
+  try {
+    const stat = fs.statSync(filePath);
+    if (!stat.isFile()) throw new Error("not a file");
+    sizeBytes = stat.size;
+  } catch (err) {
+    throw new AppError("not_found", `Cannot read attachment ${filePath}: ${err}`, {
+      message: "Die Datei konnte nicht gelesen werden.",
+    });
+  }
+  if (sizeBytes > MAX_ATTACHMENT_BYTES) {
+    throw new AppError("invalid_input", `Attachment too large: ${sizeBytes} bytes`, {
+      message: "Die Datei ist zu groß (höchstens 100 MB).",
+    });
+  }
+
+  const filename = path.basename(filePath);
+  const sha256 = await hashFile(filePath);
+  await storeFile(filePath, attachmentPath(sha256, filename));
+
+  const raw = (await callBackend("POST /invoices/{invoice_id}/attachments", {
+    params: { invoice_id: invoiceId },
+    body: {
+      filename,
+      sha256,
+      size_bytes: sizeBytes,
+      content_type: CONTENT_TYPES[path.extname(filename).toLowerCase()] ?? null,
+    },
+  })) as RawAttachment;
+  const attachment = toAttachment(raw);
+  log.info(`📎 ${filename} attached to invoice ${invoiceId} (${sha256.slice(0, 12)})`);
+  emitEvent("attachments:changed", { invoiceId });
+  return attachment;
+}
+
+/**
+ * Attachments of an invoice, oldest first.
+ */
+export async function listInvoiceAttachments(invoiceId: number): Promise<InvoiceAttachment[]> {
+  const raw = (await callBackend("GET /invoices/{invoice_id}/attachments", {
+    params: { invoice_id: invoiceId },
+  })) as RawAttachment[];
+  return raw.map(toAttachment);
+}
+
+/**
+ * Remove an attachment. The backend deletes the file once no invoice
+ * uses it anymore.
+ */
+export async function removeInvoiceAttachment(
+  invoiceId: number,
+  attachmentId: number
+): Promise<void> {
+  await callBackend("DELETE /invoices/{invoice_id}/attachments/{attachment_id}", {
+    params: { invoice_id: invoiceId, attachment_id: attachmentId },
+  });
+  emitEvent("attachments:changed", { invoiceId });
+}
+
+/**
+ * Open an attachment with the system's default program, or show it in the
+ * file manager if its type is not a known document or image type.
+ *
+ * @returns "revealed" if the file was only shown in its folder
+ */
+export async function openInvoiceAttachment(
+  invoiceId: number,
+  attachmentId: number
+): Promise<"opened" | "revealed"> {
+  const attachment = (await listInvoiceAttachments(invoiceId)).find(
+    (candidate) => candidate.id === attachmentId
+  );
+  if (!attachment) {
+    throw new AppError("not_found", `Attachment ${attachmentId} of invoice ${invoiceId}`, {
+      message: "Der Anhang wurde nicht gefunden.",
+    });
+  }
+  const filePath = attachmentPath(attachment.sha256, attachment.filename);
+  if (!/^[0-9a-f]{64}$/.test(attachment.sha256) || !fs.existsSync(filePath)) {
+    throw new AppError("not_found", `Attachment file missing: ${filePath}`, {
+      message: "Die Datei des Anhangs wurde nicht gefunden.",
+    });
+  }
+  if (!(path.extname(filePath).toLowerCase() in CONTENT_TYPES)) {
+    log.warn(`⚠️ Attachment ${attachmentId} (${attachment.filename}) not opened, shown in folder`);
+    shell.showItemInFolder(filePath);
+    return "revealed";
+  }
+  const error = await shell.openPath(filePath);
+  if (error) {
+    throw new AppError("unavailable", `Cannot open ${filePath}: ${error}`, {
+      message: "Für diesen Dateityp ist kein Programm eingerichtet.",
+    });
+  }
+  return "opened";
+}
+
+/**
+ * Register IPC handlers for invoice attachments.
+ */
+export function registerAttachmentHandlers(): void {
+  handle("attach-invoice-file", (_event, invoiceId: number, filePath: string) =>
+    attachInvoiceFile(invoiceId, filePath)
+  );
+  handle(
+    "list-invoice-attachments",
+    (_event, invoiceId: number) => listInvoiceAttachments(invoiceId),
+    "read"
+  );
+  handle(
+    "remove-invoice-attachment",
+    (_event, invoiceId: number, attachmentId: number) =>
+      removeInvoiceAttachment(invoiceId, attachmentId),
+    "destructive"
+  );
+  handle("open-invoice-attachment", (_event, invoiceId: number, attachmentId: number) =>
+    openInvoiceAttachment(invoiceId, attachmentId)
+  );
+}
@@ -0,0 +1,34 @@
+import { app } from "electron";
+import fs from "fs";
+import os from "os";
+import path from "path";
+import { getBackendLogFilePath, writeBackendLogLine } from "./backendlog";
+
+describe("writeBackendLogLine", () => {
+  let dataDir: string;
+
+  beforeEach(() => {
+    dataDir = fs.mkdtempSync(path.join(os.tmpdir(), "billino-backendlog-"));
+    jest.spyOn(app, "getPath").mockReturnValue(dataDir);
+  });
+
+  afterEach(() => {
+    jest.restoreAllMocks();
+    fs.rmSync(dataDir, { recursive: true, force: true });
+  });
+
+  it("schreibt keine IBAN in backend.log", () => {
+    writeBackendLogLine({
+      seq: 1,
+      timestamp: "2025-01-01T12:00:00.000Z",
+      stream: "stdout",
+      level: "info",
+      text: "Profil gespeichert: iban=DE89 3704 0044 0532 0130 00",
+    });
+
+    const content = fs.readFileSync(getBackendLogFilePath(), "utf-8");
+    expect(content).not.toContain("3704 0044");
+    expect(content).toContain("[IBAN …3000]");
+    expect(content).toMatch(/^2025-01-01T12:00:00.000Z INFO +Profil gespeichert/);
+  });
+});
@@ -0,0 +1,127 @@
+/**
+ * Billino Desktop – Backend Log File
+ *
+ * Captured backend output (console.ts) is also written to
+ * AppData/Roaming/Billino/logs/backend.log, so support can read it without
+ * the developer console and independent of the shell log's own rotation.
+ * Lines pass through the PII redaction (redaction.ts) first, like the shell
+ * log.
+ *
+ * The file is rotated by size: once it reaches `backendLogMaxSizeMb`
+ * (BACKEND_LOG_MAX_SIZE, default 5 MB) it becomes backend.1.log, older
+ * files move up by one, and at most `backendLogKeep` files (BACKEND_LOG_KEEP,
+ * default 5) are kept including the active one.
+ *
+ * `open-log-folder` opens the logs folder in the system's file manager.
+ */
+
+import { shell } from "electron";
+import fs from "fs";
+import path from "path";
+import log from "electron-log/main";
+import { getConfig } from "./config";
+import { BackendLogLine, onBackendLogLine } from "./console";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import { getLogDir } from "./logging";
+import { redact } from "./redaction";
+
+const LOG_BASENAME = "backend";
+
+/** Size of the active file; null until it was read once. */
+let currentSize: number | null = null;
+let writeFailed = false;
+
+/**
+ * Path of the active backend log file.
+ */
+export function getBackendLogFilePath(): string {
+  return path.join(getLogDir(), `${LOG_BASENAME}.log`);
+}
+
+function rotatedPath(index: number): string {
+  return path.join(getLogDir(), `${LOG_BASENAME}.${index}.log`);
+}
+
+/**
+ * Shift backend.log → backend.1.log → backend.2.log …, deleting the files
+ * beyond `keep`.
+ */
+export function rotateBackendLog(keep: number = getConfig().backendLogKeep): void {
+  try {
+    for (let index = Math.max(1, keep); fs.existsSync(rotatedPath(index)); index++) {
+      fs.unlinkSync(rotatedPath(index));
+    }
+    for (let index = keep - 1; index >= 1; index--) {
+      const source = index === 1 ? getBackendLogFilePath() : rotatedPath(index - 1);
+      if (fs.existsSync(source)) fs.renameSync(source, rotatedPath(index));
+    }
+    // keep = 1: no archive, start over
+    if (fs.existsSync(getBackendLogFilePath())) fs.unlinkSync(getBackendLogFilePath());
+  } catch (err) {
+    // Never let log rotation break the app – keep appending to the old file
+    log.warn(`⚠️ Backend log rotation failed: ${err}`);
+  }
+  currentSize = null;
+}
+
+function formatLine(line: BackendLogLine): string {
+  const stream = line.stream === "stderr" ? " [stderr]" : "";
+  return `${line.timestamp} ${line.level.toUpperCase().padEnd(7)}${stream} ${redact(line.text)}\n`;
+}
+
+/**
+ * Append one captured line, rotating first if the file is full.
+ */
+export function writeBackendLogLine(line: BackendLogLine): void {
+  const filePath = getBackendLogFilePath();
+  const text = formatLine(line);
+  try {
+    if (currentSize === null) {
+      fs.mkdirSync(getLogDir(), { recursive: true });
+      currentSize = fs.existsSync(filePath) ? fs.statSync(filePath).size : 0;
+    }
+    const bytes = Buffer.byteLength(text);
+    const maxBytes = getConfig().backendLogMaxSizeMb * 1024 * 1024;
+    if (currentSize > 0 && currentSize + bytes > maxBytes) {
+      rotateBackendLog();
+      currentSize = 0;
+    }
+    fs.appendFileSync(filePath, text, "utf-8");
+    currentSize += bytes;
+    writeFailed = false;
+  } catch (err) {
+    // Warn once per failure streak, not for every line
+    if (!writeFailed) log.warn(`⚠️ Could not write backend log ${filePath}: ${err}`);
+    writeFailed = true;
+    currentSize = null;
+  }
+}
+
+/**
+ * Start writing captured backend output to backend.log.
+ */
+export function initBackendLogFile(): void {
+  onBackendLogLine(writeBackendLogLine);
+  log.info(`📝 Backend output is written to ${getBackendLogFilePath()}`);
+}
+
+/**
+ * Open the logs folder in the system's file manager.
+ */
+export async function openLogFolder(): Promise<void> {
+  fs.mkdirSync(getLogDir(), { recursive: true });
+  const error = await shell.openPath(getLogDir());
+  if (error) {
+    throw new AppError("unavailable", `Cannot open ${getLogDir()}: ${error}`, {
+      message: "Der Ordner mit den Protokolldateien konnte nicht geöffnet werden.",
+    });
+  }
+}
+
+/**
+ * Register IPC handlers for the backend log file.
+ */
+export function registerBackendLogHandlers(): void {
+  handle("open-log-folder", () => openLogFolder(), "read");
+}
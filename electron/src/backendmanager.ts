@@ -0,0 +1,258 @@
+/**
+ * Billino Desktop – Backend Manager
+ *
+ * Everything the shell knows about its backend process, in one object
+ * instead of globals spread over main.ts:
+ * - the instance requests are routed to, and old instances still
+ *   draining after a blue-green restart
+ * - the state: starting, running, restarting, crashed or stopped, plus
+ *   how the last instance exited
+ * - which restart is running (plain or blue-green) – only one at a time
+ * - the crash recovery: attempts so far, whether a respawn is underway
+ * - the lifecycle signal, aborted on shutdown so health polls, backoff
+ *   waits and the recovery loop stop instead of running into the quit
+ *
+ * The state follows the process itself, not just the health checks: when
+ * the Python process dies the `exit` event sets "crashed" immediately,
+ * with exit code, signal and the last stderr lines of that instance.
+ * Every change is sent as `backend:state-changed`.
+ *
+ * main.ts creates one manager at startup and passes it to the code that
+ * starts, stops and restarts the backend and to the IPC handlers; events
+ * go through the `emit` function given to the constructor.
+ */
+
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { ProcessHandle } from "./processes";
+
+export type BackendState = "stopped" | "starting" | "running" | "restarting" | "crashed";
+export type RestartKind = "restart" | "blue-green";
+
+export interface BackendExit {
+  exitCode: number | null;
+  signal: string | null;
+  exitedAt: string;
+  uptimeMs: number;
+  /** Last stderr lines of the exited instance (oldest first). */
+  stderrTail: string[];
+}
+
+export interface BackendStatus {
+  state: BackendState;
+  since: string;
+  /** Last unexpected exit, null if the backend never crashed. */
+  lastExit: BackendExit | null;
+}
+
+/** A backend that ran this long has recovered; the attempt count resets. */
+const STABLE_RUN_MS = 5 * 60 * 1000;
+
+export class BackendManager {
+  private current: ProcessHandle | null = null;
+  /** Old instances still finishing requests after a blue-green restart. */
+  private readonly retiring = new Set<ProcessHandle>();
+  private status: BackendStatus = {
+    state: "stopped",
+    since: new Date().toISOString(),
+    lastExit: null,
+  };
+  private runningRestart: RestartKind | null = null;
+  private crashRestarts = 0;
+  private respawning = false;
+  private readonly lifecycle = new AbortController();
+
+  constructor(private readonly emit: typeof emitEvent = emitEvent) {}
+
+  // ─── Instances ─────────────────────────────────────────────────────────────
+
+  /**
+   * The instance requests go to, null if none runs.
+   */
+  get process(): ProcessHandle | null {
+    return this.current;
+  }
+
+  /**
+   * Whether `handle` is the instance requests go to.
+   */
+  isCurrent(handle: ProcessHandle): boolean {
+    return handle === this.current;
+  }
+
+  /**
+   * Route requests to `handle` from now on.
+   *
+   * @returns The previous instance, if any
+   */
+  adopt(handle: ProcessHandle): ProcessHandle | null {
+    const previous = this.current;
+    this.current = handle;
+    return previous;
+  }
+
+  /**
+   * Forget an instance that exited.
+   *
+   * @returns true if it was the current instance (an unexpected exit)
+   */
+  release(handle: ProcessHandle): boolean {
+    this.retiring.delete(handle);
+    if (handle !== this.current) return false;
+    this.current = null;
+    return true;
+  }
+
+  /**
+   * Keep an old instance around until it has drained.
+   */
+  retire(handle: ProcessHandle): void {
+    this.retiring.add(handle);
+  }
+
+  /**
+   * Take a drained instance off the list.
+   *
+   * @returns false if it was already taken (e.g. by `takeAll` on quit)
+   */
+  finishRetiring(handle: ProcessHandle): boolean {
+    return this.retiring.delete(handle);
+  }
+
+  /**
+   * Take all instances, current and draining, to stop them.
+   */
+  takeAll(): ProcessHandle[] {
+    const handles = [...this.retiring];
+    this.retiring.clear();
+    if (this.current) handles.push(this.current);
+    this.current = null;
+    return handles;
+  }
+
+  // ─── State ─────────────────────────────────────────────────────────────────
+
+  /**
+   * Current state of the backend process.
+   */
+  get state(): BackendState {
+    return this.status.state;
+  }
+
+  /**
+   * Switch to `state` and emit `backend:state-changed` (no-op if unchanged).
+   */
+  setState(state: BackendState): void {
+    if (this.status.state === state) return;
+    this.status = { ...this.status, state, since: new Date().toISOString() };
+    this.emit("backend:state-changed", this.status);
+  }
+
+  /**
+   * Remember an unexpected exit and switch to "crashed".
+   */
+  recordCrash(exit: BackendExit): void {
+    this.status = { ...this.status, lastExit: exit };
+    this.setState("crashed");
+  }
+
+  /**
+   * State, time of the last change and the last crash.
+   */
+  getStatus(): BackendStatus {
+    return this.status;
+  }
+
+  // ─── Restarts ──────────────────────────────────────────────────────────────
+
+  /**
+   * The restart in progress, null if none.
+   */
+  get restart(): RestartKind | null {
+    return this.runningRestart;
+  }
+
+  /**
+   * Mark a restart as running; pair with `endRestart()` in a finally.
+   *
+   * @throws AppError if a restart is already running
+   */
+  beginRestart(kind: RestartKind): void {
+    if (this.runningRestart) {
+      throw new AppError("invalid_state", "A backend restart is already running");
+    }
+    this.runningRestart = kind;
+  }
+
+  endRestart(): void {
+    this.runningRestart = null;
+  }
+
+  // ─── Crash Recovery ────────────────────────────────────────────────────────
+
+  /**
+   * Respawns since the last stable run.
+   */
+  get crashAttempts(): number {
+    return this.crashRestarts;
+  }
+
+  /**
+   * Whether the crash recovery is respawning the backend.
+   */
+  get recovering(): boolean {
+    return this.respawning;
+  }
+
+  /**
+   * Start counting anew if the crashed instance had run long enough.
+   */
+  noteCrashedAfter(uptimeMs: number): void {
+    if (uptimeMs >= STABLE_RUN_MS) this.crashRestarts = 0;
+  }
+
+  /**
+   * Count a respawn attempt.
+   *
+   * @returns Its number, starting at 1
+   */
+  nextCrashAttempt(): number {
+    return ++this.crashRestarts;
+  }
+
+  setRecovering(recovering: boolean): void {
+    this.respawning = recovering;
+  }
+
+  // ─── Shutdown ──────────────────────────────────────────────────────────────
+
+  /**
+   * Aborted once the shell shuts down; pass it to anything that waits on
+   * the backend.
+   */
+  get signal(): AbortSignal {
+    return this.lifecycle.signal;
+  }
+
+  /**
+   * Whether the shell is shutting down.
+   */
+  get shuttingDown(): boolean {
+    return this.lifecycle.signal.aborted;
+  }
+
+  /**
+   * Abort in-flight health checks, waits and the crash recovery.
+   */
+  beginShutdown(): void {
+    this.lifecycle.abort();
+  }
+}
+
+/**
+ * Register IPC handlers for the backend state.
+ */
+export function registerBackendStateHandlers(manager: BackendManager): void {
+  handle("get-backend-state", () => manager.getStatus(), "read");
+}
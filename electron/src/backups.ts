@@ -0,0 +1,199 @@
+/**
+ * Billino Desktop – Backup Browser
+ *
+ * Lets the user look into a DB backup (backups/daily/billino_*.db) before
+ * restoring it: schema version, invoice count and date range, and the PDFs
+ * stored in it. The backend opens the file read-only, so inspecting never
+ * changes the backup or the live database.
+ *
+ * Restores can be partial, based on that listing: only the database (the
+ * current PDFs are kept), only the PDFs, or only the PDFs of selected
+ * months. The backend saves the current state before every restore.
+ *
+ * Backup checks can also be queued as persisted tasks (tasks.ts), so a
+ * check interrupted by a quit or crash runs after the next start.
+ */
+
+import path from "path";
+import log from "electron-log/main";
+import { callBackend } from "./api";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import { beginOperation, endOperation } from "./operations";
+import { enqueueTask, QueuedTask, registerTaskKind } from "./tasks";
+
+export interface BackupPdf {
+  filename: string;
+  /** "invoice" or "summary_invoice" */
+  type: string;
+  createdAt: string;
+}
+
+export interface BackupInspection {
+  path: string;
+  filename: string;
+  sizeBytes: number;
+  /** Modification time of the backup file (ISO). */
+  createdAt: string;
+  /** Schema version of the backup (0 = created before versioning). */
+  schemaVersion: number;
+  currentSchemaVersion: number;
+  /** The backup can be restored into this app version. */
+  compatible: boolean;
+  invoiceCount: number;
+  firstInvoiceDate: string | null;
+  lastInvoiceDate: string | null;
+  pdfs: BackupPdf[];
+  /** Months ("YYYY-MM") with PDFs, for selecting a partial restore. */
+  pdfMonths: string[];
+}
+
+/**
+ * - full: database including PDFs
+ * - database: invoices, customers, profiles, ... without PDFs
+ * - pdfs: stored PDFs only (optionally limited to `months`)
+ */
+export type RestoreScope = "full" | "database" | "pdfs";
+
+export interface RestoreOptions {
+  scope?: RestoreScope;
+  /** Months ("YYYY-MM", PDF creation date) to restore; scope "pdfs" only. */
+  months?: string[];
+}
+
+export interface RestoreResult {
+  scope: RestoreScope;
+  backupPath: string;
+  /** Backup of the state before the restore (null if there was no DB). */
+  safetyBackupPath: string | null;
+  restoredPdfs: number;
+  /** PDFs whose invoice does not exist in the restored data. */
+  skippedPdfs: number;
+}
+
+interface RawBackupInspection {
+  path: string;
+  filename: string;
+  size_bytes: number;
+  created_iso: string;
+  schema_version: number;
+  current_schema_version: number;
+  compatible: boolean;
+  invoice_count: number;
+  first_invoice_date: string | null;
+  last_invoice_date: string | null;
+  pdfs: Array<{ filename: string; type: string; created_at: string }>;
+}
+
+interface RawRestoreResult {
+  scope: RestoreScope;
+  backup_path: string;
+  safety_backup_path: string | null;
+  restored_pdfs: number;
+  skipped_pdfs: number;
+}
+
+/**
+ * Describe the contents of a backup file without restoring it.
+ *
+ * @param backupPath Absolute path to the backup (.db)
+ * @throws BackendRequestError 404 if missing, 400 if not a Billino database
+ */
+export async function inspectBackup(backupPath: string): Promise<BackupInspection> {
+  const raw = (await callBackend("POST /backups/inspect", {
+    body: { path: backupPath },
+  })) as RawBackupInspection;
+  return {
+    path: raw.path,
+    filename: raw.filename,
+    sizeBytes: raw.size_bytes,
+    createdAt: raw.created_iso,
+    schemaVersion: raw.schema_version,
+    currentSchemaVersion: raw.current_schema_version,
+    compatible: raw.compatible,
+    invoiceCount: raw.invoice_count,
+    firstInvoiceDate: raw.first_invoice_date,
+    lastInvoiceDate: raw.last_invoice_date,
+    pdfs: raw.pdfs.map((pdf) => ({
+      filename: pdf.filename,
+      type: pdf.type,
+      createdAt: pdf.created_at,
+    })),
+    pdfMonths: [...new Set(raw.pdfs.map((pdf) => pdf.created_at.slice(0, 7)))].sort(),
+  };
+}
+
+/**
+ * Restore a backup completely or partially.
+ *
+ * Registered as a running operation so the window cannot be closed while
+ * the database is being replaced.
+ *
+ * @throws BackendRequestError 400 for invalid options or an incompatible
+ *         backup, 404 if the file is missing
+ */
+export async function restoreBackup(
+  backupPath: string,
+  options: RestoreOptions = {}
+): Promise<RestoreResult> {
+  const scope = options.scope ?? "full";
+  const operation = beginOperation("restore");
+  try {
+    const raw = (await callBackend("POST /backups/restore", {
+      body: { path: backupPath, scope, months: options.months ?? null },
+      // Not aborted with the operation: a half-finished restore is worse
+      timeoutMs: 10 * 60 * 1000,
+    })) as RawRestoreResult;
+    return {
+      scope: raw.scope,
+      backupPath: raw.backup_path,
+      safetyBackupPath: raw.safety_backup_path,
+      restoredPdfs: raw.restored_pdfs,
+      skippedPdfs: raw.skipped_pdfs,
+    };
+  } finally {
+    endOperation(operation.id);
+  }
+}
+
+/**
+ * Queue a check of a backup file; it survives restarts until it ran.
+ *
+ * @param runAt ISO time, default now
+ */
+export function scheduleBackupVerification(backupPath: string, runAt?: string): QueuedTask {
+  return enqueueTask(
+    "verify-backup",
+    `Sicherung ${path.basename(backupPath)} prüfen`,
+    { path: backupPath },
+    { runAt, maxAttempts: 3 }
+  );
+}
+
+async function verifyBackupTask(payload: unknown): Promise<void> {
+  const { path: backupPath } = payload as { path: string };
+  const inspection = await inspectBackup(backupPath);
+  if (!inspection.compatible) {
+    throw new AppError("conflict", `Backup ${backupPath} is not compatible`, {
+      message: `${inspection.filename} ist mit dieser Version nicht lesbar.`,
+    });
+  }
+  log.info(`✅ Backup verified: ${inspection.filename} (${inspection.invoiceCount} invoices)`);
+}
+
+/**
+ * Register IPC handlers for browsing and restoring backups.
+ */
+export function registerBackupHandlers(): void {
+  registerTaskKind("verify-backup", verifyBackupTask);
+  handle(
+    "schedule-backup-verification",
+    (_event, backupPath: string, runAt?: string) => scheduleBackupVerification(backupPath, runAt)
+  );
+  handle("inspect-backup", (_event, backupPath: string) => inspectBackup(backupPath), "read");
+  handle(
+    "restore-backup",
+    (_event, backupPath: string, options?: RestoreOptions) => restoreBackup(backupPath, options),
+    "destructive"
+  );
+}
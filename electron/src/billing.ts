@@ -0,0 +1,398 @@
+/**
+ * Billino Desktop – Billing Commands
+ *
+ * Shell commands around invoicing rules that need the backend database:
+ * - `test-number-format`: render and validate an invoice-number pattern
+ *   (placeholders, no gaps/duplicates against existing numbers) before the
+ *   user adopts it
+ * - `list-customers`, `create-customer`, `get-invoice`, `create-invoice`:
+ *   typed customer/invoice commands with camelCase models that mirror the
+ *   backend schemas
+ *
+ * New customers and invoices are checked before they are sent (name set,
+ * at least one position, positive quantities and prices, tax rate 0–1), so
+ * the user gets a German message naming the field instead of a FastAPI
+ * validation error.
+ */
+
+import { callBackend, requestBackend } from "./api";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+
+export interface NumberFormatTestResult {
+  pattern: string;
+  /** Sample number, or null if the pattern itself is invalid. */
+  rendered: string | null;
+  valid: boolean;
+  errors: string[];
+  warnings: string[];
+  /** Highest existing counter in the sample date's period. */
+  highestCounter: number | null;
+}
+
+interface RawNumberFormatResult {
+  pattern: string;
+  rendered: string | null;
+  valid: boolean;
+  errors: string[];
+  warnings: string[];
+  highest_counter: number | null;
+}
+
+/**
+ * Render and validate an invoice-number pattern.
+ *
+ * @param pattern e.g. "RE-{YYYY}-{N:4}"
+ * @param sampleDate ISO date (YYYY-MM-DD) used for date placeholders
+ * @param counter Counter value to render
+ */
+export async function testNumberFormat(
+  pattern: string,
+  sampleDate: string,
+  counter: number
+): Promise<NumberFormatTestResult> {
+  const raw = await requestBackend<RawNumberFormatResult>("/invoices/number-format/test", {
+    body: { pattern, sample_date: sampleDate, counter },
+  });
+  return {
+    pattern: raw.pattern,
+    rendered: raw.rendered,
+    valid: raw.valid,
+    errors: raw.errors,
+    warnings: raw.warnings,
+    highestCounter: raw.highest_counter,
+  };
+}
+
+// ─── Models ──────────────────────────────────────────────────────────────────
+
+export interface Customer {
+  id: number;
+  name: string;
+  address: string | null;
+  city: string | null;
+  note: string | null;
+}
+
+export type NewCustomer = Omit<Customer, "id">;
+
+export interface CustomerQuery {
+  /** Search in name, address, city and note (at least 2 characters). */
+  search?: string;
+  page?: number;
+  /** 1–100, default 10. */
+  pageSize?: number;
+}
+
+export interface CustomerPage {
+  items: Customer[];
+  total: number;
+  page: number;
+  pageSize: number;
+  pageCount: number;
+}
+
+export interface NewInvoiceItem {
+  quantity: number;
+  description: string;
+  price: number;
+  /** Per-position rate (0–1), null for the invoice rate. */
+  taxRate?: number | null;
+}
+
+export interface InvoiceItem extends Required<NewInvoiceItem> {
+  id: number;
+  invoiceId: number;
+}
+
+export interface NewInvoice {
+  /** ISO date (YYYY-MM-DD). */
+  date: string;
+  customerId: number;
+  profileId: number;
+  totalAmount: number;
+  items: NewInvoiceItem[];
+  /** Omitted: the profile's setting. */
+  includeTax?: boolean;
+  /** Omitted: the profile's rate. */
+  taxRate?: number;
+  isGrossAmount?: boolean;
+}
+
+export interface Invoice {
+  id: number;
+  number: string;
+  date: string;
+  customerId: number;
+  customerName: string | null;
+  profileId: number;
+  profileName: string | null;
+  totalAmount: number;
+  items: InvoiceItem[];
+  includeTax: boolean;
+  taxRate: number;
+  isGrossAmount: boolean;
+  totalNet: number | null;
+  totalTax: number | null;
+  totalGross: number | null;
+}
+
+interface RawCustomer {
+  id: number;
+  name: string;
+  address?: string | null;
+  city?: string | null;
+  note?: string | null;
+}
+
+interface RawCustomerPage {
+  items: RawCustomer[];
+  total: number;
+  page: number;
+  pageSize: number;
+  pageCount: number;
+}
+
+interface RawInvoiceItem {
+  id: number;
+  invoice_id: number;
+  quantity: number;
+  description: string;
+  price: number;
+  tax_rate?: number | null;
+}
+
+interface RawInvoice {
+  id: number;
+  number: string;
+  date: string;
+  customer_id: number;
+  customer_name?: string | null;
+  profile_id: number;
+  profile_name?: string | null;
+  total_amount: number;
+  invoice_items: RawInvoiceItem[];
+  include_tax: boolean;
+  tax_rate: number;
+  is_gross_amount: boolean;
+  total_net?: number | null;
+  total_tax?: number | null;
+  total_gross?: number | null;
+}
+
+function toCustomer(raw: RawCustomer): Customer {
+  return {
+    id: raw.id,
+    name: raw.name,
+    address: raw.address ?? null,
+    city: raw.city ?? null,
+    note: raw.note ?? null,
+  };
+}
+
+function toInvoice(raw: RawInvoice): Invoice {
+  return {
+    id: raw.id,
+    number: raw.number,
+    date: raw.date,
+    customerId: raw.customer_id,
+    customerName: raw.customer_name ?? null,
+    profileId: raw.profile_id,
+    profileName: raw.profile_name ?? null,
+    totalAmount: raw.total_amount,
+    items: raw.invoice_items.map((item) => ({
+      id: item.id,
+      invoiceId: item.invoice_id,
+      quantity: item.quantity,
+      description: item.description,
+      price: item.price,
+      taxRate: item.tax_rate ?? null,
+    })),
+    includeTax: raw.include_tax,
+    taxRate: raw.tax_rate,
+    isGrossAmount: raw.is_gross_amount,
+    totalNet: raw.total_net ?? null,
+    totalTax: raw.total_tax ?? null,
+    totalGross: raw.total_gross ?? null,
+  };
+}
+
+// ─── Validation ──────────────────────────────────────────────────────────────
+
+function invalid(detail: string, message: string): AppError {
+  return new AppError("invalid_input", detail, { message });
+}
+
+function assertId(value: unknown, field: string, label: string): void {
+  if (!Number.isInteger(value) || (value as number) <= 0) {
+    throw invalid(`${field} must be a positive integer: ${String(value)}`, `${label} fehlt.`);
+  }
+}
+
+function assertTaxRate(value: unknown, field: string, label: string): void {
+  if (typeof value !== "number" || !Number.isFinite(value) || value < 0 || value > 1) {
+    throw invalid(
+      `${field} must be between 0 and 1: ${String(value)}`,
+      `${label} muss zwischen 0 und 1 liegen (z. B. 0.19).`
+    );
+  }
+}
+
+/**
+ * Check a new customer before it is sent to the backend.
+ *
+ * @throws AppError("invalid_input") with a German message
+ */
+export function validateNewCustomer(customer: NewCustomer): void {
+  if (typeof customer?.name !== "string" || !customer.name.trim()) {
+    throw invalid("Customer name is empty", "Bitte einen Kundennamen angeben.");
+  }
+}
+
+/**
+ * Check a new invoice before it is sent to the backend.
+ *
+ * @throws AppError("invalid_input") with a German message for the first
+ *         problem found
+ */
+export function validateNewInvoice(invoice: NewInvoice): void {
+  const date = typeof invoice?.date === "string" ? invoice.date : "";
+  const parsed = new Date(`${date}T00:00:00Z`);
+  // The round trip rejects dates like 2025-02-30 that Date would roll over
+  if (Number.isNaN(parsed.getTime()) || parsed.toISOString().slice(0, 10) !== date) {
+    throw invalid(`Invalid invoice date: ${date}`, "Das Rechnungsdatum ist ungültig.");
+  }
+  assertId(invoice.customerId, "customerId", "Der Kunde");
+  assertId(invoice.profileId, "profileId", "Das Profil");
+  if (
+    typeof invoice.totalAmount !== "number" ||
+    !Number.isFinite(invoice.totalAmount) ||
+    invoice.totalAmount < 0
+  ) {
+    throw invalid(
+      `totalAmount must be >= 0: ${String(invoice.totalAmount)}`,
+      "Der Rechnungsbetrag darf nicht negativ sein."
+    );
+  }
+  if (invoice.taxRate !== undefined) assertTaxRate(invoice.taxRate, "taxRate", "Der Steuersatz");
+  if (!Array.isArray(invoice.items) || invoice.items.length === 0) {
+    throw invalid("Invoice has no items", "Die Rechnung braucht mindestens eine Position.");
+  }
+
+  invoice.items.forEach((item, index) => {
+    const position = `Position ${index + 1}`;
+    if (!Number.isInteger(item.quantity) || item.quantity <= 0) {
+      throw invalid(
+        `items[${index}].quantity must be a positive integer: ${String(item.quantity)}`,
+        `${position}: Die Menge muss eine positive ganze Zahl sein.`
+      );
+    }
+    if (typeof item.description !== "string" || !item.description.trim()) {
+      throw invalid(
+        `items[${index}].description is empty`,
+        `${position}: Bitte eine Beschreibung angeben.`
+      );
+    }
+    if (typeof item.price !== "number" || !Number.isFinite(item.price) || item.price <= 0) {
+      throw invalid(
+        `items[${index}].price must be positive: ${String(item.price)}`,
+        `${position}: Der Preis muss größer als 0 sein.`
+      );
+    }
+    if (item.taxRate !== undefined && item.taxRate !== null) {
+      assertTaxRate(item.taxRate, `items[${index}].taxRate`, `${position}: Der Steuersatz`);
+    }
+  });
+}
+
+// ─── Commands ────────────────────────────────────────────────────────────────
+
+/**
+ * One page of customers, optionally filtered by a search term.
+ */
+export async function listCustomers(query: CustomerQuery = {}): Promise<CustomerPage> {
+  const search = query.search?.trim();
+  const raw = (await callBackend("GET /customers/", {
+    query: {
+      // The backend rejects search terms shorter than 2 characters
+      q: search && search.length >= 2 ? search : undefined,
+      page: query.page,
+      pageSize: query.pageSize,
+    },
+  })) as RawCustomerPage;
+  return {
+    items: raw.items.map(toCustomer),
+    total: raw.total,
+    page: raw.page,
+    pageSize: raw.pageSize,
+    pageCount: raw.pageCount,
+  };
+}
+
+/**
+ * Create a customer after validating it.
+ */
+export async function createCustomer(customer: NewCustomer): Promise<Customer> {
+  validateNewCustomer(customer);
+  const raw = (await callBackend("POST /customers/", {
+    body: {
+      name: customer.name.trim(),
+      address: customer.address || null,
+      city: customer.city || null,
+      note: customer.note || null,
+    },
+  })) as RawCustomer;
+  return toCustomer(raw);
+}
+
+/**
+ * A single invoice with its positions and computed totals.
+ */
+export async function getInvoice(invoiceId: number): Promise<Invoice> {
+  assertId(invoiceId, "invoiceId", "Die Rechnung");
+  const raw = (await callBackend("GET /invoices/{invoice_id}", {
+    params: { invoice_id: invoiceId },
+  })) as RawInvoice;
+  return toInvoice(raw);
+}
+
+/**
+ * Create an invoice after validating it. The backend assigns the number.
+ */
+export async function createInvoice(invoice: NewInvoice): Promise<Invoice> {
+  validateNewInvoice(invoice);
+  const raw = (await callBackend("POST /invoices/", {
+    body: {
+      date: invoice.date,
+      customer_id: invoice.customerId,
+      profile_id: invoice.profileId,
+      total_amount: invoice.totalAmount,
+      include_tax: invoice.includeTax,
+      tax_rate: invoice.taxRate,
+      is_gross_amount: invoice.isGrossAmount ?? false,
+      invoice_items: invoice.items.map((item) => ({
+        quantity: item.quantity,
+        description: item.description.trim(),
+        price: item.price,
+        tax_rate: item.taxRate ?? null,
+      })),
+    },
+  })) as RawInvoice;
+  return toInvoice(raw);
+}
+
+/**
+ * Register IPC handlers for billing commands.
+ */
+export function registerBillingHandlers(): void {
+  handle(
+    "test-number-format",
+    (_event, pattern: string, sampleDate: string, counter: number) =>
+      testNumberFormat(pattern, sampleDate, counter),
+    "read"
+  );
+  handle("list-customers", (_event, query?: CustomerQuery) => listCustomers(query), "read");
+  handle("create-customer", (_event, customer: NewCustomer) => createCustomer(customer));
+  handle("get-invoice", (_event, invoiceId: number) => getInvoice(invoiceId), "read");
+  handle("create-invoice", (_event, invoice: NewInvoice) => createInvoice(invoice));
+}
@@ -0,0 +1,306 @@
+/**
+ * Billino Desktop – Bulk Jobs
+ *
+ * Runs one backend call per id for many ids (e.g. regenerating 500 invoice
+ * PDFs) without flooding the backend:
+ * - at most `concurrency` calls at a time (default 4)
+ * - a result per id (done, skipped, failed, cancelled) instead of stopping
+ *   at the first error
+ * - pause/resume (running calls finish, no new ones start) and cancel
+ *   (running calls are aborted)
+ * - throttled `bulk:progress` events with the counters of the job
+ *
+ * Every job is registered as an operation, so closing the window asks
+ * before interrupting it; the operation id is the job id. Job kinds are
+ * registered with `registerBulkJobKind()`.
+ */
+
+import log from "electron-log/main";
+import { BackendRequestError, requestBackend } from "./api";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { abortOperation, beginOperation, endOperation } from "./operations";
+
+export type BulkItemStatus = "done" | "skipped" | "failed" | "cancelled";
+export type BulkJobState = "running" | "paused" | "completed" | "cancelled";
+
+export interface BulkItemResult {
+  id: number;
+  status: BulkItemStatus;
+  error: string | null;
+  durationMs: number;
+}
+
+export interface BulkProgress {
+  jobId: string;
+  kind: string;
+  label: string;
+  state: BulkJobState;
+  total: number;
+  done: number;
+  skipped: number;
+  failed: number;
+  /** Not started because the job was cancelled. */
+  cancelled: number;
+  startedAt: string;
+  finishedAt: string | null;
+}
+
+export interface BulkJobResult extends BulkProgress {
+  items: BulkItemResult[];
+}
+
+export interface BulkJobKind {
+  /** User-facing name, e.g. "PDFs neu erzeugen". */
+  label: string;
+  /**
+   * Process a single id. Resolve "skipped" if there was nothing to do;
+   * throw to mark the item as failed. Must honor `signal` (cancel).
+   */
+  run: (id: number, signal: AbortSignal) => Promise<"done" | "skipped">;
+}
+
+export interface BulkOptions {
+  /** Parallel backend calls (1–8, default 4). */
+  concurrency?: number;
+}
+
+interface BulkJob {
+  progress: BulkProgress;
+  /** Set while paused: wakes the waiting workers (resume or cancel). */
+  resume: (() => void) | null;
+  /** Set while paused: resolves when the job continues. */
+  resumed: Promise<void> | null;
+  lastEmit: number;
+}
+
+const DEFAULT_CONCURRENCY = 4;
+const MAX_CONCURRENCY = 8;
+const PROGRESS_INTERVAL_MS = 250;
+/** PDF generation can take a while for long invoices. */
+const PDF_TIMEOUT_MS = 120_000;
+
+const kinds = new Map<string, BulkJobKind>();
+const jobs = new Map<string, BulkJob>();
+
+/**
+ * Make a job kind available for `runBulk()` and the `run-bulk` command.
+ */
+export function registerBulkJobKind(kind: string, definition: BulkJobKind): void {
+  kinds.set(kind, definition);
+}
+
+function emitProgress(job: BulkJob, force = false): void {
+  const now = Date.now();
+  if (!force && now - job.lastEmit < PROGRESS_INTERVAL_MS) return;
+  job.lastEmit = now;
+  emitEvent("bulk:progress", { ...job.progress });
+}
+
+async function runItem(
+  job: BulkJob,
+  definition: BulkJobKind,
+  id: number,
+  signal: AbortSignal
+): Promise<BulkItemResult> {
+  const start = Date.now();
+  try {
+    const status = await definition.run(id, signal);
+    return { id, status, error: null, durationMs: Date.now() - start };
+  } catch (err) {
+    if (signal.aborted) {
+      return { id, status: "cancelled", error: null, durationMs: Date.now() - start };
+    }
+    const message = err instanceof Error ? err.message : String(err);
+    log.warn(`⚠️ Bulk ${job.progress.kind} #${id} failed: ${message}`);
+    return { id, status: "failed", error: message, durationMs: Date.now() - start };
+  }
+}
+
+/**
+ * Process all ids with bounded concurrency.
+ *
+ * Resolves once every id has a result – also when the job is cancelled
+ * (remaining ids are reported as "cancelled").
+ *
+ * @throws Error for unknown job kinds
+ */
+export async function runBulk(
+  kind: string,
+  ids: number[],
+  options: BulkOptions = {}
+): Promise<BulkJobResult> {
+  const definition = kinds.get(kind);
+  if (!definition) throw new AppError("invalid_input", `Unknown bulk job kind: ${kind}`);
+  const unique = [...new Set(ids)];
+  const concurrency = Math.min(
+    Math.max(Math.floor(options.concurrency ?? DEFAULT_CONCURRENCY), 1),
+    MAX_CONCURRENCY
+  );
+
+  const label = `${definition.label} (${unique.length})`;
+  const { id: jobId, signal } = beginOperation("bulk", label);
+  const job: BulkJob = {
+    progress: {
+      jobId,
+      kind,
+      label,
+      state: "running",
+      total: unique.length,
+      done: 0,
+      skipped: 0,
+      failed: 0,
+      cancelled: 0,
+      startedAt: new Date().toISOString(),
+      finishedAt: null,
+    },
+    resume: null,
+    resumed: null,
+    lastEmit: 0,
+  };
+  jobs.set(jobId, job);
+  // Wake paused workers so they can see the cancellation
+  signal.addEventListener("abort", () => job.resume?.());
+  log.info(`📦 Bulk job started: ${label}, concurrency ${concurrency} [${jobId}]`);
+  emitProgress(job, true);
+
+  const items: BulkItemResult[] = new Array(unique.length);
+  let next = 0;
+  const worker = async (): Promise<void> => {
+    while (next < unique.length) {
+      while (job.resumed && !signal.aborted) await job.resumed;
+      // Another worker may have taken the last id while this one waited
+      if (next >= unique.length) break;
+      const index = next++;
+      const id = unique[index];
+      const result = signal.aborted
+        ? { id, status: "cancelled" as const, error: null, durationMs: 0 }
+        : await runItem(job, definition, id, signal);
+      items[index] = result;
+      job.progress[result.status]++;
+      emitProgress(job);
+    }
+  };
+
+  try {
+    await Promise.all(Array.from({ length: Math.min(concurrency, unique.length) }, worker));
+  } finally {
+    endOperation(jobId);
+    jobs.delete(jobId);
+  }
+
+  const { progress } = job;
+  progress.state = signal.aborted ? "cancelled" : "completed";
+  progress.finishedAt = new Date().toISOString();
+  emitProgress(job, true);
+  log.info(
+    `📦 Bulk job ${progress.state}: ${progress.done} done, ${progress.skipped} skipped, ` +
+      `${progress.failed} failed, ${progress.cancelled} cancelled [${jobId}]`
+  );
+  return { ...progress, items };
+}
+
+function getJob(jobId: string): BulkJob {
+  const job = jobs.get(jobId);
+  if (!job) throw new AppError("not_found", `No running bulk job: ${jobId}`);
+  return job;
+}
+
+/**
+ * Let running calls finish but start no new ones until resumed.
+ */
+export function pauseBulk(jobId: string): void {
+  const job = getJob(jobId);
+  if (job.resumed) return;
+  job.resumed = new Promise((resolve) => {
+    job.resume = () => {
+      job.resume = null;
+      job.resumed = null;
+      resolve();
+    };
+  });
+  job.progress.state = "paused";
+  log.info(`⏸️ Bulk job paused [${jobId}]`);
+  emitProgress(job, true);
+}
+
+/**
+ * Continue a paused job.
+ */
+export function resumeBulk(jobId: string): void {
+  const job = getJob(jobId);
+  if (!job.resume) return;
+  job.resume();
+  job.progress.state = "running";
+  log.info(`▶️ Bulk job resumed [${jobId}]`);
+  emitProgress(job, true);
+}
+
+/**
+ * Cancel a job: running calls are aborted, remaining ids are reported
+ * as cancelled.
+ *
+ * @returns false if no job with this id is running
+ */
+export function cancelBulk(jobId: string): boolean {
+  return jobs.has(jobId) && abortOperation(jobId);
+}
+
+/**
+ * Counters of all running jobs.
+ */
+export function listBulkJobs(): BulkProgress[] {
+  return Array.from(jobs.values()).map((job) => ({ ...job.progress }));
+}
+
+async function createInvoicePdf(invoiceId: number, signal: AbortSignal): Promise<void> {
+  await requestBackend(`/pdfs/invoices/${invoiceId}`, {
+    method: "POST",
+    timeoutMs: PDF_TIMEOUT_MS,
+    signal,
+  });
+}
+
+registerBulkJobKind("create-invoice-pdf", {
+  label: "Rechnungs-PDFs erzeugen",
+  run: async (invoiceId, signal) => {
+    try {
+      await createInvoicePdf(invoiceId, signal);
+      return "done";
+    } catch (err) {
+      // The backend refuses to create a second PDF for an invoice
+      if (err instanceof BackendRequestError && err.status === 400) return "skipped";
+      throw err;
+    }
+  },
+});
+
+registerBulkJobKind("regenerate-invoice-pdf", {
+  label: "Rechnungs-PDFs neu erzeugen",
+  run: async (invoiceId, signal) => {
+    try {
+      const existing = await requestBackend<{ id: number }>(`/pdfs/by-invoice/${invoiceId}`, {
+        signal,
+      });
+      await requestBackend(`/pdfs/${existing.id}`, { method: "DELETE", signal });
+    } catch (err) {
+      if (!(err instanceof BackendRequestError && err.status === 404)) throw err;
+    }
+    await createInvoicePdf(invoiceId, signal);
+    return "done";
+  },
+});
+
+/**
+ * Register IPC handlers for bulk jobs.
+ */
+export function registerBulkHandlers(): void {
+  handle("run-bulk", (_event, kind: string, ids: number[], options?: BulkOptions) =>
+    runBulk(kind, ids, options)
+  );
+  handle("pause-bulk", (_event, jobId: string) => pauseBulk(jobId));
+  handle("resume-bulk", (_event, jobId: string) => resumeBulk(jobId));
+  handle("cancel-bulk", (_event, jobId: string) => cancelBulk(jobId));
+  handle("list-bulk-jobs", () => listBulkJobs(), "read");
+}
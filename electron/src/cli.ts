@@ -0,0 +1,137 @@
+/**
+ * Billino Desktop – Command-Line Arguments
+ *
+ * Supported flags (both `--flag value` and `--flag=value`):
+ *   --port <n>         Backend port (overrides config.toml/.env/env)
+ *   --data-dir <path>  Use a different data directory
+ *   --profile <name>   Use a separate named data profile
+ *   --attach <url>     Use an already running backend instead of spawning one
+ *   --safe-mode        Start without backend, schedulers and plugins (repairs)
+ *   --help             Print usage and exit
+ *
+ * Unknown arguments (Chromium switches, `.` in development) are ignored.
+ */
+
+import { app } from "electron";
+import path from "path";
+import log from "electron-log/main";
+import type { ConfigLayer } from "./config";
+import { canonicalizePath } from "./paths";
+
+export interface CliArgs {
+  port?: string;
+  dataDir?: string;
+  profile?: string;
+  attach?: string;
+  safeMode: boolean;
+  help: boolean;
+}
+
+export const CLI_USAGE = `Billino Desktop
+
+Usage: billino [options]
+
+  --port <n>         Backend port
+  --data-dir <path>  Use a different data directory
+  --profile <name>   Use a separate named data profile
+  --attach <url>     Use an already running backend (e.g. http://127.0.0.1:8000)
+  --safe-mode        Start without backend and schedulers (diagnostics, restore)
+  --help             Show this help
+`;
+
+const VALUE_FLAGS = {
+  "--port": "port",
+  "--data-dir": "dataDir",
+  "--profile": "profile",
+  "--attach": "attach",
+} as const;
+
+/**
+ * Thrown for malformed arguments (missing values, invalid names).
+ */
+export class CliError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "CliError";
+  }
+}
+
+/**
+ * Parse command-line arguments.
+ */
+export function parseCliArgs(argv: string[]): CliArgs {
+  const args: CliArgs = { safeMode: false, help: false };
+
+  for (let i = 0; i < argv.length; i++) {
+    const [flag, inlineValue] = argv[i].split(/=(.*)/s, 2);
+
+    if (flag === "--safe-mode") {
+      args.safeMode = true;
+      continue;
+    }
+    if (flag === "--help" || flag === "-h") {
+      args.help = true;
+      continue;
+    }
+    if (!(flag in VALUE_FLAGS)) continue;
+
+    const value = inlineValue ?? argv[++i];
+    if (value === undefined || value.startsWith("--")) {
+      throw new CliError(`Missing value for ${flag}`);
+    }
+    args[VALUE_FLAGS[flag as keyof typeof VALUE_FLAGS]] = value;
+  }
+
+  if (args.profile !== undefined && !/^[A-Za-z0-9_-]+$/.test(args.profile)) {
+    throw new CliError(
+      `Invalid profile name "${args.profile}" (allowed: letters, digits, "-" and "_")`
+    );
+  }
+  if (args.attach !== undefined && !/^https?:\/\/[^/\s]+\/?$/.test(args.attach)) {
+    throw new CliError(`Invalid --attach URL "${args.attach}" (expected http://host:port)`);
+  }
+
+  return args;
+}
+
+/**
+ * Arguments of the current process (without the executable and, in
+ * development, the app path).
+ */
+export function getProcessArgs(): string[] {
+  return process.argv.slice(app.isPackaged ? 1 : 2);
+}
+
+/**
+ * Apply arguments that change where Billino stores its data.
+ *
+ * Must run before anything reads `userData` (logging, crash reporter).
+ */
+export function applyDataDirArgs(args: CliArgs): void {
+  if (args.dataDir) {
+    app.setPath("userData", canonicalizePath(args.dataDir));
+  }
+  if (args.profile) {
+    app.setPath("userData", path.join(app.getPath("userData"), "profiles", args.profile));
+  }
+}
+
+/**
+ * Configuration values provided on the command line (highest layer).
+ */
+export function cliConfigLayer(args: CliArgs): ConfigLayer {
+  const layer: ConfigLayer = {};
+  if (args.port !== undefined) layer.port = args.port;
+  if (args.attach !== undefined) layer.attachUrl = args.attach.replace(/\/$/, "");
+  return layer;
+}
+
+/**
+ * Log the arguments that influence startup.
+ */
+export function logCliArgs(args: CliArgs): void {
+  const active = Object.entries(args).filter(([, value]) => value !== undefined && value !== false);
+  if (active.length > 0) {
+    log.info(`🧾 CLI arguments: ${active.map(([key, value]) => `${key}=${value}`).join(", ")}`);
+  }
+}
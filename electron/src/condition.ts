@@ -0,0 +1,305 @@
+/**
+ * Billino Desktop – App Condition
+ *
+ * One status object for the renderer's banner instead of the separate
+ * backend, health, queue and API-drift events: `get-app-condition` merges
+ *
+ *   - the backend state and crash recovery (respawn attempts),
+ *   - the backend health (database, degraded mode),
+ *   - the queue depths (PDF retries, tasks, e-mail outbox, backend PDFs),
+ *   - free disk space of the data directory,
+ *   - the system clock, if the NTP check (timesync.ts) found it off,
+ *   - pending updates (DB schema migrations, shell/backend API drift),
+ *
+ * into a list of issues sorted by severity. `level` and `headline` are
+ * those of the most severe issue. While the backend runs the condition is
+ * re-evaluated periodically; `app:condition` is sent when it changes.
+ */
+
+import { app } from "electron";
+import fs from "fs";
+import log from "electron-log/main";
+import { getApiCheckResult } from "./apicheck";
+import type { BackendManager, BackendStatus, RestartKind } from "./backendmanager";
+import { listOutbox } from "./email";
+import { emitEvent } from "./events";
+import { getBackendHealth } from "./health";
+import { handle } from "./ipc";
+import { getPdfQueue } from "./pdfqueue";
+import { isSafeMode } from "./safemode";
+import { listPendingTasks } from "./tasks";
+import { getTimeSyncStatus } from "./timesync";
+
+export type ConditionLevel = "ok" | "info" | "warning" | "error";
+
+export interface ConditionIssue {
+  /** Stable identifier, e.g. "backend_crashed" or "disk_low". */
+  code: string;
+  level: ConditionLevel;
+  /** German text for the banner. */
+  message: string;
+  detail?: string;
+}
+
+export interface AppCondition {
+  /** Level of the most severe issue, "ok" without issues. */
+  level: ConditionLevel;
+  /** German text of the most severe issue, null without issues. */
+  headline: string | null;
+  /** Most severe first. */
+  issues: ConditionIssue[];
+  backend: BackendStatus & {
+    restart: RestartKind | null;
+    recovering: boolean;
+    crashAttempts: number;
+    safeMode: boolean;
+  };
+  queues: {
+    /** PDFs waiting for a retry in the shell. */
+    pdf: number;
+    tasks: number;
+    outbox: number;
+    /** PDF generations running in the backend (null if unknown). */
+    backend: number | null;
+  };
+  disk: {
+    path: string;
+    /** Null if it could not be determined. */
+    freeMb: number | null;
+  };
+  checkedAt: string;
+}
+
+const LEVEL_RANK: Record<ConditionLevel, number> = { ok: 0, info: 1, warning: 2, error: 3 };
+const DISK_WARNING_MB = 500;
+const DISK_ERROR_MB = 100;
+/** Queue length from which waiting work is worth a warning. */
+const QUEUE_WARNING = 20;
+const HEALTH_MAX_AGE_MS = 10_000;
+const WATCH_INTERVAL_MS = 15_000;
+
+let watchTimer: NodeJS.Timeout | null = null;
+let lastFingerprint: string | null = null;
+
+async function freeDiskMb(dir: string): Promise<number | null> {
+  try {
+    const stats = await fs.promises.statfs(dir);
+    return Math.round((stats.bavail * stats.bsize) / 1024 / 1024);
+  } catch (err) {
+    log.warn(`⚠️ Free disk space of ${dir} unknown: ${err}`);
+    return null;
+  }
+}
+
+function backendIssues(manager: BackendManager, status: BackendStatus): ConditionIssue[] {
+  if (manager.recovering) {
+    return [
+      {
+        code: "backend_recovering",
+        level: "error",
+        message: "Das Backend ist abgestürzt und wird neu gestartet.",
+        detail: `Versuch ${manager.crashAttempts}`,
+      },
+    ];
+  }
+  switch (status.state) {
+    case "crashed":
+      return [
+        {
+          code: "backend_crashed",
+          level: "error",
+          message: "Das Backend läuft nicht. Bitte Billino neu starten.",
+          detail: status.lastExit?.stderrTail.at(-1),
+        },
+      ];
+    case "stopped":
+      return [{ code: "backend_stopped", level: "error", message: "Das Backend ist beendet." }];
+    case "starting":
+      return [{ code: "backend_starting", level: "info", message: "Billino wird gestartet …" }];
+    case "restarting":
+      return [
+        {
+          code: "backend_restarting",
+          level: "info",
+          message: "Das Backend wird neu gestartet …",
+        },
+      ];
+    default:
+      return [];
+  }
+}
+
+/**
+ * Evaluate the current condition of the app.
+ *
+ * Never throws; sources that cannot be read are left out.
+ */
+export async function getAppCondition(
+  manager: BackendManager,
+  healthUrl: string
+): Promise<AppCondition> {
+  const status = manager.getStatus();
+  const issues = backendIssues(manager, status);
+
+  if (isSafeMode()) {
+    issues.push({
+      code: "safe_mode",
+      level: "warning",
+      message: "Billino läuft im abgesicherten Modus.",
+    });
+  }
+
+  let backendQueue: number | null = null;
+  if (status.state === "running") {
+    const check = await getBackendHealth(healthUrl, { maxAgeMs: HEALTH_MAX_AGE_MS });
+    if (!check.ok) {
+      issues.push({
+        code: "backend_unreachable",
+        level: "error",
+        message: "Das Backend antwortet nicht.",
+        detail: check.error.message,
+      });
+    } else {
+      const { health } = check;
+      backendQueue = health.queueDepth;
+      if (health.dbStatus === "locked") {
+        issues.push({
+          code: "database_locked",
+          level: "warning",
+          message: "Die Datenbank ist gerade gesperrt; Änderungen können sich verzögern.",
+        });
+      } else if (health.dbStatus === "error") {
+        issues.push({
+          code: "database_error",
+          level: "error",
+          message: "Die Datenbank ist nicht erreichbar.",
+        });
+      } else if (health.status === "degraded") {
+        issues.push({
+          code: "backend_degraded",
+          level: "warning",
+          message: "Das Backend arbeitet eingeschränkt.",
+        });
+      }
+      if ((health.pendingMigrations ?? 0) > 0) {
+        issues.push({
+          code: "migrations_pending",
+          level: "warning",
+          message: "Ein Datenbank-Update steht aus.",
+          detail: `${health.pendingMigrations} fehlende Tabellen/Spalten`,
+        });
+      }
+    }
+  }
+
+  const apiCheck = getApiCheckResult();
+  if (apiCheck?.checked && !apiCheck.ok) {
+    issues.push({
+      code: "api_drift",
+      level: "warning",
+      message: "App und Backend passen nicht zusammen. Bitte Billino aktualisieren.",
+      detail: apiCheck.missing
+        .map((issue) => `${issue.method.toUpperCase()} ${issue.path} ${issue.field ?? ""}`.trim())
+        .join(", "),
+    });
+  }
+
+  const queues = {
+    pdf: getPdfQueue().length,
+    tasks: listPendingTasks().length,
+    outbox: listOutbox().length,
+    backend: backendQueue,
+  };
+  const waiting = queues.pdf + queues.tasks + queues.outbox;
+  if (waiting > 0) {
+    issues.push({
+      code: "queued_work",
+      level: waiting >= QUEUE_WARNING ? "warning" : "info",
+      message: `${waiting} Vorgänge warten auf Ausführung.`,
+      detail: `PDFs ${queues.pdf}, Aufgaben ${queues.tasks}, E-Mails ${queues.outbox}`,
+    });
+  }
+
+  const dataDir = app.getPath("userData");
+  const freeMb = await freeDiskMb(dataDir);
+  if (freeMb !== null && freeMb < DISK_WARNING_MB) {
+    issues.push({
+      code: "disk_low",
+      level: freeMb < DISK_ERROR_MB ? "error" : "warning",
+      message: `Nur noch ${freeMb} MB Speicherplatz frei. Backups und PDFs können fehlschlagen.`,
+      detail: dataDir,
+    });
+  }
+
+  const clock = getTimeSyncStatus();
+  if (clock?.ok === false && clock.offsetMs !== null) {
+    const minutes = Math.round(Math.abs(clock.offsetMs) / 60_000);
+    issues.push({
+      code: "clock_skew",
+      level: "warning",
+      message:
+        `Die Uhr des Computers geht ${minutes} Minuten ${clock.offsetMs > 0 ? "vor" : "nach"}. ` +
+        "Rechnungs- und Fälligkeitsdaten können falsch sein.",
+      detail: `Abweichung ${Math.round(clock.offsetMs / 1000)} s laut ${clock.server}`,
+    });
+  }
+
+  // Stable sort: within a level the order above (backend first) is kept
+  issues.sort((a, b) => LEVEL_RANK[b.level] - LEVEL_RANK[a.level]);
+  const top = issues[0];
+
+  return {
+    level: top?.level ?? "ok",
+    headline: top?.message ?? null,
+    issues,
+    backend: {
+      ...status,
+      restart: manager.restart,
+      recovering: manager.recovering,
+      crashAttempts: manager.crashAttempts,
+      safeMode: isSafeMode(),
+    },
+    queues,
+    disk: { path: dataDir, freeMb },
+    checkedAt: new Date().toISOString(),
+  };
+}
+
+// ─── Change Events ───────────────────────────────────────────────────────────
+
+/** Issue codes and levels; details (counts, stderr) do not trigger an event. */
+function fingerprint(condition: AppCondition): string {
+  return condition.issues.map((issue) => `${issue.code}:${issue.level}`).join("|");
+}
+
+async function publishCondition(manager: BackendManager, healthUrl: string): Promise<void> {
+  try {
+    const condition = await getAppCondition(manager, healthUrl);
+    const current = fingerprint(condition);
+    if (current === lastFingerprint) return;
+    lastFingerprint = current;
+    emitEvent("app:condition", condition);
+  } catch (err) {
+    log.warn(`⚠️ App condition check failed: ${err}`);
+  }
+}
+
+/**
+ * Re-evaluate the condition periodically and send `app:condition` on
+ * changes (call once the backend is healthy).
+ */
+export function startAppConditionWatch(manager: BackendManager, healthUrl: () => string): void {
+  if (watchTimer) return;
+  void publishCondition(manager, healthUrl());
+  watchTimer = setInterval(() => void publishCondition(manager, healthUrl()), WATCH_INTERVAL_MS);
+}
+
+/**
+ * Register IPC handlers for the app condition.
+ */
+export function registerAppConditionHandlers(
+  manager: BackendManager,
+  healthUrl: () => string
+): void {
+  handle("get-app-condition", () => getAppCondition(manager, healthUrl()), "read");
+}
@@ -0,0 +1,134 @@
+import { parseDotEnv, parseToml, resolveEnvTemplates, splitArgs } from "./config";
+
+describe("parseToml", () => {
+  it("liest Abschnitte, Zahlen, Wahrheitswerte und Strings", () => {
+    const values = parseToml(`
+port = 8000
+[backend]
+host = "127.0.0.1"
+workers = 1_000
+reload = false
+`);
+
+    expect(values.get("port")).toBe(8000);
+    expect(values.get("backend.host")).toBe("127.0.0.1");
+    expect(values.get("backend.workers")).toBe(1000);
+    expect(values.get("backend.reload")).toBe(false);
+  });
+
+  it("entfernt Kommentare, aber nicht # in Strings", () => {
+    const values = parseToml(`
+# Kommentarzeile
+[backend] # Abschnitt
+password = "a #b" # Kommentar
+path = 'C:\\Daten\\#1' # Kommentar
+port = 8001# ohne Leerzeichen
+`);
+
+    expect(values.get("backend.password")).toBe("a #b");
+    expect(values.get("backend.path")).toBe("C:\\Daten\\#1");
+    expect(values.get("backend.port")).toBe(8001);
+  });
+
+  it("löst Escapes in Basic-Strings auf, nicht in Literal-Strings", () => {
+    const values = parseToml(`
+quoted = "sag \\"hallo\\" # nicht abschneiden"
+windows = "C:\\\\Billino\\\\"
+unicode = "gr\\u00fcn"
+literal = 'C:\\n\\t'
+`);
+
+    expect(values.get("quoted")).toBe('sag "hallo" # nicht abschneiden');
+    expect(values.get("windows")).toBe("C:\\Billino\\");
+    expect(values.get("unicode")).toBe("grün");
+    expect(values.get("literal")).toBe("C:\\n\\t");
+  });
+});
+
+describe("parseDotEnv", () => {
+  it("liest Variablen mit Anführungszeichen und Kommentaren", () => {
+    const values = parseDotEnv(`
+# Kommentar
+export BACKEND_PORT=8001 # Port
+BACKEND_HOST="0.0.0.0"
+`);
+
+    expect(values.get("BACKEND_PORT")).toBe("8001");
+    expect(values.get("BACKEND_HOST")).toBe("0.0.0.0");
+  });
+});
+
+describe("splitArgs", () => {
+  it("trennt an Leerzeichen und entfernt Anführungszeichen", () => {
+    expect(splitArgs("  --timeout-keep-alive 10   --no-reload ")).toEqual([
+      "--timeout-keep-alive",
+      "10",
+      "--no-reload",
+    ]);
+    expect(splitArgs("--root-path '/billino api'")).toEqual(["--root-path", "/billino api"]);
+    expect(splitArgs("")).toEqual([]);
+  });
+
+  it("hält Windows-Pfade mit Leerzeichen zusammen", () => {
+    expect(splitArgs('--log-config "C:\\Program Files\\Billino\\logging.json"')).toEqual([
+      "--log-config",
+      "C:\\Program Files\\Billino\\logging.json",
+    ]);
+    expect(splitArgs('--log-config="C:\\Users\\Jörg Müller\\log.json" --no-reload')).toEqual([
+      "--log-config=C:\\Users\\Jörg Müller\\log.json",
+      "--no-reload",
+    ]);
+  });
+
+  it("lehnt nicht geschlossene Anführungszeichen ab", () => {
+    expect(() => splitArgs('--log-config "C:\\Program Files')).toThrow(/unterminated quote/);
+  });
+});
+
+describe("resolveEnvTemplates", () => {
+  const placeholders = {
+    DATA_DIR: "/data/billino",
+    PORT: "8001",
+    HOST: "127.0.0.1",
+    APP_VERSION: "2.0.0",
+  };
+
+  it("setzt die eingebauten Platzhalter ein", () => {
+    const env = resolveEnvTemplates(
+      { EXPORT_DIR: "${DATA_DIR}/exports", PUBLIC_URL: "http://${HOST}:${PORT}/v${APP_VERSION}" },
+      placeholders,
+      {}
+    );
+
+    expect(env).toEqual({
+      EXPORT_DIR: "/data/billino/exports",
+      PUBLIC_URL: "http://127.0.0.1:8001/v2.0.0",
+    });
+  });
+
+  it("löst verkettete Verweise und Prozessvariablen auf", () => {
+    const env = resolveEnvTemplates(
+      { REPORT_DIR: "${EXPORT_DIR}/reports", EXPORT_DIR: "${BASE}/exports", BASE: "${HOME}" },
+      placeholders,
+      { HOME: "/home/jorg" }
+    );
+
+    expect(env).toEqual({
+      REPORT_DIR: "/home/jorg/exports/reports",
+      EXPORT_DIR: "/home/jorg/exports",
+      BASE: "/home/jorg",
+    });
+  });
+
+  it("lehnt Zyklen ab", () => {
+    expect(() =>
+      resolveEnvTemplates({ A: "${B}", B: "${C}", C: "${A}" }, placeholders, {})
+    ).toThrow(/placeholder cycle A → B → C → A/);
+  });
+
+  it("lehnt unbekannte Platzhalter ab", () => {
+    expect(() => resolveEnvTemplates({ A: "${NOPE}" }, placeholders, {})).toThrow(
+      /unknown placeholder \$\{NOPE\}/
+    );
+  });
+});
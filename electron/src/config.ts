@@ -0,0 +1,835 @@
+/**
+ * Billino Desktop – Layered Configuration
+ *
+ * The shell's backend configuration is resolved from several layers, each
+ * overriding the previous one:
+ *
+ *   defaults < config.toml < .env < process env < CLI args
+ *
+ * - config.toml: AppData/Roaming/Billino/config.toml (`[backend]` section)
+ * - .env:        AppData/Roaming/Billino/.env (dev: also electron/.env)
+ * - process env: variables of the Electron process itself
+ * - CLI args:    command-line flags passed to the desktop binary
+ *
+ * For every value the winning layer is recorded, logged at startup and
+ * exposed via `get-effective-config` so "where does port 8001 come from?"
+ * has an answer.
+ *
+ * Extra environment variables for the backend go into the `[backend.env]`
+ * section of config.toml. Their values may contain `${NAME}` placeholders,
+ * resolved when the backend is spawned: the built-ins DATA_DIR, PORT,
+ * HOST and APP_VERSION, other entries of the section, and variables of
+ * the Electron process (e.g. `${USERPROFILE}`), in that order.
+ *
+ * `.env.tauri` (from the Tauri shell) in the data directory is legacy: at
+ * startup `migrateLegacyConfig()` moves its settings into config.toml and
+ * keeps a backup of the old file. `.env` is not migrated – it stays a layer
+ * above config.toml.
+ */
+
+import { app } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+
+/**
+ * How the backend port is chosen at startup:
+ * - fixed: `port` or fail
+ * - auto:  `port` if free, otherwise one assigned by the OS
+ * - range: the first free port from `port` to `portRangeEnd`
+ */
+export type PortStrategy = "fixed" | "auto" | "range";
+
+export interface BackendConfig {
+  /** Host the backend binds to. */
+  host: string;
+  /** Port the backend listens on (preferred port for auto/range). */
+  port: number;
+  portStrategy: PortStrategy;
+  /** Last port tried with the "range" strategy. */
+  portRangeEnd: number;
+  /** Max health-check attempts during startup. */
+  healthRetries: number;
+  /** Delay between startup health checks (ms). */
+  healthIntervalMs: number;
+  /** Wait for the shutdown backup before asking the user whether to keep waiting (ms). */
+  shutdownBackupTimeoutMs: number;
+  /** Time for the backend to exit after the shutdown request/SIGTERM before it is killed (ms). */
+  shutdownTimeoutMs: number;
+  /** URL of an already running backend to use instead of spawning one ("" = spawn). */
+  attachUrl: string;
+  /** Respawn the backend after a crash instead of quitting. */
+  autoRestart: boolean;
+  /** Consecutive crash restarts before giving up. */
+  maxRestartAttempts: number;
+  /** Extra uvicorn flags for the backend, e.g. "--timeout-keep-alive 10" (quotes group words). */
+  backendArgs: string;
+  /** Working directory of the backend process ("" = default). */
+  workingDir: string;
+  /** Size at which logs/backend.log is rotated (MB). */
+  backendLogMaxSizeMb: number;
+  /** Backend log files kept, the active one included. */
+  backendLogKeep: number;
+}
+
+export type ConfigSource = "default" | "config.toml" | ".env" | "env" | "cli";
+
+export interface EffectiveConfigEntry {
+  key: keyof BackendConfig;
+  value: BackendConfig[keyof BackendConfig];
+  source: ConfigSource;
+}
+
+type FieldType = "string" | "optionalString" | "port" | "positiveInt" | "boolean" | "choice";
+
+interface FieldSpec {
+  /** Key inside the `[backend]` section of config.toml. */
+  toml: string;
+  /** Variable name in .env / process env. */
+  env: string;
+  type: FieldType;
+  /** Allowed values of a "choice" field. */
+  choices?: readonly string[];
+}
+
+export const DEFAULT_CONFIG: BackendConfig = {
+  host: "127.0.0.1",
+  port: 8000,
+  portStrategy: "auto",
+  portRangeEnd: 8099,
+  healthRetries: 60,
+  healthIntervalMs: 500,
+  shutdownBackupTimeoutMs: 10_000,
+  shutdownTimeoutMs: 5_000,
+  attachUrl: "",
+  autoRestart: true,
+  maxRestartAttempts: 3,
+  backendArgs: "",
+  workingDir: "",
+  backendLogMaxSizeMb: 5,
+  backendLogKeep: 5,
+};
+
+const FIELDS: Record<keyof BackendConfig, FieldSpec> = {
+  host: { toml: "host", env: "BACKEND_HOST", type: "string" },
+  port: { toml: "port", env: "BACKEND_PORT", type: "port" },
+  portStrategy: {
+    toml: "port_strategy",
+    env: "BILLINO_PORT_STRATEGY",
+    type: "choice",
+    choices: ["fixed", "auto", "range"],
+  },
+  portRangeEnd: { toml: "port_range_end", env: "BILLINO_PORT_RANGE_END", type: "port" },
+  healthRetries: { toml: "health_retries", env: "BILLINO_HEALTH_RETRIES", type: "positiveInt" },
+  healthIntervalMs: {
+    toml: "health_interval_ms",
+    env: "BILLINO_HEALTH_INTERVAL_MS",
+    type: "positiveInt",
+  },
+  shutdownBackupTimeoutMs: {
+    toml: "shutdown_backup_timeout_ms",
+    env: "BILLINO_SHUTDOWN_BACKUP_TIMEOUT_MS",
+    type: "positiveInt",
+  },
+  shutdownTimeoutMs: {
+    toml: "shutdown_timeout_ms",
+    env: "BILLINO_SHUTDOWN_TIMEOUT_MS",
+    type: "positiveInt",
+  },
+  attachUrl: { toml: "attach_url", env: "BILLINO_ATTACH_URL", type: "optionalString" },
+  autoRestart: { toml: "auto_restart", env: "BILLINO_AUTO_RESTART", type: "boolean" },
+  maxRestartAttempts: {
+    toml: "max_restart_attempts",
+    env: "BILLINO_MAX_RESTART_ATTEMPTS",
+    type: "positiveInt",
+  },
+  backendArgs: { toml: "backend_args", env: "BILLINO_BACKEND_ARGS", type: "optionalString" },
+  workingDir: { toml: "working_dir", env: "BILLINO_BACKEND_CWD", type: "optionalString" },
+  backendLogMaxSizeMb: {
+    toml: "log_max_size_mb",
+    env: "BACKEND_LOG_MAX_SIZE",
+    type: "positiveInt",
+  },
+  backendLogKeep: { toml: "log_keep", env: "BACKEND_LOG_KEEP", type: "positiveInt" },
+};
+
+/**
+ * Set by the shell itself; not allowed in backendArgs. Workers and log level
+ * come from the backendTuning settings (BACKEND_WORKERS, LOG_LEVEL), which the
+ * backend validates – a flag would bypass that.
+ */
+const MANAGED_BACKEND_FLAGS = ["--host", "--port", "--uds", "--fd", "--workers", "--log-level"];
+
+/**
+ * Raised when a configuration value is invalid. Carries the offending
+ * layer so the user knows which file/variable to fix.
+ */
+export class ConfigError extends Error {
+  constructor(
+    public key: string,
+    public source: ConfigSource,
+    message: string
+  ) {
+    super(`Invalid configuration for "${key}" (from ${source}): ${message}`);
+    this.name = "ConfigError";
+  }
+}
+
+// ─── Layer Parsing ───────────────────────────────────────────────────────────
+
+export type ConfigLayer = Partial<Record<keyof BackendConfig, string | number | boolean>>;
+
+/**
+ * Cut off a `# comment`; a `#` inside a quoted string is part of the value.
+ */
+function stripTomlComment(line: string): string {
+  let quote: string | null = null;
+  for (let i = 0; i < line.length; i++) {
+    const char = line[i];
+    if (quote) {
+      // Basic strings ("…") have escapes, literal strings ('…') do not
+      if (char === "\\" && quote === '"') i++;
+      else if (char === quote) quote = null;
+    } else if (char === '"' || char === "'") {
+      quote = char;
+    } else if (char === "#") {
+      return line.slice(0, i);
+    }
+  }
+  return line;
+}
+
+const TOML_ESCAPES: Record<string, string> = {
+  b: "\b",
+  t: "\t",
+  n: "\n",
+  f: "\f",
+  r: "\r",
+  '"': '"',
+  "\\": "\\",
+};
+
+/**
+ * Resolve the escapes of a basic string (`\"`, `\\`, `\n`, `\u00e4`, …).
+ */
+function unescapeTomlString(value: string): string {
+  return value.replace(/\\(u[0-9A-Fa-f]{4}|U[0-9A-Fa-f]{8}|.)/g, (match, escape: string) =>
+    escape.length > 1
+      ? String.fromCodePoint(parseInt(escape.slice(1), 16))
+      : (TOML_ESCAPES[escape] ?? match)
+  );
+}
+
+/**
+ * Parse the subset of TOML used by config.toml: `[section]` headers and
+ * `key = value` pairs with string, number and boolean values.
+ *
+ * @returns Map of "section.key" → value
+ */
+export function parseToml(content: string): Map<string, string | number | boolean> {
+  const values = new Map<string, string | number | boolean>();
+  let section = "";
+
+  for (const rawLine of content.split(/\r?\n/)) {
+    const line = stripTomlComment(rawLine).trim();
+    if (!line) continue;
+
+    const header = line.match(/^\[([A-Za-z0-9_.-]+)\]$/);
+    if (header) {
+      section = header[1];
+      continue;
+    }
+
+    const pair = line.match(/^([A-Za-z0-9_-]+)\s*=\s*(.+)$/);
+    if (!pair) continue;
+
+    const [, key, rawValue] = pair;
+    let value: string | number | boolean;
+    if (/^"(?:[^"\\]|\\.)*"$/.test(rawValue)) {
+      value = unescapeTomlString(rawValue.slice(1, -1));
+    } else if (/^'[^']*'$/.test(rawValue)) {
+      value = rawValue.slice(1, -1);
+    } else if (rawValue === "true" || rawValue === "false") {
+      value = rawValue === "true";
+    } else if (/^-?\d+(\.\d+)?$/.test(rawValue.replace(/_/g, ""))) {
+      value = Number(rawValue.replace(/_/g, ""));
+    } else {
+      value = rawValue;
+    }
+    values.set(section ? `${section}.${key}` : key, value);
+  }
+
+  return values;
+}
+
+/**
+ * Parse a .env file (`KEY=value`, `#` comments, optional quotes).
+ */
+export function parseDotEnv(content: string): Map<string, string> {
+  const values = new Map<string, string>();
+
+  for (const rawLine of content.split(/\r?\n/)) {
+    const line = rawLine.trim();
+    if (!line || line.startsWith("#")) continue;
+
+    const pair = line.match(/^(?:export\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*=\s*(.*)$/);
+    if (!pair) continue;
+
+    let value = pair[2].trim();
+    if (/^".*"$/.test(value) || /^'.*'$/.test(value)) {
+      value = value.slice(1, -1);
+    } else {
+      value = value.replace(/\s+#.*$/, "");
+    }
+    values.set(pair[1], value);
+  }
+
+  return values;
+}
+
+function readFileIfExists(filePath: string): string | null {
+  try {
+    return fs.existsSync(filePath) ? fs.readFileSync(filePath, "utf-8") : null;
+  } catch (err) {
+    log.warn(`⚠️ Could not read ${filePath}: ${err}`);
+    return null;
+  }
+}
+
+function tomlLayer(): ConfigLayer {
+  const content = readFileIfExists(path.join(app.getPath("userData"), "config.toml"));
+  if (!content) return {};
+
+  const toml = parseToml(content);
+  const layer: ConfigLayer = {};
+  for (const [key, spec] of Object.entries(FIELDS) as [keyof BackendConfig, FieldSpec][]) {
+    const value = toml.get(`backend.${spec.toml}`);
+    if (value !== undefined) layer[key] = value;
+  }
+  return layer;
+}
+
+function backendEnvLayer(): Record<string, string> {
+  const content = readFileIfExists(path.join(app.getPath("userData"), "config.toml"));
+  if (!content) return {};
+
+  const templates: Record<string, string> = {};
+  for (const [key, value] of parseToml(content)) {
+    if (key.startsWith("backend.env.")) templates[key.slice("backend.env.".length)] = String(value);
+  }
+  return templates;
+}
+
+function envFileLayer(): ConfigLayer {
+  const candidates = [path.join(app.getPath("userData"), ".env")];
+  if (!app.isPackaged) {
+    candidates.unshift(path.join(__dirname, "..", ".env"));
+  }
+
+  const merged = new Map<string, string>();
+  for (const candidate of candidates) {
+    const content = readFileIfExists(candidate);
+    if (content) parseDotEnv(content).forEach((value, key) => merged.set(key, value));
+  }
+
+  return envLayer(Object.fromEntries(merged));
+}
+
+function envLayer(env: NodeJS.ProcessEnv | Record<string, string>): ConfigLayer {
+  const layer: ConfigLayer = {};
+  for (const [key, spec] of Object.entries(FIELDS) as [keyof BackendConfig, FieldSpec][]) {
+    const value = env[spec.env];
+    if (value !== undefined && value !== "") layer[key] = value;
+  }
+  return layer;
+}
+
+// ─── Validation ──────────────────────────────────────────────────────────────
+
+function coerce(
+  key: keyof BackendConfig,
+  raw: string | number | boolean,
+  source: ConfigSource
+): string | number | boolean {
+  const { type, choices } = FIELDS[key];
+
+  if (type === "choice") {
+    const value = String(raw).trim().toLowerCase();
+    if (!choices?.includes(value)) {
+      throw new ConfigError(key, source, `expected one of ${choices?.join(", ")}, got "${raw}"`);
+    }
+    return value;
+  }
+
+  if (type === "boolean") {
+    if (typeof raw === "boolean") return raw;
+    const value = String(raw).trim().toLowerCase();
+    if (["true", "1", "yes", "on"].includes(value)) return true;
+    if (["false", "0", "no", "off"].includes(value)) return false;
+    throw new ConfigError(key, source, `expected true or false, got "${raw}"`);
+  }
+
+  if (type === "string" || type === "optionalString") {
+    const value = String(raw).trim();
+    if (!value && type === "string") throw new ConfigError(key, source, "must not be empty");
+    return value;
+  }
+
+  const value = typeof raw === "number" ? raw : Number(String(raw).trim());
+  if (!Number.isInteger(value)) {
+    throw new ConfigError(key, source, `expected an integer, got "${raw}"`);
+  }
+  if (type === "port" && (value < 1024 || value > 65535)) {
+    throw new ConfigError(key, source, `port must be between 1024 and 65535, got ${value}`);
+  }
+  if (type === "positiveInt" && value <= 0) {
+    throw new ConfigError(key, source, `must be > 0, got ${value}`);
+  }
+  return value;
+}
+
+/**
+ * Split a command line into arguments: whitespace separates, double or
+ * single quotes group (no escapes, so Windows paths stay intact).
+ *
+ * @throws Error on an unterminated quote
+ */
+export function splitArgs(value: string): string[] {
+  const args: string[] = [];
+  const pattern = /"([^"]*)"|'([^']*)'|(["'])|(\S+?)(?=\s|"|'|$)|(\s+)/g;
+  let currentArg: string | null = null;
+  for (const match of value.matchAll(pattern)) {
+    const [, doubleQuoted, singleQuoted, openQuote, bare, space] = match;
+    if (openQuote) throw new Error(`unterminated quote in ${value}`);
+    if (space !== undefined) {
+      if (currentArg !== null) args.push(currentArg);
+      currentArg = null;
+      continue;
+    }
+    currentArg = (currentArg ?? "") + (doubleQuoted ?? singleQuoted ?? bare ?? "");
+  }
+  if (currentArg !== null) args.push(currentArg);
+  return args;
+}
+
+function validateBackendArgs(value: string, source: ConfigSource): void {
+  let args: string[];
+  try {
+    args = splitArgs(value);
+  } catch (err) {
+    throw new ConfigError("backendArgs", source, String((err as Error).message));
+  }
+  const managed = args.find((arg) => MANAGED_BACKEND_FLAGS.includes(arg.split("=")[0]));
+  if (managed) {
+    throw new ConfigError("backendArgs", source, `${managed} is set by Billino`);
+  }
+  const stray = args.find((arg, index) => index === 0 && !arg.startsWith("-"));
+  if (stray) {
+    throw new ConfigError("backendArgs", source, `expected flags, got "${stray}"`);
+  }
+}
+
+function validateWorkingDir(value: string, source: ConfigSource): void {
+  if (!value) return;
+  if (!path.isAbsolute(value)) {
+    throw new ConfigError("workingDir", source, `must be an absolute path, got "${value}"`);
+  }
+  if (!fs.existsSync(value) || !fs.statSync(value).isDirectory()) {
+    throw new ConfigError("workingDir", source, `directory does not exist: ${value}`);
+  }
+}
+
+// ─── Resolution ──────────────────────────────────────────────────────────────
+
+function defaultSources(): Record<keyof BackendConfig, ConfigSource> {
+  return Object.fromEntries(
+    Object.keys(DEFAULT_CONFIG).map((key) => [key, "default"])
+  ) as Record<keyof BackendConfig, ConfigSource>;
+}
+
+let current: BackendConfig = { ...DEFAULT_CONFIG };
+let sources = defaultSources();
+/** `[backend.env]` of config.toml, placeholders not yet resolved. */
+let backendEnvTemplates: Record<string, string> = {};
+/** Set when the backend runs on another port than configured. */
+let activePort: number | null = null;
+
+/**
+ * Resolve the configuration from all layers.
+ *
+ * @param cliOverrides Values from command-line arguments (highest priority)
+ * @throws ConfigError if any layer provides an invalid value
+ */
+export function loadConfig(cliOverrides: ConfigLayer = {}): BackendConfig {
+  const layers: Array<[ConfigSource, ConfigLayer]> = [
+    ["config.toml", tomlLayer()],
+    [".env", envFileLayer()],
+    ["env", envLayer(process.env)],
+    ["cli", cliOverrides],
+  ];
+
+  const config = { ...DEFAULT_CONFIG } as Record<keyof BackendConfig, string | number | boolean>;
+  const resolvedSources = defaultSources();
+
+  for (const [source, layer] of layers) {
+    const entries = Object.entries(layer) as [keyof BackendConfig, string | number | boolean][];
+    for (const [key, raw] of entries) {
+      config[key] = coerce(key, raw, source);
+      resolvedSources[key] = source;
+    }
+  }
+
+  if (config.portStrategy === "range" && config.portRangeEnd < config.port) {
+    const message = `must not be below port ${config.port}, got ${config.portRangeEnd}`;
+    throw new ConfigError("portRangeEnd", resolvedSources.portRangeEnd, message);
+  }
+  validateBackendArgs(String(config.backendArgs), resolvedSources.backendArgs);
+  validateWorkingDir(String(config.workingDir), resolvedSources.workingDir);
+
+  const templates = backendEnvLayer();
+  // Unknown placeholders and cycles show up at startup, not at the first spawn
+  resolveEnvTemplates(templates, {
+    DATA_DIR: app.getPath("userData"),
+    PORT: String(config.port),
+    HOST: String(config.host),
+    APP_VERSION: app.getVersion(),
+  });
+
+  current = config as unknown as BackendConfig;
+  sources = resolvedSources;
+  backendEnvTemplates = templates;
+
+  for (const entry of getEffectiveConfig()) {
+    if (entry.source !== "default") {
+      log.info(`⚙️ Config ${entry.key}=${entry.value} (from ${entry.source})`);
+    }
+  }
+
+  return current;
+}
+
+/**
+ * Get the resolved configuration (defaults until loadConfig() ran).
+ */
+export function getConfig(): BackendConfig {
+  return current;
+}
+
+/**
+ * Every configuration value together with the layer it came from.
+ */
+export function getEffectiveConfig(): EffectiveConfigEntry[] {
+  return (Object.keys(current) as (keyof BackendConfig)[]).map((key) => ({
+    key,
+    value: current[key],
+    source: sources[key],
+  }));
+}
+
+/**
+ * Base URL of the backend, e.g. http://127.0.0.1:8000.
+ */
+export function getBackendUrl(): string {
+  return current.attachUrl || `http://${current.host}:${getActivePort()}`;
+}
+
+/**
+ * Port of the backend instance currently serving requests. Differs from
+ * the configured port if it was taken at startup or after a blue-green
+ * restart.
+ */
+export function getActivePort(): number {
+  return activePort ?? current.port;
+}
+
+export interface BackendUrlChange {
+  url: string;
+  previousUrl: string;
+  port: number;
+}
+
+const urlListeners: Array<(change: BackendUrlChange) => void> = [];
+
+/**
+ * Route all further backend requests to another port.
+ *
+ * Everything in the shell that holds on to the backend URL (redirect
+ * rules, caches, …) is updated through `onBackendUrlChanged()` listeners
+ * before `backend:url-changed` goes out, so the renderer never learns of a
+ * URL the shell does not serve yet.
+ */
+export function setActivePort(port: number): void {
+  const previousUrl = getBackendUrl();
+  activePort = port === current.port ? null : port;
+  const url = getBackendUrl();
+  if (url === previousUrl) return;
+
+  const change: BackendUrlChange = { url, previousUrl, port };
+  for (const listener of urlListeners) {
+    try {
+      listener(change);
+    } catch (err) {
+      log.error(`❌ Backend URL listener failed: ${err}`);
+    }
+  }
+  log.info(`🔀 Backend URL changed: ${previousUrl} → ${url}`);
+  emitEvent("backend:url-changed", change);
+}
+
+/**
+ * Register a listener for backend URL changes (port switch).
+ */
+export function onBackendUrlChanged(listener: (change: BackendUrlChange) => void): void {
+  urlListeners.push(listener);
+}
+
+/**
+ * Whether the shell attaches to an existing backend instead of spawning one.
+ */
+export function isAttachedMode(): boolean {
+  return current.attachUrl !== "";
+}
+
+// ─── Backend Environment ─────────────────────────────────────────────────────
+
+/** Values for the built-in placeholders, known at spawn time. */
+export interface EnvPlaceholders {
+  DATA_DIR: string;
+  PORT: string;
+  HOST: string;
+  APP_VERSION: string;
+}
+
+const PLACEHOLDER_PATTERN = /\$\{([A-Za-z_][A-Za-z0-9_]*)\}/g;
+
+/**
+ * Expand `${NAME}` placeholders in env templates.
+ *
+ * @throws ConfigError on unknown placeholders and self-references
+ */
+export function resolveEnvTemplates(
+  templates: Record<string, string>,
+  placeholders: EnvPlaceholders,
+  processEnv: NodeJS.ProcessEnv = process.env
+): Record<string, string> {
+  const resolved = new Map<string, string>();
+
+  const resolve = (name: string, chain: string[]): string => {
+    const done = resolved.get(name);
+    if (done !== undefined) return done;
+    if (chain.includes(name)) {
+      throw new ConfigError(
+        `env.${chain[0]}`,
+        "config.toml",
+        `placeholder cycle ${[...chain, name].join(" → ")}`
+      );
+    }
+    const value = templates[name].replace(PLACEHOLDER_PATTERN, (_match, ref: string) => {
+      if (ref in placeholders) return placeholders[ref as keyof EnvPlaceholders];
+      if (ref in templates) return resolve(ref, [...chain, name]);
+      const fromProcess = processEnv[ref];
+      if (fromProcess !== undefined) return fromProcess;
+      throw new ConfigError(`env.${name}`, "config.toml", `unknown placeholder \${${ref}}`);
+    });
+    resolved.set(name, value);
+    return value;
+  };
+
+  for (const name of Object.keys(templates)) resolve(name, []);
+  return Object.fromEntries(resolved);
+}
+
+/**
+ * The extra backend environment variables with placeholders resolved.
+ *
+ * @param port Port of the instance being spawned
+ */
+export function getBackendEnv(port: number): Record<string, string> {
+  return resolveEnvTemplates(backendEnvTemplates, {
+    DATA_DIR: app.getPath("userData"),
+    PORT: String(port),
+    HOST: current.host,
+    APP_VERSION: app.getVersion(),
+  });
+}
+
+// ─── Legacy Migration ────────────────────────────────────────────────────────
+
+export interface ConfigMigrationResult {
+  /** Legacy files that were converted (empty: nothing to migrate). */
+  files: string[];
+  /** Copies of the legacy files, kept next to them. */
+  backups: string[];
+  /** config.toml keys written (`[backend]` section). */
+  keys: string[];
+  /** Variables that were not migrated (unknown or invalid), with reason. */
+  skipped: string[];
+}
+
+/** Files of earlier shells; `.env` is a live layer and stays where it is. */
+const LEGACY_ENV_FILES = [".env.tauri"];
+
+interface MigratedValue {
+  /** Comment lines directly above the variable. */
+  comments: string[];
+  line: string;
+}
+
+function tomlValue(value: string | number | boolean): string {
+  if (typeof value !== "string") return String(value);
+  return `"${value.replace(/\\/g, "\\\\").replace(/"/g, '\\"')}"`;
+}
+
+/**
+ * Convert the variables of a legacy .env file to config.toml lines.
+ */
+function convertDotEnv(
+  content: string,
+  values: Map<string, MigratedValue>,
+  skipped: string[]
+): void {
+  const byEnv = new Map(
+    (Object.entries(FIELDS) as [keyof BackendConfig, FieldSpec][]).map(([key, spec]) => [
+      spec.env,
+      key,
+    ])
+  );
+  let comments: string[] = [];
+
+  for (const rawLine of content.split(/\r?\n/)) {
+    const line = rawLine.trim();
+    if (!line) {
+      comments = [];
+      continue;
+    }
+    if (line.startsWith("#")) {
+      comments.push(line);
+      continue;
+    }
+
+    const pair = parseDotEnv(line);
+    for (const [name, raw] of pair) {
+      const key = byEnv.get(name);
+      if (!key) {
+        skipped.push(`${name} (no shell setting)`);
+      } else if (raw !== "") {
+        try {
+          const value = coerce(key, raw, ".env");
+          values.set(FIELDS[key].toml, {
+            comments,
+            line: `${FIELDS[key].toml} = ${tomlValue(value)}`,
+          });
+        } catch (err) {
+          skipped.push(`${name} (${err instanceof Error ? err.message : err})`);
+        }
+      }
+    }
+    comments = [];
+  }
+}
+
+/**
+ * Write `values` into the `[backend]` section of a config.toml, replacing
+ * existing keys and keeping everything else as it is.
+ */
+function mergeIntoToml(
+  content: string,
+  values: Map<string, MigratedValue>,
+  header: string
+): string {
+  const lines = content ? content.split(/\r?\n/) : [];
+  const remaining = new Map(values);
+  let section = "";
+  let backendHeader = -1;
+
+  for (let i = 0; i < lines.length; i++) {
+    const line = lines[i].trim();
+    const sectionMatch = line.match(/^\[([A-Za-z0-9_.-]+)\]/);
+    if (sectionMatch) {
+      section = sectionMatch[1];
+      if (section === "backend") backendHeader = i;
+      continue;
+    }
+    const key = line.match(/^([A-Za-z0-9_-]+)\s*=/)?.[1];
+    const value = key !== undefined ? remaining.get(key) : undefined;
+    if (section === "backend" && key !== undefined && value) {
+      lines[i] = [...value.comments, value.line].join("\n");
+      remaining.delete(key);
+    }
+  }
+
+  if (remaining.size > 0) {
+    const block = [
+      header,
+      ...[...remaining.values()].flatMap((value) => [...value.comments, value.line]),
+    ];
+    if (backendHeader >= 0) {
+      lines.splice(backendHeader + 1, 0, ...block);
+    } else {
+      while (lines.length > 0 && !lines[lines.length - 1].trim()) lines.pop();
+      if (lines.length > 0) lines.push("");
+      lines.push("[backend]", ...block);
+    }
+  }
+
+  const merged = lines.join("\n");
+  return merged.endsWith("\n") ? merged : `${merged}\n`;
+}
+
+/**
+ * Move the settings of a legacy `.env.tauri` file in the data directory
+ * into config.toml. The old file is copied to `<name>.<timestamp>.bak` and
+ * then removed, so this runs only once. `.env` is left alone: it still
+ * overrides config.toml.
+ */
+export function migrateLegacyConfig(): ConfigMigrationResult {
+  const userData = app.getPath("userData");
+  const result: ConfigMigrationResult = { files: [], backups: [], keys: [], skipped: [] };
+  const values = new Map<string, MigratedValue>();
+
+  for (const name of LEGACY_ENV_FILES) {
+    const filePath = path.join(userData, name);
+    const content = readFileIfExists(filePath);
+    if (content === null) continue;
+    convertDotEnv(content, values, result.skipped);
+    result.files.push(filePath);
+  }
+  if (result.files.length === 0) return result;
+
+  const stamp = new Date().toISOString().replace(/[-:]/g, "").replace("T", "-").slice(0, 15);
+  for (const filePath of result.files) {
+    const backup = `${filePath}.${stamp}.bak`;
+    fs.copyFileSync(filePath, backup);
+    result.backups.push(backup);
+  }
+
+  if (values.size > 0) {
+    const tomlPath = path.join(userData, "config.toml");
+    const names = result.files.map((filePath) => path.basename(filePath)).join(", ");
+    const header = `# Migrated from ${names} on ${new Date().toISOString().slice(0, 10)}`;
+    const merged = mergeIntoToml(readFileIfExists(tomlPath) ?? "", values, header);
+    fs.writeFileSync(`${tomlPath}.tmp`, merged, "utf-8");
+    fs.renameSync(`${tomlPath}.tmp`, tomlPath);
+    result.keys = [...values.keys()];
+  }
+
+  for (const filePath of result.files) fs.unlinkSync(filePath);
+
+  log.info(
+    `⚙️ Migrated legacy config ${result.files.join(", ")} → config.toml ` +
+      `(${result.keys.length} keys, backups: ${result.backups.join(", ")})`
+  );
+  for (const skipped of result.skipped) log.warn(`⚠️ Legacy config not migrated: ${skipped}`);
+  return result;
+}
+
+/**
+ * Register the IPC handlers for the configuration.
+ */
+export function registerConfigHandlers(): void {
+  handle("get-effective-config", () => getEffectiveConfig(), "read");
+  handle("get-backend-url", () => getBackendUrl(), "read");
+  handle("migrate-legacy-config", () => migrateLegacyConfig());
+}
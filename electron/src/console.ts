@@ -0,0 +1,267 @@
+/**
+ * Billino Desktop – Developer Console
+ *
+ * Captures the backend's stdout/stderr line by line into a ring buffer and
+ * shows it live in a separate "Entwicklerkonsole" window with level filter
+ * and search – without starting the backend manually in a terminal.
+ *
+ * Every line also goes to the app log ("[backend] …", "[backend:err] …")
+ * at its own level, so backend output survives in production log files.
+ * The last lines are available to diagnostics panels via
+ * `get-backend-logs`.
+ *
+ * The window is toggled with Ctrl+Shift+L in the main window or via the
+ * `toggle-developer-console` command. It is a secondary window and only
+ * reads the log.
+ */
+
+import { BrowserWindow, WebContents } from "electron";
+import path from "path";
+import log from "electron-log/main";
+import { handle } from "./ipc";
+import { registerWindow } from "./windows";
+
+export type BackendLogLevel = "debug" | "info" | "warning" | "error";
+
+export interface BackendLogLine {
+  /** Monotonic line number (for incremental fetching). */
+  seq: number;
+  timestamp: string;
+  stream: "stdout" | "stderr";
+  level: BackendLogLevel;
+  text: string;
+}
+
+export interface BackendLogQuery {
+  /** Minimum level (default: debug = everything). */
+  level?: BackendLogLevel;
+  /** Case-insensitive substring. */
+  search?: string;
+  /** Only lines with seq greater than this. */
+  afterSeq?: number;
+  /** Return at most this many (newest) lines. */
+  limit?: number;
+}
+
+const MAX_LINES = 5_000;
+const LEVEL_ORDER: BackendLogLevel[] = ["debug", "info", "warning", "error"];
+
+const lines: BackendLogLine[] = [];
+const partial: Record<BackendLogLine["stream"], string> = { stdout: "", stderr: "" };
+let nextSeq = 1;
+let lastLevel: BackendLogLevel = "info";
+let consoleWindow: BrowserWindow | null = null;
+const lineListeners: Array<(line: BackendLogLine) => void> = [];
+
+/**
+ * Level of a backend line: "[INFO] [billino] ..." (our logger),
+ * "INFO:     ..." (uvicorn). Continuation lines (tracebacks, indented
+ * output) inherit the level of the previous line.
+ */
+function parseLevel(text: string, stream: BackendLogLine["stream"]): BackendLogLevel {
+  const match = /^\[?(DEBUG|INFO|WARNING|WARN|ERROR|CRITICAL)\]?:?\s/.exec(text);
+  if (match) {
+    const name = match[1];
+    if (name === "WARN") return "warning";
+    if (name === "CRITICAL") return "error";
+    return name.toLowerCase() as BackendLogLevel;
+  }
+  if (/^(\s|Traceback )/.test(text)) return lastLevel;
+  return stream === "stderr" ? "warning" : "info";
+}
+
+const APP_LOG: Record<BackendLogLevel, (message: string) => void> = {
+  debug: (message) => log.debug(message),
+  info: (message) => log.info(message),
+  warning: (message) => log.warn(message),
+  error: (message) => log.error(message),
+};
+
+/**
+ * Feed a decoded chunk of backend output. Incomplete lines are kept until
+ * the rest arrives.
+ */
+export function captureBackendOutput(stream: BackendLogLine["stream"], chunk: string): void {
+  const parts = (partial[stream] + chunk).split(/\r?\n/);
+  partial[stream] = parts.pop() ?? "";
+
+  const added: BackendLogLine[] = [];
+  for (const text of parts) {
+    if (!text.trim()) continue;
+    const level = parseLevel(text, stream);
+    lastLevel = level;
+    added.push({ seq: nextSeq++, timestamp: new Date().toISOString(), stream, level, text });
+  }
+  if (added.length === 0) return;
+
+  const prefix = stream === "stderr" ? "[backend:err]" : "[backend]";
+  for (const line of added) APP_LOG[line.level](`${prefix} ${line.text}`);
+
+  lines.push(...added);
+  if (lines.length > MAX_LINES) lines.splice(0, lines.length - MAX_LINES);
+  for (const line of added) lineListeners.forEach((listener) => listener(line));
+
+  // Only the console window listens; don't flood the main renderer
+  if (consoleWindow && !consoleWindow.isDestroyed()) {
+    consoleWindow.webContents.send("backend:log", added);
+  }
+}
+
+/**
+ * Subscribe to every captured backend line.
+ */
+export function onBackendLogLine(listener: (line: BackendLogLine) => void): void {
+  lineListeners.push(listener);
+}
+
+/**
+ * Sequence number of the newest captured line (0 before the first).
+ */
+export function getLastBackendLogSeq(): number {
+  return nextSeq - 1;
+}
+
+/**
+ * The last `limit` stderr lines captured after `afterSeq`.
+ */
+export function getBackendStderrTail(limit: number, afterSeq = 0): string[] {
+  return lines
+    .filter((line) => line.stream === "stderr" && line.seq > afterSeq)
+    .slice(-limit)
+    .map((line) => line.text);
+}
+
+/**
+ * Captured lines matching the query (oldest first).
+ */
+export function getBackendLog(query: BackendLogQuery = {}): BackendLogLine[] {
+  const minLevel = LEVEL_ORDER.indexOf(query.level ?? "debug");
+  const search = query.search?.toLowerCase();
+  const result = lines.filter(
+    (line) =>
+      line.seq > (query.afterSeq ?? 0) &&
+      LEVEL_ORDER.indexOf(line.level) >= minLevel &&
+      (!search || line.text.toLowerCase().includes(search))
+  );
+  return query.limit ? result.slice(-query.limit) : result;
+}
+
+const CONSOLE_HTML = `<!DOCTYPE html>
+<html lang="de">
+<head>
+<meta charset="utf-8">
+<meta http-equiv="Content-Security-Policy"
+  content="default-src 'none'; style-src 'unsafe-inline'; script-src 'unsafe-inline'">
+<title>Billino – Entwicklerkonsole</title>
+<style>
+  body { margin: 0; font: 12px Consolas, monospace; background: #1e1e1e; color: #ddd;
+    display: flex; flex-direction: column; height: 100vh; }
+  header { display: flex; gap: 8px; padding: 6px; background: #2d2d2d; }
+  header input { flex: 1; }
+  #log { flex: 1; overflow: auto; margin: 0; padding: 6px; white-space: pre-wrap; }
+  .debug { color: #888; } .warning { color: #e5c07b; } .error { color: #e06c75; }
+</style>
+</head>
+<body>
+<header>
+  <select id="level">
+    <option value="debug">Alle</option><option value="info">Info</option>
+    <option value="warning">Warnungen</option><option value="error">Fehler</option>
+  </select>
+  <input id="search" type="search" placeholder="Suchen…">
+  <label><input id="follow" type="checkbox" checked> Mitlaufen</label>
+  <button id="clear">Leeren</button>
+</header>
+<pre id="log"></pre>
+<script>
+  const order = ["debug", "info", "warning", "error"];
+  const log = document.getElementById("log");
+  const level = document.getElementById("level");
+  const search = document.getElementById("search");
+  const follow = document.getElementById("follow");
+  let lastSeq = 0;
+  let clearedAt = 0;
+  const matches = (line) =>
+    order.indexOf(line.level) >= order.indexOf(level.value) &&
+    (!search.value || line.text.toLowerCase().includes(search.value.toLowerCase()));
+  const append = (batch) => {
+    if (batch.length) lastSeq = batch[batch.length - 1].seq;
+    for (const line of batch.filter(matches)) {
+      const row = document.createElement("div");
+      row.className = line.level;
+      row.textContent = line.timestamp.slice(11, 23) + "  " + line.text;
+      log.appendChild(row);
+    }
+    while (log.childElementCount > ${MAX_LINES}) log.firstChild.remove();
+    if (follow.checked) log.scrollTop = log.scrollHeight;
+  };
+  const reload = async () => {
+    log.textContent = "";
+    append(await window.billino.getBackendLog({
+      level: level.value, search: search.value, afterSeq: clearedAt,
+    }));
+  };
+  level.onchange = reload;
+  search.oninput = reload;
+  // Only clears the view – the captured lines stay available to others
+  document.getElementById("clear").onclick = () => {
+    clearedAt = lastSeq;
+    log.textContent = "";
+  };
+  window.billino.onBackendLog(append);
+  reload();
+</script>
+</body>
+</html>`;
+
+/**
+ * Open the developer console, or close it if it is already open.
+ */
+export function toggleDeveloperConsole(): void {
+  if (consoleWindow && !consoleWindow.isDestroyed()) {
+    consoleWindow.close();
+    return;
+  }
+
+  consoleWindow = new BrowserWindow({
+    width: 1000,
+    height: 600,
+    title: "Billino – Entwicklerkonsole",
+    icon: path.join(__dirname, "..", "icons", "icon.ico"),
+    autoHideMenuBar: true,
+    webPreferences: {
+      preload: path.join(__dirname, "preload.js"),
+      nodeIntegration: false,
+      contextIsolation: true,
+    },
+  });
+  registerWindow(consoleWindow, "secondary", () => {
+    consoleWindow = null;
+  });
+  consoleWindow.loadURL(`data:text/html;charset=utf-8,${encodeURIComponent(CONSOLE_HTML)}`);
+}
+
+/**
+ * Toggle the console with Ctrl+Shift+L from the given window.
+ */
+export function bindDeveloperConsoleShortcut(contents: WebContents): void {
+  contents.on("before-input-event", (event, input) => {
+    if (input.type === "keyDown" && input.control && input.shift && input.key === "L") {
+      event.preventDefault();
+      toggleDeveloperConsole();
+    }
+  });
+}
+
+/**
+ * Register IPC handlers for the developer console.
+ */
+export function registerConsoleHandlers(): void {
+  handle("get-backend-log", (_event, query?: BackendLogQuery) => getBackendLog(query), "read");
+  handle(
+    "get-backend-logs",
+    (_event, count?: number) => getBackendLog({ limit: Math.min(count ?? 200, MAX_LINES) }),
+    "read"
+  );
+  handle("toggle-developer-console", () => toggleDeveloperConsole());
+}
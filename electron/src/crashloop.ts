@@ -0,0 +1,168 @@
+/**
+ * Billino Desktop – Crash-Loop Detection
+ *
+ * Every normal launch is tracked in AppData/Roaming/Billino/launches.json:
+ * it is marked "starting" right away and "ok" once the backend is
+ * healthy. A launch counts as failed if startup fails, if the backend
+ * crashes and cannot be recovered, or if the app dies before the backend
+ * became healthy (the marker is still "starting" at the next launch).
+ *
+ * After CRASH_LOOP_THRESHOLD failed launches in a row Billino starts in
+ * safe mode instead of repeating the same failing sequence; the safe-mode
+ * window shows why. Leaving safe mode ("Normal neu starten") allows one
+ * more attempt – if that fails too, the next launch is safe mode again.
+ * Launches in safe mode are not tracked.
+ */
+
+import { app } from "electron";
+import fs from "fs";
+import path from "path";
+import log from "electron-log/main";
+import { handle } from "./ipc";
+
+/** "cancelled": quit by the user while still starting – not a failure. */
+export type LaunchOutcome = "starting" | "ok" | "failed" | "crashed" | "cancelled";
+
+export interface LaunchRecord {
+  startedAt: string;
+  outcome: LaunchOutcome;
+  /** Why the launch failed, null otherwise. */
+  reason: string | null;
+  version: string;
+}
+
+export interface LaunchHistory {
+  /** Failed launches in a row, the current one not included. */
+  consecutiveFailures: number;
+  /** Whether this launch was switched to safe mode because of them. */
+  crashLoopDetected: boolean;
+  /** Newest first. */
+  launches: LaunchRecord[];
+}
+
+interface LaunchState {
+  consecutiveFailures: number;
+  launches: LaunchRecord[];
+}
+
+const CRASH_LOOP_THRESHOLD = 3;
+const MAX_RECORDS = 10;
+
+let crashLoopDetected = false;
+let current: LaunchRecord | null = null;
+
+function getStatePath(): string {
+  return path.join(app.getPath("userData"), "launches.json");
+}
+
+function loadState(): LaunchState {
+  try {
+    const state = JSON.parse(fs.readFileSync(getStatePath(), "utf-8")) as Partial<LaunchState>;
+    return {
+      consecutiveFailures: state.consecutiveFailures ?? 0,
+      launches: Array.isArray(state.launches) ? state.launches : [],
+    };
+  } catch {
+    return { consecutiveFailures: 0, launches: [] };
+  }
+}
+
+function saveState(state: LaunchState): void {
+  try {
+    state.launches = state.launches.slice(0, MAX_RECORDS);
+    fs.mkdirSync(path.dirname(getStatePath()), { recursive: true });
+    fs.writeFileSync(getStatePath(), JSON.stringify(state, null, 2), "utf-8");
+  } catch (err) {
+    log.warn(`⚠️ Could not save launch state: ${err}`);
+  }
+}
+
+/**
+ * Account for the previous launch and track this one.
+ *
+ * @returns True if the last launches failed in a row and this one should
+ *          run in safe mode (it is then not tracked)
+ */
+export function checkStartupCrashLoop(): boolean {
+  const state = loadState();
+  const previous = state.launches[0];
+  if (previous?.outcome === "starting") {
+    previous.outcome = "crashed";
+    previous.reason = "Billino wurde beendet, bevor der Start abgeschlossen war.";
+    state.consecutiveFailures += 1;
+  }
+
+  if (state.consecutiveFailures >= CRASH_LOOP_THRESHOLD) {
+    crashLoopDetected = true;
+    log.warn(
+      `🔁 The last ${state.consecutiveFailures} launches failed – starting in safe mode ` +
+        `(last reason: ${previous?.reason ?? "unknown"})`
+    );
+    // One more failed normal launch brings safe mode back
+    state.consecutiveFailures = CRASH_LOOP_THRESHOLD - 1;
+    saveState(state);
+    return true;
+  }
+
+  current = {
+    startedAt: new Date().toISOString(),
+    outcome: "starting",
+    reason: null,
+    version: app.getVersion(),
+  };
+  state.launches.unshift(current);
+  saveState(state);
+  return false;
+}
+
+function finishLaunch(outcome: LaunchOutcome, reason: string | null): void {
+  if (!current || current.outcome === outcome) return;
+  const state = loadState();
+  const record = state.launches.find((launch) => launch.startedAt === current?.startedAt);
+  current.outcome = outcome;
+  current.reason = reason;
+  if (record) Object.assign(record, current);
+  if (outcome === "ok") state.consecutiveFailures = 0;
+  else if (outcome !== "cancelled") state.consecutiveFailures += 1;
+  saveState(state);
+}
+
+/**
+ * Mark this launch as successful (the backend is healthy).
+ */
+export function markLaunchSucceeded(): void {
+  finishLaunch("ok", null);
+}
+
+/**
+ * Mark this launch as failed (startup error, unrecoverable backend crash).
+ */
+export function markLaunchFailed(reason: string): void {
+  finishLaunch("failed", reason);
+}
+
+/**
+ * Note a regular quit; a launch still starting is then not a failure.
+ */
+export function markLaunchQuit(): void {
+  if (current?.outcome === "starting") finishLaunch("cancelled", null);
+}
+
+/**
+ * Tracked launches and whether safe mode was entered because of them.
+ */
+export function getLaunchHistory(): LaunchHistory {
+  const state = loadState();
+  return {
+    consecutiveFailures: state.consecutiveFailures,
+    crashLoopDetected,
+    launches: state.launches,
+  };
+}
+
+/**
+ * Register IPC handlers for the launch history.
+ */
+export function registerCrashLoopHandlers(): void {
+  handle("get-launch-history", () => getLaunchHistory(), "read");
+}
@@ -0,0 +1,161 @@
+/**
+ * Billino Desktop – Crash Report Bundle
+ *
+ * Collects what support needs after a backend crash into one ZIP that
+ * users attach to a ticket:
+ *
+ *   report.json   app/OS info, backend state and last exit (code, signal,
+ *                 stderr tail)
+ *   backend.log   the last 500 captured backend log lines
+ *   config.json   the effective BackendConfig with the source of each value
+ *   health.json   the recent health-check results
+ *
+ * Everything passes through PII redaction (redaction.ts); credentials in
+ * the attach URL are removed. Bundles are written to
+ * AppData/Roaming/Billino/crash-reports/ and never uploaded.
+ */
+
+import { app } from "electron";
+import fs from "fs";
+import os from "os";
+import path from "path";
+import log from "electron-log/main";
+import type { BackendManager } from "./backendmanager";
+import { getBackendUrl, getEffectiveConfig } from "./config";
+import { getBackendLog } from "./console";
+import { listCrashDumps } from "./diagnostics";
+import { getRecentHealthChecks } from "./health";
+import { handle } from "./ipc";
+import { redact, redactValue } from "./redaction";
+import { createZip } from "./zip";
+
+export interface CrashReportInfo {
+  path: string;
+  sizeBytes: number;
+  createdAt: string;
+  /** Files inside the ZIP. */
+  files: string[];
+}
+
+const LOG_LINES = 500;
+/** Keep at most this many bundles. */
+const MAX_REPORTS = 10;
+
+/**
+ * Directory the crash report bundles are written to.
+ */
+export function getCrashReportDir(): string {
+  return path.join(app.getPath("userData"), "crash-reports");
+}
+
+function timestamp(date: Date): string {
+  const pad = (value: number): string => String(value).padStart(2, "0");
+  return (
+    `${date.getFullYear()}${pad(date.getMonth() + 1)}${pad(date.getDate())}-` +
+    `${pad(date.getHours())}${pad(date.getMinutes())}${pad(date.getSeconds())}`
+  );
+}
+
+/** Drop user:password@ from URLs (attach URL behind a proxy). */
+function stripCredentials(value: unknown): unknown {
+  return typeof value === "string" ? value.replace(/\/\/[^/@\s]+@/g, "//") : value;
+}
+
+function collectReport(manager: BackendManager, createdAt: string): unknown {
+  return {
+    createdAt,
+    app: {
+      version: app.getVersion(),
+      electron: process.versions.electron,
+      node: process.versions.node,
+      packaged: app.isPackaged,
+      locale: app.getLocale(),
+    },
+    os: {
+      platform: process.platform,
+      release: os.release(),
+      version: os.version(),
+      arch: process.arch,
+      cpus: os.cpus().length,
+      cpuModel: os.cpus()[0]?.model ?? null,
+      totalMemoryMb: Math.round(os.totalmem() / 1024 / 1024),
+      freeMemoryMb: Math.round(os.freemem() / 1024 / 1024),
+      uptimeS: Math.round(os.uptime()),
+    },
+    backend: {
+      url: stripCredentials(getBackendUrl()),
+      pid: manager.process?.pid ?? null,
+      ...manager.getStatus(),
+    },
+    crashDumps: listCrashDumps().map((dump) => dump.filename),
+  };
+}
+
+function collectBackendLog(): string {
+  return getBackendLog({ limit: LOG_LINES })
+    .map(
+      (line) =>
+        `${line.timestamp} [${line.stream}] [${line.level.toUpperCase()}] ${redact(line.text)}`
+    )
+    .join("\n");
+}
+
+function pruneReports(dir: string): void {
+  const bundles = fs
+    .readdirSync(dir)
+    .filter((name) => name.startsWith("billino-crash-report-") && name.endsWith(".zip"))
+    .sort();
+  for (const name of bundles.slice(0, Math.max(0, bundles.length - MAX_REPORTS))) {
+    fs.rmSync(path.join(dir, name), { force: true });
+  }
+}
+
+/**
+ * Write a crash report bundle.
+ *
+ * @returns Path and contents of the ZIP
+ */
+export function createCrashReport(manager: BackendManager): CrashReportInfo {
+  const now = new Date();
+  const createdAt = now.toISOString();
+  const json = (value: unknown): string => JSON.stringify(redactValue(value), null, 2);
+
+  const entries = [
+    { name: "report.json", data: json(collectReport(manager, createdAt)) },
+    { name: "backend.log", data: collectBackendLog() },
+    {
+      name: "config.json",
+      data: json(
+        getEffectiveConfig().map((entry) => ({ ...entry, value: stripCredentials(entry.value) }))
+      ),
+    },
+    {
+      name: "health.json",
+      data: json(
+        getRecentHealthChecks().map((check) => ({ ...check, url: stripCredentials(check.url) }))
+      ),
+    },
+  ].map((entry) => ({ ...entry, modified: now }));
+
+  const dir = getCrashReportDir();
+  fs.mkdirSync(dir, { recursive: true });
+  const file = path.join(dir, `billino-crash-report-${timestamp(now)}.zip`);
+  const zip = createZip(entries);
+  fs.writeFileSync(file, zip);
+  pruneReports(dir);
+
+  log.info(`🧯 Crash report written: ${file}`);
+  return {
+    path: file,
+    sizeBytes: zip.length,
+    createdAt,
+    files: entries.map((entry) => entry.name),
+  };
+}
+
+/**
+ * Register IPC handlers for crash reports.
+ */
+export function registerCrashReportHandlers(manager: BackendManager): void {
+  handle("create-crash-report", () => createCrashReport(manager));
+}
@@ -0,0 +1,114 @@
+/**
+ * Billino Desktop – Credential Store
+ *
+ * Passwords for external services (SMTP, WebDAV) are not part of
+ * settings.json. They are encrypted with Electron's safeStorage – backed by
+ * DPAPI on Windows, the Keychain on macOS and the Secret Service/kwallet on
+ * Linux – and stored in AppData/Roaming/Billino/credentials.json. The file
+ * is useless on another machine or user account.
+ *
+ * Main-process modules may also keep other secrets here (e.g. OAuth tokens
+ * under "oauth:<provider>", the signing certificate under "signing:pkcs12").
+ * The renderer can only set, check and delete the service passwords, and
+ * never read them back.
+ */
+
+import { app, safeStorage } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+
+export type CredentialService = "smtp" | "webdav" | "signing";
+export type CredentialKey = CredentialService | `oauth:${string}` | "signing:pkcs12";
+
+const SERVICES: readonly CredentialService[] = ["smtp", "webdav", "signing"];
+
+function getCredentialsPath(): string {
+  return path.join(app.getPath("userData"), "credentials.json");
+}
+
+function readStore(): Partial<Record<CredentialKey, string>> {
+  try {
+    return JSON.parse(fs.readFileSync(getCredentialsPath(), "utf-8"));
+  } catch {
+    return {};
+  }
+}
+
+function writeStore(store: Partial<Record<CredentialKey, string>>): void {
+  fs.writeFileSync(getCredentialsPath(), JSON.stringify(store, null, 2), "utf-8");
+}
+
+function assertService(service: CredentialService): void {
+  if (!SERVICES.includes(service)) {
+    throw new AppError("invalid_input", `Unknown credential service: ${service}`);
+  }
+}
+
+/**
+ * Decrypted secret, or null if none is stored.
+ */
+export function getCredential(service: CredentialKey): string | null {
+  const encrypted = readStore()[service];
+  if (!encrypted) return null;
+  try {
+    return safeStorage.decryptString(Buffer.from(encrypted, "base64"));
+  } catch (err) {
+    // Copied from another machine/user or keyring reset
+    log.warn(`⚠️ Stored secret for ${service} cannot be decrypted: ${err}`);
+    return null;
+  }
+}
+
+/**
+ * Encrypt and store a secret.
+ *
+ * @throws Error if the OS provides no encryption (e.g. Linux without keyring)
+ */
+export function setCredential(service: CredentialKey, secret: string): void {
+  if (!safeStorage.isEncryptionAvailable()) {
+    throw new AppError("unavailable", "No OS keyring available to store the password securely", {
+      message: "Passwörter können auf diesem System nicht sicher gespeichert werden.",
+      hint: "Unter Linux einen Schlüsselbund (z. B. GNOME Keyring) einrichten.",
+    });
+  }
+  const store = readStore();
+  store[service] = safeStorage.encryptString(secret).toString("base64");
+  writeStore(store);
+  log.info(`🔑 Secret stored for ${service}`);
+}
+
+/**
+ * Remove a stored secret.
+ */
+export function deleteCredential(service: CredentialKey): void {
+  const store = readStore();
+  if (!(service in store)) return;
+  delete store[service];
+  writeStore(store);
+  log.info(`🔑 Secret removed for ${service}`);
+}
+
+/**
+ * Register IPC handlers for storing service passwords.
+ */
+export function registerCredentialHandlers(): void {
+  handle(
+    "has-credential",
+    (_event, service: CredentialService) => {
+      assertService(service);
+      return getCredential(service) !== null;
+    },
+    "read"
+  );
+  handle("set-credential", (_event, service: CredentialService, secret: string) => {
+    assertService(service);
+    setCredential(service, secret);
+  });
+  handle("delete-credential", (_event, service: CredentialService) => {
+    assertService(service);
+    deleteCredential(service);
+  });
+}
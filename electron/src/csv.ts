@@ -0,0 +1,43 @@
+/**
+ * Billino Desktop – CSV Dialect
+ *
+ * The backend writes every CSV export (invoice list in the year archive,
+ * …) in the dialect it is sent. This module resolves the dialect for one
+ * export – global setting plus that export's override – and converts it
+ * into the backend's request format.
+ */
+
+import { CsvDialect, CsvEncoding, CsvExportName, getSettings } from "./settings";
+
+/** Request body of the backend's CsvDialect model. */
+export interface BackendCsvDialect {
+  separator: string;
+  decimal_mark: string;
+  encoding: "utf-8" | "utf-8-sig" | "cp1252";
+}
+
+const BACKEND_ENCODINGS: Record<CsvEncoding, BackendCsvDialect["encoding"]> = {
+  "utf-8": "utf-8",
+  "utf-8-bom": "utf-8-sig",
+  "windows-1252": "cp1252",
+};
+
+/**
+ * Effective dialect of an export (override over the global setting).
+ */
+export function getCsvDialect(exportName: CsvExportName): CsvDialect {
+  const { dialect, overrides } = getSettings().csv;
+  return { ...dialect, ...overrides[exportName] };
+}
+
+/**
+ * Effective dialect of an export as backend request body.
+ */
+export function backendCsvDialect(exportName: CsvExportName): BackendCsvDialect {
+  const dialect = getCsvDialect(exportName);
+  return {
+    separator: dialect.separator,
+    decimal_mark: dialect.decimalMark,
+    encoding: BACKEND_ENCODINGS[dialect.encoding],
+  };
+}
@@ -0,0 +1,131 @@
+/**
+ * Billino Desktop – Database Maintenance
+ *
+ * Numbers for the settings "Database" panel, so users can see why their
+ * billino.db is as large as it is: rows per table, stored PDFs, free pages
+ * (reclaimable by VACUUM) and the size of the write-ahead log.
+ *
+ * Also exposes WAL checkpoints and journal-mode switching. In WAL mode the
+ * latest changes live in billino.db-wal until a checkpoint; backups go
+ * through the SQLite backup API (which includes them) and are written as
+ * standalone files, so copying a backup never loses the last day of data.
+ */
+
+import { requestBackend } from "./api";
+import { handle } from "./ipc";
+
+export interface DbTableStats {
+  name: string;
+  rowCount: number;
+}
+
+export interface DbStats {
+  path: string;
+  fileSizeBytes: number;
+  /** Size of billino.db-wal (0 if none). */
+  walSizeBytes: number;
+  pageSize: number;
+  pageCount: number;
+  freelistPages: number;
+  /** Space held by free pages, reclaimable by VACUUM. */
+  freelistBytes: number;
+  journalMode: string;
+  indexCount: number;
+  /** Stored PDFs (Base64), usually the largest part of the file. */
+  pdfContentBytes: number;
+  tables: DbTableStats[];
+}
+
+export type CheckpointMode = "PASSIVE" | "FULL" | "RESTART" | "TRUNCATE";
+export type JournalMode = "delete" | "truncate" | "persist" | "wal";
+
+export interface CheckpointResult {
+  mode: CheckpointMode;
+  /** Blocked by an active reader/writer; retry later. */
+  busy: boolean;
+  walFrames: number;
+  checkpointedFrames: number;
+  /** Size of the -wal file afterwards. */
+  walSizeBytes: number;
+}
+
+interface RawDbStats {
+  path: string;
+  file_size_bytes: number;
+  wal_size_bytes: number;
+  page_size: number;
+  page_count: number;
+  freelist_pages: number;
+  freelist_bytes: number;
+  journal_mode: string;
+  index_count: number;
+  pdf_content_bytes: number;
+  tables: Array<{ name: string; row_count: number }>;
+}
+
+/**
+ * Statistics of the SQLite database file.
+ */
+export async function getDbStats(): Promise<DbStats> {
+  const raw = await requestBackend<RawDbStats>("/database/stats");
+  return {
+    path: raw.path,
+    fileSizeBytes: raw.file_size_bytes,
+    walSizeBytes: raw.wal_size_bytes,
+    pageSize: raw.page_size,
+    pageCount: raw.page_count,
+    freelistPages: raw.freelist_pages,
+    freelistBytes: raw.freelist_bytes,
+    journalMode: raw.journal_mode,
+    indexCount: raw.index_count,
+    pdfContentBytes: raw.pdf_content_bytes,
+    tables: raw.tables.map((table) => ({ name: table.name, rowCount: table.row_count })),
+  };
+}
+
+/**
+ * Copy the write-ahead log into the database file.
+ *
+ * @param mode TRUNCATE (default) also empties the -wal file
+ */
+export async function checkpointWal(mode: CheckpointMode = "TRUNCATE"): Promise<CheckpointResult> {
+  const raw = await requestBackend<{
+    mode: CheckpointMode;
+    busy: boolean;
+    wal_frames: number;
+    checkpointed_frames: number;
+    wal_size_bytes: number;
+  }>("/database/checkpoint", { method: "POST", body: { mode } });
+  return {
+    mode: raw.mode,
+    busy: raw.busy,
+    walFrames: raw.wal_frames,
+    checkpointedFrames: raw.checkpointed_frames,
+    walSizeBytes: raw.wal_size_bytes,
+  };
+}
+
+/**
+ * Switch the journal mode (leaving WAL checkpoints first).
+ *
+ * @returns The previous and the active mode
+ * @throws BackendRequestError 409 while the database is locked
+ */
+export async function setJournalMode(
+  mode: JournalMode
+): Promise<{ previousMode: JournalMode; journalMode: JournalMode }> {
+  const raw = await requestBackend<{ previous_mode: JournalMode; journal_mode: JournalMode }>(
+    "/database/journal-mode",
+    { method: "PUT", body: { mode } }
+  );
+  return { previousMode: raw.previous_mode, journalMode: raw.journal_mode };
+}
+
+/**
+ * Register IPC handlers for database maintenance.
+ */
+export function registerDatabaseHandlers(): void {
+  handle("get-db-stats", () => getDbStats(), "read");
+  handle("checkpoint-wal", (_event, mode?: CheckpointMode) => checkpointWal(mode));
+  handle("set-journal-mode", (_event, mode: JournalMode) => setJournalMode(mode));
+}
@@ -0,0 +1,167 @@
+/**
+ * Billino Desktop – Diagnostics
+ *
+ * Collects information that helps with support requests:
+ * - Native crash dumps (minidumps) of the Electron processes
+ * - Basic environment info (version, platform, paths)
+ * - An anonymized copy of the database (names, addresses, bank data and
+ *   texts scrambled, amounts scaled, PDFs removed by the backend), saved
+ *   where the user picks in a save dialog
+ *
+ * Crash dumps are written to AppData/Roaming/Billino/crashes and are never
+ * uploaded automatically – users attach them to bug reports themselves.
+ */
+
+import { app, crashReporter } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { requestBackend } from "./api";
+import { handle } from "./ipc";
+import { chooseSavePath } from "./savedialog";
+import { downloadExport } from "./transfers";
+
+export interface CrashDumpInfo {
+  filename: string;
+  path: string;
+  sizeBytes: number;
+  createdIso: string;
+}
+
+export interface AnonymizedDbExport {
+  path: string;
+  sizeBytes: number;
+  anonymizedValues: number;
+  scaledAmounts: number;
+  removedPdfs: number;
+}
+
+export interface DiagnosticsInfo {
+  version: string;
+  platform: string;
+  arch: string;
+  electron: string;
+  userData: string;
+  crashDumpDir: string;
+  crashDumps: CrashDumpInfo[];
+}
+
+/**
+ * Directory where native crash dumps are stored.
+ */
+export function getCrashDumpDir(): string {
+  return path.join(app.getPath("userData"), "crashes");
+}
+
+/**
+ * Start the native crash reporter.
+ *
+ * Must run before the `ready` event so crashes during startup are captured.
+ * Dumps stay local (`uploadToServer: false`).
+ */
+export function initCrashReporter(): void {
+  const crashDir = getCrashDumpDir();
+  fs.mkdirSync(crashDir, { recursive: true });
+  app.setPath("crashDumps", crashDir);
+
+  crashReporter.start({
+    productName: "Billino",
+    uploadToServer: false,
+    compress: true,
+  });
+
+  log.info(`🧯 Crash reporter active, dumps in: ${crashDir}`);
+}
+
+/**
+ * List all minidumps below the crash dump directory, newest first.
+ *
+ * Crashpad stores dumps in nested folders (`reports/`, `completed/`, `pending/`
+ * depending on platform), so the directory is walked recursively.
+ */
+export function listCrashDumps(): CrashDumpInfo[] {
+  const dumps: CrashDumpInfo[] = [];
+
+  const walk = (dir: string): void => {
+    let entries: fs.Dirent[];
+    try {
+      entries = fs.readdirSync(dir, { withFileTypes: true });
+    } catch {
+      return;
+    }
+
+    for (const entry of entries) {
+      const fullPath = path.join(dir, entry.name);
+      if (entry.isDirectory()) {
+        walk(fullPath);
+      } else if (entry.isFile() && entry.name.toLowerCase().endsWith(".dmp")) {
+        const stat = fs.statSync(fullPath);
+        dumps.push({
+          filename: entry.name,
+          path: fullPath,
+          sizeBytes: stat.size,
+          createdIso: stat.mtime.toISOString(),
+        });
+      }
+    }
+  };
+
+  walk(getCrashDumpDir());
+  return dumps.sort((a, b) => b.createdIso.localeCompare(a.createdIso));
+}
+
+/**
+ * Collect environment info and crash dumps for the diagnostics view.
+ */
+export function getDiagnostics(): DiagnosticsInfo {
+  return {
+    version: app.getVersion(),
+    platform: process.platform,
+    arch: process.arch,
+    electron: process.versions.electron,
+    userData: app.getPath("userData"),
+    crashDumpDir: getCrashDumpDir(),
+    crashDumps: listCrashDumps(),
+  };
+}
+
+/**
+ * Save an anonymized copy of the database that users can attach to a bug
+ * report. It still reproduces data-dependent bugs (same structure, text
+ * lengths, umlauts and amount ratios) without personal data.
+ *
+ * @param targetPath Absolute destination path (.db)
+ */
+export async function exportAnonymizedDb(targetPath: string): Promise<AnonymizedDbExport> {
+  const created = await requestBackend<{
+    job_id: string;
+    anonymized_values: number;
+    scaled_amounts: number;
+    removed_pdfs: number;
+  }>("/exports/anonymized-db", { method: "POST", timeoutMs: 5 * 60 * 1000 });
+  const download = await downloadExport(created.job_id, targetPath);
+  log.info(`🕶️ Anonymized database saved: ${download.path}`);
+  return {
+    path: download.path,
+    sizeBytes: download.bytes,
+    anonymizedValues: created.anonymized_values,
+    scaledAmounts: created.scaled_amounts,
+    removedPdfs: created.removed_pdfs,
+  };
+}
+
+/**
+ * Register IPC handlers for diagnostics.
+ */
+export function registerDiagnosticsHandlers(): void {
+  handle("get-diagnostics", () => getDiagnostics(), "read");
+  handle("list-crash-dumps", () => listCrashDumps(), "read");
+  handle("export-anonymized-db", async () => {
+    const targetPath = await chooseSavePath({
+      title: "Anonymisierte Datenbank speichern",
+      defaultName: "billino-anonymisiert.db",
+      filters: [{ name: "Datenbank", extensions: ["db"] }],
+    });
+    return targetPath ? exportAnonymizedDb(targetPath) : null;
+  });
+}
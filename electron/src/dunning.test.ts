@@ -0,0 +1,82 @@
+import { computeDueDate, computeDunning, DunningInvoice } from "./dunning";
+import { DunningSettings } from "./settings";
+
+const policy: DunningSettings = {
+  paymentTermDays: 14,
+  // Absichtlich unsortiert
+  levels: [
+    { daysOverdue: 35, fee: 10 },
+    { daysOverdue: 7, fee: 0 },
+    { daysOverdue: 21, fee: 5 },
+  ],
+  annualInterestPercent: 7.3,
+};
+
+function invoice(id: number, date: string, grossAmount: number): DunningInvoice {
+  return { id, number: `R-${id}`, date, customerName: null, grossAmount };
+}
+
+describe("computeDueDate", () => {
+  it("addiert das Zahlungsziel", () => {
+    expect(computeDueDate("2024-01-01", policy)).toBe("2024-01-15");
+    expect(computeDueDate("2024-01-01T10:30:00", policy)).toBe("2024-01-15");
+  });
+
+  it("verschiebt ein Wochenende auf den Montag", () => {
+    // 15.06.2024 ist ein Samstag
+    expect(computeDueDate("2024-06-01", policy)).toBe("2024-06-17");
+  });
+
+  it("überspringt Feiertage", () => {
+    // 29.03.2024 Karfreitag, dann Wochenende und Ostermontag
+    expect(computeDueDate("2024-03-15", policy)).toBe("2024-04-02");
+    // 31.10.2024 Reformationstag nur in einigen Ländern
+    expect(computeDueDate("2024-10-17", policy, null)).toBe("2024-10-31");
+    expect(computeDueDate("2024-10-17", policy, "SN")).toBe("2024-11-01");
+  });
+});
+
+describe("computeDunning", () => {
+  it("summiert Gebühren und berechnet Zinsen anteilig", () => {
+    const [candidate] = computeDunning([invoice(1, "2024-01-01", 1000)], policy, "2024-02-19");
+
+    expect(candidate.dueDate).toBe("2024-01-15");
+    expect(candidate.daysOverdue).toBe(35);
+    expect(candidate.level).toBe(3);
+    expect(candidate.fees).toBe(15);
+    // 1000 € × 7,3 % × 35 / 365
+    expect(candidate.interest).toBe(7);
+    expect(candidate.totalDue).toBe(1022);
+  });
+
+  it("lässt Rechnungen unterhalb der ersten Stufe weg", () => {
+    // Fällig am 14.02., am 19.02. erst 5 Tage überfällig
+    expect(computeDunning([invoice(1, "2024-01-31", 100)], policy, "2024-02-19")).toEqual([]);
+  });
+
+  it("rundet Zinsen auf Cent und sortiert nach Dringlichkeit", () => {
+    const candidates = computeDunning(
+      [invoice(1, "2024-01-22", 365), invoice(2, "2024-01-01", 1000)],
+      policy,
+      "2024-02-19"
+    );
+
+    expect(candidates.map((c) => c.id)).toEqual([2, 1]);
+    expect(candidates[1].level).toBe(1);
+    expect(candidates[1].daysOverdue).toBe(14);
+    expect(candidates[1].fees).toBe(0);
+    // 365 € × 7,3 % × 14 / 365 = 1,022 €
+    expect(candidates[1].interest).toBe(1.02);
+  });
+
+  it("berechnet ohne Verzugszins keine Zinsen", () => {
+    const [candidate] = computeDunning(
+      [invoice(1, "2024-01-01", 1000)],
+      { ...policy, annualInterestPercent: 0 },
+      "2024-02-19"
+    );
+
+    expect(candidate.interest).toBe(0);
+    expect(candidate.totalDue).toBe(1015);
+  });
+});
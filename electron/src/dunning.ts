@@ -0,0 +1,196 @@
+/**
+ * Billino Desktop – Dunning Schedule
+ *
+ * Computes which invoices are due for which reminder level (Mahnstufe)
+ * under the dunning policy from the settings (`dunning` section):
+ * due date = invoice date + payment term, moved to the next business day
+ * if it falls on a weekend or public holiday (settings: calendar.state);
+ * a level applies once the invoice is at least `daysOverdue` days past due.
+ * Fees accumulate over the levels reached; default interest is charged pro
+ * rata on the gross amount.
+ *
+ * Payment status is not tracked by the backend, so paid (or disputed)
+ * invoices are passed in by the caller via `excludeInvoiceIds`.
+ */
+
+import { requestBackend } from "./api";
+import { AppError } from "./errors";
+import { GermanState, nextBusinessDay } from "./holidays";
+import { handle } from "./ipc";
+import { DunningSettings, getSettings } from "./settings";
+
+export interface DunningInvoice {
+  id: number;
+  number: string;
+  /** Invoice date (YYYY-MM-DD, optionally with time). */
+  date: string;
+  customerName: string | null;
+  grossAmount: number;
+}
+
+export interface DunningCandidate extends DunningInvoice {
+  dueDate: string;
+  daysOverdue: number;
+  /** Reminder level, 1-based. */
+  level: number;
+  /** Sum of the fees of all levels reached. */
+  fees: number;
+  interest: number;
+  /** Gross amount + fees + interest. */
+  totalDue: number;
+}
+
+export interface DunningOptions {
+  /** Reference date (YYYY-MM-DD, default: today). */
+  asOf?: string;
+  /** Invoices to leave out (paid, disputed, ...). */
+  excludeInvoiceIds?: number[];
+}
+
+interface RawInvoicePage {
+  items: Array<{
+    id: number;
+    number: string;
+    date: string;
+    total_amount: number;
+    total_gross: number | null;
+    customer_name: string | null;
+  }>;
+  pageCount: number;
+}
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+const PAGE_SIZE = 100;
+
+function parseDay(value: string): number {
+  const match = /^(\d{4})-(\d{2})-(\d{2})/.exec(value);
+  if (!match) {
+    throw new AppError("invalid_input", `Invalid date: ${value}`);
+  }
+  return Date.UTC(Number(match[1]), Number(match[2]) - 1, Number(match[3]));
+}
+
+function formatDay(time: number): string {
+  return new Date(time).toISOString().slice(0, 10);
+}
+
+function today(): string {
+  const now = new Date();
+  return formatDay(Date.UTC(now.getFullYear(), now.getMonth(), now.getDate()));
+}
+
+const round2 = (value: number): number => Math.round(value * 100) / 100;
+
+/**
+ * Due date of an invoice: invoice date + payment term, moved to the next
+ * business day.
+ */
+export function computeDueDate(
+  invoiceDate: string,
+  policy: DunningSettings,
+  state: GermanState | null = null
+): string {
+  const termEnd = parseDay(invoiceDate) + policy.paymentTermDays * DAY_MS;
+  return nextBusinessDay(formatDay(termEnd), state);
+}
+
+/**
+ * Assign reminder levels to invoices (pure computation, no I/O).
+ *
+ * @returns Invoices that reached at least level 1, most urgent first
+ */
+export function computeDunning(
+  invoices: DunningInvoice[],
+  policy: DunningSettings,
+  asOf: string = today(),
+  state: GermanState | null = null
+): DunningCandidate[] {
+  const reference = parseDay(asOf);
+  const levels = [...policy.levels].sort((a, b) => a.daysOverdue - b.daysOverdue);
+  const candidates: DunningCandidate[] = [];
+
+  for (const invoice of invoices) {
+    const due = parseDay(computeDueDate(invoice.date, policy, state));
+    const daysOverdue = Math.floor((reference - due) / DAY_MS);
+    const reached = levels.filter((level) => daysOverdue >= level.daysOverdue);
+    if (reached.length === 0) continue;
+
+    const fees = round2(reached.reduce((sum, level) => sum + level.fee, 0));
+    const interest = round2(
+      (invoice.grossAmount * policy.annualInterestPercent * daysOverdue) / 100 / 365
+    );
+    candidates.push({
+      ...invoice,
+      dueDate: formatDay(due),
+      daysOverdue,
+      level: reached.length,
+      fees,
+      interest,
+      totalDue: round2(invoice.grossAmount + fees + interest),
+    });
+  }
+
+  return candidates.sort((a, b) => b.level - a.level || b.daysOverdue - a.daysOverdue);
+}
+
+/**
+ * Load all invoices dated on or before `lastDate` (and on or after
+ * `firstDate`, if given) from the backend.
+ */
+export async function fetchInvoices(
+  lastDate: string,
+  firstDate?: string
+): Promise<DunningInvoice[]> {
+  const invoices: DunningInvoice[] = [];
+  for (let page = 1; ; page++) {
+    const query = new URLSearchParams({
+      // Dates may carry a time part ("2025-10-01T09:30:00")
+      filter: `date:lte:${lastDate}T23:59:59`,
+      sort: "date:asc",
+      page: String(page),
+      pageSize: String(PAGE_SIZE),
+    });
+    if (firstDate) query.append("filter", `date:gte:${firstDate}`);
+    const result = await requestBackend<RawInvoicePage>(`/invoices/?${query}`);
+    for (const item of result.items) {
+      invoices.push({
+        id: item.id,
+        number: item.number,
+        date: item.date,
+        customerName: item.customer_name,
+        grossAmount: item.total_gross ?? item.total_amount,
+      });
+    }
+    if (page >= result.pageCount) return invoices;
+  }
+}
+
+/**
+ * Invoices due for a reminder under the configured dunning policy.
+ */
+export async function getDunningCandidates(
+  options: DunningOptions = {}
+): Promise<DunningCandidate[]> {
+  const { dunning: policy, calendar } = getSettings();
+  const asOf = options.asOf ?? today();
+  if (policy.levels.length === 0) return [];
+
+  // Only invoices old enough to reach the first level can be candidates
+  const firstLevel = Math.min(...policy.levels.map((level) => level.daysOverdue));
+  const lastDate = formatDay(parseDay(asOf) - (policy.paymentTermDays + firstLevel) * DAY_MS);
+
+  const excluded = new Set(options.excludeInvoiceIds ?? []);
+  const invoices = (await fetchInvoices(lastDate)).filter((inv) => !excluded.has(inv.id));
+  return computeDunning(invoices, policy, asOf, calendar.state);
+}
+
+/**
+ * Register IPC handlers for the dunning workflow.
+ */
+export function registerDunningHandlers(): void {
+  handle(
+    "get-dunning-candidates",
+    (_event, options?: DunningOptions) => getDunningCandidates(options),
+    "read"
+  );
+}
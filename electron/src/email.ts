@@ -0,0 +1,354 @@
+/**
+ * Billino Desktop – E-Mail Sending
+ *
+ * Sends mail through the SMTP server from the settings (section "email");
+ * the password comes from the credential store. `sendTestEmail()` lets the
+ * user check the settings with a short test message instead of a real
+ * invoice – on failure the SMTP conversation is returned so the exact
+ * server reply (wrong port, rejected login, relay denied) is visible.
+ *
+ * `sendEmail()` sends a message, optionally with an invoice PDF. If the
+ * server cannot be reached or answers with a temporary error, the mail
+ * goes to the outbox (a "send-email" task in the persisted task queue)
+ * and is retried with backoff, also after a restart. `listOutbox()` shows
+ * what is waiting, `cancelOutboxEntry()` drops a mail. Permanent
+ * rejections (5xx, e.g. unknown recipient) are reported at once.
+ */
+
+import { randomUUID } from "crypto";
+import log from "electron-log/main";
+import { requestBackend } from "./api";
+import { getCredential } from "./credentials";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { EmailSettings, getSettings } from "./settings";
+import { SmtpError, SmtpSession } from "./smtp";
+import {
+  cancelTask,
+  enqueueTask,
+  listPendingTasks,
+  QueuedTask,
+  registerTaskKind,
+  TaskStatus,
+} from "./tasks";
+
+export interface TestEmailResult {
+  success: boolean;
+  to: string;
+  error: string | null;
+  /** SMTP reply code of the failing step (null for network errors). */
+  replyCode: number | null;
+  /** Full conversation ("S: ..." / "C: ..."), credentials masked. */
+  transcript: string[];
+}
+
+export interface EmailRequest {
+  to: string;
+  subject: string;
+  text: string;
+  /** Attach the stored PDF of this invoice. */
+  invoiceId?: number;
+}
+
+export interface SendEmailResult {
+  /** "queued": not sent yet, retried from the outbox. */
+  status: "sent" | "queued";
+  /** Task id of the outbox entry, null if sent. */
+  outboxId: string | null;
+  /** Why the first attempt failed (German), null if sent. */
+  error: string | null;
+}
+
+export interface OutboxEntry {
+  id: string;
+  to: string;
+  subject: string;
+  invoiceId: number | null;
+  /** "failed": gave up after the last attempt, kept until cancelled. */
+  status: TaskStatus;
+  attempts: number;
+  maxAttempts: number;
+  nextAttemptAt: string;
+  lastError: string | null;
+  createdAt: string;
+}
+
+interface Attachment {
+  filename: string;
+  contentType: string;
+  content: Buffer;
+}
+
+const SEND_TIMEOUT_MS = 30_000;
+const OUTBOX_TASK = "send-email";
+/** With the queue's backoff this retries for about a day. */
+const OUTBOX_MAX_ATTEMPTS = 12;
+/** Delay before the first retry of a mail that just failed. */
+const OUTBOX_FIRST_RETRY_MS = 60_000;
+
+function encodeHeader(value: string): string {
+  // RFC 2047 for umlauts in subject/names
+  return /^[\x20-\x7e]*$/.test(value)
+    ? value
+    : `=?UTF-8?B?${Buffer.from(value, "utf-8").toString("base64")}?=`;
+}
+
+function base64Lines(data: Buffer): string {
+  return data.toString("base64").replace(/.{76}/g, "$&\r\n");
+}
+
+function buildMessage(
+  from: string,
+  to: string,
+  subject: string,
+  text: string,
+  attachment?: Attachment
+): string {
+  const domain = from.split("@")[1] ?? "billino.local";
+  const headers = [
+    `From: Billino <${from}>`,
+    `To: <${to}>`,
+    `Subject: ${encodeHeader(subject)}`,
+    `Date: ${new Date().toUTCString()}`,
+    `Message-ID: <${randomUUID()}@${domain}>`,
+    "MIME-Version: 1.0",
+  ];
+  const textPart = [
+    "Content-Type: text/plain; charset=utf-8",
+    "Content-Transfer-Encoding: base64",
+    "",
+    base64Lines(Buffer.from(text, "utf-8")),
+  ];
+  if (!attachment) return [...headers, ...textPart].join("\r\n");
+
+  const boundary = `billino-${randomUUID()}`;
+  const filename = encodeHeader(attachment.filename);
+  return [
+    ...headers,
+    `Content-Type: multipart/mixed; boundary="${boundary}"`,
+    "",
+    `--${boundary}`,
+    ...textPart,
+    `--${boundary}`,
+    `Content-Type: ${attachment.contentType}; name="${filename}"`,
+    "Content-Transfer-Encoding: base64",
+    `Content-Disposition: attachment; filename="${filename}"`,
+    "",
+    base64Lines(attachment.content),
+    `--${boundary}--`,
+    "",
+  ].join("\r\n");
+}
+
+/**
+ * Connect, log in and send one message.
+ *
+ * @throws SmtpError with the transcript if any step fails
+ */
+async function transmit(settings: EmailSettings, to: string, message: string): Promise<string[]> {
+  let session: SmtpSession | null = null;
+  try {
+    session = await SmtpSession.connect({
+      host: settings.smtpHost,
+      port: settings.smtpPort,
+      security: settings.security,
+      timeoutMs: SEND_TIMEOUT_MS,
+    });
+    if (settings.username) {
+      const password = getCredential("smtp");
+      if (password === null) {
+        throw new SmtpError("Kein SMTP-Passwort gespeichert", session.transcript);
+      }
+      await session.authenticate(settings.username, password);
+    }
+    await session.sendMail(settings.fromAddress, [to], message);
+    await session.quit();
+    return session.transcript;
+  } catch (err) {
+    session?.close();
+    if (err instanceof SmtpError) throw err;
+    const text = err instanceof Error ? err.message : String(err);
+    throw new SmtpError(text, session?.transcript ?? []);
+  }
+}
+
+/**
+ * Send a short test message with the configured SMTP settings.
+ *
+ * Never throws for SMTP problems – the result describes the failure.
+ */
+export async function sendTestEmail(to: string): Promise<TestEmailResult> {
+  const settings = getSettings().email;
+  const failed = (
+    error: string,
+    replyCode: number | null,
+    transcript: string[]
+  ): TestEmailResult => {
+    log.warn(`📧 Test e-mail to ${to} failed: ${error}`);
+    return { success: false, to, error, replyCode, transcript };
+  };
+
+  if (!/^[^\s@<>]+@[^\s@<>]+$/.test(to)) return failed("Ungültige Empfängeradresse", null, []);
+  if (!settings.smtpHost) return failed("Kein SMTP-Server eingetragen", null, []);
+  if (!settings.fromAddress) return failed("Keine Absenderadresse eingetragen", null, []);
+
+  const message = buildMessage(
+    settings.fromAddress,
+    to,
+    "Billino – Testnachricht",
+    "Diese Nachricht bestätigt, dass der E-Mail-Versand aus Billino funktioniert.\n\n" +
+      `Server: ${settings.smtpHost}:${settings.smtpPort} (${settings.security})\n` +
+      `Gesendet: ${new Date().toLocaleString("de-DE")}\n`
+  );
+  try {
+    const transcript = await transmit(settings, to, message);
+    log.info(`📧 Test e-mail sent to ${to}`);
+    return { success: true, to, error: null, replyCode: null, transcript };
+  } catch (err) {
+    const smtp = err as SmtpError;
+    return failed(smtp.message, smtp.replyCode, smtp.transcript);
+  }
+}
+
+/**
+ * Settings needed to send at all; missing ones are not worth a retry.
+ */
+function checkSendable(settings: EmailSettings, to: string): void {
+  const problem = !/^[^\s@<>]+@[^\s@<>]+$/.test(to)
+    ? "Ungültige Empfängeradresse."
+    : !settings.smtpHost
+      ? "Kein SMTP-Server eingetragen."
+      : !settings.fromAddress
+        ? "Keine Absenderadresse eingetragen."
+        : settings.username && getCredential("smtp") === null
+          ? "Kein SMTP-Passwort gespeichert."
+          : null;
+  if (problem) {
+    throw new AppError("invalid_state", `Cannot send e-mail: ${problem}`, {
+      message: problem,
+      hint: "Unter Einstellungen → E-Mail die Versandeinstellungen prüfen.",
+    });
+  }
+}
+
+/** 5xx replies mean the server refused the mail; retrying will not help. */
+function isPermanent(err: SmtpError): boolean {
+  return err.replyCode !== null && err.replyCode >= 500;
+}
+
+async function invoiceAttachment(invoiceId: number): Promise<Attachment> {
+  const invoice = await requestBackend<{ number: string }>(`/invoices/${invoiceId}`);
+  const pdf = await requestBackend<{ content: string }>(`/pdfs/by-invoice/${invoiceId}`);
+  const name = invoice.number.replace(/[^A-Za-z0-9-]+/g, "_").replace(/^_+|_+$/g, "");
+  return {
+    filename: `Rechnung_${name}.pdf`,
+    contentType: "application/pdf",
+    content: Buffer.from(pdf.content, "base64"),
+  };
+}
+
+/**
+ * Build and send a mail once. The PDF is loaded at send time, so a queued
+ * mail carries the current PDF (e.g. signed in the meantime).
+ *
+ * @throws SmtpError if sending failed, AppError for a missing PDF
+ */
+async function deliver(request: EmailRequest): Promise<void> {
+  const settings = getSettings().email;
+  checkSendable(settings, request.to);
+  const attachment =
+    request.invoiceId !== undefined ? await invoiceAttachment(request.invoiceId) : undefined;
+  const message = buildMessage(
+    settings.fromAddress,
+    request.to,
+    request.subject,
+    request.text,
+    attachment
+  );
+  await transmit(settings, request.to, message);
+  log.info(`📧 E-mail "${request.subject}" sent to ${request.to}`);
+  emitEvent("email:sent", { to: request.to, subject: request.subject });
+}
+
+function queueEmail(request: EmailRequest, error: string): QueuedTask {
+  const task = enqueueTask(OUTBOX_TASK, `E-Mail an ${request.to}: ${request.subject}`, request, {
+    runAt: new Date(Date.now() + OUTBOX_FIRST_RETRY_MS).toISOString(),
+    maxAttempts: OUTBOX_MAX_ATTEMPTS,
+  });
+  log.warn(`📧 E-mail to ${request.to} queued in outbox: ${error}`);
+  return task;
+}
+
+/**
+ * Send a mail; if the server is unreachable it is queued in the outbox
+ * and retried in the background.
+ *
+ * @throws AppError if the settings are incomplete, the PDF is missing or
+ *         the server rejected the mail for good
+ */
+export async function sendEmail(request: EmailRequest): Promise<SendEmailResult> {
+  try {
+    await deliver(request);
+    return { status: "sent", outboxId: null, error: null };
+  } catch (err) {
+    if (!(err instanceof SmtpError)) throw err;
+    if (isPermanent(err)) {
+      throw new AppError("conflict", `SMTP server rejected the mail: ${err.message}`, {
+        message: `Der Mailserver hat die E-Mail abgelehnt: ${err.message}`,
+      });
+    }
+    const task = queueEmail(request, err.message);
+    return { status: "queued", outboxId: task.id, error: err.message };
+  }
+}
+
+function toOutboxEntry(task: QueuedTask): OutboxEntry {
+  const request = task.payload as EmailRequest;
+  return {
+    id: task.id,
+    to: request.to,
+    subject: request.subject,
+    invoiceId: request.invoiceId ?? null,
+    status: task.status,
+    attempts: task.attempts,
+    maxAttempts: task.maxAttempts,
+    nextAttemptAt: task.runAt,
+    lastError: task.lastError,
+    createdAt: task.createdAt,
+  };
+}
+
+/**
+ * Mails waiting to be sent, and those that gave up.
+ */
+export function listOutbox(): OutboxEntry[] {
+  return listPendingTasks()
+    .filter((task) => task.kind === OUTBOX_TASK)
+    .map(toOutboxEntry);
+}
+
+/**
+ * Drop a mail from the outbox; it will not be sent.
+ *
+ * @throws AppError if there is no such mail
+ */
+export function cancelOutboxEntry(id: string): void {
+  if (!listOutbox().some((entry) => entry.id === id)) {
+    throw new AppError("not_found", `Outbox entry not found: ${id}`, {
+      message: "Die E-Mail ist nicht mehr im Postausgang.",
+    });
+  }
+  cancelTask(id);
+}
+
+/**
+ * Register IPC handlers for e-mail.
+ */
+export function registerEmailHandlers(): void {
+  registerTaskKind(OUTBOX_TASK, (payload) => deliver(payload as EmailRequest));
+  handle("send-test-email", (_event, to: string) => sendTestEmail(to));
+  handle("send-email", (_event, request: EmailRequest) => sendEmail(request));
+  handle("list-outbox", () => listOutbox(), "read");
+  handle("cancel-outbox-entry", (_event, id: string) => cancelOutboxEntry(id), "destructive");
+}
@@ -0,0 +1,183 @@
+/**
+ * Billino Desktop – Command Errors
+ *
+ * Every failed IPC command reaches the renderer as an AppError payload:
+ *
+ *   { code: "backend_busy", message: "Der Billino-Dienst ist gerade …",
+ *     detail: "Backend request /pdfs/… failed (HTTP 503): …", hint: "…" }
+ *
+ * - `code` is stable and meant for dialogs and i18n keys
+ * - `message` is a German text for the user
+ * - `detail` is the technical cause (for logs and support)
+ * - `hint` tells the user what to do, if there is anything
+ *
+ * Commands throw AppError where they know the cause; everything else
+ * (backend errors, fs errors, fetch failures) is classified by
+ * `toAppError()` in ipc.ts. Electron only passes the message of a thrown
+ * error to the renderer, so the payload travels JSON-encoded behind
+ * APP_ERROR_PREFIX and the preload script turns it back into an object.
+ */
+
+import { BackendRequestError } from "./api";
+import { PermissionDeniedError } from "./permissions";
+
+export type ErrorCode =
+  | "invalid_input"
+  | "not_found"
+  | "conflict"
+  | "invalid_state"
+  | "cancelled"
+  | "permission_denied"
+  | "unavailable"
+  | "backend_unreachable"
+  | "backend_timeout"
+  | "backend_busy"
+  | "backend_error"
+  | "network_error"
+  | "file_not_found"
+  | "file_access_denied"
+  | "disk_full"
+  | "internal";
+
+export interface AppErrorPayload {
+  code: ErrorCode;
+  message: string;
+  detail: string;
+  hint: string | null;
+}
+
+export interface AppErrorOptions {
+  /** User message; defaults to the generic text of the code. */
+  message?: string;
+  /** Remediation hint; defaults to the generic hint of the code. */
+  hint?: string | null;
+}
+
+/** Marks an encoded AppError in an error message. */
+export const APP_ERROR_PREFIX = "AppError:";
+
+const DEFAULTS: Record<ErrorCode, { message: string; hint: string | null }> = {
+  invalid_input: { message: "Die Eingabe ist ungültig.", hint: null },
+  not_found: { message: "Der Eintrag wurde nicht gefunden.", hint: null },
+  conflict: {
+    message: "Der Vorgang steht im Widerspruch zu vorhandenen Daten.",
+    hint: null,
+  },
+  invalid_state: {
+    message: "Der Vorgang ist gerade nicht möglich.",
+    hint: "Bitte später erneut versuchen.",
+  },
+  cancelled: { message: "Der Vorgang wurde abgebrochen.", hint: null },
+  permission_denied: {
+    message: "Diese Aktion ist in diesem Fenster nicht erlaubt.",
+    hint: "Bitte im Hauptfenster ausführen.",
+  },
+  unavailable: { message: "Diese Funktion ist auf diesem System nicht verfügbar.", hint: null },
+  backend_unreachable: {
+    message: "Der Billino-Dienst ist nicht erreichbar.",
+    hint: "Billino neu starten. Hilft das nicht, die Protokolle unter Diagnose prüfen.",
+  },
+  backend_timeout: {
+    message: "Der Billino-Dienst hat nicht rechtzeitig geantwortet.",
+    hint: "Bitte erneut versuchen.",
+  },
+  backend_busy: {
+    message: "Der Billino-Dienst ist gerade ausgelastet.",
+    hint: "In einigen Sekunden erneut versuchen.",
+  },
+  backend_error: {
+    message: "Im Billino-Dienst ist ein Fehler aufgetreten.",
+    hint: "Details stehen im Protokoll.",
+  },
+  network_error: {
+    message: "Der Download ist fehlgeschlagen.",
+    hint: "Bitte die Internetverbindung prüfen.",
+  },
+  file_not_found: { message: "Die Datei wurde nicht gefunden.", hint: null },
+  file_access_denied: {
+    message: "Auf die Datei kann nicht zugegriffen werden.",
+    hint: "Prüfen, ob die Datei in einem anderen Programm geöffnet ist.",
+  },
+  disk_full: {
+    message: "Auf dem Datenträger ist nicht genug Speicherplatz frei.",
+    hint: "Speicherplatz freigeben und erneut versuchen.",
+  },
+  internal: {
+    message: "Ein unerwarteter Fehler ist aufgetreten.",
+    hint: "Details stehen im Protokoll.",
+  },
+};
+
+/**
+ * A command failure with a stable code.
+ */
+export class AppError extends Error {
+  public code: ErrorCode;
+  public userMessage: string;
+  public hint: string | null;
+
+  /**
+   * @param detail Technical description (English, for logs)
+   */
+  constructor(code: ErrorCode, detail: string, options: AppErrorOptions = {}) {
+    super(detail);
+    this.name = "AppError";
+    this.code = code;
+    this.userMessage = options.message ?? DEFAULTS[code].message;
+    this.hint = options.hint !== undefined ? options.hint : DEFAULTS[code].hint;
+  }
+
+  toPayload(): AppErrorPayload {
+    return { code: this.code, message: this.userMessage, detail: this.message, hint: this.hint };
+  }
+}
+
+function fromBackend(err: BackendRequestError): AppError {
+  // FastAPI details for 4xx are written for the user (German); validation
+  // errors and raw bodies are JSON
+  const userMessage = /^[[{]/.test(err.detail) ? {} : { message: err.detail };
+  if (err.status === 429 || err.status === 503 || /database is locked/i.test(err.detail)) {
+    return new AppError("backend_busy", err.message);
+  }
+  if (err.status === 400 || err.status === 422) {
+    return new AppError("invalid_input", err.message, userMessage);
+  }
+  if (err.status === 404) return new AppError("not_found", err.message, userMessage);
+  if (err.status === 409) return new AppError("conflict", err.message, userMessage);
+  return new AppError("backend_error", err.message);
+}
+
+const FS_CODES: Record<string, ErrorCode> = {
+  ENOENT: "file_not_found",
+  EACCES: "file_access_denied",
+  EPERM: "file_access_denied",
+  EBUSY: "file_access_denied",
+  ENOSPC: "disk_full",
+};
+
+/**
+ * Classify any thrown value.
+ */
+export function toAppError(err: unknown): AppError {
+  if (err instanceof AppError) return err;
+  if (err instanceof BackendRequestError) return fromBackend(err);
+  if (err instanceof PermissionDeniedError) return new AppError("permission_denied", err.message);
+
+  const detail = err instanceof Error ? err.message : String(err);
+  const { name, code } = (err ?? {}) as { name?: string; code?: string };
+  if (name === "TimeoutError") return new AppError("backend_timeout", detail);
+  if (name === "AbortError") return new AppError("cancelled", detail);
+  // Node's fetch: "fetch failed" when nothing listens on the port
+  if (err instanceof TypeError && /fetch failed/.test(detail)) {
+    return new AppError("backend_unreachable", detail);
+  }
+  if (code && code in FS_CODES) return new AppError(FS_CODES[code], detail);
+  return new AppError("internal", detail);
+}
+
+/**
+ * Error to throw from an IPC handler so the payload survives the trip.
+ */
+export function encodeAppError(err: AppError): Error {
+  return new Error(APP_ERROR_PREFIX + JSON.stringify(err.toPayload()));
+}
@@ -0,0 +1,255 @@
+/**
+ * Billino Desktop – Renderer Events
+ *
+ * Events pushed from the main process to the renderer(s) go through
+ * `emitEvent()` so they are delivered to every window and can be recorded.
+ *
+ * Every channel has a typed payload in `EventPayloads`; emitting an
+ * unknown channel or a wrong payload is a compile error. The payload is
+ * sent with `seq` (increasing per app run, so the renderer can order
+ * events and notice gaps) and `emittedAt` (ISO timestamp) added – see
+ * `AppEvent`. The renderer can import these types from this module.
+ *
+ * Lifecycle events (backend state, crashes, restarts, error spikes,
+ * resource warnings, app condition) are kept in a journal of the last 200,
+ * so a window that opens later can catch up: `get-event-history` returns
+ * them, `replay-events` sends the ones after a given `seq` to the calling
+ * window as if they were new.
+ */
+
+import { webContents, WebContents } from "electron";
+import type { AccessibilityPrefs } from "./accessibility";
+import type { ApiCheckResult } from "./apicheck";
+import type { BackendExit, BackendStatus } from "./backendmanager";
+import type { BulkProgress } from "./bulk";
+import type { AppCondition } from "./condition";
+import type { BackendUrlChange } from "./config";
+import type { FiscalCloseStep } from "./fiscal";
+import type { ImportFormat } from "./importers";
+import { handle } from "./ipc";
+import type { MaintenanceSummary } from "./maintenance";
+import type { BackendErrorSpike } from "./metrics";
+import type { StaleBackendStopResult } from "./orphans";
+import type { PdfQueueCompletion } from "./pdfqueue";
+import type { MonthlySummary } from "./reports";
+import type { BackendResourceWarning } from "./resources";
+import type { RestartResult } from "./routing";
+import { recordEvent } from "./session";
+import type { ShutdownReport, ShutdownStage } from "./shutdown";
+import type { SignedPdfResult } from "./signing";
+import type { CustomerStatement } from "./statements";
+import type { QueuedTask } from "./tasks";
+import type { ThresholdWarning } from "./thresholds";
+import type { TransferProgress } from "./transfers";
+
+// ─── Payloads ────────────────────────────────────────────────────────────────
+
+export interface EventMeta {
+  /** Increases by one with every event of this app run. */
+  seq: number;
+  emittedAt: string;
+}
+
+export type BackendReadyEvent = RestartResult;
+
+export interface BackendCrashedEvent extends BackendExit {
+  /** Readable cause, e.g. "exit code 1" or "signal SIGKILL". */
+  reason: string;
+  /** Respawns since the last stable run. */
+  attempts: number;
+  willRestart: boolean;
+}
+
+export interface BackendRestartingEvent {
+  attempt: number;
+  maxAttempts: number;
+  delayMs: number;
+}
+
+export interface BackendRestartProgressEvent {
+  phase: "stopping" | "waiting-for-port" | "starting";
+  attempt: number;
+}
+
+export interface BackendErrorEvent {
+  message: string;
+  attempts: number;
+}
+
+export interface BackendStoppingEvent {
+  pid: number | null;
+  stage: ShutdownStage;
+}
+
+export interface OperationAbortedEvent {
+  id: string;
+}
+
+export interface AttachmentsChangedEvent {
+  invoiceId: number;
+}
+
+export interface EmailSentEvent {
+  to: string;
+  subject: string;
+}
+
+export interface TaskCompletedEvent {
+  id: string;
+  kind: string;
+  label: string;
+}
+
+export interface FiscalYearProgressEvent {
+  year: number;
+  step: FiscalCloseStep;
+  index: number;
+  total: number;
+}
+
+export interface ImportProgressEvent {
+  format: ImportFormat;
+  done: number;
+  total: number;
+}
+
+/** Payload of every event channel. */
+export interface EventPayloads {
+  "accessibility:changed": AccessibilityPrefs;
+  "app:condition": AppCondition;
+  "attachments:changed": AttachmentsChangedEvent;
+  "backend:api-drift": ApiCheckResult;
+  "backend:crashed": BackendCrashedEvent;
+  "backend:error": BackendErrorEvent;
+  "backend:error-spike": BackendErrorSpike;
+  "backend:ready": BackendReadyEvent;
+  "backend:resource-warning": BackendResourceWarning;
+  "backend:restart-progress": BackendRestartProgressEvent;
+  "backend:restarting": BackendRestartingEvent;
+  "backend:stale-stopped": StaleBackendStopResult;
+  "backend:state-changed": BackendStatus;
+  "backend:stopped": ShutdownReport;
+  "backend:stopping": BackendStoppingEvent;
+  "backend:url-changed": BackendUrlChange;
+  "bulk:progress": BulkProgress;
+  "email:sent": EmailSentEvent;
+  "fiscal-year:progress": FiscalYearProgressEvent;
+  "import:progress": ImportProgressEvent;
+  "maintenance:finished": MaintenanceSummary;
+  "operation:aborted": OperationAbortedEvent;
+  "pdf-queue:completed": PdfQueueCompletion;
+  "pdf:signed": SignedPdfResult;
+  "report:open": MonthlySummary;
+  "statement:generated": CustomerStatement;
+  "tasks:completed": TaskCompletedEvent;
+  "tasks:failed": QueuedTask;
+  "threshold:warning": ThresholdWarning;
+  "transfer:progress": TransferProgress;
+}
+
+export type EventChannel = keyof EventPayloads;
+
+/** An event as the renderer receives it. */
+export type AppEvent<C extends EventChannel> = EventPayloads[C] & EventMeta;
+
+// ─── Journal ─────────────────────────────────────────────────────────────────
+
+/** Channels kept in the journal; progress events are left out. */
+const JOURNALED_CHANNELS: ReadonlySet<EventChannel> = new Set<EventChannel>([
+  "app:condition",
+  "backend:api-drift",
+  "backend:crashed",
+  "backend:error",
+  "backend:error-spike",
+  "backend:ready",
+  "backend:resource-warning",
+  "backend:restarting",
+  "backend:stale-stopped",
+  "backend:state-changed",
+  "backend:stopped",
+  "backend:stopping",
+  "backend:url-changed",
+]);
+
+const JOURNAL_SIZE = 200;
+
+export interface JournalEntry<C extends EventChannel = EventChannel> {
+  channel: C;
+  event: AppEvent<C>;
+}
+
+/**
+ * Ring buffer of the last lifecycle events (oldest first).
+ */
+export class EventJournal {
+  private readonly entries: JournalEntry[] = [];
+
+  constructor(private readonly size: number = JOURNAL_SIZE) {}
+
+  record<C extends EventChannel>(channel: C, event: AppEvent<C>): void {
+    if (!JOURNALED_CHANNELS.has(channel)) return;
+    this.entries.push({ channel, event } as JournalEntry);
+    if (this.entries.length > this.size) this.entries.shift();
+  }
+
+  /**
+   * Journaled events with a `seq` greater than `sinceSeq` (all if omitted).
+   */
+  since(sinceSeq = 0): JournalEntry[] {
+    return this.entries.filter((entry) => entry.event.seq > sinceSeq);
+  }
+}
+
+const journal = new EventJournal();
+
+// ─── Emitting ────────────────────────────────────────────────────────────────
+
+let seq = 0;
+
+/**
+ * Send an event to all open windows.
+ *
+ * @param channel Event name, e.g. "operation:aborted"
+ * @param payload Payload; `seq` and `emittedAt` are added
+ */
+export function emitEvent<C extends EventChannel>(channel: C, payload: EventPayloads[C]): void {
+  const event: AppEvent<C> = { ...payload, seq: ++seq, emittedAt: new Date().toISOString() };
+  recordEvent(channel, event);
+  journal.record(channel, event);
+  for (const contents of webContents.getAllWebContents()) {
+    contents.send(channel, event);
+  }
+}
+
+/**
+ * Journaled lifecycle events after `sinceSeq` (all if omitted).
+ */
+export function getEventHistory(sinceSeq?: number): JournalEntry[] {
+  return journal.since(sinceSeq);
+}
+
+/**
+ * Send the journaled events after `sinceSeq` to one window again, in
+ * their original order and with their original `seq`.
+ *
+ * @returns Number of events sent
+ */
+export function replayEventsSince(contents: WebContents, sinceSeq?: number): number {
+  const entries = journal.since(sinceSeq);
+  for (const entry of entries) {
+    contents.send(entry.channel, entry.event);
+  }
+  return entries.length;
+}
+
+/**
+ * Register IPC handlers for the event journal.
+ */
+export function registerEventHandlers(): void {
+  handle("get-event-history", (_event, sinceSeq?: number) => getEventHistory(sinceSeq), "read");
+  handle(
+    "replay-events",
+    (event, sinceSeq?: number) => replayEventsSince(event.sender, sinceSeq),
+    "read"
+  );
+}
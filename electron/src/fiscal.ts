@@ -0,0 +1,240 @@
+/**
+ * Billino Desktop – Fiscal-Year Close
+ *
+ * `close-fiscal-year` runs the year-end steps as one guided action:
+ * 1. Final database backup (/backups/trigger)
+ * 2. Year archive: invoice list, VAT summary and PDFs as ZIP
+ *    (optionally downloaded to a user-chosen path)
+ * 3. VAT summary per tax rate
+ * 4. Counter rollover check: first invoice number of the next year
+ *
+ * Steps run in order and stop at the first failure; later steps are
+ * reported as skipped. Progress is emitted as `fiscal-year:progress`.
+ */
+
+import log from "electron-log/main";
+import { callBackend, requestBackend } from "./api";
+import { backendCsvDialect } from "./csv";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { beginOperation, endOperation } from "./operations";
+import { chooseSavePath } from "./savedialog";
+import { downloadExport } from "./transfers";
+
+export type FiscalCloseStep = "backup" | "archive" | "vat-summary" | "rollover";
+export type FiscalStepStatus = "ok" | "warning" | "failed" | "skipped";
+
+export interface FiscalStepResult {
+  step: FiscalCloseStep;
+  status: FiscalStepStatus;
+  detail: string | null;
+}
+
+export interface VatRateSummary {
+  taxRate: number;
+  invoiceCount: number;
+  net: number;
+  tax: number;
+  gross: number;
+}
+
+export interface VatSummary {
+  year: number;
+  invoiceCount: number;
+  totalNet: number;
+  totalTax: number;
+  totalGross: number;
+  rates: VatRateSummary[];
+}
+
+export interface CounterRollover {
+  lastNumber: string | null;
+  nextNumber: string;
+  /** Numbers of the next year have already been issued. */
+  nextYearStarted: boolean;
+  warnings: string[];
+}
+
+export interface FiscalCloseReport {
+  year: number;
+  success: boolean;
+  steps: FiscalStepResult[];
+  backupPath: string | null;
+  archiveJobId: string | null;
+  /** Local path of the downloaded archive, if a target was given. */
+  archivePath: string | null;
+  vatSummary: VatSummary | null;
+  rollover: CounterRollover | null;
+  completedAt: string;
+}
+
+interface RawVatSummary {
+  year: number;
+  invoice_count: number;
+  total_net: number;
+  total_tax: number;
+  total_gross: number;
+  rates: Array<{
+    tax_rate: number;
+    invoice_count: number;
+    net: number;
+    tax: number;
+    gross: number;
+  }>;
+}
+
+interface RawArchive {
+  job_id: string;
+  filename: string;
+  invoice_count: number;
+  pdf_count: number;
+  attachment_count: number;
+}
+
+interface RawRollover {
+  last_number: string | null;
+  next_number: string;
+  next_year_started: boolean;
+  warnings: string[];
+}
+
+const STEPS: FiscalCloseStep[] = ["backup", "archive", "vat-summary", "rollover"];
+/** Backups and archives of large databases can take a while. */
+const STEP_TIMEOUT_MS = 5 * 60_000;
+
+function mapVatSummary(raw: RawVatSummary): VatSummary {
+  return {
+    year: raw.year,
+    invoiceCount: raw.invoice_count,
+    totalNet: raw.total_net,
+    totalTax: raw.total_tax,
+    totalGross: raw.total_gross,
+    rates: raw.rates.map((rate) => ({
+      taxRate: rate.tax_rate,
+      invoiceCount: rate.invoice_count,
+      net: rate.net,
+      tax: rate.tax,
+      gross: rate.gross,
+    })),
+  };
+}
+
+/**
+ * Close a fiscal year: backup, archive, VAT summary and rollover check.
+ *
+ * @param year Fiscal year, e.g. 2025
+ * @param archiveTargetPath Optional absolute path to save the archive ZIP to
+ * @returns Report with the outcome of every step (never throws for step failures)
+ */
+export async function closeFiscalYear(
+  year: number,
+  archiveTargetPath?: string
+): Promise<FiscalCloseReport> {
+  if (!Number.isInteger(year) || year < 2000 || year > 2098) {
+    throw new AppError("invalid_input", `Invalid fiscal year: ${year}`);
+  }
+
+  const report: FiscalCloseReport = {
+    year,
+    success: false,
+    steps: STEPS.map((step): FiscalStepResult => ({ step, status: "skipped", detail: null })),
+    backupPath: null,
+    archiveJobId: null,
+    archivePath: null,
+    vatSummary: null,
+    rollover: null,
+    completedAt: "",
+  };
+
+  const { id, signal } = beginOperation("fiscal-close", `Jahresabschluss ${year}`);
+  const options = { signal, timeoutMs: STEP_TIMEOUT_MS };
+
+  const runners: Record<FiscalCloseStep, () => Promise<Omit<FiscalStepResult, "step">>> = {
+    backup: async () => {
+      const result = (await callBackend("POST /backups/trigger", options)) as {
+        backup_path?: string;
+      };
+      report.backupPath = result.backup_path ?? null;
+      return { status: "ok", detail: report.backupPath };
+    },
+    archive: async () => {
+      const archive = await requestBackend<RawArchive>(`/fiscal-years/${year}/archive`, {
+        ...options,
+        method: "POST",
+        body: backendCsvDialect("fiscalArchive"),
+      });
+      report.archiveJobId = archive.job_id;
+      if (archiveTargetPath) {
+        report.archivePath = (await downloadExport(archive.job_id, archiveTargetPath)).path;
+      }
+      const counts =
+        `${archive.invoice_count} Rechnungen, ${archive.pdf_count} PDFs, ` +
+        `${archive.attachment_count} Anhänge`;
+      return { status: "ok", detail: `${archive.filename} (${counts})` };
+    },
+    "vat-summary": async () => {
+      const summary = mapVatSummary(
+        await requestBackend<RawVatSummary>(`/fiscal-years/${year}/vat-summary`, options)
+      );
+      report.vatSummary = summary;
+      return {
+        status: summary.invoiceCount === 0 ? "warning" : "ok",
+        detail: summary.invoiceCount === 0 ? `Keine Rechnungen in ${year}` : null,
+      };
+    },
+    rollover: async () => {
+      const raw = await requestBackend<RawRollover>(`/fiscal-years/${year}/rollover`, options);
+      report.rollover = {
+        lastNumber: raw.last_number,
+        nextNumber: raw.next_number,
+        nextYearStarted: raw.next_year_started,
+        warnings: raw.warnings,
+      };
+      return {
+        status: raw.warnings.length > 0 ? "warning" : "ok",
+        detail: raw.warnings.length > 0 ? raw.warnings.join(" ") : raw.next_number,
+      };
+    },
+  };
+
+  log.info(`📅 Closing fiscal year ${year}`);
+  try {
+    for (const [index, step] of STEPS.entries()) {
+      emitEvent("fiscal-year:progress", { year, step, index, total: STEPS.length });
+      try {
+        report.steps[index] = { step, ...(await runners[step]()) };
+      } catch (err) {
+        const detail = err instanceof Error ? err.message : String(err);
+        report.steps[index] = { step, status: "failed", detail };
+        log.error(`❌ Fiscal year ${year}: step ${step} failed: ${detail}`);
+        break;
+      }
+    }
+  } finally {
+    endOperation(id);
+  }
+
+  report.success = report.steps.every((s) => s.status === "ok" || s.status === "warning");
+  report.completedAt = new Date().toISOString();
+  log.info(
+    `📅 Fiscal year ${year} ${report.success ? "closed" : "not closed"}: ` +
+      report.steps.map((s) => `${s.step}=${s.status}`).join(", ")
+  );
+  return report;
+}
+
+/**
+ * Register IPC handlers for the fiscal-year close.
+ */
+export function registerFiscalHandlers(): void {
+  handle("close-fiscal-year", async (_event, year: number, saveArchive?: boolean) => {
+    if (!saveArchive) return closeFiscalYear(year);
+    const archiveTargetPath = await chooseSavePath({
+      title: "Jahresarchiv speichern",
+      defaultName: `billino-archiv-${year}.zip`,
+      filters: [{ name: "ZIP-Archiv", extensions: ["zip"] }],
+    });
+    return archiveTargetPath ? closeFiscalYear(year, archiveTargetPath) : null;
+  });
+}
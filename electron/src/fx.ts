@@ -0,0 +1,253 @@
+/**
+ * Billino Desktop – ECB Exchange Rates
+ *
+ * Fetches the ECB euro foreign exchange reference rates and caches them in
+ * AppData/Roaming/Billino/fx-rates.json, so invoices to non-EUR customers
+ * get correct EUR equivalents even while offline:
+ * - the last 90 days are refreshed in the background (at most hourly)
+ * - older dates load the full history once, on demand
+ * - weekends and TARGET holidays use the last published rate before the date
+ *
+ * ECB rates are quoted as 1 EUR = x units of the foreign currency.
+ */
+
+import { app } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+
+export interface FxConversion {
+  amount: number;
+  currency: string;
+  /** Requested date (YYYY-MM-DD). */
+  date: string;
+  /** Units of `currency` per EUR. */
+  rate: number;
+  /** Publication date of the rate used (≤ date). */
+  rateDate: string;
+  eurAmount: number;
+  /** The rate is older than expected for the date (offline, no newer data). */
+  stale: boolean;
+}
+
+export interface FxCacheInfo {
+  fetchedAt: string | null;
+  firstDate: string | null;
+  lastDate: string | null;
+  days: number;
+  currencies: string[];
+}
+
+interface FxCache {
+  fetchedAt: string | null;
+  /** date → currency → rate */
+  rates: Record<string, Record<string, number>>;
+}
+
+const ECB_BASE_URL = "https://www.ecb.europa.eu/stats/eurofxref";
+const RECENT_URL = `${ECB_BASE_URL}/eurofxref-hist-90d.xml`;
+const HISTORY_URL = `${ECB_BASE_URL}/eurofxref-hist.xml`;
+
+/** First publication of the reference rates. */
+const ECB_FIRST_DATE = "1999-01-04";
+const FETCH_TIMEOUT_MS = 30_000;
+const REFRESH_INTERVAL_MS = 60 * 60 * 1000;
+/** Longest regular gap between two publications (Easter weekend). */
+const MAX_PUBLICATION_GAP_DAYS = 4;
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+let cache: FxCache | null = null;
+
+function getCachePath(): string {
+  return path.join(app.getPath("userData"), "fx-rates.json");
+}
+
+function loadCache(): FxCache {
+  if (cache) return cache;
+  try {
+    cache = JSON.parse(fs.readFileSync(getCachePath(), "utf-8")) as FxCache;
+  } catch {
+    cache = { fetchedAt: null, rates: {} };
+  }
+  return cache;
+}
+
+function saveCache(updated: FxCache): void {
+  const cachePath = getCachePath();
+  fs.writeFileSync(`${cachePath}.tmp`, JSON.stringify(updated), "utf-8");
+  fs.renameSync(`${cachePath}.tmp`, cachePath);
+  cache = updated;
+}
+
+/**
+ * Parse the ECB XML feed (`<Cube time="..."><Cube currency=".." rate=".."/>`).
+ */
+export function parseEcbXml(xml: string): FxCache["rates"] {
+  const rates: FxCache["rates"] = {};
+  const days = xml.split(/<Cube\s+time=/).slice(1);
+  for (const day of days) {
+    const date = /^["'](\d{4}-\d{2}-\d{2})["']/.exec(day)?.[1];
+    if (!date) continue;
+    rates[date] = {};
+    const entries = day.matchAll(/currency=["']([A-Z]{3})["']\s+rate=["']([\d.]+)["']/g);
+    for (const [, currency, rate] of entries) {
+      rates[date][currency] = Number(rate);
+    }
+  }
+  return rates;
+}
+
+async function fetchRates(url: string): Promise<number> {
+  const response = await fetch(url, {
+    signal: AbortSignal.timeout(FETCH_TIMEOUT_MS),
+  }).catch((err) => {
+    // Offline or timed out – not a backend problem
+    throw new AppError("network_error", `ECB rate download failed (${url}): ${err}`);
+  });
+  if (!response.ok) {
+    throw new AppError(
+      "network_error",
+      `ECB rate download failed (${url}): HTTP ${response.status}`
+    );
+  }
+  const fetched = parseEcbXml(await response.text());
+  const current = loadCache();
+  saveCache({
+    fetchedAt: new Date().toISOString(),
+    rates: { ...current.rates, ...fetched },
+  });
+  const days = Object.keys(fetched).length;
+  log.info(`💱 ECB rates updated: ${days} days from ${path.basename(url)}`);
+  return days;
+}
+
+/**
+ * Download the latest 90 days of reference rates.
+ *
+ * @param full Load the complete history since 1999 instead
+ */
+export async function refreshFxRates(full = false): Promise<FxCacheInfo> {
+  await fetchRates(full ? HISTORY_URL : RECENT_URL);
+  return getFxCacheInfo();
+}
+
+/**
+ * Describe the offline cache.
+ */
+export function getFxCacheInfo(): FxCacheInfo {
+  const { fetchedAt, rates } = loadCache();
+  const dates = Object.keys(rates).sort();
+  const latest = dates.length > 0 ? rates[dates[dates.length - 1]] : {};
+  return {
+    fetchedAt,
+    firstDate: dates[0] ?? null,
+    lastDate: dates[dates.length - 1] ?? null,
+    days: dates.length,
+    currencies: Object.keys(latest).sort(),
+  };
+}
+
+function findRate(currency: string, date: string): { rate: number; rateDate: string } | null {
+  const { rates } = loadCache();
+  const candidates = Object.keys(rates)
+    .filter((d) => d <= date && rates[d][currency] !== undefined)
+    .sort();
+  const rateDate = candidates[candidates.length - 1];
+  return rateDate ? { rate: rates[rateDate][currency], rateDate } : null;
+}
+
+function daysBetween(from: string, to: string): number {
+  return Math.round((Date.parse(to) - Date.parse(from)) / DAY_MS);
+}
+
+function needsRefresh(date: string): boolean {
+  const info = getFxCacheInfo();
+  if (!info.lastDate || !info.fetchedAt) return true;
+  const recentlyFetched = Date.now() - Date.parse(info.fetchedAt) < REFRESH_INTERVAL_MS;
+  return date > info.lastDate && !recentlyFetched;
+}
+
+/**
+ * Convert a foreign-currency amount to EUR with the ECB rate of a date.
+ *
+ * Uses the cache when possible; downloads missing data when online. If the
+ * download fails, the newest cached rate before the date is used and the
+ * result is marked as stale.
+ *
+ * @param amount Amount in `currency`
+ * @param currency ISO 4217 code, e.g. "USD"
+ * @param date YYYY-MM-DD (default: today)
+ * @throws Error if no rate for the currency is available at all
+ */
+export async function convert(
+  amount: number,
+  currency: string,
+  date: string = new Date().toISOString().slice(0, 10)
+): Promise<FxConversion> {
+  const code = currency.toUpperCase();
+  if (!/^\d{4}-\d{2}-\d{2}$/.test(date) || date < ECB_FIRST_DATE) {
+    throw new AppError("invalid_input", `Invalid date: ${date}`);
+  }
+  if (code === "EUR") {
+    return {
+      amount,
+      currency: code,
+      date,
+      rate: 1,
+      rateDate: date,
+      eurAmount: amount,
+      stale: false,
+    };
+  }
+
+  try {
+    const info = getFxCacheInfo();
+    if (info.firstDate === null || date < info.firstDate) {
+      // Older than the cached window: the 90-day feed would not help
+      const olderThanRecentFeed = daysBetween(date, new Date().toISOString()) > 85;
+      await fetchRates(olderThanRecentFeed ? HISTORY_URL : RECENT_URL);
+    } else if (needsRefresh(date)) {
+      await fetchRates(RECENT_URL);
+    }
+  } catch (err) {
+    log.warn(`⚠️ ECB rates unavailable, using offline cache: ${err}`);
+  }
+
+  const found = findRate(code, date);
+  if (!found) {
+    throw new AppError("not_found", `No ECB exchange rate available for ${code} on ${date}`);
+  }
+  return {
+    amount,
+    currency: code,
+    date,
+    rate: found.rate,
+    rateDate: found.rateDate,
+    eurAmount: Math.round((amount / found.rate) * 100) / 100,
+    stale: daysBetween(found.rateDate, date) > MAX_PUBLICATION_GAP_DAYS,
+  };
+}
+
+/**
+ * Refresh the cache in the background (startup); failures are only logged.
+ */
+export function initFxRates(): void {
+  if (!needsRefresh(new Date().toISOString().slice(0, 10))) return;
+  refreshFxRates().catch((err) => log.warn(`⚠️ ECB rate refresh failed: ${err}`));
+}
+
+/**
+ * Register IPC handlers for currency conversion.
+ */
+export function registerFxHandlers(): void {
+  handle(
+    "convert-currency",
+    (_event, amount: number, currency: string, date?: string) =>
+      convert(amount, currency, date),
+    "read"
+  );
+  handle("get-fx-cache-info", () => getFxCacheInfo(), "read");
+  handle("refresh-fx-rates", (_event, full?: boolean) => refreshFxRates(full));
+}
@@ -0,0 +1,336 @@
+/**
+ * Billino Desktop – Backend Health Model
+ *
+ * Typed view of the backend's `/health` response. Fields added in later
+ * backend versions are optional and get defaults, so the shell keeps
+ * working against older backend builds.
+ *
+ * Consumers that only need a recent status (UI status widget, commands)
+ * use `getBackendHealth()`, which shares the last result per URL for a few
+ * seconds and joins a check that is already running. Callers that wait on
+ * the backend pass the lifecycle signal, so shutdown does not wait for an
+ * in-flight check.
+ *
+ * Every completed check is also kept as a sample (latency, DB response
+ * time, success) for the last 24 hours; `getHealthMetrics()` aggregates a
+ * time window of them (p50/p95 latency, failure rate, availability) for
+ * the backend status dashboard.
+ */
+
+export interface HealthStatus {
+  status: "ok" | "starting" | "degraded" | "error" | string;
+  ready: boolean;
+  uptimeMs: number;
+  timestamp: string | null;
+  dbStatus: "ok" | "locked" | "error" | "unknown" | string;
+  dbResponseTimeMs: number | null;
+  environment: string | null;
+  /** Backend application version (null if not reported). */
+  appVersion: string | null;
+  /** Missing tables/columns in the DB schema (null if not reported). */
+  pendingMigrations: number | null;
+  /** ISO timestamp of the newest DB backup (null if none/not reported). */
+  lastBackup: string | null;
+  /** PDF generations currently running in the backend. */
+  queueDepth: number;
+}
+
+/** Raw `/health` payload as sent by the backend (snake_case). */
+interface RawHealthResponse {
+  status?: string;
+  ready?: boolean;
+  uptime_ms?: number;
+  timestamp?: string;
+  db_status?: string;
+  db_response_time_ms?: number;
+  version?: string;
+  environment?: string;
+  pending_migrations?: number | null;
+  last_backup?: string | null;
+  queue_depth?: number;
+}
+
+/**
+ * Convert a raw `/health` payload into a HealthStatus, applying defaults.
+ */
+export function parseHealthStatus(raw: unknown): HealthStatus {
+  const data = (raw ?? {}) as RawHealthResponse;
+  return {
+    status: data.status ?? "unknown",
+    ready: data.ready === true,
+    uptimeMs: data.uptime_ms ?? 0,
+    timestamp: data.timestamp ?? null,
+    dbStatus: data.db_status ?? "unknown",
+    dbResponseTimeMs: data.db_response_time_ms ?? null,
+    environment: data.environment ?? null,
+    appVersion: data.version ?? null,
+    pendingMigrations: data.pending_migrations ?? null,
+    lastBackup: data.last_backup ?? null,
+    queueDepth: data.queue_depth ?? 0,
+  };
+}
+
+/**
+ * Whether the backend reports itself ready for traffic.
+ */
+export function isHealthy(health: HealthStatus): boolean {
+  return health.ready || health.status === "ok";
+}
+
+// ─── Health Check ────────────────────────────────────────────────────────────
+
+export type HealthErrorCode =
+  | "unreachable"
+  | "timeout"
+  | "http_error"
+  | "invalid_response"
+  | "cancelled";
+
+export interface HealthCheckError {
+  code: HealthErrorCode;
+  message: string;
+}
+
+export type HealthCheckResult =
+  | { ok: true; health: HealthStatus; latencyMs: number; checkedAt: string }
+  | { ok: false; error: HealthCheckError; latencyMs: number; checkedAt: string };
+
+/**
+ * Perform a single health check against the backend.
+ *
+ * Never throws – failures are returned as a typed error so IPC callers
+ * (which only receive serialized values) can distinguish the cause.
+ *
+ * @param signal Aborts the request (result: `cancelled`), e.g. on shutdown
+ */
+export async function performHealthCheck(
+  healthUrl: string,
+  timeoutMs = 2_000,
+  signal?: AbortSignal
+): Promise<HealthCheckResult> {
+  const start = Date.now();
+  const checkedAt = new Date(start).toISOString();
+  const fail = (code: HealthErrorCode, message: string): HealthCheckResult => ({
+    ok: false,
+    error: { code, message },
+    latencyMs: Date.now() - start,
+    checkedAt,
+  });
+
+  const timeout = AbortSignal.timeout(timeoutMs);
+  let response: Response;
+  try {
+    response = await fetch(healthUrl, {
+      signal: signal ? AbortSignal.any([signal, timeout]) : timeout,
+    });
+  } catch (err) {
+    if (signal?.aborted) {
+      return fail("cancelled", "Health check cancelled");
+    }
+    if (err instanceof Error && err.name === "TimeoutError") {
+      return fail("timeout", `No response from backend within ${timeoutMs}ms`);
+    }
+    return fail("unreachable", `Backend not reachable at ${healthUrl}: ${err}`);
+  }
+
+  if (!response.ok) {
+    return fail("http_error", `Health endpoint returned status ${response.status}`);
+  }
+
+  try {
+    const health = parseHealthStatus(await response.json());
+    return { ok: true, health, latencyMs: Date.now() - start, checkedAt };
+  } catch (err) {
+    return fail("invalid_response", `Invalid health response: ${err}`);
+  }
+}
+
+// ─── Cached Health ───────────────────────────────────────────────────────────
+
+export interface HealthCacheOptions {
+  /** Ignore the cached result and check now. */
+  forceRefresh?: boolean;
+  /** Accept a cached result up to this age (default 5 s). */
+  maxAgeMs?: number;
+  /** Stop waiting (result: `cancelled`); a shared check keeps running for the others. */
+  signal?: AbortSignal;
+}
+
+const DEFAULT_MAX_AGE_MS = 5_000;
+/** Completed checks kept for crash reports. */
+const MAX_RECENT_CHECKS = 50;
+
+export type RecordedHealthCheck = HealthCheckResult & { url: string };
+
+const recentChecks: RecordedHealthCheck[] = [];
+const lastResults = new Map<string, { result: HealthCheckResult; at: number }>();
+const running = new Map<string, Promise<HealthCheckResult>>();
+
+/**
+ * Health of the backend, from the cache if recent enough.
+ *
+ * Concurrent callers share one request; every completed check (also
+ * forced ones, e.g. the startup poll) refreshes the cache.
+ */
+export function getBackendHealth(
+  healthUrl: string,
+  options: HealthCacheOptions = {}
+): Promise<HealthCheckResult> {
+  const cached = lastResults.get(healthUrl);
+  const maxAgeMs = options.maxAgeMs ?? DEFAULT_MAX_AGE_MS;
+  if (!options.forceRefresh && cached && Date.now() - cached.at <= maxAgeMs) {
+    return Promise.resolve(cached.result);
+  }
+
+  let check = running.get(healthUrl);
+  if (!check) {
+    check = performHealthCheck(healthUrl)
+      .then((result) => {
+        lastResults.set(healthUrl, { result, at: Date.now() });
+        recentChecks.push({ ...result, url: healthUrl });
+        if (recentChecks.length > MAX_RECENT_CHECKS) recentChecks.shift();
+        recordHealthSample(result);
+        return result;
+      })
+      .finally(() => running.delete(healthUrl));
+    running.set(healthUrl, check);
+  }
+  return options.signal ? untilAborted(check, options.signal) : check;
+}
+
+/**
+ * The last completed health checks (oldest first).
+ */
+export function getRecentHealthChecks(): RecordedHealthCheck[] {
+  return [...recentChecks];
+}
+
+/**
+ * Resolve with `check`, or with a `cancelled` result as soon as `signal` aborts.
+ */
+function untilAborted(
+  check: Promise<HealthCheckResult>,
+  signal: AbortSignal
+): Promise<HealthCheckResult> {
+  const cancelled = (): HealthCheckResult => ({
+    ok: false,
+    error: { code: "cancelled", message: "Health check cancelled" },
+    latencyMs: 0,
+    checkedAt: new Date().toISOString(),
+  });
+  if (signal.aborted) return Promise.resolve(cancelled());
+
+  return new Promise((resolve) => {
+    const onAbort = (): void => resolve(cancelled());
+    signal.addEventListener("abort", onAbort, { once: true });
+    void check.then((result) => {
+      signal.removeEventListener("abort", onAbort);
+      resolve(result);
+    });
+  });
+}
+
+// ─── Health History ──────────────────────────────────────────────────────────
+
+export interface HealthSample {
+  /** Epoch ms of the check. */
+  at: number;
+  ok: boolean;
+  latencyMs: number;
+  dbResponseTimeMs: number | null;
+  /** Error code of a failed check, null otherwise. */
+  error: HealthErrorCode | null;
+}
+
+export interface Percentiles {
+  p50: number | null;
+  p95: number | null;
+  max: number | null;
+}
+
+export interface HealthMetrics {
+  windowSecs: number;
+  checks: number;
+  failures: number;
+  /** Failed share of the checks (0–1), null without checks. */
+  failureRate: number | null;
+  /** Successful share of the checks (0–1), null without checks. */
+  availability: number | null;
+  latencyMs: Percentiles;
+  dbResponseTimeMs: Percentiles;
+  /** Backend uptime as reported by the newest successful check. */
+  backendUptimeMs: number | null;
+  lastFailure: { at: string; error: HealthErrorCode } | null;
+  /** Samples of the window, oldest first. */
+  samples: HealthSample[];
+}
+
+const HEALTH_HISTORY_MS = 24 * 60 * 60 * 1000;
+/** Startup polls every 500 ms; keep the buffer bounded regardless. */
+const MAX_HEALTH_SAMPLES = 10_000;
+export const DEFAULT_METRICS_WINDOW_SECS = 3_600;
+
+const samples: HealthSample[] = [];
+let lastUptimeMs: number | null = null;
+
+function recordHealthSample(result: HealthCheckResult): void {
+  // Cancelled checks say nothing about the backend
+  if (!result.ok && result.error.code === "cancelled") return;
+  const at = Date.parse(result.checkedAt);
+  samples.push({
+    at,
+    ok: result.ok,
+    latencyMs: result.latencyMs,
+    dbResponseTimeMs: result.ok ? result.health.dbResponseTimeMs : null,
+    error: result.ok ? null : result.error.code,
+  });
+  if (result.ok) lastUptimeMs = result.health.uptimeMs;
+
+  const oldest = Date.now() - HEALTH_HISTORY_MS;
+  while (samples.length > 0 && (samples[0].at < oldest || samples.length > MAX_HEALTH_SAMPLES)) {
+    samples.shift();
+  }
+}
+
+/**
+ * Nearest-rank percentiles of the given values.
+ */
+export function percentiles(values: number[]): Percentiles {
+  if (values.length === 0) return { p50: null, p95: null, max: null };
+  const sorted = [...values].sort((a, b) => a - b);
+  const rank = (p: number): number => sorted[Math.max(0, Math.ceil(p * sorted.length) - 1)];
+  return { p50: rank(0.5), p95: rank(0.95), max: sorted[sorted.length - 1] };
+}
+
+/**
+ * Aggregates of the health checks in the last `windowSecs` seconds.
+ */
+export function getHealthMetrics(
+  windowSecs: number = DEFAULT_METRICS_WINDOW_SECS,
+  now: number = Date.now()
+): HealthMetrics {
+  const inWindow = samples.filter((sample) => sample.at >= now - windowSecs * 1000);
+  const failed = inWindow.filter((sample) => !sample.ok);
+  const succeeded = inWindow.filter((sample) => sample.ok);
+  const share = (count: number): number | null =>
+    inWindow.length > 0 ? Math.round((count / inWindow.length) * 10_000) / 10_000 : null;
+  const lastFailed = failed[failed.length - 1];
+  const dbTimes = succeeded
+    .map((sample) => sample.dbResponseTimeMs)
+    .filter((ms): ms is number => ms !== null);
+
+  return {
+    windowSecs,
+    checks: inWindow.length,
+    failures: failed.length,
+    failureRate: share(failed.length),
+    availability: share(succeeded.length),
+    latencyMs: percentiles(inWindow.map((sample) => sample.latencyMs)),
+    dbResponseTimeMs: percentiles(dbTimes),
+    backendUptimeMs: lastUptimeMs,
+    lastFailure: lastFailed
+      ? { at: new Date(lastFailed.at).toISOString(), error: lastFailed.error ?? "unreachable" }
+      : null,
+    samples: inWindow,
+  };
+}
@@ -0,0 +1,62 @@
+import { easterSunday, isBusinessDay, listHolidays, nextBusinessDay } from "./holidays";
+
+const day = (time: number): string => new Date(time).toISOString().slice(0, 10);
+
+describe("easterSunday", () => {
+  it("berechnet Ostersonntag", () => {
+    expect(day(easterSunday(2008))).toBe("2008-03-23");
+    expect(day(easterSunday(2019))).toBe("2019-04-21");
+    expect(day(easterSunday(2024))).toBe("2024-03-31");
+    expect(day(easterSunday(2025))).toBe("2025-04-20");
+  });
+});
+
+describe("listHolidays", () => {
+  const dateOf = (year: number, name: string, state: "SN" | null = "SN"): string | undefined =>
+    listHolidays(year, state).find((holiday) => holiday.name === name)?.date;
+
+  it("legt bewegliche Feiertage relativ zu Ostern", () => {
+    expect(dateOf(2024, "Karfreitag")).toBe("2024-03-29");
+    expect(dateOf(2024, "Christi Himmelfahrt")).toBe("2024-05-09");
+    expect(dateOf(2024, "Pfingstmontag")).toBe("2024-05-20");
+  });
+
+  it("legt Buß- und Bettag auf den Mittwoch vor dem 23. November", () => {
+    expect(dateOf(2023, "Buß- und Bettag")).toBe("2023-11-22");
+    expect(dateOf(2024, "Buß- und Bettag")).toBe("2024-11-20");
+    // Der 23.11.2022 ist selbst ein Mittwoch
+    expect(dateOf(2022, "Buß- und Bettag")).toBe("2022-11-16");
+    expect(dateOf(2024, "Buß- und Bettag", null)).toBeUndefined();
+  });
+
+  it("führt den Reformationstag 2017 nur einmal", () => {
+    const reformation = listHolidays(2017, "BB").filter((h) => h.date === "2017-10-31");
+
+    expect(reformation).toEqual([{ date: "2017-10-31", name: "Reformationstag", states: null }]);
+    expect(listHolidays(2017).some((h) => h.date === "2017-10-31")).toBe(true);
+    expect(listHolidays(2016).some((h) => h.date === "2016-10-31")).toBe(false);
+  });
+
+  it("fasst Feiertage am selben Tag zusammen", () => {
+    const may1 = listHolidays(2008).filter((h) => h.date === "2008-05-01");
+
+    expect(may1).toEqual([
+      { date: "2008-05-01", name: "Tag der Arbeit / Christi Himmelfahrt", states: null },
+    ]);
+  });
+
+  it("berücksichtigt Einführungsjahre", () => {
+    expect(dateOf(2018, "Internationaler Frauentag", null)).toBeUndefined();
+    expect(listHolidays(2019, "BE").some((h) => h.name === "Internationaler Frauentag")).toBe(true);
+  });
+});
+
+describe("nextBusinessDay", () => {
+  it("überspringt Wochenenden und Feiertage", () => {
+    expect(nextBusinessDay("2024-12-24", null)).toBe("2024-12-24");
+    expect(nextBusinessDay("2024-12-25", null)).toBe("2024-12-27");
+    expect(nextBusinessDay("2024-03-29", null)).toBe("2024-04-02");
+    expect(nextBusinessDay("2024-11-20", "SN")).toBe("2024-11-21");
+    expect(isBusinessDay("2024-11-20", null)).toBe(true);
+  });
+});
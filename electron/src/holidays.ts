@@ -0,0 +1,211 @@
+/**
+ * Billino Desktop – German Public Holidays
+ *
+ * Rule-based holiday calendar per Bundesland, used to move payment and
+ * dunning deadlines that fall on a weekend or public holiday to the next
+ * business day (§193 BGB). The user's state is configured in the settings
+ * (`calendar.state`); without a state only nationwide holidays apply.
+ *
+ * Only holidays valid in the whole state are included (e.g. not Mariä
+ * Himmelfahrt in Bavaria or Fronleichnam in parts of Saxony/Thuringia).
+ */
+
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import { getSettings } from "./settings";
+
+export const GERMAN_STATES = [
+  "BW", // Baden-Württemberg
+  "BY", // Bayern
+  "BE", // Berlin
+  "BB", // Brandenburg
+  "HB", // Bremen
+  "HH", // Hamburg
+  "HE", // Hessen
+  "MV", // Mecklenburg-Vorpommern
+  "NI", // Niedersachsen
+  "NW", // Nordrhein-Westfalen
+  "RP", // Rheinland-Pfalz
+  "SL", // Saarland
+  "SN", // Sachsen
+  "ST", // Sachsen-Anhalt
+  "SH", // Schleswig-Holstein
+  "TH", // Thüringen
+] as const;
+
+export type GermanState = (typeof GERMAN_STATES)[number];
+
+export interface Holiday {
+  /** YYYY-MM-DD */
+  date: string;
+  name: string;
+  /** null = nationwide */
+  states: GermanState[] | null;
+}
+
+interface HolidayRule {
+  name: string;
+  /** Fixed date ([month, day]) or offset in days from Easter Sunday. */
+  date: [number, number] | { easter: number } | "repentance";
+  states?: GermanState[];
+  from?: number;
+  until?: number;
+}
+
+const HOLIDAY_RULES: HolidayRule[] = [
+  { name: "Neujahr", date: [1, 1] },
+  { name: "Heilige Drei Könige", date: [1, 6], states: ["BW", "BY", "ST"] },
+  { name: "Internationaler Frauentag", date: [3, 8], states: ["BE"], from: 2019 },
+  { name: "Internationaler Frauentag", date: [3, 8], states: ["MV"], from: 2023 },
+  { name: "Karfreitag", date: { easter: -2 } },
+  { name: "Ostersonntag", date: { easter: 0 }, states: ["BB"] },
+  { name: "Ostermontag", date: { easter: 1 } },
+  { name: "Tag der Arbeit", date: [5, 1] },
+  { name: "Christi Himmelfahrt", date: { easter: 39 } },
+  { name: "Pfingstsonntag", date: { easter: 49 }, states: ["BB"] },
+  { name: "Pfingstmontag", date: { easter: 50 } },
+  { name: "Fronleichnam", date: { easter: 60 }, states: ["BW", "BY", "HE", "NW", "RP", "SL"] },
+  { name: "Mariä Himmelfahrt", date: [8, 15], states: ["SL"] },
+  { name: "Weltkindertag", date: [9, 20], states: ["TH"], from: 2019 },
+  { name: "Tag der Deutschen Einheit", date: [10, 3], from: 1990 },
+  { name: "Reformationstag", date: [10, 31], states: ["BB", "MV", "SN", "ST", "TH"] },
+  { name: "Reformationstag", date: [10, 31], states: ["HB", "HH", "NI", "SH"], from: 2018 },
+  // 500th anniversary of the Reformation: once nationwide
+  { name: "Reformationstag", date: [10, 31], from: 2017, until: 2017 },
+  { name: "Allerheiligen", date: [11, 1], states: ["BW", "BY", "NW", "RP", "SL"] },
+  { name: "Buß- und Bettag", date: "repentance", states: ["SN"] },
+  { name: "1. Weihnachtstag", date: [12, 25] },
+  { name: "2. Weihnachtstag", date: [12, 26] },
+];
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+/**
+ * Easter Sunday (Gregorian calendar, anonymous algorithm).
+ */
+export function easterSunday(year: number): number {
+  const a = year % 19;
+  const b = Math.floor(year / 100);
+  const c = year % 100;
+  const d = Math.floor(b / 4);
+  const e = b % 4;
+  const f = Math.floor((b + 8) / 25);
+  const g = Math.floor((b - f + 1) / 3);
+  const h = (19 * a + b - d - g + 15) % 30;
+  const i = Math.floor(c / 4);
+  const k = c % 4;
+  const l = (32 + 2 * e + 2 * i - h - k) % 7;
+  const m = Math.floor((a + 11 * h + 22 * l) / 451);
+  const month = Math.floor((h + l - 7 * m + 114) / 31);
+  const day = ((h + l - 7 * m + 114) % 31) + 1;
+  return Date.UTC(year, month - 1, day);
+}
+
+function ruleDate(rule: HolidayRule, year: number): number {
+  if (rule.date === "repentance") {
+    // Wednesday before 23 November
+    const nov23 = Date.UTC(year, 10, 23);
+    const back = (new Date(nov23).getUTCDay() + 4) % 7 || 7;
+    return nov23 - back * DAY_MS;
+  }
+  if (Array.isArray(rule.date)) {
+    return Date.UTC(year, rule.date[0] - 1, rule.date[1]);
+  }
+  return easterSunday(year) + rule.date.easter * DAY_MS;
+}
+
+function formatDay(time: number): string {
+  return new Date(time).toISOString().slice(0, 10);
+}
+
+function parseDay(value: string): number {
+  const match = /^(\d{4})-(\d{2})-(\d{2})/.exec(value);
+  if (!match) {
+    throw new AppError("invalid_input", `Invalid date: ${value}`);
+  }
+  return Date.UTC(Number(match[1]), Number(match[2]) - 1, Number(match[3]));
+}
+
+function assertState(state: string | null | undefined): void {
+  if (state && !(GERMAN_STATES as readonly string[]).includes(state)) {
+    throw new AppError("invalid_input", `Unknown German state: ${state}`);
+  }
+}
+
+/**
+ * Public holidays of a year, nationwide and (if given) of one state.
+ *
+ * One entry per date: rules that overlap (Reformationstag 2017 in the
+ * states that always have it) or holidays that fall on the same day
+ * (Tag der Arbeit and Christi Himmelfahrt in 2008) are merged.
+ */
+export function listHolidays(year: number, state: GermanState | null = null): Holiday[] {
+  assertState(state);
+  const byDate = new Map<string, Holiday>();
+  for (const rule of HOLIDAY_RULES) {
+    if (rule.from !== undefined && year < rule.from) continue;
+    if (rule.until !== undefined && year > rule.until) continue;
+    if (rule.states && (state === null || !rule.states.includes(state))) continue;
+
+    const date = formatDay(ruleDate(rule, year));
+    const states = rule.states ?? null;
+    const existing = byDate.get(date);
+    if (!existing) {
+      byDate.set(date, { date, name: rule.name, states });
+      continue;
+    }
+    if (!existing.name.split(" / ").includes(rule.name)) {
+      existing.name = `${existing.name} / ${rule.name}`;
+    }
+    existing.states =
+      existing.states === null || states === null
+        ? null
+        : [...new Set([...existing.states, ...states])];
+  }
+  return [...byDate.values()].sort((a, b) => a.date.localeCompare(b.date));
+}
+
+/**
+ * Whether a date is a business day (Mon–Fri, no public holiday).
+ */
+export function isBusinessDay(date: string, state: GermanState | null = null): boolean {
+  const time = parseDay(date);
+  const weekday = new Date(time).getUTCDay();
+  if (weekday === 0 || weekday === 6) return false;
+  const day = formatDay(time);
+  return !listHolidays(Number(day.slice(0, 4)), state).some((h) => h.date === day);
+}
+
+/**
+ * First business day on or after a date.
+ *
+ * @param state Bundesland (default: from settings `calendar.state`)
+ */
+export function nextBusinessDay(
+  date: string,
+  state: GermanState | null = getSettings().calendar.state
+): string {
+  let time = parseDay(date);
+  while (!isBusinessDay(formatDay(time), state)) {
+    time += DAY_MS;
+  }
+  return formatDay(time);
+}
+
+/**
+ * Register IPC handlers for holiday lookups.
+ */
+export function registerHolidayHandlers(): void {
+  handle(
+    "list-holidays",
+    (_event, year: number, state?: GermanState | null) =>
+      listHolidays(year, state === undefined ? getSettings().calendar.state : state),
+    "read"
+  );
+  handle(
+    "next-business-day",
+    (_event, date: string, state?: GermanState | null) =>
+      nextBusinessDay(date, state === undefined ? getSettings().calendar.state : state),
+    "read"
+  );
+}
@@ -0,0 +1,386 @@
+/**
+ * Billino Desktop – Automation Hooks
+ *
+ * Runs user-configured automations (settings: automation.hooks) when
+ * something happens, without any backend changes:
+ * - invoice.created: recognized in the backend output
+ *   (data: id, number)
+ * - backup.finished: recognized in the backend output, also for scheduled
+ *   backups (data: path)
+ * - backend.crashed: the backend process exited unexpectedly
+ *   (data: exitCode, signal)
+ *
+ * A hook either runs a program or POSTs to a webhook URL. Arguments and
+ * bodies may contain placeholders: {{event}}, {{timestamp}} and
+ * {{data.<field>}}.
+ *
+ * Safety: only executables listed in automation.allowedExecutables and
+ * webhook hosts in automation.allowedHosts are ever called. The renderer
+ * cannot write the automation section (update-settings rejects it): hooks
+ * are saved with `save-automation-hook`, which shows the program and its
+ * arguments (or the URL) in a native dialog and adds the target to the
+ * allowlist only once the user confirmed. Programs are started without a
+ * shell (placeholders cannot inject commands); webhooks require HTTPS
+ * except for localhost. Hook failures are logged and never affect the app.
+ */
+
+import { execFile } from "child_process";
+import { BrowserWindow, dialog, MessageBoxOptions } from "electron";
+import path from "path";
+import log from "electron-log/main";
+import { onBackendLogLine } from "./console";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import {
+  AutomationEvent,
+  AutomationHook,
+  AutomationSettings,
+  getSettings,
+  updateSettings,
+} from "./settings";
+
+export interface HookPayload {
+  event: AutomationEvent;
+  timestamp: string;
+  data: Record<string, string | number | null>;
+}
+
+export interface HookRunResult {
+  hookId: string;
+  success: boolean;
+  /** Exit code (command) or HTTP status (webhook). */
+  status: number | null;
+  output: string | null;
+  error: string | null;
+  durationMs: number;
+}
+
+const HOOK_TIMEOUT_MS = 30_000;
+const MAX_OUTPUT_CHARS = 2_000;
+
+/** Backend log lines that mark a domain event. */
+const BACKEND_EVENT_PATTERNS: Array<{
+  event: AutomationEvent;
+  pattern: RegExp;
+  data: (match: RegExpExecArray) => HookPayload["data"];
+}> = [
+  {
+    event: "invoice.created",
+    pattern: /Invoice (.+) created successfully \(id=(\d+)\)/,
+    data: (match) => ({ number: match[1], id: Number(match[2]) }),
+  },
+  {
+    event: "backup.finished",
+    pattern: /Datenbank-Backup erstellt: (.+)$/,
+    data: (match) => ({ path: match[1].trim() }),
+  },
+];
+
+/** Example data for test runs. */
+const SAMPLE_DATA: Record<AutomationEvent, HookPayload["data"]> = {
+  "invoice.created": { id: 1, number: "25 | 001" },
+  "backup.finished": { path: "backups/daily/billino_2025-01-01_12-00-00.db" },
+  "backend.crashed": { exitCode: 1, signal: null },
+};
+
+/**
+ * Replace {{event}}, {{timestamp}} and {{data.<field>}}.
+ *
+ * @param escape Applied to every inserted value (e.g. for JSON strings)
+ */
+export function renderTemplate(
+  template: string,
+  payload: HookPayload,
+  escape: (value: string) => string = (value) => value
+): string {
+  return template.replace(/\{\{\s*([\w.]+)\s*\}\}/g, (match, key: string) => {
+    let value: unknown;
+    if (key === "event") value = payload.event;
+    else if (key === "timestamp") value = payload.timestamp;
+    else if (key.startsWith("data.")) value = payload.data[key.slice(5)];
+    return value === undefined ? match : escape(value === null ? "" : String(value));
+  });
+}
+
+function sameExecutable(a: string, b: string): boolean {
+  const left = path.resolve(a);
+  const right = path.resolve(b);
+  return process.platform === "win32" ? left.toLowerCase() === right.toLowerCase() : left === right;
+}
+
+/**
+ * Reason why a hook target is not allowed, or null if it is.
+ */
+export function checkHookAllowed(
+  hook: AutomationHook,
+  settings: AutomationSettings
+): string | null {
+  if (hook.type === "command") {
+    if (!path.isAbsolute(hook.target)) return "Programmpfad muss absolut sein";
+    return settings.allowedExecutables.some((allowed) => sameExecutable(allowed, hook.target))
+      ? null
+      : `Programm nicht freigegeben: ${hook.target}`;
+  }
+
+  let url: URL;
+  try {
+    url = new URL(hook.target);
+  } catch {
+    return `Ungültige URL: ${hook.target}`;
+  }
+  const local = ["localhost", "127.0.0.1", "[::1]"].includes(url.hostname);
+  if (url.protocol !== "https:" && !(url.protocol === "http:" && local)) {
+    return "Webhooks benötigen HTTPS (außer localhost)";
+  }
+  return settings.allowedHosts.some((host) => host.toLowerCase() === url.hostname.toLowerCase())
+    ? null
+    : `Host nicht freigegeben: ${url.hostname}`;
+}
+
+function runCommand(hook: AutomationHook, payload: HookPayload): Promise<HookRunResult> {
+  const start = Date.now();
+  const args = hook.args.map((arg) => renderTemplate(arg, payload));
+  return new Promise((resolve) => {
+    execFile(
+      hook.target,
+      args,
+      {
+        timeout: HOOK_TIMEOUT_MS,
+        windowsHide: true,
+        env: { ...process.env, BILLINO_EVENT: payload.event },
+      },
+      (err, stdout, stderr) => {
+        const output = `${stdout}${stderr}`.trim().slice(0, MAX_OUTPUT_CHARS) || null;
+        const exitCode = err ? (typeof err.code === "number" ? err.code : null) : 0;
+        resolve({
+          hookId: hook.id,
+          success: !err,
+          status: exitCode,
+          output,
+          error: err ? err.message : null,
+          durationMs: Date.now() - start,
+        });
+      }
+    );
+  });
+}
+
+async function runWebhook(hook: AutomationHook, payload: HookPayload): Promise<HookRunResult> {
+  const start = Date.now();
+  const body = hook.body
+    ? renderTemplate(hook.body, payload, (value) => JSON.stringify(value).slice(1, -1))
+    : JSON.stringify(payload);
+  try {
+    const response = await fetch(hook.target, {
+      method: "POST",
+      headers: { "Content-Type": "application/json", "User-Agent": "Billino-Desktop" },
+      body,
+      signal: AbortSignal.timeout(HOOK_TIMEOUT_MS),
+    });
+    const text = (await response.text()).slice(0, MAX_OUTPUT_CHARS);
+    return {
+      hookId: hook.id,
+      success: response.ok,
+      status: response.status,
+      output: text || null,
+      error: response.ok ? null : `HTTP ${response.status}`,
+      durationMs: Date.now() - start,
+    };
+  } catch (err) {
+    return {
+      hookId: hook.id,
+      success: false,
+      status: null,
+      output: null,
+      error: err instanceof Error ? err.message : String(err),
+      durationMs: Date.now() - start,
+    };
+  }
+}
+
+/**
+ * Run a single hook (allowlist is checked first).
+ */
+export async function runHook(hook: AutomationHook, payload: HookPayload): Promise<HookRunResult> {
+  const denied = checkHookAllowed(hook, getSettings().automation);
+  if (denied) {
+    return {
+      hookId: hook.id,
+      success: false,
+      status: null,
+      output: null,
+      error: denied,
+      durationMs: 0,
+    };
+  }
+  const result =
+    hook.type === "command" ? await runCommand(hook, payload) : await runWebhook(hook, payload);
+  const outcome = result.success ? "ok" : `failed: ${result.error}`;
+  log.info(`🪝 Hook ${hook.id} (${payload.event}) ${outcome} in ${result.durationMs} ms`);
+  return result;
+}
+
+/**
+ * Run all enabled hooks for an event in the background.
+ */
+export function fireHooks(event: AutomationEvent, data: HookPayload["data"]): void {
+  const hooks = getSettings().automation.hooks.filter((h) => h.enabled && h.event === event);
+  if (hooks.length === 0) return;
+
+  const payload: HookPayload = { event, timestamp: new Date().toISOString(), data };
+  for (const hook of hooks) {
+    void runHook(hook, payload).catch((err) => log.warn(`⚠️ Hook ${hook.id} crashed: ${err}`));
+  }
+}
+
+/**
+ * Watch the backend output for domain events.
+ */
+export function initAutomationHooks(): void {
+  onBackendLogLine((line) => {
+    for (const { event, pattern, data } of BACKEND_EVENT_PATTERNS) {
+      const match = pattern.exec(line.text);
+      if (match) fireHooks(event, data(match));
+    }
+  });
+}
+
+// ─── Configuration ───────────────────────────────────────────────────────────
+
+function isValidHook(hook: unknown): hook is AutomationHook {
+  const h = hook as Partial<AutomationHook>;
+  return (
+    typeof h === "object" &&
+    h !== null &&
+    typeof h.id === "string" &&
+    h.id.length > 0 &&
+    typeof h.event === "string" &&
+    Object.keys(SAMPLE_DATA).includes(h.event) &&
+    typeof h.enabled === "boolean" &&
+    (h.type === "command" || h.type === "webhook") &&
+    typeof h.target === "string" &&
+    Array.isArray(h.args) &&
+    h.args.every((arg) => typeof arg === "string") &&
+    (h.body === null || typeof h.body === "string")
+  );
+}
+
+/** Allowlist with the hook's target added. */
+function allowTarget(settings: AutomationSettings, hook: AutomationHook): AutomationSettings {
+  if (hook.type === "command") {
+    const known = settings.allowedExecutables.some((allowed) =>
+      sameExecutable(allowed, hook.target)
+    );
+    return known
+      ? settings
+      : { ...settings, allowedExecutables: [...settings.allowedExecutables, hook.target] };
+  }
+  let host: string;
+  try {
+    host = new URL(hook.target).hostname.toLowerCase();
+  } catch {
+    return settings;
+  }
+  const known = settings.allowedHosts.some((allowed) => allowed.toLowerCase() === host);
+  return known ? settings : { ...settings, allowedHosts: [...settings.allowedHosts, host] };
+}
+
+/** Whether what the hook runs (program, arguments, URL, body) is unchanged. */
+function sameAction(a: AutomationHook, b: AutomationHook): boolean {
+  return (
+    a.type === b.type &&
+    a.target === b.target &&
+    a.body === b.body &&
+    a.args.length === b.args.length &&
+    a.args.every((arg, index) => arg === b.args[index])
+  );
+}
+
+async function confirmHook(hook: AutomationHook): Promise<boolean> {
+  const action =
+    hook.type === "command"
+      ? `Programm: ${hook.target}\nArgumente: ${hook.args.join(" ") || "(keine)"}`
+      : `Adresse: ${hook.target}`;
+  const options: MessageBoxOptions = {
+    type: "warning",
+    title: "Billino – Automation freigeben",
+    message:
+      hook.type === "command"
+        ? "Soll Billino dieses Programm automatisch ausführen?"
+        : "Soll Billino diese Adresse automatisch aufrufen?",
+    detail:
+      `${action}\nEreignis: ${hook.event}\n\n` +
+      "Nur freigeben, wenn Sie diese Automation selbst eingerichtet haben.",
+    buttons: ["Freigeben", "Abbrechen"],
+    defaultId: 1,
+    cancelId: 1,
+    noLink: true,
+  };
+  const parent = BrowserWindow.getFocusedWindow();
+  const { response } = parent
+    ? await dialog.showMessageBox(parent, options)
+    : await dialog.showMessageBox(options);
+  return response === 0;
+}
+
+/**
+ * Add or replace a hook. A new or changed program, argument list, URL or
+ * body needs the user's confirmation in a native dialog; its target is then
+ * added to the allowlist.
+ *
+ * @returns The automation settings, or null if the user declined
+ */
+export async function saveAutomationHook(hook: unknown): Promise<AutomationSettings | null> {
+  if (!isValidHook(hook)) throw new AppError("invalid_input", "Invalid automation hook");
+
+  const automation = getSettings().automation;
+  const existing = automation.hooks.find((h) => h.id === hook.id);
+  let allowed = automation;
+  if (!existing || !sameAction(existing, hook) || checkHookAllowed(hook, automation) !== null) {
+    // Errors that remain with the target allowed cannot be confirmed away
+    const invalid = checkHookAllowed(hook, allowTarget(automation, hook));
+    if (invalid) throw new AppError("invalid_input", invalid, { message: invalid });
+    if (!(await confirmHook(hook))) {
+      log.info(`🪝 Hook ${hook.id} not confirmed`);
+      return null;
+    }
+    allowed = allowTarget(automation, hook);
+    log.info(`🪝 Hook ${hook.id} confirmed: ${hook.type} ${hook.target}`);
+  }
+
+  const hooks = existing
+    ? automation.hooks.map((h) => (h.id === hook.id ? hook : h))
+    : [...automation.hooks, hook];
+  return updateSettings({ automation: { ...allowed, hooks } }).automation;
+}
+
+/**
+ * Remove a hook (no confirmation needed; the allowlist stays as it is).
+ */
+export function removeAutomationHook(hookId: string): AutomationSettings {
+  const automation = getSettings().automation;
+  if (!automation.hooks.some((h) => h.id === hookId)) {
+    throw new AppError("not_found", `Unknown hook: ${hookId}`);
+  }
+  log.info(`🪝 Hook ${hookId} removed`);
+  const hooks = automation.hooks.filter((h) => h.id !== hookId);
+  return updateSettings({ automation: { ...automation, hooks } }).automation;
+}
+
+/**
+ * Register IPC handlers for automation hooks.
+ */
+export function registerHookHandlers(): void {
+  // Runs the hook once with example data so the user can check it
+  handle("test-automation-hook", (_event, hookId: string) => {
+    const hook = getSettings().automation.hooks.find((h) => h.id === hookId);
+    if (!hook) throw new AppError("not_found", `Unknown hook: ${hookId}`);
+    return runHook(hook, {
+      event: hook.event,
+      timestamp: new Date().toISOString(),
+      data: SAMPLE_DATA[hook.event],
+    });
+  });
+  handle("save-automation-hook", (_event, hook: unknown) => saveAutomationHook(hook));
+  handle("remove-automation-hook", (_event, hookId: string) => removeAutomationHook(hookId));
+}
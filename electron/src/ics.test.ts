@@ -0,0 +1,60 @@
+import { buildIcs, escapeText, foldLine } from "./ics";
+
+const unfold = (text: string): string => text.replace(/\r\n /g, "");
+
+describe("escapeText", () => {
+  it("maskiert Backslash, Semikolon, Komma und Zeilenumbrüche", () => {
+    expect(escapeText("a\\b; c, d\r\ne\nf")).toBe("a\\\\b\\; c\\, d\\ne\\nf");
+  });
+});
+
+describe("foldLine", () => {
+  it("lässt kurze Zeilen unverändert", () => {
+    expect(foldLine("SUMMARY:kurz")).toBe("SUMMARY:kurz");
+  });
+
+  it("bricht nach 75 Oktetts um und rückt Folgezeilen ein", () => {
+    const line = `DESCRIPTION:${"x".repeat(150)}`;
+    const parts = foldLine(line).split("\r\n");
+
+    expect(parts[0]).toHaveLength(75);
+    expect(parts.slice(1).every((part) => part.startsWith(" "))).toBe(true);
+    expect(unfold(foldLine(line))).toBe(line);
+  });
+
+  it("zählt Bytes und teilt keine Umlaute", () => {
+    const line = `SUMMARY:${"ä".repeat(60)}`;
+    const folded = foldLine(line);
+
+    for (const part of folded.split("\r\n")) {
+      expect(Buffer.byteLength(part, "utf-8")).toBeLessThanOrEqual(75);
+    }
+    expect(unfold(folded)).toBe(line);
+  });
+});
+
+describe("buildIcs", () => {
+  it("erzeugt ganztägige Termine mit CRLF", () => {
+    const ics = buildIcs(
+      [
+        {
+          invoiceId: 1,
+          uid: "invoice-1-due@billino",
+          date: "2024-12-31",
+          summary: "Zahlung fällig: 24 | 001, Müller",
+          description: "Rechnung 24 | 001\nBetrag: 119,00 €",
+        },
+      ],
+      new Date("2024-12-01T08:30:00.123Z")
+    );
+    const lines = ics.split("\r\n");
+
+    expect(ics.endsWith("END:VCALENDAR\r\n")).toBe(true);
+    expect(lines).toContain("UID:invoice-1-due@billino");
+    expect(lines).toContain("DTSTAMP:20241201T083000Z");
+    expect(lines).toContain("DTSTART;VALUE=DATE:20241231");
+    expect(lines).toContain("DTEND;VALUE=DATE:20250101");
+    expect(lines).toContain("SUMMARY:Zahlung fällig: 24 | 001\\, Müller");
+    expect(lines).toContain("DESCRIPTION:Rechnung 24 | 001\\nBetrag: 119\\,00 €");
+  });
+});
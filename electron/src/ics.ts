@@ -0,0 +1,225 @@
+/**
+ * Billino Desktop – Calendar Export (iCalendar)
+ *
+ * Writes the payment due dates and dunning deadlines of a date range as an
+ * .ics file, so upcoming payments show up in Outlook, Google or Apple
+ * Calendar. Dates follow the dunning policy (payment term, business days,
+ * reminder levels) – see dunning.ts.
+ *
+ * By default the file goes to AppData/Roaming/Billino/calendar/
+ * billino-termine.ics and is overwritten on every export; with
+ * `chooseLocation` the user picks the destination in a save dialog (the
+ * renderer never passes a path). Event UIDs are stable, so calendars that
+ * subscribe to (or re-import) this file update events instead of
+ * duplicating them.
+ */
+
+import { app } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { computeDueDate, DunningInvoice, fetchInvoices } from "./dunning";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import { chooseSavePath } from "./savedialog";
+import { getSettings } from "./settings";
+
+export interface IcsRange {
+  /** First day (YYYY-MM-DD, default: today). */
+  from?: string;
+  /** Last day (YYYY-MM-DD, default: 90 days after `from`). */
+  to?: string;
+  /** Invoices to leave out (already paid). */
+  excludeInvoiceIds?: number[];
+}
+
+export interface IcsExportResult {
+  path: string;
+  from: string;
+  to: string;
+  events: number;
+  invoices: number;
+}
+
+export interface CalendarEvent {
+  invoiceId: number;
+  uid: string;
+  date: string;
+  summary: string;
+  description: string;
+}
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+const DEFAULT_RANGE_DAYS = 90;
+/** Longest shift of a due date by weekends/holidays (Easter, Christmas). */
+const MAX_BUSINESS_DAY_SHIFT = 5;
+
+function addDays(day: string, days: number): string {
+  return new Date(Date.parse(`${day}T00:00:00Z`) + days * DAY_MS).toISOString().slice(0, 10);
+}
+
+function today(): string {
+  const now = new Date();
+  return new Date(Date.UTC(now.getFullYear(), now.getMonth(), now.getDate()))
+    .toISOString()
+    .slice(0, 10);
+}
+
+function formatEuro(amount: number): string {
+  return amount.toLocaleString("de-DE", { style: "currency", currency: "EUR" });
+}
+
+/** Escape TEXT values (RFC 5545 3.3.11). */
+export function escapeText(value: string): string {
+  return value
+    .replace(/\\/g, "\\\\")
+    .replace(/;/g, "\\;")
+    .replace(/,/g, "\\,")
+    .replace(/\r?\n/g, "\\n");
+}
+
+/** Fold content lines longer than 75 octets (RFC 5545 3.1). */
+export function foldLine(line: string): string {
+  const parts: string[] = [];
+  let current = "";
+  for (const char of line) {
+    const limit = parts.length === 0 ? 75 : 74;
+    if (Buffer.byteLength(current + char, "utf-8") > limit) {
+      parts.push(current);
+      current = "";
+    }
+    current += char;
+  }
+  parts.push(current);
+  return parts.join("\r\n ");
+}
+
+function invoiceEvents(invoice: DunningInvoice): CalendarEvent[] {
+  const { dunning: policy, calendar } = getSettings();
+  const due = computeDueDate(invoice.date, policy, calendar.state);
+  const customer = invoice.customerName ? ` – ${invoice.customerName}` : "";
+  const amount = formatEuro(invoice.grossAmount);
+  const details = `Rechnung ${invoice.number}${customer}\nBetrag: ${amount}`;
+
+  const events: CalendarEvent[] = [
+    {
+      invoiceId: invoice.id,
+      uid: `invoice-${invoice.id}-due@billino`,
+      date: due,
+      summary: `Zahlung fällig: ${invoice.number}${customer} (${amount})`,
+      description: `${details}\nRechnungsdatum: ${invoice.date.slice(0, 10)}`,
+    },
+  ];
+  [...policy.levels]
+    .sort((a, b) => a.daysOverdue - b.daysOverdue)
+    .forEach((level, index) => {
+      events.push({
+        invoiceId: invoice.id,
+        uid: `invoice-${invoice.id}-level-${index + 1}@billino`,
+        // Same rule as computeDunning(): the level applies from this day on
+        date: addDays(due, level.daysOverdue),
+        summary: `${index + 1}. Mahnung: ${invoice.number}${customer}`,
+        description:
+          `${details}\nFällig seit: ${due}` +
+          (level.fee > 0 ? `\nMahngebühr: ${formatEuro(level.fee)}` : ""),
+      });
+    });
+  return events;
+}
+
+/**
+ * Build the iCalendar document (CRLF line endings).
+ */
+export function buildIcs(events: CalendarEvent[], stamp: Date = new Date()): string {
+  const dtstamp = stamp.toISOString().replace(/[-:]/g, "").replace(/\.\d{3}/, "");
+  const lines = [
+    "BEGIN:VCALENDAR",
+    "VERSION:2.0",
+    "PRODID:-//Billino//Zahlungstermine//DE",
+    "CALSCALE:GREGORIAN",
+    "METHOD:PUBLISH",
+    "X-WR-CALNAME:Billino – Zahlungstermine",
+  ];
+  for (const event of events) {
+    const start = event.date.replace(/-/g, "");
+    const end = addDays(event.date, 1).replace(/-/g, "");
+    lines.push(
+      "BEGIN:VEVENT",
+      `UID:${event.uid}`,
+      `DTSTAMP:${dtstamp}`,
+      `DTSTART;VALUE=DATE:${start}`,
+      `DTEND;VALUE=DATE:${end}`,
+      `SUMMARY:${escapeText(event.summary)}`,
+      `DESCRIPTION:${escapeText(event.description)}`,
+      "TRANSP:TRANSPARENT",
+      "END:VEVENT"
+    );
+  }
+  lines.push("END:VCALENDAR");
+  return lines.map(foldLine).join("\r\n") + "\r\n";
+}
+
+/**
+ * Default location of the calendar file (stable for subscriptions).
+ */
+export function getDefaultIcsPath(): string {
+  return path.join(app.getPath("userData"), "calendar", "billino-termine.ics");
+}
+
+/**
+ * Export due dates and dunning deadlines within a date range as .ics.
+ *
+ * @param targetPath Absolute destination (default: getDefaultIcsPath())
+ */
+export async function exportDueDatesIcs(
+  range: IcsRange = {},
+  targetPath: string = getDefaultIcsPath()
+): Promise<IcsExportResult> {
+  const from = range.from ?? today();
+  const to = range.to ?? addDays(from, DEFAULT_RANGE_DAYS);
+  if (to < from) throw new AppError("invalid_input", `Invalid range: ${from} – ${to}`);
+
+  // Earliest invoice whose last deadline can still fall into the range
+  const { dunning: policy } = getSettings();
+  const lastLevel = Math.max(0, ...policy.levels.map((level) => level.daysOverdue));
+  const firstInvoiceDate = addDays(
+    from,
+    -(policy.paymentTermDays + lastLevel + MAX_BUSINESS_DAY_SHIFT)
+  );
+
+  const excluded = new Set(range.excludeInvoiceIds ?? []);
+  const invoices = (await fetchInvoices(to, firstInvoiceDate)).filter(
+    (invoice) => !excluded.has(invoice.id)
+  );
+  const events = invoices
+    .flatMap(invoiceEvents)
+    .filter((event) => event.date >= from && event.date <= to)
+    .sort((a, b) => a.date.localeCompare(b.date));
+
+  fs.mkdirSync(path.dirname(targetPath), { recursive: true });
+  fs.writeFileSync(targetPath, buildIcs(events), "utf-8");
+  log.info(`📅 Calendar exported: ${events.length} events (${from} – ${to}) → ${targetPath}`);
+
+  return {
+    path: targetPath,
+    from,
+    to,
+    events: events.length,
+    invoices: new Set(events.map((event) => event.invoiceId)).size,
+  };
+}
+
+/**
+ * Register IPC handlers for the calendar export.
+ */
+export function registerIcsHandlers(): void {
+  handle("export-due-dates-ics", async (_event, range?: IcsRange, chooseLocation?: boolean) => {
+    if (!chooseLocation) return exportDueDatesIcs(range);
+    const targetPath = await chooseSavePath({
+      title: "Zahlungstermine exportieren",
+      defaultName: "billino-termine.ics",
+      filters: [{ name: "Kalender", extensions: ["ics"] }],
+    });
+    return targetPath ? exportDueDatesIcs(range, targetPath) : null;
+  });
+}
@@ -0,0 +1,136 @@
+/**
+ * Billino Desktop – Image Optimization
+ *
+ * Helpers to keep images in the data dir (and therefore in backups) small:
+ * - downscale(): fit an image into a bounding box (never upscales)
+ * - stripExif(): remove EXIF/XMP segments from a JPEG without re-encoding
+ * - optimizeImage(): downscale + re-encode a file as PNG or JPEG
+ *
+ * Re-encoding through nativeImage never carries metadata over, so
+ * optimizeImage() output is always EXIF-free.
+ */
+
+import { nativeImage, NativeImage } from "electron";
+import path from "path";
+import fs from "fs";
+import { AppError } from "./errors";
+
+export type ImageFormat = "png" | "jpeg";
+
+export interface OptimizeOptions {
+  maxWidth: number;
+  maxHeight: number;
+  /** Output format (default: keep PNG as PNG, everything else → JPEG). */
+  format?: ImageFormat;
+  /** JPEG quality 1-100 (default 85). */
+  quality?: number;
+}
+
+export interface OptimizedImage {
+  path: string;
+  format: ImageFormat;
+  width: number;
+  height: number;
+  sizeBytes: number;
+  /** Size of the source file, to report savings. */
+  originalBytes: number;
+}
+
+/**
+ * Scale an image down so it fits into maxWidth × maxHeight (keeps aspect
+ * ratio; images that already fit are returned unchanged).
+ */
+export function downscale(image: NativeImage, maxWidth: number, maxHeight: number): NativeImage {
+  const { width, height } = image.getSize();
+  const scale = Math.min(maxWidth / width, maxHeight / height, 1);
+  if (scale === 1) return image;
+  return image.resize({
+    width: Math.max(1, Math.round(width * scale)),
+    height: Math.max(1, Math.round(height * scale)),
+    quality: "best",
+  });
+}
+
+/**
+ * Decode an image file.
+ *
+ * @throws Error if the file cannot be decoded as an image
+ */
+export function loadImage(filePath: string): NativeImage {
+  const image = nativeImage.createFromPath(filePath);
+  if (image.isEmpty()) {
+    throw new AppError("invalid_input", `Image could not be decoded: ${path.basename(filePath)}`, {
+      message: "Das Bild konnte nicht gelesen werden.",
+    });
+  }
+  return image;
+}
+
+/**
+ * Encode an image in the given format.
+ */
+export function encodeImage(image: NativeImage, format: ImageFormat, quality = 85): Buffer {
+  return format === "png" ? image.toPNG() : image.toJPEG(Math.min(100, Math.max(1, quality)));
+}
+
+/**
+ * Remove APP1 (EXIF/XMP) segments from JPEG data without re-encoding.
+ *
+ * Non-JPEG data is returned unchanged.
+ */
+export function stripExif(data: Buffer): Buffer {
+  if (data.length < 4 || data[0] !== 0xff || data[1] !== 0xd8) return data;
+
+  const parts: Buffer[] = [data.subarray(0, 2)];
+  let offset = 2;
+
+  while (offset + 4 <= data.length && data[offset] === 0xff) {
+    const marker = data[offset + 1];
+    // Start of scan: the rest is entropy-coded image data
+    if (marker === 0xda) break;
+
+    const segmentLength = data.readUInt16BE(offset + 2);
+    const segmentEnd = offset + 2 + segmentLength;
+    if (segmentEnd > data.length) break;
+
+    if (marker !== 0xe1) {
+      parts.push(data.subarray(offset, segmentEnd));
+    }
+    offset = segmentEnd;
+  }
+
+  parts.push(data.subarray(offset));
+  return Buffer.concat(parts);
+}
+
+/**
+ * Downscale and re-encode an image file.
+ *
+ * The target is written atomically (temp file + rename) and may be the
+ * same path as the source.
+ */
+export function optimizeImage(
+  sourcePath: string,
+  targetPath: string,
+  options: OptimizeOptions
+): OptimizedImage {
+  const originalBytes = fs.statSync(sourcePath).size;
+  const format =
+    options.format ?? (path.extname(sourcePath).toLowerCase() === ".png" ? "png" : "jpeg");
+
+  const image = downscale(loadImage(sourcePath), options.maxWidth, options.maxHeight);
+  const tmpPath = `${targetPath}.tmp`;
+  fs.mkdirSync(path.dirname(targetPath), { recursive: true });
+  fs.writeFileSync(tmpPath, encodeImage(image, format, options.quality));
+  fs.renameSync(tmpPath, targetPath);
+
+  const { width, height } = image.getSize();
+  return {
+    path: targetPath,
+    format,
+    width,
+    height,
+    sizeBytes: fs.statSync(targetPath).size,
+    originalBytes,
+  };
+}
@@ -0,0 +1,511 @@
+/**
+ * Billino Desktop – Legacy Data Import
+ *
+ * Reads the CSV exports of the invoicing tools most users switch from and
+ * maps them onto Billino's customers and invoices:
+ *
+ *   lexoffice-customers   Lexoffice "Kontakte exportieren"
+ *   lexoffice-invoices    Lexoffice "Belegliste" (Ausgangsrechnungen)
+ *   wiso-customers        WISO MeinBüro "Kunden exportieren"
+ *   wiso-invoices         WISO MeinBüro "Rechnungen exportieren"
+ *
+ * Columns are found by their header, so reordered or additional columns
+ * do not matter; separator (; , tab) and encoding (UTF-8 or Windows-1252)
+ * are detected. Every import is a dry run unless `dryRun: false` is
+ * passed: the preview lists each row as new, duplicate (customer name
+ * exists already / invoice number repeated in the file) or invalid, with
+ * the reasons.
+ *
+ * Imported invoices get a Billino number – numbers are consecutive per
+ * year and cannot be taken over. The original number goes into the item
+ * text ("Rechnung RE-1001 (Lexoffice)"); each invoice becomes one item
+ * with its gross amount. Invoices need their customer in Billino, so
+ * customers are imported first.
+ */
+
+import fs from "fs";
+import path from "path";
+import log from "electron-log/main";
+import { callBackend, requestBackend } from "./api";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { beginOperation, endOperation } from "./operations";
+
+export type ImportFormat =
+  | "lexoffice-customers"
+  | "lexoffice-invoices"
+  | "wiso-customers"
+  | "wiso-invoices";
+export type ImportKind = "customers" | "invoices";
+export type ImportRowStatus = "new" | "duplicate" | "invalid";
+
+/** Billino customer (POST /customers/). */
+export interface ImportedCustomer {
+  name: string;
+  address: string | null;
+  city: string | null;
+  note: string | null;
+}
+
+export interface ImportedInvoice {
+  legacyNumber: string;
+  /** YYYY-MM-DD */
+  date: string;
+  customerName: string;
+  /** Billino customer, null if not found. */
+  customerId: number | null;
+  gross: number;
+  /** As decimal, e.g. 0.19 (0 = without VAT). */
+  taxRate: number;
+}
+
+export interface ImportRow<T = ImportedCustomer | ImportedInvoice> {
+  /** Line in the file (1 = header). */
+  line: number;
+  status: ImportRowStatus;
+  data: T | null;
+  errors: string[];
+}
+
+export interface ImportOptions {
+  /** Only preview (default). */
+  dryRun?: boolean;
+  /** Issuing profile of imported invoices (required for invoices). */
+  profileId?: number;
+}
+
+export interface ImportResult {
+  format: ImportFormat;
+  kind: ImportKind;
+  file: string;
+  dryRun: boolean;
+  rows: ImportRow[];
+  counts: Record<ImportRowStatus, number>;
+  /** Rows pushed to the backend (0 for a dry run). */
+  created: number;
+  failed: Array<{ line: number; error: string }>;
+}
+
+interface FormatSpec {
+  kind: ImportKind;
+  tool: string;
+  /** Billino field → accepted headers. */
+  columns: Record<string, string[]>;
+  required: string[];
+}
+
+const FORMATS: Record<ImportFormat, FormatSpec> = {
+  "lexoffice-customers": {
+    kind: "customers",
+    tool: "Lexoffice",
+    columns: {
+      number: ["Kundennummer", "Kunden-Nr."],
+      company: ["Firmenname", "Unternehmen", "Firma"],
+      firstName: ["Vorname"],
+      lastName: ["Nachname", "Name"],
+      street: ["Straße", "Strasse", "Rechnungsadresse Straße"],
+      zip: ["PLZ", "Rechnungsadresse PLZ"],
+      city: ["Ort", "Stadt", "Rechnungsadresse Ort"],
+      note: ["Notiz", "Notizen", "Bemerkung"],
+    },
+    required: [],
+  },
+  "lexoffice-invoices": {
+    kind: "invoices",
+    tool: "Lexoffice",
+    columns: {
+      number: ["Rechnungsnummer", "Belegnummer"],
+      date: ["Rechnungsdatum", "Belegdatum", "Datum"],
+      customer: ["Kunde", "Kontakt", "Kontaktname", "Empfänger"],
+      net: ["Nettobetrag", "Netto"],
+      gross: ["Bruttobetrag", "Brutto", "Betrag"],
+      taxRate: ["Steuersatz", "USt-Satz", "Umsatzsteuersatz"],
+    },
+    required: ["number", "date", "customer"],
+  },
+  "wiso-customers": {
+    kind: "customers",
+    tool: "WISO MeinBüro",
+    columns: {
+      number: ["Kd.-Nr.", "Kundennummer", "Kunden-Nr."],
+      company: ["Firma", "Firmenname"],
+      firstName: ["Vorname"],
+      lastName: ["Name", "Nachname"],
+      street: ["Straße", "Strasse"],
+      zip: ["PLZ"],
+      city: ["Ort"],
+      note: ["Bemerkung", "Notiz", "Info"],
+    },
+    required: [],
+  },
+  "wiso-invoices": {
+    kind: "invoices",
+    tool: "WISO MeinBüro",
+    columns: {
+      number: ["Rechnungs-Nr.", "Rechnungsnummer", "Re.-Nr."],
+      date: ["Datum", "Rechnungsdatum"],
+      customer: ["Kunde", "Firma", "Name"],
+      net: ["Netto", "Nettobetrag", "Summe netto"],
+      gross: ["Brutto", "Bruttobetrag", "Summe brutto"],
+      taxRate: ["MwSt-Satz", "MwSt.-Satz", "USt-Satz", "Steuersatz"],
+    },
+    required: ["number", "date", "customer"],
+  },
+};
+
+/** German VAT rates a computed rate is snapped to. */
+const KNOWN_TAX_RATES = [0, 0.05, 0.07, 0.16, 0.19];
+const MAX_IMPORT_BYTES = 20 * 1024 * 1024;
+const PAGE_SIZE = 100;
+
+// ─── Parsing ─────────────────────────────────────────────────────────────────
+
+/**
+ * Decode an export: UTF-8 (with or without BOM), otherwise Windows-1252.
+ */
+export function decodeCsv(buffer: Buffer): string {
+  if (buffer[0] === 0xef && buffer[1] === 0xbb && buffer[2] === 0xbf) {
+    return buffer.subarray(3).toString("utf8");
+  }
+  try {
+    return new TextDecoder("utf-8", { fatal: true }).decode(buffer);
+  } catch {
+    return new TextDecoder("windows-1252").decode(buffer);
+  }
+}
+
+/**
+ * Split CSV text into rows; quoted fields may contain separators, quotes
+ * ("") and line breaks. The separator is taken from the header line.
+ */
+export function parseCsv(text: string): string[][] {
+  const headerLine = text.slice(0, text.search(/\r?\n|$/));
+  const separator = [";", "\t", ","].reduce((best, candidate) =>
+    headerLine.split(candidate).length > headerLine.split(best).length ? candidate : best
+  );
+
+  const rows: string[][] = [];
+  let row: string[] = [];
+  let field = "";
+  let quoted = false;
+  for (let i = 0; i < text.length; i++) {
+    const char = text[i];
+    if (quoted) {
+      if (char === '"' && text[i + 1] === '"') {
+        field += '"';
+        i++;
+      } else if (char === '"') {
+        quoted = false;
+      } else {
+        field += char;
+      }
+    } else if (char === '"') {
+      quoted = true;
+    } else if (char === separator) {
+      row.push(field);
+      field = "";
+    } else if (char === "\n" || char === "\r") {
+      if (char === "\r" && text[i + 1] === "\n") i++;
+      row.push(field);
+      rows.push(row);
+      row = [];
+      field = "";
+    } else {
+      field += char;
+    }
+  }
+  if (field !== "" || row.length > 0) {
+    row.push(field);
+    rows.push(row);
+  }
+  return rows;
+}
+
+/**
+ * "1.234,56" / "1234.56" / "19 %" → number; null if empty or invalid.
+ */
+export function parseAmount(value: string): number | null {
+  let text = value.replace(/[€%\s]/g, "").replace(/EUR/i, "");
+  if (text === "") return null;
+  if (text.includes(",") || /^-?\d{1,3}(\.\d{3})+$/.test(text)) {
+    // German notation: "." groups thousands
+    text = text.replace(/\./g, "").replace(",", ".");
+  }
+  const number = Number(text);
+  return Number.isFinite(number) ? number : null;
+}
+
+/**
+ * "31.12.2024" / "31.12.24" / "2024-12-31" → "2024-12-31"; null if invalid.
+ */
+export function parseDate(value: string): string | null {
+  const text = value.trim();
+  const iso = /^(\d{4})-(\d{2})-(\d{2})/.exec(text);
+  if (iso) return `${iso[1]}-${iso[2]}-${iso[3]}`;
+  const german = /^(\d{1,2})\.(\d{1,2})\.(\d{2}|\d{4})$/.exec(text);
+  if (!german) return null;
+  const year = german[3].length === 2 ? `20${german[3]}` : german[3];
+  return `${year}-${german[2].padStart(2, "0")}-${german[1].padStart(2, "0")}`;
+}
+
+const normalizeHeader = (header: string): string =>
+  header.toLowerCase().replace(/[^a-z0-9äöüß]/g, "");
+
+function mapColumns(spec: FormatSpec, header: string[], format: ImportFormat): Map<string, number> {
+  const positions = new Map<string, number>();
+  const normalized = header.map(normalizeHeader);
+  for (const [field, aliases] of Object.entries(spec.columns)) {
+    const index = aliases
+      .map((alias) => normalized.indexOf(normalizeHeader(alias)))
+      .find((position) => position >= 0);
+    if (index !== undefined) positions.set(field, index);
+  }
+
+  const missing = spec.required.filter((field) => !positions.has(field));
+  if (spec.kind === "customers" && !positions.has("company") && !positions.has("lastName")) {
+    missing.push("company");
+  }
+  if (spec.kind === "invoices" && !positions.has("gross") && !positions.has("net")) {
+    missing.push("gross");
+  }
+  if (missing.length > 0) {
+    const expected = missing.map((field) => spec.columns[field][0]).join(", ");
+    throw new AppError("invalid_input", `${format}: missing columns ${missing.join(", ")}`, {
+      message: `Die Datei passt nicht zum ${spec.tool}-Export: Spalte fehlt (${expected}).`,
+      hint: "Bitte den Export mit den Standardspalten erstellen.",
+    });
+  }
+  return positions;
+}
+
+function mapCustomer(get: (field: string) => string, spec: FormatSpec): ImportedCustomer | null {
+  const person = [get("firstName"), get("lastName")].filter(Boolean).join(" ");
+  const name = get("company") || person;
+  if (!name) return null;
+  const notes = [
+    get("company") && person ? `Ansprechpartner: ${person}` : "",
+    get("number") ? `Kundennummer ${get("number")} (${spec.tool})` : "",
+    get("note"),
+  ].filter(Boolean);
+  return {
+    name,
+    address: get("street") || null,
+    city: [get("zip"), get("city")].filter(Boolean).join(" ") || null,
+    note: notes.length > 0 ? notes.join("\n") : null,
+  };
+}
+
+function mapInvoice(get: (field: string) => string, errors: string[]): ImportedInvoice | null {
+  const date = parseDate(get("date"));
+  if (!date) errors.push(`Ungültiges Datum: "${get("date")}"`);
+  const net = parseAmount(get("net"));
+  let gross = parseAmount(get("gross"));
+  let rate = parseAmount(get("taxRate"));
+  // "19" and "0,19" both mean 19 %
+  if (rate !== null && rate >= 1) rate = rate / 100;
+
+  if (rate === null && net !== null && gross !== null && net > 0) {
+    const computed = gross / net - 1;
+    rate = KNOWN_TAX_RATES.reduce((best, known) =>
+      Math.abs(known - computed) < Math.abs(best - computed) ? known : best
+    );
+  }
+  if (gross === null && net !== null) gross = Math.round(net * (1 + (rate ?? 0)) * 100) / 100;
+  if (gross === null) errors.push("Kein Betrag");
+  else if (gross < 0) errors.push("Gutschriften werden nicht übernommen");
+
+  if (!get("number")) errors.push("Keine Rechnungsnummer");
+  if (!get("customer")) errors.push("Kein Kunde");
+  if (errors.length > 0 || !date || gross === null) return null;
+  return {
+    legacyNumber: get("number"),
+    date,
+    customerName: get("customer"),
+    customerId: null,
+    gross,
+    taxRate: rate ?? 0,
+  };
+}
+
+/**
+ * Map the rows of an export onto Billino customers or invoices (no I/O).
+ */
+export function mapImportRows(format: ImportFormat, table: string[][]): ImportRow[] {
+  const spec = FORMATS[format];
+  const [header = [], ...records] = table;
+  const positions = mapColumns(spec, header, format);
+
+  return records.flatMap((record, index): ImportRow[] => {
+    if (record.every((cell) => cell.trim() === "")) return [];
+    const get = (field: string): string => {
+      const position = positions.get(field);
+      return position === undefined ? "" : (record[position] ?? "").trim();
+    };
+    const errors: string[] = [];
+    const data = spec.kind === "customers" ? mapCustomer(get, spec) : mapInvoice(get, errors);
+    if (!data && errors.length === 0) errors.push("Kein Name");
+    return [{ line: index + 2, status: data ? "new" : "invalid", data, errors }];
+  });
+}
+
+// ─── Import ──────────────────────────────────────────────────────────────────
+
+async function fetchCustomerIds(): Promise<Map<string, number>> {
+  const ids = new Map<string, number>();
+  for (let page = 1; ; page++) {
+    const result = await requestBackend<{
+      items: Array<{ id: number; name: string }>;
+      pageCount: number;
+    }>(`/customers/?page=${page}&pageSize=${PAGE_SIZE}`);
+    for (const customer of result.items) ids.set(customer.name.trim().toLowerCase(), customer.id);
+    if (page >= result.pageCount) return ids;
+  }
+}
+
+function markDuplicates(kind: ImportKind, rows: ImportRow[], customers: Map<string, number>): void {
+  const seen = new Set<string>();
+  for (const row of rows) {
+    if (!row.data) continue;
+    if (kind === "customers") {
+      const key = (row.data as ImportedCustomer).name.toLowerCase();
+      if (customers.has(key) || seen.has(key)) {
+        row.status = "duplicate";
+        row.errors.push("Kunde existiert bereits");
+      }
+      seen.add(key);
+      continue;
+    }
+
+    const invoice = row.data as ImportedInvoice;
+    invoice.customerId = customers.get(invoice.customerName.toLowerCase()) ?? null;
+    if (seen.has(invoice.legacyNumber)) {
+      row.status = "duplicate";
+      row.errors.push("Rechnungsnummer doppelt in der Datei");
+    } else if (invoice.customerId === null) {
+      row.status = "invalid";
+      row.errors.push(`Kunde "${invoice.customerName}" nicht gefunden – zuerst Kunden importieren`);
+    }
+    seen.add(invoice.legacyNumber);
+  }
+}
+
+async function pushRow(
+  spec: FormatSpec,
+  row: ImportRow,
+  profileId: number | undefined
+): Promise<void> {
+  if (spec.kind === "customers") {
+    await callBackend("POST /customers/", { body: row.data as ImportedCustomer });
+    return;
+  }
+
+  const invoice = row.data as ImportedInvoice;
+  const withTax = invoice.taxRate > 0;
+  await callBackend("POST /invoices/", {
+    body: {
+      date: invoice.date,
+      customer_id: invoice.customerId as number,
+      profile_id: profileId as number,
+      total_amount: invoice.gross,
+      invoice_items: [
+        {
+          quantity: 1,
+          description: `Rechnung ${invoice.legacyNumber} (${spec.tool})`,
+          price: invoice.gross,
+        },
+      ],
+      include_tax: withTax,
+      tax_rate: withTax ? invoice.taxRate : 0,
+      is_gross_amount: withTax,
+    },
+  });
+}
+
+/**
+ * Preview (default) or import a CSV export of another invoicing tool.
+ *
+ * @param filePath Absolute path of the export
+ * @throws AppError if the file cannot be read or lacks required columns
+ */
+export async function importLegacyData(
+  filePath: string,
+  format: ImportFormat,
+  options: ImportOptions = {}
+): Promise<ImportResult> {
+  const spec = FORMATS[format];
+  if (!spec) throw new AppError("invalid_input", `Unknown import format: ${format}`);
+  if (!path.isAbsolute(filePath)) {
+    throw new AppError("invalid_input", `Import path must be absolute: ${filePath}`);
+  }
+  const dryRun = options.dryRun ?? true;
+  if (!dryRun && spec.kind === "invoices" && options.profileId === undefined) {
+    throw new AppError("invalid_input", "Invoice import needs a profile", {
+      message: "Bitte das Profil wählen, unter dem die Rechnungen angelegt werden.",
+    });
+  }
+
+  let buffer: Buffer;
+  try {
+    if (fs.statSync(filePath).size > MAX_IMPORT_BYTES) throw new Error("file too large");
+    buffer = fs.readFileSync(filePath);
+  } catch (err) {
+    throw new AppError("not_found", `Cannot read import file ${filePath}: ${err}`, {
+      message: "Die Datei konnte nicht gelesen werden.",
+    });
+  }
+
+  const rows = mapImportRows(format, parseCsv(decodeCsv(buffer)));
+  markDuplicates(spec.kind, rows, await fetchCustomerIds());
+
+  const result: ImportResult = {
+    format,
+    kind: spec.kind,
+    file: filePath,
+    dryRun,
+    rows,
+    counts: { new: 0, duplicate: 0, invalid: 0 },
+    created: 0,
+    failed: [],
+  };
+  for (const row of rows) result.counts[row.status]++;
+  if (dryRun) return result;
+
+  const pending = rows.filter((row) => row.status === "new");
+  const { id, signal } = beginOperation("import", `${spec.tool}-Import`);
+  try {
+    for (const row of pending) {
+      if (signal.aborted) break;
+      try {
+        await pushRow(spec, row, options.profileId);
+        result.created++;
+      } catch (err) {
+        result.failed.push({ line: row.line, error: String(err) });
+      }
+      emitEvent("import:progress", {
+        format,
+        done: result.created + result.failed.length,
+        total: pending.length,
+      });
+    }
+  } finally {
+    endOperation(id);
+  }
+
+  log.info(
+    `📥 ${spec.tool} ${spec.kind} imported: ${result.created} created, ` +
+      `${result.failed.length} failed, ${result.counts.duplicate} duplicates skipped`
+  );
+  return result;
+}
+
+/**
+ * Register IPC handlers for legacy imports.
+ */
+export function registerImportHandlers(): void {
+  handle(
+    "import-legacy-data",
+    (_event, filePath: string, format: ImportFormat, options?: ImportOptions) =>
+      importLegacyData(filePath, format, options)
+  );
+}
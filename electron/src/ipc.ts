@@ -0,0 +1,62 @@
+/**
+ * Billino Desktop – IPC Command Registration
+ *
+ * All renderer-invokable commands are registered through `handle()` instead
+ * of `ipcMain.handle()` directly, so cross-cutting concerns (window
+ * permissions, session recording, tracing, error logging) apply to every
+ * command in one place. Failures are turned into AppError payloads
+ * (errors.ts) here.
+ */
+
+import { ipcMain, IpcMainInvokeEvent } from "electron";
+import { performance } from "perf_hooks";
+import log from "electron-log/main";
+import { encodeAppError, toAppError } from "./errors";
+import { assertCommandAllowed, CommandAccess } from "./permissions";
+import { recordCommand } from "./session";
+import { withSpan } from "./tracing";
+
+export type CommandHandler<A extends unknown[], R> = (
+  event: IpcMainInvokeEvent,
+  ...args: A
+) => R | Promise<R>;
+
+const registered = new Set<string>();
+
+/**
+ * Register an IPC command handler.
+ *
+ * @param channel Kebab-case channel name (e.g. "get-settings")
+ * @param handler Receives the invoke event and the renderer's arguments
+ * @param access Required access level; secondary windows may only use
+ *               `read` commands (default: `write`)
+ */
+export function handle<A extends unknown[], R>(
+  channel: string,
+  handler: CommandHandler<A, R>,
+  access: CommandAccess = "write"
+): void {
+  registered.add(channel);
+
+  ipcMain.handle(channel, async (event, ...args: unknown[]) => {
+    const start = performance.now();
+    try {
+      assertCommandAllowed(channel, access, event.sender);
+      const result = await withSpan(`command:${channel}`, () => handler(event, ...(args as A)));
+      recordCommand(channel, args, performance.now() - start, "ok");
+      return result;
+    } catch (err) {
+      const appError = toAppError(err);
+      recordCommand(channel, args, performance.now() - start, "error", String(err));
+      log.warn(`⚠️ Command ${channel} failed [${appError.code}]: ${appError.message}`);
+      throw encodeAppError(appError);
+    }
+  });
+}
+
+/**
+ * Names of all registered commands.
+ */
+export function getRegisteredCommands(): string[] {
+  return Array.from(registered).sort();
+}
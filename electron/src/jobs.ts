@@ -0,0 +1,120 @@
+/**
+ * Billino Desktop – Heavy Job Scheduling
+ *
+ * Heavy, non-urgent jobs (verified backups, index rebuilds, thumbnail
+ * generation) are deferred while the machine runs on battery, and run
+ * automatically once AC power is back. Users can run deferred jobs
+ * explicitly, and the behaviour can be disabled in the power settings.
+ */
+
+import { powerMonitor } from "electron";
+import { randomUUID } from "crypto";
+import log from "electron-log/main";
+import { handle } from "./ipc";
+import { getSettings } from "./settings";
+
+export interface HeavyJobOptions {
+  /** Run immediately regardless of power state. */
+  urgent?: boolean;
+}
+
+export interface DeferredJobInfo {
+  id: string;
+  name: string;
+  deferredAt: string;
+}
+
+interface DeferredJob extends DeferredJobInfo {
+  run: () => Promise<void>;
+}
+
+export interface PowerState {
+  onBattery: boolean;
+  deferHeavyJobs: boolean;
+  deferredJobs: DeferredJobInfo[];
+}
+
+const deferred: DeferredJob[] = [];
+
+/**
+ * Whether heavy jobs should currently be deferred.
+ */
+export function shouldDeferHeavyJobs(): boolean {
+  return getSettings().power.deferHeavyJobsOnBattery && powerMonitor.isOnBatteryPower();
+}
+
+async function runJob(name: string, run: () => Promise<void>): Promise<void> {
+  log.info(`🏋️ Running heavy job: ${name}`);
+  try {
+    await run();
+    log.info(`✅ Heavy job finished: ${name}`);
+  } catch (err) {
+    log.error(`❌ Heavy job failed: ${name}: ${err}`);
+  }
+}
+
+/**
+ * Run a heavy job now, or defer it until AC power is available.
+ *
+ * Jobs with the same name are deduplicated while deferred.
+ */
+export async function scheduleHeavyJob(
+  name: string,
+  run: () => Promise<void>,
+  options: HeavyJobOptions = {}
+): Promise<void> {
+  if (options.urgent || !shouldDeferHeavyJobs()) {
+    await runJob(name, run);
+    return;
+  }
+
+  if (deferred.some((job) => job.name === name)) {
+    log.debug(`🔋 Heavy job already deferred: ${name}`);
+    return;
+  }
+
+  deferred.push({ id: randomUUID(), name, deferredAt: new Date().toISOString(), run });
+  log.info(`🔋 On battery – deferring heavy job: ${name}`);
+}
+
+/**
+ * Run all deferred jobs sequentially (on AC power or on explicit request).
+ */
+export async function runDeferredJobs(): Promise<number> {
+  const jobs = deferred.splice(0, deferred.length);
+  for (const job of jobs) {
+    await runJob(job.name, job.run);
+  }
+  return jobs.length;
+}
+
+/**
+ * Current power state and deferred jobs, for the settings UI.
+ */
+export function getPowerState(): PowerState {
+  return {
+    onBattery: powerMonitor.isOnBatteryPower(),
+    deferHeavyJobs: shouldDeferHeavyJobs(),
+    deferredJobs: deferred.map(({ run: _run, ...info }) => info),
+  };
+}
+
+/**
+ * Watch power source changes and register IPC handlers.
+ *
+ * Must be called after the app is ready (powerMonitor requirement).
+ */
+export function initHeavyJobScheduler(): void {
+  powerMonitor.on("on-ac", () => {
+    if (deferred.length === 0) return;
+    log.info(`🔌 AC power restored – running ${deferred.length} deferred job(s)`);
+    void runDeferredJobs();
+  });
+
+  powerMonitor.on("on-battery", () => {
+    log.info("🔋 Switched to battery power");
+  });
+
+  handle("get-power-state", () => getPowerState(), "read");
+  handle("run-deferred-jobs", () => runDeferredJobs());
+}
@@ -0,0 +1,225 @@
+/**
+ * Billino Desktop – Shell Log Files
+ *
+ * Writes the shell log to AppData/Roaming/Billino/logs/billino-desktop.log
+ * and keeps the folder bounded on long-lived installs:
+ * - Rotation by size (settings: logging.maxFileSizeMb)
+ * - Rotation by day (the previous day's log is archived at startup, and
+ *   before the first line written after midnight while the app runs)
+ * - Retention: archived logs older than logging.retentionDays or beyond
+ *   logging.maxArchivedFiles are deleted
+ *
+ * Archived files are named billino-desktop_YYYY-MM-DD_HH-MM-SS.log.
+ *
+ * With BILLINO_LOG_FORMAT=json the file gets one JSON object per line
+ * instead of text – `timestamp`, `level`, `target` ("shell", "backend" for
+ * captured backend output, or the electron-log scope), `message` and the
+ * `backendState` at that moment – so logs from a customer installation
+ * can be fed into a log aggregator. The console output stays text.
+ */
+
+import { app } from "electron";
+import path from "path";
+import fs from "fs";
+import { format } from "util";
+import log from "electron-log/main";
+import { getSettings, LoggingSettings, onSettingsChanged } from "./settings";
+
+const LOG_BASENAME = "billino-desktop";
+const ARCHIVE_PATTERN = new RegExp(`^${LOG_BASENAME}_\\d{4}-\\d{2}-\\d{2}_\\d{2}-\\d{2}-\\d{2}(-\\d+)?\\.log$`);
+
+/** Day (toDateString) the active log file belongs to. */
+let logDay = new Date().toDateString();
+
+/**
+ * Directory containing the shell's log files.
+ */
+export function getLogDir(): string {
+  return path.join(app.getPath("userData"), "logs");
+}
+
+/**
+ * Path of the active shell log file.
+ */
+export function getLogFilePath(): string {
+  return path.join(getLogDir(), `${LOG_BASENAME}.log`);
+}
+
+function timestampForFilename(date: Date): string {
+  const pad = (n: number): string => String(n).padStart(2, "0");
+  return (
+    `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}_` +
+    `${pad(date.getHours())}-${pad(date.getMinutes())}-${pad(date.getSeconds())}`
+  );
+}
+
+/**
+ * Move the given log file to a timestamped archive name.
+ */
+function archiveLogFile(filePath: string, timestamp: Date = new Date()): void {
+  if (!fs.existsSync(filePath)) return;
+
+  const base = path.join(path.dirname(filePath), `${LOG_BASENAME}_${timestampForFilename(timestamp)}`);
+  let target = `${base}.log`;
+  for (let i = 1; fs.existsSync(target); i++) {
+    target = `${base}-${i}.log`;
+  }
+
+  try {
+    fs.renameSync(filePath, target);
+  } catch (err) {
+    // Never let log rotation break the app – keep writing to the old file
+    console.error(`Log rotation failed for ${filePath}: ${err}`);
+  }
+}
+
+/**
+ * Archive yesterday's (or older) log so every day starts with a fresh file.
+ */
+function rotateIfFromPreviousDay(): void {
+  const filePath = getLogFilePath();
+  if (!fs.existsSync(filePath)) return;
+
+  const modified = fs.statSync(filePath).mtime;
+  if (modified.toDateString() !== new Date().toDateString()) {
+    archiveLogFile(filePath, modified);
+  }
+  logDay = new Date().toDateString();
+}
+
+/**
+ * Start a new file when the day changed since the last line was written
+ * (called for every line, so it must stay cheap).
+ */
+function rotateOnDayChange(date: Date): void {
+  const day = date.toDateString();
+  if (day === logDay) return;
+  logDay = day;
+
+  const filePath = getLogFilePath();
+  if (!fs.existsSync(filePath)) return;
+  archiveLogFile(filePath, fs.statSync(filePath).mtime);
+  // Creates the new file and resets electron-log's size count for maxSize
+  log.transports.file.getFile().clear();
+  // Not from inside the hook: pruning logs itself
+  setImmediate(() => pruneArchivedLogs());
+}
+
+/**
+ * Delete archived logs that exceed the retention window or file limit.
+ *
+ * @returns Number of deleted files
+ */
+export function pruneArchivedLogs(settings: LoggingSettings = getSettings().logging): number {
+  const logDir = getLogDir();
+  let entries: { name: string; mtimeMs: number }[];
+  try {
+    entries = fs
+      .readdirSync(logDir)
+      .filter((name) => ARCHIVE_PATTERN.test(name))
+      .map((name) => ({ name, mtimeMs: fs.statSync(path.join(logDir, name)).mtimeMs }))
+      .sort((a, b) => b.mtimeMs - a.mtimeMs);
+  } catch {
+    return 0;
+  }
+
+  const cutoff = Date.now() - settings.retentionDays * 24 * 60 * 60 * 1000;
+  let deleted = 0;
+
+  entries.forEach((entry, index) => {
+    if (index < settings.maxArchivedFiles && entry.mtimeMs >= cutoff) return;
+    try {
+      fs.unlinkSync(path.join(logDir, entry.name));
+      deleted++;
+    } catch (err) {
+      log.warn(`⚠️ Could not delete old log ${entry.name}: ${err}`);
+    }
+  });
+
+  if (deleted > 0) {
+    log.info(`🗑️ Deleted ${deleted} old log file(s)`);
+  }
+  return deleted;
+}
+
+export type LogFormat = "text" | "json";
+
+/** A message as electron-log passes it to hooks and transports. */
+type LogMessage = Parameters<(typeof log.hooks)[number]>[0];
+
+export interface StructuredLogLine {
+  timestamp: string;
+  level: string;
+  target: string;
+  message: string;
+  backendState: string | null;
+}
+
+let backendStateProvider: () => string | null = () => null;
+
+/**
+ * Output format of the log file from BILLINO_LOG_FORMAT (default: text).
+ */
+export function getLogFormat(env: NodeJS.ProcessEnv = process.env): LogFormat {
+  const value = env.BILLINO_LOG_FORMAT?.trim().toLowerCase();
+  if (value && value !== "json" && value !== "text") {
+    console.error(`Unknown BILLINO_LOG_FORMAT "${value}" – using text`);
+  }
+  return value === "json" ? "json" : "text";
+}
+
+/**
+ * Tell the JSON format where to get the backend state from (main.ts).
+ */
+export function setLogBackendStateProvider(provider: () => string | null): void {
+  backendStateProvider = provider;
+}
+
+/**
+ * One log message as a structured line.
+ */
+function toStructuredLogLine(message: LogMessage): StructuredLogLine {
+  const text = format(...message.data);
+  const target = message.scope ?? (/^\[backend(:err)?\] /.test(text) ? "backend" : "shell");
+  return {
+    timestamp: message.date.toISOString(),
+    level: message.level,
+    target,
+    message: text,
+    backendState: backendStateProvider(),
+  };
+}
+
+function applyLoggingSettings(settings: LoggingSettings): void {
+  log.transports.file.maxSize = Math.max(1, settings.maxFileSizeMb) * 1024 * 1024;
+}
+
+/**
+ * Initialize electron-log with file rotation and retention.
+ *
+ * Must run before the first log call so early messages land in the file.
+ */
+export function initLogging(): void {
+  log.initialize();
+  log.transports.file.resolvePathFn = () => getLogFilePath();
+  log.transports.file.archiveLogFn = (file) => archiveLogFile(file.path);
+  if (getLogFormat() === "json") {
+    log.transports.file.format = ({ message }) => [JSON.stringify(toStructuredLogLine(message))];
+  }
+
+  fs.mkdirSync(getLogDir(), { recursive: true });
+  rotateIfFromPreviousDay();
+  log.hooks.push((message, transport) => {
+    if (transport === log.transports.file) rotateOnDayChange(message.date);
+    return message;
+  });
+
+  const { logging } = getSettings();
+  applyLoggingSettings(logging);
+  pruneArchivedLogs(logging);
+
+  onSettingsChanged((settings) => {
+    applyLoggingSettings(settings.logging);
+    pruneArchivedLogs(settings.logging);
+  });
+}
@@ -0,0 +1,121 @@
+/**
+ * Billino Desktop – Company Logo
+ *
+ * Validates and optimizes the company logo before it reaches the invoice
+ * PDFs: PNG/JPEG input is downscaled to at most 800×400 px and stored as PNG
+ * in AppData/Roaming/Billino/branding/logo.png, where the backend's PDF
+ * templates pick it up. Re-encoding also drops EXIF metadata (see images.ts).
+ */
+
+import { app, NativeImage } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { AppError } from "./errors";
+import { downscale, loadImage, optimizeImage } from "./images";
+import { handle } from "./ipc";
+import { sniffMimeType } from "./transfers";
+
+export interface LogoInfo {
+  path: string;
+  width: number;
+  height: number;
+  sizeBytes: number;
+  /** Small PNG data URL for the settings page. */
+  previewDataUrl: string;
+}
+
+const MAX_LOGO_WIDTH = 800;
+const MAX_LOGO_HEIGHT = 400;
+const PREVIEW_HEIGHT = 120;
+/** Reject source files above this size before decoding them. */
+const MAX_SOURCE_BYTES = 20 * 1024 * 1024;
+const ACCEPTED_TYPES = ["image/png", "image/jpeg"];
+
+/**
+ * Where the backend expects the logo (DATA_DIR/branding/logo.png).
+ */
+export function getLogoPath(): string {
+  return path.join(app.getPath("userData"), "branding", "logo.png");
+}
+
+function describeLogo(image: NativeImage, logoPath: string): LogoInfo {
+  const { width, height } = image.getSize();
+  return {
+    path: logoPath,
+    width,
+    height,
+    sizeBytes: fs.statSync(logoPath).size,
+    previewDataUrl: downscale(image, PREVIEW_HEIGHT * 4, PREVIEW_HEIGHT).toDataURL(),
+  };
+}
+
+/**
+ * Validate, downscale and store a new company logo.
+ *
+ * @param sourcePath Absolute path of a PNG or JPEG file
+ * @throws Error if the file is missing, too large or not a PNG/JPEG image
+ */
+export function setCompanyLogo(sourcePath: string): LogoInfo {
+  if (!path.isAbsolute(sourcePath) || !fs.existsSync(sourcePath)) {
+    throw new AppError("file_not_found", `Logo file not found: ${sourcePath}`);
+  }
+  const { size } = fs.statSync(sourcePath);
+  if (size > MAX_SOURCE_BYTES) {
+    throw new AppError(
+      "invalid_input",
+      `Logo file is too large (${Math.round(size / 1024 / 1024)} MB, max 20 MB)`,
+      { message: "Das Logo ist zu groß (höchstens 20 MB)." }
+    );
+  }
+  const mimeType = sniffMimeType(sourcePath);
+  if (!mimeType || !ACCEPTED_TYPES.includes(mimeType)) {
+    throw new AppError("invalid_input", "Logo must be a PNG or JPEG image", {
+      message: "Das Logo muss ein PNG- oder JPEG-Bild sein.",
+    });
+  }
+
+  const logoPath = getLogoPath();
+  const optimized = optimizeImage(sourcePath, logoPath, {
+    maxWidth: MAX_LOGO_WIDTH,
+    maxHeight: MAX_LOGO_HEIGHT,
+    format: "png",
+  });
+
+  log.info(
+    `🖼️ Company logo updated: ${mimeType} (${size} bytes) → ` +
+      `${optimized.width}×${optimized.height} PNG (${optimized.sizeBytes} bytes)`
+  );
+  return describeLogo(loadImage(logoPath), logoPath);
+}
+
+/**
+ * Get the current company logo, or null if none is set.
+ */
+export function getCompanyLogo(): LogoInfo | null {
+  const logoPath = getLogoPath();
+  if (!fs.existsSync(logoPath)) return null;
+
+  try {
+    return describeLogo(loadImage(logoPath), logoPath);
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Remove the company logo (PDFs are generated without one afterwards).
+ */
+export function removeCompanyLogo(): void {
+  fs.rmSync(getLogoPath(), { force: true });
+  log.info("🗑️ Company logo removed");
+}
+
+/**
+ * Register IPC handlers for logo management.
+ */
+export function registerLogoHandlers(): void {
+  handle("set-company-logo", (_event, sourcePath: string) => setCompanyLogo(sourcePath));
+  handle("get-company-logo", () => getCompanyLogo(), "read");
+  handle("remove-company-logo", () => removeCompanyLogo());
+}
@@ -12,35 +12,188 @@
  *   %APPDATA%/Billino/backups/
  *   %APPDATA%/Billino/pdfs/
  *   %APPDATA%/Billino/logs/
+ *   %APPDATA%/Billino/crashes/
  */
 
-import { app, BrowserWindow, dialog, protocol } from "electron";
-import { ChildProcess, spawn } from "child_process";
+import { app, BrowserWindow, dialog, powerMonitor, protocol } from "electron";
+import { spawn } from "child_process";
 import path from "path";
+import { setTimeout as delay } from "timers/promises";
 import fs from "fs";
 import log from "electron-log/main";
+import { initCrashReporter, registerDiagnosticsHandlers } from "./diagnostics";
+import {
+  BlockedByAntivirusError,
+  checkBackendBinary,
+  detectAntivirusExitBlock,
+  detectAntivirusSpawnBlock,
+  showBlockedByAntivirusDialog,
+} from "./antivirus";
+import { initLogging, setLogBackendStateProvider } from "./logging";
+import { initLogRedaction } from "./redaction";
+import { closeSecondaryWindows, registerWindow, WindowRole } from "./windows";
+import {
+  abortAllOperations,
+  listActiveOperations,
+  OPERATION_LABELS,
+  registerOperationHandlers,
+  waitForOperations,
+} from "./operations";
+import { loadSettings, registerSettingsHandlers } from "./settings";
+import { initHeavyJobScheduler } from "./jobs";
+import {
+  getActivePort,
+  getBackendEnv,
+  getBackendUrl,
+  getConfig,
+  isAttachedMode,
+  loadConfig,
+  migrateLegacyConfig,
+  registerConfigHandlers,
+  setActivePort,
+  splitArgs,
+} from "./config";
+import {
+  applyDataDirArgs,
+  CLI_USAGE,
+  cliConfigLayer,
+  CliArgs,
+  getProcessArgs,
+  logCliArgs,
+  parseCliArgs,
+} from "./cli";
+import { getBackendHealth, HealthCacheOptions, HealthStatus, isHealthy } from "./health";
+import { logStartupSummary, registerTimingHandlers, timePhase, timePhaseAsync } from "./timings";
+import { handle } from "./ipc";
+import { PDF_SCHEME_PRIVILEGES, registerPdfProtocol } from "./pdfs";
+import { registerTransferHandlers } from "./transfers";
+import { registerLogoHandlers } from "./logo";
+import { registerSpellcheckHandlers } from "./spellcheck";
+import { registerBillingHandlers } from "./billing";
+import { registerFiscalHandlers } from "./fiscal";
+import { registerDunningHandlers } from "./dunning";
+import { registerVatHandlers } from "./vat";
+import { registerThresholdHandlers, startThresholdMonitoring } from "./thresholds";
+import { registerReportHandlers, startMonthlyReports } from "./reports";
+import { initFxRates, registerFxHandlers } from "./fx";
+import { registerHolidayHandlers } from "./holidays";
+import { registerBackupHandlers } from "./backups";
+import { registerDatabaseHandlers } from "./database";
+import { isUncPath } from "./paths";
+import { registerSigningHandlers } from "./signing";
+import { registerTaskHandlers, startTaskQueue } from "./tasks";
+import { registerAttachmentHandlers } from "./attachments";
+import { registerStatementHandlers } from "./statements";
+import { registerImportHandlers } from "./importers";
+import { registerReplayHandlers } from "./replay";
+import {
+  newBackendInstanceId,
+  registerStaleBackendHandlers,
+  retireStaleBackends,
+} from "./orphans";
+import { BackendExit, BackendManager, registerBackendStateHandlers } from "./backendmanager";
+import { registerAppConditionHandlers, startAppConditionWatch } from "./condition";
+import {
+  checkStartupCrashLoop,
+  markLaunchFailed,
+  markLaunchQuit,
+  markLaunchSucceeded,
+  registerCrashLoopHandlers,
+} from "./crashloop";
+import { registerCrashReportHandlers } from "./crashreport";
+import { getInitialWindowState, trackWindowState } from "./placement";
+import {
+  bindDeveloperConsoleShortcut,
+  captureBackendOutput,
+  getBackendStderrTail,
+  getLastBackendLogSeq,
+  onBackendLogLine,
+  registerConsoleHandlers,
+} from "./console";
+import { registerProbeHandlers } from "./probes";
+import { registerCredentialHandlers } from "./credentials";
+import { registerEmailHandlers } from "./email";
+import { registerOAuthHandlers } from "./oauth";
+import { registerIcsHandlers } from "./ics";
+import { fireHooks, initAutomationHooks, registerHookHandlers } from "./hooks";
+import { registerMirrorHandlers, startPdfMirror } from "./mirror";
+import { registerBulkHandlers } from "./bulk";
+import { registerPdfQueueHandlers } from "./pdfqueue";
+import { applyBackendPriority, getTuningEnv, registerTuningHandlers } from "./tuning";
+import {
+  BlueGreenResult,
+  findFreePort,
+  installBackendRedirect,
+  RestartResult,
+  selectBackendPort,
+  waitForPortRelease,
+} from "./routing";
+import { BackendRestartProgressEvent, emitEvent, registerEventHandlers } from "./events";
+import { initSessionRecording } from "./session";
+import { checkBackendApi, registerApiCheckHandlers } from "./apicheck";
+import { backendPath } from "./api";
+import { registerApiProxyHandlers } from "./proxy";
+import { initBackendLogFile, registerBackendLogHandlers } from "./backendlog";
+import { initBackendMetrics, registerMetricsHandlers } from "./metrics";
+import { checkTimeSync, registerTimeSyncHandlers } from "./timesync";
+import { registerResourceHandlers, startResourceMonitoring } from "./resources";
+import { registerRetentionHandlers } from "./retention";
+import { AppError } from "./errors";
+import { registerAccessibilityHandlers, startAccessibilityMonitoring } from "./accessibility";
+import { registerTracingHandlers, traceRendererRequests } from "./tracing";
+import {
+  createSafeModeWindow,
+  enableSafeMode,
+  installAppMenu,
+  isSafeMode,
+  registerSafeModeHandlers,
+} from "./safemode";
+import { ProcessHandle } from "./processes";
+import { getShutdownTokenEnv, ShutdownReport, shutdownBackendProcess } from "./shutdown";
+import { registerMaintenanceHandlers, startMaintenanceScheduler } from "./maintenance";
+
+// ─── Endpoints ───────────────────────────────────────────────────────────────
+
+const healthUrl = (): string => `${getBackendUrl()}${backendPath("GET /health")}`;
 
-// ─── Constants ───────────────────────────────────────────────────────────────
+// ─── Globals ─────────────────────────────────────────────────────────────────
 
-const BACKEND_PORT = 8000;
-const BACKEND_HOST = "127.0.0.1";
-const BACKEND_URL = `http://${BACKEND_HOST}:${BACKEND_PORT}`;
-const HEALTH_URL = `${BACKEND_URL}/health`;
-const BACKUP_URL = `${BACKEND_URL}/backups/trigger`;
-const HEALTH_RETRIES = 60; // max attempts
-const HEALTH_INTERVAL_MS = 500; // ms between attempts
+/** Backend instances, state, restarts, crash recovery and shutdown. */
+const backend = new BackendManager();
+/** uvicorn logged "Application startup complete" since the last health check. */
+let readinessHinted = false;
+/** Ends the current wait between health checks early. */
+let wakeHealthPoll: (() => void) | null = null;
+
+// ─── Command-Line Arguments ──────────────────────────────────────────────────
+
+let cliArgs: CliArgs = { safeMode: false, help: false };
+try {
+  cliArgs = parseCliArgs(getProcessArgs());
+} catch (err) {
+  console.error(`${err}\n\n${CLI_USAGE}`);
+  app.exit(2);
+}
 
-// ─── Globals ─────────────────────────────────────────────────────────────────
+if (cliArgs.help) {
+  console.log(CLI_USAGE);
+  app.exit(0);
+}
 
-let backendProcess: ChildProcess | null = null;
-let mainWindow: BrowserWindow | null = null;
-let isQuitting = false;
+// Must run before logging/crash reporter resolve paths below userData
+applyDataDirArgs(cliArgs);
 
 // ─── Logging ─────────────────────────────────────────────────────────────────
 
-log.initialize();
-log.transports.file.resolvePathFn = () =>
-  path.join(app.getPath("userData"), "logs", "billino-desktop.log");
+// File logging with size/day rotation and retention (see logging.ts)
+initLogging();
+// BILLINO_LOG_FORMAT=json lines carry the backend state
+setLogBackendStateProvider(() => backend.state);
+// Mask IBANs, e-mail addresses and names in everything that gets logged
+initLogRedaction();
+
+// Capture native crashes as early as possible (before `ready`)
+initCrashReporter();
 
 // ─── User Data Directories ──────────────────────────────────────────────────
 
@@ -90,65 +243,158 @@ function getBackendPath(): string {
  * - ENV=production (packaged) or development
  * - BACKEND_HOST / BACKEND_PORT
  * - DATA_DIR → AppData/Roaming/Billino
- * - BACKUP_ENABLED=true
+ * - BACKUP_ENABLED=true (false in safe mode)
+ * - PYTHONUTF8 / PYTHONIOENCODING → UTF-8 for paths and log output
+ * - BACKEND_WORKERS / SQLITE_CACHE_MB / LOG_LEVEL → backendTuning settings
+ *
+ * The process priority (backendTuning.priority) is set right after spawning.
+ *
+ * @param port Port to listen on (another one for blue-green restarts)
+ * @throws BlockedByAntivirusError if the bundled executable is missing
  */
-function startBackend(): void {
-  const backendPath = getBackendPath();
+function startBackend(port: number = getConfig().port): ProcessHandle {
+  // Only the first start counts towards the startup timings
+  const timed = <T>(phase: string, fn: () => T): T =>
+    backend.process || backend.restart ? fn() : timePhase(phase, fn);
+  const backendPath = timed("binary-resolution", getBackendPath);
   const userData = app.getPath("userData");
 
+  const missingBinary = checkBackendBinary(backendPath);
+  if (missingBinary) throw missingBinary;
+
+  const config = getConfig();
+
+  // config.toml [backend.env]; the variables below are managed by the shell and win
+  const customEnv = getBackendEnv(port);
   const env: NodeJS.ProcessEnv = {
     ...process.env,
+    ...customEnv,
     APP_ENV: "desktop",
     ENV: app.isPackaged ? "production" : "development",
-    BACKEND_HOST,
-    BACKEND_PORT: String(BACKEND_PORT),
+    BACKEND_HOST: config.host,
+    BACKEND_PORT: String(port),
     DATA_DIR: userData,
-    BACKUP_ENABLED: "true",
+    // Safe mode: no scheduled backups while repairing
+    BACKUP_ENABLED: isSafeMode() ? "false" : "true",
+    // Pipes use the ANSI code page otherwise: "Jörg" arrives as "J\xf6rg"
+    PYTHONUTF8: "1",
+    PYTHONIOENCODING: "utf-8",
+    // The backend ties itself to this process (Job Object / PDEATHSIG)
+    BILLINO_PARENT_PID: String(process.pid),
+    // Tells this instance apart from stale ones of earlier sessions
+    BILLINO_INSTANCE_ID: newBackendInstanceId(),
+    // Only this shell may call POST /shutdown
+    ...getShutdownTokenEnv(),
+    // Workers, SQLite cache, log level from the settings (clamped)
+    ...getTuningEnv(),
   };
 
+  for (const [key, value] of Object.entries(customEnv)) {
+    if (env[key] !== value) log.warn(`⚠️ [backend.env] ${key} is set by Billino and ignored`);
+  }
+
   log.info(`🚀 Starting backend: ${backendPath}`);
   log.info(`📂 Data directory: ${userData}`);
+  if (isUncPath(userData)) {
+    // SQLite relies on file locks, which SMB shares implement unreliably
+    log.warn("⚠️ Data directory is on a network share – use it from one computer at a time");
+  }
+
+  // config.toml backend_args / working_dir, validated by loadConfig()
+  const args = splitArgs(config.backendArgs);
+  if (args.length > 0) log.info(`⚙️ Backend arguments: ${args.join(" ")}`);
+  if (config.workingDir) log.info(`📁 Backend working directory: ${config.workingDir}`);
+
+  const spawnedAt = Date.now();
+  const firstLogSeq = getLastBackendLogSeq();
+  const child = timed("spawn", () => {
+    if (app.isPackaged) {
+      // Production: run the bundled executable
+      return spawn(backendPath, args, {
+        env,
+        cwd: config.workingDir || undefined,
+        stdio: ["ignore", "pipe", "pipe"],
+        windowsHide: true,
+        // Own process group on Unix, so a kill reaches the workers too
+        detached: process.platform !== "win32",
+      });
+    }
 
-  if (app.isPackaged) {
-    // Production: run the bundled executable
-    backendProcess = spawn(backendPath, [], {
-      env,
-      stdio: ["ignore", "pipe", "pipe"],
-      windowsHide: true,
-    });
-  } else {
     // Development: run via Python
-    backendProcess = spawn("python", [backendPath], {
+    return spawn("python", [backendPath, ...args], {
       env,
       stdio: ["ignore", "pipe", "pipe"],
-      cwd: path.join(__dirname, "..", "..", "backend"),
+      cwd: config.workingDir || path.join(__dirname, "..", "..", "backend"),
+      detached: process.platform !== "win32",
     });
-  }
-
-  // Pipe backend output to electron-log
-  backendProcess.stdout?.on("data", (data: Buffer) => {
-    log.info(`[backend] ${data.toString().trim()}`);
   });
-
-  backendProcess.stderr?.on("data", (data: Buffer) => {
-    log.warn(`[backend:err] ${data.toString().trim()}`);
+  const handle = new ProcessHandle(child);
+  applyBackendPriority(child.pid);
+
+  // Backend output goes line by line to electron-log and the console
+  // buffer. setEncoding decodes with a StringDecoder, so umlauts split
+  // across two chunks stay intact.
+  child.stdout?.setEncoding("utf8");
+  child.stdout?.on("data", (data: string) => captureBackendOutput("stdout", data));
+
+  child.stderr?.setEncoding("utf8");
+  child.stderr?.on("data", (data: string) => captureBackendOutput("stderr", data));
+
+  child.on("exit", (code, signal) => {
+    log.info(`🛑 Backend exited: code=${code}, signal=${signal} (pid ${child.pid})`);
+    // A replaced instance or a standby that failed to start
+    if (!backend.release(handle)) return;
+
+    if (!backend.shuttingDown) {
+      backend.setState("crashed");
+      const uptimeMs = Date.now() - spawnedAt;
+      const blocked = detectAntivirusExitBlock(code, uptimeMs, backendPath);
+      if (blocked) {
+        showBlockedByAntivirusDialog(blocked);
+        app.quit();
+        return;
+      }
+      // A respawned instance that dies is noticed by the recovery loop
+      const duringRecovery = backend.recovering;
+      void describeExit(code, signal, uptimeMs).then((exit) => {
+        backend.recordCrash(exit);
+        if (!duringRecovery) void recoverFromCrash(exit);
+      });
+    }
   });
 
-  backendProcess.on("exit", (code, signal) => {
-    log.info(`🛑 Backend exited: code=${code}, signal=${signal}`);
-    backendProcess = null;
+  /** Exit details, once the output still in the pipes has been read. */
+  const describeExit = async (
+    code: number | null,
+    signal: NodeJS.Signals | null,
+    uptimeMs: number
+  ): Promise<BackendExit> => {
+    const exitedAt = new Date().toISOString();
+    // Pipes close after "exit" – unless uvicorn workers still hold them
+    await Promise.race([
+      new Promise((resolve) => child.once("close", resolve)),
+      new Promise((resolve) => setTimeout(resolve, OUTPUT_DRAIN_MS)),
+    ]);
+    return {
+      exitCode: code,
+      signal,
+      exitedAt,
+      uptimeMs,
+      stderrTail: getBackendStderrTail(CRASH_STDERR_LINES, firstLogSeq),
+    };
+  };
 
-    if (!isQuitting) {
-      log.error("❌ Backend crashed unexpectedly!");
-      dialog.showErrorBox(
-        "Billino – Fehler",
-        "Das Backend ist unerwartet beendet worden.\nBitte starte die App neu."
-      );
+  child.on("error", (err) => {
+    if (!backend.isCurrent(handle)) {
+      log.error(`❌ Failed to start backend instance: ${err.message}`);
+      return;
+    }
+    const blocked = detectAntivirusSpawnBlock(err, backendPath);
+    if (blocked) {
+      showBlockedByAntivirusDialog(blocked);
       app.quit();
+      return;
     }
-  });
-
-  backendProcess.on("error", (err) => {
     log.error(`❌ Failed to start backend: ${err.message}`);
     dialog.showErrorBox(
       "Billino – Startfehler",
@@ -156,78 +402,449 @@ function startBackend(): void {
     );
     app.quit();
   });
+
+  return handle;
+}
+
+const STARTUP_COMPLETE_PATTERN = /Application startup complete/;
+
+/**
+ * Treat uvicorn's "Application startup complete" line as a hint that the
+ * backend is ready, so the next health check runs at once instead of after
+ * the poll interval. Only a hint: readiness is still decided by /health.
+ */
+function watchBackendReadiness(): void {
+  onBackendLogLine((line) => {
+    if (!STARTUP_COMPLETE_PATTERN.test(line.text)) return;
+    log.info("⚡ Backend reported startup complete – checking health now");
+    readinessHinted = true;
+    wakeHealthPoll?.();
+  });
+}
+
+/**
+ * Wait between two health checks, or less if a readiness hint arrives or
+ * the shell shuts down.
+ */
+function sleepUntilReadinessHint(ms: number, signal: AbortSignal): Promise<void> {
+  if (readinessHinted || signal.aborted) {
+    readinessHinted = false;
+    return Promise.resolve();
+  }
+  return new Promise((resolve) => {
+    const wake = (): void => {
+      clearTimeout(timer);
+      signal.removeEventListener("abort", wake);
+      wakeHealthPoll = null;
+      readinessHinted = false;
+      resolve();
+    };
+    const timer = setTimeout(wake, ms);
+    signal.addEventListener("abort", wake, { once: true });
+    wakeHealthPoll = wake;
+  });
+}
+
+/**
+ * Wait `ms`, or less if `signal` aborts; check `signal.aborted` afterwards.
+ */
+function pause(ms: number, signal: AbortSignal = backend.signal): Promise<void> {
+  return delay(ms, undefined, { signal }).catch(() => undefined);
 }
 
 /**
  * Poll the /health endpoint until the backend reports ready.
  *
- * @throws Error if backend doesn't become healthy within the timeout
+ * @param url Health URL (default: the active backend)
+ * @param signal Stops polling (default: aborted on shutdown)
+ * @throws Error if backend doesn't become healthy within the timeout, or
+ *         the AbortError if `signal` aborts first
  */
-async function waitForBackend(): Promise<void> {
+async function waitForBackend(
+  url: string = healthUrl(),
+  signal: AbortSignal = backend.signal
+): Promise<HealthStatus> {
+  const { healthRetries, healthIntervalMs } = getConfig();
   log.info("⏳ Waiting for backend to become ready...");
+  readinessHinted = false;
+
+  for (let attempt = 1; attempt <= healthRetries; attempt++) {
+    // Failed checks are expected while the backend is still starting
+    const result = await getBackendHealth(url, { forceRefresh: true, signal });
+    signal.throwIfAborted();
+    if (result.ok && isHealthy(result.health)) {
+      const { health } = result;
+      log.info(
+        `✅ Backend ready after ${attempt} attempt(s) ` +
+          `(version=${health.appVersion ?? "?"}, pending migrations=${health.pendingMigrations ?? "?"})`
+      );
+      if (health.pendingMigrations) {
+        log.warn(`⚠️ Database schema has ${health.pendingMigrations} pending migration(s)`);
+      }
+      return health;
+    }
 
-  for (let attempt = 1; attempt <= HEALTH_RETRIES; attempt++) {
-    try {
-      const response = await fetch(HEALTH_URL);
-      if (response.ok) {
-        const data = (await response.json()) as { ready?: boolean; status?: string };
-        if (data.ready === true || data.status === "ok") {
-          log.info(`✅ Backend ready after ${attempt} attempt(s)`);
+    await sleepUntilReadinessHint(healthIntervalMs, signal);
+    signal.throwIfAborted();
+  }
+
+  throw new Error(`Backend did not become ready after ${healthRetries * healthIntervalMs}ms`);
+}
+
+// ─── Crash Recovery ──────────────────────────────────────────────────────────
+
+/** Delay before the first respawn; doubles with every further attempt. */
+const CRASH_BACKOFF_BASE_MS = 1_000;
+const CRASH_BACKOFF_MAX_MS = 30_000;
+/** stderr lines reported with `backend:crashed`. */
+const CRASH_STDERR_LINES = 20;
+/** Longest wait for the output of an exited backend. */
+const OUTPUT_DRAIN_MS = 1_000;
+
+/**
+ * React to an unexpected backend exit: respawn it with exponential backoff
+ * (`autoRestart`, `maxRestartAttempts`) and report `backend:crashed` and
+ * `backend:restarting`. When restarts are off or used up the user is told
+ * and Billino quits.
+ */
+async function recoverFromCrash(exit: BackendExit): Promise<void> {
+  log.error("❌ Backend crashed unexpectedly!");
+  fireHooks("backend.crashed", { exitCode: exit.exitCode, signal: exit.signal });
+  backend.noteCrashedAfter(exit.uptimeMs);
+
+  const { autoRestart, maxRestartAttempts } = getConfig();
+  const willRestart = autoRestart && backend.crashAttempts < maxRestartAttempts;
+  emitEvent("backend:crashed", {
+    ...exit,
+    reason: exit.signal ? `signal ${exit.signal}` : `exit code ${exit.exitCode ?? "?"}`,
+    attempts: backend.crashAttempts,
+    willRestart,
+  });
+
+  backend.setRecovering(willRestart);
+  try {
+    while (autoRestart && backend.crashAttempts < maxRestartAttempts) {
+      const attempt = backend.nextCrashAttempt();
+      const delayMs = Math.min(CRASH_BACKOFF_BASE_MS * 2 ** (attempt - 1), CRASH_BACKOFF_MAX_MS);
+      log.warn(`🔁 Restarting backend in ${delayMs}ms (${attempt}/${maxRestartAttempts})`);
+      backend.setState("restarting");
+      emitEvent("backend:restarting", { attempt, maxAttempts: maxRestartAttempts, delayMs });
+      await pause(delayMs);
+      // Quitting, or the restart command got there first
+      if (backend.shuttingDown || backend.process || backend.restart) return;
+
+      try {
+        const handle = startBackend(getActivePort());
+        backend.adopt(handle);
+        const healthy = waitForBackend().then(
+          () => true,
+          () => false
+        );
+        if (await Promise.race([healthy, handle.onceExited().then(() => false)])) {
+          log.info(`✅ Backend recovered after ${attempt} restart(s)`);
+          backend.setState("running");
           return;
         }
+        // The quit stops it along with everything else
+        if (backend.shuttingDown) return;
+        await handle.stop(BACKEND_STOP_GRACE_MS);
+        backend.release(handle);
+      } catch (err) {
+        log.error(`❌ Backend restart ${attempt} failed: ${err}`);
       }
-    } catch {
-      // Backend not yet reachable – expected during startup
     }
-
-    await new Promise((resolve) => setTimeout(resolve, HEALTH_INTERVAL_MS));
+  } finally {
+    backend.setRecovering(false);
   }
 
-  throw new Error(
-    `Backend did not become ready after ${HEALTH_RETRIES * HEALTH_INTERVAL_MS}ms`
+  if (backend.shuttingDown) return;
+  backend.setState("crashed");
+  log.error(`❌ Backend could not be recovered (${backend.crashAttempts} restart(s))`);
+  markLaunchFailed(
+    `Backend abgestürzt (${exit.signal ?? `Exit-Code ${exit.exitCode ?? "?"}`}), ` +
+      `${backend.crashAttempts} Neustart(s) erfolglos`
+  );
+  dialog.showErrorBox(
+    "Billino – Fehler",
+    backend.crashAttempts > 0
+      ? "Das Backend ist wiederholt abgestürzt und konnte nicht neu gestartet werden.\n" +
+          "Bitte starte die App neu."
+      : "Das Backend ist unerwartet beendet worden.\nBitte starte die App neu."
   );
+  app.quit();
+}
+
+/**
+ * Start the backend on request from the safe-mode window.
+ */
+async function startSafeModeBackend(): Promise<void> {
+  if (!isAttachedMode() && !backend.process) {
+    backend.setState("starting");
+    await retireStaleBackends();
+    const port = await selectBackendPort();
+    setActivePort(port);
+    backend.adopt(startBackend(port));
+    installBackendRedirect();
+  }
+  await waitForBackend();
+  backend.setState("running");
+}
+
+interface BackupJob {
+  job_id: string;
+  state: "running" | "done" | "failed";
+  backup_path: string | null;
+  error: string | null;
+}
+
+const BACKUP_POLL_INTERVAL_MS = 250;
+
+/**
+ * Ask whether to keep waiting for a slow shutdown backup.
+ *
+ * @returns true to wait another round
+ */
+async function askWaitForBackup(waitedMs: number): Promise<boolean> {
+  log.warn(`⚠️ Shutdown backup still running after ${waitedMs}ms`);
+  const { response } = await dialog.showMessageBox({
+    type: "warning",
+    title: "Billino – Datensicherung",
+    message: "Die Datensicherung beim Beenden ist noch nicht fertig.",
+    detail:
+      `Sie läuft seit ${Math.round(waitedMs / 1000)} Sekunden. Wird Billino jetzt ` +
+      "beendet, ist diese Sicherung unvollständig; ältere Sicherungen bleiben erhalten.",
+    buttons: ["Weiter warten", "Jetzt beenden"],
+    defaultId: 0,
+    cancelId: 1,
+  });
+  return response === 0;
 }
 
 /**
- * Trigger a backup via the backend API before shutdown.
+ * Trigger a backup via the backend API before shutdown and wait until it
+ * has finished. The backup runs in the background on the backend; after
+ * `shutdownBackupTimeoutMs` the user decides whether to keep waiting.
  */
 async function triggerShutdownBackup(): Promise<void> {
   log.info("💾 Triggering shutdown backup...");
+  const timeoutMs = getConfig().shutdownBackupTimeoutMs;
+  const started = Date.now();
+  let deadline = started + timeoutMs;
 
   try {
-    const response = await fetch(BACKUP_URL, {
+    const triggerPath = backendPath("POST /backups/trigger", {}, { wait: false });
+    const response = await fetch(`${getBackendUrl()}${triggerPath}`, {
       method: "POST",
-      signal: AbortSignal.timeout(10_000), // 10s timeout
+      signal: AbortSignal.timeout(timeoutMs),
     });
-
-    if (response.ok) {
-      log.info("✅ Shutdown backup completed successfully");
-    } else {
+    if (!response.ok) {
       log.warn(`⚠️ Shutdown backup returned status ${response.status}`);
+      return;
+    }
+    // Backends without background backups answer once the backup is done
+    if (response.status !== 202) {
+      log.info("✅ Shutdown backup completed successfully");
+      return;
+    }
+
+    const { job_id: jobId } = (await response.json()) as BackupJob;
+    const statusPath = backendPath("GET /backups/trigger/{job_id}", { job_id: jobId });
+    for (;;) {
+      await new Promise((resolve) => setTimeout(resolve, BACKUP_POLL_INTERVAL_MS));
+      const poll = await fetch(`${getBackendUrl()}${statusPath}`, { signal: AbortSignal.timeout(timeoutMs) });
+      const job = poll.ok ? ((await poll.json()) as BackupJob) : null;
+      if (job?.state === "done") {
+        log.info(`✅ Shutdown backup completed in ${Date.now() - started}ms: ${job.backup_path}`);
+        return;
+      }
+      if (!job || job.state === "failed") {
+        log.warn(`⚠️ Shutdown backup failed: ${job?.error ?? `status ${poll.status}`}`);
+        return;
+      }
+      if (Date.now() >= deadline) {
+        if (!(await askWaitForBackup(Date.now() - started))) {
+          log.warn("⚠️ Shutdown backup abandoned by the user");
+          return;
+        }
+        deadline = Date.now() + timeoutMs;
+      }
     }
   } catch (err) {
     log.warn(`⚠️ Shutdown backup failed: ${err}`);
   }
 }
 
+/** Time for the backend's shutdown sequence before it is killed. */
+const BACKEND_STOP_GRACE_MS = 5_000;
+
 /**
- * Gracefully terminate the backend process.
+ * Gracefully stop the backend process (and instances still draining) in
+ * stages, see shutdown.ts. Only processes spawned by the shell are
+ * touched, identified by PID.
  */
-function stopBackend(): void {
-  if (!backendProcess) return;
+async function stopBackend(): Promise<ShutdownReport[]> {
+  const current = backend.process;
+  const handles = backend.takeAll();
+  if (current) {
+    log.info("🛑 Stopping backend process...");
+    if (backend.restart !== "restart") backend.setState("stopped");
+  }
+  // Draining instances run on an earlier port; they get no shutdown request
+  return Promise.all(
+    handles.map((handle) =>
+      shutdownBackendProcess(handle, handle === current ? getBackendUrl() : null)
+    )
+  );
+}
 
-  log.info("🛑 Stopping backend process...");
+// ─── Blue-Green Restart ──────────────────────────────────────────────────────
 
-  if (process.platform === "win32") {
-    // On Windows, SIGTERM doesn't work reliably – use taskkill
-    spawn("taskkill", ["/pid", String(backendProcess.pid), "/f", "/t"], {
-      windowsHide: true,
-    });
-  } else {
-    backendProcess.kill("SIGTERM");
+/** Longest wait for running operations before the old instance stops. */
+const DRAIN_TIMEOUT_MS = 60_000;
+/** Requests sent just before the switch still reach the old instance. */
+const DRAIN_GRACE_MS = 5_000;
+
+async function drainBackend(handle: ProcessHandle): Promise<void> {
+  backend.retire(handle);
+  await Promise.race([waitForOperations(), pause(DRAIN_TIMEOUT_MS)]);
+  await pause(DRAIN_GRACE_MS);
+  if (!backend.finishRetiring(handle)) return;
+
+  log.info(`🛑 Stopping drained backend (pid ${handle.pid})`);
+  await handle.stop(BACKEND_STOP_GRACE_MS);
+}
+
+/**
+ * Restart the backend without downtime: start a second instance on the
+ * other port, wait until it is healthy, route all requests to it, then
+ * stop the old instance once its running requests are done.
+ *
+ * @throws Error in attach mode, while another restart runs, or if the new
+ *         instance does not become healthy (the old one keeps serving)
+ */
+async function restartBackendBlueGreen(): Promise<BlueGreenResult> {
+  if (isAttachedMode()) {
+    throw new AppError("invalid_state", "An attached backend cannot be restarted");
   }
+  if (!backend.process) throw new AppError("invalid_state", "Backend is not running");
 
-  backendProcess = null;
+  backend.beginRestart("blue-green");
+  const started = Date.now();
+  const { host, port: configuredPort } = getConfig();
+  try {
+    const preferred = getActivePort() === configuredPort ? configuredPort + 1 : configuredPort;
+    const port = await findFreePort(host, preferred);
+    log.info(`🔄 Blue-green restart: starting standby backend on port ${port}`);
+
+    const standby = startBackend(port);
+    try {
+      await waitForBackend(`http://${host}:${port}${backendPath("GET /health")}`);
+    } catch (err) {
+      await standby.stop(BACKEND_STOP_GRACE_MS);
+      throw err;
+    }
+
+    const previous = backend.adopt(standby);
+    setActivePort(port);
+    void checkBackendApi();
+    if (previous) void drainBackend(previous);
+
+    return { url: getBackendUrl(), port, durationMs: Date.now() - started };
+  } finally {
+    backend.endRestart();
+  }
+}
+
+// ─── Restart ─────────────────────────────────────────────────────────────────
+
+/** Spawn attempts before a restart gives up. */
+const RESTART_ATTEMPTS = 3;
+/** Longest wait for the stopped backend to release its port. */
+const PORT_RELEASE_TIMEOUT_MS = 10_000;
+
+/**
+ * Restart the backend: stop it, wait until its port is released, spawn it
+ * again and wait until it is healthy (up to RESTART_ATTEMPTS times).
+ *
+ * Progress is reported with `backend:restart-progress`, the outcome with
+ * `backend:ready` or `backend:error`. Unlike the blue-green restart the
+ * backend is unavailable meanwhile, but this also recovers a backend that
+ * hangs or no longer runs.
+ *
+ * @throws AppError in attach mode, while another restart runs, or if no
+ *         attempt became healthy
+ */
+async function restartBackend(): Promise<RestartResult> {
+  if (isAttachedMode()) {
+    throw new AppError("invalid_state", "An attached backend cannot be restarted");
+  }
+  backend.beginRestart("restart");
+  const started = Date.now();
+  const { host, port } = getConfig();
+  const progress = (phase: BackendRestartProgressEvent["phase"], attempt = 0): void =>
+    emitEvent("backend:restart-progress", { phase, attempt });
+  let attempt = 0;
+  try {
+    log.info("🔄 Restarting backend...");
+    backend.setState("restarting");
+    progress("stopping");
+    await stopBackend();
+    const stopMs = Date.now() - started;
+
+    progress("waiting-for-port");
+    const portStarted = Date.now();
+    const released = await waitForPortRelease(host, port, PORT_RELEASE_TIMEOUT_MS);
+    // The redirect sends renderer requests to another port if needed
+    const targetPort = released ? port : await findFreePort(host, port + 1);
+    if (!released) log.warn(`⚠️ Port ${port} is still in use – restarting on ${targetPort}`);
+    const portWaitMs = Date.now() - portStarted;
+
+    const startStarted = Date.now();
+    for (attempt = 1; ; attempt++) {
+      progress("starting", attempt);
+      let handle: ProcessHandle | null = null;
+      try {
+        handle = startBackend(targetPort);
+        await waitForBackend(`http://${host}:${targetPort}${backendPath("GET /health")}`);
+      } catch (err) {
+        await handle?.stop(BACKEND_STOP_GRACE_MS);
+        log.warn(`⚠️ Backend restart attempt ${attempt}/${RESTART_ATTEMPTS} failed: ${err}`);
+        if (attempt >= RESTART_ATTEMPTS) throw err;
+        continue;
+      }
+
+      // Only now, so a failed attempt does not count as a crash
+      backend.adopt(handle);
+      break;
+    }
+
+    setActivePort(targetPort);
+    void checkBackendApi();
+
+    const result: RestartResult = {
+      url: getBackendUrl(),
+      port: targetPort,
+      attempts: attempt,
+      stopMs,
+      portWaitMs,
+      startMs: Date.now() - startStarted,
+      durationMs: Date.now() - started,
+    };
+    log.info(`✅ Backend restarted in ${result.durationMs}ms (${attempt} attempt(s))`);
+    backend.setState("running");
+    emitEvent("backend:ready", result);
+    return result;
+  } catch (err) {
+    const message = err instanceof Error ? err.message : String(err);
+    log.error(`❌ Backend restart failed: ${message}`);
+    backend.setState("stopped");
+    emitEvent("backend:error", { message, attempts: attempt });
+    throw new AppError("backend_unreachable", `Backend restart failed: ${message}`, {
+      message: "Der Billino-Dienst konnte nicht neu gestartet werden.",
+    });
+  } finally {
+    backend.endRestart();
+  }
 }
 
 // ─── Custom Protocol (app://) ────────────────────────────────────────────────
@@ -340,9 +957,9 @@ function serveFile(filePath: string): Response {
  * In development: connects to Next.js dev server on localhost:3000.
  */
 function createWindow(): void {
-  mainWindow = new BrowserWindow({
-    width: 1280,
-    height: 900,
+  const windowState = getInitialWindowState({ width: 1280, height: 900 });
+  const mainWindow = new BrowserWindow({
+    ...windowState.bounds,
     minWidth: 800,
     minHeight: 600,
     title: "Billino",
@@ -359,6 +976,10 @@ function createWindow(): void {
     },
   });
 
+  if (windowState.isMaximized) mainWindow.maximize();
+  trackWindowState(mainWindow);
+  bindDeveloperConsoleShortcut(mainWindow.webContents);
+
   // Pipe renderer console.log/warn/error to electron-log for debugging
   mainWindow.webContents.on("console-message", (_event, level, message, line, sourceId) => {
     const tag = "[renderer]";
@@ -382,7 +1003,7 @@ function createWindow(): void {
     // custom app:// protocol. Since every route has its own pre-rendered
     // index.html, full-page navigation is instant from local files.
     mainWindow.webContents.on("dom-ready", () => {
-      mainWindow?.webContents.executeJavaScript(`
+      mainWindow.webContents.executeJavaScript(`
         document.addEventListener('click', function(e) {
           var anchor = e.target.closest ? e.target.closest('a') : null;
           if (!anchor) return;
@@ -404,11 +1025,154 @@ function createWindow(): void {
     mainWindow.webContents.openDevTools({ mode: "detach" });
   }
 
-  mainWindow.on("closed", () => {
-    mainWindow = null;
+  let closeConfirmed = false;
+  mainWindow.on("close", (event) => {
+    if (closeConfirmed || listActiveOperations().length === 0) return;
+
+    event.preventDefault();
+    void confirmCloseDuringOperations(mainWindow).then((proceed) => {
+      if (proceed && !mainWindow.isDestroyed()) {
+        closeConfirmed = true;
+        mainWindow.close();
+      }
+    });
   });
+
+  // Windows: veto logoff/shutdown until backup + backend stop are done
+  mainWindow.on("query-session-end", (event) => {
+    event.preventDefault();
+    handleOsSessionEnd(`query-session-end (${event.reasons.join(", ")})`);
+  });
+
+  registerWindow(mainWindow, "main", handleWindowClosed);
 }
 
+/**
+ * Ask the user what to do when the window is closed while a backup, export
+ * or batch print is still running.
+ *
+ * @returns true once it is safe to close (operations finished or aborted),
+ *          false if the user wants to keep the window open.
+ */
+async function confirmCloseDuringOperations(window: BrowserWindow): Promise<boolean> {
+  const running = listActiveOperations();
+  const names = running.map((op) => `• ${op.label || OPERATION_LABELS[op.kind]}`).join("\n");
+
+  log.info(`⚠️ Close requested with ${running.length} running operation(s)`);
+
+  const { response } = await dialog.showMessageBox(window, {
+    type: "warning",
+    title: "Billino – Vorgang läuft",
+    message: "Es läuft noch ein Vorgang. Jetzt beenden?",
+    detail:
+      `${names}\n\n` +
+      "„Warten“ schließt Billino automatisch, sobald alle Vorgänge abgeschlossen sind.",
+    buttons: ["Warten und danach beenden", "Vorgänge abbrechen und beenden", "Nicht beenden"],
+    defaultId: 0,
+    cancelId: 2,
+    noLink: true,
+  });
+
+  if (response === 0) {
+    log.info("⏳ Waiting for running operations before closing...");
+    await waitForOperations();
+    return true;
+  }
+  if (response === 1) {
+    abortAllOperations();
+    return true;
+  }
+  return false;
+}
+
+/**
+ * Decide whether a closed window ends the session.
+ *
+ * Only the main window (or the last open window) triggers shutdown –
+ * closing a secondary window must never stop the backend.
+ */
+function handleWindowClosed(role: WindowRole, remaining: number): void {
+  if (role !== "main" && remaining > 0) return;
+
+  // On macOS, apps stay active until Cmd+Q
+  if (process.platform === "darwin") return;
+
+  if (role === "main") {
+    closeSecondaryWindows();
+  }
+  app.quit();
+}
+
+// ─── OS Shutdown / Logoff ────────────────────────────────────────────────────
+
+/**
+ * Delay system shutdown/logoff while the graceful stop + backup runs.
+ *
+ * - Windows: the main window vetoes `query-session-end`, which makes Windows
+ *   list Billino as "preventing shutdown" until the app has exited.
+ * - macOS/Linux: `powerMonitor` `shutdown` is delayed via preventDefault().
+ *
+ * In both cases the regular quit sequence (backup → stop backend → exit)
+ * runs, after which the OS continues shutting down.
+ */
+function registerOsShutdownHandlers(): void {
+  powerMonitor.on("shutdown", (event: Electron.Event) => {
+    event.preventDefault();
+    handleOsSessionEnd("powerMonitor shutdown");
+  });
+}
+
+function handleOsSessionEnd(source: string): void {
+  log.info(`🖥️ OS session ending (${source}) – running graceful shutdown first`);
+  app.quit();
+}
+
+// ─── Fatal Error Handling ────────────────────────────────────────────────────
+
+let isHandlingFatalError = false;
+
+/**
+ * Handle an unrecoverable error in the main process.
+ *
+ * Logs the error with its stack trace, informs the user, makes a best-effort
+ * backup + backend stop and exits – so the backend never survives as an
+ * orphan after the shell itself crashed.
+ */
+async function handleFatalError(origin: string, err: unknown): Promise<void> {
+  const message = err instanceof Error ? err.message : String(err);
+  const stack = err instanceof Error && err.stack ? err.stack : message;
+
+  if (isHandlingFatalError) {
+    log.error(`💥 Additional fatal error during fatal-error handling (${origin}):\n${stack}`);
+    return;
+  }
+  isHandlingFatalError = true;
+  backend.beginShutdown();
+
+  log.error(`💥 Fatal error (${origin}):\n${stack}`);
+
+  dialog.showErrorBox(
+    "Billino – Fehler",
+    "Billino ist auf einen unerwarteten Fehler gestoßen und wird beendet.\n\n" +
+      `${message}\n\nDetails stehen in der Log-Datei.`
+  );
+
+  if (backend.process) {
+    await triggerShutdownBackup();
+  }
+  await stopBackend();
+
+  app.exit(1);
+}
+
+process.on("uncaughtException", (err) => {
+  void handleFatalError("uncaughtException", err);
+});
+
+process.on("unhandledRejection", (reason) => {
+  void handleFatalError("unhandledRejection", reason);
+});
+
 // ─── App Lifecycle ───────────────────────────────────────────────────────────
 
 // Register custom scheme privileges before app is ready.
@@ -423,6 +1187,7 @@ protocol.registerSchemesAsPrivileged([
       corsEnabled: true,
     },
   },
+  PDF_SCHEME_PRIVILEGES,
 ]);
 
 app.whenReady().then(async () => {
@@ -431,53 +1196,176 @@ app.whenReady().then(async () => {
   log.info(`📦 Packaged: ${app.isPackaged}`);
   log.info(`📂 userData: ${app.getPath("userData")}`);
   log.info("=" .repeat(60));
+  logCliArgs(cliArgs);
+  if (cliArgs.safeMode || checkStartupCrashLoop()) enableSafeMode();
 
   try {
     // Register app:// protocol handler for static frontend files
     registerAppProtocol();
-
-    ensureUserDataDirs();
-    startBackend();
-    await waitForBackend();
-    createWindow();
-  } catch (err) {
-    log.error(`❌ Startup failed: ${err}`);
-    dialog.showErrorBox(
-      "Billino – Startfehler",
-      `Die Anwendung konnte nicht gestartet werden:\n${err}`
+    registerPdfProtocol();
+    registerDiagnosticsHandlers();
+    registerOperationHandlers();
+    registerOsShutdownHandlers();
+    registerTimingHandlers();
+    registerTransferHandlers();
+    registerLogoHandlers();
+    registerSpellcheckHandlers();
+    registerBillingHandlers();
+    registerFiscalHandlers();
+    registerDunningHandlers();
+    registerVatHandlers();
+    registerThresholdHandlers();
+    registerReportHandlers();
+    registerFxHandlers();
+    registerHolidayHandlers();
+    registerBackupHandlers();
+    registerDatabaseHandlers();
+    registerConsoleHandlers();
+    registerBackendLogHandlers();
+    registerMetricsHandlers();
+    registerResourceHandlers(backend);
+    registerProbeHandlers();
+    registerCredentialHandlers();
+    registerEmailHandlers();
+    registerOAuthHandlers();
+    registerIcsHandlers();
+    registerHookHandlers();
+    registerMirrorHandlers();
+    registerBulkHandlers();
+    registerPdfQueueHandlers();
+    registerTuningHandlers();
+    registerApiCheckHandlers();
+    registerTracingHandlers();
+    registerAccessibilityHandlers();
+    registerSafeModeHandlers(startSafeModeBackend);
+    registerMaintenanceHandlers();
+    registerRetentionHandlers();
+    registerTimeSyncHandlers();
+    registerBackendStateHandlers(backend);
+    registerCrashReportHandlers(backend);
+    registerCrashLoopHandlers();
+    registerAppConditionHandlers(backend, healthUrl);
+    registerSigningHandlers();
+    registerTaskHandlers();
+    registerAttachmentHandlers();
+    registerStatementHandlers();
+    registerImportHandlers();
+    registerReplayHandlers();
+    registerStaleBackendHandlers();
+    registerEventHandlers();
+    registerApiProxyHandlers();
+    handle("restart-backend-blue-green", () => restartBackendBlueGreen(), "destructive");
+    handle("restart-backend", () => restartBackend(), "destructive");
+    handle(
+      "get-backend-health",
+      (_event, options?: HealthCacheOptions) => getBackendHealth(healthUrl(), options),
+      "read"
     );
-    stopBackend();
+    timePhase("config-load", () => {
+      try {
+        migrateLegacyConfig();
+      } catch (err) {
+        log.warn(`⚠️ Legacy config migration failed: ${err}`);
+      }
+      loadConfig(cliConfigLayer(cliArgs));
+      loadSettings();
+    });
+    registerConfigHandlers();
+    registerSettingsHandlers();
+    initBackendLogFile();
+    initBackendMetrics();
+    // Opt-in NTP comparison; runs alongside the startup, never blocks it
+    void checkTimeSync();
+    initSessionRecording();
+    traceRendererRequests();
+    initHeavyJobScheduler();
+    if (!isSafeMode()) initAutomationHooks();
+    watchBackendReadiness();
+    void startAccessibilityMonitoring();
+    installAppMenu();
+
+    timePhase("data-dirs", ensureUserDataDirs);
+    if (isSafeMode()) {
+      createSafeModeWindow(handleWindowClosed);
+      return;
+    }
+    if (isAttachedMode()) {
+      log.info(`🔗 Attaching to running backend at ${getBackendUrl()}`);
+    } else {
+      backend.setState("starting");
+      // A backend left over from a crashed session would keep the port
+      await retireStaleBackends();
+      const port = await selectBackendPort();
+      if (port !== getConfig().port) {
+        log.warn(`⚠️ Port ${getConfig().port} is taken – backend uses port ${port}`);
+        setActivePort(port);
+      }
+      backend.adopt(startBackend(port));
+      installBackendRedirect();
+    }
+    await timePhaseAsync("first-healthy", waitForBackend);
+    backend.setState("running");
+    markLaunchSucceeded();
+    timePhase("window-create", createWindow);
+    logStartupSummary();
+    startThresholdMonitoring();
+    startMonthlyReports();
+    startMaintenanceScheduler();
+    startTaskQueue();
+    startAppConditionWatch(backend, healthUrl);
+    startResourceMonitoring(backend);
+    initFxRates();
+    startPdfMirror();
+    void checkBackendApi();
+  } catch (err) {
+    // Quit while still starting: before-quit takes care of the rest
+    if (backend.shuttingDown) return;
+    markLaunchFailed(String(err));
+    if (err instanceof BlockedByAntivirusError) {
+      showBlockedByAntivirusDialog(err);
+    } else {
+      log.error(`❌ Startup failed: ${err}`);
+      dialog.showErrorBox(
+        "Billino – Startfehler",
+        `Die Anwendung konnte nicht gestartet werden:\n${err}`
+      );
+    }
+    await stopBackend();
     app.quit();
   }
 });
 
 app.on("window-all-closed", () => {
-  // On macOS, apps stay active until Cmd+Q
-  if (process.platform !== "darwin") {
-    app.quit();
-  }
+  // Shutdown is decided per window role in handleWindowClosed(). This
+  // listener only exists to opt out of Electron's default quit-on-close.
 });
 
 app.on("activate", () => {
   // macOS: recreate window when dock icon is clicked
   if (BrowserWindow.getAllWindows().length === 0) {
-    createWindow();
+    if (isSafeMode()) createSafeModeWindow(handleWindowClosed);
+    else createWindow();
   }
 });
 
 app.on("before-quit", async (event) => {
-  if (isQuitting) return;
+  if (backend.shuttingDown) return;
 
   event.preventDefault();
-  isQuitting = true;
+  // Ends health polls, backoff waits and the crash recovery right away
+  backend.beginShutdown();
+  markLaunchQuit();
 
   log.info("🛑 Billino shutting down...");
 
   // Step 1: Trigger backup before killing the backend
-  await triggerShutdownBackup();
+  // (an attached backend is not ours to back up or stop)
+  if (!isAttachedMode()) {
+    await triggerShutdownBackup();
+  }
 
   // Step 2: Stop backend process
-  stopBackend();
+  await stopBackend();
 
   // Step 3: Exit
   log.info("✅ Shutdown complete");
@@ -0,0 +1,397 @@
+/**
+ * Billino Desktop – Nightly Maintenance
+ *
+ * Once a day, inside the configured time window (default 2–5 o'clock) or
+ * after the computer has been idle for a while, the shell runs:
+ * 1. REINDEX – rebuilds the indexes used by search and filters
+ * 2. ANALYZE – refreshes the query planner statistics
+ * 3. missing PDFs – generates PDFs for invoices that have none (at most
+ *    MAX_PDFS_PER_RUN per night)
+ * 4. backup check – opens the newest backup read-only and checks that it
+ *    can be restored
+ * 5. retention – deletes old logs, expired exports, an oversized webview
+ *    cache and PDFs of deleted invoices (retention.ts)
+ *
+ * The run goes through the heavy-job scheduler (deferred on battery) and
+ * never starts while a backup, export or print is running. The result is
+ * shown as a notification and sent as `maintenance:finished`.
+ *
+ * Single maintenance actions are available to support through
+ * `run-maintenance-action`: only the curated MAINTENANCE_ACTIONS below
+ * (rebuild search index, refresh statistics, recalculate summary-invoice
+ * totals, regenerate an invoice PDF), so users can be guided through a fix
+ * without calling backend endpoints by hand.
+ */
+
+import { app, Notification, powerMonitor } from "electron";
+import fs from "fs";
+import path from "path";
+import log from "electron-log/main";
+import { callBackend } from "./api";
+import { inspectBackup } from "./backups";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { scheduleHeavyJob } from "./jobs";
+import { listActiveOperations } from "./operations";
+import { runRetentionCleanup } from "./retention";
+import { getSettings, MaintenanceSettings } from "./settings";
+
+export type MaintenanceTrigger = "window" | "idle" | "manual";
+export type MaintenanceStepName = "reindex" | "analyze" | "pdfs" | "backup" | "retention";
+
+export interface MaintenanceStep {
+  name: MaintenanceStepName;
+  ok: boolean;
+  /** German summary for the notification and the settings UI. */
+  detail: string;
+  durationMs: number;
+}
+
+export interface MaintenanceSummary {
+  trigger: MaintenanceTrigger;
+  startedAt: string;
+  finishedAt: string;
+  ok: boolean;
+  steps: MaintenanceStep[];
+}
+
+export interface MaintenanceStatus {
+  settings: MaintenanceSettings;
+  running: boolean;
+  lastRun: MaintenanceSummary | null;
+}
+
+const CHECK_INTERVAL_MS = 5 * 60 * 1000;
+/** Not twice in one night, even if the window is long. */
+const MIN_RUN_INTERVAL_MS = 20 * 60 * 60 * 1000;
+const MAX_PDFS_PER_RUN = 50;
+const JOB_NAME = "Nächtliche Wartung";
+
+let running = false;
+let timer: NodeJS.Timeout | null = null;
+
+function getStatePath(): string {
+  return path.join(app.getPath("userData"), "maintenance.json");
+}
+
+function loadLastRun(): MaintenanceSummary | null {
+  try {
+    return JSON.parse(fs.readFileSync(getStatePath(), "utf-8")) as MaintenanceSummary;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Whether `hour` lies in the window; windows may wrap midnight (22–4).
+ */
+export function isInMaintenanceWindow(hour: number, settings: MaintenanceSettings): boolean {
+  const { windowStartHour: start, windowEndHour: end } = settings;
+  return start <= end ? hour >= start && hour < end : hour >= start || hour < end;
+}
+
+async function runStep(
+  name: MaintenanceStepName,
+  fn: () => Promise<{ ok: boolean; detail: string }>
+): Promise<MaintenanceStep> {
+  const started = Date.now();
+  try {
+    return { name, ...(await fn()), durationMs: Date.now() - started };
+  } catch (err) {
+    log.warn(`⚠️ Maintenance step ${name} failed: ${err}`);
+    const detail = err instanceof AppError ? err.userMessage : String(err);
+    return { name, ok: false, detail, durationMs: Date.now() - started };
+  }
+}
+
+async function regenerateMissingPdfs(): Promise<{ ok: boolean; detail: string }> {
+  const { invoice_ids: missing } = (await callBackend("GET /database/missing-pdfs")) as {
+    invoice_ids: number[];
+  };
+  const batch = missing.slice(0, MAX_PDFS_PER_RUN);
+  let failed = 0;
+  for (const invoiceId of batch) {
+    try {
+      await callBackend("POST /pdfs/invoices/{invoice_id}", {
+        params: { invoice_id: invoiceId },
+      });
+    } catch (err) {
+      failed++;
+      log.warn(`⚠️ Could not generate PDF for invoice ${invoiceId}: ${err}`);
+    }
+  }
+  const rest = missing.length - batch.length;
+  return {
+    ok: failed === 0,
+    detail:
+      `${batch.length - failed} fehlende PDFs erzeugt` +
+      (failed ? `, ${failed} fehlgeschlagen` : "") +
+      (rest ? `, ${rest} folgen in der nächsten Nacht` : ""),
+  };
+}
+
+async function verifyNewestBackup(): Promise<{ ok: boolean; detail: string }> {
+  const backups = (await callBackend("GET /backups/list")) as Array<{
+    filename: string;
+    path: string;
+    created_iso: string;
+  }>;
+  const newest = [...backups].sort((a, b) => b.created_iso.localeCompare(a.created_iso))[0];
+  if (!newest) return { ok: false, detail: "Keine Sicherung vorhanden" };
+
+  const inspection = await inspectBackup(newest.path);
+  return inspection.compatible
+    ? { ok: true, detail: `${newest.filename} geprüft (${inspection.invoiceCount} Rechnungen)` }
+    : { ok: false, detail: `${newest.filename} ist mit dieser Version nicht lesbar` };
+}
+
+async function applyRetention(): Promise<{ ok: boolean; detail: string }> {
+  const report = await runRetentionCleanup();
+  const count = report.items.length;
+  const megabytes = (report.freedBytes / 1024 / 1024).toFixed(1).replace(".", ",");
+  const detail = report.dryRun
+    ? `Aufbewahrung (Probelauf): ${count} Einträge (${megabytes} MB) würden gelöscht`
+    : `Aufbewahrung: ${count} alte Einträge gelöscht (${megabytes} MB)`;
+  return { ok: report.errors.length === 0, detail };
+}
+
+function notify(summary: MaintenanceSummary): void {
+  if (!Notification.isSupported()) return;
+  new Notification({
+    title: summary.ok ? "Billino – Wartung abgeschlossen" : "Billino – Wartung mit Fehlern",
+    body: summary.steps.map((step) => `${step.ok ? "✓" : "✗"} ${step.detail}`).join("\n"),
+  }).show();
+}
+
+/**
+ * Run all maintenance steps now.
+ */
+export async function runMaintenance(
+  trigger: MaintenanceTrigger = "manual"
+): Promise<MaintenanceSummary> {
+  if (running) throw new AppError("invalid_state", "Maintenance is already running");
+  running = true;
+  const startedAt = new Date().toISOString();
+  log.info(`🧰 Maintenance started (${trigger})`);
+
+  try {
+    const steps = [
+      await runStep("reindex", async () => {
+        const result = (await callBackend("POST /database/reindex")) as { index_count: number };
+        return { ok: true, detail: `${result.index_count} Indizes neu aufgebaut` };
+      }),
+      await runStep("analyze", async () => {
+        await callBackend("POST /database/analyze");
+        return { ok: true, detail: "Datenbank-Statistiken aktualisiert" };
+      }),
+      await runStep("pdfs", regenerateMissingPdfs),
+      await runStep("backup", verifyNewestBackup),
+      await runStep("retention", applyRetention),
+    ];
+    const summary: MaintenanceSummary = {
+      trigger,
+      startedAt,
+      finishedAt: new Date().toISOString(),
+      ok: steps.every((step) => step.ok),
+      steps,
+    };
+
+    fs.writeFileSync(getStatePath(), JSON.stringify(summary, null, 2), "utf-8");
+    log.info(`🧰 Maintenance finished${summary.ok ? "" : " with errors"}`);
+    emitEvent("maintenance:finished", summary);
+    notify(summary);
+    return summary;
+  } finally {
+    running = false;
+  }
+}
+
+/**
+ * Why maintenance should run now, or null.
+ */
+function dueTrigger(): MaintenanceTrigger | null {
+  const settings = getSettings().maintenance;
+  if (!settings.enabled || running || listActiveOperations().length > 0) return null;
+
+  const lastRun = loadLastRun();
+  if (lastRun && Date.now() - Date.parse(lastRun.startedAt) < MIN_RUN_INTERVAL_MS) return null;
+
+  if (isInMaintenanceWindow(new Date().getHours(), settings)) return "window";
+  const idleSeconds = powerMonitor.getSystemIdleTime();
+  if (settings.idleMinutes > 0 && idleSeconds >= settings.idleMinutes * 60) return "idle";
+  return null;
+}
+
+/**
+ * Check periodically whether maintenance is due (call once the backend is
+ * healthy).
+ */
+export function startMaintenanceScheduler(): void {
+  if (timer) return;
+  timer = setInterval(() => {
+    const trigger = dueTrigger();
+    if (!trigger) return;
+    void scheduleHeavyJob(JOB_NAME, async () => {
+      await runMaintenance(trigger);
+    });
+  }, CHECK_INTERVAL_MS);
+}
+
+/**
+ * Settings, state and the last result, for the settings UI.
+ */
+export function getMaintenanceStatus(): MaintenanceStatus {
+  return { settings: getSettings().maintenance, running, lastRun: loadLastRun() };
+}
+
+// ─── Support Actions ─────────────────────────────────────────────────────────
+
+export type MaintenanceAction =
+  | "rebuild-search-index"
+  | "refresh-statistics"
+  | "recalculate-totals"
+  | "regenerate-pdf";
+
+export interface MaintenanceActionOptions {
+  /** Invoice for "regenerate-pdf". */
+  invoiceId?: number;
+  /**
+   * "recalculate-totals": correct differing totals instead of only reporting
+   * them (summary invoices with a stored PDF are never changed).
+   */
+  confirm?: boolean;
+}
+
+export interface MaintenanceActionInfo {
+  action: MaintenanceAction;
+  /** German label for the support panel. */
+  label: string;
+  needsInvoice: boolean;
+}
+
+export interface MaintenanceActionResult {
+  action: MaintenanceAction;
+  /** German summary for the user. */
+  detail: string;
+  durationMs: number;
+}
+
+interface ActionSpec {
+  label: string;
+  needsInvoice: boolean;
+  run: (options: MaintenanceActionOptions) => Promise<string>;
+}
+
+const MAINTENANCE_ACTIONS: Record<MaintenanceAction, ActionSpec> = {
+  "rebuild-search-index": {
+    label: "Suchindex neu aufbauen",
+    needsInvoice: false,
+    run: async () => {
+      const result = (await callBackend("POST /database/reindex")) as { index_count: number };
+      return `${result.index_count} Indizes neu aufgebaut`;
+    },
+  },
+  "refresh-statistics": {
+    label: "Datenbank-Statistiken aktualisieren",
+    needsInvoice: false,
+    run: async () => {
+      await callBackend("POST /database/analyze");
+      return "Datenbank-Statistiken aktualisiert";
+    },
+  },
+  "recalculate-totals": {
+    label: "Summen der Sammelrechnungen neu berechnen",
+    needsInvoice: false,
+    run: async ({ confirm }) => {
+      const result = (await callBackend("POST /database/recalculate-totals", {
+        body: { confirm: confirm === true },
+      })) as {
+        checked: number;
+        discrepancies: Array<{ id: number; has_pdf: boolean }>;
+        corrected: number[];
+      };
+      const withPdf = result.discrepancies.filter((entry) => entry.has_pdf).length;
+      const summary =
+        `${result.checked} Sammelrechnungen geprüft, ` +
+        `${result.discrepancies.length} mit abweichenden Summen`;
+      if (!confirm) return `${summary} (nicht korrigiert)`;
+      return `${summary}, ${result.corrected.length} korrigiert, ${withPdf} mit PDF unverändert`;
+    },
+  },
+  "regenerate-pdf": {
+    label: "PDF einer Rechnung neu erzeugen",
+    needsInvoice: true,
+    run: async ({ invoiceId }) => {
+      await callBackend("POST /pdfs/invoices/{invoice_id}", {
+        params: { invoice_id: invoiceId as number },
+      });
+      return `PDF für Rechnung ${invoiceId} neu erzeugt`;
+    },
+  },
+};
+
+/**
+ * The actions support may run, for the support panel.
+ */
+export function listMaintenanceActions(): MaintenanceActionInfo[] {
+  return (Object.entries(MAINTENANCE_ACTIONS) as [MaintenanceAction, ActionSpec][]).map(
+    ([action, spec]) => ({ action, label: spec.label, needsInvoice: spec.needsInvoice })
+  );
+}
+
+/**
+ * Run one curated maintenance action.
+ *
+ * @throws AppError for unknown actions, a missing invoice id, or while the
+ *         nightly maintenance or another action runs
+ */
+export async function runMaintenanceAction(
+  action: MaintenanceAction,
+  options: MaintenanceActionOptions = {}
+): Promise<MaintenanceActionResult> {
+  const spec = Object.hasOwn(MAINTENANCE_ACTIONS, action) ? MAINTENANCE_ACTIONS[action] : null;
+  if (!spec) {
+    throw new AppError("invalid_input", `Unknown maintenance action: ${String(action)}`, {
+      message: "Diese Wartungsaktion gibt es nicht.",
+    });
+  }
+  const { invoiceId } = options;
+  if (spec.needsInvoice && !(Number.isInteger(invoiceId) && (invoiceId as number) > 0)) {
+    throw new AppError("invalid_input", `${action} needs an invoice id`, {
+      message: "Bitte die Rechnung angeben.",
+    });
+  }
+  if (running) {
+    throw new AppError("invalid_state", "Maintenance is already running", {
+      message: "Die Wartung läuft gerade. Bitte später erneut versuchen.",
+    });
+  }
+  // Keeps the nightly run (and a second action) out until this one is done
+  running = true;
+
+  try {
+    const started = Date.now();
+    const target = spec.needsInvoice ? ` (invoice ${invoiceId})` : "";
+    log.info(`🧰 Maintenance action ${action}${target}`);
+    const detail = await spec.run(options);
+    log.info(`🧰 Maintenance action ${action} done: ${detail}`);
+    return { action, detail, durationMs: Date.now() - started };
+  } finally {
+    running = false;
+  }
+}
+
+/**
+ * Register IPC handlers for maintenance.
+ */
+export function registerMaintenanceHandlers(): void {
+  handle("get-maintenance-status", () => getMaintenanceStatus(), "read");
+  handle("run-maintenance", () => runMaintenance("manual"));
+  handle("list-maintenance-actions", () => listMaintenanceActions(), "read");
+  handle(
+    "run-maintenance-action",
+    (_event, action: MaintenanceAction, options?: MaintenanceActionOptions) =>
+      runMaintenanceAction(action, options)
+  );
+}
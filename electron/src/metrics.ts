@@ -0,0 +1,225 @@
+/**
+ * Billino Desktop – Backend Metrics
+ *
+ * Counts the warnings and errors in the captured backend output
+ * (console.ts) per minute. `get-backend-metrics` returns the rates of the
+ * last hour; the renderer can chart them or show a trend.
+ *
+ * A sudden jump is often the first sign of trouble – a lost database
+ * lock, a full disk – minutes before the backend crashes. When the
+ * current minute reaches SPIKE_FACTOR times the average of the previous
+ * BASELINE_MINUTES (and at least a minimum count, so a single error in a
+ * quiet hour is no spike), `backend:error-spike` is sent once; the next
+ * one for the same severity follows at the earliest after SPIKE_COOLDOWN_MS.
+ *
+ * The metrics also carry the aggregated health checks of a time window
+ * (health.ts): latency percentiles, failure rate, availability, uptime.
+ */
+
+import log from "electron-log/main";
+import { BackendLogLine, onBackendLogLine } from "./console";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { DEFAULT_METRICS_WINDOW_SECS, getHealthMetrics, HealthMetrics } from "./health";
+import { handle } from "./ipc";
+
+export type LogSeverity = "warning" | "error";
+
+export interface LogRateBucket {
+  /** Start of the minute (ISO). */
+  minute: string;
+  warnings: number;
+  errors: number;
+}
+
+export interface BackendErrorSpike {
+  severity: LogSeverity;
+  /** Lines of that severity in the current minute. */
+  perMinute: number;
+  /** Average per minute over the previous minutes. */
+  baselinePerMinute: number;
+  /** The line that crossed the threshold. */
+  sample: string;
+}
+
+export interface BackendLogMetrics {
+  /** Oldest first, the current (incomplete) minute last. */
+  perMinute: LogRateBucket[];
+  /** Average per minute over the complete minutes of the window. */
+  averageWarningsPerMinute: number;
+  averageErrorsPerMinute: number;
+  totalWarnings: number;
+  totalErrors: number;
+  lastSpike: (BackendErrorSpike & { at: string }) | null;
+}
+
+export interface BackendMetrics {
+  logs: BackendLogMetrics;
+  health: HealthMetrics;
+  checkedAt: string;
+}
+
+const MINUTE_MS = 60_000;
+const WINDOW_MINUTES = 60;
+const BASELINE_MINUTES = 15;
+const SPIKE_FACTOR = 3;
+/** Below these counts per minute nothing is a spike. */
+const SPIKE_MIN: Record<LogSeverity, number> = { warning: 20, error: 5 };
+const SPIKE_COOLDOWN_MS = 5 * MINUTE_MS;
+
+interface Bucket {
+  start: number;
+  warnings: number;
+  errors: number;
+}
+
+/** Oldest first; minutes without warnings/errors have no bucket. */
+const buckets: Bucket[] = [];
+let totalWarnings = 0;
+let totalErrors = 0;
+let lastSpike: (BackendErrorSpike & { at: string }) | null = null;
+const lastSpikeAt: Record<LogSeverity, number> = { warning: 0, error: 0 };
+
+function minuteStart(time: number): number {
+  return time - (time % MINUTE_MS);
+}
+
+function prune(now: number): void {
+  const oldest = minuteStart(now) - (WINDOW_MINUTES - 1) * MINUTE_MS;
+  while (buckets.length > 0 && buckets[0].start < oldest) buckets.shift();
+}
+
+function countOf(bucket: Bucket, severity: LogSeverity): number {
+  return severity === "warning" ? bucket.warnings : bucket.errors;
+}
+
+/**
+ * Average per minute of the BASELINE_MINUTES before the current one.
+ */
+function baseline(severity: LogSeverity, current: number): number {
+  const from = current - BASELINE_MINUTES * MINUTE_MS;
+  const sum = buckets
+    .filter((bucket) => bucket.start >= from && bucket.start < current)
+    .reduce((total, bucket) => total + countOf(bucket, severity), 0);
+  return sum / BASELINE_MINUTES;
+}
+
+function checkSpike(
+  severity: LogSeverity,
+  bucket: Bucket,
+  line: BackendLogLine,
+  now: number
+): void {
+  const perMinute = countOf(bucket, severity);
+  const baselinePerMinute = baseline(severity, bucket.start);
+  if (perMinute < Math.max(SPIKE_MIN[severity], baselinePerMinute * SPIKE_FACTOR)) return;
+  if (now - lastSpikeAt[severity] < SPIKE_COOLDOWN_MS) return;
+
+  lastSpikeAt[severity] = now;
+  const spike: BackendErrorSpike = {
+    severity,
+    perMinute,
+    baselinePerMinute: Math.round(baselinePerMinute * 100) / 100,
+    sample: line.text,
+  };
+  lastSpike = { ...spike, at: new Date(now).toISOString() };
+  log.warn(
+    `📈 Backend ${severity}s jumped to ${perMinute}/min ` +
+      `(baseline ${spike.baselinePerMinute}/min): ${line.text}`
+  );
+  emitEvent("backend:error-spike", spike);
+}
+
+/**
+ * Count one captured backend line.
+ */
+export function recordBackendLogLine(line: BackendLogLine, now: number = Date.now()): void {
+  if (line.level !== "warning" && line.level !== "error") return;
+  prune(now);
+
+  const start = minuteStart(now);
+  let bucket = buckets[buckets.length - 1];
+  if (!bucket || bucket.start !== start) {
+    bucket = { start, warnings: 0, errors: 0 };
+    buckets.push(bucket);
+  }
+  if (line.level === "warning") {
+    bucket.warnings++;
+    totalWarnings++;
+  } else {
+    bucket.errors++;
+    totalErrors++;
+  }
+  checkSpike(line.level, bucket, line, now);
+}
+
+/**
+ * Warning/error rates of the last hour.
+ */
+export function getBackendLogMetrics(now: number = Date.now()): BackendLogMetrics {
+  prune(now);
+  const current = minuteStart(now);
+  const perMinute: LogRateBucket[] = [];
+  for (let index = WINDOW_MINUTES - 1; index >= 0; index--) {
+    const start = current - index * MINUTE_MS;
+    const bucket = buckets.find((entry) => entry.start === start);
+    perMinute.push({
+      minute: new Date(start).toISOString(),
+      warnings: bucket?.warnings ?? 0,
+      errors: bucket?.errors ?? 0,
+    });
+  }
+
+  const complete = perMinute.slice(0, -1);
+  const average = (pick: (bucket: LogRateBucket) => number): number => {
+    const sum = complete.reduce((total, bucket) => total + pick(bucket), 0);
+    return Math.round((sum / complete.length) * 100) / 100;
+  };
+  return {
+    perMinute,
+    averageWarningsPerMinute: average((bucket) => bucket.warnings),
+    averageErrorsPerMinute: average((bucket) => bucket.errors),
+    totalWarnings,
+    totalErrors,
+    lastSpike,
+  };
+}
+
+/** Health samples are kept for 24 hours. */
+const MAX_WINDOW_SECS = 24 * 60 * 60;
+
+/**
+ * Current backend metrics.
+ *
+ * @param windowSecs Time window of the health aggregates (default: 1 hour)
+ */
+export function getBackendMetrics(
+  windowSecs: number = DEFAULT_METRICS_WINDOW_SECS
+): BackendMetrics {
+  if (!Number.isFinite(windowSecs) || windowSecs <= 0) {
+    throw new AppError("invalid_input", `Invalid metrics window: ${windowSecs}`);
+  }
+  return {
+    logs: getBackendLogMetrics(),
+    health: getHealthMetrics(Math.min(windowSecs, MAX_WINDOW_SECS)),
+    checkedAt: new Date().toISOString(),
+  };
+}
+
+/**
+ * Start counting warnings and errors in the backend output.
+ */
+export function initBackendMetrics(): void {
+  onBackendLogLine((line) => recordBackendLogLine(line));
+}
+
+/**
+ * Register IPC handlers for backend metrics.
+ */
+export function registerMetricsHandlers(): void {
+  handle(
+    "get-backend-metrics",
+    (_event, windowSecs?: number) => getBackendMetrics(windowSecs),
+    "read"
+  );
+}
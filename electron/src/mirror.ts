@@ -0,0 +1,244 @@
+/**
+ * Billino Desktop – PDF Mirror Folder
+ *
+ * Optionally copies every invoice PDF into a user-chosen folder
+ * (settings: pdfMirror) as
+ *
+ *   <folder>/<Year>/<Month>/<Customer>/<Number>.pdf
+ *
+ * so documents can be browsed in Explorer and picked up by archival or
+ * DMS tools. The PDFs themselves stay in the backend database; the folder
+ * is a copy that is kept in sync:
+ * - a full sync on start, when the mirror is enabled or the folder changes,
+ *   and every 15 minutes (restores files deleted in the folder)
+ * - an immediate update when the backend reports a generated PDF
+ * - invoices whose customer or date changed are moved to the new folder
+ *
+ * Files that Billino did not write are never touched.
+ */
+
+import { app } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { requestBackend } from "./api";
+import { onBackendLogLine } from "./console";
+import { DunningInvoice, fetchInvoices } from "./dunning";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import { getSettings, onSettingsChanged } from "./settings";
+
+export interface MirrorSyncResult {
+  folder: string;
+  written: number;
+  moved: number;
+  unchanged: number;
+  /** Invoices without a generated PDF yet. */
+  missingPdfs: number;
+  errors: string[];
+}
+
+interface MirrorState {
+  folder: string;
+  /** invoice id → path relative to the folder */
+  files: Record<string, string>;
+}
+
+const SYNC_INTERVAL_MS = 15 * 60 * 1000;
+/** Collect PDF events of a batch run into one sync. */
+const EVENT_DEBOUNCE_MS = 2_000;
+
+let syncRunning: Promise<MirrorSyncResult> | null = null;
+let pendingIds = new Set<number>();
+let debounceTimer: NodeJS.Timeout | null = null;
+
+function getStatePath(): string {
+  return path.join(app.getPath("userData"), "pdf-mirror.json");
+}
+
+function loadState(folder: string): MirrorState {
+  try {
+    const state = JSON.parse(fs.readFileSync(getStatePath(), "utf-8")) as MirrorState;
+    // Another folder: start over (old copies stay where they are)
+    if (state.folder === folder) return state;
+  } catch {
+    // First sync
+  }
+  return { folder, files: {} };
+}
+
+function saveState(state: MirrorState): void {
+  fs.writeFileSync(getStatePath(), JSON.stringify(state, null, 2), "utf-8");
+}
+
+/**
+ * Folder/file name without characters Windows does not allow.
+ */
+export function safeName(value: string): string {
+  const cleaned = value
+    .replace(/[<>:"/\\|?*\x00-\x1f]/g, "_")
+    .replace(/[. ]+$/, "")
+    .trim();
+  // CON, PRN, NUL, COM1, ... are reserved device names
+  return /^(con|prn|aux|nul|com\d|lpt\d)$/i.test(cleaned) ? `_${cleaned}` : cleaned || "_";
+}
+
+/**
+ * Path of an invoice PDF inside the mirror folder (relative).
+ */
+export function mirrorPath(invoice: DunningInvoice): string {
+  const [year, month] = invoice.date.slice(0, 7).split("-");
+  const customer = safeName(invoice.customerName ?? "Ohne Kunde");
+  const file = `${invoice.number.replace(/[^A-Za-z0-9-]+/g, "_").replace(/^_+|_+$/g, "")}.pdf`;
+  return path.join(year, month, customer, file);
+}
+
+async function fetchPdf(invoiceId: number): Promise<Buffer | null> {
+  try {
+    const record = await requestBackend<{ content: string }>(`/pdfs/by-invoice/${invoiceId}`);
+    return Buffer.from(record.content, "base64");
+  } catch (err) {
+    if ((err as { status?: number }).status === 404) return null;
+    throw err;
+  }
+}
+
+function writeAtomic(target: string, data: Buffer): void {
+  fs.mkdirSync(path.dirname(target), { recursive: true });
+  const tmp = `${target}.tmp`;
+  fs.writeFileSync(tmp, data);
+  fs.renameSync(tmp, target);
+}
+
+function removeEmptyDirs(dir: string, root: string): void {
+  while (dir.startsWith(root) && dir !== root) {
+    try {
+      fs.rmdirSync(dir);
+    } catch {
+      return;
+    }
+    dir = path.dirname(dir);
+  }
+}
+
+async function runSync(folder: string, refreshIds: Set<number>): Promise<MirrorSyncResult> {
+  const result: MirrorSyncResult = {
+    folder,
+    written: 0,
+    moved: 0,
+    unchanged: 0,
+    missingPdfs: 0,
+    errors: [],
+  };
+  const state = loadState(folder);
+  // Include invoices dated in the future
+  const invoices = await fetchInvoices("9999-12-31");
+
+  for (const invoice of invoices) {
+    const relative = mirrorPath(invoice);
+    const target = path.join(folder, relative);
+    const previous = state.files[invoice.id];
+    try {
+      if (previous && previous !== relative && fs.existsSync(path.join(folder, previous))) {
+        // Customer renamed or invoice date changed
+        fs.mkdirSync(path.dirname(target), { recursive: true });
+        fs.renameSync(path.join(folder, previous), target);
+        removeEmptyDirs(path.dirname(path.join(folder, previous)), folder);
+        state.files[invoice.id] = relative;
+        result.moved++;
+      }
+      if (fs.existsSync(target) && !refreshIds.has(invoice.id)) {
+        if (state.files[invoice.id] === relative) result.unchanged++;
+        continue;
+      }
+      // Never overwrite a file we did not create
+      if (fs.existsSync(target) && state.files[invoice.id] !== relative) {
+        result.errors.push(`${relative}: Datei existiert bereits`);
+        continue;
+      }
+      const pdf = await fetchPdf(invoice.id);
+      if (!pdf) {
+        result.missingPdfs++;
+        continue;
+      }
+      writeAtomic(target, pdf);
+      state.files[invoice.id] = relative;
+      result.written++;
+    } catch (err) {
+      result.errors.push(`${relative}: ${err instanceof Error ? err.message : err}`);
+    }
+  }
+
+  saveState(state);
+  log.info(
+    `🗂️ PDF mirror synced: ${result.written} written, ${result.moved} moved, ` +
+      `${result.unchanged} unchanged, ${result.errors.length} error(s)`
+  );
+  return result;
+}
+
+/**
+ * Bring the mirror folder up to date.
+ *
+ * @param refreshIds Invoices whose PDF is rewritten even if the file exists
+ *                   (regenerated PDFs)
+ * @throws Error if the mirror is disabled or no folder is set
+ */
+export async function syncPdfMirror(refreshIds: number[] = []): Promise<MirrorSyncResult> {
+  const { enabled, folder } = getSettings().pdfMirror;
+  if (!enabled || !folder) throw new AppError("invalid_state", "PDF mirror is not enabled");
+  if (!path.isAbsolute(folder)) {
+    throw new AppError("invalid_input", `Mirror folder must be absolute: ${folder}`);
+  }
+
+  // One sync at a time; a running sync is awaited, then we run again
+  while (syncRunning) await syncRunning.catch(() => undefined);
+  syncRunning = runSync(folder, new Set(refreshIds));
+  try {
+    return await syncRunning;
+  } finally {
+    syncRunning = null;
+  }
+}
+
+function syncInBackground(refreshIds: number[] = []): void {
+  const { enabled, folder } = getSettings().pdfMirror;
+  if (!enabled || !folder) return;
+  void syncPdfMirror(refreshIds).catch((err) => log.warn(`⚠️ PDF mirror sync failed: ${err}`));
+}
+
+/**
+ * Start keeping the mirror folder in sync (call once the backend is up).
+ */
+export function startPdfMirror(): void {
+  syncInBackground();
+  setInterval(() => syncInBackground(), SYNC_INTERVAL_MS).unref();
+
+  onBackendLogLine((line) => {
+    const match = /PDF (?:generated|created) for invoice (\d+)/.exec(line.text);
+    if (!match) return;
+    pendingIds.add(Number(match[1]));
+    if (debounceTimer) clearTimeout(debounceTimer);
+    debounceTimer = setTimeout(() => {
+      const ids = [...pendingIds];
+      pendingIds = new Set();
+      syncInBackground(ids);
+    }, EVENT_DEBOUNCE_MS);
+  });
+
+  let previous = getSettings().pdfMirror;
+  onSettingsChanged((settings) => {
+    const current = settings.pdfMirror;
+    if (current.enabled !== previous.enabled || current.folder !== previous.folder) {
+      syncInBackground();
+    }
+    previous = current;
+  });
+}
+
+/**
+ * Register IPC handlers for the PDF mirror.
+ */
+export function registerMirrorHandlers(): void {
+  handle("sync-pdf-mirror", () => syncPdfMirror());
+}
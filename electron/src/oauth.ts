@@ -0,0 +1,306 @@
+/**
+ * Billino Desktop – OAuth 2.0 for Native Apps
+ *
+ * Reusable authorization-code flow with PKCE and a loopback redirect
+ * (RFC 8252) for connecting Billino to cloud storage or banking APIs:
+ * 1. a temporary HTTP listener starts on 127.0.0.1 with a random port
+ * 2. the system browser opens the provider's login page
+ * 3. the provider redirects to http://127.0.0.1:<port>/callback?code=...
+ * 4. the code is exchanged for tokens, which are stored encrypted in the
+ *    credential store under "oauth:<provider>"
+ *
+ * Integrations register their provider once with `registerOAuthProvider()`
+ * and get a valid access token via `getAccessToken()` (refreshed when it
+ * is about to expire).
+ */
+
+import { shell } from "electron";
+import { createHash, randomBytes } from "crypto";
+import http from "http";
+import { AddressInfo } from "net";
+import log from "electron-log/main";
+import { deleteCredential, getCredential, setCredential } from "./credentials";
+import { handle } from "./ipc";
+
+export interface OAuthProvider {
+  /** Short id, used as key in the credential store (e.g. "dropbox"). */
+  id: string;
+  /** Display name for dialogs and logs. */
+  name: string;
+  authorizationUrl: string;
+  tokenUrl: string;
+  clientId: string;
+  /** Only for providers that require it even for native apps. */
+  clientSecret?: string;
+  scopes: string[];
+  /** Additional query parameters for the authorization request. */
+  extraAuthParams?: Record<string, string>;
+}
+
+export interface OAuthTokens {
+  accessToken: string;
+  refreshToken: string | null;
+  tokenType: string;
+  /** Expiry of the access token (ISO), null if the provider sent none. */
+  expiresAt: string | null;
+  scope: string | null;
+}
+
+export interface OAuthStatus {
+  providerId: string;
+  connected: boolean;
+  expiresAt: string | null;
+  scope: string | null;
+}
+
+export class OAuthError extends Error {
+  constructor(
+    message: string,
+    /** OAuth error code from the provider (e.g. "access_denied"). */
+    readonly code: string | null = null
+  ) {
+    super(message);
+    this.name = "OAuthError";
+  }
+}
+
+const AUTHORIZE_TIMEOUT_MS = 5 * 60 * 1000;
+/** Refresh tokens this long before they expire. */
+const EXPIRY_MARGIN_MS = 60_000;
+
+const providers = new Map<string, OAuthProvider>();
+
+/**
+ * Make a provider available for `authorize()` and the IPC commands.
+ */
+export function registerOAuthProvider(provider: OAuthProvider): void {
+  providers.set(provider.id, provider);
+}
+
+function getProvider(providerId: string): OAuthProvider {
+  const provider = providers.get(providerId);
+  if (!provider) throw new OAuthError(`Unknown OAuth provider: ${providerId}`);
+  return provider;
+}
+
+function base64Url(data: Buffer): string {
+  return data.toString("base64").replace(/\+/g, "-").replace(/\//g, "_").replace(/=+$/, "");
+}
+
+const RESULT_PAGE = (message: string): string =>
+  `<!DOCTYPE html><html lang="de"><head><meta charset="utf-8"><title>Billino</title></head>` +
+  `<body style="font-family: sans-serif; text-align: center; margin-top: 15vh">` +
+  `<h2>${message}</h2><p>Sie können dieses Fenster jetzt schließen.</p></body></html>`;
+
+/**
+ * Wait for the redirect on a temporary loopback listener.
+ *
+ * @returns The authorization code and the redirect URI that was used
+ */
+async function receiveAuthorizationCode(
+  buildAuthUrl: (redirectUri: string) => string,
+  state: string,
+  timeoutMs: number
+): Promise<{ code: string; redirectUri: string }> {
+  const server = http.createServer();
+  await new Promise<void>((resolve, reject) => {
+    server.once("error", reject);
+    server.listen(0, "127.0.0.1", resolve);
+  });
+  const { port } = server.address() as AddressInfo;
+  const redirectUri = `http://127.0.0.1:${port}/callback`;
+
+  try {
+    return await new Promise((resolve, reject) => {
+      const timer = setTimeout(
+        () => reject(new OAuthError("Anmeldung nicht innerhalb der Zeit abgeschlossen")),
+        timeoutMs
+      );
+      server.on("request", (request, response) => {
+        const url = new URL(request.url ?? "/", redirectUri);
+        if (url.pathname !== "/callback") {
+          response.writeHead(404).end();
+          return;
+        }
+        const error = url.searchParams.get("error");
+        const code = url.searchParams.get("code");
+        const ok = !error && code && url.searchParams.get("state") === state;
+        response
+          .writeHead(ok ? 200 : 400, { "Content-Type": "text/html; charset=utf-8" })
+          .end(RESULT_PAGE(ok ? "Billino ist verbunden." : "Anmeldung fehlgeschlagen."));
+
+        clearTimeout(timer);
+        if (error) {
+          const description = url.searchParams.get("error_description");
+          reject(new OAuthError(description ?? error, error));
+        } else if (!ok) {
+          // Missing code or foreign state: possibly a forged redirect
+          reject(new OAuthError("Ungültige Antwort des Anbieters (state)"));
+        } else {
+          resolve({ code: code as string, redirectUri });
+        }
+      });
+
+      void shell.openExternal(buildAuthUrl(redirectUri)).catch(reject);
+    });
+  } finally {
+    server.close();
+  }
+}
+
+async function requestTokens(
+  provider: OAuthProvider,
+  params: Record<string, string>
+): Promise<OAuthTokens> {
+  const body = new URLSearchParams({ client_id: provider.clientId, ...params });
+  if (provider.clientSecret) body.set("client_secret", provider.clientSecret);
+
+  const response = await fetch(provider.tokenUrl, {
+    method: "POST",
+    headers: {
+      "Content-Type": "application/x-www-form-urlencoded",
+      Accept: "application/json",
+    },
+    body,
+    signal: AbortSignal.timeout(30_000),
+  });
+  const data = (await response.json().catch(() => ({}))) as Record<string, unknown>;
+  if (!response.ok || typeof data.access_token !== "string") {
+    throw new OAuthError(
+      String(data.error_description ?? data.error ?? `Token request failed (${response.status})`),
+      typeof data.error === "string" ? data.error : null
+    );
+  }
+
+  const expiresIn = Number(data.expires_in);
+  return {
+    accessToken: data.access_token,
+    refreshToken: typeof data.refresh_token === "string" ? data.refresh_token : null,
+    tokenType: typeof data.token_type === "string" ? data.token_type : "Bearer",
+    expiresAt: Number.isFinite(expiresIn)
+      ? new Date(Date.now() + expiresIn * 1000).toISOString()
+      : null,
+    scope: typeof data.scope === "string" ? data.scope : null,
+  };
+}
+
+function loadTokens(providerId: string): OAuthTokens | null {
+  const stored = getCredential(`oauth:${providerId}`);
+  if (!stored) return null;
+  try {
+    return JSON.parse(stored) as OAuthTokens;
+  } catch {
+    return null;
+  }
+}
+
+function saveTokens(providerId: string, tokens: OAuthTokens): void {
+  setCredential(`oauth:${providerId}`, JSON.stringify(tokens));
+}
+
+/**
+ * Run the interactive login in the system browser and store the tokens.
+ *
+ * @throws OAuthError if the user denies access, the state does not match,
+ *         the login times out or the token exchange fails
+ */
+export async function authorize(
+  providerId: string,
+  timeoutMs = AUTHORIZE_TIMEOUT_MS
+): Promise<OAuthStatus> {
+  const provider = getProvider(providerId);
+  const state = base64Url(randomBytes(16));
+  const verifier = base64Url(randomBytes(32));
+  const challenge = base64Url(createHash("sha256").update(verifier).digest());
+
+  log.info(`🔐 OAuth login started: ${provider.name}`);
+  const { code, redirectUri } = await receiveAuthorizationCode(
+    (redirect) => {
+      const url = new URL(provider.authorizationUrl);
+      const params = {
+        response_type: "code",
+        client_id: provider.clientId,
+        redirect_uri: redirect,
+        scope: provider.scopes.join(" "),
+        state,
+        code_challenge: challenge,
+        code_challenge_method: "S256",
+        ...provider.extraAuthParams,
+      };
+      for (const [key, value] of Object.entries(params)) url.searchParams.set(key, value);
+      return url.toString();
+    },
+    state,
+    timeoutMs
+  );
+
+  const tokens = await requestTokens(provider, {
+    grant_type: "authorization_code",
+    code,
+    redirect_uri: redirectUri,
+    code_verifier: verifier,
+  });
+  saveTokens(providerId, tokens);
+  log.info(`🔐 OAuth connected: ${provider.name}`);
+  return getOAuthStatus(providerId);
+}
+
+/**
+ * A valid access token, refreshed if it expires within the next minute.
+ *
+ * @throws OAuthError if not connected or the refresh was rejected (the
+ *         user has to log in again)
+ */
+export async function getAccessToken(providerId: string): Promise<string> {
+  const provider = getProvider(providerId);
+  const tokens = loadTokens(providerId);
+  if (!tokens) throw new OAuthError(`${provider.name} ist nicht verbunden`, "not_connected");
+
+  const expiresAt = tokens.expiresAt ? Date.parse(tokens.expiresAt) : Infinity;
+  if (expiresAt - EXPIRY_MARGIN_MS > Date.now()) return tokens.accessToken;
+  if (!tokens.refreshToken) {
+    throw new OAuthError(`${provider.name}: Anmeldung abgelaufen`, "expired");
+  }
+
+  const refreshed = await requestTokens(provider, {
+    grant_type: "refresh_token",
+    refresh_token: tokens.refreshToken,
+  });
+  // Providers may omit the refresh token when it stays the same
+  saveTokens(providerId, {
+    ...refreshed,
+    refreshToken: refreshed.refreshToken ?? tokens.refreshToken,
+  });
+  log.info(`🔐 OAuth token refreshed: ${provider.name}`);
+  return refreshed.accessToken;
+}
+
+/**
+ * Connection state of a provider (without any token values).
+ */
+export function getOAuthStatus(providerId: string): OAuthStatus {
+  const tokens = loadTokens(providerId);
+  return {
+    providerId,
+    connected: tokens !== null,
+    expiresAt: tokens?.expiresAt ?? null,
+    scope: tokens?.scope ?? null,
+  };
+}
+
+/**
+ * Forget the stored tokens of a provider.
+ */
+export function disconnectOAuth(providerId: string): void {
+  deleteCredential(`oauth:${providerId}`);
+  log.info(`🔐 OAuth disconnected: ${providerId}`);
+}
+
+/**
+ * Register IPC handlers for connecting integrations.
+ */
+export function registerOAuthHandlers(): void {
+  handle("oauth-connect", (_event, providerId: string) => authorize(providerId));
+  handle("oauth-status", (_event, providerId: string) => getOAuthStatus(providerId), "read");
+  handle("oauth-disconnect", (_event, providerId: string) => disconnectOAuth(providerId));
+}
@@ -0,0 +1,153 @@
+/**
+ * Billino Desktop – Long-Running Operation Tracker
+ *
+ * Keeps track of operations that must not be interrupted by closing the
+ * window (backups, restores, exports, uploads, batch printing, fiscal-year
+ * close, bulk jobs).
+ * Operations are registered either by the main process itself or by the
+ * renderer via IPC.
+ */
+
+import { randomUUID } from "crypto";
+import log from "electron-log/main";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+
+export type OperationKind =
+  | "backup"
+  | "restore"
+  | "export"
+  | "upload"
+  | "print"
+  | "fiscal-close"
+  | "bulk"
+  | "import";
+
+export interface ActiveOperation {
+  id: string;
+  kind: OperationKind;
+  label: string;
+  startedAt: string;
+}
+
+interface TrackedOperation extends ActiveOperation {
+  controller: AbortController;
+}
+
+/** User-facing names used in confirmation dialogs. */
+export const OPERATION_LABELS: Record<OperationKind, string> = {
+  backup: "Backup",
+  restore: "Wiederherstellung",
+  export: "Export",
+  upload: "Upload",
+  print: "Stapeldruck",
+  "fiscal-close": "Jahresabschluss",
+  bulk: "Stapelverarbeitung",
+  import: "Datenimport",
+};
+
+const operations = new Map<string, TrackedOperation>();
+let idleWaiters: Array<() => void> = [];
+
+/**
+ * Register a running operation.
+ *
+ * @returns The operation id and an AbortSignal that fires when the user
+ *          decides to abort running operations.
+ */
+export function beginOperation(
+  kind: OperationKind,
+  label: string = OPERATION_LABELS[kind]
+): { id: string; signal: AbortSignal } {
+  const id = randomUUID();
+  const controller = new AbortController();
+  operations.set(id, {
+    id,
+    kind,
+    label,
+    startedAt: new Date().toISOString(),
+    controller,
+  });
+  log.info(`⏳ Operation started: ${kind} (${label}) [${id}]`);
+  return { id, signal: controller.signal };
+}
+
+/**
+ * Mark an operation as finished (successfully, failed or aborted).
+ */
+export function endOperation(id: string): void {
+  const operation = operations.get(id);
+  if (!operation) return;
+
+  operations.delete(id);
+  log.info(`✅ Operation finished: ${operation.kind} (${operation.label}) [${id}]`);
+
+  if (operations.size === 0) {
+    const waiters = idleWaiters;
+    idleWaiters = [];
+    waiters.forEach((resolve) => resolve());
+  }
+}
+
+/**
+ * List all operations that are currently running.
+ */
+export function listActiveOperations(): ActiveOperation[] {
+  return Array.from(operations.values()).map(({ controller: _controller, ...op }) => op);
+}
+
+/**
+ * Resolve once no operation is running anymore.
+ */
+export function waitForOperations(): Promise<void> {
+  if (operations.size === 0) return Promise.resolve();
+  return new Promise((resolve) => idleWaiters.push(resolve));
+}
+
+/**
+ * Abort a single operation (e.g. a transfer cancelled by the user).
+ *
+ * @returns false if no operation with this id is running
+ */
+export function abortOperation(id: string): boolean {
+  const operation = operations.get(id);
+  if (!operation) return false;
+
+  log.warn(`⚠️ Aborting operation: ${operation.kind} (${operation.label}) [${id}]`);
+  operation.controller.abort();
+  emitEvent("operation:aborted", { id });
+  endOperation(id);
+  return true;
+}
+
+/**
+ * Abort all running operations.
+ *
+ * Main-process operations observe their AbortSignal; renderer-owned
+ * operations are notified via the `operation:aborted` event.
+ */
+export function abortAllOperations(): void {
+  for (const operation of operations.values()) {
+    log.warn(`⚠️ Aborting operation: ${operation.kind} (${operation.label}) [${operation.id}]`);
+    operation.controller.abort();
+    emitEvent("operation:aborted", { id: operation.id });
+  }
+
+  for (const id of Array.from(operations.keys())) {
+    endOperation(id);
+  }
+}
+
+/**
+ * Register IPC handlers so the renderer can announce its own operations.
+ */
+export function registerOperationHandlers(): void {
+  handle("begin-operation", (_event, kind: OperationKind, label?: string) => {
+    if (!(kind in OPERATION_LABELS)) {
+      throw new Error(`Unknown operation kind: ${kind}`);
+    }
+    return beginOperation(kind, label).id;
+  });
+  handle("end-operation", (_event, id: string) => endOperation(id));
+  handle("list-operations", () => listActiveOperations(), "read");
+}
@@ -0,0 +1,221 @@
+/**
+ * Billino Desktop – Stale Backends
+ *
+ * A backend left over from an earlier session (the app crashed before the
+ * backend was tied to it, or a development run) keeps the configured port,
+ * so the new backend cannot start there. At startup the shell probes
+ * `/health` on the configured port and every port a backend ran on before;
+ * a Billino backend answers with its instance id, PID and data directory.
+ * Instances this shell spawned carry an instance id it knows and are
+ * never touched.
+ *
+ * A stale backend of this app's data directory is stopped before the new
+ * one starts: POST /shutdown (it flushes and closes the database), then a
+ * kill of its PID if the port is still taken. The request carries this
+ * launch's shutdown secret, so backends of earlier launches refuse it and
+ * go straight to the kill. The PID comes from whatever answered /health,
+ * so it is only killed if it listens on that port and is a Billino backend
+ * process; otherwise the kill is refused and reported. It is not adopted –
+ * without the child process the shell would get neither its output nor
+ * its exit, and crash recovery and restarts would not work. Stale backends of other
+ * data directories are left alone; the renderer can list them with
+ * `find-stale-backends` and stop one with `kill-stale-backend`.
+ */
+
+import { app } from "electron";
+import { randomUUID } from "crypto";
+import path from "path";
+import log from "electron-log/main";
+import { backendPath } from "./api";
+import { getConfig } from "./config";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { checkBackendProcess, killProcessTree } from "./processes";
+import { getKnownBackendPorts, waitForPortRelease } from "./routing";
+import { sendShutdownRequest } from "./shutdown";
+
+export interface StaleBackend {
+  url: string;
+  port: number;
+  /** Null for backends older than the instance id. */
+  instanceId: string | null;
+  pid: number | null;
+  version: string;
+  dataDir: string | null;
+  /** Runs on this app's data directory. */
+  sameDataDir: boolean;
+  ready: boolean;
+}
+
+export interface StaleBackendStopResult {
+  port: number;
+  pid: number | null;
+  /** What ended it; null if it is still running. */
+  stoppedBy: "endpoint" | "kill" | null;
+  /** Why the PID was not killed (not verified as the backend on the port). */
+  killRefused: string | null;
+}
+
+interface HealthIdentity {
+  version?: unknown;
+  ready?: unknown;
+  db_status?: unknown;
+  instance_id?: unknown;
+  pid?: unknown;
+  data_dir?: unknown;
+}
+
+const PROBE_TIMEOUT_MS = 1_500;
+/** Time for a stale backend to shut down after the request. */
+const SHUTDOWN_WAIT_MS = 5_000;
+const KILL_WAIT_MS = 2_000;
+
+/** Instance ids of the backends this shell spawned. */
+const spawnedInstances = new Set<string>();
+
+/**
+ * A fresh instance id for a backend about to be spawned (BILLINO_INSTANCE_ID).
+ */
+export function newBackendInstanceId(): string {
+  const id = randomUUID().replace(/-/g, "");
+  spawnedInstances.add(id);
+  return id;
+}
+
+function normalizeDir(dir: string): string {
+  const resolved = path.resolve(dir);
+  return process.platform === "win32" ? resolved.toLowerCase() : resolved;
+}
+
+async function probe(host: string, port: number): Promise<StaleBackend | null> {
+  const url = `http://${host}:${port}`;
+  let health: HealthIdentity;
+  try {
+    const response = await fetch(`${url}${backendPath("GET /health")}`, {
+      signal: AbortSignal.timeout(PROBE_TIMEOUT_MS),
+    });
+    if (!response.ok) return null;
+    health = (await response.json()) as HealthIdentity;
+  } catch {
+    return null;
+  }
+
+  // Something else listens there
+  if (typeof health.version !== "string" || typeof health.db_status !== "string") return null;
+  const instanceId = typeof health.instance_id === "string" ? health.instance_id : null;
+  if (instanceId && spawnedInstances.has(instanceId)) return null;
+
+  const dataDir = typeof health.data_dir === "string" ? health.data_dir : null;
+  return {
+    url,
+    port,
+    instanceId,
+    pid: typeof health.pid === "number" ? health.pid : null,
+    version: health.version,
+    dataDir,
+    sameDataDir:
+      dataDir !== null && normalizeDir(dataDir) === normalizeDir(app.getPath("userData")),
+    ready: health.ready === true,
+  };
+}
+
+/**
+ * Billino backends not spawned by this shell, on the configured port or
+ * any port a backend ran on before.
+ */
+export async function findStaleBackends(): Promise<StaleBackend[]> {
+  const { host, port } = getConfig();
+  const ports = [...new Set([port, ...getKnownBackendPorts()])];
+  const found = await Promise.all(ports.map((candidate) => probe(host, candidate)));
+  return found.filter((backend): backend is StaleBackend => backend !== null);
+}
+
+async function stopStaleBackend(stale: StaleBackend): Promise<StaleBackendStopResult> {
+  const { host } = getConfig();
+  const result = (
+    stoppedBy: StaleBackendStopResult["stoppedBy"],
+    killRefused: string | null = null
+  ): StaleBackendStopResult => {
+    const outcome = { port: stale.port, pid: stale.pid, stoppedBy, killRefused };
+    emitEvent("backend:stale-stopped", outcome);
+    return outcome;
+  };
+
+  try {
+    const response = await sendShutdownRequest(stale.url, PROBE_TIMEOUT_MS);
+    if (!response.ok) {
+      log.info(`🔒 Stale backend on port ${stale.port} refused shutdown (HTTP ${response.status})`);
+    } else if (await waitForPortRelease(host, stale.port, SHUTDOWN_WAIT_MS)) {
+      log.info(`🛑 Stale backend on port ${stale.port} shut down`);
+      return result("endpoint");
+    }
+  } catch (err) {
+    log.warn(`⚠️ Shutdown request to stale backend on port ${stale.port} failed: ${err}`);
+  }
+
+  if (stale.pid !== null) {
+    const refused = await checkBackendProcess(stale.pid, stale.port);
+    if (refused) {
+      log.error(`❌ Not killing the stale backend on port ${stale.port}: ${refused}`);
+      return result(null, refused);
+    }
+    if (
+      (await killProcessTree(stale.pid)) &&
+      (await waitForPortRelease(host, stale.port, KILL_WAIT_MS))
+    ) {
+      log.warn(`⚠️ Stale backend ${stale.pid} on port ${stale.port} killed`);
+      return result("kill");
+    }
+  }
+  log.error(`❌ Stale backend on port ${stale.port} is still running`);
+  return result(null);
+}
+
+/**
+ * Stop the stale backend on `port`.
+ *
+ * @throws AppError if no stale Billino backend listens there
+ */
+export async function killStaleBackend(port: number): Promise<StaleBackendStopResult> {
+  const stale = (await findStaleBackends()).find((backend) => backend.port === port);
+  if (!stale) {
+    throw new AppError("not_found", `No stale backend on port ${port}`, {
+      message: "Auf diesem Port läuft kein übrig gebliebenes Billino-Backend.",
+    });
+  }
+  return stopStaleBackend(stale);
+}
+
+/**
+ * Stop stale backends of this data directory (call before the backend is
+ * spawned). Never throws.
+ */
+export async function retireStaleBackends(): Promise<void> {
+  try {
+    for (const stale of await findStaleBackends()) {
+      if (!stale.sameDataDir) {
+        log.warn(
+          `⚠️ Another Billino backend (${stale.dataDir ?? "unknown data directory"}) ` +
+            `runs on port ${stale.port} – leaving it alone`
+        );
+        continue;
+      }
+      log.warn(
+        `⚠️ Backend from an earlier session (pid ${stale.pid ?? "?"}) ` +
+          `still runs on port ${stale.port} – stopping it`
+      );
+      await stopStaleBackend(stale);
+    }
+  } catch (err) {
+    log.warn(`⚠️ Check for stale backends failed: ${err}`);
+  }
+}
+
+/**
+ * Register IPC handlers for stale backends.
+ */
+export function registerStaleBackendHandlers(): void {
+  handle("find-stale-backends", () => findStaleBackends(), "read");
+  handle("kill-stale-backend", (_event, port: number) => killStaleBackend(port), "destructive");
+}
@@ -0,0 +1,54 @@
+/**
+ * Billino Desktop – Path Canonicalization
+ *
+ * The data directory can come from the command line, a shortcut or a
+ * restored configuration, in any of these spellings:
+ *   C:\Users\Jörg\..\Jörg\Billino
+ *   \\?\C:\Users\Jörg\Billino            (long-path prefix)
+ *   \\?\UNC\nas\billino                   (long-path prefix, network share)
+ *   \\nas\billino
+ *
+ * Node's fs functions add the long-path prefix themselves when needed, but
+ * the backend receives the path as DATA_DIR and Python/SQLite compare and
+ * log it as text. The shell therefore always passes the canonical form
+ * without prefix; the backend adds it again for paths over MAX_PATH.
+ */
+
+import fs from "fs";
+import path from "path";
+
+const EXTENDED_PREFIX = "\\\\?\\";
+const UNC_PREFIX = `${EXTENDED_PREFIX}UNC\\`;
+
+/**
+ * Remove a `\\?\` or `\\?\UNC\` prefix.
+ */
+export function stripLongPathPrefix(p: string): string {
+  if (p.startsWith(UNC_PREFIX)) return `\\\\${p.slice(UNC_PREFIX.length)}`;
+  if (p.startsWith(EXTENDED_PREFIX)) return p.slice(EXTENDED_PREFIX.length);
+  return p;
+}
+
+/**
+ * True for network paths (\\server\share\...), with or without prefix.
+ */
+export function isUncPath(p: string): boolean {
+  const stripped = stripLongPathPrefix(p);
+  return stripped.startsWith("\\\\") || stripped.startsWith("//");
+}
+
+/**
+ * Absolute, normalized path without long-path prefix.
+ *
+ * Existing paths are additionally resolved via the file system (symlinks,
+ * junctions, 8.3 short names like JRGMLL~1, drive letter case).
+ */
+export function canonicalizePath(p: string): string {
+  const resolved = path.resolve(stripLongPathPrefix(p));
+  try {
+    return stripLongPathPrefix(fs.realpathSync.native(resolved));
+  } catch {
+    // Not created yet (first start) or share unreachable: keep the resolved path
+    return resolved;
+  }
+}
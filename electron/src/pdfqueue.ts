@@ -0,0 +1,214 @@
+/**
+ * Billino Desktop – Deferred PDF Generation
+ *
+ * "PDF erzeugen" goes through `generate-invoice-pdf` instead of calling the
+ * backend directly. If the backend is busy (timeout, 429/502/503/504,
+ * locked database) or briefly unreachable (restart), the request is not
+ * lost but queued:
+ * - retried one at a time with exponential backoff (5 s … 5 min)
+ * - deduplicated per invoice (clicking twice queues it once)
+ * - completion (or final failure) is reported with a `pdf-queue:completed`
+ *   event and a system notification
+ * - new PDFs are signed right away if auto-signing is on (signing.ts)
+ *
+ * A retry after a timeout may find the PDF already created by the earlier
+ * attempt; that counts as success. The queue lives in memory – after a
+ * restart the user simply generates the PDF again.
+ */
+
+import { Notification } from "electron";
+import log from "electron-log/main";
+import { BackendRequestError, requestBackend } from "./api";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { autoSignInvoicePdf } from "./signing";
+
+export type PdfRequestStatus = "created" | "exists" | "queued";
+
+export interface PdfRequestResult {
+  invoiceId: number;
+  status: PdfRequestStatus;
+  /** Position in the queue (1 = next), only for "queued". */
+  position: number | null;
+}
+
+export interface QueuedPdfInfo {
+  invoiceId: number;
+  queuedAt: string;
+  attempts: number;
+  nextAttemptAt: string;
+  lastError: string | null;
+}
+
+export interface PdfQueueCompletion {
+  invoiceId: number;
+  success: boolean;
+  attempts: number;
+  error: string | null;
+}
+
+const PDF_TIMEOUT_MS = 60_000;
+const FIRST_RETRY_MS = 5_000;
+const MAX_RETRY_MS = 5 * 60 * 1000;
+const MAX_ATTEMPTS = 8;
+const BUSY_STATUS = new Set([429, 502, 503, 504]);
+
+const queue = new Map<number, QueuedPdfInfo>();
+let timer: NodeJS.Timeout | null = null;
+let processing = false;
+
+/**
+ * Whether a failed request should be retried later.
+ */
+export function isBusyError(err: unknown): boolean {
+  if (err instanceof BackendRequestError) {
+    return BUSY_STATUS.has(err.status) || /database is locked/i.test(err.detail);
+  }
+  // AbortSignal.timeout() or the backend being down/restarting ("fetch failed")
+  const name = (err as { name?: string } | null)?.name;
+  return name === "TimeoutError" || name === "AbortError" || err instanceof TypeError;
+}
+
+function isAlreadyCreated(err: unknown): boolean {
+  return (
+    err instanceof BackendRequestError && err.status === 400 && /already exists/.test(err.detail)
+  );
+}
+
+async function createPdf(invoiceId: number): Promise<"created" | "exists"> {
+  try {
+    await requestBackend(`/pdfs/invoices/${invoiceId}`, {
+      method: "POST",
+      timeoutMs: PDF_TIMEOUT_MS,
+    });
+    await autoSignInvoicePdf(invoiceId);
+    return "created";
+  } catch (err) {
+    if (isAlreadyCreated(err)) return "exists";
+    throw err;
+  }
+}
+
+function backoffMs(attempts: number): number {
+  return Math.min(FIRST_RETRY_MS * 2 ** Math.max(attempts - 1, 0), MAX_RETRY_MS);
+}
+
+function scheduleNext(): void {
+  if (timer) clearTimeout(timer);
+  timer = null;
+  if (queue.size === 0 || processing) return;
+  const due = Math.min(...[...queue.values()].map((item) => Date.parse(item.nextAttemptAt)));
+  timer = setTimeout(() => void processQueue(), Math.max(due - Date.now(), 0));
+}
+
+function complete(item: QueuedPdfInfo, success: boolean, error: string | null): void {
+  queue.delete(item.invoiceId);
+  const completion: PdfQueueCompletion = {
+    invoiceId: item.invoiceId,
+    success,
+    attempts: item.attempts,
+    error,
+  };
+  emitEvent("pdf-queue:completed", completion);
+  if (success) {
+    log.info(`✅ Queued PDF for invoice ${item.invoiceId} created (${item.attempts} attempts)`);
+  } else {
+    log.error(`❌ Queued PDF for invoice ${item.invoiceId} failed: ${error}`);
+  }
+  if (Notification.isSupported()) {
+    new Notification({
+      title: "Billino – PDF-Erzeugung",
+      body: success
+        ? "Das PDF wurde nachträglich erzeugt."
+        : `Das PDF konnte nicht erzeugt werden: ${error}`,
+    }).show();
+  }
+}
+
+/**
+ * Try all queued PDFs whose retry time has come, one after another.
+ */
+async function processQueue(force = false): Promise<void> {
+  if (processing) return;
+  processing = true;
+  try {
+    const due = [...queue.values()].filter(
+      (item) => force || Date.parse(item.nextAttemptAt) <= Date.now()
+    );
+    for (const item of due) {
+      item.attempts++;
+      try {
+        await createPdf(item.invoiceId);
+        complete(item, true, null);
+      } catch (err) {
+        const message = err instanceof Error ? err.message : String(err);
+        if (!isBusyError(err) || item.attempts >= MAX_ATTEMPTS) {
+          complete(item, false, message);
+          continue;
+        }
+        item.lastError = message;
+        item.nextAttemptAt = new Date(Date.now() + backoffMs(item.attempts)).toISOString();
+        log.warn(
+          `⏳ PDF for invoice ${item.invoiceId} still busy (attempt ${item.attempts}), ` +
+            `next try ${item.nextAttemptAt}`
+        );
+      }
+    }
+  } finally {
+    processing = false;
+    scheduleNext();
+  }
+}
+
+function enqueue(invoiceId: number, error: string | null): number {
+  if (!queue.has(invoiceId)) {
+    const now = Date.now();
+    queue.set(invoiceId, {
+      invoiceId,
+      queuedAt: new Date(now).toISOString(),
+      // The first attempt already happened
+      attempts: 1,
+      nextAttemptAt: new Date(now + backoffMs(1)).toISOString(),
+      lastError: error,
+    });
+    log.warn(`⏳ Backend busy – PDF for invoice ${invoiceId} queued: ${error}`);
+    scheduleNext();
+  }
+  return [...queue.keys()].indexOf(invoiceId) + 1;
+}
+
+/**
+ * Create the PDF of an invoice, or queue it if the backend is busy.
+ *
+ * @throws BackendRequestError for errors a retry cannot fix (e.g. invoice
+ *         not found)
+ */
+export async function generateInvoicePdf(invoiceId: number): Promise<PdfRequestResult> {
+  if (queue.has(invoiceId)) {
+    return { invoiceId, status: "queued", position: enqueue(invoiceId, null) };
+  }
+  try {
+    return { invoiceId, status: await createPdf(invoiceId), position: null };
+  } catch (err) {
+    if (!isBusyError(err)) throw err;
+    const message = err instanceof Error ? err.message : String(err);
+    return { invoiceId, status: "queued", position: enqueue(invoiceId, message) };
+  }
+}
+
+/**
+ * PDFs waiting for a retry.
+ */
+export function getPdfQueue(): QueuedPdfInfo[] {
+  return [...queue.values()].map((item) => ({ ...item }));
+}
+
+/**
+ * Register IPC handlers for PDF generation.
+ */
+export function registerPdfQueueHandlers(): void {
+  handle("generate-invoice-pdf", (_event, invoiceId: number) => generateInvoicePdf(invoiceId));
+  handle("get-pdf-queue", () => getPdfQueue(), "read");
+  // "Jetzt erneut versuchen" – ignores the backoff
+  handle("retry-pdf-queue", () => processQueue(true));
+}
@@ -0,0 +1,185 @@
+/**
+ * Billino Desktop – billino-pdf:// Protocol
+ *
+ * Streams stored PDFs to the webview so the embedded viewer can load them
+ * by URL instead of receiving base64 strings through IPC:
+ *
+ *   billino-pdf://invoice/<invoiceId>
+ *   billino-pdf://summary/<summaryInvoiceId>
+ *   billino-pdf://pdf/<pdfId>
+ *
+ * PDFs live in the backend database; the handler fetches them once, keeps
+ * the decoded bytes in a small cache and answers HTTP range requests
+ * (`Range: bytes=…`) from it, so the viewer can load pages incrementally.
+ * No filesystem path is ever exposed to the renderer.
+ */
+
+import { CustomScheme, protocol } from "electron";
+import log from "electron-log/main";
+import { getBackendUrl, onBackendUrlChanged } from "./config";
+
+export const PDF_SCHEME = "billino-pdf";
+
+/** Privileges for protocol.registerSchemesAsPrivileged (before app ready). */
+export const PDF_SCHEME_PRIVILEGES: CustomScheme = {
+  scheme: PDF_SCHEME,
+  privileges: {
+    standard: true,
+    secure: true,
+    supportFetchAPI: true,
+    stream: true,
+  },
+};
+
+const ENDPOINTS: Record<string, string> = {
+  invoice: "/pdfs/by-invoice/",
+  summary: "/pdfs/by-summary/",
+  pdf: "/pdfs/",
+};
+
+/** Keep decoded PDFs this long so follow-up range requests hit the cache. */
+const CACHE_TTL_MS = 60_000;
+const CACHE_MAX_ENTRIES = 8;
+
+interface CachedPdf {
+  data: Buffer;
+  fetchedAt: number;
+}
+
+const cache = new Map<string, CachedPdf>();
+
+/**
+ * Thrown when a PDF can't be resolved; carries the HTTP status to answer with.
+ */
+class PdfRequestError extends Error {
+  constructor(
+    public status: number,
+    message: string
+  ) {
+    super(message);
+    this.name = "PdfRequestError";
+  }
+}
+
+/**
+ * Map a billino-pdf:// URL to the backend endpoint returning the PDF record.
+ */
+function resolveEndpoint(requestUrl: string): string {
+  const url = new URL(requestUrl);
+  const prefix = ENDPOINTS[url.hostname];
+  const id = url.pathname.replace(/^\/+|\/+$/g, "");
+
+  if (!prefix || !/^\d+$/.test(id)) {
+    throw new PdfRequestError(400, `Invalid PDF URL: ${requestUrl}`);
+  }
+  return `${getBackendUrl()}${prefix}${id}`;
+}
+
+async function loadPdf(endpoint: string): Promise<Buffer> {
+  const cached = cache.get(endpoint);
+  if (cached && Date.now() - cached.fetchedAt < CACHE_TTL_MS) {
+    return cached.data;
+  }
+
+  const response = await fetch(endpoint);
+  if (response.status === 404) {
+    throw new PdfRequestError(404, "PDF not found");
+  }
+  if (!response.ok) {
+    throw new PdfRequestError(502, `Backend returned HTTP ${response.status}`);
+  }
+
+  const record = (await response.json()) as { content?: unknown };
+  if (typeof record.content !== "string") {
+    throw new PdfRequestError(502, "Backend response contains no PDF content");
+  }
+
+  const data = Buffer.from(record.content, "base64");
+  cache.delete(endpoint);
+  cache.set(endpoint, { data, fetchedAt: Date.now() });
+  while (cache.size > CACHE_MAX_ENTRIES) {
+    cache.delete(cache.keys().next().value as string);
+  }
+  return data;
+}
+
+/**
+ * Parse a single-range `Range: bytes=start-end` header.
+ *
+ * @returns [start, end] (inclusive), null if absent, "invalid" if unsatisfiable
+ */
+export function parseRange(
+  header: string | null,
+  size: number
+): [number, number] | null | "invalid" {
+  if (!header) return null;
+
+  const match = header.match(/^bytes=(\d*)-(\d*)$/);
+  if (!match || (match[1] === "" && match[2] === "")) return "invalid";
+
+  let start: number;
+  let end: number;
+  if (match[1] === "") {
+    // Suffix range: the last N bytes
+    start = Math.max(0, size - Number(match[2]));
+    end = size - 1;
+  } else {
+    start = Number(match[1]);
+    end = match[2] === "" ? size - 1 : Math.min(Number(match[2]), size - 1);
+  }
+
+  return start > end || start >= size ? "invalid" : [start, end];
+}
+
+function pdfResponse(data: Buffer, rangeHeader: string | null): Response {
+  const headers: Record<string, string> = {
+    "Content-Type": "application/pdf",
+    "Accept-Ranges": "bytes",
+    "Cache-Control": "no-store",
+  };
+
+  const range = parseRange(rangeHeader, data.length);
+  if (range === "invalid") {
+    return new Response(null, {
+      status: 416,
+      headers: { ...headers, "Content-Range": `bytes */${data.length}` },
+    });
+  }
+  if (range === null) {
+    return new Response(data, {
+      status: 200,
+      headers: { ...headers, "Content-Length": String(data.length) },
+    });
+  }
+
+  const [start, end] = range;
+  return new Response(data.subarray(start, end + 1), {
+    status: 206,
+    headers: {
+      ...headers,
+      "Content-Range": `bytes ${start}-${end}/${data.length}`,
+      "Content-Length": String(end - start + 1),
+    },
+  });
+}
+
+/**
+ * Register the billino-pdf:// protocol handler (after app ready).
+ */
+export function registerPdfProtocol(): void {
+  protocol.handle(PDF_SCHEME, async (request) => {
+    try {
+      const data = await loadPdf(resolveEndpoint(request.url));
+      return pdfResponse(data, request.headers.get("Range"));
+    } catch (err) {
+      const status = err instanceof PdfRequestError ? err.status : 502;
+      log.warn(`⚠️ [pdf-protocol] ${status} for ${request.url}: ${err}`);
+      return new Response(String(err), { status });
+    }
+  });
+
+  // Entries are keyed by the old backend URL and would only linger
+  onBackendUrlChanged(() => cache.clear());
+
+  log.info(`✅ Custom ${PDF_SCHEME}:// protocol registered`);
+}
@@ -0,0 +1,59 @@
+/**
+ * Billino Desktop – Command Permissions per Window
+ *
+ * Every IPC command declares the access it needs:
+ * - `read`: queries without side effects – allowed for every window
+ * - `write`: changes state (settings, operations, jobs) – main window only
+ * - `destructive`: restart, restore, kill – main window only
+ *
+ * Secondary windows therefore only get the read-only subset. The check runs
+ * in the main process for every invocation, so it does not depend on the UI
+ * hiding buttons.
+ */
+
+import { WebContents } from "electron";
+import { getWindowRole, WindowRole } from "./windows";
+
+export type CommandAccess = "read" | "write" | "destructive";
+
+const ROLE_ACCESS: Record<WindowRole, readonly CommandAccess[]> = {
+  main: ["read", "write", "destructive"],
+  secondary: ["read"],
+};
+
+/**
+ * Thrown when a window invokes a command outside its permissions.
+ */
+export class PermissionDeniedError extends Error {
+  constructor(
+    public channel: string,
+    public access: CommandAccess,
+    public role: WindowRole | null
+  ) {
+    super(`Command "${channel}" (${access}) is not allowed for ${role ?? "unregistered"} windows`);
+    this.name = "PermissionDeniedError";
+  }
+}
+
+/**
+ * Whether a window with the given role may use commands of this access level.
+ */
+export function isAccessAllowed(role: WindowRole | null, access: CommandAccess): boolean {
+  return role !== null && ROLE_ACCESS[role].includes(access);
+}
+
+/**
+ * Ensure the sender of a command may invoke it.
+ *
+ * @throws PermissionDeniedError if the sender's window role lacks the access
+ */
+export function assertCommandAllowed(
+  channel: string,
+  access: CommandAccess,
+  sender: WebContents
+): void {
+  const role = getWindowRole(sender);
+  if (!isAccessAllowed(role, access)) {
+    throw new PermissionDeniedError(channel, access, role);
+  }
+}
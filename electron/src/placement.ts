@@ -0,0 +1,195 @@
+/**
+ * Billino Desktop – Window State
+ *
+ * Remembers size, position and maximized state of the main window in
+ * AppData/Roaming/Billino/window-state.json and restores them on start.
+ *
+ * The saved position is only trusted if it is still visible: a laptop that
+ * was docked to a second monitor, a changed display arrangement or a new
+ * DPI scaling would otherwise open the window off-screen or larger than the
+ * screen. In that case the window is moved onto the nearest display and
+ * scaled down to fit its work area. The same check runs when a display is
+ * removed while Billino is open.
+ */
+
+import { app, BrowserWindow, Display, Rectangle, screen } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+
+interface WindowState {
+  bounds: Rectangle;
+  isMaximized: boolean;
+  /** Display the window was on when saved. */
+  displayId: number;
+  scaleFactor: number;
+}
+
+/** Part of the title bar that must be on screen so the window can be grabbed. */
+const MIN_VISIBLE_WIDTH = 200;
+const MIN_VISIBLE_HEIGHT = 50;
+
+const SAVE_DELAY_MS = 500;
+
+function getStatePath(): string {
+  return path.join(app.getPath("userData"), "window-state.json");
+}
+
+function loadState(): WindowState | null {
+  try {
+    const state = JSON.parse(fs.readFileSync(getStatePath(), "utf-8")) as WindowState;
+    const { x, y, width, height } = state.bounds ?? {};
+    return [x, y, width, height].every(Number.isFinite) ? state : null;
+  } catch {
+    return null;
+  }
+}
+
+function saveState(window: BrowserWindow): void {
+  if (window.isDestroyed() || window.isMinimized()) return;
+  const bounds = window.getNormalBounds();
+  const display = screen.getDisplayMatching(bounds);
+  const state: WindowState = {
+    bounds,
+    isMaximized: window.isMaximized(),
+    displayId: display.id,
+    scaleFactor: display.scaleFactor,
+  };
+  try {
+    fs.writeFileSync(getStatePath(), JSON.stringify(state, null, 2), "utf-8");
+  } catch (err) {
+    log.warn(`⚠️ Could not save window state: ${err}`);
+  }
+}
+
+function intersection(a: Rectangle, b: Rectangle): { width: number; height: number } {
+  return {
+    width: Math.min(a.x + a.width, b.x + b.width) - Math.max(a.x, b.x),
+    height: Math.min(a.y + a.height, b.y + b.height) - Math.max(a.y, b.y),
+  };
+}
+
+/**
+ * True if enough of the title bar lies on some display to move the window.
+ */
+function isReachable(bounds: Rectangle, displays: Display[]): boolean {
+  const titleBar = { ...bounds, height: MIN_VISIBLE_HEIGHT };
+  return displays.some((display) => {
+    const visible = intersection(titleBar, display.workArea);
+    return visible.width >= MIN_VISIBLE_WIDTH && visible.height >= MIN_VISIBLE_HEIGHT;
+  });
+}
+
+/**
+ * Fit bounds into a work area: shrink (keeping the aspect ratio) if too
+ * large, then move inside. Windows that had to be moved are centered.
+ */
+function fitIntoWorkArea(bounds: Rectangle, workArea: Rectangle, center: boolean): Rectangle {
+  const scale = Math.min(1, workArea.width / bounds.width, workArea.height / bounds.height);
+  const width = Math.round(bounds.width * scale);
+  const height = Math.round(bounds.height * scale);
+  const maxX = workArea.x + workArea.width - width;
+  const maxY = workArea.y + workArea.height - height;
+  return {
+    x: center
+      ? workArea.x + Math.round((workArea.width - width) / 2)
+      : Math.min(Math.max(bounds.x, workArea.x), maxX),
+    y: center
+      ? workArea.y + Math.round((workArea.height - height) / 2)
+      : Math.min(Math.max(bounds.y, workArea.y), maxY),
+    width,
+    height,
+  };
+}
+
+/**
+ * Bounds that are guaranteed to be visible on the current display setup.
+ */
+function ensureVisible(bounds: Rectangle, savedDisplayId?: number): Rectangle {
+  const displays = screen.getAllDisplays();
+  if (isReachable(bounds, displays)) {
+    const display = screen.getDisplayMatching(bounds);
+    const fitted = fitIntoWorkArea(bounds, display.workArea, false);
+    if (fitted.width !== bounds.width || fitted.height !== bounds.height) {
+      log.info(`🪟 Window larger than display ${display.id} – resized to fit`);
+    }
+    return fitted;
+  }
+
+  // Off-screen: prefer the display it was on, else the one nearest to it
+  const target =
+    displays.find((display) => display.id === savedDisplayId) ??
+    screen.getDisplayNearestPoint({ x: bounds.x, y: bounds.y });
+  log.info(
+    `🪟 Saved window position (${bounds.x}, ${bounds.y}) is off-screen – ` +
+      `moved to display ${target.id}`
+  );
+  return fitIntoWorkArea(bounds, target.workArea, true);
+}
+
+/**
+ * Initial window bounds from the saved state.
+ *
+ * @param defaults Size to use when nothing was saved yet
+ * @returns Options for the BrowserWindow constructor and whether the
+ *          window should be maximized after creation
+ */
+export function getInitialWindowState(defaults: { width: number; height: number }): {
+  bounds: Partial<Rectangle>;
+  isMaximized: boolean;
+} {
+  const state = loadState();
+  if (!state) return { bounds: defaults, isMaximized: false };
+
+  let bounds = state.bounds;
+  const display = screen.getAllDisplays().find((d) => d.id === state.displayId);
+  if (display && state.scaleFactor && display.scaleFactor !== state.scaleFactor) {
+    // Same monitor, new DPI scaling: keep the physical size of the window
+    const ratio = state.scaleFactor / display.scaleFactor;
+    log.info(`🪟 Display scaling changed (${state.scaleFactor} → ${display.scaleFactor})`);
+    bounds = {
+      ...bounds,
+      width: Math.round(bounds.width * ratio),
+      height: Math.round(bounds.height * ratio),
+    };
+  }
+
+  return { bounds: ensureVisible(bounds, state.displayId), isMaximized: state.isMaximized };
+}
+
+/**
+ * Persist the window state on changes and keep the window on screen when
+ * displays are removed or rearranged.
+ */
+export function trackWindowState(window: BrowserWindow): void {
+  let saveTimer: NodeJS.Timeout | null = null;
+  const scheduleSave = (): void => {
+    if (saveTimer) clearTimeout(saveTimer);
+    saveTimer = setTimeout(() => saveState(window), SAVE_DELAY_MS);
+  };
+
+  const keepOnScreen = (): void => {
+    if (window.isDestroyed() || window.isMaximized() || window.isFullScreen()) return;
+    const bounds = window.getBounds();
+    const visible = ensureVisible(bounds);
+    if (JSON.stringify(visible) !== JSON.stringify(bounds)) {
+      window.setBounds(visible);
+    }
+  };
+
+  window.on("resize", scheduleSave);
+  window.on("move", scheduleSave);
+  window.on("maximize", scheduleSave);
+  window.on("unmaximize", scheduleSave);
+  window.on("close", () => {
+    if (saveTimer) clearTimeout(saveTimer);
+    saveState(window);
+  });
+
+  screen.on("display-removed", keepOnScreen);
+  screen.on("display-metrics-changed", keepOnScreen);
+  window.on("closed", () => {
+    screen.removeListener("display-removed", keepOnScreen);
+    screen.removeListener("display-metrics-changed", keepOnScreen);
+  });
+}
@@ -3,9 +3,129 @@
  *
  * Exposes a minimal, safe API to the renderer process via contextBridge.
  * This keeps nodeIntegration disabled while providing necessary desktop features.
+ * Failed commands reject with an AppErrorPayload ({ code, message, detail, hint }).
  */
 
 import { contextBridge, ipcRenderer } from "electron";
+import type { AnonymizedDbExport, CrashDumpInfo, DiagnosticsInfo } from "./diagnostics";
+import type { CrashReportInfo } from "./crashreport";
+import type { AppCondition } from "./condition";
+import type { AppEvent, EventChannel, JournalEntry } from "./events";
+import type { ActiveOperation, OperationKind } from "./operations";
+import type { AutomationHook, AutomationSettings, SettingsPatch, ShellSettings } from "./settings";
+import type { PowerState } from "./jobs";
+import type { StartupTimings } from "./timings";
+import type { HealthCacheOptions, HealthCheckResult } from "./health";
+import type { BackendUrlChange, ConfigMigrationResult, EffectiveConfigEntry } from "./config";
+import type { SessionRecordingStatus } from "./session";
+import type { RequestReplayReport } from "./replay";
+import type { StaleBackend, StaleBackendStopResult } from "./orphans";
+import type { TransferResult } from "./transfers";
+import type { LogoInfo } from "./logo";
+import type { DictionaryInfo, DictionaryLanguage, SpellingIssue } from "./spellcheck";
+import type {
+  Customer,
+  CustomerPage,
+  CustomerQuery,
+  Invoice,
+  NewCustomer,
+  NewInvoice,
+  NumberFormatTestResult,
+} from "./billing";
+import type { FiscalCloseReport } from "./fiscal";
+import type { DunningCandidate, DunningOptions } from "./dunning";
+import type { VatCategory, VatRateEntry, VatRateLookup } from "./vat";
+import type { ThresholdStatus } from "./thresholds";
+import type { MonthlySummary } from "./reports";
+import type { FxCacheInfo, FxConversion } from "./fx";
+import type { GermanState, Holiday } from "./holidays";
+import type { BackupInspection, RestoreOptions, RestoreResult } from "./backups";
+import type { CheckpointMode, CheckpointResult, DbStats, JournalMode } from "./database";
+import type { BackendLogLine, BackendLogQuery } from "./console";
+import type { BackendMetrics } from "./metrics";
+import type { BackendResources } from "./resources";
+import type { TimeSyncStatus } from "./timesync";
+import type { ProbeKind, ProbeResult } from "./probes";
+import type { CredentialService } from "./credentials";
+import type { EmailRequest, OutboxEntry, SendEmailResult, TestEmailResult } from "./email";
+import type { OAuthStatus } from "./oauth";
+import type { IcsExportResult, IcsRange } from "./ics";
+import type { HookRunResult } from "./hooks";
+import type { MirrorSyncResult } from "./mirror";
+import type { BulkJobResult, BulkOptions, BulkProgress } from "./bulk";
+import type { PdfRequestResult, QueuedPdfInfo } from "./pdfqueue";
+import type { BackendTuningInfo } from "./tuning";
+import type { BlueGreenResult, RestartResult } from "./routing";
+import type { ApiCheckResult } from "./apicheck";
+import type { TraceExport, TraceFormat } from "./tracing";
+import type { AppErrorPayload } from "./errors";
+import type { BackendResponseInfo } from "./api";
+import type { AccessibilityPrefs } from "./accessibility";
+import type { LocalBackup } from "./safemode";
+import type { LaunchHistory } from "./crashloop";
+import type {
+  MaintenanceAction,
+  MaintenanceActionInfo,
+  MaintenanceActionOptions,
+  MaintenanceActionResult,
+  MaintenanceStatus,
+  MaintenanceSummary,
+} from "./maintenance";
+import type { RetentionReport } from "./retention";
+import type { BackendStatus } from "./backendmanager";
+import type { SignedPdfResult } from "./signing";
+import type { QueuedTask } from "./tasks";
+import type { InvoiceAttachment } from "./attachments";
+import type { CustomerStatement, StatementRange } from "./statements";
+import type { ImportFormat, ImportOptions, ImportResult } from "./importers";
+
+/** Same as APP_ERROR_PREFIX in errors.ts (sandboxed preload cannot import it). */
+const APP_ERROR_PREFIX = "AppError:";
+
+/**
+ * Recover the AppError payload from a failed invoke. Electron prefixes the
+ * message ("Error invoking remote method 'x': Error: AppError:{...}").
+ */
+function toErrorPayload(err: unknown): AppErrorPayload {
+  const message = err instanceof Error ? err.message : String(err);
+  const index = message.indexOf(APP_ERROR_PREFIX);
+  if (index !== -1) {
+    try {
+      return JSON.parse(message.slice(index + APP_ERROR_PREFIX.length)) as AppErrorPayload;
+    } catch {
+      // Fall through to the generic payload
+    }
+  }
+  return {
+    code: "internal",
+    message: "Ein unerwarteter Fehler ist aufgetreten.",
+    detail: message,
+    hint: null,
+  };
+}
+
+/**
+ * ipcRenderer.invoke() that rejects with an AppErrorPayload (a plain object,
+ * so code and hint survive the context bridge).
+ */
+async function invoke(channel: string, ...args: unknown[]): Promise<any> {
+  try {
+    return await ipcRenderer.invoke(channel, ...args);
+  } catch (err) {
+    throw toErrorPayload(err);
+  }
+}
+
+/**
+ * ipcRenderer.on() for a main-process event; the callback gets the typed
+ * payload with `seq` and `emittedAt`.
+ */
+function subscribe<C extends EventChannel>(
+  channel: C,
+  callback: (event: AppEvent<C>) => void
+): void {
+  ipcRenderer.on(channel, (_event, payload: AppEvent<C>) => callback(payload));
+}
 
 contextBridge.exposeInMainWorld("billino", {
   /**
@@ -13,6 +133,14 @@ contextBridge.exposeInMainWorld("billino", {
    */
   platform: process.platform,
 
+  /**
+   * Send a request to the backend (relative path, e.g. "/invoices/").
+   * Resolves with status and parsed body also for HTTP errors; rejects only
+   * if the request is invalid or the backend is unreachable.
+   */
+  apiRequest: (method: string, path: string, body?: unknown): Promise<BackendResponseInfo> =>
+    invoke("api-request", method, path, body),
+
   /**
    * Check if the app is running as a packaged Electron build.
    */
@@ -21,5 +149,896 @@ contextBridge.exposeInMainWorld("billino", {
   /**
    * Get app version from package.json.
    */
-  getVersion: (): Promise<string> => ipcRenderer.invoke("get-version"),
+  getVersion: (): Promise<string> => invoke("get-version"),
+
+  /**
+   * Get diagnostics info (version, paths, crash dumps) for bug reports.
+   */
+  getDiagnostics: (): Promise<DiagnosticsInfo> => invoke("get-diagnostics"),
+
+  /**
+   * List native crash dumps stored in the data directory.
+   */
+  listCrashDumps: (): Promise<CrashDumpInfo[]> => invoke("list-crash-dumps"),
+
+  /**
+   * Bundle backend exit, log tail, config, OS info and recent health checks
+   * into a ZIP for a support ticket. Resolves with its path.
+   */
+  createCrashReport: (): Promise<CrashReportInfo> => invoke("create-crash-report"),
+
+  /**
+   * Backend, queue, disk and update status merged into one prioritized
+   * condition for the status banner.
+   */
+  getAppCondition: (): Promise<AppCondition> => invoke("get-app-condition"),
+
+  /**
+   * Called when the app condition changes (other issues or levels).
+   */
+  onAppConditionChanged: (callback: (condition: AppEvent<"app:condition">) => void): void =>
+    subscribe("app:condition", callback),
+
+  /**
+   * The last backend lifecycle events (state changes, crashes, restarts),
+   * optionally only those after a `seq`.
+   */
+  getEventHistory: (sinceSeq?: number): Promise<JournalEntry[]> =>
+    invoke("get-event-history", sinceSeq),
+
+  /**
+   * Resend the lifecycle events after `sinceSeq` to this window's
+   * subscribers, e.g. right after a window opened. Resolves with the count.
+   */
+  replayEvents: (sinceSeq?: number): Promise<number> => invoke("replay-events", sinceSeq),
+
+  /**
+   * Save an anonymized copy of the database for a bug report (destination
+   * chosen in a save dialog; null if the user cancels it).
+   */
+  exportAnonymizedDb: (): Promise<AnonymizedDbExport | null> => invoke("export-anonymized-db"),
+
+  /**
+   * Announce a long-running operation (backup, export, batch print) so that
+   * closing the window asks for confirmation while it runs.
+   */
+  beginOperation: (kind: OperationKind, label?: string): Promise<string> =>
+    invoke("begin-operation", kind, label),
+
+  /**
+   * Mark an announced operation as finished.
+   */
+  endOperation: (id: string): Promise<void> => invoke("end-operation", id),
+
+  /**
+   * List operations that are currently running.
+   */
+  listOperations: (): Promise<ActiveOperation[]> => invoke("list-operations"),
+
+  /**
+   * Subscribe to aborts of announced operations (user chose "abort and quit").
+   */
+  onOperationAborted: (callback: (id: string) => void): void => {
+    ipcRenderer.on("operation:aborted", (_event, payload: { id: string }) => callback(payload.id));
+  },
+
+  /**
+   * Read the desktop-shell settings.
+   */
+  getSettings: (): Promise<ShellSettings> => invoke("get-settings"),
+
+  /**
+   * Update desktop-shell settings (merged per section). The `automation`
+   * section is rejected – use saveAutomationHook / removeAutomationHook.
+   */
+  updateSettings: (patch: SettingsPatch): Promise<ShellSettings> =>
+    invoke("update-settings", patch),
+
+  /**
+   * Get power source and heavy jobs deferred while on battery.
+   */
+  getPowerState: (): Promise<PowerState> => invoke("get-power-state"),
+
+  /**
+   * Run all deferred heavy jobs now, regardless of power source.
+   */
+  runDeferredJobs: (): Promise<number> => invoke("run-deferred-jobs"),
+
+  /**
+   * Get durations of the individual startup phases.
+   */
+  getStartupTimings: (): Promise<StartupTimings> => invoke("get-startup-timings"),
+
+  /**
+   * Health of the backend (status, DB, latency), or a typed error if it is
+   * unreachable. Results up to 5 s old are reused unless `forceRefresh` is
+   * set.
+   */
+  getBackendHealth: (options?: HealthCacheOptions): Promise<HealthCheckResult> =>
+    invoke("get-backend-health", options),
+
+  /**
+   * Get the effective backend configuration and the layer each value came
+   * from (default, config.toml, .env, env, cli).
+   */
+  getEffectiveConfig: (): Promise<EffectiveConfigEntry[]> => invoke("get-effective-config"),
+
+  /**
+   * Move settings from a legacy .env.tauri file into config.toml (also runs
+   * automatically at startup; .env stays in use).
+   */
+  migrateLegacyConfig: (): Promise<ConfigMigrationResult> => invoke("migrate-legacy-config"),
+
+  /**
+   * Effective backend URL (the port may differ from the configured one).
+   */
+  getBackendUrl: (): Promise<string> => invoke("get-backend-url"),
+
+  /**
+   * Billino backends left over from earlier sessions (not spawned by this app).
+   */
+  findStaleBackends: (): Promise<StaleBackend[]> => invoke("find-stale-backends"),
+
+  /**
+   * Stop a stale backend: shutdown request, then kill by PID.
+   */
+  killStaleBackend: (port: number): Promise<StaleBackendStopResult> =>
+    invoke("kill-stale-backend", port),
+
+  /**
+   * Whether a debug session is being recorded (settings: debug.recordSession)
+   * and the path of the session file.
+   */
+  getSessionRecordingStatus: (): Promise<SessionRecordingStatus> =>
+    invoke("get-session-recording-status"),
+
+  /**
+   * Re-issue the recorded backend requests of a command (by its trace id) and
+   * diff the responses. Needs debug.requestReplay; requests may change data.
+   */
+  replayRequest: (correlationId: string): Promise<RequestReplayReport> =>
+    invoke("replay-request", correlationId),
+
+  /**
+   * Stream a backend export to a file the user picks in the save dialog
+   * (`defaultName` is suggested) without buffering it in memory. Null if
+   * the dialog was cancelled. Progress arrives via onTransferProgress().
+   */
+  downloadExport: (jobId: string, defaultName?: string): Promise<TransferResult | null> =>
+    invoke("download-export", jobId, defaultName),
+
+  /**
+   * Cancel a running download/upload (false for any other id).
+   */
+  cancelTransfer: (transferId: string): Promise<boolean> => invoke("cancel-transfer", transferId),
+
+  /**
+   * Subscribe to progress of running downloads/uploads.
+   */
+  onTransferProgress: (callback: (progress: AppEvent<"transfer:progress">) => void): void =>
+    subscribe("transfer:progress", callback),
+
+  /**
+   * Stream a local file (absolute path) as multipart to an upload route
+   * (profile logo, invoice attachments). Only PNG, JPEG, WebP and PDF files
+   * are accepted (detected by content).
+   */
+  uploadFile: (filePath: string, endpoint: string): Promise<unknown> =>
+    invoke("upload-file", filePath, endpoint),
+
+  /**
+   * Validate, downscale and store a new company logo (PNG/JPEG, absolute
+   * path). Returns dimensions and a preview.
+   */
+  setCompanyLogo: (sourcePath: string): Promise<LogoInfo> => invoke("set-company-logo", sourcePath),
+
+  /**
+   * Get the current company logo, or null if none is set.
+   */
+  getCompanyLogo: (): Promise<LogoInfo | null> => invoke("get-company-logo"),
+
+  /**
+   * Remove the company logo.
+   */
+  removeCompanyLogo: (): Promise<void> => invoke("remove-company-logo"),
+
+  /**
+   * List supported spell-check dictionaries and whether they are installed.
+   */
+  listDictionaries: (): Promise<DictionaryInfo[]> => invoke("list-dictionaries"),
+
+  /**
+   * Download and install a dictionary (requires internet once).
+   */
+  installDictionary: (lang: DictionaryLanguage): Promise<DictionaryInfo> =>
+    invoke("install-dictionary", lang),
+
+  /**
+   * Remove an installed dictionary.
+   */
+  removeDictionary: (lang: DictionaryLanguage): Promise<void> => invoke("remove-dictionary", lang),
+
+  /**
+   * Add a word to the personal dictionary.
+   */
+  addDictionaryWord: (word: string): Promise<void> => invoke("add-dictionary-word", word),
+
+  /**
+   * Find misspelled words (with suggestions) in a text.
+   */
+  checkSpelling: (text: string, lang: DictionaryLanguage): Promise<SpellingIssue[]> =>
+    invoke("check-spelling", text, lang),
+
+  /**
+   * Render and validate an invoice-number pattern (e.g. "RE-{YYYY}-{N:4}")
+   * against existing numbers before adopting it.
+   */
+  testNumberFormat: (
+    pattern: string,
+    sampleDate: string,
+    counter: number
+  ): Promise<NumberFormatTestResult> => invoke("test-number-format", pattern, sampleDate, counter),
+
+  /**
+   * One page of customers, optionally filtered by a search term.
+   */
+  listCustomers: (query?: CustomerQuery): Promise<CustomerPage> => invoke("list-customers", query),
+
+  /**
+   * Create a customer (the name must not be empty).
+   */
+  createCustomer: (customer: NewCustomer): Promise<Customer> => invoke("create-customer", customer),
+
+  /**
+   * A single invoice with positions and totals.
+   */
+  getInvoice: (invoiceId: number): Promise<Invoice> => invoke("get-invoice", invoiceId),
+
+  /**
+   * Create an invoice. It is validated first (at least one position,
+   * positive quantities and prices); errors carry a German message.
+   */
+  createInvoice: (invoice: NewInvoice): Promise<Invoice> => invoke("create-invoice", invoice),
+
+  /**
+   * Close a fiscal year in one step: final backup, year archive (with
+   * `saveArchive` saved where the user picks in the save dialog), VAT summary
+   * and invoice-number rollover check. Null if the dialog was cancelled.
+   */
+  closeFiscalYear: (year: number, saveArchive?: boolean): Promise<FiscalCloseReport | null> =>
+    invoke("close-fiscal-year", year, saveArchive),
+
+  /**
+   * Subscribe to fiscal-year close progress (one event per step).
+   */
+  onFiscalYearProgress: (callback: (progress: AppEvent<"fiscal-year:progress">) => void): void =>
+    subscribe("fiscal-year:progress", callback),
+
+  /**
+   * Invoices due for a reminder (Mahnung) under the dunning policy from the
+   * settings. Pass paid invoices via `excludeInvoiceIds`.
+   */
+  getDunningCandidates: (options?: DunningOptions): Promise<DunningCandidate[]> =>
+    invoke("get-dunning-candidates", options),
+
+  /**
+   * German VAT rate that applied on a date (YYYY-MM-DD), e.g. 0.16 for
+   * standard-rated invoices from the second half of 2020.
+   */
+  getVatRate: (date: string, category?: VatCategory): Promise<VatRateLookup> =>
+    invoke("get-vat-rate", date, category),
+
+  /**
+   * Full effective-dated VAT rate table.
+   */
+  listVatRates: (): Promise<VatRateEntry[]> => invoke("list-vat-rates"),
+
+  /**
+   * Current year's revenue against the §19 UStG (Kleinunternehmer) limits.
+   */
+  checkSmallBusinessThresholds: (year?: number): Promise<ThresholdStatus> =>
+    invoke("check-small-business-thresholds", year),
+
+  /**
+   * Subscribe to §19 threshold warnings from the periodic check.
+   */
+  onThresholdWarning: (callback: (warning: AppEvent<"threshold:warning">) => void): void =>
+    subscribe("threshold:warning", callback),
+
+  /**
+   * Revenue, invoice count and outstanding amount of a month (YYYY-MM,
+   * default: last month).
+   */
+  getMonthlySummary: (month?: string): Promise<MonthlySummary> =>
+    invoke("get-monthly-summary", month),
+
+  /**
+   * Subscribe to clicks on the monthly summary notification (show the view).
+   */
+  onMonthlySummaryOpen: (callback: (summary: AppEvent<"report:open">) => void): void =>
+    subscribe("report:open", callback),
+
+  /**
+   * Convert a foreign-currency amount to EUR with the ECB reference rate of
+   * a date (YYYY-MM-DD). Works offline with cached rates.
+   */
+  convertCurrency: (amount: number, currency: string, date?: string): Promise<FxConversion> =>
+    invoke("convert-currency", amount, currency, date),
+
+  /**
+   * Describe the offline exchange-rate cache.
+   */
+  getFxCacheInfo: (): Promise<FxCacheInfo> => invoke("get-fx-cache-info"),
+
+  /**
+   * Download current ECB rates (or the full history with `full`).
+   */
+  refreshFxRates: (full?: boolean): Promise<FxCacheInfo> => invoke("refresh-fx-rates", full),
+
+  /**
+   * Public holidays of a year (nationwide + the configured or given state).
+   */
+  listHolidays: (year: number, state?: GermanState | null): Promise<Holiday[]> =>
+    invoke("list-holidays", year, state),
+
+  /**
+   * First business day on or after a date, skipping weekends and holidays.
+   */
+  nextBusinessDay: (date: string, state?: GermanState | null): Promise<string> =>
+    invoke("next-business-day", date, state),
+
+  /**
+   * Show what a backup contains (schema version, invoices, PDFs) without
+   * restoring it.
+   */
+  inspectBackup: (backupPath: string): Promise<BackupInspection> =>
+    invoke("inspect-backup", backupPath),
+
+  /**
+   * Restore a backup: everything, only the database, or only PDFs
+   * (optionally of selected months). The current state is backed up first.
+   */
+  restoreBackup: (backupPath: string, options?: RestoreOptions): Promise<RestoreResult> =>
+    invoke("restore-backup", backupPath, options),
+
+  /**
+   * Database file statistics (rows per table, PDFs, free pages, WAL size).
+   */
+  getDbStats: (): Promise<DbStats> => invoke("get-db-stats"),
+
+  /**
+   * Copy the write-ahead log into the database file (TRUNCATE by default).
+   */
+  checkpointWal: (mode?: CheckpointMode): Promise<CheckpointResult> =>
+    invoke("checkpoint-wal", mode),
+
+  /**
+   * Switch the database journal mode ("delete" or "wal").
+   */
+  setJournalMode: (
+    mode: JournalMode
+  ): Promise<{ previousMode: JournalMode; journalMode: JournalMode }> =>
+    invoke("set-journal-mode", mode),
+
+  /**
+   * Captured backend output (developer console), filtered by level/search.
+   */
+  getBackendLog: (query?: BackendLogQuery): Promise<BackendLogLine[]> =>
+    invoke("get-backend-log", query),
+
+  /**
+   * The last `count` backend output lines (default 200), for diagnostics.
+   */
+  getBackendLogs: (count?: number): Promise<BackendLogLine[]> => invoke("get-backend-logs", count),
+
+  /**
+   * Open the logs folder (shell log and backend.log) in the file manager.
+   */
+  openLogFolder: (): Promise<void> => invoke("open-log-folder"),
+
+  /**
+   * Backend metrics: warning/error rates per minute (last hour) and health
+   * check aggregates (p50/p95 latency, failure rate) of the last
+   * `windowSecs` seconds (default 3600).
+   */
+  getBackendMetrics: (windowSecs?: number): Promise<BackendMetrics> =>
+    invoke("get-backend-metrics", windowSecs),
+
+  /**
+   * Subscribe to sudden jumps of backend warnings/errors (early crash warning).
+   */
+  onBackendErrorSpike: (callback: (spike: AppEvent<"backend:error-spike">) => void): void =>
+    subscribe("backend:error-spike", callback),
+
+  /**
+   * Memory, CPU and handle samples of the backend process.
+   */
+  getBackendResources: (): Promise<BackendResources> => invoke("get-backend-resources"),
+
+  /**
+   * Subscribe to the backend exceeding the configured memory limit.
+   */
+  onBackendResourceWarning: (
+    callback: (warning: AppEvent<"backend:resource-warning">) => void
+  ): void => subscribe("backend:resource-warning", callback),
+
+  /**
+   * Subscribe to new backend output lines (developer console window only).
+   */
+  onBackendLog: (callback: (lines: BackendLogLine[]) => void): void => {
+    ipcRenderer.on("backend:log", (_event, lines: BackendLogLine[]) => callback(lines));
+  },
+
+  /**
+   * Open or close the developer console window (also: Ctrl+Shift+L).
+   */
+  toggleDeveloperConsole: (): Promise<void> => invoke("toggle-developer-console"),
+
+  /**
+   * Check reachability of a configured service (SMTP, WebDAV, VIES) incl.
+   * latency and TLS certificate details.
+   */
+  probeService: (kind: ProbeKind): Promise<ProbeResult> => invoke("probe-service", kind),
+
+  /**
+   * Whether a password is stored for a service (the password itself is
+   * never returned to the renderer).
+   */
+  hasCredential: (service: CredentialService): Promise<boolean> =>
+    invoke("has-credential", service),
+
+  /**
+   * Store a service password encrypted in the OS keyring.
+   */
+  setCredential: (service: CredentialService, secret: string): Promise<void> =>
+    invoke("set-credential", service, secret),
+
+  /**
+   * Remove a stored service password.
+   */
+  deleteCredential: (service: CredentialService): Promise<void> =>
+    invoke("delete-credential", service),
+
+  /**
+   * Send a test e-mail with the SMTP settings; returns the SMTP
+   * conversation if it fails.
+   */
+  sendTestEmail: (to: string): Promise<TestEmailResult> => invoke("send-test-email", to),
+
+  /**
+   * Connect an integration via OAuth (opens the system browser).
+   */
+  oauthConnect: (providerId: string): Promise<OAuthStatus> => invoke("oauth-connect", providerId),
+
+  /**
+   * Connection state of an OAuth integration (no token values).
+   */
+  oauthStatus: (providerId: string): Promise<OAuthStatus> => invoke("oauth-status", providerId),
+
+  /**
+   * Remove the stored tokens of an OAuth integration.
+   */
+  oauthDisconnect: (providerId: string): Promise<void> => invoke("oauth-disconnect", providerId),
+
+  /**
+   * Export payment due dates and dunning deadlines as an .ics calendar
+   * (default path: data folder, overwritten on each export). With
+   * `chooseLocation` a save dialog asks for the destination; null if the
+   * user cancels it.
+   */
+  exportDueDatesIcs: (
+    range?: IcsRange,
+    chooseLocation?: boolean
+  ): Promise<IcsExportResult | null> => invoke("export-due-dates-ics", range, chooseLocation),
+
+  /**
+   * Run an automation hook once with example data (allowlist applies).
+   */
+  testAutomationHook: (hookId: string): Promise<HookRunResult> =>
+    invoke("test-automation-hook", hookId),
+
+  /**
+   * Add or replace an automation hook. New or changed programs and URLs are
+   * confirmed by the user in a native dialog; null if declined.
+   */
+  saveAutomationHook: (hook: AutomationHook): Promise<AutomationSettings | null> =>
+    invoke("save-automation-hook", hook),
+
+  /**
+   * Remove an automation hook.
+   */
+  removeAutomationHook: (hookId: string): Promise<AutomationSettings> =>
+    invoke("remove-automation-hook", hookId),
+
+  /**
+   * Sync the PDF mirror folder now (Year/Month/Customer/Number.pdf).
+   */
+  syncPdfMirror: (): Promise<MirrorSyncResult> => invoke("sync-pdf-mirror"),
+
+  /**
+   * Run a backend call for many ids (e.g. "regenerate-invoice-pdf") with
+   * bounded concurrency. Resolves with a result per id once finished; the
+   * job id arrives with the first `bulk:progress` event.
+   */
+  runBulk: (kind: string, ids: number[], options?: BulkOptions): Promise<BulkJobResult> =>
+    invoke("run-bulk", kind, ids, options),
+
+  /**
+   * Pause a bulk job (running calls finish, no new ones start).
+   */
+  pauseBulk: (jobId: string): Promise<void> => invoke("pause-bulk", jobId),
+
+  /**
+   * Continue a paused bulk job.
+   */
+  resumeBulk: (jobId: string): Promise<void> => invoke("resume-bulk", jobId),
+
+  /**
+   * Cancel a bulk job; running calls are aborted.
+   */
+  cancelBulk: (jobId: string): Promise<boolean> => invoke("cancel-bulk", jobId),
+
+  /**
+   * Counters of all running bulk jobs.
+   */
+  listBulkJobs: (): Promise<BulkProgress[]> => invoke("list-bulk-jobs"),
+
+  /**
+   * Subscribe to bulk job progress (throttled, plus pause/resume/finish).
+   */
+  onBulkProgress: (callback: (progress: AppEvent<"bulk:progress">) => void): void =>
+    subscribe("bulk:progress", callback),
+
+  /**
+   * Create an invoice PDF; queued for retry if the backend is busy.
+   */
+  generateInvoicePdf: (invoiceId: number): Promise<PdfRequestResult> =>
+    invoke("generate-invoice-pdf", invoiceId),
+
+  /**
+   * PDFs waiting for a retry.
+   */
+  getPdfQueue: (): Promise<QueuedPdfInfo[]> => invoke("get-pdf-queue"),
+
+  /**
+   * Retry all queued PDFs now.
+   */
+  retryPdfQueue: (): Promise<void> => invoke("retry-pdf-queue"),
+
+  /**
+   * Subscribe to queued PDFs being created (or finally failing).
+   */
+  onPdfQueueCompleted: (callback: (completion: AppEvent<"pdf-queue:completed">) => void): void =>
+    subscribe("pdf-queue:completed", callback),
+
+  /**
+   * Backend tuning: requested and effective values plus the machine's
+   * limits (changes apply on the next backend start).
+   */
+  getBackendTuning: (): Promise<BackendTuningInfo> => invoke("get-backend-tuning"),
+
+  /**
+   * Restart the backend without downtime (second instance on another port,
+   * switch when healthy, old one stops after finishing its requests).
+   */
+  restartBackendBlueGreen: (): Promise<BlueGreenResult> => invoke("restart-backend-blue-green"),
+
+  /**
+   * Subscribe to backend URL changes (port switch after a restart). Requests
+   * to earlier URLs keep working; they are redirected.
+   */
+  onBackendUrlChanged: (callback: (url: string, previousUrl: string) => void): void => {
+    ipcRenderer.on("backend:url-changed", (_event, change: BackendUrlChange) =>
+      callback(change.url, change.previousUrl)
+    );
+  },
+
+  /**
+   * Result of the last backend API check (null before the first one).
+   */
+  getApiCheck: (): Promise<ApiCheckResult | null> => invoke("get-api-check"),
+
+  /**
+   * Check the backend's OpenAPI spec against the endpoints the shell uses.
+   */
+  checkBackendApi: (): Promise<ApiCheckResult> => invoke("check-backend-api"),
+
+  /**
+   * Subscribe to API drift reports: the backend lacks endpoints or fields
+   * the shell needs (shell and backend versions do not match).
+   */
+  onBackendApiDrift: (callback: (result: AppEvent<"backend:api-drift">) => void): void =>
+    subscribe("backend:api-drift", callback),
+
+  /**
+   * Export the recorded latency traces (commands, backend requests) to a
+   * file chosen in a save dialog; "chrome" opens in chrome://tracing or
+   * Perfetto. null if the user cancels the dialog.
+   */
+  exportTrace: (format?: TraceFormat): Promise<TraceExport | null> =>
+    invoke("export-trace", format),
+
+  /**
+   * OS accessibility settings: reduced motion, high contrast, text scale.
+   */
+  getAccessibilityPrefs: (): Promise<AccessibilityPrefs> => invoke("get-accessibility-prefs"),
+
+  /**
+   * Subscribe to changes of the OS accessibility settings.
+   */
+  onAccessibilityChanged: (callback: (prefs: AppEvent<"accessibility:changed">) => void): void =>
+    subscribe("accessibility:changed", callback),
+
+  /**
+   * Whether the shell was started with --safe-mode.
+   */
+  getSafeMode: (): Promise<boolean> => invoke("get-safe-mode"),
+
+  /**
+   * Recent launches, failed ones in a row, and whether safe mode was
+   * entered automatically because of them.
+   */
+  getLaunchHistory: (): Promise<LaunchHistory> => invoke("get-launch-history"),
+
+  /**
+   * Backup files in the data directory, newest first (no backend needed).
+   */
+  listLocalBackups: (): Promise<LocalBackup[]> => invoke("list-local-backups"),
+
+  /**
+   * Safe mode only: start the backend (without backup scheduler) and wait
+   * until it is ready.
+   */
+  startSafeModeBackend: (): Promise<void> => invoke("start-safe-mode-backend"),
+
+  /**
+   * Restart Billino, in safe mode if `safe` is true.
+   */
+  relaunchApp: (safe?: boolean): Promise<void> => invoke("relaunch-app", safe),
+
+  /**
+   * Restart the backend (stop, wait for the port, start, health check).
+   * Progress arrives via onBackendRestartProgress.
+   */
+  restartBackend: (): Promise<RestartResult> => invoke("restart-backend"),
+
+  /**
+   * Subscribe to restart phases: stopping, waiting-for-port, starting.
+   */
+  onBackendRestartProgress: (
+    callback: (progress: AppEvent<"backend:restart-progress">) => void
+  ): void => subscribe("backend:restart-progress", callback),
+
+  /**
+   * Subscribe to successful backend restarts.
+   */
+  onBackendReady: (callback: (result: AppEvent<"backend:ready">) => void): void =>
+    subscribe("backend:ready", callback),
+
+  /**
+   * Subscribe to failed backend restarts.
+   */
+  onBackendError: (callback: (error: AppEvent<"backend:error">) => void): void =>
+    subscribe("backend:error", callback),
+
+  /**
+   * Subscribe to unexpected backend exits.
+   */
+  onBackendCrashed: (callback: (crash: AppEvent<"backend:crashed">) => void): void =>
+    subscribe("backend:crashed", callback),
+
+  /**
+   * Subscribe to automatic restarts after a crash.
+   */
+  onBackendRestarting: (callback: (restart: AppEvent<"backend:restarting">) => void): void =>
+    subscribe("backend:restarting", callback),
+
+  /**
+   * Nightly maintenance: settings, whether it runs, and the last result.
+   */
+  getMaintenanceStatus: (): Promise<MaintenanceStatus> => invoke("get-maintenance-status"),
+
+  /**
+   * Run maintenance now (reindex, ANALYZE, missing PDFs, backup check).
+   */
+  runMaintenance: (): Promise<MaintenanceSummary> => invoke("run-maintenance"),
+
+  /**
+   * Result of the startup clock check against NTP (opt-in in the settings);
+   * `refresh` checks again.
+   */
+  getTimeSyncStatus: (refresh?: boolean): Promise<TimeSyncStatus> =>
+    invoke("get-time-sync-status", refresh),
+
+  /**
+   * Maintenance actions support may ask the user to run.
+   */
+  listMaintenanceActions: (): Promise<MaintenanceActionInfo[]> =>
+    invoke("list-maintenance-actions"),
+
+  /**
+   * Run one of them, e.g. ("regenerate-pdf", { invoiceId: 42 }).
+   * "recalculate-totals" only reports differences unless `confirm` is set.
+   */
+  runMaintenanceAction: (
+    action: MaintenanceAction,
+    options?: MaintenanceActionOptions
+  ): Promise<MaintenanceActionResult> => invoke("run-maintenance-action", action, options),
+
+  /**
+   * Subscribe to finished maintenance runs.
+   */
+  onMaintenanceFinished: (callback: (summary: AppEvent<"maintenance:finished">) => void): void =>
+    subscribe("maintenance:finished", callback),
+
+  /**
+   * What the retention rules would delete now (dry run, nothing is deleted).
+   */
+  previewRetentionCleanup: (): Promise<RetentionReport> => invoke("preview-retention-cleanup"),
+
+  /**
+   * Result of the last retention cleanup, null if none ran yet.
+   */
+  getLastRetentionReport: (): Promise<RetentionReport | null> =>
+    invoke("get-last-retention-report"),
+
+  /**
+   * Delete what the retention rules select now.
+   */
+  runRetentionCleanup: (): Promise<RetentionReport> => invoke("run-retention-cleanup"),
+
+  /**
+   * Backend process state and its last crash (exit code, stderr tail).
+   */
+  getBackendState: (): Promise<BackendStatus> => invoke("get-backend-state"),
+
+  /**
+   * Subscribe to backend state changes.
+   */
+  onBackendStateChanged: (callback: (status: AppEvent<"backend:state-changed">) => void): void =>
+    subscribe("backend:state-changed", callback),
+
+  /**
+   * Subscribe to the stages of a backend shutdown (request, SIGTERM, kill).
+   */
+  onBackendStopping: (callback: (stage: AppEvent<"backend:stopping">) => void): void =>
+    subscribe("backend:stopping", callback),
+
+  /**
+   * Subscribe to stopped backend instances, with the stage that ended them.
+   */
+  onBackendStopped: (callback: (report: AppEvent<"backend:stopped">) => void): void =>
+    subscribe("backend:stopped", callback),
+
+  /**
+   * Sign the stored PDF of an invoice (PAdES) with the configured certificate.
+   */
+  signInvoicePdf: (invoiceId: number): Promise<SignedPdfResult> =>
+    invoke("sign-invoice-pdf", invoiceId),
+
+  /**
+   * Whether a PKCS#12 signing certificate has been imported.
+   */
+  hasSigningCertificate: (): Promise<boolean> => invoke("has-signing-certificate"),
+
+  /**
+   * Import a .p12/.pfx certificate and its passphrase into the credential store.
+   */
+  importSigningCertificate: (filePath: string, passphrase: string): Promise<void> =>
+    invoke("import-signing-certificate", filePath, passphrase),
+
+  /**
+   * Remove the signing certificate and its passphrase/PIN.
+   */
+  removeSigningCertificate: (): Promise<void> => invoke("remove-signing-certificate"),
+
+  /**
+   * Subscribe to signed invoice PDFs.
+   */
+  onPdfSigned: (callback: (result: AppEvent<"pdf:signed">) => void): void =>
+    subscribe("pdf:signed", callback),
+
+  /**
+   * Queued one-off tasks that have not completed (pending, running, failed).
+   */
+  listPendingTasks: (): Promise<QueuedTask[]> => invoke("list-pending-tasks"),
+
+  /**
+   * Remove a queued task.
+   */
+  cancelTask: (id: string): Promise<void> => invoke("cancel-task", id),
+
+  /**
+   * Queue a check of a backup file (runs after a restart if interrupted).
+   */
+  scheduleBackupVerification: (backupPath: string, runAt?: string): Promise<QueuedTask> =>
+    invoke("schedule-backup-verification", backupPath, runAt),
+
+  /**
+   * Subscribe to queued tasks that failed for good.
+   */
+  onTaskFailed: (callback: (task: AppEvent<"tasks:failed">) => void): void =>
+    subscribe("tasks:failed", callback),
+
+  /**
+   * Attach a file to an invoice (copied into the data folder).
+   */
+  attachInvoiceFile: (invoiceId: number, filePath: string): Promise<InvoiceAttachment> =>
+    invoke("attach-invoice-file", invoiceId, filePath),
+
+  /**
+   * Attachments of an invoice, oldest first.
+   */
+  listInvoiceAttachments: (invoiceId: number): Promise<InvoiceAttachment[]> =>
+    invoke("list-invoice-attachments", invoiceId),
+
+  /**
+   * Remove an attachment from an invoice.
+   */
+  removeInvoiceAttachment: (invoiceId: number, attachmentId: number): Promise<void> =>
+    invoke("remove-invoice-attachment", invoiceId, attachmentId),
+
+  /**
+   * Open an attachment with the default program. Other than documents and
+   * images it is only shown in its folder ("revealed"), never run.
+   */
+  openInvoiceAttachment: (
+    invoiceId: number,
+    attachmentId: number
+  ): Promise<"opened" | "revealed"> => invoke("open-invoice-attachment", invoiceId, attachmentId),
+
+  /**
+   * Subscribe to added or removed attachments.
+   */
+  onAttachmentsChanged: (callback: (change: AppEvent<"attachments:changed">) => void): void =>
+    subscribe("attachments:changed", callback),
+
+  /**
+   * Generate a statement PDF of a customer's invoices in a period; show it
+   * via billino-pdf://pdf/<pdfId>.
+   */
+  generateCustomerStatement: (
+    customerId: number,
+    range: StatementRange,
+    profileId?: number
+  ): Promise<CustomerStatement> =>
+    invoke("generate-customer-statement", customerId, range, profileId),
+
+  /**
+   * Preview (default) or import a Lexoffice/WISO CSV export. Pass
+   * { dryRun: false } to create the new rows in Billino.
+   */
+  importLegacyData: (
+    filePath: string,
+    format: ImportFormat,
+    options?: ImportOptions
+  ): Promise<ImportResult> => invoke("import-legacy-data", filePath, format, options),
+
+  /**
+   * Subscribe to the progress of a running import.
+   */
+  onImportProgress: (callback: (progress: AppEvent<"import:progress">) => void): void =>
+    subscribe("import:progress", callback),
+
+  /**
+   * Send an e-mail, optionally with an invoice PDF; queued in the outbox if
+   * the server cannot be reached.
+   */
+  sendEmail: (request: EmailRequest): Promise<SendEmailResult> => invoke("send-email", request),
+
+  /**
+   * Mails in the outbox (waiting for a retry or given up).
+   */
+  listOutbox: (): Promise<OutboxEntry[]> => invoke("list-outbox"),
+
+  /**
+   * Drop a mail from the outbox without sending it.
+   */
+  cancelOutboxEntry: (id: string): Promise<void> => invoke("cancel-outbox-entry", id),
+
+  /**
+   * Subscribe to sent e-mails (also those sent from the outbox).
+   */
+  onEmailSent: (callback: (mail: AppEvent<"email:sent">) => void): void =>
+    subscribe("email:sent", callback),
 });
@@ -0,0 +1,224 @@
+/**
+ * Billino Desktop – Service Reachability Probes
+ *
+ * Checks the external services configured in the settings, so a wrong
+ * host, port or certificate shows up next to the setting instead of when
+ * the first invoice is sent:
+ * - smtp: connect, greeting, EHLO (and STARTTLS if configured), QUIT
+ * - webdav: OPTIONS request to the backup folder
+ * - vies: status endpoint of the EU VAT number validation (VIES)
+ *
+ * A probe never sends credentials and never changes anything on the server.
+ */
+
+import http from "http";
+import https from "https";
+import tls from "tls";
+import log from "electron-log/main";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import { getSettings } from "./settings";
+import { SmtpError, SmtpSession } from "./smtp";
+
+export type ProbeKind = "smtp" | "webdav" | "vies";
+
+export interface TlsDetails {
+  /** Negotiated protocol, e.g. "TLSv1.3". */
+  protocol: string | null;
+  cipher: string;
+  /** Certificate chain is valid for the host name. */
+  authorized: boolean;
+  authorizationError: string | null;
+  subject: string | null;
+  issuer: string | null;
+  /** Certificate expiry (ISO). */
+  validTo: string | null;
+}
+
+export interface ProbeResult {
+  kind: ProbeKind;
+  /** Host:port or URL that was checked. */
+  target: string;
+  reachable: boolean;
+  /** Time until the server answered (greeting / response headers). */
+  latencyMs: number | null;
+  tls: TlsDetails | null;
+  /** Human-readable summary of the server's answer. */
+  detail: string | null;
+  error: string | null;
+  checkedAt: string;
+}
+
+const PROBE_TIMEOUT_MS = 10_000;
+const VIES_STATUS_URL = "https://ec.europa.eu/taxation_customs/vies/rest-api/check-status";
+
+/**
+ * Certificate and cipher details of an encrypted connection.
+ */
+export function describeTls(socket: tls.TLSSocket): TlsDetails {
+  const cert = socket.getPeerCertificate();
+  const hasCert = cert && Object.keys(cert).length > 0;
+  return {
+    protocol: socket.getProtocol(),
+    cipher: socket.getCipher().name,
+    authorized: socket.authorized,
+    authorizationError: socket.authorizationError ? String(socket.authorizationError) : null,
+    subject: hasCert ? (cert.subject?.CN ?? null) : null,
+    issuer: hasCert ? (cert.issuer?.O ?? cert.issuer?.CN ?? null) : null,
+    validTo: hasCert && cert.valid_to ? new Date(cert.valid_to).toISOString() : null,
+  };
+}
+
+function result(kind: ProbeKind, target: string, values: Partial<ProbeResult>): ProbeResult {
+  return {
+    kind,
+    target,
+    reachable: false,
+    latencyMs: null,
+    tls: null,
+    detail: null,
+    error: null,
+    checkedAt: new Date().toISOString(),
+    ...values,
+  };
+}
+
+async function probeSmtp(): Promise<ProbeResult> {
+  const { smtpHost, smtpPort, security } = getSettings().email;
+  const target = `${smtpHost}:${smtpPort}`;
+  if (!smtpHost) return result("smtp", target, { error: "Kein SMTP-Server eingetragen" });
+
+  try {
+    const session = await SmtpSession.connect({
+      host: smtpHost,
+      port: smtpPort,
+      security,
+      timeoutMs: PROBE_TIMEOUT_MS,
+    });
+    const tlsSocket = session.tlsSocket;
+    const details = tlsSocket ? describeTls(tlsSocket) : null;
+    const greeting = session.transcript.find((line) => line.startsWith("S: 220"));
+    await session.quit();
+    return result("smtp", target, {
+      reachable: true,
+      latencyMs: session.greetingMs,
+      tls: details,
+      detail: greeting?.slice(3) ?? null,
+    });
+  } catch (err) {
+    const transcript = err instanceof SmtpError ? err.transcript : [];
+    return result("smtp", target, {
+      // Any server reply means the host is reachable, just misconfigured
+      reachable: transcript.some((line) => line.startsWith("S: ")),
+      error: String(err instanceof Error ? err.message : err),
+      detail: transcript.at(-1) ?? null,
+    });
+  }
+}
+
+/**
+ * Send a request and report status, latency and TLS details.
+ */
+function probeHttp(
+  kind: ProbeKind,
+  url: string,
+  method: string,
+  describe: (status: number, headers: http.IncomingHttpHeaders, body: string) => string
+): Promise<ProbeResult> {
+  return new Promise((resolve) => {
+    let parsed: URL;
+    try {
+      parsed = new URL(url);
+    } catch {
+      resolve(result(kind, url, { error: `Ungültige URL: ${url}` }));
+      return;
+    }
+
+    const start = Date.now();
+    const client = parsed.protocol === "https:" ? https : http;
+    const request = client.request(parsed, { method, timeout: PROBE_TIMEOUT_MS }, (response) => {
+      const latencyMs = Date.now() - start;
+      const socket = response.socket;
+      const details = socket instanceof tls.TLSSocket ? describeTls(socket) : null;
+      let body = "";
+      response.setEncoding("utf8");
+      response.on("data", (chunk: string) => {
+        if (body.length < 64 * 1024) body += chunk;
+      });
+      response.on("end", () =>
+        resolve(
+          result(kind, url, {
+            reachable: true,
+            latencyMs,
+            tls: details,
+            detail: describe(response.statusCode ?? 0, response.headers, body),
+          })
+        )
+      );
+    });
+    request.on("timeout", () => request.destroy(new Error(`Timeout nach ${PROBE_TIMEOUT_MS} ms`)));
+    request.on("error", (err) => resolve(result(kind, url, { error: err.message })));
+    request.end();
+  });
+}
+
+function probeWebDav(): Promise<ProbeResult> {
+  const { url } = getSettings().webdavBackup;
+  if (!url) {
+    return Promise.resolve(result("webdav", url, { error: "Keine WebDAV-Adresse eingetragen" }));
+  }
+  return probeHttp("webdav", url, "OPTIONS", (status, headers) => {
+    const dav = headers["dav"] ? `WebDAV ${headers["dav"]}` : "kein WebDAV-Header";
+    if (status === 401) return `HTTP 401 – Anmeldung erforderlich (${dav})`;
+    if (status === 404) return "HTTP 404 – Ordner nicht gefunden";
+    return `HTTP ${status} (${dav})`;
+  });
+}
+
+function probeVies(): Promise<ProbeResult> {
+  return probeHttp("vies", VIES_STATUS_URL, "GET", (status, _headers, body) => {
+    if (status !== 200) return `HTTP ${status}`;
+    try {
+      const data = JSON.parse(body) as {
+        vow?: { available?: boolean };
+        countries?: Array<{ countryCode: string; availability: string }>;
+      };
+      const unavailable = (data.countries ?? [])
+        .filter((country) => country.availability !== "Available")
+        .map((country) => country.countryCode);
+      if (data.vow?.available === false) return "VIES ist derzeit nicht verfügbar";
+      return unavailable.length
+        ? `Verfügbar, außer: ${unavailable.join(", ")}`
+        : "Alle Mitgliedstaaten verfügbar";
+    } catch {
+      return `HTTP ${status}`;
+    }
+  });
+}
+
+/**
+ * Check whether a configured external service is reachable.
+ */
+export async function probeService(kind: ProbeKind): Promise<ProbeResult> {
+  const probes: Record<ProbeKind, () => Promise<ProbeResult>> = {
+    smtp: probeSmtp,
+    webdav: probeWebDav,
+    vies: probeVies,
+  };
+  const probe = probes[kind];
+  if (!probe) throw new AppError("invalid_input", `Unknown service: ${kind}`);
+
+  const probeResult = await probe();
+  const status = probeResult.reachable
+    ? `reachable (${probeResult.latencyMs} ms)`
+    : `unreachable: ${probeResult.error}`;
+  log.info(`🔌 Probe ${kind} ${probeResult.target}: ${status}`);
+  return probeResult;
+}
+
+/**
+ * Register IPC handlers for service probes.
+ */
+export function registerProbeHandlers(): void {
+  handle("probe-service", (_event, kind: ProbeKind) => probeService(kind), "read");
+}
@@ -0,0 +1,225 @@
+/**
+ * Billino Desktop – Process Handles
+ *
+ * Stops processes the shell spawned by their PID – never "whatever listens
+ * on the backend port", which may be another program.
+ *
+ * - `terminate()` asks the process to exit: SIGTERM on Unix (the backend
+ *   runs its shutdown sequence), `taskkill /t` without /f on Windows
+ * - `forceKill()` ends it and its children (uvicorn workers): SIGKILL to
+ *   the process group on Unix (the backend is spawned as group leader),
+ *   `taskkill /f /t` on Windows; `kill()` also waits for the exit
+ * - `stop()` terminates, waits and force-kills what is still running
+ *
+ * Windows only closes processes gracefully that have a window; for the
+ * windowless backend taskkill refuses and `stop()` force-kills at once.
+ *
+ * Before a process the shell did not spawn is killed, `checkBackendProcess()`
+ * makes sure it listens on the expected port and is a Billino backend:
+ * Get-NetTCPConnection / Win32_Process on Windows, lsof / ps elsewhere.
+ *
+ * If the app itself is killed, none of this runs: the backend then ends
+ * through its Job Object (Windows) or PDEATHSIG (Linux), see
+ * backend/utils/parent_process.py.
+ */
+
+import { ChildProcess, execFile, spawn } from "child_process";
+import log from "electron-log/main";
+
+/** How long a force-killed process may take to disappear. */
+const FORCE_KILL_WAIT_MS = 2_000;
+const COMMAND_TIMEOUT_MS = 5_000;
+/** The bundled executable or, in development, python backend/main.py. */
+const BACKEND_COMMAND = /billino-backend(?:\.exe)?|[\\/]backend[\\/]main\.py/i;
+
+function run(command: string, args: string[]): Promise<string | null> {
+  return new Promise((resolve) => {
+    execFile(command, args, { timeout: COMMAND_TIMEOUT_MS, windowsHide: true }, (err, stdout) =>
+      resolve(err ? null : stdout)
+    );
+  });
+}
+
+function powershell(script: string): Promise<string | null> {
+  return run("powershell.exe", ["-NoProfile", "-NonInteractive", "-Command", script]);
+}
+
+/** PIDs with a listening socket on `port`; null if they cannot be read. */
+async function listeningPids(port: number): Promise<number[] | null> {
+  const output =
+    process.platform === "win32"
+      ? await powershell(
+          `Get-NetTCPConnection -State Listen -LocalPort ${port} -ErrorAction SilentlyContinue` +
+            " | Select-Object -ExpandProperty OwningProcess"
+        )
+      : await run("lsof", ["-nP", `-iTCP:${port}`, "-sTCP:LISTEN", "-t"]);
+  if (output === null) return null;
+  return output
+    .split(/\s+/)
+    .filter((line) => /^\d+$/.test(line))
+    .map(Number);
+}
+
+async function commandLine(pid: number): Promise<string | null> {
+  const output =
+    process.platform === "win32"
+      ? await powershell(`(Get-CimInstance Win32_Process -Filter "ProcessId = ${pid}").CommandLine`)
+      : await run("ps", ["-o", "command=", "-p", String(pid)]);
+  return output?.trim() || null;
+}
+
+/**
+ * Check that `pid` (e.g. reported by whatever answers /health) listens on
+ * `port` and is a Billino backend, before it is killed.
+ *
+ * @returns Why the process must not be killed, or null if it is the backend
+ */
+export async function checkBackendProcess(pid: number, port: number): Promise<string | null> {
+  const pids = await listeningPids(port);
+  if (pids === null) return `listening processes on port ${port} could not be read`;
+  if (!pids.includes(pid)) {
+    return `pid ${pid} does not listen on port ${port} (listening: ${pids.join(", ") || "none"})`;
+  }
+  const command = await commandLine(pid);
+  if (command === null) return `command line of pid ${pid} could not be read`;
+  if (!BACKEND_COMMAND.test(command)) return `pid ${pid} is not a Billino backend (${command})`;
+  return null;
+}
+
+function taskkill(pid: number, force: boolean): Promise<boolean> {
+  const args = ["/pid", String(pid), "/t", ...(force ? ["/f"] : [])];
+  return new Promise((resolve) => {
+    const child = spawn("taskkill", args, { windowsHide: true, stdio: "ignore" });
+    child.on("error", () => resolve(false));
+    child.on("exit", (code) => resolve(code === 0));
+  });
+}
+
+/**
+ * Force-kill a process (and its children) that this shell did not spawn,
+ * e.g. a backend left over from an earlier session.
+ *
+ * @returns false if the process could not be killed
+ */
+export async function killProcessTree(pid: number): Promise<boolean> {
+  if (process.platform === "win32") return taskkill(pid, true);
+  try {
+    // The backend is a group leader (spawned with `detached`)
+    process.kill(-pid, "SIGKILL");
+    return true;
+  } catch {
+    try {
+      process.kill(pid, "SIGKILL");
+      return true;
+    } catch {
+      return false;
+    }
+  }
+}
+
+/**
+ * A spawned child process, identified by its PID.
+ */
+export class ProcessHandle {
+  public readonly pid: number | undefined;
+  private exited: boolean;
+  private readonly exit: Promise<void>;
+
+  constructor(public readonly child: ChildProcess) {
+    this.pid = child.pid;
+    this.exited = child.exitCode !== null || child.signalCode !== null;
+    this.exit = this.exited
+      ? Promise.resolve()
+      : new Promise((resolve) => {
+          child.once("exit", () => {
+            this.exited = true;
+            resolve();
+          });
+        });
+  }
+
+  /**
+   * Whether the process was started and has not exited yet.
+   */
+  get running(): boolean {
+    return this.pid !== undefined && !this.exited;
+  }
+
+  /**
+   * Ask the process to exit.
+   *
+   * @returns false if the request could not be delivered
+   */
+  async terminate(): Promise<boolean> {
+    if (!this.running || this.pid === undefined) return false;
+    if (process.platform === "win32") return taskkill(this.pid, false);
+    return this.child.kill("SIGTERM");
+  }
+
+  /**
+   * Kill the process and its children immediately.
+   */
+  forceKill(): void {
+    if (!this.running || this.pid === undefined) return;
+    if (process.platform === "win32") {
+      void taskkill(this.pid, true);
+      return;
+    }
+    try {
+      // Negative PID: the whole process group, workers included
+      process.kill(-this.pid, "SIGKILL");
+    } catch {
+      // Not a group leader (spawned without `detached`)
+      this.child.kill("SIGKILL");
+    }
+  }
+
+  /**
+   * Force-kill and wait until the process is gone.
+   *
+   * @returns false if it is still running after the kill
+   */
+  async kill(): Promise<boolean> {
+    this.forceKill();
+    if (await this.waitForExit(FORCE_KILL_WAIT_MS)) return true;
+    log.error(`❌ Process ${this.pid} is still running after kill`);
+    return false;
+  }
+
+  /**
+   * Resolves when the process exits.
+   */
+  onceExited(): Promise<void> {
+    return this.exit;
+  }
+
+  /**
+   * Wait until the process has exited.
+   *
+   * @returns false if it was still running after `timeoutMs`
+   */
+  async waitForExit(timeoutMs: number): Promise<boolean> {
+    if (!this.running) return true;
+    let timer: NodeJS.Timeout | undefined;
+    const timeout = new Promise<boolean>((resolve) => {
+      timer = setTimeout(() => resolve(false), timeoutMs);
+    });
+    try {
+      return await Promise.race([this.exit.then(() => true), timeout]);
+    } finally {
+      clearTimeout(timer);
+    }
+  }
+
+  /**
+   * Terminate gracefully, force-kill after `graceMs`.
+   */
+  async stop(graceMs: number): Promise<void> {
+    if (!this.running) return;
+    if ((await this.terminate()) && (await this.waitForExit(graceMs))) return;
+    if (!this.running) return;
+
+    log.warn(`⚠️ Process ${this.pid} did not exit gracefully – killing it`);
+    await this.kill();
+  }
+}
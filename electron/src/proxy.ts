@@ -0,0 +1,100 @@
+/**
+ * Billino Desktop – Backend API Proxy
+ *
+ * The frontend sends its backend requests through `api-request` instead
+ * of fetching the backend URL itself. The shell knows the active port
+ * (it changes after a port conflict or a blue-green restart), adds the
+ * Authorization header for an attach URL with credentials and traces the
+ * request like its own. The frontend needs no backend URL at all.
+ *
+ * Only relative backend paths are accepted, endpoints the shell manages
+ * itself (shutdown) are refused, and secondary windows may only read.
+ * HTTP errors are not thrown: the renderer gets status and body, as from
+ * fetch(), and keeps its own error handling for FastAPI validation
+ * details.
+ */
+
+import log from "electron-log/main";
+import { BackendResponseInfo, HttpMethod, sendBackendRequest } from "./api";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import { assertCommandAllowed } from "./permissions";
+
+const METHODS: readonly HttpMethod[] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+const MAX_PATH_LENGTH = 2_048;
+/** Endpoints only the shell may call. */
+const SHELL_ONLY = new Set(["POST /shutdown"]);
+/** PDF generation and imports can take a while. */
+const PROXY_TIMEOUT_MS = 120_000;
+
+function invalidRequest(detail: string): AppError {
+  return new AppError("invalid_input", `Invalid API request: ${detail}`, {
+    message: "Die Anfrage an den Billino-Dienst ist ungültig.",
+  });
+}
+
+/**
+ * Check method and path of a proxied request.
+ *
+ * @returns The upper-case method
+ * @throws AppError for anything but a relative backend path
+ */
+export function validateApiRequest(method: unknown, path: unknown): HttpMethod {
+  const upper = typeof method === "string" ? (method.toUpperCase() as HttpMethod) : null;
+  if (!upper || !METHODS.includes(upper)) throw invalidRequest(`method ${String(method)}`);
+  if (typeof path !== "string" || path.length > MAX_PATH_LENGTH) {
+    throw invalidRequest("path missing or too long");
+  }
+  // "//host" would be a different origin; ".." could leave a path prefix
+  if (
+    !path.startsWith("/") ||
+    path.startsWith("//") ||
+    /[\\\u0000-\u001f]/.test(path) ||
+    path.split("?")[0].split("/").some((segment) => segment === ".." || segment === ".")
+  ) {
+    throw invalidRequest(`path ${path}`);
+  }
+
+  const route = `${upper} ${path.split("?")[0].replace(/\/+$/, "") || "/"}`;
+  if (SHELL_ONLY.has(route)) {
+    log.warn(`⚠️ Proxy refused shell-only endpoint ${route}`);
+    throw new AppError("permission_denied", `${route} is reserved for the shell`);
+  }
+  return upper;
+}
+
+/**
+ * Send a frontend request to the active backend.
+ *
+ * @param path Backend path with query string, e.g. "/invoices/?limit=20"
+ * @throws AppError for invalid requests and an unreachable backend
+ */
+export function apiRequest(
+  method: string,
+  path: string,
+  body?: unknown
+): Promise<BackendResponseInfo> {
+  const validMethod = validateApiRequest(method, path);
+  return sendBackendRequest(path, {
+    method: validMethod,
+    body: validMethod === "GET" || validMethod === "DELETE" ? undefined : body,
+    timeoutMs: PROXY_TIMEOUT_MS,
+  });
+}
+
+/**
+ * Register IPC handlers for the API proxy. GET requests are open to every
+ * window, others need write access (main window).
+ */
+export function registerApiProxyHandlers(): void {
+  handle(
+    "api-request",
+    (event, method: string, path: string, body?: unknown) => {
+      if (validateApiRequest(method, path) !== "GET") {
+        assertCommandAllowed("api-request", "write", event.sender);
+      }
+      return apiRequest(method, path, body);
+    },
+    "read"
+  );
+}
@@ -0,0 +1,114 @@
+/**
+ * Billino Desktop – PII Redaction
+ *
+ * Masks personal data before it reaches any log output (shell log, captured
+ * backend output, renderer console, diagnostic bundles), so log files can be
+ * shared with support safely.
+ *
+ * Built-in rules:
+ * - IBANs → `[IBAN …1234]` (last four characters kept for correlation)
+ * - E-mail addresses → `[E-MAIL]`
+ * - Name fields in key/value or JSON output (`name=…`, `"name": "…"`)
+ *
+ * Users can add names and extra regular expressions in the `redaction`
+ * settings section.
+ */
+
+import log from "electron-log/main";
+import { getSettings, RedactionSettings } from "./settings";
+
+interface RedactionRule {
+  pattern: RegExp;
+  replace: (match: string, groups: (string | undefined)[]) => string;
+}
+
+const IBAN_PATTERN = /\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,4})?\b/g;
+const EMAIL_PATTERN = /[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}/g;
+const NAME_FIELD_PATTERN =
+  /(["']?\b(?:name|customer_name|customer|contact|kunde|ansprechpartner)["']?\s*[:=]\s*)(["'])?([^"',}\]\n]+)\2?/gi;
+
+const BUILTIN_RULES: RedactionRule[] = [
+  {
+    pattern: IBAN_PATTERN,
+    replace: (match) => `[IBAN …${match.replace(/ /g, "").slice(-4)}]`,
+  },
+  { pattern: EMAIL_PATTERN, replace: () => "[E-MAIL]" },
+  {
+    pattern: NAME_FIELD_PATTERN,
+    replace: (_match, [prefix, quote = ""]) => `${prefix}${quote}[NAME]${quote}`,
+  },
+];
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
+
+let cachedSettings: RedactionSettings | null = null;
+let cachedRules: RedactionRule[] = BUILTIN_RULES;
+
+/**
+ * Build the active rule set from settings (cached until settings change).
+ */
+function getRules(settings: RedactionSettings): RedactionRule[] {
+  if (settings === cachedSettings) return cachedRules;
+
+  const rules = [...BUILTIN_RULES];
+  for (const name of settings.names.filter((n) => n.trim().length > 1)) {
+    rules.push({
+      pattern: new RegExp(`\\b${escapeRegExp(name.trim())}\\b`, "gi"),
+      replace: () => "[NAME]",
+    });
+  }
+  for (const source of settings.extraPatterns) {
+    try {
+      rules.push({ pattern: new RegExp(source, "g"), replace: () => "[REDACTED]" });
+    } catch (err) {
+      console.error(`Ignoring invalid redaction pattern "${source}": ${err}`);
+    }
+  }
+
+  cachedSettings = settings;
+  cachedRules = rules;
+  return rules;
+}
+
+/**
+ * Mask personal data in a string according to the current settings.
+ */
+export function redact(text: string, settings: RedactionSettings = getSettings().redaction): string {
+  if (!settings.enabled) return text;
+
+  return getRules(settings).reduce(
+    (result, rule) =>
+      result.replace(rule.pattern, (match: string, ...args: unknown[]) =>
+        // Trailing replacer arguments are offset and input string
+        rule.replace(match, args.slice(0, -2) as (string | undefined)[])
+      ),
+    text
+  );
+}
+
+/**
+ * Redact an arbitrary log argument (strings, errors, plain objects).
+ */
+export function redactValue(value: unknown): unknown {
+  if (typeof value === "string") return redact(value);
+  if (value instanceof Error) return redact(value.stack ?? value.message);
+  if (value === null || typeof value !== "object") return value;
+
+  try {
+    return JSON.parse(redact(JSON.stringify(value)));
+  } catch {
+    return redact(String(value));
+  }
+}
+
+/**
+ * Install the redaction hook for every electron-log transport.
+ */
+export function initLogRedaction(): void {
+  log.hooks.push((message) => {
+    message.data = message.data.map(redactValue);
+    return message;
+  });
+}
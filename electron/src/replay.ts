@@ -0,0 +1,204 @@
+/**
+ * Billino Desktop – Backend Request Replay
+ *
+ * Debugging aid for intermittent backend bugs: re-issues the backend
+ * requests of a recorded debug session (session.ts) by their correlation
+ * id – the trace id of the command that sent them – and diffs each new
+ * response against the recorded one.
+ *
+ * Requests are sent again as recorded, POST/PUT/DELETE included, so a
+ * replay can create or change data. It therefore needs
+ * `debug.requestReplay` in the settings. Values redacted during recording
+ * are sent as their placeholders and show up as differences.
+ */
+
+import fs from "fs";
+import path from "path";
+import log from "electron-log/main";
+import { getBackendUrl } from "./config";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import {
+  getSessionDir,
+  getSessionRecordingStatus,
+  hashResponse,
+  loadSession,
+  MAX_RECORDED_RESPONSE_CHARS,
+  RequestEntry,
+} from "./session";
+import { getSettings } from "./settings";
+
+export interface ResponseDifference {
+  /** JSON path, e.g. "items[0].total_gross" ("" for the whole body). */
+  path: string;
+  expected: unknown;
+  actual: unknown;
+}
+
+export interface ReplayedRequest {
+  method: string;
+  path: string;
+  expectedStatus: number;
+  actualStatus: number;
+  durationMs: number;
+  /** Same status and same body (by content or hash). */
+  identical: boolean;
+  differences: ResponseDifference[];
+}
+
+export interface RequestReplayReport {
+  correlationId: string;
+  sessionFile: string;
+  requests: ReplayedRequest[];
+}
+
+/** Enough to see what changed; the rest is noise. */
+const MAX_DIFFERENCES = 50;
+const REPLAY_TIMEOUT_MS = 60_000;
+
+/**
+ * Session files, the one being recorded first, then newest first.
+ */
+function sessionFiles(): string[] {
+  const dir = getSessionDir();
+  let files: string[] = [];
+  try {
+    files = fs
+      .readdirSync(dir)
+      .filter((name) => name.endsWith(".jsonl"))
+      .sort()
+      .reverse()
+      .map((name) => path.join(dir, name));
+  } catch {
+    // No sessions recorded yet
+  }
+  const recording = getSessionRecordingStatus().file;
+  return recording ? [recording, ...files.filter((file) => file !== recording)] : files;
+}
+
+function findRequests(correlationId: string): { file: string; requests: RequestEntry[] } {
+  for (const file of sessionFiles()) {
+    let requests: RequestEntry[];
+    try {
+      requests = loadSession(file).filter(
+        (entry): entry is RequestEntry =>
+          entry.type === "request" && entry.correlationId === correlationId
+      );
+    } catch (err) {
+      log.warn(`⚠️ Skipping unreadable session file ${file}: ${err}`);
+      continue;
+    }
+    if (requests.length > 0) return { file, requests };
+  }
+  throw new AppError("not_found", `No recorded requests with correlation id ${correlationId}`, {
+    message: "Zu dieser ID wurden keine Anfragen aufgezeichnet.",
+  });
+}
+
+/**
+ * Differences between two JSON values, depth-first.
+ */
+export function diffJson(expected: unknown, actual: unknown, at = ""): ResponseDifference[] {
+  const differences: ResponseDifference[] = [];
+  const walk = (a: unknown, b: unknown, where: string): void => {
+    if (differences.length >= MAX_DIFFERENCES) return;
+    if (Array.isArray(a) && Array.isArray(b)) {
+      for (let i = 0; i < Math.max(a.length, b.length); i++) walk(a[i], b[i], `${where}[${i}]`);
+      return;
+    }
+    const isObject = (value: unknown): value is Record<string, unknown> =>
+      typeof value === "object" && value !== null && !Array.isArray(value);
+    if (isObject(a) && isObject(b)) {
+      for (const key of new Set([...Object.keys(a), ...Object.keys(b)])) {
+        walk(a[key], b[key], where ? `${where}.${key}` : key);
+      }
+      return;
+    }
+    if (JSON.stringify(a) !== JSON.stringify(b)) {
+      differences.push({ path: where, expected: a, actual: b });
+    }
+  };
+  walk(expected, actual, at);
+  return differences;
+}
+
+async function replayOne(entry: RequestEntry): Promise<ReplayedRequest> {
+  const started = Date.now();
+  const response = await fetch(`${getBackendUrl()}${entry.path}`, {
+    method: entry.method,
+    headers: {
+      "X-Trace-Id": `replay-${entry.correlationId}`,
+      ...(entry.body !== undefined && { "Content-Type": "application/json" }),
+    },
+    body: entry.body === undefined ? undefined : JSON.stringify(entry.body),
+    signal: AbortSignal.timeout(REPLAY_TIMEOUT_MS),
+  });
+  const text = await response.text();
+
+  let differences: ResponseDifference[] = [];
+  if (entry.response !== undefined && text.length <= MAX_RECORDED_RESPONSE_CHARS) {
+    let actual: unknown = text;
+    try {
+      actual = text ? JSON.parse(text) : null;
+    } catch {
+      // Non-JSON response – compare the raw text
+    }
+    differences = diffJson(entry.response, actual);
+  } else if (hashResponse(text) !== entry.responseHash) {
+    differences = [{ path: "", expected: entry.responseHash, actual: hashResponse(text) }];
+  }
+
+  return {
+    method: entry.method,
+    path: entry.path,
+    expectedStatus: entry.status,
+    actualStatus: response.status,
+    durationMs: Date.now() - started,
+    identical: response.status === entry.status && differences.length === 0,
+    differences,
+  };
+}
+
+/**
+ * Re-issue the recorded backend requests of `correlationId` in order and
+ * compare the responses.
+ *
+ * @throws AppError if replay is disabled or nothing was recorded for the id
+ */
+export async function replayRequest(correlationId: string): Promise<RequestReplayReport> {
+  if (!getSettings().debug.requestReplay) {
+    throw new AppError("invalid_state", "Request replay is disabled", {
+      message: "Das Wiederholen von Anfragen ist nicht aktiviert.",
+      hint: "Unter Einstellungen → Diagnose „Anfragen wiederholen“ einschalten.",
+    });
+  }
+
+  const { file, requests } = findRequests(correlationId);
+  log.warn(`🔁 Replaying ${requests.length} backend request(s) of ${correlationId}`);
+
+  const replayed: ReplayedRequest[] = [];
+  for (const entry of requests) {
+    const result = await replayOne(entry);
+    if (!result.identical) {
+      log.warn(
+        `⚠️ Replay of ${entry.method} ${entry.path} differs: ` +
+          `HTTP ${result.expectedStatus} → ${result.actualStatus}, ` +
+          `${result.differences.length} difference(s)`
+      );
+    }
+    replayed.push(result);
+  }
+
+  return { correlationId, sessionFile: file, requests: replayed };
+}
+
+/**
+ * Register IPC handlers for request replay.
+ */
+export function registerReplayHandlers(): void {
+  handle(
+    "replay-request",
+    (_event, correlationId: string) => replayRequest(correlationId),
+    "destructive"
+  );
+}
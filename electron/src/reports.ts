@@ -0,0 +1,191 @@
+/**
+ * Billino Desktop – Monthly Summary Report
+ *
+ * On the first business day of a month (settings: calendar.state for the
+ * holidays) the shell sums up the previous month – revenue, invoice count
+ * and the outstanding amount – and shows a notification. Clicking it
+ * brings up the main window and sends `report:open` with the summary, so
+ * the renderer can show its summary view. If the app is not running that
+ * day, the notification follows at the next start; each month is reported
+ * once (settings: reports.monthlySummary).
+ *
+ * Billino does not record payments: "outstanding" is the part of the
+ * month's invoices that is not yet due under the payment term of the
+ * dunning policy.
+ */
+
+import { app, Notification } from "electron";
+import fs from "fs";
+import path from "path";
+import log from "electron-log/main";
+import { computeDueDate, fetchInvoices } from "./dunning";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { nextBusinessDay } from "./holidays";
+import { handle } from "./ipc";
+import { getSettings } from "./settings";
+import { getMainWindow } from "./windows";
+
+export interface MonthlySummary {
+  /** Reported month, YYYY-MM. */
+  month: string;
+  /** Gross revenue of all invoices dated in the month. */
+  revenue: number;
+  invoiceCount: number;
+  /** Gross amount of the month's invoices not yet due on `asOf`. */
+  outstanding: number;
+  outstandingCount: number;
+  asOf: string;
+  createdAt: string;
+}
+
+const CHECK_INTERVAL_MS = 6 * 60 * 60 * 1000;
+const INITIAL_DELAY_MS = 90_000;
+const MONTH_PATTERN = /^(\d{4})-(\d{2})$/;
+
+let timer: NodeJS.Timeout | null = null;
+// Garbage-collected notifications lose their click handler
+let lastNotification: Notification | null = null;
+
+const euro = (value: number): string =>
+  value.toLocaleString("de-DE", { style: "currency", currency: "EUR" });
+
+const round2 = (value: number): number => Math.round(value * 100) / 100;
+
+function formatMonth(year: number, monthIndex: number): string {
+  const date = new Date(Date.UTC(year, monthIndex, 1));
+  return date.toISOString().slice(0, 7);
+}
+
+function today(): string {
+  const now = new Date();
+  return new Date(Date.UTC(now.getFullYear(), now.getMonth(), now.getDate()))
+    .toISOString()
+    .slice(0, 10);
+}
+
+function previousMonth(day: string): string {
+  return formatMonth(Number(day.slice(0, 4)), Number(day.slice(5, 7)) - 2);
+}
+
+function lastDayOfMonth(month: string): string {
+  const [year, monthNumber] = month.split("-").map(Number);
+  return new Date(Date.UTC(year, monthNumber, 0)).toISOString().slice(0, 10);
+}
+
+/**
+ * Totals of all invoices dated in `month`.
+ *
+ * @param month YYYY-MM
+ * @param asOf Reference date for "outstanding" (YYYY-MM-DD, default: today)
+ */
+export async function computeMonthlySummary(
+  month: string,
+  asOf: string = today()
+): Promise<MonthlySummary> {
+  if (!MONTH_PATTERN.test(month ?? "")) {
+    throw new AppError("invalid_input", `Invalid month: ${month}`, {
+      message: "Bitte einen Monat im Format JJJJ-MM angeben.",
+    });
+  }
+
+  const { dunning: policy, calendar } = getSettings();
+  const invoices = await fetchInvoices(lastDayOfMonth(month), `${month}-01`);
+  const open = invoices.filter(
+    (invoice) => computeDueDate(invoice.date, policy, calendar.state) > asOf
+  );
+
+  return {
+    month,
+    revenue: round2(invoices.reduce((sum, invoice) => sum + invoice.grossAmount, 0)),
+    invoiceCount: invoices.length,
+    outstanding: round2(open.reduce((sum, invoice) => sum + invoice.grossAmount, 0)),
+    outstandingCount: open.length,
+    asOf,
+    createdAt: new Date().toISOString(),
+  };
+}
+
+// ─── Monthly Notification ────────────────────────────────────────────────────
+
+function getReportedPath(): string {
+  return path.join(app.getPath("userData"), "monthly-reports.json");
+}
+
+function loadReported(): string[] {
+  try {
+    return JSON.parse(fs.readFileSync(getReportedPath(), "utf-8")) as string[];
+  } catch {
+    return [];
+  }
+}
+
+function openSummary(summary: MonthlySummary): void {
+  const window = getMainWindow();
+  if (window) {
+    if (window.isMinimized()) window.restore();
+    window.show();
+    window.focus();
+  }
+  emitEvent("report:open", summary);
+}
+
+function notify(summary: MonthlySummary): void {
+  if (!Notification.isSupported()) return;
+  const [year, month] = summary.month.split("-");
+  const notification = new Notification({
+    title: `Billino – Monatsübersicht ${month}/${year}`,
+    body:
+      `${summary.invoiceCount} Rechnungen, Umsatz ${euro(summary.revenue)}` +
+      (summary.outstandingCount > 0
+        ? `\nNoch nicht fällig: ${euro(summary.outstanding)} (${summary.outstandingCount})`
+        : ""),
+  });
+  notification.on("click", () => openSummary(summary));
+  notification.show();
+  lastNotification = notification;
+}
+
+async function runMonthlyReport(): Promise<void> {
+  if (!getSettings().reports.monthlySummary) return;
+
+  const day = today();
+  const month = previousMonth(day);
+  const firstBusinessDay = nextBusinessDay(`${day.slice(0, 7)}-01`);
+  if (day < firstBusinessDay) return;
+
+  const reported = loadReported();
+  if (reported.includes(month)) return;
+
+  try {
+    const summary = await computeMonthlySummary(month, day);
+    log.info(
+      `📊 Monthly summary ${month}: ${summary.invoiceCount} invoices, ` +
+        `revenue ${summary.revenue}, outstanding ${summary.outstanding}`
+    );
+    notify(summary);
+    fs.writeFileSync(getReportedPath(), JSON.stringify([...reported, month].slice(-24)), "utf-8");
+  } catch (err) {
+    log.warn(`⚠️ Monthly summary for ${month} failed: ${err}`);
+  }
+}
+
+/**
+ * Start the monthly report check (call once the backend is healthy).
+ */
+export function startMonthlyReports(): void {
+  if (timer) return;
+  setTimeout(() => void runMonthlyReport(), INITIAL_DELAY_MS);
+  timer = setInterval(() => void runMonthlyReport(), CHECK_INTERVAL_MS);
+}
+
+/**
+ * Register IPC handlers for reports.
+ */
+export function registerReportHandlers(): void {
+  handle(
+    "get-monthly-summary",
+    (_event, month?: string) => computeMonthlySummary(month ?? previousMonth(today())),
+    "read"
+  );
+}
@@ -0,0 +1,240 @@
+/**
+ * Billino Desktop – Backend Resources
+ *
+ * Samples memory (RSS), CPU usage and open handles of the backend process
+ * every `intervalSeconds` (settings `resourceMonitor`, default 30). A leak
+ * in the PDF generation or a runaway query shows up here long before the
+ * machine starts swapping.
+ *
+ * - Windows: Get-Process (working set, CPU seconds, handle count)
+ * - Linux: /proc/<pid> (VmRSS, utime + stime, entries in fd/)
+ * - macOS: ps (RSS, CPU time); open handles are not read
+ *
+ * CPU usage is the CPU time used between two samples, relative to the
+ * whole machine (100 % = all cores busy). Above `memoryWarningMb` the shell
+ * logs a warning and sends `backend:resource-warning`, again at the
+ * earliest after WARNING_COOLDOWN_MS. `get-backend-resources` returns the
+ * samples of the last two hours.
+ */
+
+import { execFile } from "child_process";
+import fs from "fs";
+import os from "os";
+import log from "electron-log/main";
+import type { BackendManager } from "./backendmanager";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { getSettings } from "./settings";
+
+export interface ResourceSample {
+  pid: number;
+  /** Resident memory (MB). */
+  rssMb: number;
+  /** CPU usage since the previous sample (%); null for the first one. */
+  cpuPercent: number | null;
+  /** Open handles (Windows) or file descriptors (Linux); null on macOS. */
+  handles: number | null;
+  sampledAt: string;
+}
+
+export interface BackendResourceWarning {
+  pid: number;
+  rssMb: number;
+  memoryWarningMb: number;
+}
+
+export interface BackendResources {
+  enabled: boolean;
+  memoryWarningMb: number;
+  /** The latest sample, null before the first or while no backend runs. */
+  current: ResourceSample | null;
+  /** Oldest first. */
+  samples: ResourceSample[];
+  lastWarning: (BackendResourceWarning & { at: string }) | null;
+}
+
+interface RawUsage {
+  rssBytes: number;
+  /** CPU time used so far (seconds). */
+  cpuSeconds: number;
+  handles: number | null;
+}
+
+const COMMAND_TIMEOUT_MS = 5_000;
+/** 2 hours at the default interval. */
+const MAX_SAMPLES = 240;
+const WARNING_COOLDOWN_MS = 15 * 60_000;
+/** Linux clock ticks per second (USER_HZ, 100 on all common kernels). */
+const CLOCK_TICKS = 100;
+
+const samples: ResourceSample[] = [];
+let previous: { pid: number; cpuSeconds: number; at: number } | null = null;
+let lastWarning: (BackendResourceWarning & { at: string }) | null = null;
+let lastWarningAt = 0;
+let timer: NodeJS.Timeout | null = null;
+
+function run(command: string, args: string[]): Promise<string | null> {
+  return new Promise((resolve) => {
+    execFile(command, args, { timeout: COMMAND_TIMEOUT_MS, windowsHide: true }, (err, stdout) =>
+      resolve(err ? null : stdout)
+    );
+  });
+}
+
+async function readWindowsUsage(pid: number): Promise<RawUsage | null> {
+  const output = await run("powershell.exe", [
+    "-NoProfile",
+    "-NonInteractive",
+    "-Command",
+    `Get-Process -Id ${pid} | Select-Object WorkingSet64, CPU, HandleCount | ConvertTo-Json`,
+  ]);
+  if (!output) return null;
+  try {
+    const parsed = JSON.parse(output) as {
+      WorkingSet64: number;
+      /** Processor time in seconds. */
+      CPU: number | null;
+      HandleCount: number;
+    };
+    return {
+      rssBytes: parsed.WorkingSet64,
+      cpuSeconds: parsed.CPU ?? 0,
+      handles: parsed.HandleCount,
+    };
+  } catch {
+    return null;
+  }
+}
+
+async function readLinuxUsage(pid: number): Promise<RawUsage | null> {
+  try {
+    const status = await fs.promises.readFile(`/proc/${pid}/status`, "utf-8");
+    const stat = await fs.promises.readFile(`/proc/${pid}/stat`, "utf-8");
+    // "VmRSS:     123456 kB"
+    const rss = status.match(/^VmRSS:\s+(\d+)\s+kB/m);
+    // The command name in parentheses may contain spaces; fields follow the last ")"
+    const fields = stat.slice(stat.lastIndexOf(")") + 2).split(" ");
+    const ticks = Number(fields[11]) + Number(fields[12]);
+    let handles: number | null = null;
+    try {
+      handles = (await fs.promises.readdir(`/proc/${pid}/fd`)).length;
+    } catch {
+      // fd/ of another user's process is not readable
+    }
+    return {
+      rssBytes: rss ? Number(rss[1]) * 1024 : 0,
+      cpuSeconds: ticks / CLOCK_TICKS,
+      handles,
+    };
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Parse a ps CPU time: "[dd-]hh:mm:ss" or "m:ss.cc" (macOS).
+ */
+export function parseCpuTime(value: string): number | null {
+  const match = value.trim().match(/^(?:(\d+)-)?([\d:.]+)$/);
+  if (!match) return null;
+  const parts = match[2].split(":").map(Number);
+  if (parts.some((part) => !Number.isFinite(part))) return null;
+  const seconds = parts.reduce((total, part) => total * 60 + part, 0);
+  return Number(match[1] ?? 0) * 86_400 + seconds;
+}
+
+async function readMacUsage(pid: number): Promise<RawUsage | null> {
+  const output = await run("ps", ["-o", "rss=,time=", "-p", String(pid)]);
+  // "  123456   0:01.23"
+  const match = output?.trim().match(/^(\d+)\s+(\S+)$/);
+  const cpuSeconds = match ? parseCpuTime(match[2]) : null;
+  if (!match || cpuSeconds === null) return null;
+  return { rssBytes: Number(match[1]) * 1024, cpuSeconds, handles: null };
+}
+
+function readUsage(pid: number): Promise<RawUsage | null> {
+  if (process.platform === "win32") return readWindowsUsage(pid);
+  if (process.platform === "linux") return readLinuxUsage(pid);
+  return readMacUsage(pid);
+}
+
+function checkMemory(sample: ResourceSample, memoryWarningMb: number, now: number): void {
+  if (sample.rssMb < memoryWarningMb || now - lastWarningAt < WARNING_COOLDOWN_MS) return;
+  lastWarningAt = now;
+  const warning: BackendResourceWarning = { pid: sample.pid, rssMb: sample.rssMb, memoryWarningMb };
+  lastWarning = { ...warning, at: sample.sampledAt };
+  log.warn(`🐘 Backend uses ${sample.rssMb} MB memory (limit ${memoryWarningMb} MB)`);
+  emitEvent("backend:resource-warning", warning);
+}
+
+/**
+ * Take one sample of the backend process.
+ *
+ * @returns null if the process could not be read (e.g. it just exited)
+ */
+export async function sampleBackendResources(pid: number): Promise<ResourceSample | null> {
+  const usage = await readUsage(pid);
+  if (!usage) return null;
+
+  const now = Date.now();
+  let cpuPercent: number | null = null;
+  // After a restart the CPU time starts over with the new process
+  if (previous && previous.pid === pid && now > previous.at) {
+    const share = (usage.cpuSeconds - previous.cpuSeconds) / ((now - previous.at) / 1000);
+    cpuPercent = Math.max(0, Math.round((share / os.cpus().length) * 1000) / 10);
+  }
+  previous = { pid, cpuSeconds: usage.cpuSeconds, at: now };
+
+  const sample: ResourceSample = {
+    pid,
+    rssMb: Math.round((usage.rssBytes / 1024 / 1024) * 10) / 10,
+    cpuPercent,
+    handles: usage.handles,
+    sampledAt: new Date(now).toISOString(),
+  };
+  samples.push(sample);
+  if (samples.length > MAX_SAMPLES) samples.shift();
+  checkMemory(sample, getSettings().resourceMonitor.memoryWarningMb, now);
+  return sample;
+}
+
+/**
+ * Samples so far and the latest one of the running backend.
+ */
+export function getBackendResources(manager: BackendManager): BackendResources {
+  const { enabled, memoryWarningMb } = getSettings().resourceMonitor;
+  const latest = samples[samples.length - 1];
+  const pid = manager.process?.running ? manager.process.pid : undefined;
+  return {
+    enabled,
+    memoryWarningMb,
+    current: latest && latest.pid === pid ? latest : null,
+    samples: [...samples],
+    lastWarning,
+  };
+}
+
+/**
+ * Sample the backend periodically. The interval is read from the settings
+ * before each sample, so changes apply without a restart.
+ */
+export function startResourceMonitoring(manager: BackendManager): void {
+  if (timer) return;
+  const tick = async (): Promise<void> => {
+    const { enabled } = getSettings().resourceMonitor;
+    const instance = manager.process;
+    if (enabled && instance?.running && instance.pid !== undefined) {
+      await sampleBackendResources(instance.pid);
+    }
+    const intervalMs = Math.max(5, getSettings().resourceMonitor.intervalSeconds) * 1000;
+    timer = setTimeout(() => void tick(), intervalMs);
+  };
+  timer = setTimeout(() => void tick(), 0);
+}
+
+/**
+ * Register IPC handlers for backend resource monitoring.
+ */
+export function registerResourceHandlers(manager: BackendManager): void {
+  handle("get-backend-resources", () => getBackendResources(manager), "read");
+}
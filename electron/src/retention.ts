@@ -0,0 +1,278 @@
+/**
+ * Billino Desktop – Data Retention
+ *
+ * Long-lived installs collect files nobody needs any more. The nightly
+ * maintenance (maintenance.ts) applies these rules (settings `retention`):
+ * - logs: rotated shell and backend logs older than logging.retentionDays
+ *   (the active files are never touched)
+ * - cache: the webview's HTTP cache once it exceeds `cacheMaxMb` – Billino
+ *   keeps no thumbnail files of its own, previews and images the frontend
+ *   loaded end up there
+ * - exports: files the backend prepared for download (year archives,
+ *   anonymized databases) older than `exportMaxAgeDays`
+ * - pdfs: stored PDFs of invoices that were deleted (only with
+ *   `orphanedPdfs`, off by default)
+ *
+ * Every cleanup first builds a dry-run report of what would be deleted and
+ * logs it; with `dryRun` set nothing more happens. Otherwise exactly the
+ * listed entries are deleted: the backend gets the export file names and
+ * PDF ids of the report and re-checks only those, so nothing is deleted
+ * that the report did not show. `preview-retention-cleanup` returns that
+ * report for the settings UI, `run-retention-cleanup` deletes.
+ */
+
+import { app, session } from "electron";
+import fs from "fs";
+import path from "path";
+import log from "electron-log/main";
+import { callBackend } from "./api";
+import { getBackendLogFilePath } from "./backendlog";
+import { handle } from "./ipc";
+import { getLogDir, getLogFilePath } from "./logging";
+import { getSettings } from "./settings";
+
+export type RetentionCategory = "logs" | "cache" | "exports" | "pdfs";
+
+export interface RetentionItem {
+  category: RetentionCategory;
+  /** File name, or a description for cache and PDFs. */
+  name: string;
+  sizeBytes: number;
+  /** Id of the stored PDF (pdfs only). */
+  pdfId?: number;
+}
+
+export interface RetentionReport {
+  /** true: nothing was deleted. */
+  dryRun: boolean;
+  items: RetentionItem[];
+  totals: Record<RetentionCategory, { count: number; bytes: number }>;
+  /** Bytes freed (or to be freed in a dry run). */
+  freedBytes: number;
+  /** Rules that could not be applied, e.g. the backend was unreachable. */
+  errors: string[];
+  createdAt: string;
+}
+
+interface RawCleanupResult {
+  dry_run: boolean;
+  exports: Array<{ job_id: string; filename: string; size_bytes: number; modified_at: number }>;
+  orphaned_pdfs: Array<{
+    id: number;
+    type: string;
+    invoice_id: number | null;
+    summary_invoice_id: number | null;
+    size_bytes: number;
+  }>;
+  freed_bytes: number;
+}
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+let lastReport: RetentionReport | null = null;
+
+function getReportPath(): string {
+  return path.join(app.getPath("userData"), "retention-report.json");
+}
+
+/**
+ * Rotated log files older than `retentionDays`.
+ */
+export function findExpiredLogs(retentionDays: number, now: number = Date.now()): RetentionItem[] {
+  const active = new Set([path.basename(getLogFilePath()), path.basename(getBackendLogFilePath())]);
+  const cutoff = now - retentionDays * DAY_MS;
+  let names: string[];
+  try {
+    names = fs.readdirSync(getLogDir());
+  } catch {
+    return [];
+  }
+  const items: RetentionItem[] = [];
+  for (const name of names) {
+    if (!name.endsWith(".log") || active.has(name)) continue;
+    try {
+      const stat = fs.statSync(path.join(getLogDir(), name));
+      if (stat.isFile() && stat.mtimeMs < cutoff) {
+        items.push({ category: "logs", name, sizeBytes: stat.size });
+      }
+    } catch {
+      // Deleted in the meantime
+    }
+  }
+  return items;
+}
+
+async function findCacheOverflow(cacheMaxMb: number): Promise<RetentionItem[]> {
+  const sizeBytes = await session.defaultSession.getCacheSize();
+  return sizeBytes > cacheMaxMb * 1024 * 1024
+    ? [{ category: "cache", name: "HTTP-Cache", sizeBytes }]
+    : [];
+}
+
+/**
+ * Ask the backend for expired exports and orphaned PDFs, or delete them.
+ *
+ * @param selection Delete only these entries of an earlier dry run
+ */
+async function cleanBackend(
+  dryRun: boolean,
+  selection?: { exportFilenames: string[]; pdfIds: number[] }
+): Promise<RetentionItem[]> {
+  const { exportMaxAgeDays, orphanedPdfs } = getSettings().retention;
+  const raw = (await callBackend("POST /database/cleanup", {
+    body: {
+      export_max_age_days: exportMaxAgeDays,
+      orphaned_pdfs: orphanedPdfs,
+      dry_run: dryRun,
+      ...(selection && {
+        export_filenames: selection.exportFilenames,
+        pdf_ids: selection.pdfIds,
+      }),
+    },
+  })) as RawCleanupResult;
+  return [
+    ...raw.exports.map((entry) => ({
+      category: "exports" as const,
+      name: entry.filename,
+      sizeBytes: entry.size_bytes,
+    })),
+    ...raw.orphaned_pdfs.map((entry) => ({
+      category: "pdfs" as const,
+      name:
+        entry.invoice_id !== null
+          ? `PDF der gelöschten Rechnung ${entry.invoice_id}`
+          : `PDF der gelöschten Sammelrechnung ${entry.summary_invoice_id}`,
+      sizeBytes: entry.size_bytes,
+      pdfId: entry.id,
+    })),
+  ];
+}
+
+function buildReport(dryRun: boolean, items: RetentionItem[], errors: string[]): RetentionReport {
+  const totals: RetentionReport["totals"] = {
+    logs: { count: 0, bytes: 0 },
+    cache: { count: 0, bytes: 0 },
+    exports: { count: 0, bytes: 0 },
+    pdfs: { count: 0, bytes: 0 },
+  };
+  for (const item of items) {
+    totals[item.category].count++;
+    totals[item.category].bytes += item.sizeBytes;
+  }
+  return {
+    dryRun,
+    items,
+    totals,
+    freedBytes: items.reduce((sum, item) => sum + item.sizeBytes, 0),
+    errors,
+    createdAt: new Date().toISOString(),
+  };
+}
+
+function logReport(report: RetentionReport): void {
+  const verb = report.dryRun ? "would delete" : "deleted";
+  const parts = (Object.entries(report.totals) as [RetentionCategory, { count: number }][])
+    .filter(([, total]) => total.count > 0)
+    .map(([category, total]) => `${total.count} ${category}`);
+  const megabytes = (report.freedBytes / 1024 / 1024).toFixed(1);
+  log.info(`🗑️ Retention ${verb}: ${parts.join(", ") || "nothing"} (${megabytes} MB)`);
+  for (const error of report.errors) log.warn(`⚠️ Retention: ${error}`);
+}
+
+function saveReport(report: RetentionReport): RetentionReport {
+  lastReport = report;
+  try {
+    fs.writeFileSync(getReportPath(), JSON.stringify(report, null, 2), "utf-8");
+  } catch (err) {
+    log.warn(`⚠️ Could not save retention report: ${err}`);
+  }
+  return report;
+}
+
+/**
+ * What the retention rules would delete now (nothing is deleted).
+ */
+export async function previewRetentionCleanup(): Promise<RetentionReport> {
+  const { retention, logging } = getSettings();
+  const items: RetentionItem[] = [...findExpiredLogs(logging.retentionDays)];
+  const errors: string[] = [];
+  try {
+    items.push(...(await findCacheOverflow(retention.cacheMaxMb)));
+  } catch (err) {
+    errors.push(`cache: ${err}`);
+  }
+  try {
+    items.push(...(await cleanBackend(true)));
+  } catch (err) {
+    errors.push(`backend: ${err}`);
+  }
+  return buildReport(true, items, errors);
+}
+
+/**
+ * Apply the retention rules: build and log the dry-run report, then delete
+ * exactly what it lists (unless `retention.dryRun` is set).
+ */
+export async function runRetentionCleanup(): Promise<RetentionReport> {
+  const preview = await previewRetentionCleanup();
+  logReport(preview);
+  if (getSettings().retention.dryRun) return saveReport(preview);
+
+  const deleted: RetentionItem[] = [];
+  const errors = [...preview.errors];
+  for (const item of preview.items.filter((entry) => entry.category === "logs")) {
+    try {
+      fs.unlinkSync(path.join(getLogDir(), item.name));
+      deleted.push(item);
+    } catch (err) {
+      errors.push(`${item.name}: ${err}`);
+    }
+  }
+  const cache = preview.items.find((entry) => entry.category === "cache");
+  if (cache) {
+    try {
+      await session.defaultSession.clearCache();
+      deleted.push(cache);
+    } catch (err) {
+      errors.push(`cache: ${err}`);
+    }
+  }
+  const exportFilenames = preview.items
+    .filter((entry) => entry.category === "exports")
+    .map((entry) => entry.name);
+  const pdfIds = preview.items.flatMap((entry) =>
+    entry.category === "pdfs" && entry.pdfId !== undefined ? [entry.pdfId] : []
+  );
+  if (exportFilenames.length > 0 || pdfIds.length > 0) {
+    try {
+      deleted.push(...(await cleanBackend(false, { exportFilenames, pdfIds })));
+    } catch (err) {
+      errors.push(`backend: ${err}`);
+    }
+  }
+
+  const report = buildReport(false, deleted, errors);
+  logReport(report);
+  return saveReport(report);
+}
+
+/**
+ * Result of the last cleanup (also after a restart), null if none ran yet.
+ */
+export function getLastRetentionReport(): RetentionReport | null {
+  if (lastReport) return lastReport;
+  try {
+    return (lastReport = JSON.parse(fs.readFileSync(getReportPath(), "utf-8")) as RetentionReport);
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Register IPC handlers for data retention.
+ */
+export function registerRetentionHandlers(): void {
+  handle("preview-retention-cleanup", () => previewRetentionCleanup(), "read");
+  handle("get-last-retention-report", () => getLastRetentionReport(), "read");
+  handle("run-retention-cleanup", () => runRetentionCleanup(), "destructive");
+}
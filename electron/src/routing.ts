@@ -0,0 +1,188 @@
+/**
+ * Billino Desktop – Backend Routing
+ *
+ * Blue-green restarts (main.ts) start a second backend on another port and
+ * switch over once it is healthy. The frontend is built with a fixed
+ * backend URL (the configured port), so renderer requests to that origin
+ * are redirected to the active instance. Main-process code uses
+ * getBackendUrl(), which already points there.
+ *
+ * The same redirect covers a backend that had to start on another port
+ * because the configured one was taken (`portStrategy` auto/range).
+ *
+ * The renderer may still hold older URLs: the port the frontend was built
+ * for, a port configured before, or one it learned from an earlier
+ * `backend:url-changed` and kept in its storage. Every port the backend
+ * ever ran on is remembered in backend-ports.json, and requests to any of
+ * them are redirected to the active port. The rule is rebuilt whenever
+ * the port changes.
+ *
+ * A plain restart (`restart-backend`) stops the backend first and waits
+ * until its port is released before starting it again.
+ */
+
+import { app, session } from "electron";
+import fs from "fs";
+import net from "net";
+import path from "path";
+import log from "electron-log/main";
+import { DEFAULT_CONFIG, getActivePort, getConfig, onBackendUrlChanged } from "./config";
+
+export interface BlueGreenResult {
+  /** Backend URL after the switch. */
+  url: string;
+  port: number;
+  durationMs: number;
+}
+
+export interface RestartResult {
+  url: string;
+  port: number;
+  /** Spawn attempts until the backend was healthy. */
+  attempts: number;
+  /** Stopping the old process. */
+  stopMs: number;
+  /** Waiting for the port to be released. */
+  portWaitMs: number;
+  /** Spawning until healthy (all attempts). */
+  startMs: number;
+  durationMs: number;
+}
+
+const PORT_POLL_INTERVAL_MS = 200;
+/** Remembered ports; older ones are dropped. */
+const MAX_KNOWN_PORTS = 16;
+
+/**
+ * A free port on `host`: `preferred` if available, otherwise one assigned
+ * by the OS.
+ */
+export async function findFreePort(host: string, preferred: number): Promise<number> {
+  const tryListen = (port: number): Promise<number | null> =>
+    new Promise((resolve) => {
+      const server = net.createServer();
+      server.once("error", () => resolve(null));
+      server.listen(port, host, () => {
+        const { port: bound } = server.address() as net.AddressInfo;
+        server.close(() => resolve(bound));
+      });
+    });
+
+  const port = (preferred <= 65535 ? await tryListen(preferred) : null) ?? (await tryListen(0));
+  if (port === null) throw new Error(`No free port on ${host}`);
+  return port;
+}
+
+/**
+ * Port for a new backend according to the configured `portStrategy`.
+ *
+ * @throws Error if the port is taken ("fixed") or the range is exhausted
+ */
+export async function selectBackendPort(): Promise<number> {
+  const { host, port, portStrategy, portRangeEnd } = getConfig();
+  if (portStrategy === "auto") return findFreePort(host, port);
+
+  const last = portStrategy === "range" ? portRangeEnd : port;
+  for (let candidate = port; candidate <= last; candidate++) {
+    if ((await findFreePort(host, candidate)) === candidate) return candidate;
+  }
+  throw new Error(
+    portStrategy === "range"
+      ? `No free port between ${port} and ${portRangeEnd} on ${host}`
+      : `Port ${port} on ${host} is already in use`
+  );
+}
+
+/**
+ * Wait until nothing listens on `port` any more (a stopped process may
+ * keep its socket for a moment).
+ *
+ * @returns false if the port was still taken after `timeoutMs`
+ */
+export async function waitForPortRelease(
+  host: string,
+  port: number,
+  timeoutMs: number
+): Promise<boolean> {
+  const deadline = Date.now() + timeoutMs;
+  for (;;) {
+    if ((await findFreePort(host, port)) === port) return true;
+    if (Date.now() >= deadline) return false;
+    await new Promise((resolve) => setTimeout(resolve, PORT_POLL_INTERVAL_MS));
+  }
+}
+
+function getKnownPortsPath(): string {
+  return path.join(app.getPath("userData"), "backend-ports.json");
+}
+
+function loadKnownPorts(): number[] {
+  try {
+    const data = JSON.parse(fs.readFileSync(getKnownPortsPath(), "utf-8")) as { ports?: unknown };
+    return Array.isArray(data.ports) ? data.ports.filter(Number.isInteger) : [];
+  } catch {
+    return [];
+  }
+}
+
+/**
+ * Every port a backend ran on (backend-ports.json, most recent last).
+ */
+export function getKnownBackendPorts(): number[] {
+  return loadKnownPorts();
+}
+
+/**
+ * Add `port` to backend-ports.json (most recent last).
+ */
+function rememberPort(port: number): number[] {
+  const ports = [...loadKnownPorts().filter((known) => known !== port), port].slice(
+    -MAX_KNOWN_PORTS
+  );
+  const file = getKnownPortsPath();
+  try {
+    fs.writeFileSync(`${file}.tmp`, JSON.stringify({ ports }, null, 2));
+    fs.renameSync(`${file}.tmp`, file);
+  } catch (err) {
+    log.warn(`⚠️ Could not save backend ports: ${err}`);
+  }
+  return ports;
+}
+
+/**
+ * (Re-)register the redirect from all stale ports to the active one.
+ */
+function updateBackendRedirect(knownPorts: number[]): void {
+  const { host, port } = getConfig();
+  const active = getActivePort();
+  const stale = new Set([DEFAULT_CONFIG.port, port, ...knownPorts]);
+  stale.delete(active);
+
+  const { webRequest } = session.defaultSession;
+  if (stale.size === 0) {
+    webRequest.onBeforeRequest(null);
+    return;
+  }
+
+  const hosts = new Set([host, "127.0.0.1", "localhost"]);
+  const urls = [...hosts].flatMap((name) => [...stale].map((old) => `http://${name}:${old}/*`));
+  webRequest.onBeforeRequest({ urls }, (details, callback) => {
+    const url = new URL(details.url);
+    url.port = String(getActivePort());
+    callback({ redirectURL: url.toString() });
+  });
+}
+
+let redirectInstalled = false;
+
+/**
+ * Redirect renderer requests for stale backend ports to the active one
+ * (call after the configuration is loaded and the port is chosen).
+ */
+export function installBackendRedirect(): void {
+  if (!redirectInstalled) {
+    redirectInstalled = true;
+    onBackendUrlChanged(({ port }) => updateBackendRedirect(rememberPort(port)));
+  }
+  updateBackendRedirect(rememberPort(getActivePort()));
+}
@@ -0,0 +1,304 @@
+/**
+ * Billino Desktop – Safe Mode
+ *
+ * For repairs when a normal start fails (broken database, crashing
+ * plugin, faulty update). Started with `--safe-mode` or via
+ * "Hilfe → Im abgesicherten Modus neu starten":
+ * - the backend is not spawned automatically; the safe-mode window can
+ *   start it on demand, then without the backup scheduler
+ * - automation hooks, threshold monitoring, nightly maintenance, the task
+ *   queue, exchange rate updates, the PDF mirror and the API check stay off
+ * - instead of the frontend a diagnostics window opens that lists crash
+ *   dumps and local backups and can restore one
+ *
+ * "Normal neu starten" relaunches without the flag. Safe mode is also
+ * entered automatically after repeated failed launches (crashloop.ts).
+ */
+
+import { app, BrowserWindow, Menu, MenuItemConstructorOptions } from "electron";
+import fs from "fs";
+import path from "path";
+import log from "electron-log/main";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+import { registerWindow, WindowRole } from "./windows";
+
+export interface LocalBackup {
+  filename: string;
+  path: string;
+  sizeBytes: number;
+  createdIso: string;
+}
+
+const SAFE_MODE_FLAG = "--safe-mode";
+
+let safeMode = false;
+let backendStart: Promise<void> | null = null;
+
+/**
+ * Switch the shell into safe mode (call before startup decisions).
+ */
+export function enableSafeMode(): void {
+  safeMode = true;
+  log.warn("🛟 Safe mode: backend, schedulers and plugins are not started");
+}
+
+/**
+ * Whether the shell runs in safe mode.
+ */
+export function isSafeMode(): boolean {
+  return safeMode;
+}
+
+/**
+ * Backup files in the data directory (daily, manual and safety backups),
+ * newest first. Read from disk, so it works without a backend.
+ */
+export function listLocalBackups(): LocalBackup[] {
+  const root = path.join(app.getPath("userData"), "backups");
+  const dirs = [root];
+  try {
+    for (const entry of fs.readdirSync(root, { withFileTypes: true })) {
+      if (entry.isDirectory()) dirs.push(path.join(root, entry.name));
+    }
+  } catch {
+    return [];
+  }
+
+  const backups: LocalBackup[] = [];
+  for (const dir of dirs) {
+    for (const filename of fs.readdirSync(dir)) {
+      if (!filename.endsWith(".db")) continue;
+      const filePath = path.join(dir, filename);
+      const stat = fs.statSync(filePath);
+      backups.push({
+        filename,
+        path: filePath,
+        sizeBytes: stat.size,
+        createdIso: stat.mtime.toISOString(),
+      });
+    }
+  }
+  return backups.sort((a, b) => b.createdIso.localeCompare(a.createdIso));
+}
+
+/**
+ * Restart Billino, with or without safe mode. The regular shutdown
+ * (backup, backend stop) runs first.
+ */
+export function relaunchApp(safe: boolean): void {
+  const args = process.argv.slice(1).filter((arg) => arg !== SAFE_MODE_FLAG);
+  if (safe) args.push(SAFE_MODE_FLAG);
+  log.info(`🔄 Relaunching ${safe ? "in safe mode" : "normally"}`);
+  app.relaunch({ args });
+  app.quit();
+}
+
+/**
+ * Application menu: the standard menus plus the safe-mode entry.
+ */
+export function installAppMenu(): void {
+  const help: MenuItemConstructorOptions = {
+    label: "Hilfe",
+    submenu: [
+      safeMode
+        ? { label: "Normal neu starten", click: () => relaunchApp(false) }
+        : { label: "Im abgesicherten Modus neu starten", click: () => relaunchApp(true) },
+    ],
+  };
+  const template: MenuItemConstructorOptions[] = [
+    ...(process.platform === "darwin" ? [{ role: "appMenu" as const }] : []),
+    { role: "fileMenu" },
+    { role: "editMenu" },
+    { role: "viewMenu" },
+    { role: "windowMenu" },
+    help,
+  ];
+  Menu.setApplicationMenu(Menu.buildFromTemplate(template));
+}
+
+const SAFE_MODE_HTML = `<!DOCTYPE html>
+<html lang="de">
+<head>
+<meta charset="utf-8">
+<meta http-equiv="Content-Security-Policy"
+  content="default-src 'none'; style-src 'unsafe-inline'; script-src 'unsafe-inline'">
+<title>Billino – Abgesicherter Modus</title>
+<style>
+  body { margin: 0; padding: 16px 24px; font: 14px "Segoe UI", sans-serif; color: #222; }
+  h1 { font-size: 20px; } h2 { font-size: 16px; margin-top: 24px; }
+  .actions { display: flex; gap: 8px; }
+  #status { margin: 12px 0; color: #555; } .error { color: #b00020; }
+  table { border-collapse: collapse; width: 100%; }
+  td, th { text-align: left; padding: 4px 8px; border-bottom: 1px solid #ddd; }
+  dl { display: grid; grid-template-columns: max-content 1fr; gap: 4px 16px; }
+  dt { color: #555; } dd { margin: 0; }
+</style>
+</head>
+<body>
+<h1>Abgesicherter Modus</h1>
+<p>Billino wurde ohne Billino-Dienst, Zeitpläne und Automationen gestartet. Hier
+  lassen sich Probleme untersuchen und Sicherungen wiederherstellen.</p>
+<div class="actions">
+  <button id="start">Billino-Dienst starten</button>
+  <button id="console">Entwicklerkonsole</button>
+  <button id="restart">Normal neu starten</button>
+</div>
+<p id="crash-loop" class="error" hidden></p>
+<p id="status">Der Billino-Dienst läuft nicht. Zum Prüfen und Wiederherstellen von
+  Sicherungen bitte starten.</p>
+<h2>Diagnose</h2>
+<dl id="diagnostics"></dl>
+<h2>Sicherungen</h2>
+<table>
+  <thead><tr><th>Datei</th><th>Erstellt</th><th>Größe</th><th></th></tr></thead>
+  <tbody id="backups"></tbody>
+</table>
+<script>
+  const api = window.billino;
+  const status = document.getElementById("status");
+  let backendRunning = false;
+  const show = (text, isError) => {
+    status.textContent = text;
+    status.className = isError ? "error" : "";
+  };
+  const fail = (err) => show(err.message + (err.hint ? " " + err.hint : ""), true);
+  const cell = (row, text) => {
+    const td = row.insertCell();
+    td.textContent = text;
+    return td;
+  };
+  const button = (td, label, onclick) => {
+    const b = document.createElement("button");
+    b.textContent = label;
+    b.className = "needs-backend";
+    b.disabled = !backendRunning;
+    b.onclick = onclick;
+    td.appendChild(b);
+  };
+
+  const inspect = async (backup) => {
+    try {
+      const info = await api.inspectBackup(backup.path);
+      show(backup.filename + ": " + info.invoiceCount + " Rechnungen" +
+        (info.lastInvoiceDate ? ", letzte vom " + info.lastInvoiceDate : "") +
+        (info.compatible ? "" : " – mit dieser Version nicht kompatibel"), !info.compatible);
+    } catch (err) { fail(err); }
+  };
+  const restore = async (backup) => {
+    const question = backup.filename + " wiederherstellen?";
+    if (!confirm(question + " Der aktuelle Stand wird vorher gesichert.")) return;
+    show("Wiederherstellung läuft…");
+    try {
+      const result = await api.restoreBackup(backup.path, { scope: "full" });
+      show("Wiederhergestellt. Vorheriger Stand gesichert unter " +
+        (result.safetyBackupPath || "–") + ". Jetzt normal neu starten.");
+      loadBackups();
+    } catch (err) { fail(err); }
+  };
+
+  const loadDiagnostics = async () => {
+    const info = await api.getDiagnostics();
+    const list = document.getElementById("diagnostics");
+    const rows = [
+      ["Version", info.version],
+      ["System", info.platform + " " + info.arch],
+      ["Datenordner", info.userData],
+      ["Absturzberichte", info.crashDumps.length + " in " + info.crashDumpDir],
+    ];
+    for (const [label, value] of rows) {
+      list.appendChild(document.createElement("dt")).textContent = label;
+      list.appendChild(document.createElement("dd")).textContent = value;
+    }
+  };
+  const loadLaunchHistory = async () => {
+    const history = await api.getLaunchHistory();
+    if (!history.crashLoopDetected) return;
+    const last = history.launches.find((launch) => launch.reason);
+    const notice = document.getElementById("crash-loop");
+    notice.textContent = "Billino konnte mehrmals hintereinander nicht starten und wurde " +
+      "deshalb im abgesicherten Modus geöffnet." + (last ? " Zuletzt: " + last.reason : "");
+    notice.hidden = false;
+  };
+  const loadBackups = async () => {
+    const body = document.getElementById("backups");
+    body.textContent = "";
+    const backups = await api.listLocalBackups();
+    if (backups.length === 0) cell(body.insertRow(), "Keine Sicherungen gefunden");
+    for (const backup of backups) {
+      const row = body.insertRow();
+      cell(row, backup.filename);
+      cell(row, new Date(backup.createdIso).toLocaleString("de-DE"));
+      cell(row, (backup.sizeBytes / 1048576).toFixed(1) + " MB");
+      const actions = cell(row, "");
+      button(actions, "Prüfen", () => inspect(backup));
+      button(actions, "Wiederherstellen", () => restore(backup));
+    }
+  };
+
+  document.getElementById("start").onclick = async (event) => {
+    event.target.disabled = true;
+    show("Billino-Dienst wird gestartet…");
+    try {
+      await api.startSafeModeBackend();
+      backendRunning = true;
+      document.querySelectorAll(".needs-backend").forEach((b) => { b.disabled = false; });
+      show("Der Billino-Dienst läuft (ohne Zeitpläne).");
+    } catch (err) {
+      event.target.disabled = false;
+      fail(err);
+    }
+  };
+  document.getElementById("console").onclick = () => api.toggleDeveloperConsole();
+  document.getElementById("restart").onclick = () => api.relaunchApp(false);
+  loadLaunchHistory().catch(fail);
+  loadDiagnostics().catch(fail);
+  loadBackups().catch(fail);
+</script>
+</body>
+</html>`;
+
+/**
+ * Open the safe-mode window. It takes the role of the main window, so
+ * closing it quits Billino.
+ */
+export function createSafeModeWindow(
+  onClosed: (role: WindowRole, remaining: number) => void
+): BrowserWindow {
+  const window = new BrowserWindow({
+    width: 900,
+    height: 700,
+    title: "Billino – Abgesicherter Modus",
+    icon: path.join(__dirname, "..", "icons", "icon.ico"),
+    webPreferences: {
+      preload: path.join(__dirname, "preload.js"),
+      nodeIntegration: false,
+      contextIsolation: true,
+    },
+  });
+  registerWindow(window, "main", onClosed);
+  window.loadURL(`data:text/html;charset=utf-8,${encodeURIComponent(SAFE_MODE_HTML)}`);
+  return window;
+}
+
+/**
+ * Register IPC handlers for safe mode.
+ *
+ * @param startBackend Spawns the backend and resolves once it is healthy
+ */
+export function registerSafeModeHandlers(startBackend: () => Promise<void>): void {
+  handle("get-safe-mode", () => isSafeMode(), "read");
+  handle("list-local-backups", () => listLocalBackups(), "read");
+  handle("start-safe-mode-backend", () => {
+    if (!safeMode) {
+      throw new AppError("invalid_state", "Backend is managed automatically outside safe mode");
+    }
+    // Repeated clicks wait for the same start
+    backendStart ??= startBackend().catch((err) => {
+      backendStart = null;
+      throw err;
+    });
+    return backendStart;
+  });
+  handle("relaunch-app", (_event, safe?: boolean) => relaunchApp(Boolean(safe)));
+}
@@ -0,0 +1,42 @@
+/**
+ * Billino Desktop – Save Dialog
+ *
+ * Exports that write a file outside the app data (calendar, trace,
+ * anonymized database) never take a destination path from the renderer:
+ * the main process asks the user with the native save dialog, so a
+ * compromised renderer cannot overwrite arbitrary files.
+ */
+
+import { app, BrowserWindow, dialog, FileFilter, SaveDialogOptions } from "electron";
+import path from "path";
+import log from "electron-log/main";
+
+export interface SavePathRequest {
+  /** Dialog title. */
+  title: string;
+  /** Suggested file name (in the user's documents folder). */
+  defaultName: string;
+  filters: FileFilter[];
+}
+
+/**
+ * Ask the user where to save an export.
+ *
+ * @returns Absolute path, or null if the dialog was cancelled
+ */
+export async function chooseSavePath(request: SavePathRequest): Promise<string | null> {
+  const options: SaveDialogOptions = {
+    title: request.title,
+    defaultPath: path.join(app.getPath("documents"), request.defaultName),
+    filters: request.filters,
+  };
+  const parent = BrowserWindow.getFocusedWindow();
+  const { canceled, filePath } = parent
+    ? await dialog.showSaveDialog(parent, options)
+    : await dialog.showSaveDialog(options);
+  if (canceled || !filePath) {
+    log.info(`💾 Save dialog cancelled: ${request.title}`);
+    return null;
+  }
+  return filePath;
+}
@@ -0,0 +1,83 @@
+import { app } from "electron";
+import fs from "fs";
+import os from "os";
+import path from "path";
+import {
+  loadSession,
+  recordCommand,
+  recordEvent,
+  recordRequest,
+  replaySession,
+  startSessionRecording,
+  stopSessionRecording,
+} from "./session";
+
+describe("replaySession", () => {
+  let dir: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), "billino-session-"));
+    jest.spyOn(app, "getPath").mockReturnValue(dir);
+  });
+
+  afterEach(() => {
+    stopSessionRecording();
+    jest.restoreAllMocks();
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  function recordShortSession(): string {
+    const file = startSessionRecording();
+    recordCommand("get-settings", [], 1.23, "ok");
+    recordEvent("settings:changed", { theme: "dark" });
+    recordCommand("send-invoice-email", [7, { to: "max@example.com" }], 80, "ok");
+    recordRequest("trace-1", "POST", "/invoices/7/email", undefined, 200, 75, "{}");
+    recordCommand("delete-invoice", [42], 3, "error", "Rechnung nicht gefunden");
+    stopSessionRecording();
+    return file;
+  }
+
+  it("spielt die Befehle einer Aufzeichnung in Reihenfolge ab", async () => {
+    const entries = loadSession(recordShortSession());
+    const calls: Array<[string, unknown[]]> = [];
+
+    const report = await replaySession(entries, (channel, args) => {
+      calls.push([channel, args]);
+    });
+
+    expect(entries.map((entry) => entry.type)).toEqual([
+      "command",
+      "event",
+      "command",
+      "request",
+      "command",
+    ]);
+    // Geschwärzte Werte werden als Platzhalter abgespielt
+    expect(calls).toEqual([
+      ["get-settings", []],
+      ["send-invoice-email", [7, { to: "[E-MAIL]" }]],
+      ["delete-invoice", [42]],
+    ]);
+    expect(report.commands).toBe(3);
+    expect(report.expectedEvents).toEqual(["settings:changed"]);
+  });
+
+  it("meldet Befehle mit anderem Ergebnis als aufgezeichnet", async () => {
+    const entries = loadSession(recordShortSession());
+
+    const report = await replaySession(entries, (channel) => {
+      if (channel === "send-invoice-email") throw new Error("SMTP nicht erreichbar");
+    });
+
+    expect(report.mismatches).toEqual([
+      {
+        seq: 3,
+        channel: "send-invoice-email",
+        expected: "ok",
+        actual: "error",
+        error: "Error: SMTP nicht erreichbar",
+      },
+      { seq: 5, channel: "delete-invoice", expected: "error", actual: "ok", error: undefined },
+    ]);
+  });
+});
@@ -0,0 +1,355 @@
+/**
+ * Billino Desktop – Debug Session Recording
+ *
+ * Opt-in (settings: debug.recordSession). While enabled, every invoked IPC
+ * command (arguments, duration, result status), every emitted event and
+ * every backend request of the shell (with its trace id as correlation id,
+ * body and response) is appended to a JSONL session file in
+ * AppData/Roaming/Billino/sessions/.
+ *
+ * A recorded session can be replayed against a set of command handlers with
+ * `replaySession()` to reproduce hard-to-trigger UI/lifecycle bugs, e.g. in
+ * tests; single backend requests are re-issued with `replayRequest()`
+ * (replay.ts). Arguments and payloads pass through PII redaction before
+ * they are written, so redacted values replay as their placeholders.
+ */
+
+import { app } from "electron";
+import path from "path";
+import crypto from "crypto";
+import fs from "fs";
+import { performance } from "perf_hooks";
+import log from "electron-log/main";
+import { handle } from "./ipc";
+import { redactValue } from "./redaction";
+import { getSettings, onSettingsChanged } from "./settings";
+
+export type CommandStatus = "ok" | "error";
+
+export interface SessionHeader {
+  type: "session";
+  startedAt: string;
+  version: string;
+  platform: string;
+}
+
+export interface CommandEntry {
+  type: "command";
+  seq: number;
+  /** Time since the recording started (ms). */
+  atMs: number;
+  channel: string;
+  args: unknown[];
+  durationMs: number;
+  status: CommandStatus;
+  error?: string;
+}
+
+export interface EventEntry {
+  type: "event";
+  seq: number;
+  atMs: number;
+  channel: string;
+  payload: unknown;
+}
+
+export interface RequestEntry {
+  type: "request";
+  seq: number;
+  atMs: number;
+  /** X-Trace-Id sent with the request; shared by all requests of a command. */
+  correlationId: string;
+  method: string;
+  path: string;
+  body?: unknown;
+  status: number;
+  durationMs: number;
+  /** Parsed response, omitted if larger than MAX_RECORDED_RESPONSE_CHARS. */
+  response?: unknown;
+  /** SHA-256 of the raw response text. */
+  responseHash: string;
+}
+
+export type SessionEntry = CommandEntry | EventEntry | RequestEntry;
+
+export interface SessionRecordingStatus {
+  recording: boolean;
+  file: string | null;
+}
+
+/** Commands whose arguments contain secrets: only the first one is recorded. */
+const SECRET_ARG_CHANNELS = new Set(["set-credential"]);
+
+/** Keep at most this many session files. */
+const MAX_SESSION_FILES = 10;
+/** Larger responses (PDFs, exports) are only recorded by hash. */
+export const MAX_RECORDED_RESPONSE_CHARS = 64 * 1024;
+
+let sessionFile: string | null = null;
+let sessionStart = 0;
+let seq = 0;
+
+/**
+ * Directory containing recorded session files.
+ */
+export function getSessionDir(): string {
+  return path.join(app.getPath("userData"), "sessions");
+}
+
+// ─── Recording ───────────────────────────────────────────────────────────────
+
+function append(entry: SessionHeader | SessionEntry): void {
+  if (!sessionFile) return;
+  try {
+    fs.appendFileSync(sessionFile, `${JSON.stringify(entry)}\n`, "utf-8");
+  } catch (err) {
+    // Recording is a debugging aid – never let it break a command
+    log.warn(`⚠️ Session recording failed, stopping: ${err}`);
+    sessionFile = null;
+  }
+}
+
+function pruneSessionFiles(): void {
+  try {
+    const dir = getSessionDir();
+    fs.readdirSync(dir)
+      .filter((name) => name.endsWith(".jsonl"))
+      .sort()
+      .reverse()
+      .slice(MAX_SESSION_FILES)
+      .forEach((name) => fs.unlinkSync(path.join(dir, name)));
+  } catch (err) {
+    log.warn(`⚠️ Could not prune old session files: ${err}`);
+  }
+}
+
+/**
+ * Start recording into a new session file.
+ *
+ * @returns Path of the session file
+ */
+export function startSessionRecording(): string {
+  if (sessionFile) return sessionFile;
+
+  const dir = getSessionDir();
+  fs.mkdirSync(dir, { recursive: true });
+  const startedAt = new Date();
+  const file = path.join(dir, `session_${startedAt.toISOString().replace(/[:.]/g, "-")}.jsonl`);
+
+  sessionFile = file;
+  sessionStart = performance.now();
+  seq = 0;
+  append({
+    type: "session",
+    startedAt: startedAt.toISOString(),
+    version: app.getVersion(),
+    platform: process.platform,
+  });
+  pruneSessionFiles();
+
+  log.info(`🎬 Recording debug session: ${file}`);
+  return file;
+}
+
+/**
+ * Stop the current recording (no-op if none is running).
+ */
+export function stopSessionRecording(): void {
+  if (!sessionFile) return;
+  log.info(`⏹️ Debug session recorded: ${sessionFile} (${seq} entries)`);
+  sessionFile = null;
+}
+
+/**
+ * Whether a session is being recorded and where.
+ */
+export function getSessionRecordingStatus(): SessionRecordingStatus {
+  return { recording: sessionFile !== null, file: sessionFile };
+}
+
+/**
+ * Record an invoked command. Called by the IPC layer for every command.
+ */
+export function recordCommand(
+  channel: string,
+  args: unknown[],
+  durationMs: number,
+  status: CommandStatus,
+  error?: string
+): void {
+  if (!sessionFile) return;
+  append({
+    type: "command",
+    seq: ++seq,
+    atMs: Math.round(performance.now() - sessionStart),
+    channel,
+    args: SECRET_ARG_CHANNELS.has(channel)
+      ? args.map((arg, i) => (i === 0 ? arg : "***"))
+      : args.map(redactValue),
+    durationMs: Math.round(durationMs * 10) / 10,
+    status,
+    ...(error !== undefined && { error: String(redactValue(error)) }),
+  });
+}
+
+/**
+ * Record an event emitted to the renderer.
+ */
+export function recordEvent(channel: string, payload: unknown): void {
+  if (!sessionFile) return;
+  append({
+    type: "event",
+    seq: ++seq,
+    atMs: Math.round(performance.now() - sessionStart),
+    channel,
+    payload: redactValue(payload),
+  });
+}
+
+/**
+ * Record a backend request sent by the shell.
+ *
+ * @param text Raw response text
+ */
+export function recordRequest(
+  correlationId: string,
+  method: string,
+  requestPath: string,
+  body: unknown,
+  status: number,
+  durationMs: number,
+  text: string
+): void {
+  if (!sessionFile) return;
+  let response: unknown;
+  if (text.length <= MAX_RECORDED_RESPONSE_CHARS) {
+    try {
+      response = redactValue(text ? JSON.parse(text) : null);
+    } catch {
+      response = redactValue(text);
+    }
+  }
+  append({
+    type: "request",
+    seq: ++seq,
+    atMs: Math.round(performance.now() - sessionStart),
+    correlationId,
+    method,
+    path: requestPath,
+    ...(body !== undefined && { body: redactValue(body) }),
+    status,
+    durationMs: Math.round(durationMs * 10) / 10,
+    ...(response !== undefined && { response }),
+    responseHash: hashResponse(text),
+  });
+}
+
+/**
+ * SHA-256 of a response text, as stored in RequestEntry.responseHash.
+ */
+export function hashResponse(text: string): string {
+  return crypto.createHash("sha256").update(text).digest("hex");
+}
+
+/**
+ * Start/stop recording according to the settings and follow later changes.
+ */
+export function initSessionRecording(): void {
+  const apply = (enabled: boolean): void => {
+    if (enabled) startSessionRecording();
+    else stopSessionRecording();
+  };
+
+  apply(getSettings().debug.recordSession);
+  onSettingsChanged((settings) => apply(settings.debug.recordSession));
+
+  handle("get-session-recording-status", () => getSessionRecordingStatus(), "read");
+}
+
+// ─── Replay ──────────────────────────────────────────────────────────────────
+
+/**
+ * Invokes a command during replay, e.g. a test double of the IPC layer.
+ */
+export type ReplayInvoker = (channel: string, args: unknown[]) => unknown | Promise<unknown>;
+
+export interface ReplayMismatch {
+  seq: number;
+  channel: string;
+  expected: CommandStatus;
+  actual: CommandStatus;
+  error?: string;
+}
+
+export interface ReplayReport {
+  /** Number of replayed commands. */
+  commands: number;
+  /** Commands whose result status differs from the recording. */
+  mismatches: ReplayMismatch[];
+  /** Event channels in recorded order, to compare against emitted events. */
+  expectedEvents: string[];
+}
+
+/**
+ * Read a recorded session file (header line is skipped).
+ */
+export function loadSession(file: string): SessionEntry[] {
+  return fs
+    .readFileSync(file, "utf-8")
+    .split(/\r?\n/)
+    .filter((line) => line.trim() !== "")
+    .map((line) => JSON.parse(line) as SessionHeader | SessionEntry)
+    .filter((entry): entry is SessionEntry => entry.type !== "session");
+}
+
+/**
+ * Replay the commands of a recorded session in order.
+ *
+ * @param entries Entries from loadSession()
+ * @param invoke Called for every recorded command with its arguments
+ * @param options.timing Keep the recorded delays between commands
+ */
+export async function replaySession(
+  entries: SessionEntry[],
+  invoke: ReplayInvoker,
+  options: { timing?: boolean } = {}
+): Promise<ReplayReport> {
+  const report: ReplayReport = { commands: 0, mismatches: [], expectedEvents: [] };
+  let lastAtMs = 0;
+
+  for (const entry of entries) {
+    if (entry.type === "event") {
+      report.expectedEvents.push(entry.channel);
+      continue;
+    }
+    // Backend requests follow from the commands (see replay.ts for single ones)
+    if (entry.type === "request") continue;
+
+    if (options.timing && entry.atMs > lastAtMs) {
+      await new Promise((resolve) => setTimeout(resolve, entry.atMs - lastAtMs));
+    }
+    lastAtMs = entry.atMs;
+
+    let actual: CommandStatus = "ok";
+    let error: string | undefined;
+    try {
+      await invoke(entry.channel, entry.args);
+    } catch (err) {
+      actual = "error";
+      error = String(err);
+    }
+
+    report.commands++;
+    if (actual !== entry.status) {
+      report.mismatches.push({
+        seq: entry.seq,
+        channel: entry.channel,
+        expected: entry.status,
+        actual,
+        error,
+      });
+    }
+  }
+
+  return report;
+}
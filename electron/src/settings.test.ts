@@ -0,0 +1,14 @@
+import { assertRendererPatch } from "./settings";
+
+describe("assertRendererPatch", () => {
+  it("erlaubt normale Abschnitte", () => {
+    expect(() => assertRendererPatch({ retention: { dryRun: true } })).not.toThrow();
+  });
+
+  it("lehnt Änderungen an Automationen ab", () => {
+    expect(() => assertRendererPatch({ automation: { allowedExecutables: ["/bin/sh"] } })).toThrow(
+      /automation/
+    );
+    expect(() => assertRendererPatch({ automation: { hooks: [] } })).toThrow(/automation/);
+  });
+});
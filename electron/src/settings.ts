@@ -0,0 +1,481 @@
+/**
+ * Billino Desktop – Shell Settings
+ *
+ * Persists desktop-shell settings (not business data – that lives in the
+ * backend DB) as JSON in AppData/Roaming/Billino/settings.json.
+ *
+ * Settings are grouped into sections; updates are merged per section so the
+ * renderer can change single values without sending the whole object.
+ * Sections in MAIN_PROCESS_SECTIONS are rejected in renderer updates – the
+ * automation hooks and their allowlist only change through hooks.ts after
+ * the user confirmed them in a native dialog.
+ */
+
+import { app } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { AppError } from "./errors";
+import type { GermanState } from "./holidays";
+import { handle } from "./ipc";
+
+export interface PowerSettings {
+  /** Defer non-urgent heavy jobs while running on battery. */
+  deferHeavyJobsOnBattery: boolean;
+}
+
+export interface LoggingSettings {
+  /** Rotate the shell log once it exceeds this size (MB). */
+  maxFileSizeMb: number;
+  /** Delete rotated logs older than this many days. */
+  retentionDays: number;
+  /** Keep at most this many rotated log files. */
+  maxArchivedFiles: number;
+}
+
+export interface RedactionSettings {
+  /** Mask IBANs, e-mail addresses and names in all log output. */
+  enabled: boolean;
+  /** Additional regular expressions whose matches are masked. */
+  extraPatterns: string[];
+  /** Names (customers, contacts) that should always be masked. */
+  names: string[];
+}
+
+export interface DebugSettings {
+  /** Record every IPC command and event to a session file for replay. */
+  recordSession: boolean;
+  /** Allow re-issuing recorded backend requests (they may change data). */
+  requestReplay: boolean;
+}
+
+export interface DunningLevel {
+  /** Days after the due date from which this level applies. */
+  daysOverdue: number;
+  /** Reminder fee in EUR charged with this level. */
+  fee: number;
+}
+
+export interface DunningSettings {
+  /** Payment term: invoices are due this many days after the invoice date. */
+  paymentTermDays: number;
+  /** Reminder levels in ascending order (1. Mahnung, 2. Mahnung, ...). */
+  levels: DunningLevel[];
+  /** Annual default interest in percent (0 = no interest). */
+  annualInterestPercent: number;
+}
+
+export interface SmallBusinessSettings {
+  /** Watch yearly revenue against the §19 UStG thresholds. */
+  monitorThresholds: boolean;
+  /** Warn when revenue reaches these percentages of a threshold. */
+  warnAtPercent: number[];
+}
+
+export interface ReportSettings {
+  /** On the first business day of a month, notify with last month's totals. */
+  monthlySummary: boolean;
+}
+
+export interface CalendarSettings {
+  /** Bundesland whose public holidays shift deadlines (null = nationwide only). */
+  state: GermanState | null;
+}
+
+/**
+ * - tls: implicit TLS (usually port 465)
+ * - starttls: plain connection upgraded via STARTTLS (usually port 587)
+ * - none: unencrypted (local relays only)
+ */
+export type SmtpSecurity = "tls" | "starttls" | "none";
+
+export interface EmailSettings {
+  smtpHost: string;
+  smtpPort: number;
+  security: SmtpSecurity;
+  username: string;
+  /** Sender address of outgoing invoices. */
+  fromAddress: string;
+}
+
+export interface WebDavBackupSettings {
+  /** Copy backups to a WebDAV server (Nextcloud, NAS, ...). */
+  enabled: boolean;
+  /** Target folder, e.g. https://cloud.example.de/remote.php/dav/files/me/Billino */
+  url: string;
+  username: string;
+}
+
+export interface PdfMirrorSettings {
+  /** Copy every invoice PDF into `folder` (Year/Month/Customer/). */
+  enabled: boolean;
+  folder: string;
+}
+
+export type BackendLogLevel = "DEBUG" | "INFO" | "WARNING" | "ERROR";
+
+/**
+ * Scheduling priority of the backend process: "belowNormal" is Windows'
+ * "Niedriger als normal" (nice 10 on Unix), "low" is "Niedrig" (nice 19).
+ */
+export type BackendPriority = "normal" | "belowNormal" | "low";
+
+/**
+ * Applied when the backend is (re)started; values beyond what the machine
+ * can handle are clamped (see tuning.ts).
+ */
+export interface BackendTuningSettings {
+  /** uvicorn worker processes. */
+  workers: number;
+  /** SQLite page cache per connection in MB (null = SQLite default, ~2 MB). */
+  sqliteCacheMb: number | null;
+  /** Backend log level (null = INFO, DEBUG in development). */
+  logLevel: BackendLogLevel | null;
+  /** Lower it so PDF batches don't make the desktop stutter on slow PCs. */
+  priority: BackendPriority;
+}
+
+export type AutomationEvent = "invoice.created" | "backup.finished" | "backend.crashed";
+
+export interface AutomationHook {
+  id: string;
+  event: AutomationEvent;
+  enabled: boolean;
+  /** Run a program or POST to a URL. */
+  type: "command" | "webhook";
+  /** Executable path (command) or URL (webhook); must be on the allowlist. */
+  target: string;
+  /** Program arguments with {{placeholders}} (command only). */
+  args: string[];
+  /** Request body template (webhook only; default: payload as JSON). */
+  body: string | null;
+}
+
+/**
+ * Not writable via update-settings: a compromised renderer could otherwise
+ * allow and run any program (see save-automation-hook in hooks.ts).
+ */
+export interface AutomationSettings {
+  hooks: AutomationHook[];
+  /** Executables hooks may run (absolute paths). */
+  allowedExecutables: string[];
+  /** Host names webhooks may call. */
+  allowedHosts: string[];
+}
+
+/**
+ * Nightly maintenance (maintenance.ts): runs once a day inside the time
+ * window, or earlier when the computer has been idle long enough.
+ */
+export interface MaintenanceSettings {
+  enabled: boolean;
+  /** Window start hour (0–23, local time). */
+  windowStartHour: number;
+  /** Window end hour (exclusive; may be smaller than the start: 22–4). */
+  windowEndHour: number;
+  /** Also run after this many idle minutes (0 = window only). */
+  idleMinutes: number;
+}
+
+/**
+ * Clock check against an NTP server at startup (timesync.ts). Off by
+ * default because it contacts an external server.
+ */
+export interface TimeSyncSettings {
+  enabled: boolean;
+  /** NTP server host name. */
+  server: string;
+  /** Larger offsets are reported as a warning. */
+  maxOffsetSeconds: number;
+}
+
+/**
+ * Cleanup rules of the nightly maintenance (retention.ts). Old logs follow
+ * `logging.retentionDays`.
+ */
+export interface RetentionSettings {
+  /** Export files prepared for download are deleted after this many days. */
+  exportMaxAgeDays: number;
+  /**
+   * Delete stored PDFs of deleted invoices. Off by default: they may be the
+   * only copy of an invoice that has to be kept.
+   */
+  orphanedPdfs: boolean;
+  /** Clear the webview cache once it is larger (MB). */
+  cacheMaxMb: number;
+  /** Only report what would be deleted. */
+  dryRun: boolean;
+}
+
+/**
+ * Memory/CPU sampling of the backend process (resources.ts).
+ */
+export interface ResourceMonitorSettings {
+  enabled: boolean;
+  /** Seconds between two samples. */
+  intervalSeconds: number;
+  /** Above this resident memory a warning is sent. */
+  memoryWarningMb: number;
+}
+
+export type CsvSeparator = ";" | "," | "\t";
+export type CsvEncoding = "utf-8" | "utf-8-bom" | "windows-1252";
+
+/** Exports that write CSV files (per-export overrides in CsvSettings). */
+export type CsvExportName = "fiscalArchive";
+
+export interface CsvDialect {
+  separator: CsvSeparator;
+  decimalMark: "," | ".";
+  encoding: CsvEncoding;
+}
+
+/**
+ * CSV format of all exports. The default is what German Excel opens
+ * correctly: semicolons, decimal commas, UTF-8 with BOM.
+ */
+export interface CsvSettings {
+  dialect: CsvDialect;
+  overrides: Partial<Record<CsvExportName, Partial<CsvDialect>>>;
+}
+
+/**
+ * PAdES signing of invoice PDFs (signing.ts). The PKCS#12 certificate and
+ * the passphrase/PIN live in the credential store, not here.
+ */
+export interface SigningSettings {
+  /** Sign every invoice PDF right after it was generated. */
+  autoSign: boolean;
+  source: "pkcs12" | "pkcs11";
+  /** PKCS#11 library of the USB token (e.g. the vendor's .dll/.so). */
+  pkcs11ModulePath: string;
+  tokenLabel: string;
+  keyLabel: string;
+  reason: string;
+  location: string;
+}
+
+export interface ShellSettings {
+  power: PowerSettings;
+  logging: LoggingSettings;
+  redaction: RedactionSettings;
+  debug: DebugSettings;
+  dunning: DunningSettings;
+  smallBusiness: SmallBusinessSettings;
+  reports: ReportSettings;
+  calendar: CalendarSettings;
+  email: EmailSettings;
+  webdavBackup: WebDavBackupSettings;
+  automation: AutomationSettings;
+  pdfMirror: PdfMirrorSettings;
+  backendTuning: BackendTuningSettings;
+  maintenance: MaintenanceSettings;
+  timeSync: TimeSyncSettings;
+  resourceMonitor: ResourceMonitorSettings;
+  retention: RetentionSettings;
+  csv: CsvSettings;
+  signing: SigningSettings;
+}
+
+export type SettingsPatch = {
+  [K in keyof ShellSettings]?: Partial<ShellSettings[K]>;
+};
+
+export const DEFAULT_SETTINGS: ShellSettings = {
+  power: {
+    deferHeavyJobsOnBattery: true,
+  },
+  logging: {
+    maxFileSizeMb: 5,
+    retentionDays: 30,
+    maxArchivedFiles: 20,
+  },
+  redaction: {
+    enabled: true,
+    extraPatterns: [],
+    names: [],
+  },
+  debug: {
+    recordSession: false,
+    requestReplay: false,
+  },
+  dunning: {
+    paymentTermDays: 14,
+    levels: [
+      { daysOverdue: 7, fee: 0 },
+      { daysOverdue: 21, fee: 5 },
+      { daysOverdue: 35, fee: 10 },
+    ],
+    annualInterestPercent: 0,
+  },
+  smallBusiness: {
+    monitorThresholds: true,
+    warnAtPercent: [80, 95],
+  },
+  reports: {
+    monthlySummary: true,
+  },
+  calendar: {
+    state: null,
+  },
+  email: {
+    smtpHost: "",
+    smtpPort: 587,
+    security: "starttls",
+    username: "",
+    fromAddress: "",
+  },
+  webdavBackup: {
+    enabled: false,
+    url: "",
+    username: "",
+  },
+  automation: {
+    hooks: [],
+    allowedExecutables: [],
+    allowedHosts: [],
+  },
+  pdfMirror: {
+    enabled: false,
+    folder: "",
+  },
+  backendTuning: {
+    workers: 1,
+    sqliteCacheMb: null,
+    logLevel: null,
+    priority: "normal",
+  },
+  maintenance: {
+    enabled: true,
+    windowStartHour: 2,
+    windowEndHour: 5,
+    idleMinutes: 30,
+  },
+  timeSync: {
+    enabled: false,
+    server: "pool.ntp.org",
+    maxOffsetSeconds: 120,
+  },
+  resourceMonitor: {
+    enabled: true,
+    intervalSeconds: 30,
+    memoryWarningMb: 1024,
+  },
+  retention: {
+    exportMaxAgeDays: 7,
+    orphanedPdfs: false,
+    cacheMaxMb: 200,
+    dryRun: false,
+  },
+  csv: {
+    dialect: { separator: ";", decimalMark: ",", encoding: "utf-8-bom" },
+    overrides: {},
+  },
+  signing: {
+    autoSign: false,
+    source: "pkcs12",
+    pkcs11ModulePath: "",
+    tokenLabel: "",
+    keyLabel: "",
+    reason: "Rechnung",
+    location: "",
+  },
+};
+
+/** Sections only the main process may change. */
+const MAIN_PROCESS_SECTIONS: ReadonlySet<string> = new Set(["automation"]);
+
+let current: ShellSettings | null = null;
+const listeners: Array<(settings: ShellSettings) => void> = [];
+
+function getSettingsPath(): string {
+  return path.join(app.getPath("userData"), "settings.json");
+}
+
+/**
+ * Merge a (possibly partial) settings object into a base, section by section.
+ */
+function mergeSettings(base: ShellSettings, patch: SettingsPatch): ShellSettings {
+  const merged = { ...base } as Record<string, unknown>;
+  for (const [section, values] of Object.entries(patch)) {
+    if (!(section in base) || typeof values !== "object" || values === null) continue;
+    merged[section] = { ...(base as unknown as Record<string, object>)[section], ...values };
+  }
+  return merged as unknown as ShellSettings;
+}
+
+/**
+ * Load settings from disk, falling back to defaults for missing values.
+ */
+export function loadSettings(): ShellSettings {
+  const settingsPath = getSettingsPath();
+  let stored: SettingsPatch = {};
+
+  try {
+    if (fs.existsSync(settingsPath)) {
+      stored = JSON.parse(fs.readFileSync(settingsPath, "utf-8")) as SettingsPatch;
+    }
+  } catch (err) {
+    log.warn(`⚠️ Could not read settings (${settingsPath}), using defaults: ${err}`);
+  }
+
+  current = mergeSettings(DEFAULT_SETTINGS, stored);
+  return current;
+}
+
+/**
+ * Get the current settings (loaded lazily).
+ */
+export function getSettings(): ShellSettings {
+  return current ?? loadSettings();
+}
+
+/**
+ * Apply a partial update, persist it and notify listeners.
+ */
+export function updateSettings(patch: SettingsPatch): ShellSettings {
+  const updated = mergeSettings(getSettings(), patch);
+  const settingsPath = getSettingsPath();
+
+  fs.mkdirSync(path.dirname(settingsPath), { recursive: true });
+  fs.writeFileSync(settingsPath, JSON.stringify(updated, null, 2), "utf-8");
+  current = updated;
+  log.info(`⚙️ Settings updated: ${Object.keys(patch).join(", ")}`);
+
+  listeners.forEach((listener) => listener(updated));
+  return updated;
+}
+
+/**
+ * Subscribe to settings changes.
+ */
+export function onSettingsChanged(listener: (settings: ShellSettings) => void): void {
+  listeners.push(listener);
+}
+
+/**
+ * Reject renderer updates of sections only the main process may change.
+ *
+ * @throws AppError permission_denied
+ */
+export function assertRendererPatch(patch: SettingsPatch): void {
+  const denied = Object.keys(patch ?? {}).filter((section) => MAIN_PROCESS_SECTIONS.has(section));
+  if (denied.length === 0) return;
+
+  log.warn(`🔒 Rejected settings update from the renderer: ${denied.join(", ")}`);
+  throw new AppError("permission_denied", `Settings not writable: ${denied.join(", ")}`, {
+    message: "Automationen können nur über die Automations-Einstellungen geändert werden.",
+    hint: null,
+  });
+}
+
+/**
+ * Register IPC handlers for reading/updating settings.
+ */
+export function registerSettingsHandlers(): void {
+  handle("get-settings", () => getSettings(), "read");
+  handle("update-settings", (_event, patch: SettingsPatch) => {
+    assertRendererPatch(patch);
+    return updateSettings(patch);
+  });
+}
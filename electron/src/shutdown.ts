@@ -0,0 +1,218 @@
+/**
+ * Billino Desktop – Staged Backend Shutdown
+ *
+ * Before stopping, the shell asks the backend for unfinished work
+ * (GET /shutdown/pending-work: an open write transaction, PDF generations,
+ * a running backup) and waits up to PENDING_WORK_WAIT_MS while there is
+ * some – otherwise an invoice saved right before closing the app could be
+ * rolled back by the kill. Backends without the endpoint are not waited for.
+ *
+ * Then it stops the instance in stages, each only if the previous one did
+ * not end the process:
+ *
+ *   1. endpoint:  POST /shutdown – the backend runs its lifespan shutdown
+ *                 (the only graceful way on Windows, see processes.ts)
+ *                 with this launch's secret (BILLINO_SHUTDOWN_TOKEN) in a
+ *                 header; other local programs cannot stop the backend
+ *   2. terminate: SIGTERM / taskkill without /f
+ *   3. kill:      force kill after `shutdownTimeoutMs` in total
+ *
+ * Every stage is announced with `backend:stopping`; the outcome goes out
+ * as `backend:stopped` and is returned as a ShutdownReport, which says
+ * which stage ended the process and whether the timeout ran out.
+ */
+
+import { randomBytes } from "crypto";
+import { setTimeout as delay } from "timers/promises";
+import log from "electron-log/main";
+import { backendPath } from "./api";
+import { getConfig } from "./config";
+import { emitEvent } from "./events";
+import { ProcessHandle } from "./processes";
+
+export type ShutdownStage = "endpoint" | "terminate" | "kill";
+
+export interface PendingWorkCheck {
+  /** Nothing was pending (anymore) when the shutdown began. */
+  idle: boolean;
+  /** Still pending after the wait, e.g. ["write", "pdf:2", "backup"]. */
+  pending: string[];
+  waitedMs: number;
+}
+
+export interface ShutdownReport {
+  pid: number | null;
+  /** Stage that ended the process; null if it had exited before or survived the kill. */
+  stage: ShutdownStage | null;
+  /** Stages that were tried, in order. */
+  attempted: ShutdownStage[];
+  /** The graceful stages did not end the process within the timeout. */
+  timedOut: boolean;
+  /** False only if the process is still running after the kill. */
+  stopped: boolean;
+  durationMs: number;
+  /** Null if not checked (no URL, old backend, not reachable). */
+  pendingWork: PendingWorkCheck | null;
+}
+
+/** The shutdown request itself must answer quickly. */
+const ENDPOINT_TIMEOUT_MS = 2_000;
+/** Longest wait for pending writes before stopping anyway. */
+const PENDING_WORK_WAIT_MS = 3_000;
+const PENDING_WORK_POLL_MS = 200;
+
+/** Header that carries the shutdown secret (checked in routers/health.py). */
+const SHUTDOWN_TOKEN_HEADER = "X-Billino-Shutdown-Token";
+/** Generated once per launch and passed to every spawned backend. */
+const SHUTDOWN_TOKEN = randomBytes(32).toString("hex");
+
+interface RawPendingWork {
+  idle: boolean;
+  write_in_progress: boolean | null;
+  pdf_jobs: number;
+  backup_running: boolean;
+}
+
+async function fetchPendingWork(url: string): Promise<RawPendingWork | null> {
+  try {
+    const response = await fetch(`${url}${backendPath("GET /shutdown/pending-work")}`, {
+      signal: AbortSignal.timeout(ENDPOINT_TIMEOUT_MS),
+    });
+    return response.ok ? ((await response.json()) as RawPendingWork) : null;
+  } catch {
+    return null;
+  }
+}
+
+function describePendingWork(work: RawPendingWork): string[] {
+  const pending: string[] = [];
+  if (work.write_in_progress) pending.push("write");
+  if (work.pdf_jobs > 0) pending.push(`pdf:${work.pdf_jobs}`);
+  if (work.backup_running) pending.push("backup");
+  return pending;
+}
+
+/**
+ * Wait until the backend reports no unfinished writes, at most
+ * PENDING_WORK_WAIT_MS.
+ *
+ * @returns Null if the backend cannot tell (old version, not reachable)
+ */
+export async function waitForPendingWork(
+  url: string,
+  maxWaitMs: number = PENDING_WORK_WAIT_MS
+): Promise<PendingWorkCheck | null> {
+  const started = Date.now();
+  let work = await fetchPendingWork(url);
+  if (!work) return null;
+  if (!work.idle) log.info(`⏳ Waiting for backend work: ${describePendingWork(work).join(", ")}`);
+
+  while (!work.idle && Date.now() - started < maxWaitMs) {
+    await delay(PENDING_WORK_POLL_MS);
+    work = (await fetchPendingWork(url)) ?? work;
+  }
+
+  const check: PendingWorkCheck = {
+    idle: work.idle,
+    pending: describePendingWork(work),
+    waitedMs: Date.now() - started,
+  };
+  if (!check.idle) {
+    log.warn(`⚠️ Stopping backend with work still pending: ${check.pending.join(", ")}`);
+  }
+  return check;
+}
+
+/**
+ * Environment variable for a backend about to be spawned.
+ */
+export function getShutdownTokenEnv(): { BILLINO_SHUTDOWN_TOKEN: string } {
+  return { BILLINO_SHUTDOWN_TOKEN: SHUTDOWN_TOKEN };
+}
+
+/**
+ * POST /shutdown with the secret; backends of earlier launches refuse it.
+ */
+export function sendShutdownRequest(url: string, timeoutMs: number): Promise<Response> {
+  return fetch(`${url}${backendPath("POST /shutdown")}`, {
+    method: "POST",
+    headers: { [SHUTDOWN_TOKEN_HEADER]: SHUTDOWN_TOKEN },
+    signal: AbortSignal.timeout(timeoutMs),
+  });
+}
+
+async function requestShutdown(url: string): Promise<boolean> {
+  try {
+    const response = await sendShutdownRequest(url, ENDPOINT_TIMEOUT_MS);
+    if (!response.ok) log.warn(`⚠️ Backend refused shutdown request (HTTP ${response.status})`);
+    return response.ok;
+  } catch (err) {
+    log.warn(`⚠️ Shutdown request failed: ${err}`);
+    return false;
+  }
+}
+
+/**
+ * Stop a backend instance: shutdown request, SIGTERM, force kill.
+ *
+ * @param url Base URL of the instance; without it the endpoint stage is skipped
+ * @param timeoutMs Budget for the graceful stages (default: `shutdownTimeoutMs`)
+ */
+export async function shutdownBackendProcess(
+  handle: ProcessHandle,
+  url: string | null,
+  timeoutMs: number = getConfig().shutdownTimeoutMs
+): Promise<ShutdownReport> {
+  const started = Date.now();
+  // The wait for pending work does not shorten the graceful stages
+  const pendingWork = url && handle.running ? await waitForPendingWork(url) : null;
+  const deadline = Date.now() + timeoutMs;
+  const pid = handle.pid ?? null;
+  const attempted: ShutdownStage[] = [];
+  const remaining = (): number => Math.max(0, deadline - Date.now());
+
+  const finish = (stage: ShutdownStage | null, timedOut = false): ShutdownReport => {
+    const report: ShutdownReport = {
+      pid,
+      stage,
+      attempted,
+      timedOut,
+      stopped: !handle.running,
+      durationMs: Date.now() - started,
+      pendingWork,
+    };
+    log.info(
+      `🛑 Backend ${pid ?? "?"} stopped by ${stage ?? "-"} in ${report.durationMs}ms` +
+        (timedOut ? ` (graceful shutdown timed out after ${timeoutMs}ms)` : "")
+    );
+    emitEvent("backend:stopped", report);
+    return report;
+  };
+  const enter = (stage: ShutdownStage): void => {
+    attempted.push(stage);
+    emitEvent("backend:stopping", { pid, stage });
+  };
+
+  if (!handle.running) return finish(null);
+
+  if (url) {
+    enter("endpoint");
+    if ((await requestShutdown(url)) && (await handle.waitForExit(remaining()))) {
+      return finish("endpoint");
+    }
+  }
+
+  if (handle.running && remaining() > 0) {
+    enter("terminate");
+    if ((await handle.terminate()) && (await handle.waitForExit(remaining()))) {
+      return finish("terminate");
+    }
+  }
+  if (!handle.running) return finish(attempted[attempted.length - 1] ?? null);
+
+  log.warn(`⚠️ Backend ${pid} did not exit gracefully – killing it`);
+  enter("kill");
+  const timedOut = remaining() === 0;
+  const killed = await handle.kill();
+  return finish(killed ? "kill" : null, timedOut);
+}
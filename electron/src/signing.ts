@@ -0,0 +1,157 @@
+/**
+ * Billino Desktop – Invoice PDF Signing
+ *
+ * Signs stored invoice PDFs with a PAdES signature, so sent invoices carry
+ * a verifiable signature. The backend does the signing (pyHanko); the
+ * shell supplies the key with each request:
+ * - PKCS#12: the .p12/.pfx file is imported once and kept encrypted in
+ *   the credential store ("signing:pkcs12"), its passphrase as "signing"
+ * - PKCS#11: a USB token or smartcard; module, token and key label come
+ *   from the settings, the PIN is stored as "signing"
+ *
+ * With `signing.autoSign` every PDF generated through the shell is signed
+ * right away; a failed signature is reported but keeps the PDF.
+ */
+
+import { Notification } from "electron";
+import fs from "fs";
+import log from "electron-log/main";
+import { callBackend } from "./api";
+import { deleteCredential, getCredential, setCredential } from "./credentials";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { getSettings } from "./settings";
+
+export interface SignedPdfResult {
+  invoiceId: number;
+  pdfId: number;
+  signedAt: string;
+}
+
+/** PKCS#12 files are a few KB; anything this large is not one. */
+const MAX_PKCS12_BYTES = 1024 * 1024;
+/** Tokens may ask for the PIN on their own display. */
+const SIGN_TIMEOUT_MS = 60_000;
+
+function notConfigured(detail: string, message: string): AppError {
+  return new AppError("invalid_state", detail, {
+    message,
+    hint: "Unter Einstellungen → Signatur ein Zertifikat einrichten.",
+  });
+}
+
+function signingBody(): Record<string, unknown> {
+  const settings = getSettings().signing;
+  const secret = getCredential("signing");
+  const body: Record<string, unknown> = {
+    reason: settings.reason || null,
+    location: settings.location || null,
+  };
+
+  if (settings.source === "pkcs11") {
+    if (!settings.pkcs11ModulePath || !settings.tokenLabel || !settings.keyLabel) {
+      throw notConfigured("PKCS#11 token is not configured", "Kein Signatur-Token eingerichtet.");
+    }
+    if (secret === null) {
+      throw notConfigured("No PIN stored for the signing token", "Keine Token-PIN gespeichert.");
+    }
+    body.pkcs11 = {
+      module_path: settings.pkcs11ModulePath,
+      token_label: settings.tokenLabel,
+      key_label: settings.keyLabel,
+      pin: secret,
+    };
+    return body;
+  }
+
+  const pkcs12 = getCredential("signing:pkcs12");
+  if (pkcs12 === null) {
+    throw notConfigured("No signing certificate imported", "Kein Signaturzertifikat importiert.");
+  }
+  body.pkcs12 = pkcs12;
+  body.passphrase = secret;
+  return body;
+}
+
+/**
+ * Store a PKCS#12 certificate and its passphrase in the credential store.
+ *
+ * @param filePath Absolute path of the .p12/.pfx file
+ */
+export function importSigningCertificate(filePath: string, passphrase: string): void {
+  let data: Buffer;
+  try {
+    data = fs.readFileSync(filePath);
+  } catch (err) {
+    throw new AppError("not_found", `Cannot read certificate ${filePath}: ${err}`, {
+      message: "Die Zertifikatsdatei konnte nicht gelesen werden.",
+    });
+  }
+  if (data.length === 0 || data.length > MAX_PKCS12_BYTES) {
+    throw new AppError("invalid_input", `Not a PKCS#12 file: ${filePath}`, {
+      message: "Die Datei ist kein PKCS#12-Zertifikat (.p12/.pfx).",
+    });
+  }
+  setCredential("signing:pkcs12", data.toString("base64"));
+  setCredential("signing", passphrase);
+  log.info("🔏 Signing certificate imported");
+}
+
+/**
+ * Remove the stored certificate and passphrase/PIN.
+ */
+export function removeSigningCertificate(): void {
+  deleteCredential("signing:pkcs12");
+  deleteCredential("signing");
+}
+
+/**
+ * Sign the stored PDF of an invoice.
+ *
+ * @throws AppError if no certificate is set up, the PDF does not exist or
+ *         the backend rejects the certificate
+ */
+export async function signInvoicePdf(invoiceId: number): Promise<SignedPdfResult> {
+  const pdf = (await callBackend("POST /pdfs/invoices/{invoice_id}/sign", {
+    params: { invoice_id: invoiceId },
+    body: signingBody(),
+    timeoutMs: SIGN_TIMEOUT_MS,
+  })) as { id: number };
+  const result = { invoiceId, pdfId: pdf.id, signedAt: new Date().toISOString() };
+  log.info(`🔏 PDF of invoice ${invoiceId} signed`);
+  emitEvent("pdf:signed", result);
+  return result;
+}
+
+/**
+ * Sign a freshly generated PDF if auto-signing is on. Never throws: the
+ * unsigned PDF stays usable.
+ */
+export async function autoSignInvoicePdf(invoiceId: number): Promise<void> {
+  if (!getSettings().signing.autoSign) return;
+  try {
+    await signInvoicePdf(invoiceId);
+  } catch (err) {
+    const message = err instanceof AppError ? err.userMessage : String(err);
+    log.warn(`⚠️ Auto-signing PDF of invoice ${invoiceId} failed: ${err}`);
+    if (Notification.isSupported()) {
+      new Notification({
+        title: "Billino – Signatur",
+        body: `Das PDF wurde erzeugt, aber nicht signiert: ${message}`,
+      }).show();
+    }
+  }
+}
+
+/**
+ * Register IPC handlers for PDF signing.
+ */
+export function registerSigningHandlers(): void {
+  handle("sign-invoice-pdf", (_event, invoiceId: number) => signInvoicePdf(invoiceId));
+  handle("has-signing-certificate", () => getCredential("signing:pkcs12") !== null, "read");
+  handle("import-signing-certificate", (_event, filePath: string, passphrase: string) =>
+    importSigningCertificate(filePath, passphrase)
+  );
+  handle("remove-signing-certificate", () => removeSigningCertificate(), "destructive");
+}
@@ -0,0 +1,262 @@
+/**
+ * Billino Desktop – Minimal SMTP Client
+ *
+ * Speaks just enough SMTP (RFC 5321) for connection checks and sending
+ * simple messages: greeting, EHLO, STARTTLS, AUTH, MAIL/RCPT/DATA and QUIT.
+ * Every line in both directions is kept in a transcript, so a failing
+ * setup can be diagnosed from the exact server replies.
+ */
+
+import net from "net";
+import tls from "tls";
+import type { SmtpSecurity } from "./settings";
+
+export interface SmtpReply {
+  code: number;
+  /** Reply text of all lines (without codes). */
+  text: string;
+}
+
+/**
+ * Failure during an SMTP conversation; carries the transcript so far.
+ */
+export class SmtpError extends Error {
+  constructor(
+    message: string,
+    readonly transcript: string[],
+    /** Reply code of the failing command (null for network errors). */
+    readonly replyCode: number | null = null
+  ) {
+    super(message);
+    this.name = "SmtpError";
+  }
+}
+
+export interface SmtpConnectOptions {
+  host: string;
+  port: number;
+  security: SmtpSecurity;
+  timeoutMs: number;
+}
+
+type Waiter = { resolve: (reply: SmtpReply) => void; reject: (err: Error) => void };
+
+export class SmtpSession {
+  /** "S: ..." for server lines, "C: ..." for client lines. */
+  readonly transcript: string[] = [];
+  /** Time from connect to the server greeting. */
+  greetingMs = 0;
+  /** Extensions announced in the last EHLO reply (AUTH, SIZE, ...). */
+  capabilities: string[] = [];
+
+  private socket: net.Socket;
+  private buffer = "";
+  private pendingLines: string[] = [];
+  private replies: SmtpReply[] = [];
+  private waiters: Waiter[] = [];
+  private failure: Error | null = null;
+
+  private constructor(
+    socket: net.Socket,
+    private readonly options: SmtpConnectOptions
+  ) {
+    this.socket = socket;
+    this.attach(socket);
+  }
+
+  /**
+   * Connect, read the greeting and send EHLO (upgrading via STARTTLS if
+   * configured).
+   *
+   * @throws SmtpError on network errors, timeouts or unexpected replies
+   */
+  static async connect(options: SmtpConnectOptions): Promise<SmtpSession> {
+    const start = Date.now();
+    const socket =
+      options.security === "tls"
+        ? tls.connect({ host: options.host, port: options.port, servername: options.host })
+        : net.connect({ host: options.host, port: options.port });
+    const session = new SmtpSession(socket, options);
+
+    await session.expect(await session.read(), [220]);
+    session.greetingMs = Date.now() - start;
+    await session.ehlo();
+
+    if (options.security === "starttls") {
+      if (!session.capabilities.includes("STARTTLS")) {
+        session.close();
+        throw new SmtpError("Server does not offer STARTTLS", session.transcript);
+      }
+      await session.command("STARTTLS", [220]);
+      await session.upgrade();
+      await session.ehlo();
+    }
+    return session;
+  }
+
+  /**
+   * Authenticate with AUTH PLAIN or AUTH LOGIN (whichever the server offers).
+   * Credentials are masked in the transcript.
+   */
+  async authenticate(username: string, password: string): Promise<void> {
+    const auth = this.capabilities.find((cap) => cap.startsWith("AUTH")) ?? "";
+    const mechanisms = auth.split(/[\s=]+/).slice(1);
+    if (mechanisms.includes("PLAIN")) {
+      const token = Buffer.from(`\0${username}\0${password}`).toString("base64");
+      await this.command(`AUTH PLAIN ${token}`, [235], "AUTH PLAIN ***");
+    } else if (mechanisms.includes("LOGIN")) {
+      await this.command("AUTH LOGIN", [334]);
+      await this.command(Buffer.from(username).toString("base64"), [334], "***");
+      await this.command(Buffer.from(password).toString("base64"), [235], "***");
+    } else {
+      this.close();
+      throw new SmtpError(
+        `No supported AUTH mechanism (server offers: ${mechanisms.join(", ") || "none"})`,
+        this.transcript
+      );
+    }
+  }
+
+  /**
+   * Send a complete RFC 5322 message (CRLF line endings).
+   */
+  async sendMail(from: string, to: string[], message: string): Promise<void> {
+    await this.command(`MAIL FROM:<${from}>`, [250]);
+    for (const recipient of to) {
+      await this.command(`RCPT TO:<${recipient}>`, [250, 251]);
+    }
+    await this.command("DATA", [354]);
+    // Dot-stuffing: lines starting with "." get a second one
+    const body = message.replace(/^\./gm, "..");
+    this.transcript.push(`C: <${Buffer.byteLength(body)} bytes message>`);
+    this.socket.write(`${body}\r\n.\r\n`);
+    this.expect(await this.read(), [250]);
+  }
+
+  /** The TLS socket, if the connection is encrypted. */
+  get tlsSocket(): tls.TLSSocket | null {
+    return this.socket instanceof tls.TLSSocket ? this.socket : null;
+  }
+
+  /**
+   * Send a command and wait for its reply.
+   *
+   * @param expected Accepted reply codes
+   * @param logAs Transcript text instead of the command (for credentials)
+   */
+  async command(line: string, expected: number[], logAs?: string): Promise<SmtpReply> {
+    this.transcript.push(`C: ${logAs ?? line}`);
+    this.socket.write(`${line}\r\n`);
+    return this.expect(await this.read(), expected);
+  }
+
+  /**
+   * Wait for the next complete reply.
+   */
+  read(): Promise<SmtpReply> {
+    const reply = this.replies.shift();
+    if (reply) return Promise.resolve(reply);
+    if (this.failure) return Promise.reject(this.failure);
+    return new Promise((resolve, reject) => this.waiters.push({ resolve, reject }));
+  }
+
+  /**
+   * Say goodbye and close the connection (errors are ignored).
+   */
+  async quit(): Promise<void> {
+    try {
+      await this.command("QUIT", [221]);
+    } catch {
+      // The server may close without replying
+    } finally {
+      this.close();
+    }
+  }
+
+  close(): void {
+    this.socket.destroy();
+  }
+
+  expect(reply: SmtpReply, expected: number[]): SmtpReply {
+    if (!expected.includes(reply.code)) {
+      this.close();
+      throw new SmtpError(
+        `Unexpected SMTP reply ${reply.code}: ${reply.text}`,
+        this.transcript,
+        reply.code
+      );
+    }
+    return reply;
+  }
+
+  private async ehlo(): Promise<void> {
+    const reply = await this.command("EHLO billino.local", [250]);
+    this.capabilities = reply.text
+      .split("\n")
+      .slice(1)
+      .map((line) => line.trim().toUpperCase());
+  }
+
+  private attach(socket: net.Socket): void {
+    socket.setEncoding("utf8");
+    socket.setTimeout(this.options.timeoutMs, () =>
+      socket.destroy(new Error(`Timeout after ${this.options.timeoutMs} ms`))
+    );
+    socket.on("data", this.onData);
+    socket.on("error", this.onError);
+    socket.on("close", this.onClose);
+  }
+
+  private detach(socket: net.Socket): void {
+    socket.removeListener("data", this.onData);
+    socket.removeListener("error", this.onError);
+    socket.removeListener("close", this.onClose);
+    socket.setTimeout(0);
+  }
+
+  private async upgrade(): Promise<void> {
+    const plain = this.socket;
+    this.detach(plain);
+    const secure = tls.connect({ socket: plain, servername: this.options.host });
+    this.socket = secure;
+    this.attach(secure);
+    await new Promise<void>((resolve, reject) => {
+      secure.once("secureConnect", resolve);
+      secure.once("error", reject);
+    });
+    this.transcript.push("-- TLS handshake completed --");
+  }
+
+  private onData = (chunk: string): void => {
+    const parts = (this.buffer + chunk).split("\r\n");
+    this.buffer = parts.pop() ?? "";
+    for (const line of parts) {
+      this.transcript.push(`S: ${line}`);
+      this.pendingLines.push(line);
+      // "250-..." continues a multi-line reply, "250 ..." ends it
+      if (line.length >= 3 && line[3] !== "-") {
+        const reply = {
+          code: Number(line.slice(0, 3)),
+          text: this.pendingLines.map((l) => l.slice(4)).join("\n"),
+        };
+        this.pendingLines = [];
+        const waiter = this.waiters.shift();
+        if (waiter) waiter.resolve(reply);
+        else this.replies.push(reply);
+      }
+    }
+  };
+
+  private onError = (err: Error): void => {
+    this.fail(new SmtpError(err.message, this.transcript));
+  };
+
+  private onClose = (): void => {
+    this.fail(new SmtpError("Connection closed by server", this.transcript));
+  };
+
+  private fail(err: Error): void {
+    this.failure ??= err;
+    for (const waiter of this.waiters.splice(0)) waiter.reject(this.failure);
+  }
+}
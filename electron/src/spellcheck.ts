@@ -0,0 +1,508 @@
+/**
+ * Billino Desktop – Spell Checking
+ *
+ * Manages local Hunspell dictionaries (de_DE, en_US) in
+ * AppData/Roaming/Billino/dictionaries/ and checks texts such as line-item
+ * descriptions before the PDF goes out:
+ * - `list-dictionaries` / `install-dictionary` / `remove-dictionary`
+ * - `check-spelling`: misspelled words with offsets and suggestions
+ * - `add-dictionary-word`: personal word list (user.txt)
+ *
+ * The checker understands the parts of the Hunspell format that matter for
+ * these dictionaries: prefix/suffix rules incl. cross products, flag
+ * aliases, NEEDAFFIX/ONLYINCOMPOUND/FORBIDDENWORD and the basic compound
+ * flags (German compound nouns). Dictionaries are downloaded once from the
+ * LibreOffice dictionary repository; checking itself works offline.
+ */
+
+import { app } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+
+export type DictionaryLanguage = "de_DE" | "en_US";
+
+export interface DictionaryInfo {
+  lang: DictionaryLanguage;
+  installed: boolean;
+  sizeBytes: number;
+  installedAt: string | null;
+}
+
+export interface SpellingIssue {
+  word: string;
+  /** Character offset in the checked text. */
+  offset: number;
+  suggestions: string[];
+}
+
+const DICTIONARY_BASE_URL = "https://raw.githubusercontent.com/LibreOffice/dictionaries/master";
+
+const DICTIONARY_SOURCES: Record<DictionaryLanguage, string> = {
+  de_DE: `${DICTIONARY_BASE_URL}/de/de_DE_frami`,
+  en_US: `${DICTIONARY_BASE_URL}/en/en_US`,
+};
+
+const DOWNLOAD_TIMEOUT_MS = 60_000;
+const MAX_SUGGESTIONS = 5;
+const WORD_PATTERN = /\p{L}[\p{L}'’]*/gu;
+
+// ─── Dictionary Format ───────────────────────────────────────────────────────
+
+interface AffixEntry {
+  flag: string;
+  cross: boolean;
+  strip: string;
+  add: string;
+  condition: RegExp;
+}
+
+interface Dictionary {
+  words: Map<string, string[][]>;
+  prefixes: Map<string, AffixEntry[]>;
+  suffixes: Map<string, AffixEntry[]>;
+  needAffix?: string;
+  onlyInCompound?: string;
+  forbidden?: string;
+  compound?: string;
+  compoundBegin?: string;
+  compoundMiddle?: string;
+  compoundEnd?: string;
+  compoundMin: number;
+  tryChars: string;
+}
+
+type FlagMode = "char" | "long" | "num";
+
+function parseFlags(raw: string, mode: FlagMode, aliases: string[][]): string[] {
+  if (aliases.length > 0 && /^\d+$/.test(raw)) {
+    return aliases[Number(raw) - 1] ?? [];
+  }
+  if (mode === "long") return raw.match(/.{1,2}/gu) ?? [];
+  if (mode === "num") return raw.split(",").filter(Boolean);
+  return Array.from(raw);
+}
+
+function readEncoded(filePath: string, encoding: string): string {
+  const data = fs.readFileSync(filePath);
+  return /^UTF-?8$/i.test(encoding) ? data.toString("utf-8") : data.toString("latin1");
+}
+
+function affixCondition(condition: string, type: "PFX" | "SFX"): RegExp {
+  const source = condition === "." ? "" : condition;
+  try {
+    return new RegExp(type === "SFX" ? `${source}$` : `^${source}`, "u");
+  } catch {
+    return /(?!)/;
+  }
+}
+
+function loadDictionary(dicPath: string, affPath: string): Dictionary {
+  const encoding =
+    fs.readFileSync(affPath, "latin1").match(/^SET\s+(\S+)/m)?.[1] ?? "ISO8859-1";
+
+  const dictionary: Dictionary = {
+    words: new Map(),
+    prefixes: new Map(),
+    suffixes: new Map(),
+    compoundMin: 3,
+    tryChars: "",
+  };
+  let flagMode: FlagMode = "char";
+  const aliases: string[][] = [];
+  const crossProduct = new Map<string, boolean>();
+
+  for (const line of readEncoded(affPath, encoding).split(/\r?\n/)) {
+    const parts = line.trim().split(/\s+/);
+    const [keyword, first] = parts;
+
+    switch (keyword) {
+      case "FLAG":
+        flagMode = first === "long" ? "long" : first === "num" ? "num" : "char";
+        break;
+      case "AF":
+        // The first AF line holds the alias count
+        if (parts.length === 2 && !(aliases.length === 0 && /^\d+$/.test(first))) {
+          aliases.push(parseFlags(first, flagMode, []));
+        }
+        break;
+      case "TRY":
+        dictionary.tryChars = first ?? "";
+        break;
+      case "NEEDAFFIX":
+        dictionary.needAffix = first;
+        break;
+      case "ONLYINCOMPOUND":
+        dictionary.onlyInCompound = first;
+        break;
+      case "FORBIDDENWORD":
+        dictionary.forbidden = first;
+        break;
+      case "COMPOUNDFLAG":
+        dictionary.compound = first;
+        break;
+      case "COMPOUNDBEGIN":
+        dictionary.compoundBegin = first;
+        break;
+      case "COMPOUNDMIDDLE":
+        dictionary.compoundMiddle = first;
+        break;
+      case "COMPOUNDEND":
+        dictionary.compoundEnd = first;
+        break;
+      case "COMPOUNDMIN":
+        dictionary.compoundMin = Math.max(1, Number(first) || 3);
+        break;
+      case "PFX":
+      case "SFX": {
+        if (parts.length === 4 && /^[YN]$/.test(parts[2])) {
+          crossProduct.set(`${keyword}${first}`, parts[2] === "Y");
+          break;
+        }
+        if (parts.length < 5) break;
+        const entry: AffixEntry = {
+          flag: first,
+          cross: crossProduct.get(`${keyword}${first}`) ?? false,
+          strip: parts[2] === "0" ? "" : parts[2],
+          add: parts[3] === "0" ? "" : parts[3].split("/")[0],
+          condition: affixCondition(parts[4], keyword),
+        };
+        const index = keyword === "PFX" ? dictionary.prefixes : dictionary.suffixes;
+        index.set(entry.add, [...(index.get(entry.add) ?? []), entry]);
+        break;
+      }
+    }
+  }
+
+  const dicLines = readEncoded(dicPath, encoding).split(/\r?\n/);
+  for (const line of dicLines.slice(1)) {
+    const field = line.split(/\t|\s+(?=[a-z][a-z]:)/)[0].trim();
+    if (!field) continue;
+
+    const slash = field.search(/(?<!\\)\//);
+    const word = (slash === -1 ? field : field.slice(0, slash)).replace(/\\\//g, "/");
+    const flags = slash === -1 ? [] : parseFlags(field.slice(slash + 1), flagMode, aliases);
+    dictionary.words.set(word, [...(dictionary.words.get(word) ?? []), flags]);
+  }
+
+  if (!dictionary.tryChars) {
+    dictionary.tryChars = "esianrtolcdugmphbyfvkwzESIANRTOLCDUGMPHBYFVKWZ";
+  }
+  return dictionary;
+}
+
+// ─── Checking ────────────────────────────────────────────────────────────────
+
+type FlagFilter = (flags: string[]) => boolean;
+
+function has(flags: string[], flag: string | undefined): boolean {
+  return flag !== undefined && flags.includes(flag);
+}
+
+/**
+ * Whether `word` is a dictionary word or an affixed form of one whose flags
+ * pass `accept`.
+ */
+function hasForm(dict: Dictionary, word: string, accept: FlagFilter, inCompound = false): boolean {
+  const usable = (flags: string[], affixed: boolean): boolean =>
+    !has(flags, dict.forbidden) &&
+    (affixed || !has(flags, dict.needAffix)) &&
+    (inCompound || !has(flags, dict.onlyInCompound)) &&
+    accept(flags);
+
+  if ((dict.words.get(word) ?? []).some((flags) => usable(flags, false))) return true;
+
+  const stemsWithFlag = (stem: string, ...required: string[]): boolean =>
+    (dict.words.get(stem) ?? []).some(
+      (flags) => required.every((flag) => flags.includes(flag)) && usable(flags, true)
+    );
+
+  const suffixStems = (form: string, crossOnly: boolean): Array<[string, AffixEntry]> => {
+    const stems: Array<[string, AffixEntry]> = [];
+    for (let i = 0; i <= form.length; i++) {
+      for (const sfx of dict.suffixes.get(form.slice(i)) ?? []) {
+        if (crossOnly && !sfx.cross) continue;
+        const stem = form.slice(0, i) + sfx.strip;
+        if (stem && sfx.condition.test(stem)) stems.push([stem, sfx]);
+      }
+    }
+    return stems;
+  };
+
+  for (const [stem, sfx] of suffixStems(word, false)) {
+    if (stemsWithFlag(stem, sfx.flag)) return true;
+  }
+
+  for (let i = 0; i <= word.length; i++) {
+    for (const pfx of dict.prefixes.get(word.slice(0, i)) ?? []) {
+      const stem = pfx.strip + word.slice(i);
+      if (!stem) continue;
+      if (pfx.condition.test(stem) && stemsWithFlag(stem, pfx.flag)) return true;
+      if (!pfx.cross) continue;
+      for (const [crossStem, sfx] of suffixStems(stem, true)) {
+        if (pfx.condition.test(crossStem) && stemsWithFlag(crossStem, pfx.flag, sfx.flag)) {
+          return true;
+        }
+      }
+    }
+  }
+  return false;
+}
+
+function capitalize(word: string): string {
+  return word.charAt(0).toUpperCase() + word.slice(1);
+}
+
+/**
+ * Check a compound word (e.g. "Haarschnitt") against the compound flags.
+ */
+function isCompound(
+  dict: Dictionary,
+  word: string,
+  position: "begin" | "middle" = "begin"
+): boolean {
+  if (!dict.compound && !dict.compoundBegin && !dict.compoundEnd) return false;
+
+  const partFlag = position === "begin" ? dict.compoundBegin : dict.compoundMiddle;
+  const headFilter: FlagFilter = (flags) => has(flags, dict.compound) || has(flags, partFlag);
+  const endFilter: FlagFilter = (flags) =>
+    has(flags, dict.compound) || has(flags, dict.compoundEnd);
+  const variants = (part: string): string[] =>
+    position === "begin" ? [part] : [part, capitalize(part)];
+
+  for (let i = dict.compoundMin; i <= word.length - dict.compoundMin; i++) {
+    const head = word.slice(0, i);
+    if (!variants(head).some((h) => hasForm(dict, h, headFilter, true))) continue;
+
+    const tail = word.slice(i);
+    for (const candidate of [tail, capitalize(tail)]) {
+      if (hasForm(dict, candidate, endFilter, true)) return true;
+    }
+    if (position === "begin" && isCompound(dict, tail, "middle")) return true;
+  }
+  return false;
+}
+
+function isCorrect(dict: Dictionary, word: string, userWords: Set<string>): boolean {
+  const forms = [word];
+  if (word === word.toUpperCase()) {
+    forms.push(word.toLowerCase(), capitalize(word.toLowerCase()));
+  } else if (word.charAt(0) === word.charAt(0).toUpperCase()) {
+    forms.push(word.toLowerCase());
+  }
+
+  return forms.some(
+    (form) =>
+      userWords.has(form) ||
+      userWords.has(form.toLowerCase()) ||
+      hasForm(dict, form, () => true) ||
+      isCompound(dict, form)
+  );
+}
+
+/**
+ * Candidates within one edit (delete, transpose, replace, insert).
+ */
+function editsOf(word: string, alphabet: string): string[] {
+  const edits = new Set<string>();
+  const chars = Array.from(new Set(Array.from(alphabet)));
+  for (let i = 0; i <= word.length; i++) {
+    const [left, right] = [word.slice(0, i), word.slice(i)];
+    if (right) edits.add(left + right.slice(1));
+    if (right.length > 1) edits.add(left + right[1] + right[0] + right.slice(2));
+    for (const c of chars) {
+      if (right) edits.add(left + c + right.slice(1));
+      edits.add(left + c + right);
+    }
+  }
+  edits.delete(word);
+  return Array.from(edits);
+}
+
+function suggest(dict: Dictionary, word: string, userWords: Set<string>): string[] {
+  const suggestions: string[] = [];
+  for (const candidate of editsOf(word, dict.tryChars)) {
+    if (hasForm(dict, candidate, () => true) || userWords.has(candidate)) {
+      suggestions.push(candidate);
+      if (suggestions.length >= MAX_SUGGESTIONS) break;
+    }
+  }
+  return suggestions;
+}
+
+// ─── Dictionary Management ───────────────────────────────────────────────────
+
+const loaded = new Map<DictionaryLanguage, Dictionary>();
+
+/**
+ * Directory containing installed dictionaries and the personal word list.
+ */
+export function getDictionaryDir(): string {
+  return path.join(app.getPath("userData"), "dictionaries");
+}
+
+function dictionaryFiles(lang: DictionaryLanguage): { dic: string; aff: string } {
+  const base = path.join(getDictionaryDir(), lang);
+  return { dic: `${base}.dic`, aff: `${base}.aff` };
+}
+
+function assertLanguage(lang: string): asserts lang is DictionaryLanguage {
+  if (!(lang in DICTIONARY_SOURCES)) {
+    throw new AppError("invalid_input", `Unsupported dictionary language: ${lang}`);
+  }
+}
+
+function getUserWordsPath(): string {
+  return path.join(getDictionaryDir(), "user.txt");
+}
+
+function loadUserWords(): Set<string> {
+  try {
+    const content = fs.readFileSync(getUserWordsPath(), "utf-8");
+    return new Set(content.split(/\r?\n/).map((w) => w.trim()).filter(Boolean));
+  } catch {
+    return new Set();
+  }
+}
+
+/**
+ * List supported dictionaries and whether they are installed.
+ */
+export function listDictionaries(): DictionaryInfo[] {
+  return (Object.keys(DICTIONARY_SOURCES) as DictionaryLanguage[]).map((lang) => {
+    const { dic, aff } = dictionaryFiles(lang);
+    const installed = fs.existsSync(dic) && fs.existsSync(aff);
+    return {
+      lang,
+      installed,
+      sizeBytes: installed ? fs.statSync(dic).size + fs.statSync(aff).size : 0,
+      installedAt: installed ? fs.statSync(dic).mtime.toISOString() : null,
+    };
+  });
+}
+
+/**
+ * Download and install a dictionary (.dic + .aff).
+ */
+export async function installDictionary(lang: DictionaryLanguage): Promise<DictionaryInfo> {
+  assertLanguage(lang);
+  const files = dictionaryFiles(lang);
+  fs.mkdirSync(getDictionaryDir(), { recursive: true });
+
+  for (const [ext, target] of [
+    ["aff", files.aff],
+    ["dic", files.dic],
+  ] as const) {
+    const url = `${DICTIONARY_SOURCES[lang]}.${ext}`;
+    const response = await fetch(url, {
+      signal: AbortSignal.timeout(DOWNLOAD_TIMEOUT_MS),
+    }).catch((err) => {
+      throw new AppError("network_error", `Dictionary download failed (${url}): ${err}`);
+    });
+    if (!response.ok) {
+      throw new AppError(
+        "network_error",
+        `Dictionary download failed (${url}): HTTP ${response.status}`
+      );
+    }
+    fs.writeFileSync(`${target}.part`, Buffer.from(await response.arrayBuffer()));
+  }
+  fs.renameSync(`${files.aff}.part`, files.aff);
+  fs.renameSync(`${files.dic}.part`, files.dic);
+
+  loaded.delete(lang);
+  log.info(`📖 Dictionary installed: ${lang}`);
+  return listDictionaries().find((d) => d.lang === lang)!;
+}
+
+/**
+ * Remove an installed dictionary.
+ */
+export function removeDictionary(lang: DictionaryLanguage): void {
+  assertLanguage(lang);
+  const { dic, aff } = dictionaryFiles(lang);
+  fs.rmSync(dic, { force: true });
+  fs.rmSync(aff, { force: true });
+  loaded.delete(lang);
+  log.info(`🗑️ Dictionary removed: ${lang}`);
+}
+
+/**
+ * Add a word to the personal word list (used for all languages).
+ */
+export function addDictionaryWord(word: string): void {
+  const trimmed = word.trim();
+  if (!trimmed || /\s/.test(trimmed)) {
+    throw new AppError("invalid_input", `Invalid dictionary word: "${word}"`);
+  }
+  const words = loadUserWords();
+  if (words.has(trimmed)) return;
+
+  fs.mkdirSync(getDictionaryDir(), { recursive: true });
+  fs.appendFileSync(getUserWordsPath(), `${trimmed}\n`, "utf-8");
+}
+
+function getDictionary(lang: DictionaryLanguage): Dictionary {
+  const cached = loaded.get(lang);
+  if (cached) return cached;
+
+  const { dic, aff } = dictionaryFiles(lang);
+  if (!fs.existsSync(dic) || !fs.existsSync(aff)) {
+    throw new AppError("not_found", `Dictionary ${lang} is not installed`);
+  }
+
+  const start = Date.now();
+  const dictionary = loadDictionary(dic, aff);
+  loaded.set(lang, dictionary);
+  log.info(
+    `📖 Dictionary ${lang} loaded (${dictionary.words.size} words, ${Date.now() - start}ms)`
+  );
+  return dictionary;
+}
+
+/**
+ * Find misspelled words in a text.
+ *
+ * Words containing digits and single letters are skipped; hyphenated words
+ * are checked part by part.
+ */
+export function checkSpelling(text: string, lang: DictionaryLanguage): SpellingIssue[] {
+  assertLanguage(lang);
+  const dict = getDictionary(lang);
+  const userWords = loadUserWords();
+  const issues: SpellingIssue[] = [];
+
+  for (const match of text.matchAll(WORD_PATTERN)) {
+    const word = match[0].replace(/['’]+$/, "");
+    if (word.length < 2) continue;
+    const offset = match.index ?? 0;
+    const neighbours = text.charAt(offset - 1) + text.charAt(offset + match[0].length);
+    if (/\d/.test(neighbours)) continue;
+
+    if (!isCorrect(dict, word, userWords)) {
+      issues.push({
+        word,
+        offset,
+        suggestions: suggest(dict, word, userWords),
+      });
+    }
+  }
+  return issues;
+}
+
+/**
+ * Register IPC handlers for dictionaries and spell checking.
+ */
+export function registerSpellcheckHandlers(): void {
+  handle("list-dictionaries", () => listDictionaries(), "read");
+  handle("install-dictionary", (_event, lang: DictionaryLanguage) => installDictionary(lang));
+  handle("remove-dictionary", (_event, lang: DictionaryLanguage) => removeDictionary(lang));
+  handle("add-dictionary-word", (_event, word: string) => addDictionaryWord(word));
+  handle(
+    "check-spelling",
+    (_event, text: string, lang: DictionaryLanguage) => checkSpelling(text, lang),
+    "read"
+  );
+}
@@ -0,0 +1,87 @@
+/**
+ * Billino Desktop – Customer Statements
+ *
+ * A statement (Kontoauszug) lists all invoices of one customer in a period
+ * with their amounts and the period's totals, for customers who ask for an
+ * account overview. The backend builds the PDF with the same generator as
+ * invoices and stores it as type "customer_statement"; the renderer shows
+ * it through billino-pdf://pdf/<pdfId>.
+ *
+ * Billino does not record payments, so the statement lists what was
+ * invoiced, not what is still open.
+ */
+
+import log from "electron-log/main";
+import { callBackend } from "./api";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+
+export interface StatementRange {
+  /** First day, YYYY-MM-DD (inclusive). */
+  from: string;
+  /** Last day, YYYY-MM-DD (inclusive). */
+  to: string;
+}
+
+export interface CustomerStatement {
+  customerId: number;
+  pdfId: number;
+  range: StatementRange;
+  createdAt: string;
+}
+
+const DATE_PATTERN = /^\d{4}-\d{2}-\d{2}$/;
+
+function validateRange(range: StatementRange): void {
+  if (!DATE_PATTERN.test(range?.from ?? "") || !DATE_PATTERN.test(range?.to ?? "")) {
+    throw new AppError("invalid_input", `Invalid statement range: ${JSON.stringify(range)}`, {
+      message: "Bitte einen gültigen Zeitraum angeben.",
+    });
+  }
+  if (range.from > range.to) {
+    throw new AppError("invalid_input", "Statement range ends before it starts", {
+      message: "Das Enddatum liegt vor dem Startdatum.",
+    });
+  }
+}
+
+/**
+ * Generate a statement PDF of all invoices of a customer in `range`.
+ *
+ * @param profileId Issuing profile (default: of the customer's latest invoice)
+ * @throws AppError for an invalid range, or if the customer does not exist
+ *         or has no invoices
+ */
+export async function generateCustomerStatement(
+  customerId: number,
+  range: StatementRange,
+  profileId?: number
+): Promise<CustomerStatement> {
+  validateRange(range);
+  const pdf = (await callBackend("POST /pdfs/customers/{customer_id}/statement", {
+    params: { customer_id: customerId },
+    query: { date_from: range.from, date_to: range.to, profile_id: profileId },
+  })) as { id: number; created_at: string };
+
+  const statement = {
+    customerId,
+    pdfId: pdf.id,
+    range: { from: range.from, to: range.to },
+    createdAt: pdf.created_at,
+  };
+  log.info(`📄 Statement for customer ${customerId} (${range.from} – ${range.to}) generated`);
+  emitEvent("statement:generated", statement);
+  return statement;
+}
+
+/**
+ * Register IPC handlers for customer statements.
+ */
+export function registerStatementHandlers(): void {
+  handle(
+    "generate-customer-statement",
+    (_event, customerId: number, range: StatementRange, profileId?: number) =>
+      generateCustomerStatement(customerId, range, profileId)
+  );
+}
@@ -0,0 +1,219 @@
+/**
+ * Billino Desktop – Persisted Task Queue
+ *
+ * Run-once tasks ("verify backup X", "re-send email Y") that must not be
+ * lost when Billino quits or crashes. Tasks are stored in
+ * AppData/Roaming/Billino/tasks.json and drained one at a time once the
+ * backend is healthy:
+ * - a task runs when its `runAt` time has come
+ * - failures are retried with backoff up to `maxAttempts`, then the task
+ *   stays listed as "failed" until it is cancelled
+ * - a task that was running when the app died runs again after the
+ *   restart, so runners must be idempotent
+ *
+ * Modules register a runner per task kind; tasks of a kind nobody
+ * registered wait (e.g. written by a newer version).
+ */
+
+import { app } from "electron";
+import { randomUUID } from "crypto";
+import fs from "fs";
+import path from "path";
+import log from "electron-log/main";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+
+export type TaskStatus = "pending" | "running" | "failed";
+
+export interface QueuedTask {
+  id: string;
+  kind: string;
+  /** German description for the task list. */
+  label: string;
+  payload: unknown;
+  status: TaskStatus;
+  createdAt: string;
+  runAt: string;
+  attempts: number;
+  maxAttempts: number;
+  lastError: string | null;
+}
+
+export interface EnqueueOptions {
+  /** ISO time; default: now. */
+  runAt?: string;
+  maxAttempts?: number;
+}
+
+type TaskRunner = (payload: unknown) => Promise<void>;
+
+const DRAIN_INTERVAL_MS = 30_000;
+const FIRST_RETRY_MS = 60_000;
+const MAX_RETRY_MS = 60 * 60 * 1000;
+const DEFAULT_MAX_ATTEMPTS = 5;
+
+const runners = new Map<string, TaskRunner>();
+let tasks: QueuedTask[] | null = null;
+let timer: NodeJS.Timeout | null = null;
+let draining = false;
+
+function getTasksPath(): string {
+  return path.join(app.getPath("userData"), "tasks.json");
+}
+
+function loadTasks(): QueuedTask[] {
+  if (tasks) return tasks;
+  try {
+    tasks = JSON.parse(fs.readFileSync(getTasksPath(), "utf-8")) as QueuedTask[];
+  } catch {
+    tasks = [];
+  }
+  // Interrupted by a crash or quit: run again
+  for (const task of tasks) {
+    if (task.status === "running") task.status = "pending";
+  }
+  return tasks;
+}
+
+function saveTasks(): void {
+  const filePath = getTasksPath();
+  const tmpPath = `${filePath}.tmp`;
+  fs.writeFileSync(tmpPath, JSON.stringify(loadTasks(), null, 2), "utf-8");
+  fs.renameSync(tmpPath, filePath);
+}
+
+/**
+ * Register the runner for a task kind (call at startup, before draining).
+ */
+export function registerTaskKind(kind: string, runner: TaskRunner): void {
+  runners.set(kind, runner);
+}
+
+/**
+ * Add a task to the queue; it is stored before this returns.
+ */
+export function enqueueTask(
+  kind: string,
+  label: string,
+  payload: unknown,
+  options: EnqueueOptions = {}
+): QueuedTask {
+  const now = new Date().toISOString();
+  const task: QueuedTask = {
+    id: randomUUID(),
+    kind,
+    label,
+    payload,
+    status: "pending",
+    createdAt: now,
+    runAt: options.runAt ?? now,
+    attempts: 0,
+    maxAttempts: options.maxAttempts ?? DEFAULT_MAX_ATTEMPTS,
+    lastError: null,
+  };
+  loadTasks().push(task);
+  saveTasks();
+  log.info(`🗂️ Task queued: ${label} (${kind}, due ${task.runAt})`);
+  if (timer) setImmediate(() => void drainTasks());
+  return task;
+}
+
+/**
+ * Tasks that have not completed yet (pending, running, failed).
+ */
+export function listPendingTasks(): QueuedTask[] {
+  return loadTasks().map((task) => ({ ...task }));
+}
+
+/**
+ * Remove a task. A running task finishes, but is not retried.
+ *
+ * @throws AppError if there is no such task
+ */
+export function cancelTask(id: string): void {
+  const list = loadTasks();
+  const index = list.findIndex((task) => task.id === id);
+  if (index < 0) throw new AppError("not_found", `Task not found: ${id}`);
+  const [task] = list.splice(index, 1);
+  saveTasks();
+  log.info(`🗂️ Task cancelled: ${task.label}`);
+}
+
+async function runTask(task: QueuedTask, runner: TaskRunner): Promise<void> {
+  task.status = "running";
+  task.attempts++;
+  saveTasks();
+  let error: unknown = null;
+  try {
+    await runner(task.payload);
+  } catch (err) {
+    error = err;
+  }
+
+  const list = loadTasks();
+  // Cancelled while running: not retried
+  if (!list.includes(task)) return;
+  if (error === null) {
+    list.splice(list.indexOf(task), 1);
+    log.info(`✅ Task done: ${task.label}`);
+    emitEvent("tasks:completed", { id: task.id, kind: task.kind, label: task.label });
+  } else {
+    task.lastError = error instanceof AppError ? error.userMessage : String(error);
+    if (task.attempts >= task.maxAttempts) {
+      task.status = "failed";
+      log.error(`❌ Task failed for good: ${task.label}: ${error}`);
+      emitEvent("tasks:failed", { ...task });
+    } else {
+      const delayMs = Math.min(FIRST_RETRY_MS * 2 ** (task.attempts - 1), MAX_RETRY_MS);
+      task.status = "pending";
+      task.runAt = new Date(Date.now() + delayMs).toISOString();
+      log.warn(`⚠️ Task ${task.label} failed (attempt ${task.attempts}), retry ${task.runAt}`);
+    }
+  }
+  saveTasks();
+}
+
+/**
+ * Run all due tasks, one after another.
+ */
+export async function drainTasks(): Promise<number> {
+  if (draining) return 0;
+  draining = true;
+  let ran = 0;
+  try {
+    const now = Date.now();
+    const due = loadTasks().filter(
+      (task) => task.status === "pending" && Date.parse(task.runAt) <= now
+    );
+    for (const task of due) {
+      if (!loadTasks().includes(task)) continue;
+      const runner = runners.get(task.kind);
+      if (!runner) continue;
+      await runTask(task, runner);
+      ran++;
+    }
+  } finally {
+    draining = false;
+  }
+  return ran;
+}
+
+/**
+ * Start draining the queue periodically (call once the backend is healthy).
+ */
+export function startTaskQueue(): void {
+  if (timer) return;
+  const waiting = loadTasks().filter((task) => task.status === "pending").length;
+  if (waiting > 0) log.info(`🗂️ Resuming ${waiting} queued task(s)`);
+  timer = setInterval(() => void drainTasks(), DRAIN_INTERVAL_MS);
+  void drainTasks();
+}
+
+/**
+ * Register IPC handlers for the task queue.
+ */
+export function registerTaskHandlers(): void {
+  handle("list-pending-tasks", () => listPendingTasks(), "read");
+  handle("cancel-task", (_event, id: string) => cancelTask(id));
+}
@@ -0,0 +1,18 @@
+// Stand-in for electron-log/main in unit tests: logging goes nowhere.
+const noop = () => undefined;
+
+const log = {
+  error: noop,
+  warn: noop,
+  info: noop,
+  verbose: noop,
+  debug: noop,
+  silly: noop,
+  log: noop,
+  hooks: [],
+  transports: { file: {}, console: {} },
+  initialize: noop,
+  scope: () => log,
+};
+
+module.exports = log;
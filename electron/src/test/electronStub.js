@@ -0,0 +1,37 @@
+// Stand-in for the electron module: unit tests run in plain Node, where
+// require("electron") only returns the path of the binary. Tests replace
+// single functions with jest.spyOn() where they depend on them.
+const os = require("os");
+
+const noop = () => undefined;
+
+module.exports = {
+  app: {
+    getPath: () => os.tmpdir(),
+    getVersion: () => "0.0.0-test",
+    isPackaged: false,
+    isReady: () => false,
+    on: noop,
+    once: noop,
+  },
+  ipcMain: { handle: noop, on: noop, removeHandler: noop },
+  webContents: { getAllWebContents: () => [] },
+  BrowserWindow: { getAllWindows: () => [], getFocusedWindow: () => null },
+  dialog: {
+    showMessageBox: async () => ({ response: 0 }),
+    showSaveDialog: async () => ({ canceled: true }),
+    showOpenDialog: async () => ({ canceled: true, filePaths: [] }),
+  },
+  shell: { openPath: async () => "" },
+  session: {
+    defaultSession: { getCacheSize: async () => 0, clearCache: async () => undefined },
+  },
+  nativeTheme: { on: noop },
+  powerMonitor: { on: noop, getSystemIdleTime: () => 0, isOnBatteryPower: () => false },
+  Notification: class {
+    static isSupported() {
+      return false;
+    }
+    show() {}
+  },
+};
@@ -0,0 +1,10 @@
+import { getSmallBusinessLimits } from "./thresholds";
+
+describe("getSmallBusinessLimits", () => {
+  it("kennt die Grenzen bis 2019, ab 2020 und ab 2025", () => {
+    expect(getSmallBusinessLimits(2019)).toEqual({ previousYear: 17_500, currentYear: 50_000 });
+    expect(getSmallBusinessLimits(2020)).toEqual({ previousYear: 22_000, currentYear: 50_000 });
+    expect(getSmallBusinessLimits(2024)).toEqual({ previousYear: 22_000, currentYear: 50_000 });
+    expect(getSmallBusinessLimits(2025)).toEqual({ previousYear: 25_000, currentYear: 100_000 });
+  });
+});
@@ -0,0 +1,202 @@
+/**
+ * Billino Desktop – Kleinunternehmer Threshold Monitoring
+ *
+ * Small businesses invoicing without VAT (§19 UStG) lose that status when
+ * their revenue exceeds the legal limits:
+ * - since 2025: 25,000 € in the previous year and 100,000 € in the current
+ *   year (exceeding the latter ends the status immediately)
+ * - 2020 to 2024: 22,000 € (previous year) and 50,000 € (expected current
+ *   year)
+ * - until 2019: 17,500 € (previous year) and 50,000 € (expected current year)
+ *
+ * The yearly revenue is the net amount invoiced by profiles without VAT, from
+ * the backend's VAT summary (`small_business_only`). The check runs
+ * at startup and every few hours while any profile invoices without VAT;
+ * crossing a configured margin (settings: smallBusiness.warnAtPercent)
+ * emits `threshold:warning` and shows a notification once per year and
+ * margin.
+ */
+
+import { app, Notification } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { requestBackend } from "./api";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { getSettings } from "./settings";
+
+export type ThresholdKind = "current-year" | "next-year";
+
+export interface ThresholdWarning {
+  year: number;
+  kind: ThresholdKind;
+  limit: number;
+  revenue: number;
+  /** Margin that was reached, e.g. 80 (percent of the limit). */
+  percent: number;
+  exceeded: boolean;
+  message: string;
+}
+
+export interface ThresholdStatus {
+  year: number;
+  revenue: number;
+  previousYearRevenue: number;
+  limits: { previousYear: number; currentYear: number };
+  /** Previous year above its limit: §19 does not apply this year. */
+  previousYearExceeded: boolean;
+  warnings: ThresholdWarning[];
+  checkedAt: string;
+}
+
+const CHECK_INTERVAL_MS = 6 * 60 * 60 * 1000;
+const INITIAL_DELAY_MS = 60_000;
+
+let timer: NodeJS.Timeout | null = null;
+
+/**
+ * §19 UStG limits for a calendar year.
+ */
+export function getSmallBusinessLimits(year: number): ThresholdStatus["limits"] {
+  if (year >= 2025) return { previousYear: 25_000, currentYear: 100_000 };
+  if (year >= 2020) return { previousYear: 22_000, currentYear: 50_000 };
+  return { previousYear: 17_500, currentYear: 50_000 };
+}
+
+const euro = (value: number): string =>
+  value.toLocaleString("de-DE", { style: "currency", currency: "EUR" });
+
+/**
+ * Net revenue of the §19 profiles in a year; invoices of profiles with VAT
+ * do not count.
+ */
+async function fetchRevenue(year: number): Promise<number> {
+  const summary = await requestBackend<{ total_net: number }>(
+    `/fiscal-years/${year}/vat-summary?small_business_only=true`
+  );
+  return summary.total_net;
+}
+
+/**
+ * Compute the current year's revenue against both §19 limits.
+ */
+export async function checkSmallBusinessThresholds(
+  year: number = new Date().getFullYear()
+): Promise<ThresholdStatus> {
+  const [revenue, previousYearRevenue] = await Promise.all([
+    fetchRevenue(year),
+    fetchRevenue(year - 1),
+  ]);
+  const limits = getSmallBusinessLimits(year);
+  // This year's revenue decides next year's status
+  const nextYearLimit = getSmallBusinessLimits(year + 1).previousYear;
+  const margins = [...getSettings().smallBusiness.warnAtPercent].sort((a, b) => b - a);
+
+  const warnings: ThresholdWarning[] = [];
+  const checks: Array<[ThresholdKind, number, string]> = [
+    ["current-year", limits.currentYear, `die Umsatzgrenze ${year}`],
+    ["next-year", nextYearLimit, `die Vorjahresgrenze für ${year + 1}`],
+  ];
+  for (const [kind, limit, label] of checks) {
+    const reached = (revenue / limit) * 100;
+    const exceeded = revenue > limit;
+    const percent = exceeded ? 100 : margins.find((margin) => reached >= margin);
+    if (percent === undefined) continue;
+
+    warnings.push({
+      year,
+      kind,
+      limit,
+      revenue,
+      percent,
+      exceeded,
+      message: exceeded
+        ? `Der Umsatz ${year} (${euro(revenue)}) überschreitet ${label} (${euro(limit)}).`
+        : `Der Umsatz ${year} (${euro(revenue)}) hat ${Math.floor(reached)} % ` +
+          `von ${label} (${euro(limit)}) erreicht.`,
+    });
+  }
+
+  return {
+    year,
+    revenue,
+    previousYearRevenue,
+    limits,
+    previousYearExceeded: previousYearRevenue > limits.previousYear,
+    warnings,
+    checkedAt: new Date().toISOString(),
+  };
+}
+
+// ─── Periodic Monitoring ─────────────────────────────────────────────────────
+
+function getNotifiedPath(): string {
+  return path.join(app.getPath("userData"), "threshold-warnings.json");
+}
+
+function loadNotified(): string[] {
+  try {
+    return JSON.parse(fs.readFileSync(getNotifiedPath(), "utf-8")) as string[];
+  } catch {
+    return [];
+  }
+}
+
+/**
+ * Whether any profile invoices without VAT (otherwise §19 is irrelevant).
+ */
+async function usesSmallBusinessRule(): Promise<boolean> {
+  const profiles = await requestBackend<{ items: Array<{ include_tax: boolean }> }>(
+    "/profiles/?pageSize=100"
+  );
+  return profiles.items.some((profile) => !profile.include_tax);
+}
+
+async function runThresholdCheck(): Promise<void> {
+  if (!getSettings().smallBusiness.monitorThresholds) return;
+
+  try {
+    if (!(await usesSmallBusinessRule())) return;
+
+    const status = await checkSmallBusinessThresholds();
+    const notified = loadNotified();
+    for (const warning of status.warnings) {
+      const key = `${warning.year}:${warning.kind}:${warning.percent}`;
+      if (notified.includes(key)) continue;
+
+      log.warn(`⚠️ §19 UStG: ${warning.message}`);
+      emitEvent("threshold:warning", warning);
+      if (Notification.isSupported()) {
+        new Notification({
+          title: "Billino – Kleinunternehmerregelung",
+          body: warning.message,
+        }).show();
+      }
+      notified.push(key);
+    }
+    fs.writeFileSync(getNotifiedPath(), JSON.stringify(notified), "utf-8");
+  } catch (err) {
+    log.warn(`⚠️ Threshold check failed: ${err}`);
+  }
+}
+
+/**
+ * Start periodic threshold monitoring (call once the backend is healthy).
+ */
+export function startThresholdMonitoring(): void {
+  if (timer) return;
+  setTimeout(() => void runThresholdCheck(), INITIAL_DELAY_MS);
+  timer = setInterval(() => void runThresholdCheck(), CHECK_INTERVAL_MS);
+}
+
+/**
+ * Register IPC handlers for threshold checks.
+ */
+export function registerThresholdHandlers(): void {
+  handle(
+    "check-small-business-thresholds",
+    (_event, year?: number) => checkSmallBusinessThresholds(year),
+    "read"
+  );
+}
@@ -0,0 +1,156 @@
+/**
+ * Billino Desktop – Clock Check
+ *
+ * Invoice dates, due dates, dunning deadlines and audit timestamps all come
+ * from the system clock. If it is off by days (dead CMOS battery, a VM
+ * restored from a snapshot), invoices get wrong dates without anyone
+ * noticing.
+ *
+ * When enabled in the settings (`timeSync`, off by default – it contacts
+ * an external server), the shell asks an NTP server for the current time
+ * once at startup (SNTP over UDP port 123) and compares. An offset above
+ * `maxOffsetSeconds` is logged and shows up as a `clock_skew` issue in the
+ * app condition; `get-time-sync-status` returns the last result and can
+ * check again.
+ */
+
+import dgram from "dgram";
+import log from "electron-log/main";
+import { handle } from "./ipc";
+import { getSettings } from "./settings";
+
+export interface TimeSyncStatus {
+  enabled: boolean;
+  server: string;
+  /** Local clock minus NTP time (ms); positive = local clock is ahead. */
+  offsetMs: number | null;
+  roundTripMs: number | null;
+  maxOffsetSeconds: number;
+  /** Whether the offset is within the limit; null if not checked. */
+  ok: boolean | null;
+  error: string | null;
+  checkedAt: string | null;
+}
+
+export interface NtpSample {
+  offsetMs: number;
+  roundTripMs: number;
+}
+
+const NTP_PORT = 123;
+const NTP_TIMEOUT_MS = 5_000;
+/** Seconds from 1900-01-01 (NTP epoch) to 1970-01-01 (Unix epoch). */
+const NTP_EPOCH_OFFSET_S = 2_208_988_800;
+
+let lastStatus: TimeSyncStatus | null = null;
+
+function readTimestamp(buffer: Buffer, offset: number): number {
+  const seconds = buffer.readUInt32BE(offset);
+  const fraction = buffer.readUInt32BE(offset + 4);
+  return (seconds - NTP_EPOCH_OFFSET_S) * 1000 + (fraction * 1000) / 2 ** 32;
+}
+
+/**
+ * Ask an NTP server for the time (RFC 4330 client request).
+ *
+ * @returns Clock offset and round trip, from the four SNTP timestamps
+ */
+export function queryNtp(server: string, timeoutMs: number = NTP_TIMEOUT_MS): Promise<NtpSample> {
+  return new Promise((resolve, reject) => {
+    const socket = dgram.createSocket("udp4");
+    const request = Buffer.alloc(48);
+    request[0] = 0x23; // LI 0, version 4, mode 3 (client)
+    let sentAt = 0;
+    let settled = false;
+
+    const settle = (result: NtpSample | Error): void => {
+      if (settled) return;
+      settled = true;
+      clearTimeout(timer);
+      socket.close();
+      if (result instanceof Error) reject(result);
+      else resolve(result);
+    };
+    const timer = setTimeout(
+      () => settle(new Error(`No answer from ${server} within ${timeoutMs}ms`)),
+      timeoutMs
+    );
+
+    socket.on("error", settle);
+    socket.on("message", (message) => {
+      const receivedAt = Date.now();
+      // Mode 4 = server; stratum 0 is a "kiss-o'-death" refusal
+      if (message.length < 48 || (message[0] & 0x07) !== 4 || message[1] === 0) {
+        settle(new Error(`Invalid NTP answer from ${server}`));
+        return;
+      }
+      const serverReceived = readTimestamp(message, 32);
+      const serverSent = readTimestamp(message, 40);
+      settle({
+        offsetMs: Math.round((sentAt - serverReceived + (receivedAt - serverSent)) / 2),
+        roundTripMs: receivedAt - sentAt - (serverSent - serverReceived),
+      });
+    });
+    socket.send(request, NTP_PORT, server, (err) => {
+      if (err) settle(err);
+    });
+    sentAt = Date.now();
+  });
+}
+
+/**
+ * Compare the system clock with the configured NTP server.
+ *
+ * Never throws; a failed query is reported in `error`. Disabled in the
+ * settings, nothing is sent.
+ */
+export async function checkTimeSync(): Promise<TimeSyncStatus> {
+  const { enabled, server, maxOffsetSeconds } = getSettings().timeSync;
+  const status: TimeSyncStatus = {
+    enabled,
+    server,
+    offsetMs: null,
+    roundTripMs: null,
+    maxOffsetSeconds,
+    ok: null,
+    error: null,
+    checkedAt: null,
+  };
+  if (!enabled) return (lastStatus = status);
+
+  try {
+    const sample = await queryNtp(server);
+    status.offsetMs = sample.offsetMs;
+    status.roundTripMs = sample.roundTripMs;
+    status.ok = Math.abs(sample.offsetMs) <= maxOffsetSeconds * 1000;
+    if (status.ok) {
+      log.info(`🕒 System clock within ${sample.offsetMs}ms of ${server}`);
+    } else {
+      log.warn(`⚠️ System clock is off by ${Math.round(sample.offsetMs / 1000)}s (${server})`);
+    }
+  } catch (err) {
+    status.error = err instanceof Error ? err.message : String(err);
+    log.warn(`⚠️ Clock check against ${server} failed: ${status.error}`);
+  }
+  status.checkedAt = new Date().toISOString();
+  return (lastStatus = status);
+}
+
+/**
+ * Result of the last clock check (null before the first).
+ */
+export function getTimeSyncStatus(): TimeSyncStatus | null {
+  return lastStatus;
+}
+
+/**
+ * Register IPC handlers for the clock check.
+ */
+export function registerTimeSyncHandlers(): void {
+  handle(
+    "get-time-sync-status",
+    (_event, refresh?: boolean) =>
+      refresh || !lastStatus ? checkTimeSync() : Promise.resolve(lastStatus),
+    "read"
+  );
+}
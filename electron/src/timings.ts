@@ -0,0 +1,83 @@
+/**
+ * Billino Desktop – Startup Timing Instrumentation
+ *
+ * Records how long each startup phase takes (config load, binary
+ * resolution, spawn, first healthy response, ...) so startup regressions
+ * can be measured instead of guessed. Timestamps are relative to process
+ * start (`performance.now()`).
+ */
+
+import { performance } from "perf_hooks";
+import log from "electron-log/main";
+import { handle } from "./ipc";
+
+export interface PhaseTiming {
+  phase: string;
+  startMs: number;
+  durationMs: number;
+}
+
+export interface StartupTimings {
+  /** Time from process start until the last recorded phase ended. */
+  totalMs: number;
+  phases: PhaseTiming[];
+}
+
+const phases: PhaseTiming[] = [];
+
+function record(phase: string, start: number): void {
+  const durationMs = Math.round(performance.now() - start);
+  phases.push({ phase, startMs: Math.round(start), durationMs });
+  log.info(`⏱️ Startup phase "${phase}": ${durationMs}ms`);
+}
+
+/**
+ * Time a synchronous startup phase.
+ */
+export function timePhase<T>(phase: string, fn: () => T): T {
+  const start = performance.now();
+  try {
+    return fn();
+  } finally {
+    record(phase, start);
+  }
+}
+
+/**
+ * Time an asynchronous startup phase.
+ */
+export async function timePhaseAsync<T>(phase: string, fn: () => Promise<T>): Promise<T> {
+  const start = performance.now();
+  try {
+    return await fn();
+  } finally {
+    record(phase, start);
+  }
+}
+
+/**
+ * Get all recorded startup phases.
+ */
+export function getStartupTimings(): StartupTimings {
+  const last = phases[phases.length - 1];
+  return {
+    totalMs: last ? last.startMs + last.durationMs : 0,
+    phases: [...phases],
+  };
+}
+
+/**
+ * Log a one-line summary of all phases.
+ */
+export function logStartupSummary(): void {
+  const { totalMs } = getStartupTimings();
+  const summary = phases.map((p) => `${p.phase}=${p.durationMs}ms`).join(", ");
+  log.info(`⏱️ Startup finished after ${totalMs}ms (${summary})`);
+}
+
+/**
+ * Register the IPC handler exposing startup timings.
+ */
+export function registerTimingHandlers(): void {
+  handle("get-startup-timings", () => getStartupTimings(), "read");
+}
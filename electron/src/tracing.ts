@@ -0,0 +1,250 @@
+/**
+ * Billino Desktop – Latency Tracing
+ *
+ * Every IPC command is a trace. Spans inside it are collected via
+ * AsyncLocalStorage, so nested work needs no extra parameters:
+ *
+ *   command:generate-invoice-pdf     (ipc.ts, command entry to result)
+ *   └─ backend POST /pdfs/invoices/7 (api.ts, request to last byte)
+ *      ├─ backend:handler            (backend Server-Timing header)
+ *      └─ parse                      (JSON deserialization)
+ *
+ * Requests the renderer sends to the backend itself (most saves) are
+ * traced as `renderer <METHOD> <path>` with the backend's handler time.
+ * Shell requests carry the trace id as X-Trace-Id; the backend logs slow
+ * requests with it. The last spans are kept in memory and can be exported
+ * as JSON or in Chrome trace format (chrome://tracing, Perfetto) to answer
+ * "why does saving an invoice take 4 seconds"; `export-trace` asks for the
+ * destination in a save dialog.
+ */
+
+import { AsyncLocalStorage } from "async_hooks";
+import crypto from "crypto";
+import fs from "fs";
+import { performance } from "perf_hooks";
+import { session } from "electron";
+import log from "electron-log/main";
+import { getConfig } from "./config";
+import { handle } from "./ipc";
+import { chooseSavePath } from "./savedialog";
+
+export type SpanStatus = "ok" | "error";
+export type TraceFormat = "json" | "chrome";
+
+export interface TraceSpan {
+  traceId: string;
+  spanId: string;
+  parentId: string | null;
+  name: string;
+  /** Start relative to process start (ms). */
+  startMs: number;
+  durationMs: number;
+  status: SpanStatus;
+  attributes: Record<string, string | number>;
+}
+
+export interface TraceExport {
+  path: string;
+  format: TraceFormat;
+  spans: number;
+}
+
+interface SpanContext {
+  traceId: string;
+  spanId: string;
+}
+
+/** About an hour of normal use; older spans are dropped. */
+const MAX_SPANS = 5000;
+
+const context = new AsyncLocalStorage<SpanContext>();
+const spans: TraceSpan[] = [];
+
+function newId(bytes: number): string {
+  return crypto.randomBytes(bytes).toString("hex");
+}
+
+function push(span: TraceSpan): void {
+  spans.push(span);
+  if (spans.length > MAX_SPANS) spans.splice(0, spans.length - MAX_SPANS);
+}
+
+/**
+ * Trace id of the running command, if any.
+ */
+export function currentTraceId(): string | null {
+  return context.getStore()?.traceId ?? null;
+}
+
+/**
+ * Run `fn` as a span. Outside a command it starts a new trace.
+ *
+ * @param attributes Extra details (e.g. status code); may be extended by
+ *                   `fn` through the passed object until it finishes
+ */
+export async function withSpan<T>(
+  name: string,
+  fn: (attributes: Record<string, string | number>) => Promise<T> | T,
+  attributes: Record<string, string | number> = {}
+): Promise<T> {
+  const parent = context.getStore();
+  const own: SpanContext = { traceId: parent?.traceId ?? newId(16), spanId: newId(8) };
+  const start = performance.now();
+  let status: SpanStatus = "ok";
+  try {
+    return await context.run(own, () => fn(attributes));
+  } catch (err) {
+    status = "error";
+    throw err;
+  } finally {
+    push({
+      traceId: own.traceId,
+      spanId: own.spanId,
+      parentId: parent?.spanId ?? null,
+      name,
+      startMs: start,
+      durationMs: performance.now() - start,
+      status,
+      attributes,
+    });
+  }
+}
+
+/**
+ * Record a span measured elsewhere (e.g. reported by the backend) as a
+ * child of the current span.
+ */
+export function recordSpan(
+  name: string,
+  startMs: number,
+  durationMs: number,
+  attributes: Record<string, string | number> = {}
+): void {
+  const parent = context.getStore();
+  if (!parent) return;
+  push({
+    traceId: parent.traceId,
+    spanId: newId(8),
+    parentId: parent.spanId,
+    name,
+    startMs,
+    durationMs,
+    status: "ok",
+    attributes,
+  });
+}
+
+/**
+ * Processing time from a `Server-Timing: app;dur=12.3` header.
+ */
+export function parseServerTiming(header: string | null): number | null {
+  const match = header?.match(/(?:^|,)\s*app;dur=([\d.]+)/);
+  return match ? Number(match[1]) : null;
+}
+
+/**
+ * Recorded spans, oldest first.
+ */
+export function getSpans(): TraceSpan[] {
+  return spans.map((span) => ({ ...span, attributes: { ...span.attributes } }));
+}
+
+/**
+ * Trace the renderer's own backend requests (call once after the
+ * configuration is loaded). Any port matches, so requests to a blue-green
+ * standby are included.
+ */
+export function traceRendererRequests(): void {
+  const hosts = new Set([getConfig().host, "127.0.0.1", "localhost"]);
+  const filter = { urls: [...hosts].map((name) => `http://${name}/*`) };
+  const started = new Map<number, number>();
+  const { webRequest } = session.defaultSession;
+
+  webRequest.onSendHeaders(filter, (details) => started.set(details.id, performance.now()));
+  webRequest.onErrorOccurred(filter, (details) => started.delete(details.id));
+  webRequest.onCompleted(filter, (details) => {
+    const start = started.get(details.id);
+    started.delete(details.id);
+    if (start === undefined) return;
+
+    const traceId = newId(16);
+    const spanId = newId(8);
+    const url = new URL(details.url);
+    push({
+      traceId,
+      spanId,
+      parentId: null,
+      name: `renderer ${details.method} ${url.pathname}`,
+      startMs: start,
+      durationMs: performance.now() - start,
+      status: details.statusCode < 400 ? "ok" : "error",
+      attributes: { status: details.statusCode },
+    });
+    const headers = details.responseHeaders ?? {};
+    const timing = headers["Server-Timing"] ?? headers["server-timing"];
+    const handlerMs = parseServerTiming(timing?.join(",") ?? null);
+    if (handlerMs !== null) {
+      push({
+        traceId,
+        spanId: newId(8),
+        parentId: spanId,
+        name: "backend:handler",
+        startMs: start,
+        durationMs: handlerMs,
+        status: "ok",
+        attributes: {},
+      });
+    }
+  });
+}
+
+function toChromeTrace(recorded: TraceSpan[]): unknown {
+  // One "thread" per trace so the spans of a command stack up
+  const threads = new Map<string, number>();
+  return {
+    traceEvents: recorded.map((span) => {
+      if (!threads.has(span.traceId)) threads.set(span.traceId, threads.size + 1);
+      return {
+        name: span.name,
+        cat: span.name.split(/[: ]/)[0],
+        ph: "X",
+        ts: Math.round(span.startMs * 1000),
+        dur: Math.round(span.durationMs * 1000),
+        pid: process.pid,
+        tid: threads.get(span.traceId),
+        args: { ...span.attributes, traceId: span.traceId, status: span.status },
+      };
+    }),
+    displayTimeUnit: "ms",
+  };
+}
+
+/**
+ * Write the recorded spans to a file.
+ *
+ * @param format "json" (list of spans) or "chrome" (chrome://tracing, Perfetto)
+ */
+export async function exportTrace(
+  targetPath: string,
+  format: TraceFormat = "chrome"
+): Promise<TraceExport> {
+  const recorded = getSpans();
+  const content = format === "chrome" ? toChromeTrace(recorded) : { spans: recorded };
+  await fs.promises.writeFile(targetPath, JSON.stringify(content), "utf-8");
+  log.info(`🧭 Trace exported: ${recorded.length} spans → ${targetPath}`);
+  return { path: targetPath, format, spans: recorded.length };
+}
+
+/**
+ * Register IPC handlers for tracing.
+ */
+export function registerTracingHandlers(): void {
+  handle("export-trace", async (_event, format?: TraceFormat) => {
+    const targetPath = await chooseSavePath({
+      title: "Trace exportieren",
+      defaultName: `billino-trace-${new Date().toISOString().slice(0, 10)}.json`,
+      filters: [{ name: "Trace", extensions: ["json"] }],
+    });
+    return targetPath ? exportTrace(targetPath, format) : null;
+  });
+}
@@ -0,0 +1,31 @@
+import { beginOperation, endOperation } from "./operations";
+import { cancelTransfer, validateUploadEndpoint } from "./transfers";
+
+describe("validateUploadEndpoint", () => {
+  it("erlaubt die Upload-Routen für Logo und Anhänge", () => {
+    expect(validateUploadEndpoint("/profiles/1/logo")).toBe("/profiles/1/logo");
+    expect(validateUploadEndpoint("/invoices/42/attachments")).toBe("/invoices/42/attachments");
+  });
+
+  it.each([
+    "/shutdown",
+    "/backups/restore",
+    "/profiles/1/logo/../../shutdown",
+    "/profiles/x/logo",
+    "//evil.example/profiles/1/logo",
+    "http://localhost:8000/profiles/1/logo",
+  ])("lehnt %s ab", (endpoint) => {
+    expect(() => validateUploadEndpoint(endpoint)).toThrow();
+  });
+});
+
+describe("cancelTransfer", () => {
+  it("bricht nur Transfers ab, keine anderen Operationen", () => {
+    const backup = beginOperation("backup");
+
+    expect(cancelTransfer(backup.id)).toBe(false);
+    expect(cancelTransfer("unbekannt")).toBe(false);
+    expect(backup.signal.aborted).toBe(false);
+    endOperation(backup.id);
+  });
+});
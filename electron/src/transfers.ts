@@ -0,0 +1,353 @@
+/**
+ * Billino Desktop – Streaming File Transfers
+ *
+ * Moves large files between the backend and disk in chunks instead of
+ * buffering them in memory (exports can be several hundred MB):
+ * - `download-export`: streams GET /exports/<jobId>/download to a file the
+ *   user picks in the save dialog (never a path from the renderer); an
+ *   existing file is not overwritten
+ * - `upload-file`: streams a local file as multipart to one of the backend's
+ *   upload routes (UPLOAD_ROUTES – logos and attachments, nothing else)
+ *
+ * Every transfer is registered as an operation, so closing the window asks
+ * before interrupting it. `cancel-transfer` only aborts transfers, not
+ * other operations. Progress is reported with `transfer:progress` events.
+ */
+
+import { randomUUID } from "crypto";
+import fs from "fs";
+import path from "path";
+import { Readable, Transform } from "stream";
+import { pipeline } from "stream/promises";
+import type { ReadableStream as WebReadableStream } from "stream/web";
+import log from "electron-log/main";
+import { BackendRequestError } from "./api";
+import { getBackendUrl } from "./config";
+import { AppError } from "./errors";
+import { emitEvent } from "./events";
+import { handle } from "./ipc";
+import { abortOperation, beginOperation, endOperation } from "./operations";
+import { validateApiRequest } from "./proxy";
+import { chooseSavePath } from "./savedialog";
+
+export interface TransferProgress {
+  transferId: string;
+  direction: "download" | "upload";
+  transferredBytes: number;
+  /** Total size if known (Content-Length), otherwise null. */
+  totalBytes: number | null;
+}
+
+export interface TransferResult {
+  transferId: string;
+  path: string;
+  bytes: number;
+  durationMs: number;
+}
+
+/** Upload file types, detected from the file's magic bytes (not its name). */
+const UPLOAD_TYPES: Array<{ mime: string; magic: number[]; offset?: number }> = [
+  { mime: "image/png", magic: [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a] },
+  { mime: "image/jpeg", magic: [0xff, 0xd8, 0xff] },
+  { mime: "image/webp", magic: [0x57, 0x45, 0x42, 0x50], offset: 8 },
+  { mime: "application/pdf", magic: [0x25, 0x50, 0x44, 0x46, 0x2d] },
+];
+
+/** Backend routes that accept uploads; the renderer cannot pick any other. */
+const UPLOAD_ROUTES: RegExp[] = [/^\/profiles\/\d+\/logo$/, /^\/invoices\/\d+\/attachments$/];
+
+/** Largest accepted upload (attachments, logos). */
+const MAX_UPLOAD_BYTES = 100 * 1024 * 1024;
+
+/** Minimum interval between progress events per transfer. */
+const PROGRESS_INTERVAL_MS = 250;
+
+/** Operation ids of running transfers (the only ones `cancel-transfer` aborts). */
+const activeTransfers = new Set<string>();
+
+function beginTransfer(
+  kind: "export" | "upload",
+  label: string
+): { id: string; signal: AbortSignal } {
+  const operation = beginOperation(kind, label);
+  activeTransfers.add(operation.id);
+  return operation;
+}
+
+function endTransfer(id: string): void {
+  activeTransfers.delete(id);
+  endOperation(id);
+}
+
+function refuseExistingFile(filePath: string): void {
+  if (fs.existsSync(filePath)) {
+    throw new AppError("conflict", `File already exists: ${filePath}`, {
+      message: "Die Datei existiert bereits und wird nicht überschrieben.",
+      hint: "Bitte einen anderen Dateinamen wählen.",
+    });
+  }
+}
+
+/**
+ * Pass-through stream that counts bytes and emits throttled progress events.
+ */
+function progressCounter(progress: TransferProgress): Transform {
+  let lastEmit = 0;
+  return new Transform({
+    transform(chunk: Buffer, _encoding, callback) {
+      progress.transferredBytes += chunk.length;
+      const now = Date.now();
+      if (now - lastEmit >= PROGRESS_INTERVAL_MS) {
+        lastEmit = now;
+        emitEvent("transfer:progress", { ...progress });
+      }
+      callback(null, chunk);
+    },
+    flush(callback) {
+      emitEvent("transfer:progress", { ...progress });
+      callback();
+    },
+  });
+}
+
+function removePartialFile(filePath: string): void {
+  try {
+    fs.rmSync(filePath, { force: true });
+  } catch (err) {
+    log.warn(`⚠️ Could not remove partial file ${filePath}: ${err}`);
+  }
+}
+
+/**
+ * Stream a backend-generated export to disk.
+ *
+ * Data is written to `<target>.part` and renamed once complete, so an
+ * aborted download never leaves a truncated file under the final name.
+ *
+ * @param jobId Export job id (file name in the backend's exports folder)
+ * @param targetPath Absolute destination path, chosen in the main process
+ * @throws AppError("conflict") if the destination already exists
+ */
+export async function downloadExport(jobId: string, targetPath: string): Promise<TransferResult> {
+  if (!/^[A-Za-z0-9_-]+$/.test(jobId)) {
+    throw new AppError("invalid_input", `Invalid export job id: ${jobId}`);
+  }
+  if (!path.isAbsolute(targetPath)) {
+    throw new AppError("invalid_input", `Target path must be absolute: ${targetPath}`);
+  }
+  refuseExistingFile(targetPath);
+
+  const { id, signal } = beginTransfer("export", `Export ${jobId}`);
+  const partPath = `${targetPath}.part`;
+  const started = Date.now();
+
+  try {
+    const response = await fetch(`${getBackendUrl()}/exports/${jobId}/download`, { signal });
+    if (!response.ok || !response.body) {
+      const detail = response.ok ? "Empty response" : "Export download failed";
+      throw new BackendRequestError(response.status, detail, `/exports/${jobId}/download`);
+    }
+
+    const contentLength = Number(response.headers.get("content-length"));
+    const progress: TransferProgress = {
+      transferId: id,
+      direction: "download",
+      transferredBytes: 0,
+      totalBytes: Number.isFinite(contentLength) && contentLength > 0 ? contentLength : null,
+    };
+    emitEvent("transfer:progress", { ...progress });
+
+    fs.mkdirSync(path.dirname(targetPath), { recursive: true });
+    await pipeline(
+      Readable.fromWeb(response.body as WebReadableStream<Uint8Array>),
+      progressCounter(progress),
+      fs.createWriteStream(partPath),
+      { signal }
+    );
+    // The file may have been created while the download ran
+    refuseExistingFile(targetPath);
+    fs.renameSync(partPath, targetPath);
+
+    const result: TransferResult = {
+      transferId: id,
+      path: targetPath,
+      bytes: progress.transferredBytes,
+      durationMs: Date.now() - started,
+    };
+    log.info(
+      `📥 Export ${jobId} saved to ${targetPath} (${result.bytes} bytes, ${result.durationMs}ms)`
+    );
+    return result;
+  } catch (err) {
+    removePartialFile(partPath);
+    if (signal.aborted) {
+      log.warn(`⚠️ Export download cancelled: ${jobId}`);
+      throw new AppError("cancelled", "Transfer cancelled");
+    }
+    log.error(`❌ Export download failed (${jobId}): ${err}`);
+    throw err;
+  } finally {
+    endTransfer(id);
+  }
+}
+
+/**
+ * Detect the MIME type of a file from its first bytes.
+ *
+ * @returns The MIME type, or null if the type is not accepted for uploads
+ */
+export function sniffMimeType(filePath: string): string | null {
+  const header = Buffer.alloc(16);
+  const fd = fs.openSync(filePath, "r");
+  let length: number;
+  try {
+    length = fs.readSync(fd, header, 0, header.length, 0);
+  } finally {
+    fs.closeSync(fd);
+  }
+
+  const match = UPLOAD_TYPES.find(
+    ({ magic, offset = 0 }) =>
+      offset + magic.length <= length && magic.every((byte, i) => header[offset + i] === byte)
+  );
+  return match?.mime ?? null;
+}
+
+/**
+ * Check that `endpoint` is one of the upload routes.
+ *
+ * @throws AppError for invalid paths (see validateApiRequest) and any
+ *         other backend route
+ */
+export function validateUploadEndpoint(endpoint: unknown): string {
+  validateApiRequest("POST", endpoint);
+  const route = endpoint as string;
+  if (!UPLOAD_ROUTES.some((pattern) => pattern.test(route))) {
+    log.warn(`⚠️ Upload to ${route} refused`);
+    throw new AppError("permission_denied", `${route} is not an upload route`, {
+      message: "Dateien können hierhin nicht hochgeladen werden.",
+    });
+  }
+  return route;
+}
+
+/**
+ * Stream a local file to a backend endpoint as `multipart/form-data`
+ * (single field `file`).
+ *
+ * The file is validated first (size limit, type detected from content) and
+ * then read in chunks while the request body is being sent.
+ *
+ * @param filePath Absolute path of the file to upload
+ * @param endpoint Upload route, e.g. "/profiles/1/logo"
+ * @returns The backend's JSON response
+ */
+export async function uploadFile(filePath: string, endpoint: string): Promise<unknown> {
+  validateUploadEndpoint(endpoint);
+  if (!path.isAbsolute(filePath)) {
+    throw new AppError("invalid_input", `File path must be absolute: ${filePath}`);
+  }
+
+  const { size } = fs.statSync(filePath);
+  if (size === 0 || size > MAX_UPLOAD_BYTES) {
+    throw new AppError(
+      "invalid_input",
+      `File size ${size} bytes is outside the allowed range (1-${MAX_UPLOAD_BYTES})`,
+      { message: "Die Datei ist leer oder zu groß." }
+    );
+  }
+  const mimeType = sniffMimeType(filePath);
+  if (!mimeType) {
+    throw new AppError("invalid_input", `Unsupported file type: ${path.basename(filePath)}`, {
+      message: "Dieser Dateityp wird nicht unterstützt.",
+    });
+  }
+
+  const boundary = `----BillinoBoundary${randomUUID().replace(/-/g, "")}`;
+  const filename = path.basename(filePath).replace(/["\r\n]/g, "_");
+  const head = Buffer.from(
+    `--${boundary}\r\n` +
+      `Content-Disposition: form-data; name="file"; filename="${filename}"\r\n` +
+      `Content-Type: ${mimeType}\r\n\r\n`
+  );
+  const tail = Buffer.from(`\r\n--${boundary}--\r\n`);
+
+  const { id, signal } = beginTransfer("upload", `Upload ${filename}`);
+  const progress: TransferProgress = {
+    transferId: id,
+    direction: "upload",
+    transferredBytes: 0,
+    totalBytes: size,
+  };
+  emitEvent("transfer:progress", { ...progress });
+
+  async function* multipartBody(): AsyncGenerator<Buffer> {
+    yield head;
+    for await (const chunk of fs.createReadStream(filePath).pipe(progressCounter(progress))) {
+      yield chunk as Buffer;
+    }
+    yield tail;
+  }
+
+  try {
+    const response = await fetch(`${getBackendUrl()}${endpoint}`, {
+      method: "POST",
+      headers: {
+        "Content-Type": `multipart/form-data; boundary=${boundary}`,
+        "Content-Length": String(head.length + size + tail.length),
+      },
+      body: multipartBody(),
+      signal,
+      // Required by Node's fetch for streamed request bodies
+      duplex: "half",
+    });
+
+    if (!response.ok) {
+      throw new BackendRequestError(response.status, await response.text(), endpoint);
+    }
+    log.info(`📤 Uploaded ${filename} (${mimeType}, ${size} bytes) to ${endpoint}`);
+    return response.status === 204 ? null : await response.json();
+  } catch (err) {
+    if (signal.aborted) {
+      log.warn(`⚠️ Upload cancelled: ${filename}`);
+      throw new AppError("cancelled", "Transfer cancelled");
+    }
+    log.error(`❌ Upload failed (${filename} → ${endpoint}): ${err}`);
+    throw err;
+  } finally {
+    endTransfer(id);
+  }
+}
+
+/**
+ * Cancel a running download or upload.
+ *
+ * @returns false if `transferId` is not a running transfer
+ */
+export function cancelTransfer(transferId: string): boolean {
+  if (!activeTransfers.has(transferId)) {
+    log.warn(`⚠️ Cancel refused: ${transferId} is not a running transfer`);
+    return false;
+  }
+  return abortOperation(transferId);
+}
+
+/**
+ * Register IPC handlers for streaming transfers.
+ */
+export function registerTransferHandlers(): void {
+  handle("download-export", async (_event, jobId: string, defaultName?: string) => {
+    const name = path.basename(String(defaultName || jobId));
+    const extension = path.extname(name).slice(1);
+    const targetPath = await chooseSavePath({
+      title: "Export speichern",
+      defaultName: name,
+      filters: extension ? [{ name: extension.toUpperCase(), extensions: [extension] }] : [],
+    });
+    return targetPath ? downloadExport(jobId, targetPath) : null;
+  });
+  handle("upload-file", (_event, filePath: string, endpoint: string) =>
+    uploadFile(filePath, endpoint)
+  );
+  handle("cancel-transfer", (_event, transferId: string) => cancelTransfer(transferId));
+}
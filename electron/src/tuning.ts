@@ -0,0 +1,159 @@
+/**
+ * Billino Desktop – Backend Tuning
+ *
+ * Turns the backendTuning settings into environment variables for the
+ * backend process (BACKEND_WORKERS, SQLITE_CACHE_MB, LOG_LEVEL) and its
+ * process priority. Values are clamped to what the machine can take:
+ * - workers: at most one per spare CPU core, ~150 MB RAM each within a
+ *   quarter of the installed memory, and never more than 4
+ * - SQLite cache: at most 5 % of the installed memory, never above 512 MB
+ *
+ * Changes take effect on the next backend start.
+ */
+
+import os from "os";
+import log from "electron-log/main";
+import { handle } from "./ipc";
+import { BackendPriority, BackendTuningSettings, getSettings } from "./settings";
+
+export interface SystemResources {
+  cpuCores: number;
+  totalMemoryMb: number;
+  freeMemoryMb: number;
+}
+
+export interface TuningLimits {
+  maxWorkers: number;
+  maxSqliteCacheMb: number;
+}
+
+export interface BackendTuningInfo {
+  /** As stored in the settings. */
+  requested: BackendTuningSettings;
+  /** What the backend gets on its next start. */
+  effective: BackendTuningSettings;
+  limits: TuningLimits;
+  resources: SystemResources;
+}
+
+const WORKER_MEMORY_MB = 150;
+const WORKER_CAP = 4;
+const SQLITE_CACHE_CAP_MB = 512;
+const LOG_LEVELS = ["DEBUG", "INFO", "WARNING", "ERROR"];
+/** os.setPriority() maps these to priority classes on Windows. */
+const PRIORITY_VALUES: Record<BackendPriority, number> = {
+  normal: os.constants.priority.PRIORITY_NORMAL,
+  belowNormal: os.constants.priority.PRIORITY_BELOW_NORMAL,
+  low: os.constants.priority.PRIORITY_LOW,
+};
+
+/**
+ * CPU cores and memory of this machine.
+ */
+export function getSystemResources(): SystemResources {
+  return {
+    cpuCores: os.availableParallelism(),
+    totalMemoryMb: Math.round(os.totalmem() / 1024 / 1024),
+    freeMemoryMb: Math.round(os.freemem() / 1024 / 1024),
+  };
+}
+
+/**
+ * Upper bounds for the tuning values on a machine.
+ */
+export function getTuningLimits(resources: SystemResources = getSystemResources()): TuningLimits {
+  // Leave one core for the UI and the OS
+  const byCpu = resources.cpuCores - 1;
+  const byMemory = Math.floor((resources.totalMemoryMb * 0.25) / WORKER_MEMORY_MB);
+  return {
+    maxWorkers: Math.max(1, Math.min(WORKER_CAP, byCpu, byMemory)),
+    maxSqliteCacheMb: Math.max(
+      2,
+      Math.min(SQLITE_CACHE_CAP_MB, Math.floor(resources.totalMemoryMb * 0.05))
+    ),
+  };
+}
+
+function clamp(value: number, min: number, max: number): number {
+  return Math.min(Math.max(Math.round(value), min), max);
+}
+
+/**
+ * Settings clamped to the machine's limits.
+ */
+export function getEffectiveTuning(
+  requested: BackendTuningSettings,
+  limits: TuningLimits = getTuningLimits()
+): BackendTuningSettings {
+  const workers = Number.isFinite(requested.workers)
+    ? clamp(requested.workers, 1, limits.maxWorkers)
+    : 1;
+  const sqliteCacheMb =
+    requested.sqliteCacheMb === null || !Number.isFinite(requested.sqliteCacheMb)
+      ? null
+      : clamp(requested.sqliteCacheMb, 1, limits.maxSqliteCacheMb);
+  const logLevel =
+    requested.logLevel && LOG_LEVELS.includes(requested.logLevel) ? requested.logLevel : null;
+  const priority = requested.priority in PRIORITY_VALUES ? requested.priority : "normal";
+  return { workers, sqliteCacheMb, logLevel, priority };
+}
+
+/**
+ * Environment variables for spawning the backend.
+ */
+export function getTuningEnv(): NodeJS.ProcessEnv {
+  const requested = getSettings().backendTuning;
+  const effective = getEffectiveTuning(requested);
+
+  if (effective.workers !== requested.workers) {
+    log.warn(`⚠️ Backend workers reduced to ${effective.workers} (requested ${requested.workers})`);
+  }
+  if (effective.sqliteCacheMb !== requested.sqliteCacheMb) {
+    log.warn(
+      `⚠️ SQLite cache limited to ${effective.sqliteCacheMb} MB ` +
+        `(requested ${requested.sqliteCacheMb} MB)`
+    );
+  }
+
+  const env: NodeJS.ProcessEnv = { BACKEND_WORKERS: String(effective.workers) };
+  if (effective.sqliteCacheMb !== null) env.SQLITE_CACHE_MB = String(effective.sqliteCacheMb);
+  if (effective.logLevel !== null) env.LOG_LEVEL = effective.logLevel;
+  return env;
+}
+
+/**
+ * Set the configured priority on a freshly spawned backend. uvicorn
+ * workers are started later and inherit it.
+ */
+export function applyBackendPriority(pid: number | undefined): void {
+  const { priority } = getEffectiveTuning(getSettings().backendTuning);
+  if (pid === undefined || priority === "normal") return;
+  try {
+    os.setPriority(pid, PRIORITY_VALUES[priority]);
+    log.info(`🐢 Backend priority set to ${priority} (pid ${pid})`);
+  } catch (err) {
+    log.warn(`⚠️ Could not set backend priority to ${priority}: ${err}`);
+  }
+}
+
+/**
+ * Requested and effective tuning with the machine's limits, for the settings UI.
+ */
+export function getBackendTuningInfo(): BackendTuningInfo {
+  const resources = getSystemResources();
+  const limits = getTuningLimits(resources);
+  const requested = getSettings().backendTuning;
+  return {
+    requested,
+    effective: getEffectiveTuning(requested, limits),
+    limits,
+    resources,
+  };
+}
+
+/**
+ * Register IPC handlers for backend tuning.
+ */
+export function registerTuningHandlers(): void {
+  handle("get-backend-tuning", () => getBackendTuningInfo(), "read");
+}
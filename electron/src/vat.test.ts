@@ -0,0 +1,34 @@
+import { getVatRate } from "./vat";
+
+describe("getVatRate", () => {
+  it("liefert die abgesenkten Sätze vom 01.07. bis 31.12.2020", () => {
+    expect(getVatRate("2020-06-30").rate).toBe(0.19);
+    expect(getVatRate("2020-07-01").rate).toBe(0.16);
+    expect(getVatRate("2020-12-31").rate).toBe(0.16);
+    expect(getVatRate("2021-01-01").rate).toBe(0.19);
+
+    expect(getVatRate("2020-06-30", "reduced").rate).toBe(0.07);
+    expect(getVatRate("2020-07-01", "reduced").rate).toBe(0.05);
+    expect(getVatRate("2021-01-01", "reduced").rate).toBe(0.07);
+  });
+
+  it("ignoriert die Uhrzeit und nennt den Beginn des Satzes", () => {
+    const lookup = getVatRate("2020-11-15T23:59:59", "standard");
+
+    expect(lookup.date).toBe("2020-11-15");
+    expect(lookup.validFrom).toBe("2020-07-01");
+    expect(lookup.source).toBe("bundled");
+  });
+
+  it("fällt für Speisen in Restaurants auf den Regelsatz zurück", () => {
+    expect(getVatRate("2020-06-30", "restaurant").rate).toBe(0.19);
+    expect(getVatRate("2020-07-01", "restaurant").rate).toBe(0.05);
+    expect(getVatRate("2023-12-31", "restaurant").rate).toBe(0.07);
+    expect(getVatRate("2024-01-01", "restaurant").rate).toBe(0.19);
+  });
+
+  it("lehnt ungültige Daten ab", () => {
+    expect(() => getVatRate("01.07.2020")).toThrow();
+    expect(() => getVatRate("1960-01-01")).toThrow();
+  });
+});
@@ -0,0 +1,166 @@
+/**
+ * Billino Desktop – German VAT Rates
+ *
+ * Effective-dated table of German VAT rates (§12 UStG) so back-dated
+ * invoices are validated and summarized with the rate that applied on the
+ * invoice date – including the temporary cuts from 1 July to 31 December
+ * 2020 (16 % / 5 %) and the reduced rate for restaurant food (2020–2023).
+ *
+ * The table ships with the app and is updated with each release. A
+ * `vat-rates.json` in AppData/Roaming/Billino/ (same format as
+ * BUNDLED_VAT_RATES) replaces it, e.g. when a rate change takes effect
+ * before the next release.
+ */
+
+import { app } from "electron";
+import path from "path";
+import fs from "fs";
+import log from "electron-log/main";
+import { AppError } from "./errors";
+import { handle } from "./ipc";
+
+/**
+ * - standard: Regelsteuersatz
+ * - reduced: ermäßigter Steuersatz (food, books, ...)
+ * - restaurant: Speisen in der Gastronomie (standard rate outside the
+ *   temporary reductions)
+ */
+export type VatCategory = "standard" | "reduced" | "restaurant";
+
+export interface VatRateEntry {
+  category: VatCategory;
+  /** Rate as decimal fraction, e.g. 0.19. */
+  rate: number;
+  /** First day the rate applies (YYYY-MM-DD). */
+  validFrom: string;
+}
+
+export interface VatRateLookup {
+  date: string;
+  category: VatCategory;
+  rate: number;
+  validFrom: string;
+  /** "bundled" or "override" (vat-rates.json). */
+  source: "bundled" | "override";
+}
+
+export const BUNDLED_VAT_RATES: VatRateEntry[] = [
+  { category: "standard", rate: 0.1, validFrom: "1968-01-01" },
+  { category: "standard", rate: 0.11, validFrom: "1968-07-01" },
+  { category: "standard", rate: 0.12, validFrom: "1978-01-01" },
+  { category: "standard", rate: 0.13, validFrom: "1979-07-01" },
+  { category: "standard", rate: 0.14, validFrom: "1983-07-01" },
+  { category: "standard", rate: 0.15, validFrom: "1993-01-01" },
+  { category: "standard", rate: 0.16, validFrom: "1998-04-01" },
+  { category: "standard", rate: 0.19, validFrom: "2007-01-01" },
+  { category: "standard", rate: 0.16, validFrom: "2020-07-01" },
+  { category: "standard", rate: 0.19, validFrom: "2021-01-01" },
+
+  { category: "reduced", rate: 0.05, validFrom: "1968-01-01" },
+  { category: "reduced", rate: 0.055, validFrom: "1968-07-01" },
+  { category: "reduced", rate: 0.06, validFrom: "1978-01-01" },
+  { category: "reduced", rate: 0.065, validFrom: "1979-07-01" },
+  { category: "reduced", rate: 0.07, validFrom: "1983-07-01" },
+  { category: "reduced", rate: 0.05, validFrom: "2020-07-01" },
+  { category: "reduced", rate: 0.07, validFrom: "2021-01-01" },
+
+  // Corona-Steuerhilfegesetz, extended until end of 2023
+  { category: "restaurant", rate: 0.05, validFrom: "2020-07-01" },
+  { category: "restaurant", rate: 0.07, validFrom: "2021-01-01" },
+  { category: "restaurant", rate: 0.19, validFrom: "2024-01-01" },
+];
+
+const CATEGORIES: VatCategory[] = ["standard", "reduced", "restaurant"];
+const DATE_PATTERN = /^\d{4}-\d{2}-\d{2}$/;
+
+let table: { entries: VatRateEntry[]; source: VatRateLookup["source"] } | null = null;
+
+function getOverridePath(): string {
+  return path.join(app.getPath("userData"), "vat-rates.json");
+}
+
+function isValidEntry(entry: unknown): entry is VatRateEntry {
+  const e = entry as Partial<VatRateEntry>;
+  return (
+    typeof e === "object" &&
+    e !== null &&
+    CATEGORIES.includes(e.category as VatCategory) &&
+    typeof e.rate === "number" &&
+    e.rate >= 0 &&
+    e.rate < 1 &&
+    typeof e.validFrom === "string" &&
+    DATE_PATTERN.test(e.validFrom)
+  );
+}
+
+function loadTable(): NonNullable<typeof table> {
+  if (table) return table;
+
+  table = { entries: BUNDLED_VAT_RATES, source: "bundled" };
+  const overridePath = getOverridePath();
+  if (fs.existsSync(overridePath)) {
+    try {
+      const entries: unknown = JSON.parse(fs.readFileSync(overridePath, "utf-8"));
+      if (!Array.isArray(entries) || entries.length === 0 || !entries.every(isValidEntry)) {
+        throw new Error("expected a non-empty array of { category, rate, validFrom }");
+      }
+      table = { entries, source: "override" };
+      log.info(`🧾 VAT rates loaded from ${overridePath} (${entries.length} entries)`);
+    } catch (err) {
+      log.warn(`⚠️ Ignoring invalid VAT rate file ${overridePath}: ${err}`);
+    }
+  }
+  return table;
+}
+
+/**
+ * All rate entries currently in effect (bundled table or override).
+ */
+export function listVatRates(): VatRateEntry[] {
+  return [...loadTable().entries].sort(
+    (a, b) => a.category.localeCompare(b.category) || a.validFrom.localeCompare(b.validFrom)
+  );
+}
+
+/**
+ * VAT rate that applied on a given date.
+ *
+ * Categories without an entry for the date (e.g. restaurant food before
+ * July 2020) fall back to the standard rate.
+ *
+ * @param date Invoice date (YYYY-MM-DD, a time part is ignored)
+ * @throws Error for malformed dates or dates before the first entry
+ */
+export function getVatRate(date: string, category: VatCategory = "standard"): VatRateLookup {
+  const day = date.slice(0, 10);
+  if (!DATE_PATTERN.test(day)) {
+    throw new AppError("invalid_input", `Invalid date: ${date}`);
+  }
+  if (!CATEGORIES.includes(category)) {
+    throw new AppError("invalid_input", `Unknown VAT category: ${category}`);
+  }
+
+  const { entries, source } = loadTable();
+  const applicable = (c: VatCategory): VatRateEntry | undefined =>
+    entries
+      .filter((e) => e.category === c && e.validFrom <= day)
+      .sort((a, b) => b.validFrom.localeCompare(a.validFrom))[0];
+
+  const entry = applicable(category) ?? applicable("standard");
+  if (!entry) {
+    throw new AppError("not_found", `No VAT rate known for ${day}`);
+  }
+  return { date: day, category, rate: entry.rate, validFrom: entry.validFrom, source };
+}
+
+/**
+ * Register IPC handlers for VAT rate lookups.
+ */
+export function registerVatHandlers(): void {
+  handle("list-vat-rates", () => listVatRates(), "read");
+  handle(
+    "get-vat-rate",
+    (_event, date: string, category?: VatCategory) => getVatRate(date, category),
+    "read"
+  );
+}
@@ -0,0 +1,83 @@
+/**
+ * Billino Desktop – Window Registry
+ *
+ * Tracks every BrowserWindow together with its role so lifecycle decisions
+ * (shutdown, permissions) depend on *which* window closed:
+ * - `main`: the primary application window – closing it ends the session
+ * - `secondary`: detail/helper windows – closing them never stops the backend
+ */
+
+import { BrowserWindow, WebContents } from "electron";
+import log from "electron-log/main";
+
+export type WindowRole = "main" | "secondary";
+
+interface RegisteredWindow {
+  window: BrowserWindow;
+  role: WindowRole;
+}
+
+const registry = new Map<number, RegisteredWindow>();
+
+/**
+ * Register a window with its role.
+ *
+ * @param onClosed Called after the window is closed and removed from the
+ *                 registry, with the number of windows still open.
+ */
+export function registerWindow(
+  window: BrowserWindow,
+  role: WindowRole,
+  onClosed?: (role: WindowRole, remaining: number) => void
+): void {
+  const id = window.id;
+  registry.set(id, { window, role });
+  log.debug(`🪟 Window registered: id=${id}, role=${role}`);
+
+  window.on("closed", () => {
+    registry.delete(id);
+    log.debug(`🪟 Window closed: id=${id}, role=${role}, remaining=${registry.size}`);
+    onClosed?.(role, registry.size);
+  });
+}
+
+/**
+ * Get the main window, if it is open.
+ */
+export function getMainWindow(): BrowserWindow | null {
+  for (const entry of registry.values()) {
+    if (entry.role === "main" && !entry.window.isDestroyed()) {
+      return entry.window;
+    }
+  }
+  return null;
+}
+
+/**
+ * Resolve the role of the window owning the given webContents.
+ *
+ * Returns null for webContents that don't belong to a registered window.
+ */
+export function getWindowRole(contents: WebContents): WindowRole | null {
+  const window = BrowserWindow.fromWebContents(contents);
+  if (!window) return null;
+  return registry.get(window.id)?.role ?? null;
+}
+
+/**
+ * Close all secondary windows (e.g. when the main window goes away).
+ */
+export function closeSecondaryWindows(): void {
+  for (const entry of registry.values()) {
+    if (entry.role === "secondary" && !entry.window.isDestroyed()) {
+      entry.window.close();
+    }
+  }
+}
+
+/**
+ * Number of currently registered (open) windows.
+ */
+export function getWindowCount(): number {
+  return registry.size;
+}
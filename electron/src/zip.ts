@@ -0,0 +1,87 @@
+/**
+ * Billino Desktop – ZIP Archives
+ *
+ * Minimal ZIP writer for the few bundles the shell creates itself (crash
+ * reports): files are deflated with zlib, names are stored as UTF-8.
+ * No ZIP64, so a bundle must stay below 4 GB and 65,535 entries.
+ */
+
+import zlib from "zlib";
+
+export interface ZipEntry {
+  /** Path inside the archive, "/" separated. */
+  name: string;
+  data: Buffer | string;
+  modified?: Date;
+}
+
+/** Bit 11: file name is UTF-8. */
+const FLAG_UTF8 = 0x0800;
+const METHOD_DEFLATE = 8;
+const VERSION = 20;
+
+function dosDateTime(date: Date): { time: number; date: number } {
+  return {
+    time: (date.getHours() << 11) | (date.getMinutes() << 5) | Math.floor(date.getSeconds() / 2),
+    date: ((date.getFullYear() - 1980) << 9) | ((date.getMonth() + 1) << 5) | date.getDate(),
+  };
+}
+
+/**
+ * Build a ZIP archive in memory.
+ */
+export function createZip(entries: ZipEntry[]): Buffer {
+  const parts: Buffer[] = [];
+  const directory: Buffer[] = [];
+  let offset = 0;
+
+  for (const entry of entries) {
+    const name = Buffer.from(entry.name, "utf8");
+    const data = typeof entry.data === "string" ? Buffer.from(entry.data, "utf8") : entry.data;
+    const compressed = zlib.deflateRawSync(data);
+    const crc = zlib.crc32(data);
+    const stamp = dosDateTime(entry.modified ?? new Date());
+
+    const local = Buffer.alloc(30);
+    local.writeUInt32LE(0x04034b50, 0);
+    local.writeUInt16LE(VERSION, 4);
+    local.writeUInt16LE(FLAG_UTF8, 6);
+    local.writeUInt16LE(METHOD_DEFLATE, 8);
+    local.writeUInt16LE(stamp.time, 10);
+    local.writeUInt16LE(stamp.date, 12);
+    local.writeUInt32LE(crc, 14);
+    local.writeUInt32LE(compressed.length, 18);
+    local.writeUInt32LE(data.length, 22);
+    local.writeUInt16LE(name.length, 26);
+    local.writeUInt16LE(0, 28);
+
+    const central = Buffer.alloc(46);
+    central.writeUInt32LE(0x02014b50, 0);
+    central.writeUInt16LE(VERSION, 4);
+    central.writeUInt16LE(VERSION, 6);
+    central.writeUInt16LE(FLAG_UTF8, 8);
+    central.writeUInt16LE(METHOD_DEFLATE, 10);
+    central.writeUInt16LE(stamp.time, 12);
+    central.writeUInt16LE(stamp.date, 14);
+    central.writeUInt32LE(crc, 16);
+    central.writeUInt32LE(compressed.length, 20);
+    central.writeUInt32LE(data.length, 24);
+    central.writeUInt16LE(name.length, 28);
+    // Extra field, comment, disk number, attributes: all zero
+    central.writeUInt32LE(offset, 42);
+
+    parts.push(local, name, compressed);
+    directory.push(central, name);
+    offset += local.length + name.length + compressed.length;
+  }
+
+  const directorySize = directory.reduce((sum, part) => sum + part.length, 0);
+  const end = Buffer.alloc(22);
+  end.writeUInt32LE(0x06054b50, 0);
+  end.writeUInt16LE(entries.length, 8);
+  end.writeUInt16LE(entries.length, 10);
+  end.writeUInt32LE(directorySize, 12);
+  end.writeUInt32LE(offset, 16);
+
+  return Buffer.concat([...parts, ...directory, end]);
+}
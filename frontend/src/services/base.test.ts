@@ -66,4 +66,45 @@ describe("ApiClient", () => {
       );
     });
   });
+
+  describe("Desktop-Proxy", () => {
+    const apiRequest = jest.fn();
+
+    beforeEach(() => {
+      (window as unknown as { billino?: unknown }).billino = { apiRequest };
+    });
+
+    afterEach(() => {
+      delete (window as unknown as { billino?: unknown }).billino;
+    });
+
+    it("leitet Requests über window.billino.apiRequest statt fetch", async () => {
+      apiRequest.mockResolvedValueOnce({
+        status: 200,
+        statusText: "OK",
+        ok: true,
+        body: { id: 1 },
+      });
+
+      const result = await ApiClient.post("/customers/", { name: "Test" });
+
+      expect(apiRequest).toHaveBeenCalledWith("POST", "/customers/", { name: "Test" });
+      expect(global.fetch).not.toHaveBeenCalled();
+      expect(result).toEqual({ id: 1 });
+    });
+
+    it("wirft ApiError mit Status und Detail bei Fehlern", async () => {
+      apiRequest.mockResolvedValueOnce({
+        status: 422,
+        statusText: "Unprocessable Entity",
+        ok: false,
+        body: { detail: "Ungültig" },
+      });
+
+      await expect(ApiClient.get("/invoices/")).rejects.toMatchObject({
+        status: 422,
+        detail: { detail: "Ungültig" },
+      });
+    });
+  });
 });
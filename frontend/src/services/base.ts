@@ -2,6 +2,10 @@
  * ApiClient kapselt die gemeinsame Basis-URL und HTTP-GET Logik.
  * SRP: Nur Transport / Fetch. Keine Domain-Logik.
  * Open/Closed: Erweiterbar um weitere Methoden (POST etc.) ohne bestehende zu ändern.
+ *
+ * In der Desktop-App laufen alle Requests über den Proxy der Shell
+ * (`window.billino.apiRequest`): sie kennt den aktuellen Backend-Port,
+ * das Frontend braucht keine Backend-URL.
  */
 
 import { logger } from "@/lib/logger";
@@ -30,12 +34,35 @@ export class ApiError extends Error {
   }
 }
 
+/** Antwort des Desktop-Proxys (wie fetch, aber bereits geparst). */
+interface DesktopApiResponse {
+  status: number;
+  statusText: string;
+  ok: boolean;
+  body: unknown;
+}
+
+type DesktopApiRequest = (
+  method: string,
+  path: string,
+  body?: unknown
+) => Promise<DesktopApiResponse>;
+
+function desktopApiRequest(): DesktopApiRequest | undefined {
+  if (typeof window === "undefined") return undefined;
+  return (window as unknown as { billino?: { apiRequest?: DesktopApiRequest } }).billino
+    ?.apiRequest;
+}
+
 export class ApiClient {
   static baseUrl(): string {
     return process.env.NEXT_PUBLIC_API_URL || process.env.API_URL || "http://localhost:8000";
   }
 
   static async get<T>(path: string, init?: RequestInit): Promise<T> {
+    const viaDesktop = desktopApiRequest();
+    if (viaDesktop) return this.requestViaDesktop<T>(viaDesktop, "GET", path);
+
     const url = `${this.baseUrl()}${path}`;
     log.debug(`📤 REQUEST: GET ${path}`);
 
@@ -65,6 +92,9 @@ export class ApiClient {
   }
 
   static async post<T>(path: string, body: unknown, init?: RequestInit): Promise<T> {
+    const viaDesktop = desktopApiRequest();
+    if (viaDesktop) return this.requestViaDesktop<T>(viaDesktop, "POST", path, body);
+
     const url = `${this.baseUrl()}${path}`;
     const bodySize = JSON.stringify(body).length;
     log.debug(`📤 REQUEST: POST ${path}`, { bodySize: `${bodySize} bytes` });
@@ -103,6 +133,9 @@ export class ApiClient {
   }
 
   static async put<T>(path: string, body: unknown, init?: RequestInit): Promise<T> {
+    const viaDesktop = desktopApiRequest();
+    if (viaDesktop) return this.requestViaDesktop<T>(viaDesktop, "PUT", path, body);
+
     const url = `${this.baseUrl()}${path}`;
     const bodySize = JSON.stringify(body).length;
     log.debug(`📤 REQUEST: PUT ${path}`, { bodySize: `${bodySize} bytes` });
@@ -140,6 +173,35 @@ export class ApiClient {
     }
   }
 
+  /**
+   * Request über den Proxy der Desktop-Shell, Fehler wie bei fetch
+   */
+  private static async requestViaDesktop<T>(
+    apiRequest: DesktopApiRequest,
+    method: string,
+    path: string,
+    body?: unknown
+  ): Promise<T> {
+    log.debug(`📤 REQUEST: ${method} ${path} (desktop)`);
+    const res = await apiRequest(method, path, body);
+
+    if (!res.ok) {
+      const errorDetail: ApiErrorDetail =
+        res.body && typeof res.body === "object"
+          ? (res.body as ApiErrorDetail)
+          : { detail: `${res.status} ${res.statusText}` };
+      log.error(`📥 RESPONSE: ${method} ${path} [${res.status}]`, {
+        status: res.status,
+        statusText: res.statusText,
+        detail: errorDetail,
+      });
+      throw new ApiError(res.status, res.statusText, errorDetail, JSON.stringify(errorDetail));
+    }
+
+    log.debug(`📥 RESPONSE: ${method} ${path} [${res.status}] ✅`);
+    return res.body as T;
+  }
+
   /**
    * Parse error response and extract validation details
    */